@@ -0,0 +1,428 @@
+use std::fmt;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use commitment_sql::{
+    process_insert,
+    row_number_column_def,
+    row_numbers_of_on_chain_table,
+    SnapshotCommitmentVerifier,
+};
+use log::info;
+use on_chain_table::OnChainTable;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use proof_of_sql_commitment_map::generic_over_commitment::{
+    AssociatedPublicSetupType,
+    OptionType,
+    TableCommitmentType,
+};
+use proof_of_sql_commitment_map::{
+    CommitmentScheme,
+    PerCommitmentScheme,
+    TableCommitmentBytes,
+    TableCommitmentBytesPerCommitmentScheme,
+};
+use proof_of_sql_static_setups::io::{
+    initialize_from_config,
+    ProofOfSqlPublicSetupArgs,
+    PUBLIC_SETUPS,
+};
+use subxt::utils::H256;
+use sxt_core::sxt_chain_runtime::api;
+use sxt_core::sxt_chain_runtime::api::runtime_types::bounded_collections::bounded_vec::BoundedVec;
+use sxt_core::sxt_chain_runtime::api::runtime_types::proof_of_sql_commitment_map::commitment_scheme::CommitmentScheme as RuntimeCommitmentScheme;
+use sxt_core::sxt_chain_runtime::api::runtime_types::sxt_core::tables::TableIdentifier;
+
+use crate::common::ChainReader;
+use crate::table_inserts::{block_inserts, finalized_range, TableInsert};
+
+type TableCommitments = PerCommitmentScheme<OptionType<TableCommitmentType>>;
+type Setups = PerCommitmentScheme<AssociatedPublicSetupType<'static>>;
+
+/// Column of snapshot files that isn't part of the table
+const PRIMARY_KEY_BINARY_COLUMN_NAME: &str = "SXT_PRIMARY_KEY_BINARY";
+
+/// The first point where replaying a table's inserts disagrees with the chain
+#[derive(Debug)]
+enum Divergence {
+    /// The snapshot rows don't produce the commitments the table was created with
+    Snapshot { block_number: u32, reason: String },
+    /// An insert could not be replayed
+    Replay {
+        block_number: u32,
+        event_index: u32,
+        reason: String,
+    },
+    /// The row numbers finalized with an insert differ from the replayed row numbers
+    RowNumbers {
+        block_number: u32,
+        event_index: u32,
+        replayed_first: Option<i64>,
+        finalized_first: Option<i64>,
+    },
+    /// The replayed commitments differ from the commitments stored after a block
+    Commitments {
+        block_number: u32,
+        event_indices: Vec<u32>,
+        schemes: Vec<CommitmentScheme>,
+    },
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Divergence::Snapshot {
+                block_number,
+                reason,
+            } => write!(
+                f,
+                "the snapshot rows don't produce the commitments stored after block \
+                 {block_number}: {reason}"
+            ),
+            Divergence::Replay {
+                block_number,
+                event_index,
+                reason,
+            } => write!(
+                f,
+                "failed to replay the insert of block {block_number} event {event_index}: {reason}"
+            ),
+            Divergence::RowNumbers {
+                block_number,
+                event_index,
+                replayed_first,
+                finalized_first,
+            } => write!(
+                f,
+                "the insert of block {block_number} event {event_index} was finalized with row \
+                 numbers starting at {finalized_first:?}, replayed rows start at {replayed_first:?}"
+            ),
+            Divergence::Commitments {
+                block_number,
+                event_indices,
+                schemes,
+            } => write!(
+                f,
+                "{schemes:?} commitments diverge after block {block_number}, whose inserts are \
+                 events {event_indices:?}; the first divergent batch is among them"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Divergence {}
+
+/// A block with inserts into the audited table, as replayed
+struct ReplayedBlock {
+    block_number: u32,
+    block_hash: H256,
+    /// The indices of the block's `QuorumReached` events for the table
+    event_indices: Vec<u32>,
+    /// The replayed commitments after the block
+    commitments: TableCommitmentBytesPerCommitmentScheme,
+}
+
+/// Parse a table given as `NAMESPACE.NAME`
+fn parse_table(table: &str) -> Result<TableIdentifier> {
+    let (namespace, name) = table
+        .split_once('.')
+        .ok_or_else(|| anyhow!("expected a table as NAMESPACE.NAME, got {table}"))?;
+    Ok(TableIdentifier {
+        name: BoundedVec(name.to_uppercase().into_bytes()),
+        namespace: BoundedVec(namespace.to_uppercase().into_bytes()),
+    })
+}
+
+/// Fetch the commitment of a table stored after the given block, for one scheme
+async fn stored_commitment(
+    chain: &ChainReader,
+    table: &TableIdentifier,
+    block_hash: H256,
+    scheme: RuntimeCommitmentScheme,
+) -> Result<Option<TableCommitmentBytes>> {
+    let address = api::storage()
+        .commitments()
+        .commitment_storage_map(table, scheme);
+    let Some(bytes) = chain
+        .client
+        .storage()
+        .at(block_hash)
+        .fetch(&address)
+        .await?
+    else {
+        return Ok(None);
+    };
+    let data = bytes
+        .data
+        .0
+        .try_into()
+        .map_err(|_| anyhow!("stored commitment exceeds the maximum length"))?;
+    Ok(Some(TableCommitmentBytes { data }))
+}
+
+/// Fetch the commitments of a table stored after the given block
+async fn stored_commitments(
+    chain: &ChainReader,
+    table: &TableIdentifier,
+    block_hash: H256,
+) -> Result<TableCommitmentBytesPerCommitmentScheme> {
+    Ok(PerCommitmentScheme {
+        hyper_kzg: stored_commitment(chain, table, block_hash, RuntimeCommitmentScheme::HyperKzg)
+            .await?,
+        dynamic_dory: stored_commitment(
+            chain,
+            table,
+            block_hash,
+            RuntimeCommitmentScheme::DynamicDory,
+        )
+        .await?,
+    })
+}
+
+/// Serialize table commitments the way they are stored
+fn to_bytes(commitments: &TableCommitments) -> Result<TableCommitmentBytesPerCommitmentScheme> {
+    Ok(commitments.clone().try_into()?)
+}
+
+/// The Parquet files of a snapshot, given as a file or a directory searched recursively
+fn snapshot_files(path: &Path) -> Result<Vec<PathBuf>> {
+    if path.is_file() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    let mut files = Vec::new();
+    for entry in fs::read_dir(path).with_context(|| format!("{}", path.display()))? {
+        let path = entry?.path();
+        if path.is_dir() {
+            files.extend(snapshot_files(&path)?);
+        } else if path
+            .extension()
+            .is_some_and(|extension| extension == "parquet")
+        {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Replay the rows of a snapshot, checking that they produce the commitments stored after the
+/// block the table was created in. Returns the number of rows replayed.
+fn replay_snapshot(
+    snapshot: &Path,
+    commitments: &TableCommitments,
+    setups: Setups,
+    block_number: u32,
+) -> Result<usize> {
+    let snapshot_error = |reason: String| Divergence::Snapshot {
+        block_number,
+        reason,
+    };
+
+    let mut verifier = SnapshotCommitmentVerifier::new(commitments.clone(), setups)?;
+    let mut rows = 0;
+    for file in snapshot_files(snapshot)? {
+        let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(&file)?)
+            .and_then(|builder| builder.build())
+            .with_context(|| format!("{}", file.display()))?;
+        for batch in reader {
+            let mut batch = batch.with_context(|| format!("{}", file.display()))?;
+            if let Some(index) = batch.schema().fields().iter().position(|field| {
+                field
+                    .name()
+                    .eq_ignore_ascii_case(PRIMARY_KEY_BINARY_COLUMN_NAME)
+            }) {
+                batch.remove_column(index);
+            }
+            rows += batch.num_rows();
+            let batch = OnChainTable::try_from(batch)
+                .map_err(|e| snapshot_error(format!("{}: {e}", file.display())))?;
+            verifier
+                .append(batch)
+                .map_err(|e| snapshot_error(format!("{}: {e}", file.display())))?;
+        }
+    }
+    verifier
+        .finish()
+        .map_err(|e| snapshot_error(e.to_string()))?;
+    Ok(rows)
+}
+
+/// Replay an insert through `process_insert`, returning the commitments after it
+fn replay_insert(
+    insert: &TableInsert,
+    commitments: TableCommitments,
+    setups: Setups,
+) -> Result<TableCommitments, Divergence> {
+    let replay_error = |reason: String| Divergence::Replay {
+        block_number: insert.block_number,
+        event_index: insert.event_index,
+        reason,
+    };
+
+    // Finalized payloads carry the meta columns, which are added again by the replay
+    let payload: OnChainTable =
+        postcard::from_bytes(&insert.data).map_err(|e| replay_error(e.to_string()))?;
+    let finalized_row_numbers = row_numbers_of_on_chain_table(&payload).map(<[i64]>::to_vec);
+    let row_number_column = row_number_column_def().name;
+    let insert_data = OnChainTable::try_from_iter(
+        payload
+            .into_iter()
+            .filter(|(identifier, _)| *identifier != row_number_column),
+    )
+    .map_err(|e| replay_error(e.to_string()))?;
+
+    // Inserts are checked for duplicate primary keys before they are finalized, and the
+    // check doesn't change the commitments
+    let table_identifier = sxt_core::tables::table_identifier("", "");
+    let (metadata, commitments) =
+        process_insert(&table_identifier, insert_data, &[], commitments, setups)
+            .map_err(|e| replay_error(e.to_string()))?;
+
+    let replayed_row_numbers = row_numbers_of_on_chain_table(&metadata.insert_with_meta_columns)
+        .expect("process_insert adds the row number column");
+    if finalized_row_numbers.as_deref() != Some(replayed_row_numbers) {
+        return Err(Divergence::RowNumbers {
+            block_number: insert.block_number,
+            event_index: insert.event_index,
+            replayed_first: replayed_row_numbers.first().copied(),
+            finalized_first: finalized_row_numbers.and_then(|rows| rows.first().copied()),
+        });
+    }
+
+    Ok(commitments)
+}
+
+/// Returns the schemes whose replayed commitments differ from the stored commitments
+async fn diverging_schemes(
+    chain: &ChainReader,
+    table: &TableIdentifier,
+    block: &ReplayedBlock,
+) -> Result<Vec<CommitmentScheme>> {
+    let stored = stored_commitments(chain, table, block.block_hash).await?;
+    let mut schemes = Vec::new();
+    if stored.hyper_kzg != block.commitments.hyper_kzg {
+        schemes.push(CommitmentScheme::HyperKzg);
+    }
+    if stored.dynamic_dory != block.commitments.dynamic_dory {
+        schemes.push(CommitmentScheme::DynamicDory);
+    }
+    Ok(schemes)
+}
+
+/// Compare the commitments after the last block since the previous checkpoint. If they
+/// diverge, compare them after every block since to find the first divergent one.
+async fn audit_checkpoint(
+    chain: &ChainReader,
+    table: &TableIdentifier,
+    blocks: &[ReplayedBlock],
+) -> Result<()> {
+    let Some(checkpoint) = blocks.last() else {
+        return Ok(());
+    };
+    if diverging_schemes(chain, table, checkpoint)
+        .await?
+        .is_empty()
+    {
+        info!("Commitments match after block {}", checkpoint.block_number);
+        return Ok(());
+    }
+
+    for block in blocks {
+        let schemes = diverging_schemes(chain, table, block).await?;
+        if !schemes.is_empty() {
+            return Err(Divergence::Commitments {
+                block_number: block.block_number,
+                event_indices: block.event_indices.clone(),
+                schemes,
+            }
+            .into());
+        }
+    }
+    unreachable!("the checkpoint block diverges")
+}
+
+/// Handles the `audit-commitments` command.
+///
+/// Starting from the commitments stored after block `from`, which should be the block the
+/// table was created in, from scratch or from a snapshot, every insert finalized up to block
+/// `to` is replayed through `commitment_sql::process_insert`. The replayed commitments are
+/// compared with the stored commitments every `checkpoint_interval` blocks, and the first
+/// divergent batch is reported as an error.
+///
+/// The commitments stored after block `from` are trusted, unless the rows of the snapshot the
+/// table was created from are given as `snapshot`. They are then replayed first, and must
+/// produce the commitments stored after block `from`.
+pub(crate) async fn audit_commitments(
+    rpc: &url::Url,
+    table: &str,
+    from: u32,
+    to: Option<u32>,
+    checkpoint_interval: u32,
+    snapshot: Option<&Path>,
+    setup_args: &ProofOfSqlPublicSetupArgs,
+) -> Result<()> {
+    initialize_from_config(setup_args).await?;
+    let setups = *PUBLIC_SETUPS.get().expect("public setups are initialized");
+
+    let table_name = table.to_uppercase();
+    let table = parse_table(&table_name)?;
+    let chain = ChainReader::connect(rpc).await?;
+    let blocks = finalized_range(&chain, from, to).await?;
+
+    let initial = stored_commitments(&chain, &table, chain.block_hash(from).await?).await?;
+    if initial.hyper_kzg.is_none() && initial.dynamic_dory.is_none() {
+        return Err(anyhow!(
+            "{table_name} has no commitments after block {from}, expected its creation block"
+        ));
+    }
+    let mut commitments = TableCommitments::try_from(initial)?;
+    if let Some(snapshot) = snapshot {
+        let rows = replay_snapshot(snapshot, &commitments, setups, from)?;
+        info!(
+            "Snapshot of {} matches the commitments stored after block {}: {} rows replayed",
+            table_name, from, rows
+        );
+    }
+
+    let mut since_checkpoint = Vec::new();
+    let mut last_checkpoint = from;
+    let mut inserts_replayed = 0;
+    let mut checkpoints = 0;
+    for block_number in (from + 1)..=*blocks.end() {
+        let block_hash = chain.block_hash(block_number).await?;
+        let inserts = block_inserts(&chain, block_number, block_hash, &table_name).await?;
+        if inserts.is_empty() {
+            continue;
+        }
+
+        for insert in &inserts {
+            commitments = replay_insert(insert, commitments, setups)?;
+        }
+        inserts_replayed += inserts.len();
+        since_checkpoint.push(ReplayedBlock {
+            block_number,
+            block_hash,
+            event_indices: inserts.iter().map(|insert| insert.event_index).collect(),
+            commitments: to_bytes(&commitments)?,
+        });
+
+        if block_number - last_checkpoint >= checkpoint_interval {
+            audit_checkpoint(&chain, &table, &since_checkpoint).await?;
+            since_checkpoint.clear();
+            last_checkpoint = block_number;
+            checkpoints += 1;
+        }
+    }
+    if !since_checkpoint.is_empty() {
+        audit_checkpoint(&chain, &table, &since_checkpoint).await?;
+        checkpoints += 1;
+    }
+
+    println!(
+        "Commitments of {table_name} match from block {from} to {}: {inserts_replayed} inserts \
+         replayed, {checkpoints} checkpoints compared",
+        blocks.end()
+    );
+    Ok(())
+}
@@ -1,22 +1,25 @@
 use std::sync::Arc;
 use std::time::Duration;
 
-use anyhow::Error;
+use anyhow::{anyhow, Error};
 use log::info;
-use subxt::backend::rpc::reconnecting_rpc_client::RpcClient;
-use subxt::utils::AccountId32;
+use subxt::backend::legacy::rpc_methods::BlockNumber;
+use subxt::backend::legacy::LegacyRpcMethods;
+use subxt::backend::rpc::reconnecting_rpc_client::RpcClient as ReconnectingRpcClient;
+use subxt::backend::rpc::RpcClient;
+use subxt::utils::{AccountId32, H256};
 use subxt::{OnlineClient, PolkadotConfig};
+use sxt_core::sql::identifier_to_sql;
 use sxt_core::sxt_chain_runtime;
+use sxt_core::sxt_chain_runtime::api::runtime_types::sxt_core::tables::TableIdentifier;
 use tokio::sync::Mutex;
 use url::Url;
 
-/// Create the subxt client
-pub(crate) async fn create_subxt_client(
-    rpc_url: &Url,
-) -> Result<Arc<Mutex<OnlineClient<PolkadotConfig>>>, Error> {
+/// Create the RPC client shared by the subxt client and the legacy RPC methods
+async fn create_rpc_client(rpc_url: &Url) -> Result<RpcClient, Error> {
     info!("Connecting to Substrate node at: {}", rpc_url);
 
-    let ws_client = RpcClient::builder()
+    let ws_client = ReconnectingRpcClient::builder()
         .max_request_size(50 * 1024 * 1024)
         .max_response_size(50 * 1024 * 1024)
         .request_timeout(Duration::from_secs(60))
@@ -25,11 +28,65 @@ pub(crate) async fn create_subxt_client(
         .await?;
 
     info!("Substrate client connected");
+    Ok(RpcClient::new(ws_client))
+}
+
+/// Create the subxt client
+pub(crate) async fn create_subxt_client(
+    rpc_url: &Url,
+) -> Result<Arc<Mutex<OnlineClient<PolkadotConfig>>>, Error> {
+    let rpc_client = create_rpc_client(rpc_url).await?;
     Ok(Arc::new(Mutex::new(
-        OnlineClient::<PolkadotConfig>::from_rpc_client(ws_client).await?,
+        OnlineClient::<PolkadotConfig>::from_rpc_client(rpc_client).await?,
     )))
 }
 
+/// A connection to a node, for the commands that read blocks by number
+pub(crate) struct ChainReader {
+    /// The subxt client
+    pub(crate) client: OnlineClient<PolkadotConfig>,
+    /// The RPC methods looking up blocks by number
+    pub(crate) rpc: LegacyRpcMethods<PolkadotConfig>,
+    /// The RPC client, for the node specific RPC methods
+    pub(crate) rpc_client: RpcClient,
+}
+
+impl ChainReader {
+    /// Connect to the node at the given RPC endpoint
+    pub(crate) async fn connect(rpc_url: &Url) -> Result<Self, Error> {
+        let rpc_client = create_rpc_client(rpc_url).await?;
+        Ok(ChainReader {
+            client: OnlineClient::<PolkadotConfig>::from_rpc_client(rpc_client.clone()).await?,
+            rpc: LegacyRpcMethods::new(rpc_client.clone()),
+            rpc_client,
+        })
+    }
+
+    /// Get the hash of the block with the given number
+    pub(crate) async fn block_hash(&self, number: u32) -> Result<H256, Error> {
+        self.rpc
+            .chain_get_block_hash(Some(BlockNumber::from(number)))
+            .await?
+            .ok_or_else(|| anyhow!("block {number} not found"))
+    }
+
+    /// Get the number of the last finalized block
+    pub(crate) async fn finalized_block_number(&self) -> Result<u32, Error> {
+        let hash = self.rpc.chain_get_finalized_head().await?;
+        let header = self
+            .rpc
+            .chain_get_header(Some(hash))
+            .await?
+            .ok_or_else(|| anyhow!("finalized block {hash:?} not found"))?;
+        Ok(header.number)
+    }
+}
+
+/// Takes a TableIdentifier object from the chain and returns it as `NAMESPACE.NAME`
+pub(crate) fn table_to_str(table: &TableIdentifier) -> Result<String, Error> {
+    identifier_to_sql(table.namespace.0.clone(), table.name.0.clone())
+}
+
 /// Get the currently expected nonce for our account according to the chain.
 /// We use this as our starting point
 pub(crate) async fn get_starting_nonce(
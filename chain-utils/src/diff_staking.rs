@@ -0,0 +1,345 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context, Result};
+use arrow::array::{Array, StringArray};
+use arrow::csv::ReaderBuilder;
+use arrow::datatypes::{DataType, Field, Schema};
+use log::info;
+use serde::de::IgnoredAny;
+use serde::Deserialize;
+use subxt::backend::rpc::rpc_params;
+use subxt::utils::{AccountId32, H256};
+
+use crate::common::ChainReader;
+
+/// The number of stakers requested per page of the staking mirror RPC
+const PAGE_SIZE: u32 = 1024;
+
+/// The file format of a contract state export
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default)]
+pub(crate) enum ContractStateFormat {
+    /// CSV with an `address,staked,unstaking,nominations` header row. Nominations are
+    /// separated by spaces.
+    #[default]
+    Csv,
+    /// A JSON array of objects with `address`, `staked`, `unstaking` and `nominations` keys
+    Json,
+}
+
+/// A staker as exported from the EVM staking contract
+#[derive(Deserialize)]
+struct ContractStaker {
+    /// The ethereum address of the staker, hex encoded
+    address: String,
+    /// The amount staked, including the amount unstaking, as a decimal string
+    staked: String,
+    /// Whether the staker initiated unstaking
+    unstaking: bool,
+    /// The nominated validators, as hex encoded 32 byte accounts
+    #[serde(default)]
+    nominations: Vec<String>,
+}
+
+/// A mirrored staker, as returned by `stakingMirror_v1_mirroredStakers`
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MirroredStaker {
+    address: String,
+    bonded: String,
+    unbonding: Vec<IgnoredAny>,
+    nominations: Vec<AccountId32>,
+}
+
+/// A page of mirrored stakers, as returned by `stakingMirror_v1_mirroredStakers`
+#[derive(Deserialize)]
+struct MirroredStakersPage {
+    stakers: Vec<MirroredStaker>,
+    next: Option<String>,
+}
+
+/// The staking state of one staker, on either side of the mirror
+#[derive(Default, PartialEq, Eq)]
+struct StakerState {
+    staked: u128,
+    unstaking: bool,
+    nominations: BTreeSet<[u8; 32]>,
+}
+
+/// A difference between the contract and the chain for one staker
+enum Difference {
+    /// The staked amounts differ. On chain, this is the bonded amount.
+    Staked { contract: u128, chain: u128 },
+    /// Unstaking was initiated on one side only
+    Unstaking { contract: bool, chain: bool },
+    /// The nominations differ
+    Nominations {
+        missing_on_chain: Vec<[u8; 32]>,
+        unexpected_on_chain: Vec<[u8; 32]>,
+    },
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let accounts = |accounts: &[[u8; 32]]| {
+            accounts
+                .iter()
+                .map(|account| format!("0x{}", hex::encode(account)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        match self {
+            Difference::Staked { contract, chain } => {
+                write!(
+                    f,
+                    "staked {contract} in the contract, bonded {chain} on chain"
+                )
+            }
+            Difference::Unstaking { contract, chain } => write!(
+                f,
+                "unstaking {contract} in the contract, unbonding {chain} on chain"
+            ),
+            Difference::Nominations {
+                missing_on_chain,
+                unexpected_on_chain,
+            } => write!(
+                f,
+                "nominations missing on chain: [{}], only on chain: [{}]",
+                accounts(missing_on_chain),
+                accounts(unexpected_on_chain)
+            ),
+        }
+    }
+}
+
+/// Decode hex of a fixed length, with or without a `0x` prefix
+fn decode_fixed_hex<const N: usize>(hex_encoded: &str) -> Result<[u8; N]> {
+    let hex_encoded = hex_encoded.trim();
+    let bytes = hex::decode(hex_encoded.strip_prefix("0x").unwrap_or(hex_encoded))?;
+    bytes
+        .try_into()
+        .map_err(|_| anyhow!("expected {N} bytes, got {hex_encoded}"))
+}
+
+/// Parse a decimal amount
+fn parse_amount(amount: &str) -> Result<u128> {
+    amount
+        .trim()
+        .parse()
+        .with_context(|| format!("invalid amount {amount}"))
+}
+
+impl ContractStaker {
+    /// Parse the address and state of the staker
+    fn parse(self) -> Result<([u8; 20], StakerState)> {
+        let address = decode_fixed_hex(&self.address)?;
+        let nominations = self
+            .nominations
+            .iter()
+            .map(|nomination| decode_fixed_hex(nomination))
+            .collect::<Result<_>>()?;
+        Ok((
+            address,
+            StakerState {
+                staked: parse_amount(&self.staked)?,
+                unstaking: self.unstaking,
+                nominations,
+            },
+        ))
+    }
+}
+
+/// The value of a row of a string column, if not null
+fn string_value(array: &StringArray, row: usize) -> Option<&str> {
+    (!array.is_null(row)).then(|| array.value(row))
+}
+
+/// Read the stakers of a CSV contract state export
+fn read_csv_stakers(file: File) -> Result<Vec<ContractStaker>> {
+    let schema = Arc::new(Schema::new(
+        ["address", "staked", "unstaking", "nominations"]
+            .map(|name| Field::new(name, DataType::Utf8, true))
+            .to_vec(),
+    ));
+    let reader = ReaderBuilder::new(schema).with_header(true).build(file)?;
+
+    let mut stakers = Vec::new();
+    for batch in reader {
+        let batch = batch?;
+        let column = |index: usize| {
+            batch
+                .column(index)
+                .as_any()
+                .downcast_ref::<StringArray>()
+                .expect("the columns are read as strings")
+        };
+        let (address, staked, unstaking, nominations) =
+            (column(0), column(1), column(2), column(3));
+        for row in 0..batch.num_rows() {
+            stakers.push(ContractStaker {
+                address: string_value(address, row)
+                    .ok_or_else(|| anyhow!("row {row} has no address"))?
+                    .to_string(),
+                staked: string_value(staked, row).unwrap_or("0").to_string(),
+                unstaking: string_value(unstaking, row)
+                    .map(|unstaking| unstaking.trim().parse::<bool>())
+                    .transpose()
+                    .with_context(|| format!("row {row} has an invalid unstaking flag"))?
+                    .unwrap_or_default(),
+                nominations: string_value(nominations, row)
+                    .unwrap_or_default()
+                    .split_whitespace()
+                    .map(str::to_string)
+                    .collect(),
+            });
+        }
+    }
+    Ok(stakers)
+}
+
+/// Read a contract state export, keyed by staker address
+fn read_contract_state(
+    path: &Path,
+    format: ContractStateFormat,
+) -> Result<BTreeMap<[u8; 20], StakerState>> {
+    let file = File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+    let stakers = match format {
+        ContractStateFormat::Csv => read_csv_stakers(file)?,
+        ContractStateFormat::Json => serde_json::from_reader(file)?,
+    };
+
+    let mut state = BTreeMap::new();
+    for staker in stakers {
+        let (address, staker) = staker.parse()?;
+        if state.insert(address, staker).is_some() {
+            return Err(anyhow!(
+                "0x{} appears more than once in the contract state",
+                hex::encode(address)
+            ));
+        }
+    }
+    Ok(state)
+}
+
+/// Fetch every mirrored staker at the given block, keyed by staker address
+async fn read_chain_state(
+    chain: &ChainReader,
+    at: H256,
+) -> Result<BTreeMap<[u8; 20], StakerState>> {
+    let mut state = BTreeMap::new();
+    let mut start_after: Option<String> = None;
+    loop {
+        let page: MirroredStakersPage = chain
+            .rpc_client
+            .request(
+                "stakingMirror_v1_mirroredStakers",
+                rpc_params![start_after, PAGE_SIZE, at],
+            )
+            .await?;
+
+        for staker in page.stakers {
+            let bonded = parse_amount(&staker.bonded)?;
+            state.insert(
+                decode_fixed_hex(&staker.address)?,
+                StakerState {
+                    staked: bonded,
+                    unstaking: !staker.unbonding.is_empty(),
+                    nominations: staker
+                        .nominations
+                        .into_iter()
+                        .map(|account| account.0)
+                        .collect(),
+                },
+            );
+        }
+        info!("Fetched {} mirrored stakers", state.len());
+
+        match page.next {
+            Some(next) => start_after = Some(next),
+            None => return Ok(state),
+        }
+    }
+}
+
+/// Compare the state of one staker, an absent staker counting as unstaked
+fn differences(contract: &StakerState, chain: &StakerState) -> Vec<Difference> {
+    let mut differences = Vec::new();
+    if contract.staked != chain.staked {
+        differences.push(Difference::Staked {
+            contract: contract.staked,
+            chain: chain.staked,
+        });
+    }
+    if contract.unstaking != chain.unstaking {
+        differences.push(Difference::Unstaking {
+            contract: contract.unstaking,
+            chain: chain.unstaking,
+        });
+    }
+    if contract.nominations != chain.nominations {
+        differences.push(Difference::Nominations {
+            missing_on_chain: contract
+                .nominations
+                .difference(&chain.nominations)
+                .copied()
+                .collect(),
+            unexpected_on_chain: chain
+                .nominations
+                .difference(&contract.nominations)
+                .copied()
+                .collect(),
+        });
+    }
+    differences
+}
+
+/// Handles the `diff-staking` command.
+///
+/// Compares a CSV or JSON export of the EVM staking contract with the staking state mirrored
+/// on chain at the last finalized block, and prints the differences of every account. Returns
+/// an error if any account differs.
+pub(crate) async fn diff_staking(
+    rpc: &url::Url,
+    contract_state: &Path,
+    format: ContractStateFormat,
+) -> Result<()> {
+    let contract = read_contract_state(contract_state, format)?;
+    let chain = ChainReader::connect(rpc).await?;
+    let at = chain.rpc.chain_get_finalized_head().await?;
+    let mirrored = read_chain_state(&chain, at).await?;
+
+    let addresses = contract
+        .keys()
+        .chain(mirrored.keys())
+        .collect::<BTreeSet<_>>();
+    let unstaked = StakerState::default();
+    let mut differing = 0;
+    for address in &addresses {
+        let differences = differences(
+            contract.get(*address).unwrap_or(&unstaked),
+            mirrored.get(*address).unwrap_or(&unstaked),
+        );
+        if differences.is_empty() {
+            continue;
+        }
+        differing += 1;
+        for difference in differences {
+            println!("0x{}: {difference}", hex::encode(address));
+        }
+    }
+
+    if differing > 0 {
+        return Err(anyhow!(
+            "{differing} of {} accounts differ at block {at:?}",
+            addresses.len()
+        ));
+    }
+    println!(
+        "The staking state of {} accounts matches at block {at:?}",
+        addresses.len()
+    );
+    Ok(())
+}
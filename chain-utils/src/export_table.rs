@@ -0,0 +1,78 @@
+use std::fs::File;
+use std::path::Path;
+
+use anyhow::{anyhow, Context, Result};
+use log::info;
+use parquet::arrow::ArrowWriter;
+
+use crate::common::ChainReader;
+use crate::print_batch::{decode_payload, write_batches, OutputFormat};
+use crate::table_inserts::{finalized_range, table_inserts};
+
+/// The file format of an exported table
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default)]
+pub(crate) enum ExportFormat {
+    /// Parquet, keeping the column types of the table
+    #[default]
+    Parquet,
+    /// CSV with a header row
+    Csv,
+}
+
+/// Handles the `export-table` command, writing every insert into `table` finalized from
+/// block `from` to block `to` to `output`, in the order they were finalized.
+///
+/// The rows keep the meta columns added by the chain, such as `META_ROW_NUMBER`.
+pub(crate) async fn export_table(
+    rpc: &url::Url,
+    table: &str,
+    from: u32,
+    to: Option<u32>,
+    format: ExportFormat,
+    output: &Path,
+) -> Result<()> {
+    let table = table.to_uppercase();
+    let chain = ChainReader::connect(rpc).await?;
+    let blocks = finalized_range(&chain, from, to).await?;
+
+    let inserts = table_inserts(&chain, &table, blocks.clone()).await?;
+    let batches = inserts
+        .iter()
+        .map(|insert| {
+            decode_payload(&insert.data).with_context(|| {
+                format!(
+                    "insert of block {} event {}",
+                    insert.block_number, insert.event_index
+                )
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let Some(schema) = batches.first().map(|batch| batch.schema()) else {
+        return Err(anyhow!(
+            "no insert into {table} was finalized in blocks {blocks:?}"
+        ));
+    };
+
+    let file = File::create(output)?;
+    match format {
+        ExportFormat::Parquet => {
+            let mut writer = ArrowWriter::try_new(file, schema, None)?;
+            for batch in &batches {
+                writer.write(batch)?;
+            }
+            writer.close()?;
+        }
+        ExportFormat::Csv => write_batches(&batches, OutputFormat::Csv, file)?,
+    }
+
+    let rows: usize = batches.iter().map(|batch| batch.num_rows()).sum();
+    info!(
+        "Exported {} rows of {} inserts into {} to {}",
+        rows,
+        inserts.len(),
+        table,
+        output.display()
+    );
+    Ok(())
+}
@@ -1,10 +1,14 @@
 //! This utility is built to read a DDL file from a given path and submit it to the SxT Chain
 //! using a given private key.
 
+mod audit_commitments;
 mod common;
+mod diff_staking;
+mod export_table;
 mod fetch_submissions;
 mod load_tables;
 mod print_batch;
+mod table_inserts;
 mod test_staking;
 
 use std::io::Write;
@@ -61,10 +65,104 @@ enum Commands {
         rpc: url::Url,
     },
 
-    /// Stub for future utility to print batch
+    /// Print the rows of a QuorumReached or SystemTableUpdate payload
+    #[command(group(clap::ArgGroup::new("source").required(true).args(["block", "file"])))]
     PrintBatch {
+        /// Hash of the block holding the event (0x-prefixed)
+        #[arg(short, long, requires = "event_index")]
+        block: Option<H256>,
+
+        /// Index of the event within the block
+        #[arg(short, long)]
+        event_index: Option<u32>,
+
+        /// File holding the hex encoded payload
+        #[arg(short, long)]
+        file: Option<PathBuf>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: print_batch::OutputFormat,
+
+        /// Node RPC endpoint
+        #[arg(short, long, default_value = "ws://127.0.0.1:9944")]
+        rpc: url::Url,
+    },
+
+    /// Export every insert into a table finalized over a range of blocks
+    ExportTable {
+        /// The table, as NAMESPACE.NAME
+        #[arg(short, long)]
+        table: String,
+
+        /// First block of the range
+        #[arg(long, default_value_t = 0)]
+        from: u32,
+
+        /// Last block of the range, the last finalized block by default
+        #[arg(long)]
+        to: Option<u32>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: export_table::ExportFormat,
+
+        /// Path of the file to write
+        #[arg(short, long)]
+        output: PathBuf,
+
+        /// Node RPC endpoint
+        #[arg(short, long, default_value = "ws://127.0.0.1:9944")]
+        rpc: url::Url,
+    },
+
+    /// Replay every insert into a table through the commitment computation, comparing the
+    /// result with the commitments stored on chain
+    AuditCommitments {
+        /// The table, as NAMESPACE.NAME
+        #[arg(short, long)]
+        table: String,
+
+        /// The block the table was created in, from scratch or from a snapshot
+        #[arg(long)]
+        from: u32,
+
+        /// Last block to audit, the last finalized block by default
+        #[arg(long)]
+        to: Option<u32>,
+
+        /// Number of blocks between comparisons with the stored commitments
+        #[arg(long, default_value_t = 100)]
+        checkpoint_interval: u32,
+
+        /// Parquet file, or directory of Parquet files, holding the snapshot the table was
+        /// created from. Its rows are replayed first, instead of trusting the commitments
+        /// stored after block `from`
+        #[arg(long)]
+        snapshot: Option<PathBuf>,
+
+        /// Node RPC endpoint
+        #[arg(short, long, default_value = "ws://127.0.0.1:9944")]
+        rpc: url::Url,
+
+        #[command(flatten)]
+        setup_args: proof_of_sql_static_setups::io::ProofOfSqlPublicSetupArgs,
+    },
+
+    /// Compare an export of the EVM staking contract with the staking state mirrored on chain,
+    /// reporting the differences of every account
+    DiffStaking {
+        /// Path of the contract state export
         #[arg(short, long)]
-        row_data: String,
+        contract_state: PathBuf,
+
+        /// Format of the contract state export
+        #[arg(long, value_enum, default_value_t)]
+        format: diff_staking::ContractStateFormat,
+
+        /// Node RPC endpoint
+        #[arg(short, long, default_value = "ws://127.0.0.1:9944")]
+        rpc: url::Url,
     },
 
     /// Fetch SubmitData events from a given block
@@ -113,9 +211,74 @@ async fn main() {
                 process::exit(1);
             }
         }
-        Commands::PrintBatch { row_data } => {
-            if let Err(e) = print_batch::print_batch(row_data.as_str()) {
+        Commands::PrintBatch {
+            block,
+            event_index,
+            file,
+            format,
+            rpc,
+        } => {
+            let source = match (block, event_index, file.as_deref()) {
+                (Some(block), Some(index), _) => print_batch::PayloadSource::Event {
+                    block,
+                    index,
+                    rpc: &rpc,
+                },
+                (_, _, Some(file)) => print_batch::PayloadSource::File(file),
+                _ => unreachable!("clap requires a block and event index, or a file"),
+            };
+            if let Err(e) = print_batch::print_batch(source, format).await {
                 error!("Failed to print batch: {}", e);
+                process::exit(1);
+            }
+        }
+        Commands::ExportTable {
+            table,
+            from,
+            to,
+            format,
+            output,
+            rpc,
+        } => {
+            if let Err(e) =
+                export_table::export_table(&rpc, &table, from, to, format, &output).await
+            {
+                error!("Failed to export table: {:#}", e);
+                process::exit(1);
+            }
+        }
+        Commands::AuditCommitments {
+            table,
+            from,
+            to,
+            checkpoint_interval,
+            snapshot,
+            rpc,
+            setup_args,
+        } => {
+            if let Err(e) = audit_commitments::audit_commitments(
+                &rpc,
+                &table,
+                from,
+                to,
+                checkpoint_interval,
+                snapshot.as_deref(),
+                &setup_args,
+            )
+            .await
+            {
+                error!("Commitment audit failed: {:#}", e);
+                process::exit(1);
+            }
+        }
+        Commands::DiffStaking {
+            contract_state,
+            format,
+            rpc,
+        } => {
+            if let Err(e) = diff_staking::diff_staking(&rpc, &contract_state, format).await {
+                error!("Staking diff failed: {:#}", e);
+                process::exit(1);
             }
         }
         Commands::FetchSubmissions { block, rpc } => {
@@ -1,22 +1,129 @@
 use std::io::Write;
+use std::path::Path;
 
+use anyhow::{anyhow, Result};
+use arrow::csv::Writer as CsvWriter;
 use arrow::ipc::reader::StreamReader;
-use arrow::util::pretty::print_batches;
+use arrow::json::ArrayWriter as JsonWriter;
+use arrow::record_batch::RecordBatch;
+use arrow::util::pretty::pretty_format_batches;
+use subxt::utils::H256;
+use sxt_core::sql::record_batch_from_data;
+use sxt_core::sxt_chain_runtime::api::indexing::events::{QuorumReached, SystemTableUpdate};
 
-/// Print a given hex encoded record batch
-pub(crate) fn print_batch(hex_encoded: &str) -> anyhow::Result<()> {
+use crate::common::{table_to_str, ChainReader};
+
+/// How record batches are printed
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default)]
+pub(crate) enum OutputFormat {
+    /// An aligned table, for reading in a terminal
+    #[default]
+    Table,
+    /// CSV with a header row
+    Csv,
+    /// A JSON array of row objects
+    Json,
+}
+
+/// Write record batches in the given format
+pub(crate) fn write_batches(
+    batches: &[RecordBatch],
+    format: OutputFormat,
+    mut out: impl Write,
+) -> Result<()> {
+    match format {
+        OutputFormat::Table => writeln!(out, "{}", pretty_format_batches(batches)?)?,
+        OutputFormat::Csv => {
+            let mut writer = CsvWriter::new(out);
+            for batch in batches {
+                writer.write(batch)?;
+            }
+        }
+        OutputFormat::Json => {
+            let mut writer = JsonWriter::new(out);
+            writer.write_batches(&batches.iter().collect::<Vec<_>>())?;
+            writer.finish()?;
+        }
+    }
+    Ok(())
+}
+
+/// Decode a postcard serialized OnChainTable, as carried by `QuorumReached` and
+/// `SystemTableUpdate` events, into a record batch
+pub(crate) fn decode_payload(data: &[u8]) -> Result<RecordBatch> {
+    record_batch_from_data(data).map_err(|e| anyhow!("failed to decode payload: {e:?}"))
+}
+
+/// Decode hex, with or without a `0x` prefix
+fn decode_hex(hex_encoded: &str) -> Result<Vec<u8>> {
+    let hex_encoded = hex_encoded.trim();
     let hex_minus_prefix = hex_encoded.strip_prefix("0x").unwrap_or(hex_encoded);
+    Ok(hex::decode(hex_minus_prefix)?)
+}
 
-    let bytes = hex::decode(hex_minus_prefix)?;
+/// Read the payload of a `QuorumReached` or `SystemTableUpdate` event, returning the
+/// table it was inserted into with the payload
+async fn payload_from_event(
+    chain: &ChainReader,
+    block: H256,
+    index: u32,
+) -> Result<(String, Vec<u8>)> {
+    let events = chain.client.blocks().at(block).await?.events().await?;
+    let event = events
+        .iter()
+        .find(|event| event.as_ref().is_ok_and(|event| event.index() == index))
+        .ok_or_else(|| anyhow!("block {block:?} has no event {index}"))??;
 
-    let mut file = std::fs::File::create("./batch")?;
-    let _ = file.write(&bytes)?;
+    if let Some(quorum_reached) = event.as_event::<QuorumReached>()? {
+        Ok((
+            table_to_str(&quorum_reached.quorum.table)?,
+            quorum_reached.data.0,
+        ))
+    } else if let Some(update) = event.as_event::<SystemTableUpdate>()? {
+        Ok((table_to_str(&update.table)?, update.data.0))
+    } else {
+        Err(anyhow!(
+            "event {index} is {}::{}, not QuorumReached or SystemTableUpdate",
+            event.pallet_name(),
+            event.variant_name()
+        ))
+    }
+}
 
-    let mut reader = StreamReader::try_new(bytes.as_slice(), None)?;
+/// Where the payload printed by `print-batch` is read from
+pub(crate) enum PayloadSource<'a> {
+    /// A `QuorumReached` or `SystemTableUpdate` event of a block
+    Event {
+        /// The hash of the block
+        block: H256,
+        /// The index of the event within the block
+        index: u32,
+        /// Node RPC endpoint
+        rpc: &'a url::Url,
+    },
+    /// A file holding the hex encoded payload
+    File(&'a Path),
+}
 
-    let batch = (reader.next().unwrap())?;
+/// Handles the `print-batch` command
+pub(crate) async fn print_batch(source: PayloadSource<'_>, format: OutputFormat) -> Result<()> {
+    let data = match source {
+        PayloadSource::Event { block, index, rpc } => {
+            let chain = ChainReader::connect(rpc).await?;
+            let (table, data) = payload_from_event(&chain, block, index).await?;
+            eprintln!("Insert into {table}");
+            data
+        }
+        PayloadSource::File(file) => decode_hex(&std::fs::read_to_string(file)?)?,
+    };
 
-    print_batches(&[batch.clone()])?;
-    dbg!(&batch.schema());
-    Ok(())
+    let batch = decode_payload(&data)?;
+    write_batches(&[batch], format, std::io::stdout().lock())
+}
+
+/// Print a hex encoded record batch in arrow IPC format, as submitted by indexers
+pub(crate) fn print_ipc_batch(hex_encoded: &str) -> Result<()> {
+    let bytes = decode_hex(hex_encoded)?;
+    let batches = StreamReader::try_new(bytes.as_slice(), None)?.collect::<Result<Vec<_>, _>>()?;
+    write_batches(&batches, OutputFormat::Table, std::io::stdout().lock())
 }
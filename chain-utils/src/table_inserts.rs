@@ -0,0 +1,83 @@
+use std::ops::RangeInclusive;
+
+use anyhow::{anyhow, Result};
+use log::info;
+use subxt::utils::H256;
+use sxt_core::sxt_chain_runtime::api::indexing::events::QuorumReached;
+
+use crate::common::{table_to_str, ChainReader};
+
+/// A finalized insert into a table, from a `QuorumReached` event
+pub(crate) struct TableInsert {
+    /// The number of the block the insert was finalized in
+    pub(crate) block_number: u32,
+    /// The index of the `QuorumReached` event within the block
+    pub(crate) event_index: u32,
+    /// The inserted rows, as a postcard serialized OnChainTable including the meta columns
+    pub(crate) data: Vec<u8>,
+}
+
+/// Resolve the blocks from `from` to `to`, inclusive, checking that they are finalized.
+/// Without `to`, the range ends at the last finalized block.
+pub(crate) async fn finalized_range(
+    chain: &ChainReader,
+    from: u32,
+    to: Option<u32>,
+) -> Result<RangeInclusive<u32>> {
+    let finalized = chain.finalized_block_number().await?;
+    let to = to.unwrap_or(finalized);
+    if to > finalized {
+        return Err(anyhow!(
+            "block {to} is not finalized, the last finalized block is {finalized}"
+        ));
+    }
+    if from > to {
+        return Err(anyhow!("block range {from}..={to} is empty"));
+    }
+    Ok(from..=to)
+}
+
+/// Collect the inserts into `table`, as `NAMESPACE.NAME`, finalized in the given block,
+/// in event order
+pub(crate) async fn block_inserts(
+    chain: &ChainReader,
+    block_number: u32,
+    block_hash: H256,
+    table: &str,
+) -> Result<Vec<TableInsert>> {
+    let events = chain.client.blocks().at(block_hash).await?.events().await?;
+
+    let mut inserts = Vec::new();
+    for event in events.iter() {
+        let event = event?;
+        let Some(quorum_reached) = event.as_event::<QuorumReached>()? else {
+            continue;
+        };
+        if table_to_str(&quorum_reached.quorum.table)? == table {
+            inserts.push(TableInsert {
+                block_number,
+                event_index: event.index(),
+                data: quorum_reached.data.0,
+            });
+        }
+    }
+    Ok(inserts)
+}
+
+/// Collect the inserts into `table`, as `NAMESPACE.NAME`, finalized in the given range of
+/// blocks, in the order they were finalized
+pub(crate) async fn table_inserts(
+    chain: &ChainReader,
+    table: &str,
+    blocks: RangeInclusive<u32>,
+) -> Result<Vec<TableInsert>> {
+    let mut inserts = Vec::new();
+    for block_number in blocks.clone() {
+        if (block_number - blocks.start()) % 1000 == 0 {
+            info!("Reading block {} of {:?}", block_number, blocks);
+        }
+        let block_hash = chain.block_hash(block_number).await?;
+        inserts.extend(block_inserts(chain, block_number, block_hash, table).await?);
+    }
+    Ok(inserts)
+}
@@ -1,6 +1,7 @@
+use std::collections::HashMap;
 use std::fmt::Display;
 
-use deadpool_postgres::Object;
+use deadpool_postgres::{GenericClient, Object};
 use tonic::Status;
 
 /// Represents the status of a checkpoint in a processing workflow.
@@ -96,9 +97,9 @@ impl Checkpoint {
 
     /// Initializes the checkpoint schema and table in the database.
     ///
-    /// This function creates a schema named `SXT` and a table named `checkpoints`
-    /// if they do not already exist. It also deletes any previous checkpoints
-    /// that are not marked as completed.
+    /// This function creates a schema named `SXTMETA` with the `checkpoints` and
+    /// `file_checkpoints` tables if they do not already exist. It also deletes any
+    /// previous checkpoints that are not marked as completed.
     ///
     /// # Parameters
     ///
@@ -136,8 +137,24 @@ impl Checkpoint {
             .map_err(|e| Status::internal(format!("Failed to create checkpoint table: {}", e)))?;
 
         let query = "
-            delete from SXTMETA.checkpoints where status != 'completed'";
+            CREATE TABLE IF NOT EXISTS SXTMETA.file_checkpoints (
+                object_path TEXT PRIMARY KEY,
+                schema_name VARCHAR(64) NOT NULL,
+                table_name VARCHAR(64) NOT NULL,
+                etag TEXT,
+                row_count BIGINT NOT NULL,
+                total_time_taken BIGINT NOT NULL
+            );
+        ";
+
         client.execute(query, &[]).await.map_err(|e| {
+            Status::internal(format!("Failed to create file checkpoint table: {}", e))
+        })?;
+
+        let query = "
+            delete from SXTMETA.checkpoints where status != $1";
+        let completed = CheckpointStatus::Completed.to_string();
+        client.execute(query, &[&completed]).await.map_err(|e| {
             Status::internal(format!("Failed to delete from SXTMETA.checkpoints: {}", e))
         })?;
 
@@ -332,3 +349,249 @@ impl Checkpoint {
         Ok(())
     }
 }
+
+/// Records a Parquet file of a snapshot that has been loaded.
+///
+/// A file checkpoint is written in the same transaction as the rows of the file,
+/// so an interrupted load resumes from the first file that was not committed.
+pub struct FileCheckpoint {
+    /// - `schema_name`: The schema of the table the file belongs to.
+    pub schema_name: String,
+    /// - `table_name`: The table the file belongs to.
+    pub table_name: String,
+    /// - `object_path`: The path of the file within the object store.
+    pub object_path: String,
+    /// - `etag`: The entity tag of the file when it was loaded, if the store provides one.
+    pub etag: Option<String>,
+    /// - `row_count`: The number of rows read from the file.
+    pub row_count: i64,
+    /// - `total_time_taken`: The time taken to load the file, in milliseconds.
+    pub total_time_taken: i64,
+}
+
+impl FileCheckpoint {
+    /// Records the file as loaded, replacing any previous checkpoint of the same path.
+    ///
+    /// # Parameters
+    ///
+    /// - `client`: The database connection, usually the transaction loading the file.
+    ///
+    /// # Errors
+    ///
+    /// This function will return a `Status` error if it fails to execute the insert query.
+    pub async fn upsert(&self, client: &impl GenericClient) -> Result<u64, Status> {
+        let query = "
+            INSERT INTO SXTMETA.file_checkpoints
+                (object_path, schema_name, table_name, etag, row_count, total_time_taken)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (object_path) DO UPDATE
+            SET etag = EXCLUDED.etag,
+                row_count = EXCLUDED.row_count,
+                total_time_taken = EXCLUDED.total_time_taken";
+
+        client
+            .execute(
+                query,
+                &[
+                    &self.object_path,
+                    &self.schema_name,
+                    &self.table_name,
+                    &self.etag,
+                    &self.row_count,
+                    &self.total_time_taken,
+                ],
+            )
+            .await
+            .map_err(|e| Status::internal(format!("Failed to insert file checkpoint: {}", e)))
+    }
+
+    /// Returns the entity tag of every loaded file of a table, by object path.
+    ///
+    /// Returns an empty map if the checkpoint tables have not been created yet,
+    /// so that it can be used without writing to the database.
+    ///
+    /// # Errors
+    ///
+    /// This function will return a `Status` error if it fails to execute the query.
+    pub async fn loaded_files(
+        client: &Object,
+        schema_name: &str,
+        table_name: &str,
+    ) -> Result<HashMap<String, Option<String>>, Status> {
+        if !file_checkpoints_exist(client).await? {
+            return Ok(HashMap::new());
+        }
+
+        let query = "
+            SELECT object_path, etag
+            FROM SXTMETA.file_checkpoints
+            WHERE schema_name = $1
+            AND table_name = $2;
+        ";
+
+        let rows = client
+            .query(query, &[&schema_name, &table_name])
+            .await
+            .map_err(|e| Status::internal(format!("Failed to get file checkpoints: {}", e)))?;
+
+        Ok(rows.iter().map(|row| (row.get(0), row.get(1))).collect())
+    }
+}
+
+// Whether the file checkpoint table has been created by `Checkpoint::init_checkpoint`
+async fn file_checkpoints_exist(client: &Object) -> Result<bool, Status> {
+    client
+        .query_one(
+            "SELECT to_regclass('sxtmeta.file_checkpoints') IS NOT NULL",
+            &[],
+        )
+        .await
+        .map(|row| row.get(0))
+        .map_err(|e| Status::internal(format!("Failed to find file checkpoints: {}", e)))
+}
+
+/// The load progress of a table, as recorded by its checkpoints.
+#[derive(Debug, PartialEq, Eq)]
+pub struct TableProgress {
+    /// - `schema_name`: The schema of the table.
+    pub schema_name: String,
+    /// - `table_name`: The name of the table.
+    pub table_name: String,
+    /// - `partitions_completed`: The number of months completely loaded.
+    pub partitions_completed: i64,
+    /// - `partitions_failed`: The number of months whose last load failed.
+    pub partitions_failed: i64,
+    /// - `partitions_processing`: The number of months being loaded.
+    pub partitions_processing: i64,
+    /// - `files_loaded`: The number of files loaded.
+    pub files_loaded: i64,
+    /// - `rows_loaded`: The number of rows read from the loaded files.
+    pub rows_loaded: i64,
+    /// - `error`: The error of a failed month, if any.
+    pub error: Option<String>,
+}
+
+impl TableProgress {
+    /// Returns the progress of every table with a checkpoint, ordered by schema and table.
+    ///
+    /// Returns no tables if the checkpoint tables have not been created yet.
+    ///
+    /// # Errors
+    ///
+    /// This function will return a `Status` error if it fails to execute the query.
+    pub async fn all(client: &Object) -> Result<Vec<TableProgress>, Status> {
+        if !file_checkpoints_exist(client).await? {
+            return Ok(Vec::new());
+        }
+
+        let query = "
+            SELECT
+                schema_name,
+                table_name,
+                COALESCE(p.completed, 0),
+                COALESCE(p.failed, 0),
+                COALESCE(p.processing, 0),
+                COALESCE(f.files, 0),
+                COALESCE(f.rows, 0),
+                p.error
+            FROM (
+                SELECT
+                    schema_name,
+                    table_name,
+                    COUNT(*) FILTER (WHERE status = $1) AS completed,
+                    COUNT(*) FILTER (WHERE status = $2) AS failed,
+                    COUNT(*) FILTER (WHERE status = $3) AS processing,
+                    MAX(error) AS error
+                FROM SXTMETA.checkpoints
+                GROUP BY schema_name, table_name
+            ) p
+            FULL OUTER JOIN (
+                SELECT
+                    schema_name,
+                    table_name,
+                    COUNT(*) AS files,
+                    SUM(row_count)::BIGINT AS rows
+                FROM SXTMETA.file_checkpoints
+                GROUP BY schema_name, table_name
+            ) f USING (schema_name, table_name)
+            ORDER BY schema_name, table_name;
+        ";
+
+        let rows = client
+            .query(
+                query,
+                &[
+                    &CheckpointStatus::Completed.to_string(),
+                    &CheckpointStatus::Failed.to_string(),
+                    &CheckpointStatus::Processing.to_string(),
+                ],
+            )
+            .await
+            .map_err(|e| Status::internal(format!("Failed to get load progress: {}", e)))?;
+
+        Ok(rows
+            .iter()
+            .map(|row| TableProgress {
+                schema_name: row.get(0),
+                table_name: row.get(1),
+                partitions_completed: row.get(2),
+                partitions_failed: row.get(3),
+                partitions_processing: row.get(4),
+                files_loaded: row.get(5),
+                rows_loaded: row.get(6),
+                error: row.get(7),
+            })
+            .collect())
+    }
+}
+
+impl Display for TableProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}.{}: {} months completed, {} failed, {} processing; {} files, {} rows loaded",
+            self.schema_name,
+            self.table_name,
+            self.partitions_completed,
+            self.partitions_failed,
+            self.partitions_processing,
+            self.files_loaded,
+            self.rows_loaded
+        )?;
+        if let Some(error) = &self.error {
+            write!(f, "\n  last error: {error}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn table_progress_shows_the_last_error() {
+        let mut progress = TableProgress {
+            schema_name: "ETHEREUM".into(),
+            table_name: "BLOCKS".into(),
+            partitions_completed: 10,
+            partitions_failed: 1,
+            partitions_processing: 2,
+            files_loaded: 42,
+            rows_loaded: 1000,
+            error: None,
+        };
+        assert_eq!(
+            progress.to_string(),
+            "ETHEREUM.BLOCKS: 10 months completed, 1 failed, 2 processing; \
+             42 files, 1000 rows loaded"
+        );
+
+        progress.error = Some("failed due to connection reset".into());
+        assert_eq!(
+            progress.to_string(),
+            "ETHEREUM.BLOCKS: 10 months completed, 1 failed, 2 processing; \
+             42 files, 1000 rows loaded\n  last error: failed due to connection reset"
+        );
+    }
+}
@@ -0,0 +1,459 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Context};
+use arrow::ipc::writer::StreamWriter;
+use arrow_array::RecordBatch;
+use commitment_sql::SnapshotCommitmentVerifier;
+use log::{info, warn};
+use on_chain_table::OnChainTable;
+use proof_of_sql_commitment_map::generic_over_commitment::{
+    AssociatedPublicSetupType,
+    OptionType,
+    TableCommitmentType,
+};
+use proof_of_sql_commitment_map::{
+    PerCommitmentScheme,
+    TableCommitmentBytes,
+    TableCommitmentBytesPerCommitmentScheme,
+};
+use proof_of_sql_static_setups::io::{
+    initialize_from_config,
+    ProofOfSqlPublicSetupArgs,
+    PUBLIC_SETUPS,
+};
+use subxt::backend::legacy::rpc_methods::BlockNumber;
+use subxt::backend::legacy::LegacyRpcMethods;
+use subxt::backend::rpc::RpcClient;
+use subxt::storage::Storage;
+use subxt::utils::H256;
+use subxt::{OnlineClient, PolkadotConfig};
+use sxt_core::sxt_chain_runtime::api;
+use sxt_core::sxt_chain_runtime::api::runtime_types::bounded_collections::bounded_vec::BoundedVec;
+use sxt_core::sxt_chain_runtime::api::runtime_types::pallet_tables::pallet::CreateTableRequest;
+use sxt_core::sxt_chain_runtime::api::runtime_types::proof_of_sql_commitment_map::commitment_scheme::CommitmentScheme as RuntimeCommitmentScheme;
+use sxt_core::sxt_chain_runtime::api::runtime_types::proof_of_sql_commitment_map::commitment_storage_map::TableCommitmentBytes as RuntimeTableCommitmentBytes;
+use sxt_core::sxt_chain_runtime::api::runtime_types::sxt_core::tables::TableIdentifier;
+use sxt_core::sxt_chain_runtime::api::tables::events::TablesCreatedWithCommitments;
+
+use crate::data_loader::extract_schema_and_table;
+use crate::store::open_snapshot;
+use crate::verify::{SnapshotVerifier, TableVerifier};
+
+type TableCommitments = PerCommitmentScheme<OptionType<TableCommitmentType>>;
+type Setups = PerCommitmentScheme<AssociatedPublicSetupType<'static>>;
+type ChainClient = OnlineClient<PolkadotConfig>;
+type ChainRpc = LegacyRpcMethods<PolkadotConfig>;
+type ChainStorage = Storage<PolkadotConfig, ChainClient>;
+
+/// Verifies each table of a snapshot against the commitments recorded on-chain.
+pub struct ChainCommitmentVerifier {
+    /// The commitments of each table of the snapshot that has any, by `SCHEMA.TABLE`.
+    commitments: HashMap<String, TableCommitments>,
+    /// The setups of the commitment schemes.
+    setups: Setups,
+}
+
+impl ChainCommitmentVerifier {
+    /// Creates a verifier of the tables with the given commitments, by `SCHEMA.TABLE`.
+    pub fn new(
+        commitments: HashMap<String, TableCommitmentBytesPerCommitmentScheme>,
+        setups: Setups,
+    ) -> Result<Self, anyhow::Error> {
+        let commitments = commitments
+            .into_iter()
+            .map(|(table, bytes)| Ok((table.to_uppercase(), TableCommitments::try_from(bytes)?)))
+            .collect::<Result<_, anyhow::Error>>()?;
+        Ok(ChainCommitmentVerifier {
+            commitments,
+            setups,
+        })
+    }
+
+    /// Fetches the commitments of every table of the snapshot at `snapshot_url` from the node
+    /// at `rpc_url`.
+    ///
+    /// By default, each table is verified against the commitments it was created with, as
+    /// announced by `TablesCreatedWithCommitments` in the block that created it. Rows inserted
+    /// since are not part of the snapshot, so later commitments would fail verification.
+    /// Finding that block reads past states, so the node must be an archive node.
+    ///
+    /// If `block_number` is set, every table is verified against its commitments at that block
+    /// instead, which should be the block the snapshot was taken at.
+    pub async fn fetch(
+        rpc_url: &str,
+        block_number: Option<u32>,
+        snapshot_url: &str,
+        setup_args: &ProofOfSqlPublicSetupArgs,
+    ) -> Result<Self, anyhow::Error> {
+        initialize_from_config(setup_args).await?;
+        let setups = *PUBLIC_SETUPS.get().expect("public setups are initialized");
+
+        let rpc_client = RpcClient::from_insecure_url(rpc_url).await?;
+        let client = ChainClient::from_rpc_client(rpc_client.clone()).await?;
+        let rpc = ChainRpc::new(rpc_client);
+        let verified_block = match block_number {
+            Some(number) => Some(block_hash(&rpc, number).await?),
+            None => None,
+        };
+        let finalized = finalized_block_number(&rpc).await?;
+
+        let snapshot = open_snapshot(snapshot_url)?;
+        let list_result = snapshot
+            .store
+            .list_with_delimiter(Some(&snapshot.base_path))
+            .await?;
+
+        let mut commitments = HashMap::new();
+        for table in list_result.common_prefixes {
+            // Tables whose names can't be extracted fail to load anyway
+            let Ok((schema_name, table_name)) = extract_schema_and_table(&table) else {
+                continue;
+            };
+            let identifier = TableIdentifier {
+                name: BoundedVec(table_name.to_uppercase().into_bytes()),
+                namespace: BoundedVec(schema_name.to_uppercase().into_bytes()),
+            };
+            let bytes = match verified_block {
+                Some(block_hash) => {
+                    stored_commitments(&client.storage().at(block_hash), &identifier).await?
+                }
+                None => created_commitments(&client, &rpc, &identifier, finalized).await?,
+            };
+            let key = table_key(&schema_name, &table_name);
+            let Some(bytes) = bytes else {
+                warn!("{} has no commitments on-chain", key);
+                continue;
+            };
+            commitments.insert(key, bytes);
+        }
+        info!("Fetched the commitments of {} table(s)", commitments.len());
+
+        ChainCommitmentVerifier::new(commitments, setups)
+    }
+}
+
+impl SnapshotVerifier for ChainCommitmentVerifier {
+    fn table_verifier(
+        &self,
+        schema: &str,
+        table: &str,
+    ) -> Result<Option<Box<dyn TableVerifier>>, anyhow::Error> {
+        let key = table_key(schema, table);
+        let expected = self
+            .commitments
+            .get(&key)
+            .ok_or_else(|| anyhow!("{key} has no commitments on-chain"))?;
+        let verifier = SnapshotCommitmentVerifier::new(expected.clone(), self.setups)?;
+        Ok(Some(Box::new(CommitmentTableVerifier(verifier))))
+    }
+}
+
+/// Computes the commitments of a table's rows, to compare them to the commitments on-chain.
+struct CommitmentTableVerifier(SnapshotCommitmentVerifier<'static>);
+
+impl TableVerifier for CommitmentTableVerifier {
+    fn append(&mut self, batch: &RecordBatch) -> Result<(), anyhow::Error> {
+        Ok(self.0.append(on_chain_table(batch)?)?)
+    }
+
+    fn finish(self: Box<Self>) -> Result<(), anyhow::Error> {
+        Ok(self.0.finish()?)
+    }
+}
+
+/// Returns the commitments of each table of a `TablesCreatedWithCommitments` event, by
+/// `SCHEMA.TABLE`.
+pub fn commitments_of_created_tables(
+    tables: &[CreateTableRequest],
+) -> Result<HashMap<String, TableCommitmentBytesPerCommitmentScheme>, anyhow::Error> {
+    tables
+        .iter()
+        .map(|table| {
+            let key = identifier_key(&table.table_name);
+            let bytes = PerCommitmentScheme {
+                hyper_kzg: commitment_bytes(table.commitment.hyper_kzg.clone())?,
+                dynamic_dory: commitment_bytes(table.commitment.dynamic_dory.clone())?,
+            };
+            Ok((key, bytes))
+        })
+        .collect()
+}
+
+/// Converts a loaded batch into an `OnChainTable`.
+///
+/// `on-chain-table` converts from a newer arrow than the loader reads snapshots with, so the
+/// batch is carried across the two versions in the arrow IPC format.
+fn on_chain_table(batch: &RecordBatch) -> Result<OnChainTable, anyhow::Error> {
+    let mut writer = StreamWriter::try_new(Vec::new(), &batch.schema())?;
+    writer.write(batch)?;
+    let bytes = writer.into_inner()?;
+
+    let batch = arrow_ipc::reader::StreamReader::try_new(bytes.as_slice(), None)?
+        .next()
+        .ok_or_else(|| anyhow!("record batch is missing from its IPC stream"))??;
+    Ok(OnChainTable::try_from(batch)?)
+}
+
+/// The key of a table's commitments, as `SCHEMA.TABLE`
+fn table_key(schema: &str, table: &str) -> String {
+    format!("{}.{}", schema, table).to_uppercase()
+}
+
+/// The key of a table's commitments, from its on-chain identifier
+fn identifier_key(table: &TableIdentifier) -> String {
+    table_key(
+        &String::from_utf8_lossy(&table.namespace.0),
+        &String::from_utf8_lossy(&table.name.0),
+    )
+}
+
+/// Fetch the hash of a block
+async fn block_hash(rpc: &ChainRpc, number: u32) -> Result<H256, anyhow::Error> {
+    rpc.chain_get_block_hash(Some(BlockNumber::from(number)))
+        .await?
+        .ok_or_else(|| anyhow!("block {number} not found"))
+}
+
+/// Fetch the number of the last finalized block
+async fn finalized_block_number(rpc: &ChainRpc) -> Result<u32, anyhow::Error> {
+    let hash = rpc.chain_get_finalized_head().await?;
+    let header = rpc
+        .chain_get_header(Some(hash))
+        .await?
+        .ok_or_else(|| anyhow!("finalized block {hash:?} not found"))?;
+    Ok(header.number)
+}
+
+/// Fetch the commitments a table was created with, from the block that created it
+async fn created_commitments(
+    client: &ChainClient,
+    rpc: &ChainRpc,
+    table: &TableIdentifier,
+    finalized: u32,
+) -> Result<Option<TableCommitmentBytesPerCommitmentScheme>, anyhow::Error> {
+    let Some(number) = creation_block(client, rpc, table, finalized).await? else {
+        return Ok(None);
+    };
+    let hash = block_hash(rpc, number).await?;
+    let key = identifier_key(table);
+
+    for event in client
+        .events()
+        .at(hash)
+        .await?
+        .find::<TablesCreatedWithCommitments>()
+    {
+        if let Some(bytes) = commitments_of_created_tables(&event?.table_list.0)?.remove(&key) {
+            return Ok(Some(bytes));
+        }
+    }
+
+    // Genesis tables and tables created by `create_tables` aren't announced by
+    // `TablesCreatedWithCommitments`, but their commitments are stored in the same block
+    stored_commitments(&client.storage().at(hash), table).await
+}
+
+/// Find the first block whose state has the table's snapshot, which is the block that created
+/// it
+async fn creation_block(
+    client: &ChainClient,
+    rpc: &ChainRpc,
+    table: &TableIdentifier,
+    finalized: u32,
+) -> Result<Option<u32>, anyhow::Error> {
+    let address = api::storage().tables().snapshots(table);
+    let has_snapshot = |number| {
+        let address = &address;
+        async move {
+            let hash = block_hash(rpc, number).await?;
+            let snapshot = client
+                .storage()
+                .at(hash)
+                .fetch(address)
+                .await
+                .with_context(|| {
+                    format!(
+                        "failed to read the state of block {number}, is the node an archive node?"
+                    )
+                })?;
+            Ok::<_, anyhow::Error>(snapshot.is_some())
+        }
+    };
+
+    if !has_snapshot(finalized).await? {
+        return Ok(None);
+    }
+    // Snapshots are never removed, so every block from the creation block on has the snapshot
+    let (mut low, mut high) = (0, finalized);
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if has_snapshot(middle).await? {
+            high = middle;
+        } else {
+            low = middle + 1;
+        }
+    }
+    Ok(Some(high))
+}
+
+/// Fetch the commitments of a table for every scheme, if it has any
+async fn stored_commitments(
+    storage: &ChainStorage,
+    table: &TableIdentifier,
+) -> Result<Option<TableCommitmentBytesPerCommitmentScheme>, anyhow::Error> {
+    let bytes: TableCommitmentBytesPerCommitmentScheme = PerCommitmentScheme {
+        hyper_kzg: stored_commitment(storage, table, RuntimeCommitmentScheme::HyperKzg).await?,
+        dynamic_dory: stored_commitment(storage, table, RuntimeCommitmentScheme::DynamicDory)
+            .await?,
+    };
+    if bytes.hyper_kzg.is_none() && bytes.dynamic_dory.is_none() {
+        return Ok(None);
+    }
+    Ok(Some(bytes))
+}
+
+/// Fetch the commitment of a table for one scheme, if any
+async fn stored_commitment(
+    storage: &ChainStorage,
+    table: &TableIdentifier,
+    scheme: RuntimeCommitmentScheme,
+) -> Result<Option<TableCommitmentBytes>, anyhow::Error> {
+    let address = api::storage()
+        .commitments()
+        .commitment_storage_map(table, scheme);
+    commitment_bytes(storage.fetch(&address).await?)
+}
+
+/// Convert a commitment read from the chain
+fn commitment_bytes(
+    bytes: Option<RuntimeTableCommitmentBytes>,
+) -> Result<Option<TableCommitmentBytes>, anyhow::Error> {
+    let Some(bytes) = bytes else {
+        return Ok(None);
+    };
+    let data = bytes
+        .data
+        .0
+        .try_into()
+        .map_err(|_| anyhow!("commitment exceeds the maximum length"))?;
+    Ok(Some(TableCommitmentBytes { data }))
+}
+
+#[cfg(test)]
+mod test {
+    use std::marker::PhantomData;
+    use std::sync::Arc;
+
+    use arrow_array::{ArrayRef, Int64Array, StringArray};
+    use commitment_sql::{row_number_column_def, OnChainTableToTableCommitmentFn};
+    use proof_of_sql_static_setups::io::get_or_init_from_files_with_four_points_unchecked;
+    use sxt_core::sxt_chain_runtime::api::runtime_types::proof_of_sql_commitment_map::commitment_scheme::PerCommitmentScheme as RuntimePerCommitmentScheme;
+    use sxt_core::sxt_chain_runtime::api::runtime_types::sxt_core::tables::TableType;
+
+    use super::*;
+
+    fn animals(names: &[&str], populations: &[i64], first_row: i64) -> RecordBatch {
+        let rows = first_row..first_row + names.len() as i64;
+        RecordBatch::try_from_iter([
+            (
+                "ANIMAL",
+                Arc::new(StringArray::from(names.to_vec())) as ArrayRef,
+            ),
+            (
+                "POPULATION",
+                Arc::new(Int64Array::from(populations.to_vec())) as ArrayRef,
+            ),
+            (
+                row_number_column_def().name.value.as_str(),
+                Arc::new(Int64Array::from_iter_values(rows)) as ArrayRef,
+            ),
+        ])
+        .unwrap()
+    }
+
+    /// The request of a table created from a snapshot of `rows`, as announced by
+    /// `TablesCreatedWithCommitments`
+    fn created_table(rows: &RecordBatch) -> CreateTableRequest {
+        let setups = get_or_init_from_files_with_four_points_unchecked();
+        let table = on_chain_table(rows).unwrap();
+        let commitments: TableCommitments = setups
+            .into_iter()
+            .map(|any| {
+                any.map(OnChainTableToTableCommitmentFn::new(&table, 0))
+                    .transpose_result()
+                    .unwrap()
+            })
+            .collect();
+        let bytes = TableCommitmentBytesPerCommitmentScheme::try_from(commitments).unwrap();
+        let runtime_bytes = |bytes: Option<TableCommitmentBytes>| {
+            bytes.map(|bytes| RuntimeTableCommitmentBytes {
+                data: BoundedVec(bytes.data.into_inner()),
+            })
+        };
+
+        CreateTableRequest {
+            table_uuid: BoundedVec(Vec::new()),
+            table_version: 0,
+            column_uuids: BoundedVec(Vec::new()),
+            table_name: TableIdentifier {
+                name: BoundedVec(b"ZOO".to_vec()),
+                namespace: BoundedVec(b"ANIMALS".to_vec()),
+            },
+            ddl: BoundedVec(
+                b"CREATE TABLE ANIMALS.ZOO (ANIMAL VARCHAR, POPULATION BIGINT)".to_vec(),
+            ),
+            commitment: RuntimePerCommitmentScheme {
+                hyper_kzg: runtime_bytes(bytes.hyper_kzg),
+                dynamic_dory: runtime_bytes(bytes.dynamic_dory),
+                __ignore: PhantomData,
+            },
+            snapshot_url: BoundedVec(b"file:///snapshot".to_vec()),
+            table_type: TableType::Community,
+        }
+    }
+
+    fn verifier(tables: &[CreateTableRequest]) -> ChainCommitmentVerifier {
+        let setups = get_or_init_from_files_with_four_points_unchecked();
+        ChainCommitmentVerifier::new(commitments_of_created_tables(tables).unwrap(), *setups)
+            .unwrap()
+    }
+
+    #[test]
+    fn snapshots_are_verified_against_the_commitments_they_were_created_with() {
+        let snapshot = animals(&["cow", "dog", "cat", "pig"], &[100, 2, 7, 30], 0);
+        let verifier = verifier(&[created_table(&snapshot)]);
+
+        // partitions are loaded in no particular order
+        let mut table = verifier.table_verifier("animals", "zoo").unwrap().unwrap();
+        table
+            .append(&animals(&["cat", "pig"], &[7, 30], 2))
+            .unwrap();
+        table
+            .append(&animals(&["cow", "dog"], &[100, 2], 0))
+            .unwrap();
+        table.finish().unwrap();
+    }
+
+    #[test]
+    fn snapshots_with_rows_other_than_their_creation_fail_verification() {
+        let snapshot = animals(&["cow", "dog"], &[100, 2], 0);
+        let verifier = verifier(&[created_table(&snapshot)]);
+
+        let mut table = verifier.table_verifier("ANIMALS", "ZOO").unwrap().unwrap();
+        let appended = table.append(&animals(&["cow", "dog"], &[100, 3], 0));
+        assert!(appended.is_err() || table.finish().is_err());
+
+        // rows inserted after the table was created are not part of its snapshot
+        let mut table = verifier.table_verifier("ANIMALS", "ZOO").unwrap().unwrap();
+        let appended = table.append(&animals(&["cow", "dog", "cat"], &[100, 2, 7], 0));
+        assert!(appended.is_err() || table.finish().is_err());
+    }
+
+    #[test]
+    fn tables_that_were_not_created_with_commitments_fail_verification() {
+        let snapshot = animals(&["cow"], &[100], 0);
+        let verifier = verifier(&[created_table(&snapshot)]);
+
+        assert!(verifier.table_verifier("ANIMALS", "FARM").is_err());
+    }
+}
@@ -4,16 +4,18 @@ use std::time::Duration;
 
 use deadpool_postgres::{Manager, ManagerConfig, Object, Pool, RecyclingMethod};
 use lazy_static::lazy_static;
-use log::debug;
-use object_store::azure::{MicrosoftAzure, MicrosoftAzureBuilder};
 use object_store::path::Path;
-use object_store::{ClientOptions, ListResult, ObjectMeta};
+use object_store::{ListResult, ObjectMeta};
 use regex::Regex;
 use tokio::time::sleep;
 use tokio_postgres::NoTls;
 
-use crate::azure_data_loader::{estimate_load_time, load_data_from_azure};
+use crate::checkpoint::TableProgress;
+use crate::plan::LoadPlan;
+use crate::snapshot_loader::{estimate_load_time, load_snapshot, plan_snapshot, LoadOptions};
+use crate::store::open_snapshot;
 use crate::to_pg::PgColumn;
+use crate::verify::VerificationReport;
 
 lazy_static! {
     /// Regular expression to capture the year from the object store file path.
@@ -72,8 +74,8 @@ JOIN
     AND ccu.column_name = c.column_name
 WHERE
     constraint_type = 'PRIMARY KEY'
-    AND tc.table_name = $1
-    AND tc.table_schema = $2;
+    AND upper(tc.table_name) = $1
+    AND upper(tc.table_schema) = $2;
     ";
 
 const COLUMN_TYPE_QUERY: &str = "
@@ -82,30 +84,70 @@ const COLUMN_TYPE_QUERY: &str = "
         WHERE upper(table_name) = $1 and upper(table_schema) = $2
     ";
 
-/// Estimate the processing time for loading data from an Azure object store.
+/// Estimate the processing time for loading a snapshot.
 ///
 /// # Arguments
-/// - `base_path`: A string slice that represents the base path in the object store.
+/// - `snapshot_url`: The URL of the snapshot, see [`crate::store::SnapshotLocation::parse`].
 ///
 /// # Returns
 /// An `Ok(())` result on success, or an error wrapped in `anyhow::Error` on failure.
-pub async fn estimate_time(base_path: &str) -> Result<(), anyhow::Error> {
-    let store = get_object_store()?; // Get the object store client
-    estimate_load_time(&store, base_path.into()).await?;
+pub async fn estimate_time(snapshot_url: &str) -> Result<(), anyhow::Error> {
+    let snapshot = open_snapshot(snapshot_url)?; // Get the object store client
+    estimate_load_time(snapshot.store.as_ref(), &snapshot.base_path).await?;
     Ok(())
 }
 
+/// Plan the load of a snapshot without loading anything, as a dry run.
+///
+/// Files already loaded are found from the checkpoints of the database at `DATABASE_URL`,
+/// if it is set. Otherwise, every file is planned to be loaded.
+///
+/// # Arguments
+/// - `snapshot_url`: The URL of the snapshot, see [`crate::store::SnapshotLocation::parse`].
+///
+/// # Returns
+/// The files of each table and whether they would be loaded, or an error wrapped in
+/// `anyhow::Error` on failure.
+pub async fn plan_load(snapshot_url: &str) -> Result<LoadPlan, anyhow::Error> {
+    let snapshot = open_snapshot(snapshot_url)?;
+    let client = match env::var("DATABASE_URL") {
+        Ok(_) => Some(create_client_session().await?),
+        Err(_) => None,
+    };
+    plan_snapshot(
+        snapshot.store.as_ref(),
+        &snapshot.base_path,
+        client.as_ref(),
+    )
+    .await
+}
+
+/// Report the load progress of every table from the checkpoints of the database at
+/// `DATABASE_URL`.
+///
+/// # Returns
+/// The progress of each table with a checkpoint, or an error wrapped in `anyhow::Error`
+/// on failure.
+pub async fn load_status() -> Result<Vec<TableProgress>, anyhow::Error> {
+    let client = create_client_session().await?;
+    Ok(TableProgress::all(&client).await?)
+}
+
 /// Run the data loader with retry logic for connecting to the object store and the database.
 ///
 /// # Arguments
-/// - `base_path`: The base path of the data to be loaded.
+/// - `snapshot_url`: The URL of the snapshot to be loaded, see
+///   [`crate::store::SnapshotLocation::parse`].
+/// - `options`: The verifier and the number of workers of the load.
 /// - `max_retries`: The maximum number of retry attempts for the operation.
 /// - `delay`: Duration to wait between retry attempts.
 ///
 /// # Returns
 /// Returns `Ok(())` on success or an error wrapped in `anyhow::Error` if all retries fail.
+/// Tables failing verification are not retried, and are reported by a [`VerificationReport`].
 pub async fn run_data_loader(
-    base_path: &str,
+    snapshot_url: &str,
+    options: LoadOptions<'_>,
     max_retries: u32,
     delay: Duration,
 ) -> Result<(), anyhow::Error> {
@@ -117,13 +159,15 @@ pub async fn run_data_loader(
 
         // Attempt to run the data loader
         match async {
-            let store = get_object_store()?; // Get the object store client
-            load_data_from_azure(&store, base_path.into()).await?;
+            let snapshot = open_snapshot(snapshot_url)?; // Get the object store client
+            load_snapshot(snapshot.store.as_ref(), &snapshot.base_path, options).await?;
             Ok(())
         }
         .await
         {
             Ok(_) => return Ok(()), // Return if successful
+            // Loading the same rows again won't change their verification
+            Err(e) if e.is::<VerificationReport>() => return Err(e),
             Err(e) => {
                 eprintln!("Attempt {} failed: {}", attempts, e);
                 if attempts < max_retries {
@@ -169,41 +213,6 @@ pub async fn create_client_session() -> Result<Object, anyhow::Error> {
     Ok(client)
 }
 
-/// Retrieve Azure storage configuration from environment variables.
-///
-/// # Returns
-/// A tuple containing the Azure account, access key, and container name, or an error string if any
-/// required environment variable is missing.
-fn get_azure_config() -> Result<(String, String, String), String> {
-    let azure_account = env::var("AZURE_ACCOUNT_NAME").map_err(|_| "Missing AZURE_ACCOUNT_NAME")?;
-    let azure_endpoint = env::var("AZURE_ENDPOINT").map_err(|_| "Missing AZURE_ENDPOINT")?;
-    let azure_container_name =
-        env::var("AZURE_CONTAINER_NAME").map_err(|_| "Missing AZURE_CONTAINER_NAME")?;
-
-    Ok((azure_account, azure_endpoint, azure_container_name))
-}
-
-/// Initialize and configure the Microsoft Azure object store client using credentials and settings.
-///
-/// # Returns
-/// An `Ok(MicrosoftAzure)` object on success or an error wrapped in `anyhow::Error` on failure.
-fn get_object_store() -> Result<MicrosoftAzure, anyhow::Error> {
-    let client_options = ClientOptions::new().with_timeout(Duration::from_secs(1000));
-
-    // Load configuration from environment variables with default values
-    let (azure_account, azure_endpoint, azure_container_name) = get_azure_config().unwrap();
-
-    let store = MicrosoftAzureBuilder::from_env()
-        .with_account(azure_account)
-        .with_container_name(azure_container_name)
-        .with_endpoint(azure_endpoint)
-        .with_skip_signature(true)
-        .with_client_options(client_options)
-        .build()?;
-    debug!("Store created");
-    Ok(store)
-}
-
 fn get_process_only_head() -> Result<bool, anyhow::Error> {
     // Read the environment variable
     let process_only_head = env::var("PROCESS_ONLY_HEAD").unwrap_or_else(|_| "false".to_string());
@@ -283,6 +292,30 @@ pub fn extract_schema_and_table(path: &Path) -> Result<(String, String), anyhow:
     Err(anyhow::anyhow!("Could not extract schema and table names"))
 }
 
+/// Fetches the primary key columns of a table in a PostgreSQL database.
+///
+/// # Parameters
+/// - `client`: A reference to an active database connection object used to
+///   execute the query.
+/// - `schema_name`: The name of the schema in which the table resides.
+/// - `table_name`: The name of the table whose primary key is to be fetched.
+///
+/// # Returns
+/// The names of the primary key columns, empty if the table has no primary key.
+pub async fn get_primary_key_columns(
+    client: &Object,
+    schema_name: &str,
+    table_name: &str,
+) -> Result<Vec<String>, anyhow::Error> {
+    let rows = client
+        .query(
+            PRIMARY_KEY_QUERY,
+            &[&table_name.to_uppercase(), &schema_name.to_uppercase()],
+        )
+        .await?;
+    Ok(rows.iter().map(|row| row.get(0)).collect())
+}
+
 /// Fetches the column metadata for a specific table in a PostgreSQL database.
 ///
 /// This function queries the database using the provided client connection to
@@ -1,18 +1,45 @@
 //! The data-loader crate
-//! Module responsible for handling data loading from Azure Blob Storage.
+//! Module responsible for loading table snapshots from object storage.
 //!
-/// Module responsible for handling data loading from Azure Blob Storage.
+/// Module responsible for loading snapshots from an object store.
 ///
-/// This module provides functionality to interact with Azure's storage
-/// infrastructure, enabling the loading of data from blob containers into
-/// your application. It includes utilities for connecting to Azure,
-/// processing blobs, and managing data transfers.
-pub mod azure_data_loader;
+/// This module walks the table, year and month partitions of a snapshot
+/// held in any `ObjectStore` (Azure Blob Storage, S3 or the local
+/// filesystem), and loads their Parquet files into Postgres.
+pub mod snapshot_loader;
+
+/// Module for resolving snapshot URLs to object stores.
+///
+/// The `store` module maps the `file://`, `s3://` and `az://` snapshot URLs
+/// recorded on-chain to the `ObjectStore` holding the snapshot and the path
+/// of the snapshot within it.
+pub mod store;
+
+/// Module for verifying snapshot tables as they are loaded.
+///
+/// The `verify` module defines the hooks through which every batch of a
+/// table is checked, for instance against the commitments recorded
+/// on-chain, and the report of the tables that failed.
+pub mod verify;
+
+/// Module for verifying snapshots against the commitments recorded on-chain.
+///
+/// The `commitments` module fetches the commitments of every table of a
+/// snapshot from a node, and checks the loaded rows against them with
+/// `commitment_sql::SnapshotCommitmentVerifier`.
+pub mod commitments;
+
+/// Module for planning snapshot loads.
+///
+/// The `plan` module describes the files of each table of a snapshot and
+/// whether the next load reads them, as reported by a dry run.
+pub mod plan;
 
 /// Module for managing checkpoints in data processing workflows.
 ///
 /// The `checkpoint` module provides utilities to record and manage
-/// checkpoints, allowing for resuming long-running data load
+/// checkpoints of each partition and each file, allowing for resuming
+/// long-running data load
 pub mod checkpoint;
 
 /// Module for error handling and custom error types.
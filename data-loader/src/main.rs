@@ -0,0 +1,149 @@
+//! Loads table snapshots from an object store into the Postgres database at `DATABASE_URL`.
+//!
+//! ## Usage
+//! ```sh
+//! # Report the files that a load would read, without loading anything
+//! data-loader load file:///path/to/snapshot --dry-run
+//!
+//! # Load the snapshot, resuming from the files already loaded
+//! data-loader load s3://bucket/snapshot --workers 16
+//!
+//! # Load the snapshot, verifying it against the commitments recorded on-chain
+//! data-loader load s3://bucket/snapshot --verify-rpc-url ws://127.0.0.1:9944
+//!
+//! # Show the load progress recorded in the checkpoint tables
+//! data-loader status
+//! ```
+use std::process::ExitCode;
+use std::time::Duration;
+
+use clap::{Parser, Subcommand};
+use data_loader::commitments::ChainCommitmentVerifier;
+use data_loader::data_loader::{estimate_time, load_status, plan_load, run_data_loader};
+use data_loader::snapshot_loader::{LoadOptions, DEFAULT_WORKERS};
+use data_loader::verify::SnapshotVerifier;
+use proof_of_sql_static_setups::io::ProofOfSqlPublicSetupArgs;
+
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Load a snapshot into the database
+    Load {
+        /// The URL of the snapshot, as `file://`, `s3://` or `az://`
+        #[arg(env = "SNAPSHOT_URL")]
+        snapshot_url: String,
+
+        /// The number of partitions loaded at once, across all tables
+        #[arg(long, env = "LOADER_WORKERS", default_value_t = DEFAULT_WORKERS)]
+        workers: usize,
+
+        /// Only report the files that would be loaded
+        #[arg(long)]
+        dry_run: bool,
+
+        /// The number of attempts before giving up
+        #[arg(long, default_value_t = 3)]
+        max_retries: u32,
+
+        /// The seconds to wait between attempts
+        #[arg(long, default_value_t = 2)]
+        retry_delay: u64,
+
+        /// Verify every table against its commitments, read from the node at this RPC endpoint
+        #[arg(long, env = "VERIFY_RPC_URL")]
+        verify_rpc_url: Option<String>,
+
+        /// The block the snapshot was taken at, whose commitments the tables are verified
+        /// against. By default, each table is verified against the commitments it was created
+        /// with, which needs an archive node
+        #[arg(long, requires = "verify_rpc_url")]
+        verify_block: Option<u32>,
+
+        #[command(flatten)]
+        setup_args: ProofOfSqlPublicSetupArgs,
+    },
+    /// Estimate the time taken to load a snapshot
+    Estimate {
+        /// The URL of the snapshot, as `file://`, `s3://` or `az://`
+        #[arg(env = "SNAPSHOT_URL")]
+        snapshot_url: String,
+    },
+    /// Show the load progress of every table, from the checkpoint tables
+    Status,
+}
+
+async fn run(command: Command) -> Result<(), anyhow::Error> {
+    match command {
+        Command::Load {
+            snapshot_url,
+            dry_run: true,
+            ..
+        } => println!("{}", plan_load(&snapshot_url).await?),
+        Command::Load {
+            snapshot_url,
+            workers,
+            max_retries,
+            retry_delay,
+            verify_rpc_url,
+            verify_block,
+            setup_args,
+            ..
+        } => {
+            let verifier = match verify_rpc_url {
+                Some(rpc_url) => Some(
+                    ChainCommitmentVerifier::fetch(
+                        &rpc_url,
+                        verify_block,
+                        &snapshot_url,
+                        &setup_args,
+                    )
+                    .await?,
+                ),
+                None => None,
+            };
+            let options = LoadOptions {
+                verifier: verifier
+                    .as_ref()
+                    .map(|verifier| verifier as &dyn SnapshotVerifier),
+                workers,
+            };
+            run_data_loader(
+                &snapshot_url,
+                options,
+                max_retries,
+                Duration::from_secs(retry_delay),
+            )
+            .await?
+        }
+        Command::Estimate { snapshot_url } => estimate_time(&snapshot_url).await?,
+        Command::Status => {
+            let progress = load_status().await?;
+            if progress.is_empty() {
+                println!("No table has been loaded");
+            }
+            for table in progress {
+                println!("{table}");
+            }
+        }
+    }
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    env_logger::init();
+
+    match run(Cli::parse().command).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
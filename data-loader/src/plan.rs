@@ -0,0 +1,148 @@
+use std::fmt;
+
+use object_store::path::Path;
+
+/// The state of a snapshot file, compared to the files already loaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileState {
+    /// - `Pending`: The file has not been loaded.
+    Pending,
+    /// - `Loaded`: The file has been loaded, and has not changed since.
+    Loaded,
+    /// - `Changed`: The file has been loaded, but its entity tag changed since.
+    ///   It is loaded again, keeping the rows already loaded.
+    Changed,
+}
+
+/// A Parquet file of a snapshot, as planned to be loaded.
+#[derive(Debug)]
+pub struct FilePlan {
+    /// The path of the file within the object store.
+    pub location: Path,
+    /// The size of the file, in bytes.
+    pub size: usize,
+    /// Whether the file is loaded by the next run.
+    pub state: FileState,
+}
+
+impl FilePlan {
+    /// Returns `true` if the file is loaded by the next run.
+    pub fn is_to_load(&self) -> bool {
+        self.state != FileState::Loaded
+    }
+}
+
+/// The files of a snapshot table, as planned to be loaded.
+#[derive(Debug)]
+pub struct TablePlan {
+    /// The schema of the table.
+    pub schema_name: String,
+    /// The name of the table.
+    pub table_name: String,
+    /// Every file of the table, in load order.
+    pub files: Vec<FilePlan>,
+}
+
+impl TablePlan {
+    /// Returns the number of files in the given state.
+    pub fn count(&self, state: FileState) -> usize {
+        self.files.iter().filter(|file| file.state == state).count()
+    }
+
+    /// Returns the number of bytes loaded by the next run.
+    pub fn bytes_to_load(&self) -> usize {
+        self.files
+            .iter()
+            .filter(|file| file.is_to_load())
+            .map(|file| file.size)
+            .sum()
+    }
+}
+
+impl fmt::Display for TablePlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{}: {} files, {} loaded, {} changed, {} pending ({} bytes to load)",
+            self.schema_name,
+            self.table_name,
+            self.files.len(),
+            self.count(FileState::Loaded),
+            self.count(FileState::Changed),
+            self.count(FileState::Pending),
+            self.bytes_to_load()
+        )
+    }
+}
+
+/// The tables of a snapshot, as planned to be loaded.
+///
+/// Planning a load reads the snapshot listing and the checkpoints, but writes nothing.
+#[derive(Debug)]
+pub struct LoadPlan {
+    /// Every table of the snapshot, in listing order.
+    pub tables: Vec<TablePlan>,
+}
+
+impl fmt::Display for LoadPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for table in &self.tables {
+            writeln!(f, "{table}")?;
+        }
+        let files_to_load: usize = self
+            .tables
+            .iter()
+            .map(|table| table.files.iter().filter(|file| file.is_to_load()).count())
+            .sum();
+        let bytes_to_load: usize = self.tables.iter().map(TablePlan::bytes_to_load).sum();
+        write!(
+            f,
+            "Total: {} tables, {} files ({} bytes) to load",
+            self.tables.len(),
+            files_to_load,
+            bytes_to_load
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn file(name: &str, size: usize, state: FileState) -> FilePlan {
+        FilePlan {
+            location: Path::from(name),
+            size,
+            state,
+        }
+    }
+
+    #[test]
+    fn plans_only_load_files_that_are_not_loaded_or_changed() {
+        let plan = LoadPlan {
+            tables: vec![
+                TablePlan {
+                    schema_name: "ETHEREUM".into(),
+                    table_name: "BLOCKS".into(),
+                    files: vec![
+                        file("a.parquet", 100, FileState::Loaded),
+                        file("b.parquet", 20, FileState::Changed),
+                        file("c.parquet", 3, FileState::Pending),
+                    ],
+                },
+                TablePlan {
+                    schema_name: "ETHEREUM".into(),
+                    table_name: "LOGS".into(),
+                    files: vec![file("d.parquet", 100, FileState::Loaded)],
+                },
+            ],
+        };
+
+        assert_eq!(
+            plan.to_string(),
+            "ETHEREUM.BLOCKS: 3 files, 1 loaded, 1 changed, 1 pending (23 bytes to load)\n\
+             ETHEREUM.LOGS: 1 files, 1 loaded, 0 changed, 0 pending (0 bytes to load)\n\
+             Total: 2 tables, 2 files (23 bytes) to load"
+        );
+    }
+}
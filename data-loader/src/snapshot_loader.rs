@@ -0,0 +1,868 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use arrow::record_batch::RecordBatchReader;
+use arrow_array::RecordBatch;
+use deadpool_postgres::Object;
+use futures::future::join_all;
+use futures::{pin_mut, stream, StreamExt, TryStreamExt};
+use log::{debug, error, info};
+use object_store::path::Path;
+use object_store::{ObjectMeta, ObjectStore};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReader;
+use tokio::sync::Semaphore;
+use tokio_postgres::binary_copy::BinaryCopyInWriter;
+use tokio_postgres::types::ToSql;
+
+use crate::checkpoint::{Checkpoint, CheckpointStatus, FileCheckpoint};
+use crate::data_loader::{
+    create_client_session,
+    extract_schema_and_table,
+    extract_year_and_month,
+    get_primary_key_columns,
+    get_table_columns_and_types,
+    process_data_files,
+    process_list,
+    META_ROW_NUMBER_COLUMN_NAME,
+};
+use crate::plan::{FilePlan, FileState, LoadPlan, TablePlan};
+use crate::to_pg::{get_pg_values, PgColumn};
+use crate::verify::{
+    SnapshotVerifier,
+    TableVerification,
+    TableVerificationFailure,
+    VerificationReport,
+};
+
+type Store = dyn ObjectStore;
+type ColumnMap = HashMap<String, PgColumn>;
+
+/// Column of the snapshot files that is not loaded into Postgres.
+const PRIMARY_KEY_BINARY_COLUMN_NAME: &str = "sxt_primary_key_binary";
+
+/// Temporary table each file is copied into, before its rows are inserted into the table.
+const STAGING_TABLE_NAME: &str = "sxt_loader_staging";
+
+/// `TableLoader` is a struct responsible for loading data into a specific
+/// PostgreSQL table within a given schema. It facilitates the interaction
+/// between the external data source and the PostgreSQL database by mapping
+/// the columns of the source to the table schema and performing the data
+/// loading process.
+///
+struct TableLoader<'a> {
+    /// * `store` - A reference to the `Store` which contains the data source.
+    ///   This store provides access to the data that needs to be loaded.
+    store: &'a Store,
+    /// * `schema_name` - A reference to a string slice representing the schema
+    ///   name in the PostgreSQL database where the target table resides.
+    schema_name: &'a str,
+    /// * `table_name` - A reference to a string slice representing the name of
+    ///   the PostgreSQL table into which the data will be loaded.
+    table_name: &'a str,
+    /// * `column_maps` - A `ColumnMap` that stores the mappings between the
+    ///   columns of the source data and the columns of the target PostgreSQL
+    ///   table. This ensures that data is correctly inserted into the appropriate
+    ///   columns.
+    column_maps: ColumnMap,
+    /// * `primary_key` - The primary key columns of the table, whose rows are updated
+    ///   when a file that changed is loaded again.
+    primary_key: Vec<String>,
+    /// * `verification` - Verification of the table's rows, if the snapshot is verified.
+    ///   Every batch read from the snapshot is appended to it, including the batches of
+    ///   partitions that were loaded before a restart.
+    verification: Option<Mutex<TableVerification>>,
+    /// * `loaded_files` - The entity tag of every file of the table loaded by a previous
+    ///   run, by object path. Files whose entity tag changed since are loaded again.
+    loaded_files: HashMap<String, Option<String>>,
+    /// * `workers` - The workers shared by every table of the snapshot, one of which
+    ///   loads each partition.
+    workers: &'a Semaphore,
+    /// * `failed_partitions` - The partitions that failed to load. Their rows are
+    ///   missing from the verification, so the table is not verified.
+    failed_partitions: Mutex<Vec<String>>,
+}
+
+/// TableLoadEstimator is a struct used to estimate the data load time
+struct TableLoadEstimator<'a> {
+    store: &'a Store,
+}
+
+impl TableLoadEstimator<'_> {
+    async fn estimate_size(&self, prefix: &Path) -> Result<usize, anyhow::Error> {
+        // List all years (or directories) under the prefix
+        let list_result = self.store.list_with_delimiter(Some(prefix)).await?;
+
+        let mut total_size: usize = 0;
+        for year in list_result.common_prefixes {
+            debug!("Processing year {}", year);
+            // Process each month directory under the year
+            let all_months = self.store.list_with_delimiter(Some(&year)).await?;
+
+            // Create futures for processing each month concurrently
+            let month_futures: Vec<_> = all_months
+                .common_prefixes
+                .into_iter()
+                .map(|month| async move { self.part_size(&month).await })
+                .collect();
+
+            // Await all month processing futures and handle errors
+            let results: Result<Vec<usize>, anyhow::Error> =
+                join_all(month_futures).await.into_iter().collect();
+
+            match results {
+                Ok(sizes) => {
+                    for s in sizes {
+                        total_size += s;
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Error processing months for year {}: {:?}", year, e);
+                    continue; // Skip this year if there's an error
+                }
+            }
+        }
+        Ok(total_size)
+    }
+
+    async fn download_single_file(&self, prefix: &Path) -> Result<(u128, usize), anyhow::Error> {
+        let all_years = self.store.list_with_delimiter(Some(prefix)).await?;
+        let years: Vec<Path> = all_years.common_prefixes.into_iter().take(1).collect();
+        let all_months = self
+            .store
+            .list_with_delimiter(Some(years.first().unwrap()))
+            .await?;
+        let months: Vec<Path> = all_months.common_prefixes.into_iter().take(1).collect();
+
+        let all_data_files = self
+            .store
+            .list(Some(months.first().unwrap()))
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        let now = SystemTime::now();
+        let files: Vec<_> = all_data_files.into_iter().take(1).collect();
+        let content = self.store.get(&files.first().unwrap().location).await?;
+        let file_contents = content.bytes().await?;
+        let time_taken = now.elapsed().unwrap().as_millis();
+        Ok((time_taken, file_contents.len()))
+    }
+
+    async fn part_size(&self, prefix: &Path) -> Result<usize, anyhow::Error> {
+        let part_size = self
+            .store
+            .list(Some(prefix))
+            .try_collect::<Vec<_>>()
+            .await?
+            .into_iter()
+            .map(|p| p.size)
+            .sum();
+        println!("Part {}, Size {}", prefix, part_size);
+        Ok(part_size)
+    }
+}
+
+impl TableLoader<'_> {
+    // Constructor to create a new DataLoader instance
+
+    // Method to process each year directory
+    async fn load(&self, prefix: &Path) -> Result<(), anyhow::Error> {
+        // List all years (or directories) under the prefix
+        let list_result = self.store.list_with_delimiter(Some(prefix)).await?;
+
+        for year in process_list(list_result) {
+            info!("Processing year {}", year);
+            // Process each month directory under the year
+            let all_months = self.store.list_with_delimiter(Some(&year)).await?;
+
+            // Create futures for processing each month concurrently
+            let month_futures: Vec<_> = process_list(all_months)
+                .into_iter()
+                .map(|month| async move { self.process_partition(&month).await })
+                .collect();
+
+            // Await all month processing futures
+            join_all(month_futures)
+                .await
+                .into_iter()
+                .collect::<Result<Vec<_>, _>>()?;
+        }
+        Ok(())
+    }
+
+    async fn create_index(&self) -> Result<(), anyhow::Error> {
+        let _permit = self.workers.acquire().await?;
+        let client = create_client_session().await?;
+        let index_name = format!("{}_{}_HASH", self.schema_name, self.table_name);
+        let column_name = META_ROW_NUMBER_COLUMN_NAME;
+        let query = format!(
+            "CREATE INDEX IF NOT EXISTS  {index_name} on {}.{} ( {column_name})",
+            self.schema_name, self.table_name
+        );
+        debug!("Index creation query {}", query);
+        client.execute(&query, &[]).await?;
+        Ok(())
+    }
+
+    // Method to process each partition (month) and insert records into Postgres
+    async fn process_partition(&self, prefix: &Path) -> Result<(), anyhow::Error> {
+        info!("processing partition: {}", prefix);
+        let (year, month) = extract_year_and_month(prefix.as_ref())
+            .ok_or("Failed to extract year and month")
+            .map_err(|e| anyhow::anyhow!(e))?;
+        // Wait for one of the workers shared by every table
+        let _permit = self.workers.acquire().await?;
+        let mut client = create_client_session().await?;
+        if Checkpoint::is_completed(&client, self.schema_name, self.table_name, year, month).await?
+        {
+            debug!(
+                "Partition for year {} and month {} is already completed.",
+                year, month
+            );
+            if self.verification.is_some() {
+                self.verify_data_files(prefix).await?;
+            }
+            return Ok(());
+        }
+
+        let checkpoint =
+            Checkpoint::new(self.schema_name.into(), self.table_name.into(), year, month);
+        Checkpoint::delete(&client, self.schema_name, self.table_name, year, month).await?;
+        checkpoint.insert(&client).await?;
+
+        let now = SystemTime::now();
+
+        let result = self.process_data_files(prefix, &mut client).await;
+        match result {
+            Ok(()) => {
+                checkpoint
+                    .update_status(
+                        &client,
+                        CheckpointStatus::Completed,
+                        None,
+                        now.elapsed().unwrap().as_secs() as i64,
+                    )
+                    .await?
+            }
+            Err(e) => {
+                let root_cause = e
+                    .source()
+                    .map_or_else(|| e.to_string(), |source| source.to_string());
+                info!("failed partition: {}", prefix);
+                self.failed_partitions
+                    .lock()
+                    .expect("failed partitions lock is never held across a panic")
+                    .push(prefix.to_string());
+                checkpoint
+                    .update_status(
+                        &client,
+                        CheckpointStatus::Failed,
+                        Some(format!("failed due to {}", root_cause)),
+                        now.elapsed().unwrap().as_secs() as i64,
+                    )
+                    .await?
+            }
+        }
+        info!("completed partition: {}", prefix);
+        Ok(())
+    }
+
+    // Loads the files of a partition that were not loaded yet, each in its own transaction
+    async fn process_data_files(
+        &self,
+        prefix: &Path,
+        client: &mut Object,
+    ) -> Result<(), anyhow::Error> {
+        for data_file in list_data_files(self.store, prefix).await? {
+            debug!("Part file location: {}", data_file.location);
+
+            if self.loaded_files.get(data_file.location.as_ref()) == Some(&data_file.e_tag) {
+                debug!("Part file {} is already loaded", data_file.location);
+                if self.verification.is_some() {
+                    self.verify_data_file(&data_file.location).await?;
+                }
+                continue;
+            }
+
+            self.copy_data_file(client, &data_file).await?;
+        }
+        Ok(())
+    }
+
+    // Reads the files of a partition that is already loaded, only to verify them
+    async fn verify_data_files(&self, prefix: &Path) -> Result<(), anyhow::Error> {
+        for data_file in list_data_files(self.store, prefix).await? {
+            self.verify_data_file(&data_file.location).await?;
+        }
+        Ok(())
+    }
+
+    // Reads a file that is already loaded, only to verify it
+    async fn verify_data_file(&self, location: &Path) -> Result<(), anyhow::Error> {
+        let mut arrow_reader = read_parquet_file(self.store, location).await?;
+        while let Some(mut batch) = arrow_reader.next().transpose()? {
+            let updated_batch =
+                self.drop_column_from_batch(&mut batch, PRIMARY_KEY_BINARY_COLUMN_NAME)?;
+            self.verify_record_batch(updated_batch);
+        }
+        Ok(())
+    }
+
+    // Loads a file through a binary COPY into a staging table, whose rows are then inserted
+    // into the table skipping the ones already loaded. The file checkpoint is written in the
+    // same transaction, so the file is either loaded and checkpointed, or not loaded at all.
+    async fn copy_data_file(
+        &self,
+        client: &mut Object,
+        data_file: &ObjectMeta,
+    ) -> Result<(), anyhow::Error> {
+        let now = SystemTime::now();
+        let mut arrow_reader = read_parquet_file(self.store, &data_file.location).await?;
+        let primary_key_binary = PRIMARY_KEY_BINARY_COLUMN_NAME.to_uppercase();
+        let column_list = arrow_reader
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.name().clone())
+            .filter(|name| *name != primary_key_binary)
+            .collect::<Vec<String>>()
+            .join(", ");
+        let qualified_table_name = format!("{}.{}", self.schema_name, self.table_name);
+
+        let transaction = client.transaction().await?;
+        transaction
+            .batch_execute(&format!(
+                "CREATE TEMPORARY TABLE {STAGING_TABLE_NAME} (LIKE {qualified_table_name}) \
+                 ON COMMIT DROP"
+            ))
+            .await?;
+        let types = transaction
+            .prepare(&format!("SELECT {column_list} FROM {STAGING_TABLE_NAME}"))
+            .await?
+            .columns()
+            .iter()
+            .map(|column| column.type_().clone())
+            .collect::<Vec<_>>();
+        let sink = transaction
+            .copy_in(&format!(
+                "COPY {STAGING_TABLE_NAME} ({column_list}) FROM STDIN BINARY"
+            ))
+            .await?;
+        let writer = BinaryCopyInWriter::new(sink, &types);
+        pin_mut!(writer);
+
+        let mut row_count = 0;
+        while let Some(mut batch) = arrow_reader.next().transpose()? {
+            let updated_batch =
+                self.drop_column_from_batch(&mut batch, PRIMARY_KEY_BINARY_COLUMN_NAME)?;
+            self.verify_record_batch(updated_batch);
+
+            for i in 0..updated_batch.num_rows() {
+                let pg_values = get_pg_values(updated_batch, i, &self.column_maps)?;
+                writer
+                    .as_mut()
+                    .write(
+                        &pg_values
+                            .iter()
+                            .map(|p| p as &(dyn ToSql + Sync))
+                            .collect::<Vec<_>>(),
+                    )
+                    .await?;
+            }
+            row_count += updated_batch.num_rows() as i64;
+        }
+        writer.finish().await?;
+
+        // Rows of a file loaded before it changed, or by an older loader, are updated
+        let inserted_rows = transaction
+            .execute(
+                &format!(
+                    "INSERT INTO {qualified_table_name} ({column_list}) \
+                     SELECT {column_list} FROM {STAGING_TABLE_NAME} {}",
+                    self.on_conflict(&column_list)
+                ),
+                &[],
+            )
+            .await?;
+
+        FileCheckpoint {
+            schema_name: self.schema_name.into(),
+            table_name: self.table_name.into(),
+            object_path: data_file.location.to_string(),
+            etag: data_file.e_tag.clone(),
+            row_count,
+            total_time_taken: now.elapsed().unwrap().as_millis() as i64,
+        }
+        .upsert(&transaction)
+        .await?;
+        transaction.commit().await?;
+
+        debug!(
+            "Loaded {} rows of {}, inserted {}",
+            row_count, data_file.location, inserted_rows
+        );
+        Ok(())
+    }
+
+    // Replaces the rows whose primary key is already loaded with the rows of the file
+    fn on_conflict(&self, column_list: &str) -> String {
+        let is_key = |column: &&str| {
+            self.primary_key
+                .iter()
+                .any(|key| key.eq_ignore_ascii_case(column))
+        };
+        let updates = column_list
+            .split(", ")
+            .filter(|column| !is_key(column))
+            .map(|column| format!("{column} = EXCLUDED.{column}"))
+            .collect::<Vec<_>>();
+        if self.primary_key.is_empty() || updates.is_empty() {
+            return "ON CONFLICT DO NOTHING".to_string();
+        }
+        format!(
+            "ON CONFLICT ({}) DO UPDATE SET {}",
+            self.primary_key.join(", "),
+            updates.join(", ")
+        )
+    }
+
+    // Appends a record batch to the table's verification, if any
+    fn verify_record_batch(&self, batch: &RecordBatch) {
+        if let Some(verification) = &self.verification {
+            verification
+                .lock()
+                .expect("verification lock is never held across a panic")
+                .append(batch);
+        }
+    }
+
+    // Fails if any partition failed to load, before the table is verified without its rows
+    fn check_partitions(&self) -> Result<(), anyhow::Error> {
+        let failed_partitions = self
+            .failed_partitions
+            .lock()
+            .expect("failed partitions lock is never held across a panic");
+        if failed_partitions.is_empty() {
+            return Ok(());
+        }
+        Err(anyhow::anyhow!(
+            "{} partition(s) of {}.{} failed to load, skipping verification: {}",
+            failed_partitions.len(),
+            self.schema_name,
+            self.table_name,
+            failed_partitions.join(", ")
+        ))
+    }
+
+    // Checks every verified record batch once the table is loaded
+    fn finish_verification(self) -> Result<(), TableVerificationFailure> {
+        let Some(verification) = self.verification else {
+            return Ok(());
+        };
+
+        verification
+            .into_inner()
+            .expect("verification lock is never held across a panic")
+            .finish()
+            .map_err(|e| TableVerificationFailure {
+                table: format!("{}.{}", self.schema_name, self.table_name),
+                reason: e.to_string(),
+            })
+    }
+
+    // Function to drop a column from a RecordBatch
+    fn drop_column_from_batch<'b>(
+        &self,
+        batch: &'b mut RecordBatch,
+        column_name: &str,
+    ) -> Result<&'b mut RecordBatch, anyhow::Error> {
+        let schema = batch.schema();
+
+        let batch = match schema.index_of(&column_name.to_uppercase()) {
+            Ok(index) => {
+                batch.remove_column(index);
+                batch
+            }
+            Err(_) => batch,
+        };
+        Ok(batch)
+    }
+}
+
+/// Fetches a Parquet file from the store and returns a reader over its record batches.
+///
+/// # Parameters
+///
+/// - `store`: The store holding the file.
+/// - `location`: The path of the file within the store.
+///
+/// # Errors
+///
+/// This function will return an error if the file cannot be fetched or is not valid Parquet.
+pub async fn read_parquet_file(
+    store: &Store,
+    location: &Path,
+) -> Result<ParquetRecordBatchReader, anyhow::Error> {
+    let file_contents = store.get(location).await?.bytes().await?;
+    Ok(ParquetRecordBatchReader::try_new(file_contents, 1024)?)
+}
+
+/// Estimates the load time for data at the specified base path.
+///
+/// This function initializes a `DataLoader` struct and uses it to
+/// estimate the time required to load data from the given `base_path`.
+///
+/// # Parameters
+///
+/// - `store`: A reference to a `Store` instance, which is used to interact with the data source.
+/// - `base_path`: The path of the snapshot within the store.
+///
+/// # Errors
+///
+/// This function will return an error if the estimation process fails,
+/// encapsulated in a `anyhow::Error`.
+///
+pub async fn estimate_load_time(store: &Store, base_path: &Path) -> Result<(), anyhow::Error> {
+    // Initialize the DataLoader struct
+    let loader = DataLoader::new(store);
+    // Load data from the base path
+    loader.estimate(base_path).await?;
+    Ok(())
+}
+
+/// Loads the snapshot at the specified base path of an object store.
+///
+/// This function establishes a connection to the database and initializes
+/// a checkpoint before loading data using a `DataLoader` instance.
+///
+/// # Parameters
+///
+/// - `store`: A reference to a `Store` instance, which is used to interact with the data source.
+/// - `base_path`: The path of the snapshot within the store.
+/// - `options`: The verifier and the number of workers of the load.
+///
+/// # Errors
+///
+/// This function will return an error if any part of the loading process fails,
+/// including database connection issues or data loading failures,
+/// encapsulated in a `anyhow::Error`. If tables fail verification, the error is a
+/// [`VerificationReport`] listing each of them.
+pub async fn load_snapshot(
+    store: &Store,
+    base_path: &Path,
+    options: LoadOptions<'_>,
+) -> Result<(), anyhow::Error> {
+    // The connection is released before the load, whose workers hold their own
+    let client = create_client_session().await?;
+    Checkpoint::init_checkpoint(&client).await?;
+    drop(client);
+    // Initialize the DataLoader struct
+    let loader = DataLoader::new(store).with_options(options);
+    // Load data from the base path
+    loader.load_data(base_path).await?;
+    Ok(())
+}
+
+/// Plans the load of the snapshot at the specified base path of an object store,
+/// without loading anything.
+///
+/// # Parameters
+///
+/// - `store`: A reference to a `Store` instance, which is used to interact with the data source.
+/// - `base_path`: The path of the snapshot within the store.
+/// - `client`: The database the snapshot would be loaded into, if any, to find the files
+///   that are already loaded. Nothing is written to it.
+///
+/// # Errors
+///
+/// This function will return an error if the snapshot cannot be listed, or if the
+/// checkpoints cannot be read from the database.
+pub async fn plan_snapshot(
+    store: &Store,
+    base_path: &Path,
+    client: Option<&Object>,
+) -> Result<LoadPlan, anyhow::Error> {
+    let list_result = store.list_with_delimiter(Some(base_path)).await?;
+
+    let mut tables = Vec::new();
+    for table in process_list(list_result) {
+        let (schema_name, table_name) = extract_schema_and_table(&table)?;
+        let loaded_files = match client {
+            Some(client) => FileCheckpoint::loaded_files(client, &schema_name, &table_name).await?,
+            None => HashMap::new(),
+        };
+
+        let mut files = Vec::new();
+        for year in process_list(store.list_with_delimiter(Some(&table)).await?) {
+            for month in process_list(store.list_with_delimiter(Some(&year)).await?) {
+                for data_file in list_data_files(store, &month).await? {
+                    let state = match loaded_files.get(data_file.location.as_ref()) {
+                        Some(etag) if *etag == data_file.e_tag => FileState::Loaded,
+                        Some(_) => FileState::Changed,
+                        None => FileState::Pending,
+                    };
+                    files.push(FilePlan {
+                        location: data_file.location,
+                        size: data_file.size,
+                        state,
+                    });
+                }
+            }
+        }
+
+        tables.push(TablePlan {
+            schema_name,
+            table_name,
+            files,
+        });
+    }
+    Ok(LoadPlan { tables })
+}
+
+// Lists the data files of a partition, in the order they are loaded
+async fn list_data_files(store: &Store, prefix: &Path) -> Result<Vec<ObjectMeta>, anyhow::Error> {
+    let all_data_files = store.list(Some(prefix)).try_collect::<Vec<_>>().await?;
+    Ok(process_data_files(all_data_files))
+}
+
+/// The number of partitions loaded at once by default, across all tables.
+pub const DEFAULT_WORKERS: usize = 8;
+
+/// Options of a snapshot load.
+#[derive(Clone, Copy)]
+pub struct LoadOptions<'a> {
+    /// Checks the rows of each table as they are loaded, if any.
+    pub verifier: Option<&'a dyn SnapshotVerifier>,
+    /// The number of partitions loaded at once, across all tables. Each worker holds
+    /// a database connection while it loads a partition.
+    pub workers: usize,
+}
+
+impl Default for LoadOptions<'_> {
+    fn default() -> Self {
+        LoadOptions {
+            verifier: None,
+            workers: DEFAULT_WORKERS,
+        }
+    }
+}
+
+// Entry function to load data
+struct DataLoader<'a> {
+    store: &'a Store,
+    verifier: Option<&'a dyn SnapshotVerifier>,
+    // Bounds the partitions loaded at once, across all tables
+    workers: Semaphore,
+    max_workers: usize,
+}
+
+impl<'a> DataLoader<'a> {
+    // Constructor to create a new DataLoader instance
+    pub fn new(store: &'a Store) -> Self {
+        DataLoader {
+            store,
+            verifier: None,
+            workers: Semaphore::new(DEFAULT_WORKERS),
+            max_workers: DEFAULT_WORKERS,
+        }
+    }
+
+    // Verifies the rows of every table as they are loaded, with the given number of workers
+    pub fn with_options(self, options: LoadOptions<'a>) -> Self {
+        let max_workers = options.workers.max(1);
+        DataLoader {
+            verifier: options.verifier,
+            workers: Semaphore::new(max_workers),
+            max_workers,
+            ..self
+        }
+    }
+
+    // Method to load data from the base path
+    async fn load_data(&self, base_path: &Path) -> Result<(), anyhow::Error> {
+        // List all tables (or directories) under the base path
+        let list_result = self.store.list_with_delimiter(Some(base_path)).await?;
+
+        for table in list_result.common_prefixes.clone() {
+            info!(
+                "Top level directories to be processed {}",
+                table.to_string()
+            )
+        }
+
+        // Tables are loaded concurrently, their partitions waiting for the shared workers
+        let results = stream::iter(list_result.common_prefixes)
+            .map(|table| async move {
+                let result = self.process_table(&table).await;
+                (table, result)
+            })
+            .buffer_unordered(self.max_workers)
+            .collect::<Vec<_>>()
+            .await;
+
+        let mut failures = Vec::new();
+        for (table, result) in results {
+            // Process each table and catch any errors
+            match result {
+                Ok(()) => {}
+                Err(e) => match e.downcast::<TableVerificationFailure>() {
+                    // Keep loading other tables, and fail once they are all loaded
+                    Ok(failure) => {
+                        error!("Verification failed for {}", failure);
+                        failures.push(failure);
+                    }
+                    Err(e) => {
+                        // Log the error, but continue processing other tables
+                        eprintln!("Error processing table {}: {:?}", table, e);
+                        eprintln!("\n\n");
+                    }
+                },
+            }
+        }
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(VerificationReport { failures }.into())
+        }
+    }
+
+    async fn estimate(&self, base_path: &Path) -> Result<(), anyhow::Error> {
+        // List all tables (or directories) under the base path
+        let list_result = self.store.list_with_delimiter(Some(base_path)).await?;
+
+        let mut sample_value: Option<(u128, usize)> = None;
+
+        let mut total_size: usize = 0;
+        // Process each table (in this case, each year folder)
+        for table in list_result.common_prefixes {
+            if sample_value.is_none() {
+                sample_value = Some(self.sample(&table).await?);
+            }
+
+            let estimate_result = self.estimate_table(&table).await;
+
+            match estimate_result {
+                Ok(table_size) => total_size += table_size,
+                Err(e) => {
+                    // Log the error, but continue processing other tables
+                    eprintln!("Error processing table {}: {:?}", table, e);
+                    eprintln!("\n\n");
+                }
+            }
+        }
+        // Handle the case where sample_value might still be None
+        if let Some((sample_time, sample_size)) = sample_value {
+            println!("sample_time : {} \n", sample_time);
+            println!("sample_size : {} \n", sample_size);
+
+            if total_size > 0 {
+                // Prevent division by zero
+                println!("Total Size : {} \n", total_size);
+                println!(
+                    "Total Time estimated : {} \n",
+                    (sample_size as u128 / total_size as u128) * sample_time
+                );
+            } else {
+                println!("Total Size is 0, cannot estimate time.");
+            }
+        } else {
+            println!("No sample value available to estimate time.");
+        }
+        Ok(())
+    }
+
+    async fn process_table(&self, table: &Path) -> Result<(), anyhow::Error> {
+        info!("Processing table {}", table);
+
+        // Extract schema and table name
+        let (schema_name, table_name) = extract_schema_and_table(table).map_err(|e| {
+            anyhow::anyhow!(format!(
+                "Failed to extract schema and table for {}: {}",
+                table, e
+            ))
+        })?;
+
+        // The connection is one of the workers', and is released before the partitions load
+        let (column_maps, primary_key, loaded_files) = {
+            let _permit = self.workers.acquire().await?;
+            let client = create_client_session().await?;
+            // Fetch column mappings from the database
+            let column_maps = get_table_columns_and_types(&client, &schema_name, &table_name)
+                .await
+                .map_err(|e| {
+                    anyhow::anyhow!(format!(
+                        "Failed to get column mappings for {}: {}",
+                        table_name, e
+                    ))
+                })?;
+            let primary_key = get_primary_key_columns(&client, &schema_name, &table_name).await?;
+            let loaded_files =
+                FileCheckpoint::loaded_files(&client, &schema_name, &table_name).await?;
+            (column_maps, primary_key, loaded_files)
+        };
+
+        let verification = match self.verifier {
+            Some(verifier) => verifier
+                .table_verifier(&schema_name, &table_name)
+                .map_err(|e| TableVerificationFailure {
+                    table: format!("{}.{}", schema_name, table_name),
+                    reason: e.to_string(),
+                })?
+                .map(|verifier| Mutex::new(TableVerification::new(verifier))),
+            None => None,
+        };
+
+        let table_loader = TableLoader {
+            store: self.store,
+            schema_name: &schema_name,
+            table_name: &table_name,
+            column_maps,
+            primary_key,
+            verification,
+            loaded_files,
+            workers: &self.workers,
+            failed_partitions: Mutex::new(Vec::new()),
+        };
+
+        // Process table data using TableLoader
+        table_loader.load(table).await?;
+
+        let now = SystemTime::now();
+
+        // Process index for the table
+        table_loader.create_index().await?;
+        info!(
+            "Time taken to create index {}",
+            now.elapsed().unwrap().as_secs()
+        );
+
+        table_loader.check_partitions()?;
+        table_loader.finish_verification()?;
+        Ok(())
+    }
+
+    async fn sample(&self, table: &Path) -> Result<(u128, usize), anyhow::Error> {
+        info!("Sampling table {}", table);
+
+        let table_estimator = TableLoadEstimator { store: self.store };
+
+        // Process table data using TableLoader
+        // This function could include further logic for processing the table
+        let result = table_estimator.download_single_file(table).await?;
+
+        Ok(result)
+    }
+
+    async fn estimate_table(&self, table: &Path) -> Result<usize, anyhow::Error> {
+        info!("Estimating table {}", table);
+        let table_estimator = TableLoadEstimator { store: self.store };
+
+        // Process table data using TableLoader
+        // This function could include further logic for processing the table
+        let result = table_estimator.estimate_size(table).await?;
+
+        Ok(result)
+    }
+}
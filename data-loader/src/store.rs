@@ -0,0 +1,215 @@
+use std::env;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use log::debug;
+use object_store::aws::AmazonS3Builder;
+use object_store::azure::MicrosoftAzureBuilder;
+use object_store::local::LocalFileSystem;
+use object_store::path::Path;
+use object_store::{ClientOptions, ObjectStore};
+use url::Url;
+
+/// Timeout of a single request to a remote object store.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(1000);
+
+/// Where a snapshot is stored, as parsed from its URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnapshotLocation {
+    /// A directory on the local filesystem (`file:///path/to/snapshot`).
+    Local {
+        /// The directory holding the snapshot.
+        dir: PathBuf,
+    },
+    /// A prefix in an S3-compatible bucket (`s3://bucket/prefix`).
+    S3 {
+        /// Name of the bucket.
+        bucket: String,
+        /// Path of the snapshot within the bucket.
+        prefix: Path,
+    },
+    /// A prefix in an Azure Blob Storage container (`az://container/prefix`).
+    Azure {
+        /// Name of the container.
+        container: String,
+        /// Path of the snapshot within the container.
+        prefix: Path,
+    },
+    /// A bare path in the Azure container configured through `AZURE_CONTAINER_NAME`.
+    ///
+    /// This is how snapshots were referenced before URLs were supported.
+    AzureDefaultContainer {
+        /// Path of the snapshot within the container.
+        prefix: Path,
+    },
+}
+
+impl SnapshotLocation {
+    /// Parses a snapshot URL, as stored on-chain in a table's `SnapshotUrl`.
+    ///
+    /// # Arguments
+    /// - `snapshot_url`: A `file://`, `s3://` or `az://` URL, or a bare path in the default
+    ///   Azure container.
+    ///
+    /// # Returns
+    /// The parsed location, or an error if the URL is malformed or its scheme is not supported.
+    pub fn parse(snapshot_url: &str) -> Result<Self, anyhow::Error> {
+        let url = match Url::parse(snapshot_url) {
+            Ok(url) => url,
+            Err(url::ParseError::RelativeUrlWithoutBase) => {
+                return Ok(SnapshotLocation::AzureDefaultContainer {
+                    prefix: Path::from(snapshot_url),
+                });
+            }
+            Err(e) => return Err(anyhow::anyhow!("Invalid snapshot URL {snapshot_url}: {e}")),
+        };
+
+        let host = || {
+            url.host_str()
+                .filter(|host| !host.is_empty())
+                .map(str::to_string)
+                .ok_or_else(|| anyhow::anyhow!("Snapshot URL {snapshot_url} has no bucket"))
+        };
+        let prefix = || Path::from_url_path(url.path()).map_err(anyhow::Error::from);
+
+        match url.scheme() {
+            "file" => Ok(SnapshotLocation::Local {
+                dir: url
+                    .to_file_path()
+                    .map_err(|_| anyhow::anyhow!("Invalid file URL {snapshot_url}"))?,
+            }),
+            "s3" | "s3a" => Ok(SnapshotLocation::S3 {
+                bucket: host()?,
+                prefix: prefix()?,
+            }),
+            "az" | "azure" => Ok(SnapshotLocation::Azure {
+                container: host()?,
+                prefix: prefix()?,
+            }),
+            scheme => Err(anyhow::anyhow!(
+                "Unsupported snapshot URL scheme {scheme}, expected file, s3 or az"
+            )),
+        }
+    }
+}
+
+/// A snapshot resolved to the object store holding it.
+pub struct SnapshotStore {
+    /// The store holding the snapshot.
+    pub store: Arc<dyn ObjectStore>,
+    /// Path of the snapshot within the store. Tables are listed directly under it.
+    pub base_path: Path,
+}
+
+/// Opens the object store holding the snapshot at `snapshot_url`.
+///
+/// Credentials of remote stores are read from the environment, as documented by
+/// `AmazonS3Builder::from_env` and `MicrosoftAzureBuilder::from_env`.
+///
+/// # Arguments
+/// - `snapshot_url`: See [`SnapshotLocation::parse`].
+///
+/// # Returns
+/// The store and the path of the snapshot within it, or an error if the store cannot be built.
+pub fn open_snapshot(snapshot_url: &str) -> Result<SnapshotStore, anyhow::Error> {
+    let (store, base_path): (Arc<dyn ObjectStore>, Path) =
+        match SnapshotLocation::parse(snapshot_url)? {
+            SnapshotLocation::Local { dir } => (
+                Arc::new(LocalFileSystem::new_with_prefix(dir)?),
+                Path::default(),
+            ),
+            SnapshotLocation::S3 { bucket, prefix } => {
+                let store = AmazonS3Builder::from_env()
+                    .with_bucket_name(bucket)
+                    .with_client_options(client_options())
+                    .build()?;
+                (Arc::new(store), prefix)
+            }
+            SnapshotLocation::Azure { container, prefix } => {
+                (Arc::new(azure_store(container)?), prefix)
+            }
+            SnapshotLocation::AzureDefaultContainer { prefix } => {
+                let container = env::var("AZURE_CONTAINER_NAME")
+                    .map_err(|_| anyhow::anyhow!("Missing AZURE_CONTAINER_NAME"))?;
+                (Arc::new(azure_store(container)?), prefix)
+            }
+        };
+    debug!("Store created for {}", snapshot_url);
+
+    Ok(SnapshotStore { store, base_path })
+}
+
+/// Client options shared by the remote stores.
+fn client_options() -> ClientOptions {
+    ClientOptions::new().with_timeout(REQUEST_TIMEOUT)
+}
+
+/// Initialize and configure the Microsoft Azure object store client for a container.
+///
+/// The account and endpoint are read from `AZURE_ACCOUNT_NAME` and `AZURE_ENDPOINT`.
+/// Requests are unsigned, as snapshots are published in public containers.
+fn azure_store(container: String) -> Result<impl ObjectStore, anyhow::Error> {
+    let azure_account = env::var("AZURE_ACCOUNT_NAME")
+        .map_err(|_| anyhow::anyhow!("Missing AZURE_ACCOUNT_NAME"))?;
+    let azure_endpoint =
+        env::var("AZURE_ENDPOINT").map_err(|_| anyhow::anyhow!("Missing AZURE_ENDPOINT"))?;
+
+    let store = MicrosoftAzureBuilder::from_env()
+        .with_account(azure_account)
+        .with_container_name(container)
+        .with_endpoint(azure_endpoint)
+        .with_skip_signature(true)
+        .with_client_options(client_options())
+        .build()?;
+    Ok(store)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_file_urls() {
+        assert_eq!(
+            SnapshotLocation::parse("file:///var/snapshots/ethereum").unwrap(),
+            SnapshotLocation::Local {
+                dir: PathBuf::from("/var/snapshots/ethereum")
+            }
+        );
+    }
+
+    #[test]
+    fn parses_bucket_urls() {
+        assert_eq!(
+            SnapshotLocation::parse("s3://snapshots/mainnet/ethereum").unwrap(),
+            SnapshotLocation::S3 {
+                bucket: "snapshots".into(),
+                prefix: Path::from("mainnet/ethereum"),
+            }
+        );
+        assert_eq!(
+            SnapshotLocation::parse("az://snapshots/mainnet/ethereum").unwrap(),
+            SnapshotLocation::Azure {
+                container: "snapshots".into(),
+                prefix: Path::from("mainnet/ethereum"),
+            }
+        );
+    }
+
+    #[test]
+    fn bare_paths_use_the_default_azure_container() {
+        assert_eq!(
+            SnapshotLocation::parse("mainnet/ethereum").unwrap(),
+            SnapshotLocation::AzureDefaultContainer {
+                prefix: Path::from("mainnet/ethereum"),
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_urls() {
+        assert!(SnapshotLocation::parse("https://example.com/snapshot").is_err());
+        assert!(SnapshotLocation::parse("s3:///no-bucket").is_err());
+    }
+}
@@ -0,0 +1,123 @@
+use std::fmt;
+
+use arrow_array::RecordBatch;
+
+/// Checks the rows of a snapshot table as they are loaded.
+///
+/// Partitions of a table are read concurrently, so batches are appended in no particular order.
+pub trait TableVerifier: Send {
+    /// Adds a batch of the table's rows, as read from the snapshot.
+    fn append(&mut self, batch: &RecordBatch) -> Result<(), anyhow::Error>;
+
+    /// Checks the appended rows, once every partition of the table has been read.
+    fn finish(self: Box<Self>) -> Result<(), anyhow::Error>;
+}
+
+/// Provides a [`TableVerifier`] for each table of a snapshot.
+pub trait SnapshotVerifier: Sync {
+    /// Returns the verifier of `schema.table`, or `None` if the table is not verified.
+    fn table_verifier(
+        &self,
+        schema: &str,
+        table: &str,
+    ) -> Result<Option<Box<dyn TableVerifier>>, anyhow::Error>;
+}
+
+/// A table whose loaded rows failed verification.
+#[derive(Debug)]
+pub struct TableVerificationFailure {
+    /// The table, as `SCHEMA.TABLE`.
+    pub table: String,
+    /// Why the rows were rejected.
+    pub reason: String,
+}
+
+impl fmt::Display for TableVerificationFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.table, self.reason)
+    }
+}
+
+impl std::error::Error for TableVerificationFailure {}
+
+/// Every table of a snapshot whose loaded rows failed verification.
+#[derive(Debug)]
+pub struct VerificationReport {
+    /// The failed tables, in load order.
+    pub failures: Vec<TableVerificationFailure>,
+}
+
+impl fmt::Display for VerificationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Snapshot verification failed for {} table(s)",
+            self.failures.len()
+        )?;
+        for failure in &self.failures {
+            write!(f, "\n  {failure}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for VerificationReport {}
+
+/// Verification of a table being loaded.
+///
+/// The first error of [`TableVerifier::append`] is kept and reported by
+/// [`TableVerification::finish`], so that a bad batch doesn't interrupt the load itself.
+pub(crate) struct TableVerification {
+    verifier: Box<dyn TableVerifier>,
+    error: Option<anyhow::Error>,
+}
+
+impl TableVerification {
+    pub(crate) fn new(verifier: Box<dyn TableVerifier>) -> Self {
+        TableVerification {
+            verifier,
+            error: None,
+        }
+    }
+
+    pub(crate) fn append(&mut self, batch: &RecordBatch) {
+        if self.error.is_none() {
+            self.error = self.verifier.append(batch).err();
+        }
+    }
+
+    pub(crate) fn finish(self) -> Result<(), anyhow::Error> {
+        match self.error {
+            Some(error) => Err(error),
+            None => self.verifier.finish(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reports_list_every_failed_table() {
+        let report = VerificationReport {
+            failures: vec![
+                TableVerificationFailure {
+                    table: "ETHEREUM.BLOCKS".into(),
+                    reason: "commitment mismatch".into(),
+                },
+                TableVerificationFailure {
+                    table: "ETHEREUM.LOGS".into(),
+                    reason: "missing column TOPIC_0".into(),
+                },
+            ],
+        };
+
+        assert_eq!(
+            report.to_string(),
+            "Snapshot verification failed for 2 table(s)\n  \
+             ETHEREUM.BLOCKS: commitment mismatch\n  \
+             ETHEREUM.LOGS: missing column TOPIC_0"
+        );
+    }
+}
@@ -0,0 +1,413 @@
+//! Loads Parquet snapshots from a local directory through the `file://` object store.
+
+use std::fs::{self, File};
+use std::path::Path as FsPath;
+use std::sync::Arc;
+use std::time::Duration;
+
+use arrow::array::{Int64Array, StringArray};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use data_loader::checkpoint::TableProgress;
+use data_loader::data_loader::{
+    create_client_session,
+    extract_schema_and_table,
+    extract_year_and_month,
+    run_data_loader,
+};
+use data_loader::plan::FileState;
+use data_loader::snapshot_loader::{plan_snapshot, read_parquet_file, LoadOptions};
+use data_loader::store::open_snapshot;
+use data_loader::verify::{SnapshotVerifier, TableVerifier, VerificationReport};
+use futures::TryStreamExt;
+use parquet::arrow::ArrowWriter;
+use tempfile::TempDir;
+
+/// Rows written to every partition of the test snapshot.
+const ROWS_PER_PARTITION: i64 = 3;
+
+/// Builds a record batch of `ROWS_PER_PARTITION` blocks starting at `first_block`.
+fn blocks(first_block: i64) -> RecordBatch {
+    let schema = Schema::new(vec![
+        Field::new("BLOCK_NUMBER", DataType::Int64, false),
+        Field::new("BLOCK_HASH", DataType::Utf8, false),
+        Field::new("META_ROW_NUMBER", DataType::Int64, false),
+    ]);
+    let numbers: Vec<i64> = (first_block..first_block + ROWS_PER_PARTITION).collect();
+    let hashes: Vec<String> = numbers.iter().map(|n| format!("0x{n:064x}")).collect();
+
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(Int64Array::from(numbers.clone())),
+            Arc::new(StringArray::from(hashes)),
+            Arc::new(Int64Array::from(numbers)),
+        ],
+    )
+    .unwrap()
+}
+
+/// Writes a snapshot of `schema.table` with one Parquet file per `(year, month)` partition,
+/// laid out the way snapshots are published.
+fn write_snapshot(root: &FsPath, schema: &str, table: &str, partitions: &[(i16, i16)]) {
+    for (i, (year, month)) in partitions.iter().enumerate() {
+        let batch = blocks(i as i64 * ROWS_PER_PARTITION);
+        write_partition(root, schema, table, (*year, *month), &batch);
+    }
+}
+
+/// Writes the Parquet file of a `(year, month)` partition of `schema.table`.
+fn write_partition(
+    root: &FsPath,
+    schema: &str,
+    table: &str,
+    (year, month): (i16, i16),
+    batch: &RecordBatch,
+) {
+    let dir = root
+        .join(format!("SQL_{schema}_{table}"))
+        .join(format!("SXT_INTERNAL_YEAR={year}"))
+        .join(format!("SXT_INTERNAL_MONTH={month}"));
+    fs::create_dir_all(&dir).unwrap();
+
+    let file = File::create(dir.join("part-00000.parquet")).unwrap();
+    let mut writer = ArrowWriter::try_new(file, batch.schema(), None).unwrap();
+    writer.write(batch).unwrap();
+    writer.close().unwrap();
+}
+
+/// Returns the `file://` URL of a directory.
+fn file_url(dir: &TempDir) -> String {
+    url::Url::from_directory_path(dir.path())
+        .unwrap()
+        .to_string()
+}
+
+#[tokio::test]
+async fn file_snapshots_are_listed_by_table_and_partition() {
+    let dir = TempDir::new().unwrap();
+    write_snapshot(dir.path(), "TEST", "BLOCKS", &[(2024, 1), (2024, 2)]);
+
+    let snapshot = open_snapshot(&file_url(&dir)).unwrap();
+    let tables = snapshot
+        .store
+        .list_with_delimiter(Some(&snapshot.base_path))
+        .await
+        .unwrap()
+        .common_prefixes;
+    assert_eq!(tables.len(), 1);
+    assert_eq!(
+        extract_schema_and_table(&tables[0]).unwrap(),
+        ("TEST".to_string(), "BLOCKS".to_string())
+    );
+
+    let files = snapshot
+        .store
+        .list(Some(&tables[0]))
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    let mut partitions: Vec<_> = files
+        .iter()
+        .map(|file| extract_year_and_month(file.location.as_ref()).unwrap())
+        .collect();
+    partitions.sort();
+    assert_eq!(partitions, vec![(2024, 1), (2024, 2)]);
+}
+
+#[tokio::test]
+async fn file_snapshots_are_read_as_record_batches() {
+    let dir = TempDir::new().unwrap();
+    write_snapshot(dir.path(), "TEST", "BLOCKS", &[(2024, 1)]);
+
+    let snapshot = open_snapshot(&file_url(&dir)).unwrap();
+    let file = snapshot
+        .store
+        .list(Some(&snapshot.base_path))
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap()
+        .remove(0);
+
+    let batches = read_parquet_file(snapshot.store.as_ref(), &file.location)
+        .await
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(batches.len(), 1);
+    assert_eq!(batches[0].columns(), blocks(0).columns());
+}
+
+#[tokio::test]
+async fn file_snapshots_are_planned_without_a_database() {
+    let dir = TempDir::new().unwrap();
+    write_snapshot(dir.path(), "TEST", "BLOCKS", &[(2024, 1), (2024, 2)]);
+
+    let snapshot = open_snapshot(&file_url(&dir)).unwrap();
+    let plan = plan_snapshot(snapshot.store.as_ref(), &snapshot.base_path, None)
+        .await
+        .unwrap();
+
+    assert_eq!(plan.tables.len(), 1);
+    let table = &plan.tables[0];
+    assert_eq!(
+        (table.schema_name.as_str(), table.table_name.as_str()),
+        ("TEST", "BLOCKS")
+    );
+    assert_eq!(table.count(FileState::Pending), 2);
+    assert!(table.bytes_to_load() > 0);
+    assert!(table.files[0].location.as_ref() < table.files[1].location.as_ref());
+}
+
+/// Verifies that a table has as many rows as expected.
+struct ExpectRows(i64);
+
+/// Counts the rows of a table as they are loaded.
+struct RowCounter {
+    expected: i64,
+    rows: i64,
+}
+
+impl TableVerifier for RowCounter {
+    fn append(&mut self, batch: &RecordBatch) -> Result<(), anyhow::Error> {
+        self.rows += batch.num_rows() as i64;
+        Ok(())
+    }
+
+    fn finish(self: Box<Self>) -> Result<(), anyhow::Error> {
+        if self.rows == self.expected {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!(
+                "loaded {} rows, expected {}",
+                self.rows,
+                self.expected
+            ))
+        }
+    }
+}
+
+impl SnapshotVerifier for ExpectRows {
+    fn table_verifier(
+        &self,
+        _schema: &str,
+        _table: &str,
+    ) -> Result<Option<Box<dyn TableVerifier>>, anyhow::Error> {
+        Ok(Some(Box::new(RowCounter {
+            expected: self.0,
+            rows: 0,
+        })))
+    }
+}
+
+/// Creates `schema.BLOCKS` in the Postgres database at `DATABASE_URL`.
+///
+/// Snapshot directories are named `SQL_<schema>_<table>`, so the schema has no underscore.
+async fn create_blocks_table(schema: &str) {
+    let client = create_client_session().await.unwrap();
+    client
+        .batch_execute(&format!(
+            "CREATE SCHEMA {schema};
+             CREATE TABLE {schema}.BLOCKS (
+                 BLOCK_NUMBER BIGINT PRIMARY KEY,
+                 BLOCK_HASH VARCHAR NOT NULL,
+                 META_ROW_NUMBER BIGINT NOT NULL
+             );"
+        ))
+        .await
+        .unwrap();
+}
+
+/// Returns the number of rows of `schema.BLOCKS`.
+async fn count_blocks(schema: &str) -> i64 {
+    let client = create_client_session().await.unwrap();
+    client
+        .query_one(&format!("SELECT COUNT(*) FROM {schema}.BLOCKS"), &[])
+        .await
+        .unwrap()
+        .get(0)
+}
+
+/// Drops `schema` and its checkpoints.
+async fn drop_blocks_table(schema: &str) {
+    let client = create_client_session().await.unwrap();
+    client
+        .batch_execute(&format!(
+            "DROP SCHEMA {schema} CASCADE;
+             DELETE FROM SXTMETA.checkpoints WHERE schema_name = '{schema}';
+             DELETE FROM SXTMETA.file_checkpoints WHERE schema_name = '{schema}';"
+        ))
+        .await
+        .unwrap();
+}
+
+/// Creates `schema.BLOCKS`, loads a snapshot of two partitions into it, and returns the
+/// result of the load with the number of loaded rows.
+async fn load_blocks(
+    schema: &str,
+    verifier: Option<&dyn SnapshotVerifier>,
+) -> (Result<(), anyhow::Error>, i64) {
+    create_blocks_table(schema).await;
+
+    let dir = TempDir::new().unwrap();
+    write_snapshot(dir.path(), schema, "BLOCKS", &[(2024, 1), (2024, 2)]);
+    let options = LoadOptions {
+        verifier,
+        ..LoadOptions::default()
+    };
+    let result = run_data_loader(&file_url(&dir), options, 1, Duration::ZERO).await;
+
+    let rows = count_blocks(schema).await;
+    drop_blocks_table(schema).await;
+
+    (result, rows)
+}
+
+/// Loads a snapshot into the Postgres database at `DATABASE_URL`.
+///
+/// Skipped when `DATABASE_URL` is not set, since it needs a running database.
+#[tokio::test]
+async fn file_snapshots_are_loaded_into_postgres() {
+    if std::env::var("DATABASE_URL").is_err() {
+        eprintln!("DATABASE_URL is not set, skipping");
+        return;
+    }
+
+    let schema = format!("LOADERTEST{}", std::process::id());
+    let (result, rows) = load_blocks(&schema, Some(&ExpectRows(2 * ROWS_PER_PARTITION))).await;
+
+    result.unwrap();
+    assert_eq!(rows, 2 * ROWS_PER_PARTITION);
+}
+
+/// Skipped when `DATABASE_URL` is not set, since it needs a running database.
+#[tokio::test]
+async fn snapshots_failing_verification_fail_the_load() {
+    if std::env::var("DATABASE_URL").is_err() {
+        eprintln!("DATABASE_URL is not set, skipping");
+        return;
+    }
+
+    let schema = format!("LOADERVERIFY{}", std::process::id());
+    let (result, _) = load_blocks(&schema, Some(&ExpectRows(ROWS_PER_PARTITION))).await;
+
+    let report = result
+        .unwrap_err()
+        .downcast::<VerificationReport>()
+        .unwrap();
+    assert_eq!(report.failures.len(), 1);
+    assert_eq!(report.failures[0].table, format!("{schema}.BLOCKS"));
+    assert_eq!(
+        report.failures[0].reason,
+        format!(
+            "loaded {} rows, expected {}",
+            2 * ROWS_PER_PARTITION,
+            ROWS_PER_PARTITION
+        )
+    );
+}
+
+/// Skipped when `DATABASE_URL` is not set, since it needs a running database.
+#[tokio::test]
+async fn loaded_files_are_checkpointed_and_not_loaded_again() {
+    if std::env::var("DATABASE_URL").is_err() {
+        eprintln!("DATABASE_URL is not set, skipping");
+        return;
+    }
+
+    let schema = format!("LOADERRESUME{}", std::process::id());
+    create_blocks_table(&schema).await;
+    let dir = TempDir::new().unwrap();
+    write_snapshot(dir.path(), &schema, "BLOCKS", &[(2024, 1), (2024, 2)]);
+    let options = LoadOptions {
+        workers: 1,
+        ..LoadOptions::default()
+    };
+    run_data_loader(&file_url(&dir), options, 1, Duration::ZERO)
+        .await
+        .unwrap();
+
+    // Every file is checkpointed, so that a dry run plans nothing more to load
+    let snapshot = open_snapshot(&file_url(&dir)).unwrap();
+    let client = create_client_session().await.unwrap();
+    let plan = plan_snapshot(snapshot.store.as_ref(), &snapshot.base_path, Some(&client))
+        .await
+        .unwrap();
+    assert_eq!(plan.tables[0].count(FileState::Loaded), 2);
+    assert_eq!(plan.tables[0].bytes_to_load(), 0);
+
+    let progress = TableProgress::all(&client)
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|progress| progress.schema_name == schema)
+        .unwrap();
+    assert_eq!(progress.files_loaded, 2);
+    assert_eq!(progress.rows_loaded, 2 * ROWS_PER_PARTITION);
+
+    // Loading the snapshot again keeps the rows already loaded
+    run_data_loader(&file_url(&dir), options, 1, Duration::ZERO)
+        .await
+        .unwrap();
+    assert_eq!(count_blocks(&schema).await, 2 * ROWS_PER_PARTITION);
+
+    drop_blocks_table(&schema).await;
+}
+
+/// Skipped when `DATABASE_URL` is not set, since it needs a running database.
+#[tokio::test]
+async fn changed_files_update_their_loaded_rows() {
+    if std::env::var("DATABASE_URL").is_err() {
+        eprintln!("DATABASE_URL is not set, skipping");
+        return;
+    }
+
+    let schema = format!("LOADERCHANGE{}", std::process::id());
+    create_blocks_table(&schema).await;
+    let dir = TempDir::new().unwrap();
+    write_snapshot(dir.path(), &schema, "BLOCKS", &[(2024, 1)]);
+    run_data_loader(&file_url(&dir), LoadOptions::default(), 1, Duration::ZERO)
+        .await
+        .unwrap();
+
+    // The month is loaded again once its checkpoint is cleared, and its file has changed
+    let original = blocks(0);
+    let hashes: Vec<String> = (0..ROWS_PER_PARTITION)
+        .map(|n| format!("0x{n:064X}"))
+        .collect();
+    let changed = RecordBatch::try_new(
+        original.schema(),
+        vec![
+            original.column(0).clone(),
+            Arc::new(StringArray::from(hashes.clone())),
+            original.column(2).clone(),
+        ],
+    )
+    .unwrap();
+    write_partition(dir.path(), &schema, "BLOCKS", (2024, 1), &changed);
+    let client = create_client_session().await.unwrap();
+    client
+        .execute(
+            "DELETE FROM SXTMETA.checkpoints WHERE schema_name = $1",
+            &[&schema],
+        )
+        .await
+        .unwrap();
+    run_data_loader(&file_url(&dir), LoadOptions::default(), 1, Duration::ZERO)
+        .await
+        .unwrap();
+
+    let loaded: Vec<String> = client
+        .query(
+            &format!("SELECT BLOCK_HASH FROM {schema}.BLOCKS ORDER BY BLOCK_NUMBER"),
+            &[],
+        )
+        .await
+        .unwrap()
+        .iter()
+        .map(|row| row.get(0))
+        .collect();
+    assert_eq!(loaded, hashes);
+
+    drop_blocks_table(&schema).await;
+}
@@ -7,7 +7,7 @@
 //! - Fetching attestations from blocks.
 //! - Processing staking and unbonding events.
 //! - Constructing Merkle trees and generating cryptographic proofs.
-//! - Interacting with Ethereum smart contracts.
+//! - Interacting with Ethereum smart contracts through the [`TxManager`] outbox.
 //!
 //! This module is primarily responsible for processing blockchain data and forwarding it
 //! to an Ethereum contract via `alloy` and `subxt` integrations.
@@ -48,6 +48,7 @@ use watcher::attestation;
 use crate::block_processing;
 use crate::chain_listener::{Block, BlockProcessor, API};
 use crate::event_forwarder_contract::EventForwarder;
+use crate::tx_manager::{self, TxManager};
 
 /// Provider instance type for Ethereum transactions.
 /// This handles gas estimation, nonce management, and wallet signing.
@@ -106,6 +107,7 @@ pub struct EventForwarderProcessor {
     keypair: Keypair,
     channel: Option<mpsc::Sender<bool>>,
     initial_nonce: u64,
+    tx_manager: TxManager,
 }
 
 impl EventForwarderProcessor {
@@ -114,6 +116,7 @@ impl EventForwarderProcessor {
     /// # Parameters
     /// - `provider`: Ethereum provider instance.
     /// - `address`: Address of the deployed `EventForwarder` contract.
+    /// - `tx_manager`: Outbox the transactions to the contract are sent through.
     ///
     /// # Returns
     /// A new instance of `EventForwarderProcessor`.
@@ -123,6 +126,7 @@ impl EventForwarderProcessor {
         keypair: Keypair,
         channel: Option<mpsc::Sender<bool>>,
         initial_nonce: u64,
+        tx_manager: TxManager,
     ) -> Self {
         Self {
             provider,
@@ -130,11 +134,15 @@ impl EventForwarderProcessor {
             keypair,
             channel,
             initial_nonce,
+            tx_manager,
         }
     }
 
     /// Processes attestation events and forwards staking-related data to the Ethereum contract.
     ///
+    /// The block is only marked forwarded once the transactions queued for the attested block
+    /// have been confirmed on Ethereum.
+    ///
     /// # Parameters
     /// - `api`: A reference to the blockchain API.
     /// - `attestations`: A list of attestation events to process.
@@ -172,7 +180,20 @@ impl EventForwarderProcessor {
                 attestation.block_number
             );
         } else {
-            process_unbondings(api, &contract, attestations, &unbondings, &attested_block).await?;
+            process_unbondings(
+                api,
+                &contract,
+                &self.tx_manager,
+                attestations,
+                &unbondings,
+                &attested_block,
+            )
+            .await?;
+
+            self.tx_manager
+                .wait_until_settled(attestation.block_number)
+                .await
+                .context(TxManagerSnafu)?;
         }
 
         self.update_progress(api, attested_block_number).await?;
@@ -232,6 +253,7 @@ impl BlockProcessor for EventForwarderProcessor {
 async fn process_unbondings(
     api: &API,
     contract: &EventForwarderInstance,
+    tx_manager: &TxManager,
     attestations: &[BlockAttested],
     unbondings: &[Unbonded],
     attested_block: &Block,
@@ -268,6 +290,7 @@ async fn process_unbondings(
             process_unstake(
                 api,
                 contract,
+                tx_manager,
                 first_attestation,
                 stash,
                 &contract_info,
@@ -337,6 +360,7 @@ fn extract_signature_data(
 async fn process_unstake(
     api: &API,
     contract: &EventForwarderInstance,
+    tx_manager: &TxManager,
     attestation: &BlockAttested,
     stash: &subxt::utils::AccountId32,
     mut staking_contract_info: &[u8],
@@ -386,22 +410,29 @@ async fn process_unstake(
     .context(AccountBalanceProofSnafu)?;
     let proof = block_processing::convert_proof(proof).map_err(|_| Error::InvalidProofLength)?;
 
-    match contract
-        .sxtFulfillUnstake(
+    let call = contract.sxtFulfillUnstake(
+        staker,
+        amount,
+        attestation.block_number.into(),
+        proof,
+        r_values.to_vec(),
+        s_values.to_vec(),
+        v_values.to_vec(),
+    );
+
+    tx_manager
+        .enqueue(
+            attestation.block_number,
             staker,
-            amount,
-            attestation.block_number.into(),
-            proof,
-            r_values.to_vec(),
-            s_values.to_vec(),
-            v_values.to_vec(),
+            *contract.address(),
+            call.calldata().clone(),
         )
-        .send()
         .await
-    {
-        Ok(tx) => info!("processUnstake tx sent: {}", tx.tx_hash()),
-        Err(e) => error!("Failed to send transaction: {}", e),
-    }
+        .context(TxManagerSnafu)?;
+    info!(
+        "processUnstake for {} queued for attested block {}",
+        staker, attestation.block_number
+    );
 
     Ok(())
 }
@@ -565,4 +596,10 @@ pub enum Error {
         /// The source of the error
         source: block_processing::Error,
     },
+    /// An error originating in the tx_manager.rs module
+    #[snafu(display("TxManagerError: {source}"))]
+    TxManagerError {
+        /// The source of the error
+        source: tx_manager::Error,
+    },
 }
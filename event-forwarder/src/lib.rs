@@ -7,6 +7,7 @@
 //! - [`chain_listener`]: Manages real-time block streaming and event processing from the blockchain.
 //! - [`event_forwarder`]: Handles attestation events, staking, unbonding, and interactions with Ethereum smart contracts.
 //! - [`kitchen_sink`]: Integration testing framework that verifies end-to-end blockchain interactions.
+//! - [`tx_manager`]: Persisted outbox that sends Ethereum transactions and tracks them to confirmation.
 
 /// The `chain_listener` module provides a framework for subscribing to blockchain blocks,
 /// processing them in real time, and integrating with custom event processors.
@@ -16,6 +17,11 @@ pub mod chain_listener;
 /// to an Ethereum smart contract, ensuring the integrity of staking and Merkle tree proofs.
 pub mod event_forwarder;
 
+/// The `tx_manager` module sends the transactions owed to the Ethereum contract from a persisted
+/// outbox, replacing stuck transactions with bumped fees and waiting for a configurable
+/// confirmation depth.
+pub mod tx_manager;
+
 /// The event forwarder contract built with sol apis.
 pub mod event_forwarder_contract;
 
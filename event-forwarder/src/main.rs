@@ -19,6 +19,7 @@
 //! ```
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 
 use alloy::hex::FromHexError;
 use alloy::network::EthereumWallet;
@@ -30,6 +31,7 @@ use clap::{Parser, Subcommand};
 use event_forwarder::chain_listener::{ChainListener, IncrementingBlockStream};
 use event_forwarder::event_forwarder::{EventForwarderProcessor, ProviderInstance};
 use event_forwarder::kitchen_sink::KitchenSinkProcessor;
+use event_forwarder::tx_manager::{self, TxManager, TxManagerConfig};
 use hex::FromHex;
 use k256::ecdsa::SigningKey;
 use log::info;
@@ -74,6 +76,9 @@ enum EventForwarderError {
 
     #[snafu(display("Error fetching initial nonce: {source}"))]
     FetchInitialNonceError { source: subxt::Error },
+
+    #[snafu(display("Failed to open the transaction outbox: {source}"))]
+    OpenOutbox { source: tx_manager::Error },
 }
 
 /// Type alias for returning results with `CustomError`
@@ -113,6 +118,29 @@ struct Cli {
     /// The substrate rpc url
     #[arg(long, default_value = "ws://127.0.0.1:9944")]
     substrate_rpc_url: String,
+
+    /// Path of the SQLite outbox tracking the transactions sent to the Ethereum contract.
+    ///
+    /// Unconfirmed transactions are resumed from it after a restart.
+    #[arg(long, default_value = "event-forwarder-outbox.db")]
+    outbox_path: String,
+
+    /// Number of Ethereum blocks a transaction needs before its attested block is marked forwarded.
+    #[arg(long, default_value_t = 12)]
+    confirmations: u64,
+
+    /// Percentage by which EIP-1559 fees are raised when a stuck transaction is replaced.
+    #[arg(long, default_value_t = 15)]
+    fee_bump_percent: u128,
+
+    /// Seconds a transaction may stay unmined before it is replaced with higher fees.
+    #[arg(long, default_value_t = 60)]
+    replace_after_secs: u64,
+
+    /// Number of times the node may reject the first transaction of an unbonding, e.g. because
+    /// gas estimation reverts, before the unbonding fails and its attested block is held back.
+    #[arg(long, default_value_t = 5)]
+    max_send_failures: u32,
 }
 
 /// Defines the available subcommands
@@ -149,12 +177,26 @@ async fn main() -> Result<()> {
     let start_block = fetch_start_block(&config.api).await?;
     let stream = IncrementingBlockStream::new(start_block, rx, args.substrate_rpc_url);
 
+    let tx_manager = TxManager::open(
+        &args.outbox_path,
+        config.provider.clone(),
+        TxManagerConfig {
+            confirmations: args.confirmations,
+            fee_bump_percent: args.fee_bump_percent,
+            replace_after: Duration::from_secs(args.replace_after_secs),
+            max_send_failures: args.max_send_failures,
+            ..Default::default()
+        },
+    )
+    .context(OpenOutboxSnafu)?;
+
     let processor = EventForwarderProcessor::new(
         config.provider.clone(),
         config.contract_address,
         keypair,
         Some(tx),
         initial_nonce.into(),
+        tx_manager,
     );
 
     let chain_listener = ChainListener::new(processor, stream, config.api)
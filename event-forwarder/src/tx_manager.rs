@@ -0,0 +1,1082 @@
+//! # EVM Transaction Manager
+//!
+//! This module implements the [`TxManager`], which sends the transactions the event forwarder
+//! owes the Ethereum contract and sees them through to confirmation.
+//!
+//! ## Features:
+//! - A persisted outbox keyed by the attested block and the unbonding staker, so restarting the
+//!   forwarder never sends the same unbonding twice.
+//! - Transactions are signed and stored before they are broadcast, so a transaction that was in
+//!   flight during a restart is rebroadcast and tracked instead of being forgotten.
+//! - EIP-1559 fee bumping: a transaction that stays unmined is replaced, at the same nonce, with
+//!   fees raised by a configurable percentage.
+//! - Confirmation with a configurable depth. Until a transaction reaches it, the receipts of
+//!   every replacement are checked on each pass, so a reorg that drops a mined transaction puts
+//!   the entry back in flight. Settled entries are not checked again, so reorgs deeper than the
+//!   confirmation depth are not detected.
+//! - Entries whose first transaction the node keeps rejecting, e.g. because gas estimation
+//!   reverts, fail after a configurable number of passes instead of blocking the outbox.
+//!
+//! The event forwarder only marks an attested block forwarded once every entry queued for it
+//! has settled without failing, see [`TxManager::wait_until_settled`].
+
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use alloy::consensus::Transaction;
+use alloy::eips::eip2718::Encodable2718;
+use alloy::network::{ReceiptResponse, TransactionBuilder};
+use alloy::primitives::{Address, Bytes, TxHash};
+use alloy::providers::{Provider, SendableTx, WalletProvider};
+use alloy::rpc::types::TransactionRequest;
+use alloy::transports::TransportError;
+use log::{error, info, warn};
+use rusqlite::types::Type;
+use rusqlite::{params, Connection, Row};
+use snafu::{ResultExt, Snafu};
+use tokio::sync::Mutex;
+use tokio::time::sleep;
+
+use crate::event_forwarder::ProviderInstance;
+
+/// How long a write waits for a lock held by another connection to the outbox.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Schema of the outbox.
+///
+/// Every signed transaction is kept in `outbox_attempt`, so the receipt of an earlier attempt is
+/// still found if it is mined instead of its replacement.
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS outbox (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    attested_block INTEGER NOT NULL,
+    staker TEXT NOT NULL,
+    target TEXT NOT NULL,
+    calldata BLOB NOT NULL,
+    nonce INTEGER,
+    gas_limit INTEGER,
+    status TEXT NOT NULL,
+    settled_tx_hash TEXT,
+    settled_block INTEGER,
+    send_failures INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    UNIQUE (attested_block, staker)
+);
+CREATE TABLE IF NOT EXISTS outbox_attempt (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    entry_id INTEGER NOT NULL REFERENCES outbox (id),
+    tx_hash TEXT NOT NULL,
+    raw_tx BLOB NOT NULL,
+    max_fee_per_gas TEXT NOT NULL,
+    max_priority_fee_per_gas TEXT NOT NULL,
+    sent_at INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS outbox_attempt_by_entry ON outbox_attempt (entry_id, id);
+";
+
+/// Columns added to the outbox after its first release, with their definitions.
+const ADDED_COLUMNS: &[(&str, &str)] = &[
+    ("send_failures", "INTEGER NOT NULL DEFAULT 0"),
+    ("last_error", "TEXT"),
+];
+
+/// Columns selected when loading outbox entries.
+const ENTRY_COLUMNS: &str = "id, attested_block, staker, target, calldata, nonce, gas_limit, \
+                             status, settled_tx_hash, send_failures, last_error";
+
+/// Tunables of the [`TxManager`].
+#[derive(Debug, Clone, Copy)]
+pub struct TxManagerConfig {
+    /// Number of blocks, counting the one it was mined in, a transaction needs to be settled.
+    pub confirmations: u64,
+    /// Percentage by which both EIP-1559 fees are raised when a transaction is replaced.
+    pub fee_bump_percent: u128,
+    /// How long a transaction may stay unmined before it is replaced with higher fees.
+    pub replace_after: Duration,
+    /// How often the outbox is driven while waiting for an attested block to settle.
+    pub poll_interval: Duration,
+    /// Number of passes in which the node may reject the first transaction of an entry, e.g.
+    /// because gas estimation reverts, before the entry fails.
+    pub max_send_failures: u32,
+}
+
+impl Default for TxManagerConfig {
+    fn default() -> Self {
+        Self {
+            confirmations: 12,
+            fee_bump_percent: 15,
+            replace_after: Duration::from_secs(60),
+            poll_interval: Duration::from_secs(5),
+            max_send_failures: 5,
+        }
+    }
+}
+
+/// The progress of an outbox entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutboxStatus {
+    /// No transaction has been signed for the entry yet.
+    Pending,
+    /// A transaction has been signed and broadcast, but is not confirmed yet.
+    Sent,
+    /// A transaction of the entry succeeded and reached the confirmation depth.
+    Confirmed,
+    /// A transaction of the entry reverted and reached the confirmation depth.
+    ///
+    /// Resending the same call would revert again, so the entry is settled all the same.
+    Reverted,
+    /// The node rejected the first transaction of the entry too many times, so none was sent.
+    ///
+    /// The entry is settled, but its attested block is not forwarded, see
+    /// [`TxManager::wait_until_settled`].
+    Failed,
+}
+
+impl OutboxStatus {
+    /// Returns the value this status is stored as.
+    fn as_str(&self) -> &'static str {
+        match self {
+            OutboxStatus::Pending => "pending",
+            OutboxStatus::Sent => "sent",
+            OutboxStatus::Confirmed => "confirmed",
+            OutboxStatus::Reverted => "reverted",
+            OutboxStatus::Failed => "failed",
+        }
+    }
+
+    /// Rebuilds a status from its stored value.
+    fn from_stored(status: &str) -> Option<Self> {
+        Some(match status {
+            "pending" => OutboxStatus::Pending,
+            "sent" => OutboxStatus::Sent,
+            "confirmed" => OutboxStatus::Confirmed,
+            "reverted" => OutboxStatus::Reverted,
+            "failed" => OutboxStatus::Failed,
+            _ => return None,
+        })
+    }
+
+    /// Returns whether the entry needs no further transactions.
+    pub fn is_settled(&self) -> bool {
+        matches!(
+            self,
+            OutboxStatus::Confirmed | OutboxStatus::Reverted | OutboxStatus::Failed
+        )
+    }
+}
+
+/// A signed transaction sent for an outbox entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxAttempt {
+    /// Hash of the signed transaction.
+    pub tx_hash: TxHash,
+    /// The EIP-2718 encoding of the signed transaction, as it is broadcast.
+    pub raw_tx: Bytes,
+    /// The maximum fee per gas the transaction pays.
+    pub max_fee_per_gas: u128,
+    /// The maximum priority fee per gas the transaction pays.
+    pub max_priority_fee_per_gas: u128,
+    /// Unix time in seconds at which the transaction was signed.
+    pub sent_at: i64,
+}
+
+/// A call the event forwarder owes the Ethereum contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutboxEntry {
+    /// Row id of the entry, which orders entries by the time they were queued.
+    pub id: i64,
+    /// The attested block the call was queued for.
+    pub attested_block: u32,
+    /// The staker whose unbonding the call fulfills.
+    pub staker: Address,
+    /// Address of the called contract.
+    pub target: Address,
+    /// The encoded call.
+    pub calldata: Bytes,
+    /// The nonce reserved for the entry once its first transaction is signed.
+    pub nonce: Option<u64>,
+    /// The gas limit of the first transaction, reused by its replacements.
+    pub gas_limit: Option<u64>,
+    /// The progress of the entry.
+    pub status: OutboxStatus,
+    /// Hash of the transaction that settled the entry.
+    pub settled_tx_hash: Option<TxHash>,
+    /// Number of passes in which the node rejected the first transaction of the entry.
+    pub send_failures: u32,
+    /// The last rejection of the first transaction of the entry.
+    pub last_error: Option<String>,
+    /// Every transaction signed for the entry, oldest first.
+    pub attempts: Vec<TxAttempt>,
+}
+
+/// Sends the transactions queued in a persisted outbox and tracks them until they are confirmed.
+pub struct TxManager {
+    /// Ethereum provider whose wallet signs the transactions.
+    provider: Arc<ProviderInstance>,
+    /// Address of the wallet signing the transactions.
+    from: Address,
+    /// Connection to the SQLite outbox.
+    store: Mutex<Connection>,
+    /// Confirmation depth, fee bumping and polling settings.
+    config: TxManagerConfig,
+}
+
+impl TxManager {
+    /// Opens (or creates) the outbox at `path` and returns a `TxManager` sending its transactions
+    /// with `provider`.
+    ///
+    /// Entries left unsettled by a previous run are picked up on the next call to
+    /// [`TxManager::drive`].
+    pub fn open(
+        path: impl AsRef<Path>,
+        provider: Arc<ProviderInstance>,
+        config: TxManagerConfig,
+    ) -> Result<Self, Error> {
+        let conn = Connection::open(path).context(StoreSnafu)?;
+        conn.busy_timeout(BUSY_TIMEOUT).context(StoreSnafu)?;
+        conn.execute_batch(SCHEMA).context(StoreSnafu)?;
+        add_missing_columns(&conn).context(StoreSnafu)?;
+        let from = provider.default_signer_address();
+
+        Ok(Self {
+            provider,
+            from,
+            store: Mutex::new(conn),
+            config,
+        })
+    }
+
+    /// Queues a call to `target` fulfilling the unbonding of `staker` in `attested_block`.
+    ///
+    /// Queuing the same attested block and staker again is a no-op, so blocks reprocessed after
+    /// a restart do not send their unbondings twice.
+    pub async fn enqueue(
+        &self,
+        attested_block: u32,
+        staker: Address,
+        target: Address,
+        calldata: Bytes,
+    ) -> Result<(), Error> {
+        let conn = self.store.lock().await;
+        let queued = conn
+            .execute(
+                "INSERT OR IGNORE INTO outbox (attested_block, staker, target, calldata, status)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    attested_block,
+                    staker.to_string(),
+                    target.to_string(),
+                    calldata.to_vec(),
+                    OutboxStatus::Pending.as_str()
+                ],
+            )
+            .context(StoreSnafu)?;
+
+        if queued == 0 {
+            info!(
+                "Unbonding of {} in attested block {} is already in the outbox",
+                staker, attested_block
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Returns the entries queued for `attested_block`.
+    pub async fn entries(&self, attested_block: u32) -> Result<Vec<OutboxEntry>, Error> {
+        let conn = self.store.lock().await;
+        load_entries(
+            &conn,
+            &format!("SELECT {ENTRY_COLUMNS} FROM outbox WHERE attested_block = ?1 ORDER BY id"),
+            params![attested_block],
+        )
+    }
+
+    /// Returns whether every entry queued for `attested_block` has settled.
+    pub async fn is_settled(&self, attested_block: u32) -> Result<bool, Error> {
+        let conn = self.store.lock().await;
+        let unsettled: u32 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM outbox WHERE attested_block = ?1 AND status IN (?2, ?3)",
+                params![
+                    attested_block,
+                    OutboxStatus::Pending.as_str(),
+                    OutboxStatus::Sent.as_str()
+                ],
+                |row| row.get(0),
+            )
+            .context(StoreSnafu)?;
+
+        Ok(unsettled == 0)
+    }
+
+    /// Makes one pass over the unsettled entries, in the order they were queued.
+    ///
+    /// Pending entries get their first transaction. Sent entries are settled once a transaction
+    /// reaches the confirmation depth, replaced with higher fees once they have been unmined for
+    /// too long, and rebroadcast if the node no longer knows them.
+    ///
+    /// Errors driving an entry are logged and retried on the next pass, without holding up the
+    /// other entries. A pending entry fails once the node has rejected its first transaction
+    /// `max_send_failures` times.
+    pub async fn drive(&self) -> Result<(), Error> {
+        let entries = {
+            let conn = self.store.lock().await;
+            load_entries(
+                &conn,
+                &format!("SELECT {ENTRY_COLUMNS} FROM outbox WHERE status IN (?1, ?2) ORDER BY id"),
+                params![OutboxStatus::Pending.as_str(), OutboxStatus::Sent.as_str()],
+            )?
+        };
+
+        if entries.is_empty() {
+            return Ok(());
+        }
+
+        let head = self
+            .provider
+            .get_block_number()
+            .await
+            .context(TransportSnafu)?;
+
+        for entry in entries.iter() {
+            let result = match entry.status {
+                OutboxStatus::Pending => self.send(entry).await,
+                _ => self.advance(entry, head).await,
+            };
+            let Err(e) = result else {
+                continue;
+            };
+
+            warn!(
+                "Failed to drive unbonding of {} in attested block {}: {}",
+                entry.staker, entry.attested_block, e
+            );
+            if entry.status == OutboxStatus::Pending && e.is_rejection() {
+                self.record_send_failure(entry, &e).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Drives the outbox until every entry queued for `attested_block` has settled.
+    ///
+    /// Errors while driving are logged and retried on the next pass, since the attested block
+    /// must not be marked forwarded before its transactions are confirmed. Fails with
+    /// [`Error::EntryFailed`] if an entry of the block failed, as its unbonding was never sent.
+    pub async fn wait_until_settled(&self, attested_block: u32) -> Result<(), Error> {
+        loop {
+            if let Err(e) = self.drive().await {
+                warn!(
+                    "Failed to drive the outbox for attested block {}: {}",
+                    attested_block, e
+                );
+            }
+
+            if self.is_settled(attested_block).await? {
+                let entries = self.entries(attested_block).await?;
+                return match entries
+                    .into_iter()
+                    .find(|entry| entry.status == OutboxStatus::Failed)
+                {
+                    Some(entry) => Err(Error::EntryFailed {
+                        attested_block,
+                        staker: entry.staker,
+                        reason: entry.last_error.unwrap_or_default(),
+                    }),
+                    None => Ok(()),
+                };
+            }
+
+            sleep(self.config.poll_interval).await;
+        }
+    }
+
+    /// Signs and broadcasts the first transaction of a pending entry.
+    async fn send(&self, entry: &OutboxEntry) -> Result<(), Error> {
+        let nonce = self.next_nonce().await?;
+        let (max_fee_per_gas, max_priority_fee_per_gas) = self.current_fees().await?;
+        let (attempt, gas_limit) = self
+            .sign(
+                entry,
+                nonce,
+                None,
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+            )
+            .await?;
+        self.record_attempt(entry, nonce, gas_limit, &attempt)
+            .await?;
+
+        info!(
+            "Sending tx {} with nonce {} for unbonding of {} in attested block {}",
+            attempt.tx_hash, nonce, entry.staker, entry.attested_block
+        );
+        self.broadcast(&attempt).await;
+
+        Ok(())
+    }
+
+    /// Settles, replaces or rebroadcasts the transactions of a sent entry.
+    async fn advance(&self, entry: &OutboxEntry, head: u64) -> Result<(), Error> {
+        // The latest attempt is the most likely to be mined, but any of them can be.
+        for attempt in entry.attempts.iter().rev() {
+            let Some(receipt) = self
+                .provider
+                .get_transaction_receipt(attempt.tx_hash)
+                .await
+                .context(TransportSnafu)?
+            else {
+                continue;
+            };
+            let Some(mined_at) = receipt.block_number() else {
+                continue;
+            };
+
+            if head.saturating_sub(mined_at) + 1 >= self.config.confirmations.max(1) {
+                let status = if receipt.status() {
+                    OutboxStatus::Confirmed
+                } else {
+                    error!(
+                        "Tx {} for unbonding of {} in attested block {} reverted",
+                        attempt.tx_hash, entry.staker, entry.attested_block
+                    );
+                    OutboxStatus::Reverted
+                };
+                self.settle(entry, status, attempt.tx_hash, mined_at)
+                    .await?;
+            }
+
+            return Ok(());
+        }
+
+        let (Some(nonce), Some(latest)) = (entry.nonce, entry.attempts.last()) else {
+            return Err(Error::MissingAttempt { id: entry.id });
+        };
+
+        if unix_now() - latest.sent_at >= self.config.replace_after.as_secs() as i64 {
+            self.replace(entry, nonce, latest).await
+        } else {
+            let known = self
+                .provider
+                .get_transaction_by_hash(latest.tx_hash)
+                .await
+                .context(TransportSnafu)?;
+            // Dropped from the node's pool, or signed but not broadcast before a restart.
+            if known.is_none() {
+                self.broadcast(latest).await;
+            }
+            Ok(())
+        }
+    }
+
+    /// Replaces the latest transaction of an entry with one paying bumped fees.
+    async fn replace(
+        &self,
+        entry: &OutboxEntry,
+        nonce: u64,
+        latest: &TxAttempt,
+    ) -> Result<(), Error> {
+        let (current_max_fee, current_priority_fee) = self.current_fees().await?;
+        let max_priority_fee_per_gas = bump_fee(
+            latest.max_priority_fee_per_gas,
+            self.config.fee_bump_percent,
+        )
+        .max(current_priority_fee);
+        let max_fee_per_gas = bump_fee(latest.max_fee_per_gas, self.config.fee_bump_percent)
+            .max(current_max_fee)
+            .max(max_priority_fee_per_gas);
+
+        let (attempt, gas_limit) = self
+            .sign(
+                entry,
+                nonce,
+                entry.gas_limit,
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+            )
+            .await?;
+        self.record_attempt(entry, nonce, gas_limit, &attempt)
+            .await?;
+
+        info!(
+            "Replacing tx {} with {} (max fee {} -> {}) for unbonding of {} in attested block {}",
+            latest.tx_hash,
+            attempt.tx_hash,
+            latest.max_fee_per_gas,
+            max_fee_per_gas,
+            entry.staker,
+            entry.attested_block
+        );
+        self.broadcast(&attempt).await;
+
+        Ok(())
+    }
+
+    /// Signs the call of an entry at the given nonce and fees.
+    ///
+    /// The gas limit is estimated when none is given. Returns the signed attempt and the gas limit
+    /// it was signed with.
+    async fn sign(
+        &self,
+        entry: &OutboxEntry,
+        nonce: u64,
+        gas_limit: Option<u64>,
+        max_fee_per_gas: u128,
+        max_priority_fee_per_gas: u128,
+    ) -> Result<(TxAttempt, u64), Error> {
+        let mut request = TransactionRequest::default()
+            .with_from(self.from)
+            .with_to(entry.target)
+            .with_input(entry.calldata.clone())
+            .with_nonce(nonce)
+            .with_max_fee_per_gas(max_fee_per_gas)
+            .with_max_priority_fee_per_gas(max_priority_fee_per_gas);
+        if let Some(gas_limit) = gas_limit {
+            request.set_gas_limit(gas_limit);
+        }
+
+        let SendableTx::Envelope(envelope) =
+            self.provider.fill(request).await.context(TransportSnafu)?
+        else {
+            return Err(Error::UnsignedTransaction);
+        };
+
+        Ok((
+            TxAttempt {
+                tx_hash: *envelope.tx_hash(),
+                raw_tx: envelope.encoded_2718().into(),
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+                sent_at: unix_now(),
+            },
+            envelope.gas_limit(),
+        ))
+    }
+
+    /// Broadcasts a signed transaction.
+    ///
+    /// Failures are only logged: the transaction is stored, so it is rebroadcast or replaced on a
+    /// later pass.
+    async fn broadcast(&self, attempt: &TxAttempt) {
+        if let Err(e) = self.provider.send_raw_transaction(&attempt.raw_tx).await {
+            warn!("Failed to broadcast tx {}: {}", attempt.tx_hash, e);
+        }
+    }
+
+    /// Returns the nonce for the next entry.
+    ///
+    /// Nonces already reserved by the outbox are skipped, even if their transactions are not in
+    /// the node's pool.
+    async fn next_nonce(&self) -> Result<u64, Error> {
+        let pending = self
+            .provider
+            .get_transaction_count(self.from)
+            .pending()
+            .await
+            .context(TransportSnafu)?;
+
+        let conn = self.store.lock().await;
+        let reserved: Option<u64> = conn
+            .query_row("SELECT MAX(nonce) FROM outbox", [], |row| row.get(0))
+            .context(StoreSnafu)?;
+
+        Ok(reserved.map_or(pending, |nonce| pending.max(nonce + 1)))
+    }
+
+    /// Returns the `(max_fee_per_gas, max_priority_fee_per_gas)` a new transaction pays.
+    ///
+    /// The max fee allows for the base fee doubling before the transaction has to be replaced.
+    async fn current_fees(&self) -> Result<(u128, u128), Error> {
+        let gas_price = self
+            .provider
+            .get_gas_price()
+            .await
+            .context(TransportSnafu)?;
+        let max_priority_fee_per_gas = self
+            .provider
+            .get_max_priority_fee_per_gas()
+            .await
+            .context(TransportSnafu)?;
+
+        Ok((
+            gas_price
+                .saturating_mul(2)
+                .saturating_add(max_priority_fee_per_gas),
+            max_priority_fee_per_gas,
+        ))
+    }
+
+    /// Stores a signed attempt and reserves its nonce, before it is broadcast.
+    async fn record_attempt(
+        &self,
+        entry: &OutboxEntry,
+        nonce: u64,
+        gas_limit: u64,
+        attempt: &TxAttempt,
+    ) -> Result<(), Error> {
+        let mut conn = self.store.lock().await;
+        let tx = conn.transaction().context(StoreSnafu)?;
+        tx.execute(
+            "UPDATE outbox SET nonce = ?1, gas_limit = ?2, status = ?3 WHERE id = ?4",
+            params![nonce, gas_limit, OutboxStatus::Sent.as_str(), entry.id],
+        )
+        .context(StoreSnafu)?;
+        tx.execute(
+            "INSERT INTO outbox_attempt
+                (entry_id, tx_hash, raw_tx, max_fee_per_gas, max_priority_fee_per_gas, sent_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                entry.id,
+                attempt.tx_hash.to_string(),
+                attempt.raw_tx.to_vec(),
+                attempt.max_fee_per_gas.to_string(),
+                attempt.max_priority_fee_per_gas.to_string(),
+                attempt.sent_at
+            ],
+        )
+        .context(StoreSnafu)?;
+        tx.commit().context(StoreSnafu)
+    }
+
+    /// Records that the node rejected the first transaction of a pending entry, failing the
+    /// entry once this happened `max_send_failures` times.
+    async fn record_send_failure(&self, entry: &OutboxEntry, error: &Error) -> Result<(), Error> {
+        let send_failures = entry.send_failures + 1;
+        let status = if send_failures >= self.config.max_send_failures.max(1) {
+            error!(
+                "Unbonding of {} in attested block {} failed after {} rejected sends: {}",
+                entry.staker, entry.attested_block, send_failures, error
+            );
+            OutboxStatus::Failed
+        } else {
+            OutboxStatus::Pending
+        };
+
+        let conn = self.store.lock().await;
+        conn.execute(
+            "UPDATE outbox SET send_failures = ?1, last_error = ?2, status = ?3 WHERE id = ?4",
+            params![send_failures, error.to_string(), status.as_str(), entry.id],
+        )
+        .context(StoreSnafu)?;
+
+        Ok(())
+    }
+
+    /// Marks an entry settled by the transaction mined in `block_number`.
+    async fn settle(
+        &self,
+        entry: &OutboxEntry,
+        status: OutboxStatus,
+        tx_hash: TxHash,
+        block_number: u64,
+    ) -> Result<(), Error> {
+        let conn = self.store.lock().await;
+        conn.execute(
+            "UPDATE outbox SET status = ?1, settled_tx_hash = ?2, settled_block = ?3 WHERE id = ?4",
+            params![status.as_str(), tx_hash.to_string(), block_number, entry.id],
+        )
+        .context(StoreSnafu)?;
+
+        info!(
+            "Tx {} for unbonding of {} in attested block {} settled in block {}",
+            tx_hash, entry.staker, entry.attested_block, block_number
+        );
+
+        Ok(())
+    }
+}
+
+/// Loads the entries selected by `query`, which selects [`ENTRY_COLUMNS`], with their attempts.
+fn load_entries(
+    conn: &Connection,
+    query: &str,
+    query_params: impl rusqlite::Params,
+) -> Result<Vec<OutboxEntry>, Error> {
+    let mut entries = conn
+        .prepare(query)
+        .context(StoreSnafu)?
+        .query_map(query_params, |row| {
+            let status: String = row.get(7)?;
+            let settled_tx_hash: Option<String> = row.get(8)?;
+            Ok(OutboxEntry {
+                id: row.get(0)?,
+                attested_block: row.get(1)?,
+                staker: parse_column(row, 2)?,
+                target: parse_column(row, 3)?,
+                calldata: row.get::<_, Vec<u8>>(4)?.into(),
+                nonce: row.get(5)?,
+                gas_limit: row.get(6)?,
+                status: OutboxStatus::from_stored(&status).ok_or_else(|| {
+                    rusqlite::Error::FromSqlConversionFailure(
+                        7,
+                        Type::Text,
+                        format!("unknown outbox status {status}").into(),
+                    )
+                })?,
+                settled_tx_hash: settled_tx_hash
+                    .map(|hash| parse_text(8, &hash))
+                    .transpose()?,
+                send_failures: row.get(9)?,
+                last_error: row.get(10)?,
+                attempts: Vec::new(),
+            })
+        })
+        .context(StoreSnafu)?
+        .collect::<Result<Vec<_>, _>>()
+        .context(StoreSnafu)?;
+
+    let mut attempts = conn
+        .prepare(
+            "SELECT tx_hash, raw_tx, max_fee_per_gas, max_priority_fee_per_gas, sent_at
+             FROM outbox_attempt WHERE entry_id = ?1 ORDER BY id",
+        )
+        .context(StoreSnafu)?;
+    for entry in entries.iter_mut() {
+        entry.attempts = attempts
+            .query_map(params![entry.id], |row| {
+                Ok(TxAttempt {
+                    tx_hash: parse_column(row, 0)?,
+                    raw_tx: row.get::<_, Vec<u8>>(1)?.into(),
+                    max_fee_per_gas: parse_column(row, 2)?,
+                    max_priority_fee_per_gas: parse_column(row, 3)?,
+                    sent_at: row.get(4)?,
+                })
+            })
+            .context(StoreSnafu)?
+            .collect::<Result<Vec<_>, _>>()
+            .context(StoreSnafu)?;
+    }
+
+    Ok(entries)
+}
+
+/// Adds the [`ADDED_COLUMNS`] missing from an outbox created by an earlier release.
+fn add_missing_columns(conn: &Connection) -> rusqlite::Result<()> {
+    for (name, definition) in ADDED_COLUMNS {
+        let exists: bool = conn.query_row(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info('outbox') WHERE name = ?1",
+            params![name],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            conn.execute_batch(&format!(
+                "ALTER TABLE outbox ADD COLUMN {name} {definition}"
+            ))?;
+        }
+    }
+    Ok(())
+}
+
+/// Parses the text column at `idx` of `row`.
+fn parse_column<T>(row: &Row, idx: usize) -> rusqlite::Result<T>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    parse_text(idx, &row.get::<_, String>(idx)?)
+}
+
+/// Parses `text` read from the column at `idx`.
+fn parse_text<T>(idx: usize, text: &str) -> rusqlite::Result<T>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    text.parse()
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(idx, Type::Text, Box::new(e)))
+}
+
+/// Raises `fee` by `percent`, rounding up so replacements always clear the node's minimum bump.
+fn bump_fee(fee: u128, percent: u128) -> u128 {
+    fee.saturating_mul(100 + percent).div_ceil(100)
+}
+
+/// Current unix time in seconds.
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|since_epoch| since_epoch.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+/// Defines possible errors encountered while sending and tracking outbox transactions.
+#[derive(Debug, Snafu)]
+pub enum Error {
+    /// Error reading or writing the outbox.
+    #[snafu(display("Outbox store error: {source}"))]
+    Store {
+        /// The source SQLite error.
+        source: rusqlite::Error,
+    },
+
+    /// Error talking to the Ethereum node.
+    #[snafu(display("Ethereum transport error: {source}"))]
+    Transport {
+        /// The source transport error.
+        source: TransportError,
+    },
+
+    /// The provider filled a transaction without signing it.
+    ///
+    /// - **Cause:** The provider was built without a wallet.
+    /// - **Solution:** Build the provider with the forwarder's Ethereum wallet.
+    #[snafu(display("The provider did not sign the outbox transaction"))]
+    UnsignedTransaction,
+
+    /// A sent outbox entry has no stored transaction.
+    #[snafu(display("Outbox entry {id} was sent but has no stored transaction"))]
+    MissingAttempt {
+        /// Row id of the entry.
+        id: i64,
+    },
+
+    /// An outbox entry failed, so its attested block must not be marked forwarded.
+    ///
+    /// - **Cause:** The node rejected the first transaction of the entry `max_send_failures`
+    ///   times, e.g. because gas estimation reverted.
+    /// - **Solution:** Fix the cause of `reason`, then set the entry back to `pending` in the
+    ///   outbox.
+    #[snafu(display("Unbonding of {staker} in attested block {attested_block} failed: {reason}"))]
+    EntryFailed {
+        /// The attested block the entry was queued for.
+        attested_block: u32,
+        /// The staker whose unbonding the entry fulfills.
+        staker: Address,
+        /// The last rejection of the entry's transaction.
+        reason: String,
+    },
+}
+
+impl Error {
+    /// Returns whether the node answered the request with an error, e.g. because gas estimation
+    /// reverted, rather than being unreachable. Such errors are likely to happen again.
+    fn is_rejection(&self) -> bool {
+        match self {
+            Error::Transport { source } => source.is_error_resp(),
+            Error::UnsignedTransaction => true,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use alloy::network::EthereumWallet;
+    use alloy::node_bindings::{Anvil, AnvilInstance};
+    use alloy::providers::ProviderBuilder;
+    use alloy::signers::local::PrivateKeySigner;
+
+    use super::*;
+
+    const RECIPIENT: Address = Address::repeat_byte(0x42);
+
+    /// Spawns an anvil that only mines blocks on request.
+    fn spawn_anvil() -> (AnvilInstance, Arc<ProviderInstance>) {
+        let anvil = Anvil::new().arg("--no-mining").spawn();
+        let signer = PrivateKeySigner::from(anvil.keys()[0].clone());
+        let provider = ProviderBuilder::new()
+            .wallet(EthereumWallet::from(signer))
+            .on_http(anvil.endpoint_url());
+        (anvil, Arc::new(provider))
+    }
+
+    fn outbox_path(name: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("event-forwarder-{name}-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    fn config(confirmations: u64, replace_after: Duration) -> TxManagerConfig {
+        TxManagerConfig {
+            confirmations,
+            fee_bump_percent: 15,
+            replace_after,
+            poll_interval: Duration::from_millis(10),
+            max_send_failures: 2,
+        }
+    }
+
+    async fn mine(provider: &ProviderInstance, blocks: usize) {
+        for _ in 0..blocks {
+            provider
+                .raw_request::<_, String>("evm_mine".into(), ())
+                .await
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a local anvil binary"]
+    async fn entries_settle_at_the_confirmation_depth() {
+        let (_anvil, provider) = spawn_anvil();
+        let manager = TxManager::open(
+            outbox_path("depth"),
+            provider.clone(),
+            config(3, Duration::from_secs(600)),
+        )
+        .unwrap();
+
+        manager
+            .enqueue(7, RECIPIENT, RECIPIENT, Bytes::new())
+            .await
+            .unwrap();
+        manager.drive().await.unwrap();
+        assert_eq!(
+            manager.entries(7).await.unwrap()[0].status,
+            OutboxStatus::Sent
+        );
+
+        mine(&provider, 1).await;
+        manager.drive().await.unwrap();
+        assert!(!manager.is_settled(7).await.unwrap());
+
+        mine(&provider, 2).await;
+        manager.drive().await.unwrap();
+        let entry = &manager.entries(7).await.unwrap()[0];
+        assert_eq!(entry.status, OutboxStatus::Confirmed);
+        assert_eq!(entry.settled_tx_hash, Some(entry.attempts[0].tx_hash));
+        assert!(manager.is_settled(7).await.unwrap());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a local anvil binary"]
+    async fn unmined_transactions_are_replaced_with_bumped_fees() {
+        let (_anvil, provider) = spawn_anvil();
+        let manager = TxManager::open(
+            outbox_path("bump"),
+            provider.clone(),
+            config(1, Duration::ZERO),
+        )
+        .unwrap();
+
+        manager
+            .enqueue(7, RECIPIENT, RECIPIENT, Bytes::new())
+            .await
+            .unwrap();
+        manager.drive().await.unwrap();
+        manager.drive().await.unwrap();
+
+        let entry = &manager.entries(7).await.unwrap()[0];
+        let [first, replacement] = entry.attempts.as_slice() else {
+            panic!("expected one replacement, got {:?}", entry.attempts);
+        };
+        assert_ne!(first.tx_hash, replacement.tx_hash);
+        assert!(replacement.max_fee_per_gas >= bump_fee(first.max_fee_per_gas, 15));
+        assert!(
+            replacement.max_priority_fee_per_gas >= bump_fee(first.max_priority_fee_per_gas, 15)
+        );
+
+        mine(&provider, 1).await;
+        manager.wait_until_settled(7).await.unwrap();
+        let entry = &manager.entries(7).await.unwrap()[0];
+        assert_eq!(entry.status, OutboxStatus::Confirmed);
+        assert_eq!(entry.settled_tx_hash, Some(replacement.tx_hash));
+        assert_eq!(entry.nonce, Some(0));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a local anvil binary"]
+    async fn unsettled_entries_resume_after_a_restart() {
+        let (_anvil, provider) = spawn_anvil();
+        let path = outbox_path("restart");
+
+        let manager =
+            TxManager::open(&path, provider.clone(), config(1, Duration::from_secs(600))).unwrap();
+        manager
+            .enqueue(7, RECIPIENT, RECIPIENT, Bytes::new())
+            .await
+            .unwrap();
+        manager.drive().await.unwrap();
+        let sent = manager.entries(7).await.unwrap();
+        drop(manager);
+
+        let manager =
+            TxManager::open(&path, provider.clone(), config(1, Duration::from_secs(600))).unwrap();
+        // Reprocessing the attested block does not queue the unbonding again.
+        manager
+            .enqueue(7, RECIPIENT, RECIPIENT, Bytes::new())
+            .await
+            .unwrap();
+        assert_eq!(manager.entries(7).await.unwrap(), sent);
+
+        mine(&provider, 1).await;
+        manager.wait_until_settled(7).await.unwrap();
+        let entry = &manager.entries(7).await.unwrap()[0];
+        assert_eq!(entry.status, OutboxStatus::Confirmed);
+        assert_eq!(entry.attempts, sent[0].attempts);
+
+        // The next entry does not reuse the nonce reserved before the restart.
+        manager
+            .enqueue(8, RECIPIENT, RECIPIENT, Bytes::new())
+            .await
+            .unwrap();
+        manager.drive().await.unwrap();
+        assert_eq!(manager.entries(8).await.unwrap()[0].nonce, Some(1));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a local anvil binary"]
+    async fn rejected_entries_fail_without_holding_up_the_others() {
+        let (_anvil, provider) = spawn_anvil();
+        let manager = TxManager::open(
+            outbox_path("rejected"),
+            provider.clone(),
+            config(1, Duration::from_secs(600)),
+        )
+        .unwrap();
+
+        // PUSH1 0 PUSH1 0 REVERT, so gas estimation of any call reverts
+        let reverter = Address::repeat_byte(0x66);
+        provider
+            .raw_request::<_, ()>(
+                "anvil_setCode".into(),
+                (
+                    reverter,
+                    Bytes::from_static(&[0x60, 0x00, 0x60, 0x00, 0xfd]),
+                ),
+            )
+            .await
+            .unwrap();
+
+        let staker = Address::repeat_byte(0x01);
+        manager
+            .enqueue(7, staker, reverter, Bytes::new())
+            .await
+            .unwrap();
+        manager
+            .enqueue(7, RECIPIENT, RECIPIENT, Bytes::new())
+            .await
+            .unwrap();
+
+        manager.drive().await.unwrap();
+        let entries = manager.entries(7).await.unwrap();
+        assert_eq!(entries[0].status, OutboxStatus::Pending);
+        assert_eq!(entries[0].send_failures, 1);
+        assert!(entries[0].last_error.is_some());
+        assert_eq!(entries[1].status, OutboxStatus::Sent);
+
+        manager.drive().await.unwrap();
+        assert_eq!(
+            manager.entries(7).await.unwrap()[0].status,
+            OutboxStatus::Failed
+        );
+
+        mine(&provider, 1).await;
+        let result = manager.wait_until_settled(7).await;
+        assert!(
+            matches!(result, Err(Error::EntryFailed { staker: failed, .. }) if failed == staker),
+            "{result:?}"
+        );
+        assert_eq!(
+            manager.entries(7).await.unwrap()[1].status,
+            OutboxStatus::Confirmed
+        );
+    }
+}
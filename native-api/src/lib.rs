@@ -2,8 +2,14 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use proof_of_sql_commitment_map::TableCommitmentBytesPerCommitmentSchemePassBy;
-use sxt_core::native::{NativeCommitmentError, NativeError, OnChainTableBytes, RowData};
-use sxt_core::tables::TableIdentifier;
+use sxt_core::native::{
+    NativeCommitmentError,
+    NativeError,
+    OnChainTableBytes,
+    PrimaryKeysPassBy,
+    RowData,
+};
+use sxt_core::tables::{PrimaryKeys, TableIdentifier};
 
 /// The native api that our pallets can adhere to.
 /// The inputs and output to these types need to implement the `PassByCode` trait.
@@ -13,6 +19,8 @@ pub trait NativeApi: 'static {
 
     /// Process insert to support commitment metadata.
     ///
+    /// Fails if multiple rows share a value for every column of the `primary_key`.
+    ///
     /// Returns..
     /// - the processed insert data with comitment metadata
     /// - the updated commitments for the table
@@ -20,6 +28,7 @@ pub trait NativeApi: 'static {
         table_identifier: TableIdentifier,
         insert_data_bytes: OnChainTableBytes,
         previous_commitments_bytes: TableCommitmentBytesPerCommitmentSchemePassBy,
+        primary_key: PrimaryKeys,
     ) -> Result<
         (
             OnChainTableBytes,
@@ -39,6 +48,7 @@ impl NativeApi for () {
         _table_identifier: TableIdentifier,
         _insert_data_bytes: OnChainTableBytes,
         _previous_commitments_bytes: TableCommitmentBytesPerCommitmentSchemePassBy,
+        _primary_key: PrimaryKeys,
     ) -> Result<
         (
             OnChainTableBytes,
@@ -62,6 +72,7 @@ impl NativeApi for Api {
         table_identifier: TableIdentifier,
         insert_data_bytes: OnChainTableBytes,
         previous_commitments_bytes: TableCommitmentBytesPerCommitmentSchemePassBy,
+        primary_key: PrimaryKeys,
     ) -> Result<
         (
             OnChainTableBytes,
@@ -73,6 +84,7 @@ impl NativeApi for Api {
             table_identifier,
             insert_data_bytes,
             previous_commitments_bytes,
+            PrimaryKeysPassBy { primary_key },
         )
     }
 }
@@ -11,11 +11,14 @@ use proof_of_sql_commitment_map::{
 #[cfg(feature = "std")]
 use proof_of_sql_static_setups::io::PUBLIC_SETUPS;
 use sp_runtime_interface::runtime_interface;
+#[cfg(feature = "std")]
+use sqlparser::ast::Ident;
 use sxt_core::native::{
     CreateStatementPassBy,
     NativeCommitmentError,
     NativeError,
     OnChainTableBytes,
+    PrimaryKeysPassBy,
     RowData,
 };
 use sxt_core::tables::TableIdentifier;
@@ -98,37 +101,92 @@ pub trait Interface {
         ),
         NativeCommitmentError,
     > {
-        let insert_data = on_chain_table::OnChainTable::try_from(insert_data_bytes)
-            .map_err(|_| NativeCommitmentError::TableDeserialization)?;
+        process_insert_with_primary_key(
+            table_identifier,
+            insert_data_bytes,
+            previous_commitments_bytes,
+            &[],
+        )
+    }
+
+    /// Process insert to support commitment metadata, rejecting rows that share a primary key.
+    ///
+    /// Returns..
+    /// - the processed insert data with comitment metadata
+    /// - the updated commitments for the table
+    #[version(2)]
+    fn process_insert(
+        table_identifier: TableIdentifier,
+        insert_data_bytes: OnChainTableBytes,
+        previous_commitments_bytes: TableCommitmentBytesPerCommitmentSchemePassBy,
+        primary_key: PrimaryKeysPassBy,
+    ) -> Result<
+        (
+            OnChainTableBytes,
+            TableCommitmentBytesPerCommitmentSchemePassBy,
+        ),
+        NativeCommitmentError,
+    > {
+        let primary_key = primary_key
+            .primary_key
+            .iter()
+            .map(|column| Ident::new(String::from_utf8_lossy(column)))
+            .collect::<Vec<_>>();
+
+        process_insert_with_primary_key(
+            table_identifier,
+            insert_data_bytes,
+            previous_commitments_bytes,
+            &primary_key,
+        )
+    }
+}
 
-        let previous_commitments = PerCommitmentScheme::try_from(previous_commitments_bytes.data)
-            .map_err(|_| NativeCommitmentError::CommitmentDeserialization)?;
+/// Shared implementation of the `process_insert` host function versions.
+#[cfg(feature = "std")]
+fn process_insert_with_primary_key(
+    table_identifier: TableIdentifier,
+    insert_data_bytes: OnChainTableBytes,
+    previous_commitments_bytes: TableCommitmentBytesPerCommitmentSchemePassBy,
+    primary_key: &[Ident],
+) -> Result<
+    (
+        OnChainTableBytes,
+        TableCommitmentBytesPerCommitmentSchemePassBy,
+    ),
+    NativeCommitmentError,
+> {
+    let insert_data = on_chain_table::OnChainTable::try_from(insert_data_bytes)
+        .map_err(|_| NativeCommitmentError::TableDeserialization)?;
 
-        let setups = PUBLIC_SETUPS
-            .get()
-            .expect("PUBLIC_SETUPS should be initialized before runtime interface calls");
+    let previous_commitments = PerCommitmentScheme::try_from(previous_commitments_bytes.data)
+        .map_err(|_| NativeCommitmentError::CommitmentDeserialization)?;
 
-        let (
-            InsertAndCommitmentMetadata {
-                insert_with_meta_columns,
-                ..
-            },
-            new_commitments,
-        ) = commitment_sql::process_insert(
-            &table_identifier,
-            insert_data,
-            previous_commitments,
-            *setups,
-        )?;
+    let setups = PUBLIC_SETUPS
+        .get()
+        .expect("PUBLIC_SETUPS should be initialized before runtime interface calls");
 
-        let table_bytes = insert_with_meta_columns.try_into()?;
+    let (
+        InsertAndCommitmentMetadata {
+            insert_with_meta_columns,
+            ..
+        },
+        new_commitments,
+    ) = commitment_sql::process_insert(
+        &table_identifier,
+        insert_data,
+        primary_key,
+        previous_commitments,
+        *setups,
+    )?;
 
-        let data = TableCommitmentBytesPerCommitmentScheme::try_from(new_commitments)?;
+    let table_bytes = insert_with_meta_columns.try_into()?;
 
-        let new_commitments_bytes = TableCommitmentBytesPerCommitmentSchemePassBy { data };
+    let data = TableCommitmentBytesPerCommitmentScheme::try_from(new_commitments)?;
 
-        Ok((table_bytes, new_commitments_bytes))
-    }
+    let new_commitments_bytes = TableCommitmentBytesPerCommitmentSchemePassBy { data };
+
+    Ok((table_bytes, new_commitments_bytes))
 }
 
 #[cfg(all(test, feature = "std"))]
@@ -186,6 +244,17 @@ mod tests {
         assert!(res.is_ok());
     }
 
+    fn primary_key(columns: &[&str]) -> PrimaryKeysPassBy {
+        PrimaryKeysPassBy {
+            primary_key: columns
+                .iter()
+                .map(|column| column.to_uppercase().into_bytes().try_into().unwrap())
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap(),
+        }
+    }
+
     fn sample_empty_and_populated_on_chain_table() -> (OnChainTable, OnChainTable) {
         let animals_col_id = Ident::new("animals");
         let animals_data = ["cow", "dog", "cat"].map(String::from);
@@ -244,9 +313,13 @@ mod tests {
             data: empty_commitments.clone().try_into().unwrap(),
         };
 
-        let (insert_with_meta_columns, new_commitments) =
-            interface::process_insert(table_id.clone(), insert_data_bytes, empty_commitments_bytes)
-                .unwrap();
+        let (insert_with_meta_columns, new_commitments) = interface::process_insert(
+            table_id.clone(),
+            insert_data_bytes,
+            empty_commitments_bytes,
+            primary_key(&["animals"]),
+        )
+        .unwrap();
 
         let (
             InsertAndCommitmentMetadata {
@@ -254,8 +327,14 @@ mod tests {
                 ..
             },
             expected_commitments,
-        ) = commitment_sql::process_insert(&table_id, insert_data, empty_commitments, *setups)
-            .unwrap();
+        ) = commitment_sql::process_insert(
+            &table_id,
+            insert_data,
+            &[Ident::new("ANIMALS")],
+            empty_commitments,
+            *setups,
+        )
+        .unwrap();
 
         assert_eq!(
             insert_with_meta_columns,
@@ -294,7 +373,12 @@ mod tests {
             },
         };
 
-        let result = interface::process_insert(table_id, insert_data_bytes, invalid_commitments);
+        let result = interface::process_insert(
+            table_id,
+            insert_data_bytes,
+            invalid_commitments,
+            primary_key(&[]),
+        );
 
         assert!(matches!(
             result,
@@ -319,8 +403,62 @@ mod tests {
             data: TableCommitmentBytesPerCommitmentScheme::from_iter([]),
         };
 
-        let result = interface::process_insert(table_id, insert_data_bytes, no_commitments);
+        let result = interface::process_insert(
+            table_id,
+            insert_data_bytes,
+            no_commitments,
+            primary_key(&[]),
+        );
 
         assert!(matches!(result, Err(NativeCommitmentError::NoCommitments)));
     }
+
+    #[test]
+    fn we_cannot_process_insert_with_duplicate_primary_keys() {
+        let setups = get_or_init_from_files_with_four_points_unchecked();
+
+        let table_id = TableIdentifier {
+            namespace: b"animal".to_vec().try_into().unwrap(),
+            name: b"population".to_vec().try_into().unwrap(),
+        };
+
+        let (empty_table, insert_data) = sample_empty_and_populated_on_chain_table();
+
+        let empty_commitments = setups
+            .into_iter()
+            .map(|any| {
+                any.map(OnChainTableToTableCommitmentFn::new(&empty_table, 0))
+                    .transpose_result()
+                    .unwrap()
+            })
+            .collect::<PerCommitmentScheme<OptionType<TableCommitmentType>>>();
+
+        let empty_commitments_bytes = TableCommitmentBytesPerCommitmentSchemePassBy {
+            data: empty_commitments.try_into().unwrap(),
+        };
+
+        let duplicated_insert_data =
+            OnChainTable::try_from_iter(insert_data.into_iter().map(|(identifier, column)| {
+                let column = match column {
+                    OnChainColumn::VarChar(animals) => {
+                        OnChainColumn::VarChar(vec![animals[0].clone(); animals.len()])
+                    }
+                    column => column,
+                };
+                (identifier, column)
+            }))
+            .unwrap();
+
+        let result = interface::process_insert(
+            table_id,
+            OnChainTableBytes::try_from(duplicated_insert_data).unwrap(),
+            empty_commitments_bytes,
+            primary_key(&["animals"]),
+        );
+
+        assert!(matches!(
+            result,
+            Err(NativeCommitmentError::DuplicatePrimaryKey)
+        ));
+    }
 }
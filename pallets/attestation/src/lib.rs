@@ -28,11 +28,13 @@ pub use weights::*;
 pub mod pallet {
     use frame_support::dispatch::DispatchResult;
     use frame_support::pallet_prelude::{OptionQuery, *};
+    use frame_support::sp_runtime::SaturatedConversion;
     use frame_support::Blake2_128Concat;
     use frame_system::pallet_prelude::*;
     use sxt_core::attestation::{create_attestation_message, Attestation, AttestationKey};
     use sxt_core::keystore::EthereumKey;
     use sxt_core::permissions::{AttestationPalletPermission, PermissionLevel};
+    use sxt_core::rewards::WorkRewardHandler;
 
     use crate::weights::WeightInfo;
 
@@ -51,6 +53,8 @@ pub mod pallet {
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
         /// Weight information for extrinsics.
         type WeightInfo: WeightInfo;
+        /// Rewards the attestors for timely attestations.
+        type WorkRewards: WorkRewardHandler<Self::AccountId>;
     }
 
     /// Events emitted by the attestation pallet.
@@ -149,7 +153,7 @@ pub mod pallet {
         /// * [`Error::MaxAttestationsForBlockError`]
         /// * [`Error::AttestationAlreadyRecordedError`]
         #[pallet::call_index(1)]
-        #[pallet::weight(<T as Config>::WeightInfo::attest_block())]
+        #[pallet::weight(<T as Config>::WeightInfo::attest_block().saturating_add(T::WorkRewards::note_attestation_weight()))]
         pub fn attest_block(
             origin: OriginFor<T>,
             block_number: BlockNumber,
@@ -182,6 +186,11 @@ pub mod pallet {
                     block_number,
                     ..
                 } => {
+                    ensure!(
+                        current_block > block_number.into(),
+                        Error::<T>::CannotAttestFutureBlock
+                    );
+
                     let proposed_key = EthereumKey {
                         pub_key: attestor_pub_key,
                         address20: address20.clone(),
@@ -210,6 +219,11 @@ pub mod pallet {
 
                     Attestations::<T>::insert(block_number, attestations_for_block);
 
+                    let blocks_late = current_block
+                        .saturated_into::<BlockNumber>()
+                        .saturating_sub(block_number);
+                    T::WorkRewards::note_attestation(&who, blocks_late);
+
                     Self::deposit_event(Event::<T>::BlockAttested {
                         block_number,
                         attestation,
@@ -24,6 +24,7 @@ impl frame_system::Config for Test {
 impl pallet_template::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = ();
+    type WorkRewards = ();
 }
 
 impl pallet_keystore::Config for Test {
@@ -13,7 +13,7 @@ use sxt_core::attestation::{
 use sxt_core::permissions::{AttestationPalletPermission, PermissionLevel, PermissionList};
 
 use crate::mock::*;
-use crate::{Error, Pallet};
+use crate::{Attestations, Error, Pallet};
 
 fn create_signed_message_and_keypair(account_id: u64) -> (SigningKey, [u8; 33], EthereumSignature) {
     // Generate a new keypair.
@@ -272,3 +272,31 @@ fn attest_block_fails_if_future_block() {
         );
     });
 }
+
+#[test]
+fn attest_block_fails_if_the_attestation_is_for_a_future_block() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(2);
+        let account_id: u64 = 1;
+
+        let (_, public_key, signature) = create_signed_message_and_keypair(account_id);
+        let address20 =
+            sxt_core::attestation::uncompressed_public_key_to_address(&public_key).unwrap();
+
+        // The attested block is in the future, though the block submitted is not
+        let attestation = Attestation::EthereumAttestation {
+            signature,
+            proposed_pub_key: public_key,
+            address20,
+            state_root: BoundedVec::new(),
+            block_number: 1000,
+            block_hash: H256::zero(),
+        };
+
+        assert_err!(
+            Pallet::<Test>::attest_block(RuntimeOrigin::signed(account_id), 1, attestation),
+            Error::<Test>::CannotAttestFutureBlock
+        );
+        assert!(Attestations::<Test>::get(1000).is_empty());
+    });
+}
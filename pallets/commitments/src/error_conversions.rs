@@ -99,6 +99,10 @@ impl<T> From<ProcessInsertError> for Error<T> {
                 Error::ExistingCommitmentsColumnOrderMismatch
             }
             ProcessInsertError::NoCommitments => Error::NoExistingCommitments,
+            ProcessInsertError::MissingPrimaryKeyColumn { .. } => {
+                Error::InsertDataMissingPrimaryKeyColumn
+            }
+            ProcessInsertError::DuplicatePrimaryKey => Error::InsertDataWithDuplicatePrimaryKey,
         }
     }
 }
@@ -127,6 +131,10 @@ impl<T> From<NativeCommitmentError> for Error<T> {
             NativeCommitmentError::NoCommitments => Error::NoExistingCommitments,
             NativeCommitmentError::CommitmentSerialization => Error::NativeSerializeCommitment,
             NativeCommitmentError::TableSerialization => Error::NativeSerializeInsertData,
+            NativeCommitmentError::MissingPrimaryKeyColumn => {
+                Error::InsertDataMissingPrimaryKeyColumn
+            }
+            NativeCommitmentError::DuplicatePrimaryKey => Error::InsertDataWithDuplicatePrimaryKey,
         }
     }
 }
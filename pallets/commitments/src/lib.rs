@@ -58,7 +58,7 @@ pub mod pallet {
     };
     use proof_of_sql_static_setups::baked::PUBLIC_SETUPS;
     use sqlparser::ast::helpers::stmt_create_table::CreateTableBuilder;
-    use sxt_core::tables::TableIdentifier;
+    use sxt_core::tables::{PrimaryKeys, TableIdentifier};
 
     use super::*;
 
@@ -172,6 +172,10 @@ pub mod pallet {
         InsertDataDoesntMatchExistingCommitments,
         /// Table identifier already exists in commitment storage.
         TableAlreadyExists,
+        /// Insert data is missing a primary key column.
+        InsertDataMissingPrimaryKeyColumn,
+        /// Insert data contains multiple rows with the same primary key.
+        InsertDataWithDuplicatePrimaryKey,
     }
 
     impl<T: Config> Pallet<T> {
@@ -274,10 +278,13 @@ pub mod pallet {
 
         /// Processes the insert and updates commitments for the table in storage.
         ///
+        /// Rejects inserts with multiple rows sharing the same `primary_key`, which may be empty.
+        ///
         /// Returns the original insert with additional commitment metadata columns.
         pub fn process_insert_and_update_commitments<I: NativeApi>(
             table: TableIdentifier,
             insert_data: OnChainTable,
+            primary_key: PrimaryKeys,
         ) -> Result<InsertAndCommitmentMetadata, Error<T>> {
             let mut handler = CommitmentStorageMapHandler::<CommitmentStorageMap<T>>::new();
 
@@ -287,8 +294,12 @@ pub mod pallet {
 
             let table_bytes = insert_data.try_into()?;
 
-            let (insert_with_meta_columns_bytes, commitments_bytes) =
-                I::process_insert(table.clone(), table_bytes, previous_commitments)?;
+            let (insert_with_meta_columns_bytes, commitments_bytes) = I::process_insert(
+                table.clone(),
+                table_bytes,
+                previous_commitments,
+                primary_key,
+            )?;
 
             let commitments_bytes = commitments_bytes.data;
 
@@ -16,7 +16,7 @@ use proof_of_sql_commitment_map::{
 use proof_of_sql_static_setups::io::PUBLIC_SETUPS;
 use sp_core::U256;
 use sqlparser::ast::Ident;
-use sxt_core::tables::TableIdentifier;
+use sxt_core::tables::{PrimaryKeys, TableIdentifier};
 
 use crate::mock::{new_test_ext, CommitmentsModule, Test};
 use crate::test_create_table::ProcessCreateTableTestParams;
@@ -26,6 +26,7 @@ use crate::Error;
 struct ProcessInsertTestParams {
     table_id: TableIdentifier,
     insert_data: OnChainTable,
+    primary_key: PrimaryKeys,
 }
 
 impl ProcessInsertTestParams {
@@ -53,9 +54,13 @@ impl ProcessInsertTestParams {
         ])
         .unwrap();
 
+        let primary_key =
+            PrimaryKeys::try_from(vec![b"ANIMAL".to_vec().try_into().unwrap()]).unwrap();
+
         ProcessInsertTestParams {
             table_id,
             insert_data,
+            primary_key,
         }
     }
 
@@ -63,6 +68,7 @@ impl ProcessInsertTestParams {
         CommitmentsModule::process_insert_and_update_commitments::<Api>(
             self.table_id,
             self.insert_data,
+            self.primary_key,
         )
     }
 }
@@ -94,6 +100,7 @@ fn we_can_process_insert() {
         let (expected_insert_and_commitment_metadata, expected_commitments) = process_insert(
             &test_params.table_id,
             test_params.insert_data.clone(),
+            &[Ident::new("ANIMAL")],
             empty_commitments,
             *PUBLIC_SETUPS.get().unwrap(),
         )
@@ -220,3 +227,28 @@ fn we_cannot_process_inserts_that_dont_match_table() {
         );
     });
 }
+
+#[test]
+fn we_cannot_process_inserts_with_duplicate_primary_keys() {
+    new_test_ext().execute_with(|| {
+        ProcessCreateTableTestParams::new_valid().execute().unwrap();
+
+        let mut insert_params = ProcessInsertTestParams::new_valid();
+        insert_params.insert_data = OnChainTable::try_from_iter([
+            (
+                Ident::new("animal"),
+                OnChainColumn::VarChar(vec!["cow".to_string(), "cow".to_string()]),
+            ),
+            (
+                Ident::new("population"),
+                OnChainColumn::BigInt(vec![100, 2]),
+            ),
+        ])
+        .unwrap();
+
+        assert_noop!(
+            insert_params.execute(),
+            Error::<Test>::InsertDataWithDuplicatePrimaryKey
+        );
+    });
+}
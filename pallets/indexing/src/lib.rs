@@ -4,6 +4,20 @@
 //! we have enough submissions to reach a quorum, and if we do, it will finalize the data and
 //! emit an event stating that the batch id has been decided on. The event also contains the
 //! final data for the decision.
+//!
+//! Batches that exceed [`DATA_MAX_LEN`] can be submitted in chunks. Indexers first reach quorum on
+//! a [`ChunkManifest`] via `submit_chunk_manifest`, then upload the chunks with `submit_chunk`.
+//! The commitments are only updated once every chunk is present, in manifest order. If the chunks
+//! are not all uploaded before `ChunkUploadTimeout` elapses, or they fail to be committed, the
+//! batch and its chunks are discarded and the batch id can be submitted to again.
+//!
+//! Data for several tables of the same source block can be submitted together via
+//! `submit_multi_table_data`, in which case all of the tables are committed to or none are.
+//!
+//! To save block space, indexers can also reach quorum on a data hash alone via `submit_data_hash`.
+//! The data is then published once via `publish_data` and checked against the agreed hash. If no
+//! one publishes before `PublicationTimeout` elapses, the quorum expires and the batch id can be
+//! submitted to again.
 
 // We make sure this pallet uses `no_std` for compiling to Wasm.
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -38,7 +52,7 @@ pub mod pallet {
     use alloc::vec::Vec;
 
     use codec::Decode;
-    use commitment_sql::InsertAndCommitmentMetadata;
+    use commitment_sql::{row_numbers_of_on_chain_table, InsertAndCommitmentMetadata};
     use frame_support::dispatch::RawOrigin;
     use frame_support::pallet_prelude::*;
     use frame_support::{Blake2_128, Blake2_128Concat};
@@ -47,11 +61,14 @@ pub mod pallet {
     use native_api::NativeApi;
     use on_chain_table::OnChainTable;
     use sp_core::{H256, U256};
-    use sp_runtime::traits::{Bounded, Hash, StaticLookup, UniqueSaturatedInto};
+    use sp_runtime::traits::{Bounded, Hash, Saturating, StaticLookup, UniqueSaturatedInto};
     use sp_runtime::{BoundedVec, SaturatedConversion};
     use sxt_core::permissions::{IndexingPalletPermission, PermissionLevel};
+    use sxt_core::rewards::WorkRewardHandler;
     use sxt_core::tables::{
         InsertQuorumSize,
+        PrimaryKeyConstraint,
+        PrimaryKeys,
         QuorumScope,
         TableIdentifier,
         TableName,
@@ -78,6 +95,14 @@ pub mod pallet {
             + IsType<<Self as frame_system::Config>::RuntimeEvent>;
         /// The weight info to be used with the extrinsics provided by the pallet
         type WeightInfo: WeightInfo;
+        /// How many blocks a hash-only quorum waits for its data to be published
+        #[pallet::constant]
+        type PublicationTimeout: Get<BlockNumberFor<Self>>;
+        /// How many blocks a chunked batch waits for all of its chunks to be uploaded
+        #[pallet::constant]
+        type ChunkUploadTimeout: Get<BlockNumberFor<Self>>;
+        /// Rewards the indexers agreeing in quorums
+        type WorkRewards: WorkRewardHandler<Self::AccountId>;
     }
 
     /// Double Map of Submissions using the batch-id as the first key and the submitter's
@@ -100,11 +125,67 @@ pub mod pallet {
     pub type FinalData<T: Config<I>, I: 'static = ()> =
         StorageMap<_, Blake2_128Concat, BatchId, DataQuorum<T::AccountId, T::Hash>>;
 
+    /// The quorum of every table of a multi-table batch, keyed by batch id and table.
+    ///
+    /// `FinalData` holds the quorum of a multi-table batch under the first table only.
+    #[pallet::storage]
+    #[pallet::getter(fn final_table_data)]
+    pub type FinalTableData<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        BatchId,
+        Blake2_128Concat,
+        TableIdentifier,
+        DataQuorum<T::AccountId, T::Hash>,
+    >;
+
     #[pallet::storage]
     #[pallet::getter(fn block_numbers)]
     pub type BlockNumbers<T: Config<I>, I: 'static = ()> =
         StorageMap<_, Blake2_128Concat, TableIdentifier, u64>;
 
+    /// Chunked batches whose manifest has reached quorum and that are awaiting chunk uploads.
+    #[pallet::storage]
+    #[pallet::getter(fn pending_chunked_batches)]
+    pub type PendingChunkedBatches<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Blake2_128Concat, BatchId, PendingChunkedBatch<T::AccountId, T::Hash>>;
+
+    /// Uploaded chunks of pending chunked batches, keyed by batch id and chunk index.
+    #[pallet::storage]
+    #[pallet::getter(fn chunks)]
+    pub type Chunks<T: Config<I>, I: 'static = ()> =
+        StorageDoubleMap<_, Blake2_128Concat, BatchId, Twox64Concat, ChunkIndex, RowData>;
+
+    /// Batch ids of pending chunked batches, keyed by the block at which they expire.
+    #[pallet::storage]
+    pub type ChunkedBatchDeadlines<T: Config<I>, I: 'static = ()> = StorageMap<
+        _,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        BoundedVec<BatchId, ConstU32<MAX_PUBLICATIONS_PER_BLOCK>>,
+        ValueQuery,
+    >;
+
+    /// Hash-only quorums awaiting publication of their data.
+    #[pallet::storage]
+    #[pallet::getter(fn pending_publications)]
+    pub type PendingPublications<T: Config<I>, I: 'static = ()> = StorageMap<
+        _,
+        Blake2_128Concat,
+        BatchId,
+        PendingPublication<T::AccountId, T::Hash, BlockNumberFor<T>>,
+    >;
+
+    /// Batch ids of pending publications, keyed by the block at which they expire.
+    #[pallet::storage]
+    pub type PublicationDeadlines<T: Config<I>, I: 'static = ()> = StorageMap<
+        _,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        BoundedVec<BatchId, ConstU32<MAX_PUBLICATIONS_PER_BLOCK>>,
+        ValueQuery,
+    >;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config<I>, I: 'static = ()> {
@@ -154,6 +235,102 @@ pub mod pallet {
             /// Voters against this quorum
             dissents: BoundedBTreeSet<T::AccountId, ConstU32<MAX_SUBMITTERS>>,
         },
+
+        /// A quorum has been reached on a chunk manifest. Its chunks can now be uploaded.
+        ChunkManifestQuorumReached {
+            /// The quorum object representing the metadata about the decision
+            quorum: DataQuorum<T::AccountId, T::Hash>,
+            /// The number of chunks announced by the manifest
+            chunk_count: u32,
+        },
+
+        /// A chunk of a pending chunked batch has been uploaded.
+        ChunkSubmitted {
+            /// The account id of the uploader
+            who: T::AccountId,
+            /// The batch id of the chunked batch
+            batch_id: BatchId,
+            /// The position of the chunk in the manifest
+            chunk_index: ChunkIndex,
+        },
+
+        /// A multi-table submission has reached quorum and every table has been committed to.
+        ///
+        /// A `QuorumReached` event is emitted for each table beforehand, in submission order.
+        MultiTableQuorumReached {
+            /// The batch id of the multi-table submission
+            batch_id: BatchId,
+            /// The tables that were committed to
+            tables: BoundedVec<TableIdentifier, ConstU32<MAX_MULTI_TABLES>>,
+            /// The source block number the data was submitted for
+            block_number: u64,
+        },
+
+        /// A quorum has been reached on a data hash. The data can now be published.
+        DataHashQuorumReached {
+            /// The quorum object representing the metadata about the decision
+            quorum: DataQuorum<T::AccountId, T::Hash>,
+            /// The hash of the data that must be published
+            row_data_hash: T::Hash,
+            /// The block after which the quorum expires if the data has not been published
+            deadline: BlockNumberFor<T>,
+        },
+
+        /// The data for a hash-only quorum has been published and committed to.
+        ///
+        /// It is preceded by the usual `QuorumReached` or `QuorumEmptyBlock` event.
+        DataPublished {
+            /// The account id of the publisher
+            who: T::AccountId,
+            /// The batch id of the published data
+            batch_id: BatchId,
+        },
+
+        /// No data was published for a hash-only quorum before its deadline.
+        ///
+        /// The quorum is discarded and the batch id can be submitted to again.
+        PublicationExpired {
+            /// The table identifier
+            table: TableIdentifier,
+            /// The batch id of the expired quorum
+            batch_id: BatchId,
+        },
+
+        /// Every chunk of a chunked batch has been uploaded and committed to.
+        ///
+        /// A `QuorumReached` event is emitted for each chunk beforehand, in manifest order.
+        ChunkedBatchFinalized {
+            /// The table identifier
+            table: TableIdentifier,
+            /// The batch id of the chunked batch
+            batch_id: BatchId,
+            /// The number of chunks that were committed
+            chunk_count: u32,
+        },
+
+        /// Not every chunk of a chunked batch was uploaded before its deadline.
+        ///
+        /// The batch and its uploaded chunks are discarded and the batch id can be submitted to
+        /// again.
+        ChunkedBatchExpired {
+            /// The table identifier
+            table: TableIdentifier,
+            /// The batch id of the expired chunked batch
+            batch_id: BatchId,
+        },
+
+        /// Every chunk of a chunked batch was uploaded, but committing to them failed.
+        ///
+        /// None of the chunks are committed. The batch and its chunks are discarded and the
+        /// batch id can be submitted to again.
+        ChunkedBatchFailed {
+            /// The table identifier
+            table: TableIdentifier,
+            /// The batch id of the failed chunked batch
+            batch_id: BatchId,
+            /// The error that committing to the chunks failed with
+            error: DispatchError,
+        },
     }
 
     #[pallet::error]
@@ -194,6 +371,70 @@ pub mod pallet {
         TableDeserializationError,
         /// Error deserializing the table as an OnChainTable
         TableSerializationError,
+        /// There is no chunked batch awaiting chunks for this batch id
+        UnknownChunkedBatch,
+        /// The chunk index is out of range for the manifest
+        InvalidChunkIndex,
+        /// This chunk has already been uploaded
+        ChunkAlreadySubmitted,
+        /// The chunk does not match the hash agreed upon in the manifest
+        ChunkHashMismatch,
+        /// The same table appears more than once in a multi-table submission
+        DuplicateTable,
+        /// There is no hash-only quorum awaiting publication for this batch id
+        UnknownPendingPublication,
+        /// The published data does not match the hash agreed upon by the quorum
+        PublishedDataHashMismatch,
+        /// Too many hash-only quorums are already set to expire in the same block
+        TooManyPendingPublications,
+        /// Too many chunked batches are already set to expire in the same block
+        TooManyPendingChunkedBatches,
+        /// The batch inserts more than `MAX_KEYED_ROWS_PER_BATCH` rows into a table that
+        /// maintains a primary key index
+        TooManyKeyedRows,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+        fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+            let expiring = PublicationDeadlines::<T, I>::take(n);
+            let mut weight = T::DbWeight::get().reads_writes(1, 1);
+
+            for batch_id in expiring {
+                weight = weight.saturating_add(T::DbWeight::get().reads(1));
+
+                // Publications that have already been published are no longer pending
+                if let Some(pending) = PendingPublications::<T, I>::take(&batch_id) {
+                    FinalData::<T, I>::remove(&batch_id);
+                    weight = weight.saturating_add(T::DbWeight::get().writes(2));
+
+                    Self::deposit_event(Event::PublicationExpired {
+                        table: pending.quorum.table,
+                        batch_id,
+                    });
+                }
+            }
+
+            let expiring = ChunkedBatchDeadlines::<T, I>::take(n);
+            weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+
+            for batch_id in expiring {
+                weight = weight.saturating_add(T::DbWeight::get().reads(1));
+
+                // Batches that have already been finalized are no longer pending
+                if let Some(pending) = PendingChunkedBatches::<T, I>::get(&batch_id) {
+                    let writes = discard_chunked_batch::<T, I>(&batch_id);
+                    weight = weight.saturating_add(T::DbWeight::get().writes(writes));
+
+                    Self::deposit_event(Event::ChunkedBatchExpired {
+                        table: pending.quorum.table,
+                        batch_id,
+                    });
+                }
+            }
+
+            weight
+        }
     }
 
     #[pallet::call]
@@ -205,7 +446,7 @@ pub mod pallet {
         /// This extrinsic provides a transaction that indexers will use to submit
         /// data they've indexed.
         #[pallet::call_index(0)]
-        #[pallet::weight(<T as Config<I>>::WeightInfo::submit_data())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::submit_data().saturating_add(quorum_rewards_weight::<T, I>(1)).saturating_add(key_indexing_weight::<T, I>(table, 1)))]
         pub fn submit_data(
             origin: OriginFor<T>,
             table: TableIdentifier,
@@ -222,7 +463,7 @@ pub mod pallet {
         /// by this batch. The submission goes through the quorum process (public or privileged) and is
         /// finalized only if quorum is reached.
         #[pallet::call_index(1)]
-        #[pallet::weight(<T as Config<I>>::WeightInfo::submit_data())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::submit_data().saturating_add(quorum_rewards_weight::<T, I>(1)).saturating_add(key_indexing_weight::<T, I>(table, 1)))]
         pub fn submit_blockchain_data(
             origin: OriginFor<T>,
             table: TableIdentifier,
@@ -232,22 +473,416 @@ pub mod pallet {
         ) -> DispatchResult {
             submit_data_inner::<T, I>(origin, table, batch_id, data, Some(block_number))
         }
+
+        /// Submit the manifest of a batch that is too large for a single submission.
+        ///
+        /// Quorum is reached on the hash of the manifest. Once it is reached, the batch awaits
+        /// its chunks, which are uploaded with `submit_chunk` before `ChunkUploadTimeout` elapses.
+        #[pallet::call_index(2)]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::submit_chunk_manifest())]
+        pub fn submit_chunk_manifest(
+            origin: OriginFor<T>,
+            table: TableIdentifier,
+            batch_id: BatchId,
+            manifest: ChunkManifest<T::Hash>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin.clone())?;
+            let table_insert_quorum = pallet_tables::TableInsertQuorums::<T>::get(&table);
+            let quorum_scopes =
+                permitted_quorum_scopes::<T, I>(origin, &table, &table_insert_quorum)?;
+
+            validate_batch::<T, I>(&table, &batch_id)?;
+            ensure!(!manifest.chunk_hashes.is_empty(), Error::<T, I>::NoData);
+
+            let manifest_hash = T::Hashing::hash_of(&manifest);
+
+            if let Some(data_quorum) = submit_hash_and_find_quorum::<T, I>(
+                who,
+                batch_id,
+                manifest_hash,
+                table,
+                &table_insert_quorum,
+                &quorum_scopes,
+            )? {
+                record_quorum::<T, I>(&data_quorum);
+
+                let deadline = <frame_system::Pallet<T>>::block_number()
+                    .saturating_add(T::ChunkUploadTimeout::get());
+                ChunkedBatchDeadlines::<T, I>::try_mutate(deadline, |batch_ids| {
+                    batch_ids.try_push(data_quorum.batch_id.clone())
+                })
+                .map_err(|_| Error::<T, I>::TooManyPendingChunkedBatches)?;
+
+                let chunk_count = manifest.chunk_count();
+                PendingChunkedBatches::<T, I>::insert(
+                    &data_quorum.batch_id,
+                    PendingChunkedBatch {
+                        quorum: data_quorum.clone(),
+                        manifest,
+                        uploaded_chunks: 0,
+                        uploaded_len: 0,
+                    },
+                );
+
+                Pallet::<T, I>::deposit_event(Event::ChunkManifestQuorumReached {
+                    quorum: data_quorum,
+                    chunk_count,
+                });
+            }
+
+            Ok(())
+        }
+
+        /// Upload one chunk of a chunked batch whose manifest has reached quorum.
+        ///
+        /// The upload that completes the batch commits every chunk in manifest order, and is
+        /// charged for the combined length of all of them. If any chunk fails to be processed,
+        /// none of them are committed and the batch is discarded.
+        #[pallet::call_index(3)]
+        #[pallet::weight(submit_chunk_weight::<T, I>(batch_id, data))]
+        pub fn submit_chunk(
+            origin: OriginFor<T>,
+            batch_id: BatchId,
+            chunk_index: ChunkIndex,
+            data: RowData,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin.clone())?;
+            let mut pending = PendingChunkedBatches::<T, I>::get(&batch_id)
+                .ok_or(Error::<T, I>::UnknownChunkedBatch)?;
+
+            let table_insert_quorum =
+                pallet_tables::TableInsertQuorums::<T>::get(&pending.quorum.table);
+            permitted_quorum_scopes::<T, I>(origin, &pending.quorum.table, &table_insert_quorum)?;
+
+            let expected_hash = pending
+                .manifest
+                .chunk_hashes
+                .get(chunk_index as usize)
+                .ok_or(Error::<T, I>::InvalidChunkIndex)?;
+            ensure!(
+                !Chunks::<T, I>::contains_key(&batch_id, chunk_index),
+                Error::<T, I>::ChunkAlreadySubmitted
+            );
+            ensure!(
+                &T::Hashing::hash_of(&data) == expected_hash,
+                Error::<T, I>::ChunkHashMismatch
+            );
+
+            pending.uploaded_chunks = pending.uploaded_chunks.saturating_add(1);
+            pending.uploaded_len = pending.uploaded_len.saturating_add(data.len() as u32);
+            Chunks::<T, I>::insert(&batch_id, chunk_index, data);
+            Pallet::<T, I>::deposit_event(Event::ChunkSubmitted {
+                who,
+                batch_id: batch_id.clone(),
+                chunk_index,
+            });
+
+            if pending.uploaded_chunks < pending.manifest.chunk_count() {
+                PendingChunkedBatches::<T, I>::insert(&batch_id, pending);
+                return Ok(Some(<T as Config<I>>::WeightInfo::submit_chunk(0, 0)).into());
+            }
+
+            // A batch that fails to be committed would otherwise hold its chunks until it expires
+            let table = pending.quorum.table.clone();
+            if let Err(error) = frame_support::storage::with_storage_layer(|| {
+                finalize_chunked_batch::<T, I>(pending)
+            }) {
+                discard_chunked_batch::<T, I>(&batch_id);
+                Pallet::<T, I>::deposit_event(Event::ChunkedBatchFailed {
+                    table,
+                    batch_id,
+                    error,
+                });
+            }
+
+            Ok(().into())
+        }
+
+        /// Submit only the hash of a data batch, without the data itself.
+        ///
+        /// `row_data_hash` is the hash of the SCALE-encoded [`RowData`]. Quorum is reached on the
+        /// hash of `row_data_hash` and `block_number` combined, see [`quorum_data_hash`], so
+        /// hash-only submissions never count toward the quorum of submissions of the data itself.
+        /// Once reached, any permitted indexer publishes the data with `publish_data` before
+        /// `PublicationTimeout` elapses.
+        #[pallet::call_index(5)]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::submit_data_hash())]
+        pub fn submit_data_hash(
+            origin: OriginFor<T>,
+            table: TableIdentifier,
+            batch_id: BatchId,
+            row_data_hash: T::Hash,
+            block_number: u64,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin.clone())?;
+            let table_insert_quorum = pallet_tables::TableInsertQuorums::<T>::get(&table);
+            let quorum_scopes =
+                permitted_quorum_scopes::<T, I>(origin, &table, &table_insert_quorum)?;
+
+            validate_batch::<T, I>(&table, &batch_id)?;
+
+            let data_hash = quorum_data_hash::<T, _>(&row_data_hash, Some(block_number));
+
+            if let Some(data_quorum) = submit_hash_and_find_quorum::<T, I>(
+                who,
+                batch_id,
+                data_hash,
+                table,
+                &table_insert_quorum,
+                &quorum_scopes,
+            )? {
+                record_quorum::<T, I>(&data_quorum);
+
+                let deadline = <frame_system::Pallet<T>>::block_number()
+                    .saturating_add(T::PublicationTimeout::get());
+                PublicationDeadlines::<T, I>::try_mutate(deadline, |batch_ids| {
+                    batch_ids.try_push(data_quorum.batch_id.clone())
+                })
+                .map_err(|_| Error::<T, I>::TooManyPendingPublications)?;
+
+                PendingPublications::<T, I>::insert(
+                    &data_quorum.batch_id,
+                    PendingPublication {
+                        quorum: data_quorum.clone(),
+                        row_data_hash,
+                        block_number,
+                        deadline,
+                    },
+                );
+
+                Pallet::<T, I>::deposit_event(Event::DataHashQuorumReached {
+                    quorum: data_quorum,
+                    row_data_hash,
+                    deadline,
+                });
+            }
+
+            Ok(())
+        }
+
+        /// Publish the data of a hash-only quorum.
+        ///
+        /// The data is committed to only if it matches the hash agreed upon by the quorum.
+        #[pallet::call_index(6)]
+        #[pallet::weight(publish_data_weight::<T, I>(batch_id))]
+        pub fn publish_data(
+            origin: OriginFor<T>,
+            batch_id: BatchId,
+            data: RowData,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin.clone())?;
+            let pending = PendingPublications::<T, I>::get(&batch_id)
+                .ok_or(Error::<T, I>::UnknownPendingPublication)?;
+
+            let table_insert_quorum =
+                pallet_tables::TableInsertQuorums::<T>::get(&pending.quorum.table);
+            permitted_quorum_scopes::<T, I>(origin, &pending.quorum.table, &table_insert_quorum)?;
+
+            ensure!(
+                T::Hashing::hash_of(&data) == pending.row_data_hash,
+                Error::<T, I>::PublishedDataHashMismatch
+            );
+
+            PendingPublications::<T, I>::remove(&batch_id);
+            apply_quorum_data::<T, I>(&pending.quorum, data, Some(pending.block_number))?;
+
+            Pallet::<T, I>::deposit_event(Event::DataPublished { who, batch_id });
+
+            Ok(())
+        }
+
+        /// Submit data for several tables of the same source block under one batch id.
+        ///
+        /// Quorum is reached on the hash of all tables' data combined, see [`quorum_data_hash`],
+        /// using the strictest insert quorum of the tables involved. The submission is recorded
+        /// under the first table, and a `DataSubmitted` event is emitted for every table. Once
+        /// quorum is reached, every table is committed to in the order submitted. If any of them
+        /// fails, none are committed.
+        #[pallet::call_index(4)]
+        #[pallet::weight(submit_multi_table_data_weight::<T, I>(tables))]
+        pub fn submit_multi_table_data(
+            origin: OriginFor<T>,
+            batch_id: BatchId,
+            tables: MultiTableRowData,
+            block_number: u64,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin.clone())?;
+            let (first_table, _) = tables.first().ok_or(Error::<T, I>::NoData)?;
+
+            let table_insert_quorum = tables
+                .iter()
+                .map(|(table, _)| pallet_tables::TableInsertQuorums::<T>::get(table))
+                .reduce(|a, b| a.strictest(&b))
+                .unwrap_or_default();
+
+            // Only scopes that are permitted for every table can be submitted to
+            let mut quorum_scopes = Vec::from([QuorumScope::Public, QuorumScope::Privileged]);
+            for (table, _) in tables.iter() {
+                let table_scopes =
+                    permitted_quorum_scopes::<T, I>(origin.clone(), table, &table_insert_quorum)?;
+                quorum_scopes.retain(|scope| table_scopes.contains(scope));
+            }
+            ensure!(
+                !quorum_scopes.is_empty(),
+                Error::<T, I>::UnauthorizedSubmitter
+            );
+
+            for (index, (table, data)) in tables.iter().enumerate() {
+                validate_submission::<T, I>(table, &batch_id, data)?;
+                ensure!(
+                    tables
+                        .iter()
+                        .skip(index + 1)
+                        .all(|(other, _)| other != table),
+                    Error::<T, I>::DuplicateTable
+                );
+            }
+
+            let data_hash = quorum_data_hash::<T, _>(&tables, Some(block_number));
+
+            let data_quorum = submit_hash_and_find_quorum::<T, I>(
+                who.clone(),
+                batch_id.clone(),
+                data_hash,
+                first_table.clone(),
+                &table_insert_quorum,
+                &quorum_scopes,
+            )?;
+
+            // The submission of the first table has been emitted above, but it covers every table
+            for (table, _) in tables.iter().skip(1) {
+                for quorum_scope in quorum_scopes.iter() {
+                    Pallet::<T, I>::deposit_event(Event::DataSubmitted {
+                        who: who.clone(),
+                        submission: DataSubmission {
+                            table: table.clone(),
+                            batch_id: batch_id.clone(),
+                            data_hash,
+                            quorum_scope: *quorum_scope,
+                        },
+                    });
+                }
+            }
+
+            if let Some(data_quorum) = data_quorum {
+                finalize_multi_table_quorum::<T, I>(data_quorum, tables, block_number)?;
+            }
+
+            Ok(())
+        }
     }
 
-    fn submit_data_inner<T, I>(
+    /// The weight of rewarding the indexers agreeing in `quorums` quorums, at most
+    /// [`MAX_SUBMITTERS`] each.
+    fn quorum_rewards_weight<T, I>(quorums: u32) -> Weight
+    where
+        T: Config<I>,
+        I: 'static,
+    {
+        T::WorkRewards::note_quorum_agreement_weight()
+            .saturating_mul(u64::from(MAX_SUBMITTERS))
+            .saturating_mul(u64::from(quorums))
+    }
+
+    /// The weight of indexing the primary keys of `batches` batches inserted into `table`, at most
+    /// [`MAX_KEYED_ROWS_PER_BATCH`] rows each.
+    ///
+    /// Only tables with a duplicate key policy maintain a key index, the others are charged for
+    /// reading their constraint.
+    fn key_indexing_weight<T, I>(table: &TableIdentifier, batches: u32) -> Weight
+    where
+        T: Config<I>,
+        I: 'static,
+    {
+        let constraint_read = T::DbWeight::get().reads(1);
+        let maintains_key_index = pallet_tables::PrimaryKeyConstraints::<T>::get(table)
+            .is_some_and(|constraint| constraint.on_duplicate.is_some());
+        if !maintains_key_index {
+            return constraint_read;
+        }
+
+        <<T as pallet_tables::Config>::WeightInfo as pallet_tables::WeightInfo>::index_primary_key()
+            .saturating_mul(u64::from(MAX_KEYED_ROWS_PER_BATCH))
+            .saturating_mul(u64::from(batches))
+            .saturating_add(constraint_read)
+    }
+
+    /// The weight of publishing the data of `batch_id`.
+    ///
+    /// The pending publication is read to find the table the data is inserted into.
+    fn publish_data_weight<T, I>(batch_id: &BatchId) -> Weight
+    where
+        T: Config<I>,
+        I: 'static,
+    {
+        let publish_weight = <T as Config<I>>::WeightInfo::publish_data()
+            .saturating_add(quorum_rewards_weight::<T, I>(1));
+
+        match PendingPublications::<T, I>::get(batch_id) {
+            Some(pending) => {
+                publish_weight.saturating_add(key_indexing_weight::<T, I>(&pending.quorum.table, 1))
+            }
+            None => publish_weight,
+        }
+    }
+
+    /// The weight of submitting data for every table in `tables`.
+    fn submit_multi_table_data_weight<T, I>(tables: &MultiTableRowData) -> Weight
+    where
+        T: Config<I>,
+        I: 'static,
+    {
+        let table_count = tables.len() as u32;
+
+        tables.iter().fold(
+            <T as Config<I>>::WeightInfo::submit_multi_table_data(table_count)
+                .saturating_add(quorum_rewards_weight::<T, I>(table_count)),
+            |weight, (table, _)| weight.saturating_add(key_indexing_weight::<T, I>(table, 1)),
+        )
+    }
+
+    /// The weight of uploading `data` as a chunk of `batch_id`.
+    ///
+    /// The pending batch is read to tell whether this upload completes it. If it does, the upload
+    /// commits to every chunk and is charged for their combined length.
+    fn submit_chunk_weight<T, I>(batch_id: &BatchId, data: &RowData) -> Weight
+    where
+        T: Config<I>,
+        I: 'static,
+    {
+        let upload_weight = <T as Config<I>>::WeightInfo::submit_chunk(0, 0);
+        let Some(pending) = PendingChunkedBatches::<T, I>::get(batch_id) else {
+            return upload_weight;
+        };
+
+        let chunk_count = pending.manifest.chunk_count();
+        if pending.uploaded_chunks.saturating_add(1) < chunk_count {
+            return upload_weight;
+        }
+
+        <T as Config<I>>::WeightInfo::submit_chunk(
+            chunk_count,
+            pending.uploaded_len.saturating_add(data.len() as u32),
+        )
+        .saturating_add(quorum_rewards_weight::<T, I>(chunk_count))
+        .saturating_add(key_indexing_weight::<T, I>(
+            &pending.quorum.table,
+            chunk_count,
+        ))
+    }
+
+    /// Returns the quorum scopes the origin may submit data for on the given table.
+    ///
+    /// Fails with `UnauthorizedSubmitter` if there are none.
+    fn permitted_quorum_scopes<T, I>(
         origin: OriginFor<T>,
-        table: TableIdentifier,
-        batch_id: BatchId,
-        data: RowData,
-        block_number: Option<u64>,
-    ) -> DispatchResult
+        table: &TableIdentifier,
+        table_insert_quorum: &InsertQuorumSize,
+    ) -> Result<Vec<QuorumScope>, DispatchError>
     where
         T: Config<I>,
         I: NativeApi,
     {
-        let who = ensure_signed(origin.clone())?;
-        let table_insert_quorum = pallet_tables::TableInsertQuorums::<T>::get(&table);
-
         let can_submit_for_public_quorum =
             pallet_permissions::Pallet::<T>::ensure_root_or_permissioned(
                 origin.clone(),
@@ -273,44 +908,80 @@ pub mod pallet {
             Error::<T, I>::UnauthorizedSubmitter
         );
 
-        validate_submission::<T, I>(&table, &batch_id, &data)?;
+        Ok([
+            (can_submit_for_public_quorum, QuorumScope::Public),
+            (can_submit_for_privileged_quorum, QuorumScope::Privileged),
+        ]
+        .into_iter()
+        .filter_map(|(permitted, scope)| permitted.then_some(scope))
+        .collect())
+    }
 
-        let hash_input = (&data, block_number).encode();
-        let data_hash = T::Hashing::hash(&hash_input);
+    fn submit_data_inner<T, I>(
+        origin: OriginFor<T>,
+        table: TableIdentifier,
+        batch_id: BatchId,
+        data: RowData,
+        block_number: Option<u64>,
+    ) -> DispatchResult
+    where
+        T: Config<I>,
+        I: NativeApi,
+    {
+        let who = ensure_signed(origin.clone())?;
+        let table_insert_quorum = pallet_tables::TableInsertQuorums::<T>::get(&table);
+        let quorum_scopes = permitted_quorum_scopes::<T, I>(origin, &table, &table_insert_quorum)?;
 
-        let public_data_quorum = if can_submit_for_public_quorum {
-            submit_data_and_find_quorum::<T, I>(
-                who.clone(),
-                batch_id.clone(),
-                data_hash,
-                table.clone(),
-                &table_insert_quorum,
-                &QuorumScope::Public,
-            )?
-        } else {
-            None
-        };
+        validate_submission::<T, I>(&table, &batch_id, &data)?;
 
-        let privileged_data_quorum = if can_submit_for_privileged_quorum {
-            submit_data_and_find_quorum::<T, I>(
-                who.clone(),
-                batch_id,
-                data_hash,
-                table.clone(),
-                &table_insert_quorum,
-                &QuorumScope::Privileged,
-            )?
-        } else {
-            None
-        };
+        let data_hash = quorum_data_hash::<T, _>(&data, block_number);
 
-        if let Some(data_quorum) = public_data_quorum.or(privileged_data_quorum) {
+        if let Some(data_quorum) = submit_hash_and_find_quorum::<T, I>(
+            who,
+            batch_id,
+            data_hash,
+            table,
+            &table_insert_quorum,
+            &quorum_scopes,
+        )? {
             finalize_quorum::<T, I>(data_quorum, data, block_number)?;
         }
 
         Ok(())
     }
 
+    /// Submit a data hash to each of the given quorum scopes and check if we have a quorum.
+    ///
+    /// If quorum is reached in any scope, the [`DataQuorum`] of the first such scope is returned.
+    #[allow(clippy::type_complexity)]
+    fn submit_hash_and_find_quorum<T, I>(
+        who: T::AccountId,
+        batch_id: BatchId,
+        data_hash: T::Hash,
+        table: TableIdentifier,
+        table_insert_quorum: &InsertQuorumSize,
+        quorum_scopes: &[QuorumScope],
+    ) -> Result<Option<DataQuorum<T::AccountId, T::Hash>>, DispatchError>
+    where
+        T: Config<I>,
+        I: NativeApi,
+    {
+        quorum_scopes
+            .iter()
+            .try_fold(None, |data_quorum, quorum_scope| {
+                let scope_quorum = submit_data_and_find_quorum::<T, I>(
+                    who.clone(),
+                    batch_id.clone(),
+                    data_hash,
+                    table.clone(),
+                    table_insert_quorum,
+                    quorum_scope,
+                )?;
+
+                Ok(data_quorum.or(scope_quorum))
+            })
+    }
+
     /// Submit data and check if we have a quorum.
     ///
     /// If quorum is reached, the associated [`DataQuorum`] is returned, otherwise returns `None`.
@@ -401,6 +1072,16 @@ pub mod pallet {
         row_data: RowData,
         block_number: Option<u64>,
     ) -> DispatchResult
+    where
+        T: Config<I>,
+        I: NativeApi,
+    {
+        record_quorum::<T, I>(&quorum);
+        apply_quorum_data::<T, I>(&quorum, row_data, block_number)
+    }
+
+    /// Records the quorum as final data and cleans up the submissions for its batch.
+    fn record_quorum<T, I>(quorum: &DataQuorum<T::AccountId, T::Hash>)
     where
         T: Config<I>,
         I: NativeApi,
@@ -408,22 +1089,49 @@ pub mod pallet {
         Submissions::<T, I>::iter_key_prefix(&quorum.batch_id)
             .for_each(|key| Submissions::<T, I>::remove(&quorum.batch_id, key));
 
-        FinalData::<T, I>::insert(&quorum.batch_id, &quorum);
+        FinalData::<T, I>::insert(&quorum.batch_id, quorum);
+    }
 
+    /// Commits to the data agreed upon by the quorum, rewards the indexers that agreed on it and
+    /// emits the resulting events.
+    fn apply_quorum_data<T, I>(
+        quorum: &DataQuorum<T::AccountId, T::Hash>,
+        row_data: RowData,
+        block_number: Option<u64>,
+    ) -> DispatchResult
+    where
+        T: Config<I>,
+        I: NativeApi,
+    {
+        let indexed_bytes = row_data.len() as u64;
         let table_bytes = I::record_batch_to_onchain(sxt_core::native::RowData { row_data })
             .map_err(Error::<T, I>::from)?;
 
         let oc_table = OnChainTable::try_from(table_bytes)
             .map_err(|_| Error::<T, I>::TableDeserializationError)?;
 
+        let primary_key_constraint = pallet_tables::PrimaryKeyConstraints::<T>::get(&quorum.table);
+
         let InsertAndCommitmentMetadata {
             insert_with_meta_columns,
             ..
         } = pallet_commitments::Pallet::<T>::process_insert_and_update_commitments::<I>(
             quorum.table.clone(),
             oc_table.clone(),
+            primary_key_constraint
+                .clone()
+                .map(|constraint| constraint.columns)
+                .unwrap_or_default(),
         )?;
 
+        if let Some(PrimaryKeyConstraint {
+            columns,
+            on_duplicate: Some(_),
+        }) = primary_key_constraint
+        {
+            index_primary_keys::<T, I>(quorum, &insert_with_meta_columns, &columns)?;
+        }
+
         let on_chain_table_bytes: BoundedVec<u8, ConstU32<DATA_MAX_LEN>> =
             postcard::to_allocvec(&insert_with_meta_columns)
                 .map_err(|_| Error::<T, I>::TableSerializationError)?
@@ -436,6 +1144,10 @@ pub mod pallet {
             BlockNumbers::<T, I>::insert(&quorum.table, bn);
         }
 
+        for indexer in quorum.agreements.iter() {
+            T::WorkRewards::note_quorum_agreement(indexer, indexed_bytes);
+        }
+
         if oc_table.num_rows() == 0 {
             Pallet::<T, I>::deposit_event(Event::QuorumEmptyBlock {
                 table: quorum.table.clone(),
@@ -450,7 +1162,7 @@ pub mod pallet {
             });
         }
 
-        if quorum.table.is_staking_table() {
+        if pallet_system_tables::Pallet::<T>::has_system_template(&quorum.table) {
             if let Err(e) = pallet_system_tables::Pallet::<T>::process_system_table(
                 quorum.table.clone(),
                 oc_table,
@@ -471,6 +1183,143 @@ pub mod pallet {
         Ok(())
     }
 
+    /// Indexes the primary keys of rows inserted by the quorum, applying the table's
+    /// duplicate key policy.
+    fn index_primary_keys<T, I>(
+        quorum: &DataQuorum<T::AccountId, T::Hash>,
+        insert_with_meta_columns: &OnChainTable,
+        primary_key: &PrimaryKeys,
+    ) -> DispatchResult
+    where
+        T: Config<I>,
+        I: NativeApi,
+    {
+        let primary_key = primary_key
+            .iter()
+            .map(|column| core::str::from_utf8(column))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| Error::<T, I>::TableDeserializationError)?;
+
+        let row_keys = insert_with_meta_columns
+            .row_keys(primary_key)
+            .map_err(|_| Error::<T, I>::TableDeserializationError)?;
+        let row_numbers = row_numbers_of_on_chain_table(insert_with_meta_columns)
+            .ok_or(Error::<T, I>::TableDeserializationError)?;
+
+        // The cost of indexing is charged up front for at most this many rows
+        ensure!(
+            row_keys.len() <= MAX_KEYED_ROWS_PER_BATCH as usize,
+            Error::<T, I>::TooManyKeyedRows
+        );
+
+        pallet_tables::Pallet::<T>::index_primary_keys(
+            &quorum.table,
+            row_keys
+                .into_iter()
+                .zip(row_numbers.iter().map(|&row_number| row_number as u64)),
+        )
+    }
+
+    /// Performs all steps necessary after a multi-table submission reaches quorum.
+    ///
+    /// The recorded final data refers to the first table of the submission, while each table's
+    /// `QuorumReached` event carries a quorum for that table. Extrinsics are transactional, so
+    /// an error on any table reverts the commitment updates of the tables before it.
+    fn finalize_multi_table_quorum<T, I>(
+        quorum: DataQuorum<T::AccountId, T::Hash>,
+        tables: MultiTableRowData,
+        block_number: u64,
+    ) -> DispatchResult
+    where
+        T: Config<I>,
+        I: NativeApi,
+    {
+        record_quorum::<T, I>(&quorum);
+
+        let mut table_identifiers = BoundedVec::new();
+        for (table, data) in tables {
+            let table_quorum = DataQuorum {
+                table: table.clone(),
+                ..quorum.clone()
+            };
+            apply_quorum_data::<T, I>(&table_quorum, data, Some(block_number))?;
+            FinalTableData::<T, I>::insert(&quorum.batch_id, &table, table_quorum);
+            table_identifiers
+                .try_push(table)
+                .expect("source BoundedVec has the same bound");
+        }
+
+        Pallet::<T, I>::deposit_event(Event::MultiTableQuorumReached {
+            batch_id: quorum.batch_id,
+            tables: table_identifiers,
+            block_number,
+        });
+
+        Ok(())
+    }
+
+    /// Commits to every chunk of a completed chunked batch, in manifest order.
+    ///
+    /// Extrinsics are transactional, so an error on any chunk reverts the commitment updates of
+    /// the chunks before it.
+    fn finalize_chunked_batch<T, I>(
+        pending: PendingChunkedBatch<T::AccountId, T::Hash>,
+    ) -> DispatchResult
+    where
+        T: Config<I>,
+        I: NativeApi,
+    {
+        let PendingChunkedBatch {
+            quorum, manifest, ..
+        } = pending;
+        let chunk_count = manifest.chunk_count();
+
+        for chunk_index in 0..chunk_count {
+            let chunk = Chunks::<T, I>::take(&quorum.batch_id, chunk_index)
+                .ok_or(Error::<T, I>::InvalidChunkIndex)?;
+            apply_quorum_data::<T, I>(&quorum, chunk, manifest.block_number)?;
+        }
+
+        PendingChunkedBatches::<T, I>::remove(&quorum.batch_id);
+
+        Pallet::<T, I>::deposit_event(Event::ChunkedBatchFinalized {
+            table: quorum.table,
+            batch_id: quorum.batch_id,
+            chunk_count,
+        });
+
+        Ok(())
+    }
+
+    /// Discards a pending chunked batch and its uploaded chunks, so that its batch id can be
+    /// submitted to again.
+    ///
+    /// Returns the number of storage writes.
+    fn discard_chunked_batch<T, I>(batch_id: &BatchId) -> u64
+    where
+        T: Config<I>,
+        I: 'static,
+    {
+        let removed = Chunks::<T, I>::clear_prefix(batch_id, MAX_CHUNKS, None);
+        PendingChunkedBatches::<T, I>::remove(batch_id);
+        FinalData::<T, I>::remove(batch_id);
+
+        2 + u64::from(removed.backend)
+    }
+
+    /// The hash that submissions of `data` for `block_number` reach quorum on.
+    ///
+    /// This is the hash of the SCALE-encoded data followed by the block number, which is what
+    /// `submit_data` and `submit_blockchain_data` have always reached quorum on. Multi-table
+    /// submissions pass all of their tables as `data`, and hash-only submissions pass the hash of
+    /// the [`RowData`].
+    pub fn quorum_data_hash<T: frame_system::Config, D: Encode>(
+        data: &D,
+        block_number: Option<u64>,
+    ) -> T::Hash {
+        T::Hashing::hash_of(&(data, block_number))
+    }
+
     /// Run some checks to verify that table, batch_id, and data are reasonable, non-empty values\
     /// If the transaction is considered invalid, a relevant error will be returned
     pub fn validate_submission<T, I>(
@@ -478,6 +1327,18 @@ pub mod pallet {
         batch_id: &BatchId,
         data: &RowData,
     ) -> DispatchResult
+    where
+        T: Config<I>,
+        I: NativeApi,
+    {
+        validate_batch::<T, I>(table, batch_id)?;
+        ensure!(!data.is_empty(), Error::<T, I>::NoData);
+        Ok(())
+    }
+
+    /// Run some checks to verify that table and batch_id are reasonable, non-empty values that
+    /// can still be submitted to.
+    pub fn validate_batch<T, I>(table: &TableIdentifier, batch_id: &BatchId) -> DispatchResult
     where
         T: Config<I>,
         I: NativeApi,
@@ -491,7 +1352,6 @@ pub mod pallet {
             !(table.namespace.is_empty() || table.name.is_empty()),
             Error::<T, I>::InvalidTable
         );
-        ensure!(!data.is_empty(), Error::<T, I>::NoData);
         ensure!(!batch_id.is_empty(), Error::<T, I>::InvalidBatch);
         // Make sure the schema exists for this table
         ensure!(
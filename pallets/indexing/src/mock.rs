@@ -118,6 +118,9 @@ impl pallet_staking::Config for Test {
 impl pallet_indexing::pallet::Config<Api> for Test {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = pallet_indexing::weights::SubstrateWeight<Test>;
+    type PublicationTimeout = ConstU64<10>;
+    type ChunkUploadTimeout = ConstU64<10>;
+    type WorkRewards = ();
 }
 pub type BlockNumber = u64;
 
@@ -166,6 +169,7 @@ sp_runtime::impl_opaque_keys! {
 
 impl pallet_system_tables::Config for Test {
     type RuntimeEvent = RuntimeEvent;
+    type TemplateAdminOrigin = frame_system::EnsureRoot<AccountId>;
 }
 
 impl pallet_tables::Config for Test {
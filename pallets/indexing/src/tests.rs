@@ -3,19 +3,29 @@ use std::convert::Into;
 use std::io::Cursor;
 use std::sync::Arc;
 
-use arrow::array::{ArrayRef, Int32Array, Int64Array, RecordBatch};
-use arrow::datatypes::{DataType, Field, Schema};
+use arrow::array::{ArrayRef, BinaryArray, Decimal256Array, Int32Array, Int64Array, RecordBatch};
+use arrow::datatypes::{i256, DataType, Field, Schema};
 use arrow::ipc::writer::StreamWriter;
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::__private::RuntimeDebug;
-use frame_support::dispatch::DispatchResult;
+use frame_support::dispatch::{DispatchResult, GetDispatchInfo};
 use frame_support::pallet_prelude::TypeInfo;
+use frame_support::traits::Hooks;
 use frame_support::{assert_err, assert_ok};
 use frame_system::ensure_signed;
 use native_api::Api;
+use pallet_system_tables::{
+    SystemFieldType,
+    SystemRequestType,
+    SystemTemplate,
+    TemplateField,
+    TemplateFieldName,
+    TemplateFields,
+};
 use pallet_tables::{CommitmentCreationCmd, UpdateTable};
 use proof_of_sql_commitment_map::CommitmentSchemeFlags;
-use sp_core::Hasher;
+use sp_core::{Hasher, H256};
+use sp_runtime::traits::Hash as HashT;
 use sp_runtime::BoundedVec;
 use sxt_core::permissions::{IndexingPalletPermission, PermissionLevel, PermissionList};
 use sxt_core::tables::{
@@ -29,9 +39,18 @@ use sxt_core::tables::{
     TableNamespace,
     TableType,
 };
+use sxt_core::utils::eth_address_to_substrate_account_id;
 
 use crate::mock::*;
-use crate::{BatchId, Event, RowData};
+use crate::{
+    BatchId,
+    ChunkManifest,
+    Event,
+    MultiTableRowData,
+    RowData,
+    WeightInfo,
+    MAX_KEYED_ROWS_PER_BATCH,
+};
 
 /// Used as a convenience wrapper for data we need to submit
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
@@ -1364,3 +1383,949 @@ fn no_block_number_stored_when_implicit_and_empty_data() {
         assert_eq!(stored, None);
     });
 }
+
+fn create_single_submission_test_table() -> TableIdentifier {
+    let (table_id, create_stmt) = sample_table_definition();
+
+    create_single_submission_test_table_with_statement(table_id, create_stmt)
+}
+
+fn create_single_submission_test_table_with_statement(
+    table_id: TableIdentifier,
+    create_stmt: CreateStatement,
+) -> TableIdentifier {
+    Tables::create_tables(
+        RuntimeOrigin::root(),
+        vec![UpdateTable {
+            ident: table_id.clone(),
+            create_statement: create_stmt,
+            table_type: TableType::Testing(InsertQuorumSize {
+                public: Some(0),
+                privileged: None,
+            }),
+            commitment: CommitmentCreationCmd::Empty(CommitmentSchemeFlags {
+                hyper_kzg: true,
+                dynamic_dory: true,
+            }),
+            source: sxt_core::tables::Source::Ethereum,
+        }]
+        .try_into()
+        .unwrap(),
+    )
+    .unwrap();
+
+    pallet_permissions::Permissions::<Test>::insert(
+        1,
+        PermissionList::try_from(vec![PermissionLevel::IndexingPallet(
+            IndexingPalletPermission::SubmitDataForPublicQuorum,
+        )])
+        .unwrap(),
+    );
+
+    table_id
+}
+
+fn chunk_manifest(chunks: &[RowData], block_number: Option<u64>) -> ChunkManifest<H256> {
+    ChunkManifest {
+        chunk_hashes: chunks
+            .iter()
+            .map(<<Test as frame_system::Config>::Hashing as HashT>::hash_of)
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap(),
+        block_number,
+    }
+}
+
+#[test]
+fn chunked_batch_is_committed_only_after_all_chunks_are_uploaded() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let table_id = create_single_submission_test_table();
+        let batch = BatchId::try_from(b"chunked_batch".to_vec()).unwrap();
+        let chunks = [row_data(), diff_row_data()];
+        let commitment_before =
+            pallet_commitments::CommitmentStorageMap::<Test>::iter_prefix_values(&table_id)
+                .collect::<Vec<_>>();
+
+        assert_ok!(Indexing::submit_chunk_manifest(
+            RuntimeOrigin::signed(1),
+            table_id.clone(),
+            batch.clone(),
+            chunk_manifest(&chunks, Some(100)),
+        ));
+        assert!(Indexing::final_data(batch.clone()).is_some());
+        assert!(Indexing::pending_chunked_batches(batch.clone()).is_some());
+
+        // uploading out of order is fine, nothing is committed until the batch is complete
+        assert_ok!(Indexing::submit_chunk(
+            RuntimeOrigin::signed(1),
+            batch.clone(),
+            1,
+            chunks[1].clone(),
+        ));
+        assert_eq!(
+            pallet_commitments::CommitmentStorageMap::<Test>::iter_prefix_values(&table_id)
+                .collect::<Vec<_>>(),
+            commitment_before
+        );
+        assert_eq!(Indexing::block_numbers(&table_id), None);
+
+        assert_ok!(Indexing::submit_chunk(
+            RuntimeOrigin::signed(1),
+            batch.clone(),
+            0,
+            chunks[0].clone(),
+        ));
+
+        assert_ne!(
+            pallet_commitments::CommitmentStorageMap::<Test>::iter_prefix_values(&table_id)
+                .collect::<Vec<_>>(),
+            commitment_before
+        );
+        assert_eq!(Indexing::block_numbers(&table_id), Some(100));
+        assert!(Indexing::pending_chunked_batches(batch.clone()).is_none());
+        assert_eq!(Indexing::chunks(batch.clone(), 0), None);
+        assert_eq!(Indexing::chunks(batch.clone(), 1), None);
+
+        let events = System::read_events_for_pallet::<Event<Test, Api>>();
+        assert_eq!(
+            events
+                .iter()
+                .filter(|event| matches!(event, Event::QuorumReached { .. }))
+                .count(),
+            2
+        );
+        assert!(events.iter().any(|event| matches!(
+            event,
+            Event::ChunkedBatchFinalized { table, batch_id, chunk_count: 2 }
+                if table == &table_id && batch_id == &batch
+        )));
+    });
+}
+
+#[test]
+fn we_cannot_upload_chunks_that_do_not_match_the_manifest() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let table_id = create_single_submission_test_table();
+        let batch = BatchId::try_from(b"chunked_batch".to_vec()).unwrap();
+        let chunks = [row_data(), row_data()];
+
+        assert_err!(
+            Indexing::submit_chunk(RuntimeOrigin::signed(1), batch.clone(), 0, row_data()),
+            crate::Error::<Test, Api>::UnknownChunkedBatch
+        );
+
+        assert_ok!(Indexing::submit_chunk_manifest(
+            RuntimeOrigin::signed(1),
+            table_id,
+            batch.clone(),
+            chunk_manifest(&chunks, None),
+        ));
+
+        assert_err!(
+            Indexing::submit_chunk(RuntimeOrigin::signed(1), batch.clone(), 0, diff_row_data()),
+            crate::Error::<Test, Api>::ChunkHashMismatch
+        );
+        assert_err!(
+            Indexing::submit_chunk(RuntimeOrigin::signed(1), batch.clone(), 2, row_data()),
+            crate::Error::<Test, Api>::InvalidChunkIndex
+        );
+
+        assert_ok!(Indexing::submit_chunk(
+            RuntimeOrigin::signed(1),
+            batch.clone(),
+            0,
+            row_data(),
+        ));
+        assert_err!(
+            Indexing::submit_chunk(RuntimeOrigin::signed(1), batch.clone(), 0, row_data()),
+            crate::Error::<Test, Api>::ChunkAlreadySubmitted
+        );
+        assert_err!(
+            Indexing::submit_chunk(RuntimeOrigin::signed(2), batch, 1, row_data()),
+            crate::Error::<Test, Api>::UnauthorizedSubmitter
+        );
+    });
+}
+
+#[test]
+fn completing_chunk_upload_is_charged_for_every_chunk() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let table_id = create_single_submission_test_table();
+        let batch = BatchId::try_from(b"chunked_batch".to_vec()).unwrap();
+        let chunks = [row_data(), diff_row_data()];
+        let upload_weight = |chunk_index: u32| {
+            crate::Call::<Test, Api>::submit_chunk {
+                batch_id: batch.clone(),
+                chunk_index,
+                data: chunks[chunk_index as usize].clone(),
+            }
+            .get_dispatch_info()
+            .weight
+        };
+
+        assert_ok!(Indexing::submit_chunk_manifest(
+            RuntimeOrigin::signed(1),
+            table_id,
+            batch.clone(),
+            chunk_manifest(&chunks, None),
+        ));
+
+        let upload_only = crate::weights::SubstrateWeight::<Test>::submit_chunk(0, 0);
+        assert_eq!(upload_weight(0), upload_only);
+
+        assert_ok!(Indexing::submit_chunk(
+            RuntimeOrigin::signed(1),
+            batch.clone(),
+            0,
+            chunks[0].clone(),
+        ));
+
+        let total_len = (chunks[0].len() + chunks[1].len()) as u32;
+        assert_eq!(
+            upload_weight(1),
+            crate::weights::SubstrateWeight::<Test>::submit_chunk(2, total_len)
+        );
+        assert!(upload_weight(1).ref_time() > upload_only.ref_time());
+    });
+}
+
+#[test]
+fn chunked_batch_is_discarded_if_its_chunks_fail_to_be_committed() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let table_id = create_single_submission_test_table();
+        let batch = BatchId::try_from(b"chunked_batch".to_vec()).unwrap();
+        let chunks = [
+            row_data(),
+            RowData::try_from(b"not a record batch".to_vec()).unwrap(),
+        ];
+
+        assert_ok!(Indexing::submit_chunk_manifest(
+            RuntimeOrigin::signed(1),
+            table_id.clone(),
+            batch.clone(),
+            chunk_manifest(&chunks, None),
+        ));
+        assert_ok!(Indexing::submit_chunk(
+            RuntimeOrigin::signed(1),
+            batch.clone(),
+            0,
+            chunks[0].clone(),
+        ));
+
+        let commitment_before =
+            pallet_commitments::CommitmentStorageMap::<Test>::iter_prefix_values(&table_id)
+                .collect::<Vec<_>>();
+        System::reset_events();
+
+        assert_ok!(Indexing::submit_chunk(
+            RuntimeOrigin::signed(1),
+            batch.clone(),
+            1,
+            chunks[1].clone(),
+        ));
+
+        assert_eq!(
+            pallet_commitments::CommitmentStorageMap::<Test>::iter_prefix_values(&table_id)
+                .collect::<Vec<_>>(),
+            commitment_before
+        );
+        assert!(Indexing::pending_chunked_batches(batch.clone()).is_none());
+        assert_eq!(Indexing::chunks(batch.clone(), 0), None);
+        assert_eq!(Indexing::chunks(batch.clone(), 1), None);
+        assert!(Indexing::final_data(batch.clone()).is_none());
+
+        let events = System::read_events_for_pallet::<Event<Test, Api>>();
+        assert!(events.iter().any(|event| matches!(
+            event,
+            Event::ChunkedBatchFailed { table, batch_id, .. }
+                if table == &table_id && batch_id == &batch
+        )));
+        assert!(!events.iter().any(|event| matches!(
+            event,
+            Event::QuorumReached { .. } | Event::ChunkedBatchFinalized { .. }
+        )));
+
+        // the batch can be submitted to again after failing
+        assert_ok!(Indexing::submit_data(
+            RuntimeOrigin::signed(1),
+            table_id,
+            batch.clone(),
+            row_data(),
+        ));
+        assert!(Indexing::final_data(batch).is_some());
+    });
+}
+
+#[test]
+fn chunked_batch_expires_if_not_every_chunk_is_uploaded() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let table_id = create_single_submission_test_table();
+        let batch = BatchId::try_from(b"chunked_batch".to_vec()).unwrap();
+        let chunks = [row_data(), diff_row_data()];
+
+        assert_ok!(Indexing::submit_chunk_manifest(
+            RuntimeOrigin::signed(1),
+            table_id.clone(),
+            batch.clone(),
+            chunk_manifest(&chunks, None),
+        ));
+        assert_ok!(Indexing::submit_chunk(
+            RuntimeOrigin::signed(1),
+            batch.clone(),
+            0,
+            chunks[0].clone(),
+        ));
+
+        System::set_block_number(11);
+        Indexing::on_initialize(11);
+
+        assert!(Indexing::pending_chunked_batches(batch.clone()).is_none());
+        assert_eq!(Indexing::chunks(batch.clone(), 0), None);
+        assert!(Indexing::final_data(batch.clone()).is_none());
+        assert!(System::read_events_for_pallet::<Event<Test, Api>>()
+            .iter()
+            .any(|event| matches!(
+                event,
+                Event::ChunkedBatchExpired { table, batch_id }
+                    if table == &table_id && batch_id == &batch
+            )));
+
+        // the batch can be submitted to again after expiry
+        assert_ok!(Indexing::submit_data(
+            RuntimeOrigin::signed(1),
+            table_id,
+            batch.clone(),
+            row_data(),
+        ));
+        assert!(Indexing::final_data(batch).is_some());
+    });
+}
+
+fn create_multi_table_test_tables() -> (TableIdentifier, TableIdentifier) {
+    let (table_a, create_a) = sample_table_definition();
+    let table_b = TableIdentifier {
+        namespace: table_a.namespace.clone(),
+        name: TableName::try_from(b"OTHER_TABLE".to_vec()).unwrap(),
+    };
+    let create_b = CreateStatement::try_from(
+        b"CREATE TABLE test_namespace.other_table (int_column INT NOT NULL)".to_vec(),
+    )
+    .unwrap();
+
+    let update_table = |ident: TableIdentifier, create_statement, public| UpdateTable {
+        ident,
+        create_statement,
+        table_type: TableType::Testing(InsertQuorumSize {
+            public: Some(public),
+            privileged: None,
+        }),
+        commitment: CommitmentCreationCmd::Empty(CommitmentSchemeFlags {
+            hyper_kzg: true,
+            dynamic_dory: true,
+        }),
+        source: sxt_core::tables::Source::Ethereum,
+    };
+
+    Tables::create_tables(
+        RuntimeOrigin::root(),
+        vec![
+            update_table(table_a.clone(), create_a, 0),
+            update_table(table_b.clone(), create_b, 1),
+        ]
+        .try_into()
+        .unwrap(),
+    )
+    .unwrap();
+
+    for id in 1..=2 {
+        pallet_permissions::Permissions::<Test>::insert(
+            id,
+            PermissionList::try_from(vec![PermissionLevel::IndexingPallet(
+                IndexingPalletPermission::SubmitDataForPublicQuorum,
+            )])
+            .unwrap(),
+        );
+    }
+
+    (table_a, table_b)
+}
+
+#[test]
+fn multi_table_submission_commits_all_tables_once_strictest_quorum_is_reached() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let (table_a, table_b) = create_multi_table_test_tables();
+        let batch = BatchId::try_from(b"multi_batch".to_vec()).unwrap();
+        let tables = MultiTableRowData::try_from(vec![
+            (table_a.clone(), row_data()),
+            (table_b.clone(), diff_row_data()),
+        ])
+        .unwrap();
+
+        let data_hash = crate::quorum_data_hash::<Test, _>(&tables, Some(200));
+
+        assert_ok!(Indexing::submit_multi_table_data(
+            RuntimeOrigin::signed(1),
+            batch.clone(),
+            tables.clone(),
+            200,
+        ));
+
+        // table_a alone would have reached quorum, but table_b requires another submission
+        assert!(Indexing::final_data(batch.clone()).is_none());
+        assert_eq!(Indexing::block_numbers(&table_a), None);
+        assert_eq!(Indexing::block_numbers(&table_b), None);
+
+        // the submission is visible for every table
+        let submissions = System::read_events_for_pallet::<Event<Test, Api>>()
+            .into_iter()
+            .filter_map(|event| match event {
+                Event::DataSubmitted { who: 1, submission } => Some(submission),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            submissions
+                .iter()
+                .map(|submission| submission.table.clone())
+                .collect::<Vec<_>>(),
+            vec![table_a.clone(), table_b.clone()]
+        );
+        assert!(submissions
+            .iter()
+            .all(|submission| submission.data_hash == data_hash && submission.batch_id == batch));
+
+        assert_ok!(Indexing::submit_multi_table_data(
+            RuntimeOrigin::signed(2),
+            batch.clone(),
+            tables,
+            200,
+        ));
+
+        assert_eq!(
+            Indexing::final_data(batch.clone()).unwrap().data_hash,
+            data_hash
+        );
+        assert_eq!(Indexing::block_numbers(&table_a), Some(200));
+        assert_eq!(Indexing::block_numbers(&table_b), Some(200));
+        for table in [&table_a, &table_b] {
+            let quorum = Indexing::final_table_data(batch.clone(), table).unwrap();
+            assert_eq!(&quorum.table, table);
+            assert_eq!(quorum.batch_id, batch);
+        }
+
+        let events = System::read_events_for_pallet::<Event<Test, Api>>();
+        let quorum_tables = events
+            .iter()
+            .filter_map(|event| match event {
+                Event::QuorumReached { quorum, .. } => Some(quorum.table.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(quorum_tables, vec![table_a.clone(), table_b.clone()]);
+        assert!(events.iter().any(|event| matches!(
+            event,
+            Event::MultiTableQuorumReached { batch_id, tables, block_number: 200 }
+                if batch_id == &batch && tables.to_vec() == vec![table_a.clone(), table_b.clone()]
+        )));
+    });
+}
+
+#[test]
+fn we_cannot_submit_the_same_table_twice_in_a_multi_table_submission() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let (table_a, _) = create_multi_table_test_tables();
+        let batch = BatchId::try_from(b"multi_batch".to_vec()).unwrap();
+
+        assert_err!(
+            Indexing::submit_multi_table_data(
+                RuntimeOrigin::signed(1),
+                batch.clone(),
+                MultiTableRowData::try_from(vec![
+                    (table_a.clone(), row_data()),
+                    (table_a, diff_row_data()),
+                ])
+                .unwrap(),
+                200,
+            ),
+            crate::Error::<Test, Api>::DuplicateTable
+        );
+
+        assert_err!(
+            Indexing::submit_multi_table_data(
+                RuntimeOrigin::signed(1),
+                batch,
+                MultiTableRowData::default(),
+                200,
+            ),
+            crate::Error::<Test, Api>::NoData
+        );
+    });
+}
+
+#[test]
+fn hash_only_quorum_commits_published_data_that_matches_the_agreed_hash() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let table_id = create_single_submission_test_table();
+        let batch = BatchId::try_from(b"hash_batch".to_vec()).unwrap();
+        let data = row_data();
+        let row_data_hash = <<Test as frame_system::Config>::Hashing as HashT>::hash_of(&data);
+
+        assert_ok!(Indexing::submit_data_hash(
+            RuntimeOrigin::signed(1),
+            table_id.clone(),
+            batch.clone(),
+            row_data_hash,
+            300,
+        ));
+        assert!(Indexing::final_data(batch.clone()).is_some());
+        assert!(Indexing::pending_publications(batch.clone()).is_some());
+        assert_eq!(Indexing::block_numbers(&table_id), None);
+
+        assert_err!(
+            Indexing::publish_data(RuntimeOrigin::signed(1), batch.clone(), diff_row_data()),
+            crate::Error::<Test, Api>::PublishedDataHashMismatch
+        );
+        assert_err!(
+            Indexing::publish_data(RuntimeOrigin::signed(2), batch.clone(), data.clone()),
+            crate::Error::<Test, Api>::UnauthorizedSubmitter
+        );
+
+        assert_ok!(Indexing::publish_data(
+            RuntimeOrigin::signed(1),
+            batch.clone(),
+            data.clone(),
+        ));
+        assert!(Indexing::pending_publications(batch.clone()).is_none());
+        assert_eq!(Indexing::block_numbers(&table_id), Some(300));
+
+        let events = System::read_events_for_pallet::<Event<Test, Api>>();
+        assert!(events.iter().any(
+            |event| matches!(event, Event::QuorumReached { quorum, .. } if quorum.batch_id == batch)
+        ));
+
+        assert_err!(
+            Indexing::publish_data(RuntimeOrigin::signed(1), batch, data),
+            crate::Error::<Test, Api>::UnknownPendingPublication
+        );
+    });
+}
+
+#[test]
+fn hash_only_quorum_expires_if_no_data_is_published() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let table_id = create_single_submission_test_table();
+        let batch = BatchId::try_from(b"hash_batch".to_vec()).unwrap();
+        let data = row_data();
+        let row_data_hash = <<Test as frame_system::Config>::Hashing as HashT>::hash_of(&data);
+
+        assert_ok!(Indexing::submit_data_hash(
+            RuntimeOrigin::signed(1),
+            table_id.clone(),
+            batch.clone(),
+            row_data_hash,
+            300,
+        ));
+        let deadline = Indexing::pending_publications(batch.clone())
+            .unwrap()
+            .deadline;
+        assert_eq!(deadline, 11);
+
+        System::set_block_number(deadline);
+        Indexing::on_initialize(deadline);
+
+        assert!(Indexing::pending_publications(batch.clone()).is_none());
+        assert!(Indexing::final_data(batch.clone()).is_none());
+        assert!(System::read_events_for_pallet::<Event<Test, Api>>()
+            .iter()
+            .any(|event| matches!(
+                event,
+                Event::PublicationExpired { table, batch_id }
+                    if table == &table_id && batch_id == &batch
+            )));
+
+        // the batch can be submitted to again after expiry
+        assert_ok!(Indexing::submit_data(
+            RuntimeOrigin::signed(1),
+            table_id,
+            batch.clone(),
+            data,
+        ));
+        assert!(Indexing::final_data(batch).is_some());
+    });
+}
+
+#[test]
+fn data_and_hash_only_submissions_count_toward_separate_quorums() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let (_, table_id) = create_multi_table_test_tables();
+        let batch = BatchId::try_from(b"hash_batch".to_vec()).unwrap();
+        let data = diff_row_data();
+        let row_data_hash = <<Test as frame_system::Config>::Hashing as HashT>::hash_of(&data);
+
+        assert_ok!(Indexing::submit_data_hash(
+            RuntimeOrigin::signed(1),
+            table_id.clone(),
+            batch.clone(),
+            row_data_hash,
+            300,
+        ));
+        assert_ok!(Indexing::submit_blockchain_data(
+            RuntimeOrigin::signed(2),
+            table_id.clone(),
+            batch.clone(),
+            data.clone(),
+            300,
+        ));
+        assert!(Indexing::final_data(batch.clone()).is_none());
+
+        let data_hash = hash_row_data_with_block_number::<Test>(&data, Some(300));
+        assert_eq!(
+            Indexing::submissions(batch.clone(), data_hash).len_of_scope(&QuorumScope::Public),
+            1
+        );
+        assert_eq!(
+            Indexing::submissions(
+                batch,
+                crate::quorum_data_hash::<Test, _>(&row_data_hash, Some(300))
+            )
+            .len_of_scope(&QuorumScope::Public),
+            1
+        );
+    });
+}
+
+fn int_row_data(values: Vec<i32>) -> RowData {
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "int_column",
+        DataType::Int32,
+        false,
+    )]));
+
+    let int_data = Arc::new(Int32Array::from(values)) as ArrayRef;
+
+    let batch = RecordBatch::try_new(schema.clone(), vec![int_data]).unwrap();
+
+    record_batch_to_row_data(batch, schema)
+}
+
+fn create_primary_key_test_table(with_options: &str) -> TableIdentifier {
+    let (table_id, _) = sample_table_definition();
+    let create_stmt = CreateStatement::try_from(
+        format!(
+            "CREATE TABLE test_namespace.test_table (int_column INT NOT NULL, PRIMARY KEY (int_column)) {with_options}"
+        )
+        .into_bytes(),
+    )
+    .unwrap();
+
+    create_single_submission_test_table_with_statement(table_id, create_stmt)
+}
+
+#[test]
+fn we_cannot_insert_duplicate_primary_keys_within_a_batch() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let table_id = create_primary_key_test_table("");
+
+        assert_err!(
+            submit_test_data(
+                RuntimeOrigin::signed(1),
+                TestSubmission {
+                    table: table_id.clone(),
+                    batch_id: BatchId::try_from(b"batch".to_vec()).unwrap(),
+                    data: int_row_data(vec![1, 2, 1]),
+                },
+            ),
+            pallet_commitments::Error::<Test>::InsertDataWithDuplicatePrimaryKey
+        );
+
+        // without a duplicate key policy, keys are not checked across batches
+        for (batch_id, data) in [(b"batch1", row_data()), (b"batch2", diff_row_data())] {
+            assert_ok!(submit_test_data(
+                RuntimeOrigin::signed(1),
+                TestSubmission {
+                    table: table_id.clone(),
+                    batch_id: BatchId::try_from(batch_id.to_vec()).unwrap(),
+                    data,
+                },
+            ));
+        }
+        assert_eq!(
+            pallet_tables::PrimaryKeyIndex::<Test>::iter_prefix(&table_id).count(),
+            0
+        );
+    });
+}
+
+#[test]
+fn we_cannot_reinsert_primary_key_into_reject_table() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let table_id = create_primary_key_test_table("WITH (on_duplicate_key=reject)");
+
+        assert_ok!(submit_test_data(
+            RuntimeOrigin::signed(1),
+            TestSubmission {
+                table: table_id.clone(),
+                batch_id: BatchId::try_from(b"batch1".to_vec()).unwrap(),
+                data: row_data(),
+            },
+        ));
+        assert_eq!(
+            pallet_tables::PrimaryKeyIndex::<Test>::iter_prefix(&table_id).count(),
+            4
+        );
+
+        let commitment_before =
+            pallet_commitments::CommitmentStorageMap::<Test>::iter_prefix_values(&table_id)
+                .collect::<Vec<_>>();
+
+        // diff_row_data shares the keys 2 and 4 with row_data
+        assert_err!(
+            submit_test_data(
+                RuntimeOrigin::signed(1),
+                TestSubmission {
+                    table: table_id.clone(),
+                    batch_id: BatchId::try_from(b"batch2".to_vec()).unwrap(),
+                    data: diff_row_data(),
+                },
+            ),
+            pallet_tables::Error::<Test>::DuplicatePrimaryKey
+        );
+
+        assert_eq!(
+            pallet_commitments::CommitmentStorageMap::<Test>::iter_prefix_values(&table_id)
+                .collect::<Vec<_>>(),
+            commitment_before
+        );
+    });
+}
+
+#[test]
+fn we_cannot_insert_more_keyed_rows_than_are_charged_for() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let table_id = create_primary_key_test_table("WITH (on_duplicate_key=reject)");
+
+        assert_err!(
+            submit_test_data(
+                RuntimeOrigin::signed(1),
+                TestSubmission {
+                    table: table_id.clone(),
+                    batch_id: BatchId::try_from(b"batch1".to_vec()).unwrap(),
+                    data: int_row_data((0..=MAX_KEYED_ROWS_PER_BATCH as i32).collect()),
+                },
+            ),
+            crate::Error::<Test, Api>::TooManyKeyedRows
+        );
+
+        assert_ok!(submit_test_data(
+            RuntimeOrigin::signed(1),
+            TestSubmission {
+                table: table_id.clone(),
+                batch_id: BatchId::try_from(b"batch2".to_vec()).unwrap(),
+                data: int_row_data((0..MAX_KEYED_ROWS_PER_BATCH as i32).collect()),
+            },
+        ));
+        assert_eq!(
+            pallet_tables::PrimaryKeyIndex::<Test>::iter_prefix(&table_id).count(),
+            MAX_KEYED_ROWS_PER_BATCH as usize
+        );
+    });
+}
+
+#[test]
+fn key_indexing_is_charged_up_front_for_tables_with_a_key_index() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let table_id = create_primary_key_test_table("WITH (on_duplicate_key=reject)");
+        let submit_data_weight = |table: TableIdentifier| {
+            crate::Call::<Test, Api>::submit_data {
+                table,
+                batch_id: BatchId::try_from(b"batch".to_vec()).unwrap(),
+                data: row_data(),
+            }
+            .get_dispatch_info()
+            .weight
+        };
+
+        let unkeyed_table_id = TableIdentifier {
+            namespace: TableNamespace::try_from(b"TEST_NAMESPACE".to_vec()).unwrap(),
+            name: TableName::try_from(b"UNKEYED_TABLE".to_vec()).unwrap(),
+        };
+        let unkeyed_weight = submit_data_weight(unkeyed_table_id);
+        assert_eq!(
+            unkeyed_weight,
+            crate::weights::SubstrateWeight::<Test>::submit_data()
+        );
+
+        let key_indexing = <() as pallet_tables::WeightInfo>::index_primary_key()
+            .saturating_mul(MAX_KEYED_ROWS_PER_BATCH.into());
+        assert_eq!(
+            submit_data_weight(table_id),
+            unkeyed_weight.saturating_add(key_indexing)
+        );
+    });
+}
+
+const ZKPAY_TEST_WALLET: &str = "44bCf7001D9C3fe8b7aA2BBaaf1B94410db31f5c";
+
+/// Register a ZK-Pay template for the table, and a treasury holding 1000 to credit payments from
+fn register_zkpay_template(table_id: &TableIdentifier) -> u64 {
+    let fields = [
+        ("SENDER", SystemFieldType::Bytes),
+        ("AMOUNT", SystemFieldType::Decimal),
+        ("NONCE", SystemFieldType::Decimal),
+    ]
+    .iter()
+    .map(|(name, field_type)| TemplateField {
+        name: TemplateFieldName::try_from(name.as_bytes().to_vec()).unwrap(),
+        field_type: *field_type,
+    })
+    .collect::<Vec<_>>();
+    assert_ok!(SystemTables::register_system_template(
+        RuntimeOrigin::root(),
+        table_id.clone(),
+        SystemTemplate {
+            request_type: SystemRequestType::ZkPay,
+            fields: TemplateFields::try_from(fields).unwrap(),
+        },
+    ));
+
+    let treasury = 9;
+    assert_ok!(Balances::force_set_balance(
+        RuntimeOrigin::root(),
+        treasury,
+        1_000
+    ));
+    assert_ok!(SystemTables::set_zkpay_treasury(
+        RuntimeOrigin::root(),
+        Some(treasury)
+    ));
+    treasury
+}
+
+/// A single ZK-Pay payment, along with any other columns of the table
+fn zkpay_row_data(amount: i64, nonce: i64, mut columns: Vec<(Field, ArrayRef)>) -> RowData {
+    let decimal = |value: i64| -> ArrayRef {
+        Arc::new(
+            Decimal256Array::from(vec![i256::from(value)])
+                .with_precision_and_scale(75, 0)
+                .unwrap(),
+        )
+    };
+    let sender = hex::decode(ZKPAY_TEST_WALLET).unwrap();
+    columns.extend([
+        (
+            Field::new("SENDER", DataType::Binary, false),
+            Arc::new(BinaryArray::from(vec![sender.as_slice()])) as ArrayRef,
+        ),
+        (
+            Field::new("AMOUNT", DataType::Decimal256(75, 0), false),
+            decimal(amount),
+        ),
+        (
+            Field::new("NONCE", DataType::Decimal256(75, 0), false),
+            decimal(nonce),
+        ),
+    ]);
+    let (fields, arrays): (Vec<_>, Vec<_>) = columns.into_iter().unzip();
+    let schema = Arc::new(Schema::new(fields));
+    let batch = RecordBatch::try_new(schema.clone(), arrays).unwrap();
+
+    record_batch_to_row_data(batch, schema)
+}
+
+#[test]
+fn inserts_into_tables_with_a_system_template_are_processed_after_quorum() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let table_id = create_single_submission_test_table_with_statement(
+            TableIdentifier::from_str_unchecked("PAYMENTS", "RECEIVED"),
+            CreateStatement::try_from(
+                b"CREATE TABLE received.payments (sender BINARY NOT NULL, amount DECIMAL(75, 0) NOT NULL, nonce DECIMAL(75, 0) NOT NULL)"
+                    .to_vec(),
+            )
+            .unwrap(),
+        );
+        let treasury = register_zkpay_template(&table_id);
+
+        assert_ok!(submit_test_data(
+            RuntimeOrigin::signed(1),
+            TestSubmission {
+                table: table_id.clone(),
+                batch_id: BatchId::try_from(b"payment1".to_vec()).unwrap(),
+                data: zkpay_row_data(400, 1, vec![]),
+            },
+        ));
+
+        let events = System::read_events_for_pallet::<Event<Test, Api>>();
+        assert!(events.iter().any(
+            |event| matches!(event, Event::SystemTableUpdate { table, .. } if table == &table_id)
+        ));
+        let wallet = eth_address_to_substrate_account_id::<Test>(ZKPAY_TEST_WALLET).unwrap();
+        assert_eq!(Balances::free_balance(wallet), 400);
+        assert_eq!(Balances::free_balance(treasury), 600);
+    });
+}
+
+#[test]
+fn zkpay_payments_are_credited_after_quorum() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let table_id = create_single_submission_test_table_with_statement(
+            TableIdentifier::from_str_unchecked("PAYMENT", "SXT_SYSTEM_ZKPAY"),
+            CreateStatement::try_from(
+                b"CREATE TABLE SXT_SYSTEM_ZKPAY.PAYMENT(block_number bigint not null, event_index int not null, sender binary not null, amount decimal(75, 0) not null, nonce decimal(75, 0) not null, primary key(block_number, event_index))"
+                    .to_vec(),
+            )
+            .unwrap(),
+        );
+        let treasury = register_zkpay_template(&table_id);
+        let wallet = eth_address_to_substrate_account_id::<Test>(ZKPAY_TEST_WALLET).unwrap();
+
+        for (batch_id, event_index, amount, nonce) in
+            [(b"payment1", 0, 300, 1), (b"payment2", 1, 200, 2)]
+        {
+            assert_ok!(submit_test_data(
+                RuntimeOrigin::signed(1),
+                TestSubmission {
+                    table: table_id.clone(),
+                    batch_id: BatchId::try_from(batch_id.to_vec()).unwrap(),
+                    data: zkpay_row_data(
+                        amount,
+                        nonce,
+                        vec![
+                            (
+                                Field::new("BLOCK_NUMBER", DataType::Int64, false),
+                                Arc::new(Int64Array::from(vec![100])) as ArrayRef,
+                            ),
+                            (
+                                Field::new("EVENT_INDEX", DataType::Int32, false),
+                                Arc::new(Int32Array::from(vec![event_index])) as ArrayRef,
+                            ),
+                        ],
+                    ),
+                },
+            ));
+        }
+
+        assert_eq!(Balances::free_balance(wallet), 500);
+        assert_eq!(Balances::free_balance(treasury), 500);
+        assert_eq!(
+            pallet_system_tables::ZkPayNonces::<Test>::get(wallet),
+            Some(2.into())
+        );
+    });
+}
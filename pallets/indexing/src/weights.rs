@@ -27,11 +27,19 @@
 
 use frame_support::{traits::Get, weights::Weight};
 use core::marker::PhantomData;
-use crate::{pallet, Config};
+use crate::{pallet, Config, MAX_CHUNKS};
+
+/// Length in bytes of the row data submitted by the `submit_data_quorum_reached` benchmark
+const BENCHMARK_ROW_DATA_LEN: u64 = 141_832;
 
 /// Weight functions for `pallet_indexing`.
 pub trait WeightInfo {
     fn submit_data() -> Weight;
+    fn submit_chunk_manifest() -> Weight;
+    fn submit_chunk(c: u32, b: u32, ) -> Weight;
+    fn submit_multi_table_data(t: u32, ) -> Weight;
+    fn submit_data_hash() -> Weight;
+    fn publish_data() -> Weight;
     /// Storage: `Permissions::Permissions` (r:1 w:0)
     /// Proof: `Permissions::Permissions` (`max_values`: None, `max_size`: Some(2193), added: 4668, mode: `MaxEncodedLen`)
     /// Storage: `Tables::Schemas` (r:1 w:0)
@@ -74,6 +82,48 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
         Weight::from_parts(submit_avg_time, submit_avg_proof)
     }
 
+    /// Submitting a manifest performs the same quorum bookkeeping as submitting data, plus
+    /// scheduling the upload deadline, but never commits to any data itself.
+    fn submit_chunk_manifest() -> Weight {
+        <SubstrateWeight<T> as WeightInfo>::submit_data_quorum_not_reached()
+                .saturating_add(T::DbWeight::get().reads_writes(1, 3))
+    }
+
+    /// Uploading a chunk that completes its batch commits to `c` chunks of `b` bytes in total.
+    /// Each chunk accesses as much storage as an insert that reaches quorum, and committing
+    /// costs as much per byte as the benchmarked insert.
+    fn submit_chunk(c: u32, b: u32, ) -> Weight {
+        let submit_no_quorum = <SubstrateWeight<T> as WeightInfo>::submit_data_quorum_not_reached();
+        let submit_w_quorum = <SubstrateWeight<T> as WeightInfo>::submit_data_quorum_reached();
+        let commit_per_byte = submit_w_quorum.ref_time() / BENCHMARK_ROW_DATA_LEN;
+
+        submit_no_quorum
+                .saturating_add(T::DbWeight::get().reads_writes(MAX_CHUNKS as u64, 1))
+                .saturating_add(Weight::from_parts(commit_per_byte, 0).saturating_mul(b as u64))
+                .saturating_add(Weight::from_parts(0, submit_w_quorum.proof_size()).saturating_mul(c as u64))
+                .saturating_add(T::DbWeight::get().reads_writes(7, 5).saturating_mul(c as u64))
+    }
+
+    /// A multi-table submission costs about as much as `t` separate submissions, plus recording
+    /// the quorum of each table.
+    fn submit_multi_table_data(t: u32, ) -> Weight {
+        <SubstrateWeight<T> as WeightInfo>::submit_data().saturating_mul(t.max(1) as u64)
+                .saturating_add(T::DbWeight::get().writes(t as u64))
+    }
+
+    /// Submitting a hash performs the same quorum bookkeeping as submitting data, plus
+    /// scheduling the publication deadline once quorum is reached.
+    fn submit_data_hash() -> Weight {
+        <SubstrateWeight<T> as WeightInfo>::submit_data_quorum_not_reached()
+                .saturating_add(T::DbWeight::get().reads_writes(1, 2))
+    }
+
+    /// Publishing commits to the data just like an insert that reaches quorum.
+    fn publish_data() -> Weight {
+        <SubstrateWeight<T> as WeightInfo>::submit_data_quorum_reached()
+                .saturating_add(T::DbWeight::get().reads_writes(1, 1))
+    }
+
     /// Storage: `Permissions::Permissions` (r:1 w:0)
     /// Proof: `Permissions::Permissions` (`max_values`: None, `max_size`: Some(2193), added: 4668, mode: `MaxEncodedLen`)
     /// Storage: `Tables::Schemas` (r:1 w:0)
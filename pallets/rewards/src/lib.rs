@@ -1,33 +1,78 @@
 //! # Rewards Pallet
 //!
 //! This pallet contains all utilities and logic related to rewards and paying them out on the
-//! SXT Chain. It is designed to check at the beginning of every block for unpaid validator
-//! rewards and payout one page of nominators(512) at a time for up to 3 validators per block until
-//! all pages for all validators are paid.
+//! SXT Chain. It checks for unpaid validator rewards when blocks have weight left over, and pays
+//! them out one page of nominators at a time, within that weight and up to `MaxPayoutsPerBlock`
+//! pages per block. A validator with many pages may be paid across several blocks; the payouts
+//! continue from the validator they stopped at.
+//!
+//! Indexers and attestors are rewarded out of a pot account. They earn points in the active era
+//! for each quorum they agree in, weighted by the bytes indexed, and for each timely attestation.
+//! Once an era has ended, the pot pays up to `WorkRewardsPerEra` for it, shared in proportion to
+//! the points earned, with the weight left after the validator payouts.
 #![cfg_attr(not(feature = "std"), no_std)]
 
 extern crate alloc;
 extern crate core;
 use alloc::vec::Vec;
 
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
 #[cfg(test)]
 mod mock;
 
 #[cfg(test)]
 mod tests;
 
+pub mod runtime_api;
+pub mod weights;
 // Re-export pallet items so that they can be accessed from the crate namespace.
 pub use pallet::*;
+pub use weights::*;
+
+/// The points earned by an indexer or attestor in an era
+#[derive(
+    Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, Default, PartialEq, Eq, RuntimeDebug,
+)]
+pub struct WorkPoints {
+    /// Points earned by agreeing in indexing quorums
+    pub indexing: u64,
+    /// Points earned by timely attestations
+    pub attestation: u64,
+}
+
+impl WorkPoints {
+    /// The points earned for all work
+    pub fn total(&self) -> u64 {
+        self.indexing.saturating_add(self.attestation)
+    }
+
+    /// Add the points of other work
+    fn saturating_accrue(&mut self, other: WorkPoints) {
+        self.indexing = self.indexing.saturating_add(other.indexing);
+        self.attestation = self.attestation.saturating_add(other.attestation);
+    }
+}
 
 /// A Pallet that enables the automated payout of validator rewards each era.
 #[allow(clippy::manual_inspect)]
 #[frame_support::pallet]
 pub mod pallet {
     // Import various useful types required by all FRAME pallets.
+    use core::ops::Bound;
+
     use frame_support::pallet_prelude::*;
-    use frame_support::weights::Weight;
+    use frame_support::traits::fungible::{Inspect, Mutate};
+    use frame_support::traits::tokens::{Fortitude, Preservation};
+    use frame_support::weights::{Weight, WeightMeter};
+    use frame_support::PalletId;
     use frame_system::pallet_prelude::{BlockNumberFor, OriginFor};
-    use pallet_staking::WeightInfo;
+    use pallet_staking::WeightInfo as _;
+    use sp_runtime::traits::{AccountIdConversion, Zero};
+    use sp_runtime::Perbill;
+    use sxt_core::rewards::WorkRewardHandler;
 
     use super::*;
 
@@ -37,14 +82,34 @@ pub mod pallet {
 
     /// Configuration trait for the rewards pallet
     #[pallet::config]
-    pub trait Config: frame_system::Config {
+    pub trait Config: frame_system::Config + pallet_balances::Config {
         /// Binding for the runtime event, typically provided by an implementation
         /// in runtime/lib.rs
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
-        /// How many payout calls may be made per block (prevents overweight).
+        /// How many pages may be paid out per block, on top of the weight limit.
         type MaxPayoutsPerBlock: Get<u32>;
+        /// A type representing the weights required by the dispatchables of this pallet.
+        type WeightInfo: WeightInfo;
+        /// The id of the pot account that pays indexers and attestors.
+        #[pallet::constant]
+        type PotId: Get<PalletId>;
+        /// The most the pot pays indexers and attestors for one era.
+        #[pallet::constant]
+        type WorkRewardsPerEra: Get<BalanceOf<Self>>;
+        /// The bytes of indexed data worth one point to each indexer agreeing in a quorum.
+        #[pallet::constant]
+        type IndexedBytesPerPoint: Get<u64>;
+        /// The points earned by an attestor for a timely attestation.
+        #[pallet::constant]
+        type TimelyAttestationPoints: Get<u64>;
+        /// How many blocks after a block an attestation of it is still timely.
+        #[pallet::constant]
+        type AttestationDeadline: Get<u32>;
     }
 
+    /// The balance type used to reward indexers and attestors
+    pub type BalanceOf<T> = <T as pallet_balances::Config>::Balance;
+
     /// The next era that we expect to pay out.
     #[pallet::storage]
     #[pallet::getter(fn next_paid_era)]
@@ -55,6 +120,37 @@ pub mod pallet {
     #[pallet::getter(fn payer_account)]
     pub type PayerAccount<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
 
+    /// The validator of the era being paid that the payouts continue from, if a previous block
+    /// ran out of weight.
+    #[pallet::storage]
+    pub type PayoutCursor<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
+
+    /// The points earned by indexers and attestors in each era that is not paid out yet.
+    #[pallet::storage]
+    pub type ErasWorkPoints<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        sp_staking::EraIndex,
+        Blake2_128Concat,
+        T::AccountId,
+        WorkPoints,
+        ValueQuery,
+    >;
+
+    /// The total points earned by indexers and attestors in each era that is not paid out yet.
+    #[pallet::storage]
+    pub type ErasTotalWorkPoints<T: Config> =
+        StorageMap<_, Twox64Concat, sp_staking::EraIndex, u64, ValueQuery>;
+
+    /// The amount shared by the indexers and attestors of an era, fixed when its payouts start.
+    #[pallet::storage]
+    pub type ErasWorkBudget<T: Config> =
+        StorageMap<_, Twox64Concat, sp_staking::EraIndex, BalanceOf<T>, OptionQuery>;
+
+    /// The next era that we expect to pay indexers and attestors for.
+    #[pallet::storage]
+    pub type NextWorkPaidEra<T: Config> = StorageValue<_, sp_staking::EraIndex, ValueQuery>;
+
     /// Errors that could occur while processing validator rewards for payout
     #[pallet::error]
     pub enum Error<T> {
@@ -66,7 +162,7 @@ pub mod pallet {
     impl<T: Config> Pallet<T> {
         /// Set the accountID used to pay out rewards
         #[pallet::call_index(0)]
-        #[pallet::weight(Weight::zero())]
+        #[pallet::weight(<T as Config>::WeightInfo::set_payer_account())]
         pub fn set_payer_account(origin: OriginFor<T>, payer: T::AccountId) -> DispatchResult {
             // Check that the extrinsic was signed by root
             frame_system::ensure_root(origin)?;
@@ -95,6 +191,10 @@ pub mod pallet {
         Payout {
             /// The validator who had a page of nominators paid out
             validator: T::AccountId,
+            /// The era that was paid out
+            era: sp_staking::EraIndex,
+            /// The page of nominators that was paid out
+            page: sp_staking::Page,
         },
         /// Events regarding status of payouts
         Status {
@@ -120,6 +220,33 @@ pub mod pallet {
             /// The account ID of the new payer
             payer: T::AccountId,
         },
+        /// An indexer or attestor was paid out of the pot for their work in an era
+        WorkRewardPaid {
+            /// The era the work was done in
+            era: sp_staking::EraIndex,
+            /// The indexer or attestor that was paid
+            worker: T::AccountId,
+            /// The amount paid
+            amount: BalanceOf<T>,
+        },
+        /// An error occurred paying an indexer or attestor
+        WorkRewardError {
+            /// The era the work was done in
+            era: sp_staking::EraIndex,
+            /// The indexer or attestor we were trying to pay
+            worker: T::AccountId,
+            /// The error received
+            error: DispatchError,
+        },
+        /// The work of an era has been fully paid
+        WorkEraPaid {
+            /// The era that was paid out
+            era: sp_staking::EraIndex,
+            /// The points earned by all indexers and attestors in the era
+            total_points: u64,
+            /// The amount shared by the indexers and attestors of the era
+            budget: BalanceOf<T>,
+        },
     }
 
     #[pallet::hooks]
@@ -127,102 +254,366 @@ pub mod pallet {
     where
         T: pallet_staking::Config,
     {
-        /// This hook is called on every block. It will check for any unpaid eras, and perform the
-        /// payouts across a number of blocks until all payouts for the previous eras have been
-        /// paid.
-        fn on_initialize(_: BlockNumberFor<T>) -> Weight {
-            let mut total_weight: Weight = Weight::zero();
-
-            // Start by getting the last era we've paid out
-            let active_era = NextPaidEra::<T>::get();
+        /// This hook is called with the weight left over in every block. It will check for any
+        /// unpaid eras, and pay out as many pages as the weight allows, continuing in the next
+        /// blocks until all payouts for the previous eras have been paid. Indexers and attestors
+        /// are paid with the weight left after the validators.
+        fn on_idle(_: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            let mut meter = WeightMeter::with_limit(remaining_weight);
+            Self::pay_out(&mut meter);
+            Self::pay_out_work(&mut meter);
+            meter.consumed()
+        }
+    }
 
-            // And the current era
-            let current_era = pallet_staking::Pallet::<T>::active_era()
+    impl<T: Config> Pallet<T>
+    where
+        T: pallet_staking::Config,
+    {
+        /// The index of the active staking era
+        fn active_era() -> sp_staking::EraIndex {
+            pallet_staking::Pallet::<T>::active_era()
                 .map(|i| i.index)
-                .unwrap_or_default();
+                .unwrap_or_default()
+        }
+
+        /// The pages of nominators of a validator that have not been paid out for an era
+        pub(crate) fn unclaimed_pages(
+            era: sp_staking::EraIndex,
+            validator: &T::AccountId,
+        ) -> Vec<sp_staking::Page> {
+            let Some(overview) = pallet_staking::ErasStakersOverview::<T>::get(era, validator)
+            else {
+                return Vec::new();
+            };
+            let claimed = pallet_staking::ClaimedRewards::<T>::get(era, validator);
+            (0..overview.page_count)
+                .filter(|page| !claimed.contains(page))
+                .collect()
+        }
+
+        /// Pay out pages of the next unpaid era within the weight left in the meter.
+        ///
+        /// Every step checks that the weight of marking the era as paid stays available, so the
+        /// progress made can always be recorded. If the weight runs out, or `MaxPayoutsPerBlock`
+        /// pages were paid, the validator being paid is stored in `PayoutCursor` and the payouts
+        /// continue from it in the next block.
+        fn pay_out(meter: &mut WeightMeter) {
+            let reserve = <T as Config>::WeightInfo::era_paid();
+            let consume = |meter: &mut WeightMeter, weight: Weight| {
+                consume_with_reserve(meter, weight, reserve)
+            };
 
-            total_weight.saturating_add(T::DbWeight::get().reads(2));
+            if !consume(meter, <T as Config>::WeightInfo::on_idle_base()) {
+                return;
+            }
 
-            if active_era >= current_era {
+            // Start by getting the last era we've paid out, and the current era
+            let paying_era = NextPaidEra::<T>::get();
+            let current_era = Self::active_era();
+            if paying_era >= current_era {
                 // We don't need to pay anything out because we are caught up.
-                return total_weight;
+                return;
             }
 
             Self::deposit_event(Event::Status {
                 current_era,
-                paying_era: active_era,
+                paying_era,
             });
 
-            let max_page_size = T::MaxExposurePageSize::get();
-            total_weight.saturating_add(T::DbWeight::get().reads(1));
-
-            let single_call_weight: Weight =
-                pallet_staking::weights::SubstrateWeight::<T>::payout_stakers_alive_staked(
-                    max_page_size,
-                );
-
-            // Only payout this many validators per block
-            let max_payouts = T::MaxPayoutsPerBlock::get() as usize;
-            total_weight.saturating_add(T::DbWeight::get().reads(1));
-
-            // We can get all the validators that need to be rewarded by querying the list of points
-            // recipients for the era
-            let rewards_for_era = pallet_staking::Pallet::<T>::eras_reward_points(active_era);
-            total_weight.saturating_add(T::DbWeight::get().reads(1));
-
-            let payer = PayerAccount::<T>::get();
-            total_weight.saturating_add(T::DbWeight::get().reads(1));
-
-            let Some(payer) = payer else {
+            let Some(payer) = PayerAccount::<T>::get() else {
                 Self::deposit_event(Event::SetupError {
                     error: Error::<T>::NoPayerSet.into(),
                 });
-                return total_weight;
+                return;
             };
 
-            // Here we start by filtering any validators from the list that have no pending rewards
-            // for the era. After that payout a single page of Nominators for each staker up to the
-            // declared MaxPayoutsPerBlock. Each page contains up to `MaxExposurePageSize` nominators
-            // (which is currently 512). A safe limit seems to be 3 pages per block which translates
-            // to 3 validators per block in this implementation
-            let reward_weight = rewards_for_era
+            if !consume(meter, <T as Config>::WeightInfo::era_reward_points()) {
+                return;
+            }
+
+            // We can get all the validators that need to be rewarded by querying the list of
+            // points recipients for the era
+            let rewards_for_era = pallet_staking::ErasRewardPoints::<T>::get(paying_era);
+            let start = PayoutCursor::<T>::get().map_or(Bound::Unbounded, Bound::Included);
+            let validators = rewards_for_era
                 .individual
-                .into_iter()
-                .filter(|(validator, points)| {
-                    pallet_staking::EraInfo::<T>::pending_rewards(active_era, validator)
-                })
-                .take(max_payouts)
-                .map(|(validator, points)| {
+                .range((start, Bound::Unbounded))
+                .map(|(validator, _points)| validator);
+
+            // Each page contains up to `MaxExposurePageSize` nominators
+            let page_weight =
+                <T as pallet_staking::Config>::WeightInfo::payout_stakers_alive_staked(
+                    T::MaxExposurePageSize::get(),
+                );
+            let max_payouts = T::MaxPayoutsPerBlock::get();
+            let mut payouts = 0;
+
+            for validator in validators {
+                loop {
+                    if payouts >= max_payouts
+                        || !consume(meter, <T as Config>::WeightInfo::next_claimable_page())
+                    {
+                        Self::store_cursor(meter, validator);
+                        return;
+                    }
+                    let Some(page) = Self::unclaimed_pages(paying_era, validator)
+                        .first()
+                        .copied()
+                    else {
+                        break;
+                    };
+                    if !meter.can_consume(page_weight.saturating_add(reserve)) {
+                        Self::store_cursor(meter, validator);
+                        return;
+                    }
+
+                    payouts += 1;
                     let origin = frame_system::RawOrigin::Signed(payer.clone()).into();
-                    match pallet_staking::Pallet::<T>::payout_stakers(
+                    match pallet_staking::Pallet::<T>::payout_stakers_by_page(
                         origin,
                         validator.clone(),
-                        active_era,
+                        paying_era,
+                        page,
                     ) {
-                        Ok(_) => {
-                            Self::deposit_event(Event::Payout { validator });
+                        Ok(post_info) => {
+                            meter.consume(post_info.actual_weight.unwrap_or(page_weight));
+                            Self::deposit_event(Event::Payout {
+                                validator: validator.clone(),
+                                era: paying_era,
+                                page,
+                            });
                         }
                         Err(err) => {
+                            meter.consume(err.post_info.actual_weight.unwrap_or(page_weight));
                             Self::deposit_event(Event::PayoutError {
-                                validator,
+                                validator: validator.clone(),
                                 error: err.error,
                             });
+                            // Move on to the next validator rather than retrying the page
+                            break;
+                        }
+                    }
+                }
+            }
+
+            // There are no remaining payouts for this era, so we record it
+            meter.consume(reserve);
+            PayoutCursor::<T>::kill();
+            NextPaidEra::<T>::set(paying_era.saturating_add(1));
+            Self::deposit_event(Event::EraPaid { index: paying_era });
+        }
+
+        /// Store the validator that the payouts continue from in the next block
+        fn store_cursor(meter: &mut WeightMeter, validator: &T::AccountId) {
+            meter.consume(<T as Config>::WeightInfo::store_cursor());
+            PayoutCursor::<T>::put(validator);
+        }
+
+        /// The pages of nominators that are not paid out yet, for every era from the next era to
+        /// pay up to the active era
+        pub fn pending_payouts() -> Vec<runtime_api::PendingPayout<T::AccountId>> {
+            (NextPaidEra::<T>::get()..Self::active_era())
+                .flat_map(|era| {
+                    pallet_staking::ErasRewardPoints::<T>::get(era)
+                        .individual
+                        .into_keys()
+                        .filter_map(move |validator| {
+                            let pages = Self::unclaimed_pages(era, &validator);
+                            (!pages.is_empty()).then_some(runtime_api::PendingPayout {
+                                era,
+                                validator,
+                                pages,
+                            })
+                        })
+                })
+                .collect()
+        }
+
+        /// Add points earned by an indexer or attestor in the active era
+        fn note_work(worker: &T::AccountId, points: WorkPoints) {
+            if points.total() == 0 {
+                return;
+            }
+            let era = Self::active_era();
+            ErasWorkPoints::<T>::mutate(era, worker, |earned| earned.saturating_accrue(points));
+            ErasTotalWorkPoints::<T>::mutate(era, |total| {
+                *total = total.saturating_add(points.total())
+            });
+        }
+
+        /// The amount the pot can pay for one era
+        fn work_budget() -> BalanceOf<T> {
+            let available =
+                <pallet_balances::Pallet<T> as Inspect<T::AccountId>>::reducible_balance(
+                    &Self::pot_account(),
+                    Preservation::Preserve,
+                    Fortitude::Polite,
+                );
+            available.min(T::WorkRewardsPerEra::get())
+        }
+
+        /// Pay indexers and attestors out of the pot within the weight left in the meter.
+        ///
+        /// Eras are paid in order, one worker at a time. Paid workers are removed from
+        /// `ErasWorkPoints`, so the payouts continue with the remaining workers in the next
+        /// block if the weight runs out.
+        fn pay_out_work(meter: &mut WeightMeter) {
+            let reserve = <T as Config>::WeightInfo::work_era_paid();
+            let consume = |meter: &mut WeightMeter, weight: Weight| {
+                consume_with_reserve(meter, weight, reserve)
+            };
+            let pot = Self::pot_account();
+
+            loop {
+                if !consume(meter, <T as Config>::WeightInfo::work_era_base()) {
+                    return;
+                }
+                let era = NextWorkPaidEra::<T>::get();
+                if era >= Self::active_era() {
+                    return;
+                }
+
+                let total_points = ErasTotalWorkPoints::<T>::get(era);
+                let mut budget = Zero::zero();
+                if total_points > 0 {
+                    if !consume(meter, <T as Config>::WeightInfo::work_era_budget()) {
+                        return;
+                    }
+                    // The budget is fixed when the payouts of the era start, so that every
+                    // worker is paid the same share of it
+                    budget = ErasWorkBudget::<T>::get(era).unwrap_or_else(|| {
+                        let budget = Self::work_budget();
+                        ErasWorkBudget::<T>::insert(era, budget);
+                        budget
+                    });
+
+                    loop {
+                        if !consume(meter, <T as Config>::WeightInfo::pay_work_reward()) {
+                            return;
+                        }
+                        let Some((worker, points)) = ErasWorkPoints::<T>::drain_prefix(era).next()
+                        else {
+                            break;
+                        };
+
+                        let amount =
+                            Perbill::from_rational(points.total(), total_points).mul_floor(budget);
+                        if amount.is_zero() {
+                            continue;
+                        }
+                        match <pallet_balances::Pallet<T> as Mutate<T::AccountId>>::transfer(
+                            &pot,
+                            &worker,
+                            amount,
+                            Preservation::Preserve,
+                        ) {
+                            Ok(_) => Self::deposit_event(Event::WorkRewardPaid {
+                                era,
+                                worker,
+                                amount,
+                            }),
+                            Err(error) => {
+                                Self::deposit_event(Event::WorkRewardError { era, worker, error })
+                            }
                         }
                     }
+                }
+
+                meter.consume(reserve);
+                ErasTotalWorkPoints::<T>::remove(era);
+                ErasWorkBudget::<T>::remove(era);
+                NextWorkPaidEra::<T>::put(era.saturating_add(1));
+                if total_points > 0 {
+                    Self::deposit_event(Event::WorkEraPaid {
+                        era,
+                        total_points,
+                        budget,
+                    });
+                }
+            }
+        }
 
-                    single_call_weight
+        /// The expected earnings of an indexer or attestor, for every era from the next era to
+        /// pay them for up to the active era.
+        ///
+        /// Eras whose payouts have not started are expected to be paid what the pot could pay
+        /// for one era now.
+        pub fn expected_work_earnings(
+            worker: T::AccountId,
+        ) -> Vec<runtime_api::ExpectedWorkEarnings<BalanceOf<T>>> {
+            let current_budget = Self::work_budget();
+            (NextWorkPaidEra::<T>::get()..=Self::active_era())
+                .filter_map(|era| {
+                    let points = ErasWorkPoints::<T>::get(era, &worker);
+                    let total_points = ErasTotalWorkPoints::<T>::get(era);
+                    if points.total() == 0 || total_points == 0 {
+                        return None;
+                    }
+                    let budget = ErasWorkBudget::<T>::get(era).unwrap_or(current_budget);
+                    Some(runtime_api::ExpectedWorkEarnings {
+                        era,
+                        points,
+                        total_points,
+                        amount: Perbill::from_rational(points.total(), total_points)
+                            .mul_floor(budget),
+                    })
                 })
-                .reduce(|w1, w2| w1.saturating_add(w2))
-                .unwrap_or(Weight::zero());
+                .collect()
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// The account of the pot that pays indexers and attestors
+        pub fn pot_account() -> T::AccountId {
+            T::PotId::get().into_account_truncating()
+        }
+    }
+
+    impl<T: Config> WorkRewardHandler<T::AccountId> for Pallet<T>
+    where
+        T: pallet_staking::Config,
+    {
+        fn note_quorum_agreement(indexer: &T::AccountId, bytes: u64) {
+            let indexing = bytes.div_ceil(T::IndexedBytesPerPoint::get().max(1)).max(1);
+            Self::note_work(
+                indexer,
+                WorkPoints {
+                    indexing,
+                    attestation: 0,
+                },
+            );
+        }
 
-            if reward_weight == Weight::zero() {
-                // This indicates there were no remaining payouts for this era, so we record it
-                Self::deposit_event(Event::EraPaid { index: active_era });
-                NextPaidEra::<T>::set(active_era + 1);
+        fn note_attestation(attestor: &T::AccountId, blocks_late: u32) {
+            if blocks_late > T::AttestationDeadline::get() {
+                return;
             }
+            Self::note_work(
+                attestor,
+                WorkPoints {
+                    indexing: 0,
+                    attestation: T::TimelyAttestationPoints::get(),
+                },
+            );
+        }
+
+        fn note_quorum_agreement_weight() -> Weight {
+            <T as Config>::WeightInfo::note_work()
+        }
+
+        fn note_attestation_weight() -> Weight {
+            <T as Config>::WeightInfo::note_work()
+        }
+    }
 
-            total_weight.saturating_add(reward_weight);
-            total_weight
+    /// Consume weight from the meter if it leaves `reserve` available
+    fn consume_with_reserve(meter: &mut WeightMeter, weight: Weight, reserve: Weight) -> bool {
+        if meter.can_consume(weight.saturating_add(reserve)) {
+            meter.consume(weight);
+            true
+        } else {
+            false
         }
     }
 }
@@ -3,7 +3,7 @@ use frame_election_provider_support::private::sp_arithmetic::FixedU128;
 use frame_election_provider_support::{onchain, SequentialPhragmen};
 use frame_support::pallet_prelude::ConstU32;
 use frame_support::traits::{ConstU128, KeyOwnerProofSystem, VariantCountOf};
-use frame_support::{derive_impl, parameter_types};
+use frame_support::{derive_impl, parameter_types, PalletId};
 use pallet_grandpa::AuthorityId as GrandpaId;
 use proof_of_sql_static_setups::io::get_or_init_from_files_with_four_points_unchecked;
 use sp_authority_discovery::AuthorityId as AuthorityDiscoveryId;
@@ -61,9 +61,19 @@ impl frame_system::Config for Test {
 
 pub const MAX_AUTHORITIES: u32 = 100_000u32;
 
+parameter_types! {
+    pub const WorkRewardsPotId: PalletId = PalletId(*b"sxt/work");
+}
+
 impl pallet_rewards::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type MaxPayoutsPerBlock = ConstU32<3>;
+    type WeightInfo = ();
+    type PotId = WorkRewardsPotId;
+    type WorkRewardsPerEra = ConstU128<1_000>;
+    type IndexedBytesPerPoint = ConstU64<100>;
+    type TimelyAttestationPoints = ConstU64<5>;
+    type AttestationDeadline = ConstU32<10>;
 }
 
 parameter_types! {
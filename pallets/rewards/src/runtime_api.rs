@@ -0,0 +1,58 @@
+//! Runtime APIs for reading the validator rewards that are not paid out yet, and the expected
+//! earnings of indexers and attestors.
+
+use alloc::vec::Vec;
+
+use codec::{Decode, Encode};
+use frame_support::pallet_prelude::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_staking::{EraIndex, Page};
+
+use crate::WorkPoints;
+
+/// The pages of nominators of a validator that are not paid out yet for an era.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct PendingPayout<AccountId> {
+    /// The era the rewards were earned in.
+    pub era: EraIndex,
+    /// The validator that earned the rewards.
+    pub validator: AccountId,
+    /// The pages of nominators that are not paid out yet.
+    pub pages: Vec<Page>,
+}
+
+/// The expected earnings of an indexer or attestor for an era that is not paid out yet.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ExpectedWorkEarnings<Balance> {
+    /// The era the work was done in.
+    pub era: EraIndex,
+    /// The points earned in the era.
+    pub points: WorkPoints,
+    /// The points earned by all indexers and attestors in the era.
+    pub total_points: u64,
+    /// The expected share of the pot.
+    pub amount: Balance,
+}
+
+sp_api::decl_runtime_apis! {
+    /// Runtime APIs for reading the validator rewards that are not paid out yet.
+    pub trait RewardsApi<AccountId> where AccountId: codec::Codec {
+        /// Returns the pages that are not paid out yet, for every validator with reward points
+        /// in the eras from the next era to pay up to the active era.
+        fn pending_payouts() -> Vec<PendingPayout<AccountId>>;
+    }
+
+    /// Runtime APIs for reading the expected earnings of indexers and attestors.
+    pub trait WorkRewardsApi<AccountId, Balance>
+    where
+        AccountId: codec::Codec,
+        Balance: codec::Codec,
+    {
+        /// Returns the expected earnings of an indexer or attestor, for each era up to the active
+        /// era that it earned points in and was not paid for yet.
+        ///
+        /// Eras whose payouts have not started are expected to be paid what the pot could pay for
+        /// one era now.
+        fn expected_work_earnings(worker: AccountId) -> Vec<ExpectedWorkEarnings<Balance>>;
+    }
+}
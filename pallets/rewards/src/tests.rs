@@ -1,6 +1,317 @@
-use crate::mock::{new_test_ext, Staking, System};
+use frame_support::traits::fungible::{Inspect, Mutate};
+use frame_support::traits::Hooks;
+use frame_support::weights::Weight;
+use pallet_staking::{
+    ActiveEra,
+    ActiveEraInfo,
+    ClaimedRewards,
+    ErasRewardPoints,
+    ErasStakersOverview,
+};
+use sp_runtime::AccountId32;
+use sp_staking::PagedExposureMetadata;
+use sxt_core::rewards::WorkRewardHandler;
+
+use crate::mock::{new_test_ext, Balances, Rewards, RuntimeEvent, System, Test};
+use crate::runtime_api::{ExpectedWorkEarnings, PendingPayout};
+use crate::{
+    ErasTotalWorkPoints,
+    ErasWorkBudget,
+    ErasWorkPoints,
+    Event,
+    NextPaidEra,
+    NextWorkPaidEra,
+    PayerAccount,
+    PayoutCursor,
+    WeightInfo,
+    WorkPoints,
+};
 
 #[test]
 fn staking_rewards_pay_out() {
     new_test_ext().execute_with(|| {});
 }
+
+/// Make era 0 payable, with a validator earning points and exposed on `page_count` pages
+fn setup_unpaid_era(page_count: u32) -> AccountId32 {
+    let validator = AccountId32::new([1; 32]);
+    ActiveEra::<Test>::put(ActiveEraInfo {
+        index: 1,
+        start: None,
+    });
+    ErasRewardPoints::<Test>::mutate(0, |points| {
+        points.total = 10;
+        points.individual.insert(validator.clone(), 10);
+    });
+    ErasStakersOverview::<Test>::insert(
+        0,
+        &validator,
+        PagedExposureMetadata {
+            total: 1000,
+            own: 1000,
+            nominator_count: 0,
+            page_count,
+        },
+    );
+    PayerAccount::<Test>::put(AccountId32::new([2; 32]));
+    validator
+}
+
+#[test]
+fn on_idle_without_weight_does_nothing() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_unpaid_era(1);
+
+        assert_eq!(Rewards::on_idle(1, Weight::zero()), Weight::zero());
+        assert_eq!(NextPaidEra::<Test>::get(), 0);
+        assert!(System::events().is_empty());
+    });
+}
+
+#[test]
+fn pending_payouts_lists_unclaimed_pages() {
+    new_test_ext().execute_with(|| {
+        let validator = setup_unpaid_era(3);
+        ClaimedRewards::<Test>::insert(0, &validator, vec![1]);
+
+        assert_eq!(
+            Rewards::pending_payouts(),
+            vec![PendingPayout {
+                era: 0,
+                validator: validator.clone(),
+                pages: vec![0, 2],
+            }]
+        );
+
+        ClaimedRewards::<Test>::insert(0, &validator, vec![0, 1, 2]);
+        assert!(Rewards::pending_payouts().is_empty());
+
+        // Eras before the next era to pay are not pending
+        NextPaidEra::<Test>::put(1);
+        ClaimedRewards::<Test>::remove(0, &validator);
+        assert!(Rewards::pending_payouts().is_empty());
+    });
+}
+
+#[test]
+fn on_idle_stores_a_cursor_when_out_of_weight() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let validator = setup_unpaid_era(1);
+
+        // Enough to find the page, but not to pay it
+        let weight = <() as WeightInfo>::on_idle_base()
+            + <() as WeightInfo>::era_reward_points()
+            + <() as WeightInfo>::next_claimable_page()
+            + <() as WeightInfo>::era_paid();
+        let consumed = Rewards::on_idle(1, weight);
+
+        assert!(consumed.all_lte(weight));
+        assert_eq!(PayoutCursor::<Test>::get(), Some(validator));
+        assert_eq!(NextPaidEra::<Test>::get(), 0);
+        assert!(!System::events()
+            .iter()
+            .any(|record| matches!(record.event, RuntimeEvent::Rewards(Event::EraPaid { .. }))));
+    });
+}
+
+#[test]
+fn on_idle_reports_payout_errors_and_finishes_the_era() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let validator = setup_unpaid_era(1);
+        PayoutCursor::<Test>::put(&validator);
+
+        // No era reward was recorded by staking, so the payout fails
+        let weight = Weight::from_parts(u64::MAX, u64::MAX);
+        let consumed = Rewards::on_idle(1, weight);
+
+        assert!(consumed.any_gt(Weight::zero()));
+        assert_eq!(NextPaidEra::<Test>::get(), 1);
+        assert_eq!(PayoutCursor::<Test>::get(), None);
+        let events = System::events()
+            .into_iter()
+            .filter_map(|record| match record.event {
+                RuntimeEvent::Rewards(event) => Some(event),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert!(matches!(
+            events.as_slice(),
+            [
+                Event::Status {
+                    current_era: 1,
+                    paying_era: 0
+                },
+                Event::PayoutError { validator: v, .. },
+                Event::EraPaid { index: 0 },
+            ] if *v == validator
+        ));
+    });
+}
+
+/// Record work for an indexer and an attestor in era 0, fund the pot and make era 0 payable
+fn setup_unpaid_work_era(pot_balance: u128) -> (AccountId32, AccountId32) {
+    let indexer = AccountId32::new([3; 32]);
+    let attestor = AccountId32::new([4; 32]);
+    ActiveEra::<Test>::put(ActiveEraInfo {
+        index: 0,
+        start: None,
+    });
+    // 300 bytes are worth 3 points
+    Rewards::note_quorum_agreement(&indexer, 300);
+    Rewards::note_attestation(&attestor, 2);
+
+    Balances::set_balance(&Rewards::pot_account(), pot_balance);
+    ActiveEra::<Test>::put(ActiveEraInfo {
+        index: 1,
+        start: None,
+    });
+    (indexer, attestor)
+}
+
+/// The work reward events deposited so far, in a deterministic order
+fn work_events() -> Vec<Event<Test>> {
+    let mut events = System::events()
+        .into_iter()
+        .filter_map(|record| match record.event {
+            RuntimeEvent::Rewards(
+                event @ (Event::WorkRewardPaid { .. }
+                | Event::WorkRewardError { .. }
+                | Event::WorkEraPaid { .. }),
+            ) => Some(event),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    events.sort_by_key(|event| match event {
+        Event::WorkRewardPaid { worker, .. } | Event::WorkRewardError { worker, .. } => {
+            Some(worker.clone())
+        }
+        _ => None,
+    });
+    events
+}
+
+#[test]
+fn work_is_noted_in_the_active_era() {
+    new_test_ext().execute_with(|| {
+        let indexer = AccountId32::new([3; 32]);
+        let attestor = AccountId32::new([4; 32]);
+
+        // Partial points are rounded up, and empty data still earns a point
+        Rewards::note_quorum_agreement(&indexer, 150);
+        Rewards::note_quorum_agreement(&indexer, 0);
+        Rewards::note_attestation(&attestor, 10);
+        // Attestations after the deadline earn nothing
+        Rewards::note_attestation(&attestor, 11);
+
+        assert_eq!(
+            ErasWorkPoints::<Test>::get(0, &indexer),
+            WorkPoints {
+                indexing: 3,
+                attestation: 0
+            }
+        );
+        assert_eq!(
+            ErasWorkPoints::<Test>::get(0, &attestor),
+            WorkPoints {
+                indexing: 0,
+                attestation: 5
+            }
+        );
+        assert_eq!(ErasTotalWorkPoints::<Test>::get(0), 8);
+    });
+}
+
+#[test]
+fn on_idle_pays_work_out_of_the_pot() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let (indexer, attestor) = setup_unpaid_work_era(10_000);
+
+        Rewards::on_idle(1, Weight::from_parts(u64::MAX, u64::MAX));
+
+        // The era budget is capped at 1_000 and shared 3:5
+        assert_eq!(Balances::balance(&indexer), 375);
+        assert_eq!(Balances::balance(&attestor), 625);
+        assert_eq!(Balances::balance(&Rewards::pot_account()), 9_000);
+        assert_eq!(
+            work_events(),
+            vec![
+                Event::WorkEraPaid {
+                    era: 0,
+                    total_points: 8,
+                    budget: 1_000
+                },
+                Event::WorkRewardPaid {
+                    era: 0,
+                    worker: indexer.clone(),
+                    amount: 375
+                },
+                Event::WorkRewardPaid {
+                    era: 0,
+                    worker: attestor.clone(),
+                    amount: 625
+                },
+            ]
+        );
+        assert_eq!(NextWorkPaidEra::<Test>::get(), 1);
+        assert_eq!(ErasWorkPoints::<Test>::iter_prefix(0).count(), 0);
+        assert_eq!(ErasTotalWorkPoints::<Test>::get(0), 0);
+        assert_eq!(ErasWorkBudget::<Test>::get(0), None);
+    });
+}
+
+#[test]
+fn on_idle_continues_work_payouts_with_the_same_budget() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_unpaid_work_era(801);
+
+        // No payer is set, so the validator payouts stop after the base weight. This is enough to
+        // fix the budget and pay one worker, but not to finish the era
+        let weight = <() as WeightInfo>::on_idle_base()
+            + <() as WeightInfo>::work_era_base()
+            + <() as WeightInfo>::work_era_budget()
+            + <() as WeightInfo>::pay_work_reward()
+            + <() as WeightInfo>::work_era_paid();
+        Rewards::on_idle(1, weight);
+
+        // The pot keeps its existential deposit
+        assert_eq!(ErasWorkBudget::<Test>::get(0), Some(800));
+        assert_eq!(ErasWorkPoints::<Test>::iter_prefix(0).count(), 1);
+        assert_eq!(NextWorkPaidEra::<Test>::get(), 0);
+
+        Rewards::on_idle(2, Weight::from_parts(u64::MAX, u64::MAX));
+
+        assert_eq!(NextWorkPaidEra::<Test>::get(), 1);
+        assert_eq!(Balances::balance(&Rewards::pot_account()), 1);
+    });
+}
+
+#[test]
+fn expected_work_earnings_shares_the_pot() {
+    new_test_ext().execute_with(|| {
+        let (indexer, attestor) = setup_unpaid_work_era(500);
+
+        assert_eq!(
+            Rewards::expected_work_earnings(indexer.clone()),
+            vec![ExpectedWorkEarnings {
+                era: 0,
+                points: WorkPoints {
+                    indexing: 3,
+                    attestation: 0
+                },
+                total_points: 8,
+                // 3/8 of the 499 the pot can pay without being reaped
+                amount: 187,
+            }]
+        );
+        assert!(Rewards::expected_work_earnings(AccountId32::new([5; 32])).is_empty());
+
+        // A fixed budget is used over the current balance of the pot
+        ErasWorkBudget::<Test>::insert(0, 800);
+        assert_eq!(Rewards::expected_work_earnings(attestor)[0].amount, 500);
+    });
+}
@@ -0,0 +1,148 @@
+//! Weights for pallet_rewards
+//!
+//! These weights count the storage accesses of the payout engine, which pays pages through
+//! `pallet_staking` and charges the staking weight for them separately. They are not
+//! benchmarked.
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_rewards.
+pub trait WeightInfo {
+	/// Weight for `set_payer_account`
+	fn set_payer_account() -> Weight;
+	/// Reading the next era to pay, the active era, the payer and the payout cursor, once per
+	/// block with payouts pending
+	fn on_idle_base() -> Weight;
+	/// Reading the reward points of the era being paid
+	fn era_reward_points() -> Weight;
+	/// Finding the next page to pay for one validator
+	fn next_claimable_page() -> Weight;
+	/// Storing where the payouts continue in the next block
+	fn store_cursor() -> Weight;
+	/// Marking an era as paid
+	fn era_paid() -> Weight;
+	/// Reading the next era to pay indexers and attestors for, once per era
+	fn work_era_base() -> Weight;
+	/// Fixing the amount shared by the indexers and attestors of an era
+	fn work_era_budget() -> Weight;
+	/// Paying one indexer or attestor out of the pot
+	fn pay_work_reward() -> Weight;
+	/// Marking the work of an era as paid
+	fn work_era_paid() -> Weight;
+	/// Recording the work points earned by an indexer or attestor
+	fn note_work() -> Weight;
+}
+
+/// Weights for pallet_rewards using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: `Rewards::PayerAccount` (w:1)
+	fn set_payer_account() -> Weight {
+		T::DbWeight::get().writes(1)
+	}
+
+	/// Storage: `Rewards::NextPaidEra` (r:1), `Staking::ActiveEra` (r:1),
+	/// `Rewards::PayerAccount` (r:1), `Rewards::PayoutCursor` (r:1)
+	fn on_idle_base() -> Weight {
+		T::DbWeight::get().reads(4)
+	}
+
+	/// Storage: `Staking::ErasRewardPoints` (r:1)
+	fn era_reward_points() -> Weight {
+		T::DbWeight::get().reads(1)
+	}
+
+	/// Storage: `Staking::ErasStakersOverview` (r:1), `Staking::ClaimedRewards` (r:1)
+	fn next_claimable_page() -> Weight {
+		T::DbWeight::get().reads(2)
+	}
+
+	/// Storage: `Rewards::PayoutCursor` (w:1)
+	fn store_cursor() -> Weight {
+		T::DbWeight::get().writes(1)
+	}
+
+	/// Storage: `Rewards::NextPaidEra` (w:1), `Rewards::PayoutCursor` (w:1)
+	fn era_paid() -> Weight {
+		T::DbWeight::get().writes(2)
+	}
+
+	/// Storage: `Rewards::NextWorkPaidEra` (r:1), `Staking::ActiveEra` (r:1),
+	/// `Rewards::ErasTotalWorkPoints` (r:1)
+	fn work_era_base() -> Weight {
+		T::DbWeight::get().reads(3)
+	}
+
+	/// Storage: `Rewards::ErasWorkBudget` (r:1 w:1), `System::Account` (r:1)
+	fn work_era_budget() -> Weight {
+		T::DbWeight::get().reads_writes(2, 1)
+	}
+
+	/// Storage: `Rewards::ErasWorkPoints` (r:1 w:1), `System::Account` (r:2 w:2)
+	fn pay_work_reward() -> Weight {
+		T::DbWeight::get().reads_writes(3, 3)
+	}
+
+	/// Storage: `Rewards::NextWorkPaidEra` (w:1), `Rewards::ErasTotalWorkPoints` (w:1),
+	/// `Rewards::ErasWorkBudget` (w:1)
+	fn work_era_paid() -> Weight {
+		T::DbWeight::get().writes(3)
+	}
+
+	/// Storage: `Staking::ActiveEra` (r:1), `Rewards::ErasWorkPoints` (r:1 w:1),
+	/// `Rewards::ErasTotalWorkPoints` (r:1 w:1)
+	fn note_work() -> Weight {
+		T::DbWeight::get().reads_writes(3, 2)
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn set_payer_account() -> Weight {
+		RocksDbWeight::get().writes(1)
+	}
+
+	fn on_idle_base() -> Weight {
+		RocksDbWeight::get().reads(4)
+	}
+
+	fn era_reward_points() -> Weight {
+		RocksDbWeight::get().reads(1)
+	}
+
+	fn next_claimable_page() -> Weight {
+		RocksDbWeight::get().reads(2)
+	}
+
+	fn store_cursor() -> Weight {
+		RocksDbWeight::get().writes(1)
+	}
+
+	fn era_paid() -> Weight {
+		RocksDbWeight::get().writes(2)
+	}
+
+	fn work_era_base() -> Weight {
+		RocksDbWeight::get().reads(3)
+	}
+
+	fn work_era_budget() -> Weight {
+		RocksDbWeight::get().reads_writes(2, 1)
+	}
+
+	fn pay_work_reward() -> Weight {
+		RocksDbWeight::get().reads_writes(3, 3)
+	}
+
+	fn work_era_paid() -> Weight {
+		RocksDbWeight::get().writes(3)
+	}
+
+	fn note_work() -> Weight {
+		RocksDbWeight::get().reads_writes(3, 2)
+	}
+}
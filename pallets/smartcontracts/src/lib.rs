@@ -1,6 +1,10 @@
 //! Smart Contract Storage Pallet
 //! This pallet allows storing and managing smart contract data using a `StorageDoubleMap`.
-//! Users can add and remove smart contracts associated with a given `Source`.
+//! Users can add, update and remove smart contracts associated with a given `Source`.
+//!
+//! When a contract is added with an ABI and the events to index, the tables indexing those events
+//! are generated from the ABI. Without a DDL statement, the namespace and any event tables not
+//! supplied are created from the generated DDL; supplied event tables must match it.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::large_enum_variant)]
@@ -26,11 +30,26 @@ pub mod pallet {
     use frame_support::Blake2_128Concat;
     use frame_system::pallet_prelude::*;
     use native_api::NativeApi;
-    use pallet_tables::UpdateTableList;
+    use pallet_tables::{CommitmentCreationCmd, UpdateTable, UpdateTableList};
+    use proof_of_sql_commitment_map::CommitmentSchemeFlags;
     use scale_info::prelude::vec::Vec;
     use sxt_core::permissions::{PermissionLevel, SmartContractsPalletPermission};
-    use sxt_core::smartcontracts::{Contract, ContractAddress};
-    use sxt_core::tables::{Source, TableIdentifier, TableType};
+    use sxt_core::smartcontracts::{
+        generate_event_tables,
+        generate_namespace_ddl,
+        validate_event_table_ddl,
+        AbiDdlError,
+        Contract,
+        ContractABI,
+        ContractAddress,
+        ContractDetails,
+        ContractUpdate,
+        EventDetailsList,
+        ImplementationHistory,
+        ImplementationHistoryEntry,
+    };
+    use sxt_core::tables::{CreateStatement, Source, TableIdentifier, TableType};
+    use sxt_core::ByteString;
 
     use super::*;
 
@@ -78,6 +97,20 @@ pub mod pallet {
         OptionQuery,
     >;
 
+    /// The implementations each upgraded proxy contract pointed to, with their starting blocks,
+    /// oldest first.
+    #[pallet::storage]
+    #[pallet::getter(fn implementation_history)]
+    pub type ImplementationHistories<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        Source,
+        Blake2_128Concat,
+        ContractAddress,
+        ImplementationHistory,
+        ValueQuery,
+    >;
+
     /// Events for the Pallet
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -92,6 +125,28 @@ pub mod pallet {
             address: ContractAddress,
         },
 
+        /// A smart contract was updated with new events or a new implementation.
+        SmartContractUpdated {
+            /// owner
+            owner: Option<T::AccountId>,
+            /// Source chain
+            source: Source,
+            /// Address
+            address: ContractAddress,
+        },
+
+        /// A proxy contract was upgraded to a new implementation.
+        ImplementationUpgraded {
+            /// Source chain
+            source: Source,
+            /// Address of the proxy
+            address: ContractAddress,
+            /// Address of the new implementation
+            implementation: ContractAddress,
+            /// The block from which the proxy points to the new implementation
+            starting_block: u64,
+        },
+
         /// A smart contract was removed from storage.
         SmartContractRemoved {
             /// owner
@@ -117,6 +172,44 @@ pub mod pallet {
 
         /// The smart contract is missing its ddl statement
         MissingDdlStatement,
+
+        /// The event tables of the smart contract cannot be generated from its ABI
+        InvalidAbi,
+
+        /// A supplied event table does not match the DDL generated from the ABI
+        DdlMismatch,
+
+        /// No contract exists for the source and address you requested
+        ContractNotFound,
+
+        /// Only proxy contracts can be upgraded to a new implementation
+        NotAProxy,
+
+        /// A new implementation must have a starting block, no earlier than the previous one
+        InvalidStartingBlock,
+
+        /// Too many implementations were recorded for this proxy contract
+        TooManyImplementations,
+
+        /// Too many events were attempted to be indexed for this smart contract
+        TooManyEvents,
+
+        /// An event or its table is already indexed for this smart contract
+        DuplicateEvent,
+
+        /// A table to create for this smart contract already exists
+        ExistingTable,
+    }
+
+    impl<T, I> From<AbiDdlError> for Error<T, I> {
+        fn from(error: AbiDdlError) -> Self {
+            match error {
+                AbiDdlError::UnparsableDdl { .. } | AbiDdlError::DdlMismatch { .. } => {
+                    Error::<T, I>::DdlMismatch
+                }
+                _ => Error::<T, I>::InvalidAbi,
+            }
+        }
     }
 
     /// Callable Functions (Extrinsics)
@@ -150,6 +243,7 @@ pub mod pallet {
 
             // Remove the contract from storage
             ContractStorage::<T, I>::remove(&source, &address);
+            ImplementationHistories::<T, I>::remove(&source, &address);
 
             if let Some(table_ids) = ContractTables::<T, I>::take(&source, &address) {
                 for (ident, table_type) in table_ids {
@@ -175,6 +269,10 @@ pub mod pallet {
         /// verifies it doesn’t already exist, emits a `SmartContractAdded` event, and registers any
         /// associated indexing tables via `pallet_tables::create_tables_inner`.
         ///
+        /// If the contract has an ABI and event details, the event tables are generated from the
+        /// ABI. Supplied tables for those events must match the generated DDL. Without a DDL
+        /// statement, the namespace and the missing event tables are created from the generated DDL.
+        ///
         /// # Parameters
         /// - `origin`: Must be either `Root` or a signed user with appropriate smart contract permissions.
         /// - `contract`: The [`Contract`] to be added. Can be a normal or proxy contract.
@@ -187,6 +285,8 @@ pub mod pallet {
         ///
         /// # Errors
         /// - [`Error::ExistingContractError`] — if a contract with the same `source` and `address` already exists.
+        /// - [`Error::InvalidAbi`] — if the event tables cannot be generated from the ABI.
+        /// - [`Error::DdlMismatch`] — if a supplied event table does not match the generated DDL.
         /// - Any error from:
         ///     - [`pallet_permissions::Pallet::ensure_root_or_permissioned`] if origin is unauthorized.
         ///     - [`pallet_tables::Pallet::create_tables_inner`] if any table creation fails.
@@ -203,29 +303,29 @@ pub mod pallet {
                 &PermissionLevel::SmartContractsPallet(SmartContractsPalletPermission::UpdateABI),
             )?;
 
-            let (source, address, target_schema, ddl_statement, starting_block) =
-                match contract.clone() {
-                    Contract::Normal(normal_contract) => (
-                        normal_contract.details.source,
-                        normal_contract.details.address,
-                        normal_contract.details.target_schema,
-                        normal_contract.details.ddl_statement,
-                        normal_contract.details.starting_block,
-                    ),
-                    Contract::Proxy(proxy_contract) => (
-                        proxy_contract.details.source,
-                        proxy_contract.details.address,
-                        proxy_contract.details.target_schema,
-                        proxy_contract.details.ddl_statement,
-                        proxy_contract.details.starting_block,
-                    ),
-                };
+            let details = contract.details().clone();
+            let abi = contract.event_abi().cloned();
+            let source = details.source;
+            let address = details.address;
 
             ensure!(
                 !ContractStorage::<T, I>::contains_key(source.clone(), address.clone()),
                 Error::<T, I>::ExistingContractError
             );
 
+            let target_schema = details
+                .target_schema
+                .ok_or(Error::<T, I>::MissingTargetSchema)?;
+            let (ddl_statement, tables) = Self::with_event_tables(
+                &source,
+                abi.as_ref(),
+                &target_schema,
+                details.event_details.as_ref(),
+                details.ddl_statement,
+                tables,
+            )?;
+            let ddl_statement = ddl_statement.ok_or(Error::<T, I>::MissingDdlStatement)?;
+
             ContractStorage::<T, I>::insert(source.clone(), address.clone(), contract);
 
             let table_ids: BoundedVec<_, _> = tables
@@ -238,15 +338,12 @@ pub mod pallet {
             ContractTables::<T, I>::insert(&source, &address, &table_ids);
 
             // If a starting block is provided, insert it into pallet_indexing for each table
-            if let Some(start_block) = starting_block {
+            if let Some(start_block) = details.starting_block {
                 for (ident, _) in table_ids.iter() {
                     pallet_indexing::BlockNumbers::<T, I>::insert(ident, start_block);
                 }
             }
 
-            let target_schema = target_schema.ok_or(Error::<T, I>::MissingTargetSchema)?;
-            let ddl_statement = ddl_statement.ok_or(Error::<T, I>::MissingDdlStatement)?;
-
             pallet_tables::Pallet::<T>::create_namespace(
                 origin.clone(),
                 target_schema,
@@ -265,5 +362,266 @@ pub mod pallet {
             pallet_tables::Pallet::<T>::create_tables_inner(origin, tables)?;
             Ok(())
         }
+
+        /// Updates a registered smart contract without dropping its tables.
+        ///
+        /// The update can replace the ABI, append event details and upgrade a proxy to a new
+        /// implementation. The tables of the appended events are generated from the ABI unless
+        /// they are supplied, in which case they must match it. Only new tables are created; the
+        /// existing tables of the contract and their commitments are left intact. New tables are
+        /// indexed from the starting block of the new implementation, or of the contract.
+        ///
+        /// # Parameters
+        /// - `origin`: Must be either `Root` or a signed user with appropriate smart contract permissions.
+        /// - `source`: The `Source` identifier for the contract.
+        /// - `address`: The address of the smart contract.
+        /// - `update`: The [`ContractUpdate`] to apply.
+        /// - `tables`: Additional [`UpdateTable`] entries to create for this contract.
+        ///
+        /// # Emits
+        /// - [`Event::ImplementationUpgraded`] — when a proxy is upgraded to a new implementation.
+        /// - [`Event::SmartContractUpdated`] — when the contract is successfully updated.
+        ///
+        /// # Errors
+        /// - [`Error::ContractNotFound`] — if no contract exists for the `source` and `address`.
+        /// - [`Error::NotAProxy`] — if a new implementation is supplied for a normal contract.
+        /// - [`Error::InvalidStartingBlock`] — if the new implementation has no starting block, or
+        ///   one before the previous implementation.
+        /// - [`Error::DuplicateEvent`] — if an appended event or its table is already indexed.
+        /// - [`Error::ExistingTable`] — if a table to create already exists.
+        /// - Any error from [`pallet_tables::Pallet::create_tables_inner`] if table creation fails.
+        #[pallet::call_index(3)]
+        #[pallet::weight(<T as pallet::Config<I>>::WeightInfo::update_smartcontract())]
+        pub fn update_smartcontract(
+            origin: OriginFor<T>,
+            source: Source,
+            address: ContractAddress,
+            update: ContractUpdate,
+            tables: UpdateTableList,
+        ) -> DispatchResult {
+            // Ensure the caller is a signed user with proper permissions
+            let owner = pallet_permissions::Pallet::<T>::ensure_root_or_permissioned(
+                origin.clone(),
+                &PermissionLevel::SmartContractsPallet(SmartContractsPalletPermission::UpdateABI),
+            )?;
+
+            let mut contract = ContractStorage::<T, I>::get(&source, &address)
+                .ok_or(Error::<T, I>::ContractNotFound)?;
+
+            let upgraded_starting_block = match update.implementation {
+                Some(implementation) => {
+                    let Contract::Proxy(proxy_contract) = &mut contract else {
+                        return Err(Error::<T, I>::NotAProxy.into());
+                    };
+                    let starting_block = Self::record_implementation(
+                        &source,
+                        &address,
+                        &proxy_contract.implementation.details,
+                        &implementation.details,
+                        proxy_contract.details.starting_block,
+                    )?;
+
+                    Self::deposit_event(Event::ImplementationUpgraded {
+                        source: source.clone(),
+                        address: address.clone(),
+                        implementation: implementation.details.address.clone(),
+                        starting_block,
+                    });
+                    proxy_contract.implementation = implementation;
+                    Some(starting_block)
+                }
+                None => None,
+            };
+
+            // The events appended after an upgrade are those of the new implementation
+            let upgraded_abi = match &contract {
+                Contract::Proxy(proxy_contract) if upgraded_starting_block.is_some() => {
+                    proxy_contract.implementation.details.abi.clone()
+                }
+                _ => None,
+            };
+            let abi = match update.abi {
+                Some(abi) => {
+                    contract.details_mut().abi = Some(abi.clone());
+                    Some(abi)
+                }
+                None => upgraded_abi.or_else(|| contract.event_abi().cloned()),
+            };
+
+            let new_events = update.event_details.unwrap_or_default();
+            let details = contract.details_mut();
+            let mut event_details = details.event_details.clone().unwrap_or_default();
+            for event in new_events.iter() {
+                ensure!(
+                    !event_details
+                        .iter()
+                        .any(|existing| existing.signature == event.signature
+                            || existing.table == event.table),
+                    Error::<T, I>::DuplicateEvent
+                );
+                event_details
+                    .try_push(event.clone())
+                    .map_err(|_| Error::<T, I>::TooManyEvents)?;
+            }
+            if !event_details.is_empty() {
+                details.event_details = Some(event_details);
+            }
+
+            let tables = match &details.target_schema {
+                Some(target_schema) => {
+                    Self::with_event_tables(
+                        &source,
+                        abi.as_ref(),
+                        target_schema,
+                        Some(&new_events),
+                        None,
+                        tables,
+                    )?
+                    .1
+                }
+                None => tables,
+            };
+            let starting_block = upgraded_starting_block.or(details.starting_block);
+
+            let mut table_ids = ContractTables::<T, I>::get(&source, &address).unwrap_or_default();
+            for table in tables.iter() {
+                ensure!(
+                    !table_ids.iter().any(|(ident, _)| *ident == table.ident)
+                        && !pallet_tables::Schemas::<T>::contains_key(
+                            &table.ident.namespace,
+                            &table.ident.name
+                        ),
+                    Error::<T, I>::ExistingTable
+                );
+                table_ids
+                    .try_push((table.ident.clone(), table.table_type.clone()))
+                    .map_err(|_| Error::<T, I>::TooManyTables)?;
+
+                if let Some(start_block) = starting_block {
+                    pallet_indexing::BlockNumbers::<T, I>::insert(&table.ident, start_block);
+                }
+            }
+
+            ContractStorage::<T, I>::insert(&source, &address, contract);
+            ContractTables::<T, I>::insert(&source, &address, table_ids);
+
+            Self::deposit_event(Event::SmartContractUpdated {
+                owner,
+                source,
+                address,
+            });
+
+            if !tables.is_empty() {
+                pallet_tables::Pallet::<T>::create_tables_inner(origin, tables)?;
+            }
+            Ok(())
+        }
+    }
+
+    impl<T: Config<I>, I: 'static> Pallet<T, I> {
+        /// Record the upgrade of a proxy from its current implementation to a new one, returning
+        /// the starting block of the new implementation.
+        ///
+        /// The first upgrade also records the implementation the proxy was registered with,
+        /// starting at its own starting block or that of the proxy.
+        fn record_implementation(
+            source: &Source,
+            address: &ContractAddress,
+            current: &ContractDetails,
+            new: &ContractDetails,
+            proxy_starting_block: Option<u64>,
+        ) -> Result<u64, Error<T, I>> {
+            let starting_block = new
+                .starting_block
+                .ok_or(Error::<T, I>::InvalidStartingBlock)?;
+
+            ImplementationHistories::<T, I>::try_mutate(source, address, |history| {
+                if history.is_empty() {
+                    history
+                        .try_push(ImplementationHistoryEntry {
+                            address: current.address.clone(),
+                            starting_block: current.starting_block.or(proxy_starting_block),
+                        })
+                        .map_err(|_| Error::<T, I>::TooManyImplementations)?;
+                }
+
+                let previous_starting_block = history
+                    .last()
+                    .and_then(|entry| entry.starting_block)
+                    .unwrap_or_default();
+                ensure!(
+                    starting_block >= previous_starting_block,
+                    Error::<T, I>::InvalidStartingBlock
+                );
+
+                history
+                    .try_push(ImplementationHistoryEntry {
+                        address: new.address.clone(),
+                        starting_block: Some(starting_block),
+                    })
+                    .map_err(|_| Error::<T, I>::TooManyImplementations)
+            })?;
+
+            Ok(starting_block)
+        }
+
+        /// Generate the event tables of a contract from its ABI.
+        ///
+        /// Supplied tables for the events are validated against the generated DDL, and the event
+        /// tables that were not supplied are generated. Without a DDL statement, the namespace
+        /// statement is generated too. Contracts without an ABI or event details are returned
+        /// unchanged.
+        fn with_event_tables(
+            source: &Source,
+            abi: Option<&ContractABI>,
+            target_schema: &ByteString,
+            event_details: Option<&EventDetailsList>,
+            ddl_statement: Option<CreateStatement>,
+            mut tables: UpdateTableList,
+        ) -> Result<(Option<CreateStatement>, UpdateTableList), Error<T, I>> {
+            let (Some(abi), Some(event_details)) = (abi, event_details) else {
+                return Ok((ddl_statement, tables));
+            };
+            if event_details.is_empty() {
+                return Ok((ddl_statement, tables));
+            }
+
+            let schema =
+                core::str::from_utf8(target_schema).map_err(|_| Error::<T, I>::InvalidAbi)?;
+            let generated = generate_event_tables(abi, schema, event_details)?;
+
+            for event_table in generated {
+                let supplied = tables.iter().find(|table| {
+                    table
+                        .ident
+                        .name
+                        .eq_ignore_ascii_case(&event_table.table.name)
+                        && table
+                            .ident
+                            .namespace
+                            .eq_ignore_ascii_case(&event_table.table.namespace)
+                });
+                match supplied {
+                    Some(supplied) => {
+                        validate_event_table_ddl(&event_table, &supplied.create_statement)?
+                    }
+                    None => tables
+                        .try_push(UpdateTable {
+                            ident: event_table.table,
+                            create_statement: event_table.create_statement,
+                            table_type: TableType::SCI,
+                            commitment: CommitmentCreationCmd::Empty(CommitmentSchemeFlags::all()),
+                            source: source.clone(),
+                        })
+                        .map_err(|_| Error::<T, I>::TooManyTables)?,
+                }
+            }
+
+            let ddl_statement = match ddl_statement {
+                Some(ddl_statement) => ddl_statement,
+                None => generate_namespace_ddl(schema)?,
+            };
+            Ok((Some(ddl_statement), tables))
+        }
     }
 }
@@ -179,10 +179,14 @@ impl pallet_commitments::Config for Test {}
 impl pallet_indexing::pallet::Config<Api> for Test {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = pallet_indexing::weights::SubstrateWeight<Test>;
+    type PublicationTimeout = ConstU64<10>;
+    type ChunkUploadTimeout = ConstU64<10>;
+    type WorkRewards = ();
 }
 
 impl pallet_system_tables::Config for Test {
     type RuntimeEvent = RuntimeEvent;
+    type TemplateAdminOrigin = frame_system::EnsureRoot<AccountId>;
 }
 
 // Build genesis storage according to the mock runtime.
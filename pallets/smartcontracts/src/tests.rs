@@ -1,6 +1,8 @@
 use frame_support::storage::bounded_vec::BoundedVec;
 use frame_support::{assert_noop, assert_ok};
 use native_api::Api;
+use pallet_tables::{CommitmentCreationCmd, UpdateTable};
+use proof_of_sql_commitment_map::CommitmentSchemeFlags;
 use sxt_core::permissions::{
     PermissionLevel,
     PermissionList,
@@ -12,14 +14,17 @@ use sxt_core::smartcontracts::{
     ContractABI,
     ContractAddress,
     ContractDetails,
+    ContractUpdate,
+    EventDetails,
     ImplementationContract,
+    ImplementationHistoryEntry,
     NormalContract,
     ProxyContract,
 };
-use sxt_core::tables::Source;
+use sxt_core::tables::{Source, TableIdentifier, TableType};
 
 use crate::mock::{new_test_ext, RuntimeOrigin, System, Test, *};
-use crate::{ContractStorage, Error, Event};
+use crate::{ContractStorage, ContractTables, Error, Event, ImplementationHistories};
 
 /// Helper macro to set permissions for a given user
 macro_rules! set_permission {
@@ -317,3 +322,518 @@ fn remove_nonexistent_smartcontract_does_not_fail() {
         ));
     });
 }
+
+/// An ABI declaring a `Transfer` event
+const TRANSFER_ABI: &str = r#"[{"type": "event", "name": "Transfer", "inputs": [
+    {"name": "from", "type": "address", "indexed": true},
+    {"name": "to", "type": "address", "indexed": true},
+    {"name": "value", "type": "uint256", "indexed": false}
+]}]"#;
+
+/// An ABI declaring `Transfer` and `Approval` events
+const TRANSFER_AND_APPROVAL_ABI: &str = r#"[
+    {"type": "event", "name": "Transfer", "inputs": [
+        {"name": "from", "type": "address", "indexed": true},
+        {"name": "to", "type": "address", "indexed": true},
+        {"name": "value", "type": "uint256", "indexed": false}
+    ]},
+    {"type": "event", "name": "Approval", "inputs": [
+        {"name": "owner", "type": "address", "indexed": true},
+        {"name": "spender", "type": "address", "indexed": true},
+        {"name": "value", "type": "uint256", "indexed": false}
+    ]}
+]"#;
+
+/// Event details indexing an event into a table
+fn create_event_details(name: &str, signature: &str, table: &str) -> EventDetails {
+    EventDetails {
+        name: BoundedVec::try_from(name.as_bytes().to_vec()).unwrap(),
+        signature: BoundedVec::try_from(signature.as_bytes().to_vec()).unwrap(),
+        table: BoundedVec::try_from(table.as_bytes().to_vec()).unwrap(),
+    }
+}
+
+/// A normal contract indexing `Transfer` events into `ERC20.TRANSFERS`, without DDL
+fn create_erc20_contract() -> Contract {
+    Contract::Normal(NormalContract {
+        details: ContractDetails {
+            source: Source::default(),
+            address: create_contract_address(),
+            abi: Some(BoundedVec::try_from(TRANSFER_ABI.as_bytes().to_vec()).unwrap()),
+            starting_block: Some(100),
+            target_schema: Some(BoundedVec::try_from(b"erc20".to_vec()).unwrap()),
+            contract_name: None,
+            event_details: Some(
+                BoundedVec::try_from(vec![EventDetails {
+                    name: BoundedVec::try_from(b"Transfer".to_vec()).unwrap(),
+                    signature: BoundedVec::try_from(b"Transfer(address,address,uint256)".to_vec())
+                        .unwrap(),
+                    table: BoundedVec::try_from(b"transfers".to_vec()).unwrap(),
+                }])
+                .unwrap(),
+            ),
+            ddl_statement: None,
+        },
+    })
+}
+
+/// A supplied `ERC20.TRANSFERS` table
+fn transfers_table(create_statement: &str) -> UpdateTable {
+    UpdateTable {
+        ident: TableIdentifier::from_str_unchecked("TRANSFERS", "ERC20"),
+        create_statement: BoundedVec::try_from(create_statement.as_bytes().to_vec()).unwrap(),
+        table_type: TableType::SCI,
+        commitment: CommitmentCreationCmd::Empty(CommitmentSchemeFlags::all()),
+        source: Source::default(),
+    }
+}
+
+/// **Test: Adding a Smart Contract Without DDL Generates Its Event Tables**
+#[test]
+fn add_smartcontract_generates_event_tables_from_the_abi() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let who = 1;
+        set_permission!(who);
+
+        assert_ok!(SmartContracts::add_smartcontract(
+            RuntimeOrigin::signed(who),
+            create_erc20_contract(),
+            Default::default(),
+        ));
+
+        let ident = TableIdentifier::from_str_unchecked("TRANSFERS", "ERC20");
+        assert_eq!(
+            ContractTables::<Test, Api>::get(Source::default(), create_contract_address())
+                .unwrap()
+                .into_inner(),
+            vec![(ident.clone(), TableType::SCI)]
+        );
+        assert!(pallet_tables::Schemas::<Test>::contains_key(
+            &ident.namespace,
+            &ident.name
+        ));
+        assert_eq!(
+            pallet_indexing::BlockNumbers::<Test, Api>::get(&ident),
+            Some(100)
+        );
+    });
+}
+
+/// **Test: Event Tables Missing From a Supplied DDL Are Generated**
+#[test]
+fn add_smartcontract_with_ddl_generates_the_event_tables_not_supplied() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let who = 1;
+        set_permission!(who);
+
+        let Contract::Normal(mut contract) = create_erc20_contract() else {
+            unreachable!()
+        };
+        contract.details.ddl_statement =
+            Some(BoundedVec::try_from(b"CREATE SCHEMA IF NOT EXISTS ERC20".to_vec()).unwrap());
+
+        assert_ok!(SmartContracts::add_smartcontract(
+            RuntimeOrigin::signed(who),
+            Contract::Normal(contract),
+            Default::default(),
+        ));
+
+        let ident = TableIdentifier::from_str_unchecked("TRANSFERS", "ERC20");
+        assert_eq!(
+            ContractTables::<Test, Api>::get(Source::default(), create_contract_address())
+                .unwrap()
+                .into_inner(),
+            vec![(ident.clone(), TableType::SCI)]
+        );
+        assert!(pallet_tables::Schemas::<Test>::contains_key(
+            &ident.namespace,
+            &ident.name
+        ));
+    });
+}
+
+/// **Test: Supplied Event Tables Must Match the ABI**
+#[test]
+fn add_smartcontract_validates_supplied_event_tables() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let who = 1;
+        set_permission!(who);
+
+        let mismatched = transfers_table(
+            "CREATE TABLE ERC20.TRANSFERS (block_number BIGINT NOT NULL, transaction_hash BINARY NOT NULL, \
+             event_index INT NOT NULL, time_stamp TIMESTAMP NOT NULL, contract_address BINARY NOT NULL, \
+             from BINARY NOT NULL, to BINARY NOT NULL, value BIGINT NOT NULL, \
+             PRIMARY KEY (block_number, transaction_hash, event_index))",
+        );
+        assert_noop!(
+            SmartContracts::add_smartcontract(
+                RuntimeOrigin::signed(who),
+                create_erc20_contract(),
+                BoundedVec::try_from(vec![mismatched]).unwrap(),
+            ),
+            Error::<Test, Api>::DdlMismatch
+        );
+
+        let matching = transfers_table(
+            "CREATE TABLE ERC20.TRANSFERS (block_number BIGINT NOT NULL, transaction_hash BINARY NOT NULL, \
+             event_index INT NOT NULL, time_stamp TIMESTAMP NOT NULL, contract_address BINARY NOT NULL, \
+             from BINARY NOT NULL, to BINARY NOT NULL, value DECIMAL(75, 0) NOT NULL, \
+             PRIMARY KEY (block_number, transaction_hash, event_index))",
+        );
+        assert_ok!(SmartContracts::add_smartcontract(
+            RuntimeOrigin::signed(who),
+            create_erc20_contract(),
+            BoundedVec::try_from(vec![matching]).unwrap(),
+        ));
+    });
+}
+
+/// **Test: Event Tables Cannot Be Generated From an Invalid ABI**
+#[test]
+fn add_smartcontract_with_an_invalid_abi_fails() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let who = 1;
+        set_permission!(who);
+
+        let Contract::Normal(mut contract) = create_erc20_contract() else {
+            unreachable!()
+        };
+        contract.details.abi = Some(create_contract_abi());
+
+        assert_noop!(
+            SmartContracts::add_smartcontract(
+                RuntimeOrigin::signed(who),
+                Contract::Normal(contract),
+                Default::default(),
+            ),
+            Error::<Test, Api>::InvalidAbi
+        );
+    });
+}
+
+/// **Test: Appending Events Creates Their Tables and Keeps the Existing Ones**
+#[test]
+fn update_smartcontract_appends_events_and_tables() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let who = 1;
+        set_permission!(who);
+        let source = Source::default();
+        let address = create_contract_address();
+
+        assert_ok!(SmartContracts::add_smartcontract(
+            RuntimeOrigin::signed(who),
+            create_erc20_contract(),
+            Default::default(),
+        ));
+        let transfers = TableIdentifier::from_str_unchecked("TRANSFERS", "ERC20");
+        let transfers_schema =
+            pallet_tables::Schemas::<Test>::get(&transfers.namespace, &transfers.name);
+
+        let approval =
+            create_event_details("Approval", "Approval(address,address,uint256)", "approvals");
+        assert_ok!(SmartContracts::update_smartcontract(
+            RuntimeOrigin::signed(who),
+            source.clone(),
+            address.clone(),
+            ContractUpdate {
+                abi: Some(
+                    BoundedVec::try_from(TRANSFER_AND_APPROVAL_ABI.as_bytes().to_vec()).unwrap()
+                ),
+                event_details: Some(BoundedVec::try_from(vec![approval.clone()]).unwrap()),
+                implementation: None,
+            },
+            Default::default(),
+        ));
+
+        let approvals = TableIdentifier::from_str_unchecked("APPROVALS", "ERC20");
+        assert_eq!(
+            ContractTables::<Test, Api>::get(&source, &address)
+                .unwrap()
+                .into_inner(),
+            vec![
+                (transfers.clone(), TableType::SCI),
+                (approvals.clone(), TableType::SCI)
+            ]
+        );
+        assert_eq!(
+            pallet_tables::Schemas::<Test>::get(&transfers.namespace, &transfers.name),
+            transfers_schema
+        );
+        assert!(pallet_tables::Schemas::<Test>::contains_key(
+            &approvals.namespace,
+            &approvals.name
+        ));
+        assert_eq!(
+            pallet_indexing::BlockNumbers::<Test, Api>::get(&approvals),
+            Some(100)
+        );
+
+        let contract = ContractStorage::<Test, Api>::get(&source, &address).unwrap();
+        assert_eq!(
+            contract.details().event_details.as_ref().unwrap().last(),
+            Some(&approval)
+        );
+        System::assert_has_event(
+            Event::SmartContractUpdated {
+                owner: Some(who),
+                source: source.clone(),
+                address: address.clone(),
+            }
+            .into(),
+        );
+
+        // The same event cannot be appended twice
+        assert_noop!(
+            SmartContracts::update_smartcontract(
+                RuntimeOrigin::signed(who),
+                source,
+                address,
+                ContractUpdate {
+                    abi: None,
+                    event_details: Some(BoundedVec::try_from(vec![approval]).unwrap()),
+                    implementation: None,
+                },
+                Default::default(),
+            ),
+            Error::<Test, Api>::DuplicateEvent
+        );
+    });
+}
+
+/// **Test: Upgrading a Proxy Records Its Implementation History**
+#[test]
+fn update_smartcontract_upgrades_proxy_implementations() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let who = 1;
+        set_permission!(who);
+        let source = Source::default();
+        let proxy_address = create_contract_address();
+        let implementation = |address: u8, starting_block: Option<u64>| ImplementationContract {
+            details: ContractDetails {
+                source: source.clone(),
+                address: BoundedVec::try_from(vec![address; 20]).unwrap(),
+                abi: None,
+                starting_block,
+                target_schema: None,
+                contract_name: None,
+                event_details: None,
+                ddl_statement: None,
+            },
+        };
+
+        assert_ok!(SmartContracts::add_smartcontract(
+            RuntimeOrigin::signed(who),
+            Contract::Proxy(ProxyContract {
+                details: ContractDetails {
+                    source: source.clone(),
+                    address: proxy_address.clone(),
+                    abi: None,
+                    starting_block: Some(100),
+                    target_schema: Some(BoundedVec::try_from(b"test".to_vec()).unwrap()),
+                    contract_name: None,
+                    event_details: None,
+                    ddl_statement: Some(BoundedVec::try_from(b"test".to_vec()).unwrap()),
+                },
+                implementation: implementation(1, None),
+            }),
+            Default::default(),
+        ));
+
+        let upgrade = |implementation: ImplementationContract| {
+            SmartContracts::update_smartcontract(
+                RuntimeOrigin::signed(who),
+                source.clone(),
+                proxy_address.clone(),
+                ContractUpdate {
+                    abi: None,
+                    event_details: None,
+                    implementation: Some(implementation),
+                },
+                Default::default(),
+            )
+        };
+
+        // A new implementation needs a starting block
+        assert_noop!(
+            upgrade(implementation(2, None)),
+            Error::<Test, Api>::InvalidStartingBlock
+        );
+        assert_ok!(upgrade(implementation(2, Some(200))));
+        // Implementations cannot start before the previous one
+        assert_noop!(
+            upgrade(implementation(3, Some(150))),
+            Error::<Test, Api>::InvalidStartingBlock
+        );
+
+        assert_eq!(
+            ImplementationHistories::<Test, Api>::get(&source, &proxy_address).into_inner(),
+            vec![
+                ImplementationHistoryEntry {
+                    address: BoundedVec::try_from(vec![1; 20]).unwrap(),
+                    starting_block: Some(100),
+                },
+                ImplementationHistoryEntry {
+                    address: BoundedVec::try_from(vec![2; 20]).unwrap(),
+                    starting_block: Some(200),
+                },
+            ]
+        );
+        let Some(Contract::Proxy(proxy)) =
+            ContractStorage::<Test, Api>::get(&source, &proxy_address)
+        else {
+            panic!("the proxy should be stored");
+        };
+        assert_eq!(proxy.implementation, implementation(2, Some(200)));
+        System::assert_has_event(
+            Event::ImplementationUpgraded {
+                source: source.clone(),
+                address: proxy_address.clone(),
+                implementation: BoundedVec::try_from(vec![2; 20]).unwrap(),
+                starting_block: 200,
+            }
+            .into(),
+        );
+
+        // Removing the proxy removes its history
+        assert_ok!(SmartContracts::remove_smartcontract(
+            RuntimeOrigin::signed(who),
+            source.clone(),
+            proxy_address.clone(),
+        ));
+        assert!(ImplementationHistories::<Test, Api>::get(&source, &proxy_address).is_empty());
+    });
+}
+
+/// **Test: Events Appended With an Upgrade Are Validated Against the New Implementation**
+#[test]
+fn update_smartcontract_uses_the_abi_of_the_upgraded_implementation() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let who = 1;
+        set_permission!(who);
+        let source = Source::default();
+        let proxy_address = create_contract_address();
+        let implementation =
+            |address: u8, abi: &str, starting_block: Option<u64>| ImplementationContract {
+                details: ContractDetails {
+                    source: source.clone(),
+                    address: BoundedVec::try_from(vec![address; 20]).unwrap(),
+                    abi: Some(BoundedVec::try_from(abi.as_bytes().to_vec()).unwrap()),
+                    starting_block,
+                    target_schema: None,
+                    contract_name: None,
+                    event_details: None,
+                    ddl_statement: None,
+                },
+            };
+
+        assert_ok!(SmartContracts::add_smartcontract(
+            RuntimeOrigin::signed(who),
+            Contract::Proxy(ProxyContract {
+                details: ContractDetails {
+                    source: source.clone(),
+                    address: proxy_address.clone(),
+                    abi: Some(BoundedVec::try_from(TRANSFER_ABI.as_bytes().to_vec()).unwrap()),
+                    starting_block: Some(100),
+                    target_schema: Some(BoundedVec::try_from(b"erc20".to_vec()).unwrap()),
+                    contract_name: None,
+                    event_details: None,
+                    ddl_statement: None,
+                },
+                implementation: implementation(1, TRANSFER_ABI, None),
+            }),
+            Default::default(),
+        ));
+
+        // Approval is only declared by the new implementation
+        assert_ok!(SmartContracts::update_smartcontract(
+            RuntimeOrigin::signed(who),
+            source.clone(),
+            proxy_address.clone(),
+            ContractUpdate {
+                abi: None,
+                event_details: Some(
+                    BoundedVec::try_from(vec![create_event_details(
+                        "Approval",
+                        "Approval(address,address,uint256)",
+                        "approvals",
+                    )])
+                    .unwrap(),
+                ),
+                implementation: Some(implementation(2, TRANSFER_AND_APPROVAL_ABI, Some(200))),
+            },
+            Default::default(),
+        ));
+
+        let approvals = TableIdentifier::from_str_unchecked("APPROVALS", "ERC20");
+        assert!(pallet_tables::Schemas::<Test>::contains_key(
+            &approvals.namespace,
+            &approvals.name
+        ));
+        assert_eq!(
+            pallet_indexing::BlockNumbers::<Test, Api>::get(&approvals),
+            Some(200)
+        );
+    });
+}
+
+/// **Test: Only Registered Proxies Can Be Upgraded**
+#[test]
+fn update_smartcontract_rejects_unknown_contracts_and_non_proxies() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let who = 1;
+        set_permission!(who);
+        let source = Source::default();
+        let address = create_contract_address();
+        let upgrade = ContractUpdate {
+            abi: None,
+            event_details: None,
+            implementation: Some(ImplementationContract {
+                details: ContractDetails {
+                    source: source.clone(),
+                    address: BoundedVec::try_from(vec![2; 20]).unwrap(),
+                    abi: None,
+                    starting_block: Some(200),
+                    target_schema: None,
+                    contract_name: None,
+                    event_details: None,
+                    ddl_statement: None,
+                },
+            }),
+        };
+
+        assert_noop!(
+            SmartContracts::update_smartcontract(
+                RuntimeOrigin::signed(who),
+                source.clone(),
+                address.clone(),
+                upgrade.clone(),
+                Default::default(),
+            ),
+            Error::<Test, Api>::ContractNotFound
+        );
+
+        assert_ok!(SmartContracts::add_smartcontract(
+            RuntimeOrigin::signed(who),
+            create_erc20_contract(),
+            Default::default(),
+        ));
+        assert_noop!(
+            SmartContracts::update_smartcontract(
+                RuntimeOrigin::signed(who),
+                source,
+                address,
+                upgrade,
+                Default::default(),
+            ),
+            Error::<Test, Api>::NotAProxy
+        );
+    });
+}
@@ -12,6 +12,8 @@ pub trait WeightInfo {
 	fn add_smartcontract() -> Weight;
 	/// dummy comment
 	fn remove_smartcontract() -> Weight;
+	/// dummy comment
+	fn update_smartcontract() -> Weight;
 }
 
 /// Weights for pallet_template using the Substrate node and recommended hardware.
@@ -24,6 +26,10 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	fn remove_smartcontract() -> Weight {
 		Weight::from_parts(0, 0 )
 	}
+
+	fn update_smartcontract() -> Weight {
+		Weight::from_parts(0, 0)
+	}
 }
 
 // For backwards compatibility and tests
@@ -35,4 +41,8 @@ impl WeightInfo for () {
 	fn remove_smartcontract() -> Weight {
 		Weight::from_parts(0, 0 )
 	}
+
+	fn update_smartcontract() -> Weight {
+		Weight::from_parts(0, 0)
+	}
 }
\ No newline at end of file
@@ -1,6 +1,14 @@
 //! # System Tables Pallet
 //! This pallet holds logic for parsing insert statements received via indexing and
 //! performing any system related on-chain state transitions
+//!
+//! Inserts into a table are processed if a system template is registered for it. The template
+//! names the fields parsed from the inserts and the handler processing them: EVM messages,
+//! staking, or ZK-Pay payments. ZK-Pay payments are indexed with `SENDER`, `AMOUNT` and `NONCE`
+//! columns, and credited to the account of the sender, once per nonce. Payments that can't be
+//! credited are kept to be retried, without holding up the later payments of the sender. Staking rows
+//! identifying their EVM event with `BLOCK_NUMBER`, `TRANSACTION_HASH` and `EVENT_INDEX` columns
+//! are applied once per event.
 #![cfg_attr(not(feature = "std"), no_std)]
 
 extern crate alloc;
@@ -16,34 +24,73 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
-mod messages;
+pub mod messages;
+pub mod migrations;
 mod parse;
+pub mod runtime_api;
+pub use parse::{
+    StakingEventIssue,
+    StakingEventSource,
+    StakingSystemRequest,
+    SystemFieldType,
+    SystemRequestType,
+    SystemTemplate,
+    TemplateField,
+    TemplateFieldName,
+    TemplateFields,
+    STAKING_EVENT_RETENTION,
+};
 
 #[allow(clippy::manual_inspect)]
 #[frame_support::pallet]
 pub mod pallet {
+    use alloc::collections::BTreeMap;
     use alloc::vec::Vec;
 
+    use commitment_sql::sqlparser_data_type_to_proof_of_sql_column_type;
     use frame_support::dispatch::RawOrigin;
     use frame_support::pallet_prelude::*;
+    use frame_support::traits::fungible;
+    use frame_support::traits::tokens::Preservation;
     use frame_system::pallet_prelude::*;
     use itertools::Itertools;
     use on_chain_table::OnChainTable;
     use pallet_session::historical::IdentificationTuple;
-    use parse::{table_to_request, SystemRequest};
+    use parse::{default_templates, table_to_request, SystemRequest};
     use sp_core::U256;
     use sp_runtime::traits::{StaticLookup, UniqueSaturatedInto};
     use sp_runtime::{Perbill, SaturatedConversion};
     use sp_staking::offence::{OffenceDetails, OnOffenceHandler};
-    use sp_staking::SessionIndex;
+    use sp_staking::{SessionIndex, StakingAccount};
     use sxt_core::permissions::{PermissionLevel, PermissionList};
-    use sxt_core::tables::{extract_schema_uuid, TableIdentifier, TableName, TableNamespace};
+    use sxt_core::tables::{
+        create_statement_to_sqlparser_remove_with,
+        extract_schema_uuid,
+        TableIdentifier,
+        TableName,
+        TableNamespace,
+    };
     use sxt_core::utils::{convert_account_id, eth_address_to_substrate_account_id};
 
     use super::*;
-    use crate::parse::{StakingSystemRequest, SystemFieldValue, SystemRequestType};
+    use crate::messages::MessageType;
+    use crate::parse::{
+        StakingEventIssue,
+        StakingEventSource,
+        StakingSystemRequest,
+        SystemFieldType,
+        SystemFieldValue,
+        SystemRequestType,
+        SystemTemplate,
+    };
+    use crate::runtime_api::{MirroredStaker, MirroredUnbonding, MAX_MIRRORED_STAKERS_PAGE};
+
+    /// The storage version of the pallet, 1 since system templates are stored on chain and 2
+    /// since the default staking templates declare the source event fields
+    pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     #[pallet::config]
@@ -56,12 +103,87 @@ pub mod pallet {
     {
         /// The overarching runtime event type.
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// The origin allowed to register and remove system templates
+        type TemplateAdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+    }
+
+    /// The templates of the system tables, mapping each table to the fields parsed from its
+    /// inserts and the handler processing them
+    #[pallet::storage]
+    pub type SystemTemplates<T: Config> =
+        StorageMap<_, Blake2_128Concat, TableIdentifier, SystemTemplate, OptionQuery>;
+
+    /// Genesis configuration, registering the default system templates
+    #[pallet::genesis_config]
+    #[derive(frame_support::DefaultNoBound)]
+    pub struct GenesisConfig<T: Config> {
+        #[serde(skip)]
+        _marker: core::marker::PhantomData<T>,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        fn build(&self) {
+            for (table, template) in default_templates() {
+                SystemTemplates::<T>::insert(table, template);
+            }
+        }
     }
 
     /// The last processed message
     #[pallet::storage]
     pub type MessageNonce<T: Config> = StorageValue<_, U256, ValueQuery>;
 
+    /// The nonce of the last ZK-Pay payment processed for each payer
+    #[pallet::storage]
+    pub type ZkPayNonces<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, U256>;
+
+    /// A ZK-Pay payment that could not be credited
+    #[derive(Clone, Eq, PartialEq, Encode, Decode, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+    pub struct FailedZkPayment {
+        /// The ethereum address of the payer
+        pub sender: [u8; 20],
+        /// The amount to credit
+        pub amount: u128,
+    }
+
+    /// The ZK-Pay payments that could not be credited, by payer and nonce, until they are
+    /// retried with `retry_zkpay_payment`
+    #[pallet::storage]
+    pub type FailedZkPayments<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        U256,
+        FailedZkPayment,
+        OptionQuery,
+    >;
+
+    /// The account ZK-Pay payments are credited from. Without it, payments fail.
+    #[pallet::storage]
+    pub type ZkPayTreasury<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
+
+    /// The EVM block of each staking event applied for a staker, by transaction hash and event
+    /// index. Events below the watermark of the staker's last event are pruned.
+    #[pallet::storage]
+    pub type ProcessedStakingEvents<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        ([u8; 32], u32),
+        u64,
+        OptionQuery,
+    >;
+
+    /// The latest staking event applied for each staker, whose watermark bounds the events
+    /// kept in `ProcessedStakingEvents`
+    #[pallet::storage]
+    pub type LastStakingEvents<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, StakingEventSource, OptionQuery>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -69,19 +191,76 @@ pub mod pallet {
         MessageReceived {
             /// The ethereum address of the sender
             sender: [u8; 20],
+            /// The kind of the message
+            message_type: MessageType,
             /// The message payload received
             payload: Vec<u8>,
         },
-        /// There was an error processing an evm message
+        /// There was an error processing an evm message or system table insert
         MessageProcessingError {
             /// The error received
             error: DispatchError,
+            /// The kind of the message, if the error came from a message that could be decoded
+            message_type: Option<MessageType>,
         },
         /// Emitted when a validator is chilled by the offence handler
         ValidatorForceChilled {
             /// The validator that was forcefully chilled
             validator: T::AccountId,
         },
+        /// A system template was registered, replacing any previous template of the table
+        SystemTemplateRegistered {
+            /// The system table
+            table: TableIdentifier,
+            /// The handler processing the inserts into the table
+            request_type: SystemRequestType,
+        },
+        /// A system template was removed, inserts into the table are no longer processed
+        SystemTemplateRemoved {
+            /// The system table
+            table: TableIdentifier,
+        },
+        /// A ZK-Pay payment was credited to the account of its payer
+        ZkPayCredited {
+            /// The ethereum address of the payer
+            sender: [u8; 20],
+            /// The account credited
+            account: T::AccountId,
+            /// The amount credited
+            amount: u128,
+            /// The nonce of the payment
+            nonce: U256,
+            /// The treasury the credit was transferred from
+            treasury: T::AccountId,
+        },
+        /// A ZK-Pay payment could not be credited. Its nonce is used all the same. Payments with
+        /// a valid amount are kept in `FailedZkPayments` to be retried with
+        /// `retry_zkpay_payment`.
+        ZkPayFailed {
+            /// The ethereum address of the payer
+            sender: [u8; 20],
+            /// The nonce of the payment
+            nonce: U256,
+            /// The reason the payment was not credited
+            error: DispatchError,
+        },
+        /// The ZK-Pay treasury was set or cleared
+        ZkPayTreasurySet {
+            /// The new treasury, or `None` if payments are no longer credited
+            treasury: Option<T::AccountId>,
+        },
+        /// A staking row was skipped or applied out of order, and the staking state may need
+        /// to be reconciled with the EVM contract
+        SystemTableError {
+            /// The system table the row was inserted into
+            table: TableIdentifier,
+            /// The account of the staker
+            staker: T::AccountId,
+            /// The EVM event the row was indexed from
+            source: StakingEventSource,
+            /// Why the row was reported
+            issue: StakingEventIssue,
+        },
     }
 
     #[pallet::error]
@@ -110,6 +289,38 @@ pub mod pallet {
         ErrorParsingNominations,
         /// Empty Nomination Set
         EmptyNominationSet,
+        /// The message envelope version is not supported
+        UnsupportedMessageVersion,
+        /// The message can only be sent by a validator
+        NotValidator,
+        /// The message can only be sent by a bonded staker
+        NotBonded,
+        /// The commission is lower than the minimum commission of the staking pallet
+        CommissionTooLow,
+        /// The reward destination is not a valid account
+        InvalidPayee,
+        /// The table of the system template doesn't exist
+        TemplateTableNotFound,
+        /// The create statement of the template's table couldn't be parsed
+        TemplateSchemaParseError,
+        /// A field of the system template is not a column of the table
+        TemplateColumnNotFound,
+        /// A field of the system template doesn't match the type of its column
+        TemplateColumnTypeMismatch,
+        /// The system template declares the same field more than once
+        DuplicateTemplateField,
+        /// The system template lacks a field its handler reads
+        MissingTemplateField,
+        /// There is no system template for the table
+        TemplateNotFound,
+        /// The ZK-Pay payment amount is zero or doesn't fit a balance
+        InvalidZkPayAmount,
+        /// The staking row identifies its EVM event partially, or with invalid values
+        InvalidStakingEventSource,
+        /// There is no ZK-Pay treasury to credit payments from
+        ZkPayTreasuryNotSet,
+        /// There is no failed ZK-Pay payment with this payer and nonce
+        ZkPayPaymentNotFound,
     }
 
     #[pallet::call]
@@ -127,20 +338,248 @@ pub mod pallet {
             LastProcessedUserNonce::<T>::set(eth_sender, Some(U256::from(new_nonce)));
             Ok(())
         }
+
+        /// Register the template of a system table, replacing any previous template.
+        ///
+        /// Every field must be a column of the table, as created in `pallet_tables`, with a
+        /// matching type, and the fields read by the handler must be declared.
+        #[pallet::call_index(1)]
+        #[pallet::weight(register_system_template_weight::<T>(table))]
+        pub fn register_system_template(
+            origin: OriginFor<T>,
+            table: TableIdentifier,
+            template: SystemTemplate,
+        ) -> DispatchResult {
+            T::TemplateAdminOrigin::ensure_origin(origin)?;
+            validate_template::<T>(&table, &template)?;
+
+            let request_type = template.request_type;
+            SystemTemplates::<T>::insert(&table, template);
+            Self::deposit_event(Event::SystemTemplateRegistered {
+                table,
+                request_type,
+            });
+            Ok(())
+        }
+
+        /// Sudo call to set the account ZK-Pay payments are credited from, or to stop crediting
+        /// payments when `None`
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::DbWeight::get().writes(1))]
+        pub fn set_zkpay_treasury(
+            origin: OriginFor<T>,
+            treasury: Option<T::AccountId>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            ZkPayTreasury::<T>::set(treasury.clone());
+            Self::deposit_event(Event::ZkPayTreasurySet { treasury });
+            Ok(())
+        }
+
+        /// Sudo call to set the nonce of the last ZK-Pay payment processed for a payer, to
+        /// skip or replay payments
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::DbWeight::get().writes(1))]
+        pub fn set_zkpay_nonce(
+            origin: OriginFor<T>,
+            eth_wallet: String,
+            new_nonce: u64,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            let payer = eth_address_to_substrate_account_id::<T>(&eth_wallet)?;
+            ZkPayNonces::<T>::insert(payer, U256::from(new_nonce));
+            Ok(())
+        }
+
+        /// Sudo call to credit a ZK-Pay payment that failed, once the treasury can cover it
+        #[pallet::call_index(5)]
+        #[pallet::weight(
+            T::DbWeight::get().reads_writes(2, 1).saturating_add(
+                <<T as pallet_balances::Config>::WeightInfo as pallet_balances::WeightInfo>::transfer_keep_alive()
+            )
+        )]
+        pub fn retry_zkpay_payment(
+            origin: OriginFor<T>,
+            eth_wallet: String,
+            nonce: u64,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            let account = eth_address_to_substrate_account_id::<T>(&eth_wallet)?;
+            let nonce = U256::from(nonce);
+            let payment = FailedZkPayments::<T>::get(&account, nonce)
+                .ok_or(Error::<T>::ZkPayPaymentNotFound)?;
+
+            let treasury = ZkPayTreasury::<T>::get().ok_or(Error::<T>::ZkPayTreasuryNotSet)?;
+            credit_zkpay::<T>(&account, payment.amount, &treasury)?;
+            FailedZkPayments::<T>::remove(&account, nonce);
+            Self::deposit_event(Event::ZkPayCredited {
+                sender: payment.sender,
+                account,
+                amount: payment.amount,
+                nonce,
+                treasury,
+            });
+            Ok(())
+        }
+
+        /// Remove the template of a system table, so inserts into it are no longer processed
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+        pub fn remove_system_template(
+            origin: OriginFor<T>,
+            table: TableIdentifier,
+        ) -> DispatchResult {
+            T::TemplateAdminOrigin::ensure_origin(origin)?;
+            SystemTemplates::<T>::take(&table).ok_or(Error::<T>::TemplateNotFound)?;
+
+            Self::deposit_event(Event::SystemTemplateRemoved { table });
+            Ok(())
+        }
+    }
+
+    /// The reference time of parsing and checking one byte of a table's create statement, in
+    /// picoseconds
+    const TEMPLATE_SCHEMA_WEIGHT_PER_BYTE: u64 = 100_000;
+
+    /// The weight of registering a template for `table`.
+    ///
+    /// The table's schema is read and parsed to validate the template, so registering is
+    /// charged per byte of its create statement, which is also part of the proof.
+    fn register_system_template_weight<T: Config>(table: &TableIdentifier) -> Weight {
+        let schema_len = pallet_tables::Schemas::<T>::decode_len(&table.namespace, &table.name)
+            .unwrap_or_default() as u64;
+
+        T::DbWeight::get()
+            .reads_writes(1, 1)
+            .saturating_add(Weight::from_parts(
+                TEMPLATE_SCHEMA_WEIGHT_PER_BYTE.saturating_mul(schema_len),
+                schema_len,
+            ))
+    }
+
+    /// Check a template against the schema of its table in `pallet_tables`
+    fn validate_template<T: Config>(
+        table: &TableIdentifier,
+        template: &SystemTemplate,
+    ) -> DispatchResult {
+        let create_statement = pallet_tables::Schemas::<T>::get(&table.namespace, &table.name)
+            .ok_or(Error::<T>::TemplateTableNotFound)?;
+        let (create_table, _) = create_statement_to_sqlparser_remove_with(create_statement)
+            .map_err(|_| Error::<T>::TemplateSchemaParseError)?;
+
+        for (index, field) in template.fields.iter().enumerate() {
+            if template.fields[..index]
+                .iter()
+                .any(|other| other.name == field.name)
+            {
+                return Err(Error::<T>::DuplicateTemplateField.into());
+            }
+
+            let column = create_table
+                .columns
+                .iter()
+                .find(|column| column.name.value.to_uppercase().as_bytes() == field.name.as_slice())
+                .ok_or(Error::<T>::TemplateColumnNotFound)?;
+            let column_type = sqlparser_data_type_to_proof_of_sql_column_type(&column.data_type)
+                .map_err(|_| Error::<T>::TemplateColumnTypeMismatch)?;
+            if !field.field_type.matches(&column_type) {
+                return Err(Error::<T>::TemplateColumnTypeMismatch.into());
+            }
+        }
+
+        let declares = |(name, field_type): &(&str, SystemFieldType)| {
+            template.fields.iter().any(|field| {
+                field.name.as_slice() == name.as_bytes() && field.field_type == *field_type
+            })
+        };
+        if !template.request_type.required_fields().iter().all(declares) {
+            return Err(Error::<T>::MissingTemplateField.into());
+        }
+
+        Ok(())
     }
 
     impl<T: Config> Pallet<T> {
+        /// Returns true if a system template is registered for the table, in which case its
+        /// inserts should be passed to [`Pallet::process_system_table`].
+        pub fn has_system_template(table_id: &TableIdentifier) -> bool {
+            SystemTemplates::<T>::contains_key(table_id)
+        }
+
         /// Processes an insert to a system table, checking to see if there are any state
         /// modifications required onchain and applying them.
         pub fn process_system_table(
             table_id: TableIdentifier,
             oc_table: OnChainTable,
         ) -> DispatchResult {
-            match table_to_request(oc_table, table_id) {
+            match SystemTemplates::<T>::get(&table_id) {
                 None => Ok(()),
-                Some(req) => process_request::<T>(req),
+                Some(template) => {
+                    process_request::<T>(table_to_request(oc_table, table_id, &template))
+                }
             }
         }
+
+        /// Returns the mirrored state of the bonded EVM stakers, in storage order, starting
+        /// after the staker with the address `start_after`
+        pub fn mirrored_stakers(
+            start_after: Option<[u8; 20]>,
+            limit: u32,
+        ) -> Vec<MirroredStaker<T::AccountId>> {
+            let limit = limit.min(MAX_MIRRORED_STAKERS_PAGE) as usize;
+            let start_after = start_after.and_then(|address| {
+                eth_address_to_substrate_account_id::<T>(&hex::encode(address)).ok()
+            });
+            let stashes = match start_after {
+                Some(account) => pallet_staking::Bonded::<T>::iter_keys_from(
+                    pallet_staking::Bonded::<T>::hashed_key_for(account),
+                ),
+                None => pallet_staking::Bonded::<T>::iter_keys(),
+            };
+
+            stashes
+                .filter_map(Self::mirrored_staker_of)
+                .take(limit)
+                .collect()
+        }
+
+        /// Returns the mirrored state of the EVM staker with the given address, if bonded
+        pub fn mirrored_staker(address: [u8; 20]) -> Option<MirroredStaker<T::AccountId>> {
+            let account = eth_address_to_substrate_account_id::<T>(&hex::encode(address)).ok()?;
+            Self::mirrored_staker_of(account)
+        }
+
+        /// Returns the mirrored state of a bonded stash, if its account is mirrored from an
+        /// ethereum address
+        fn mirrored_staker_of(stash: T::AccountId) -> Option<MirroredStaker<T::AccountId>> {
+            let encoded = stash.encode();
+            let (padding, address) = encoded.split_at_checked(12)?;
+            if padding != [0; 12] {
+                return None;
+            }
+            let address: [u8; 20] = address.try_into().ok()?;
+            let ledger =
+                pallet_staking::Pallet::<T>::ledger(StakingAccount::Stash(stash.clone())).ok()?;
+
+            Some(MirroredStaker {
+                address,
+                bonded: ledger.total,
+                active: ledger.active,
+                unbonding: ledger
+                    .unlocking
+                    .iter()
+                    .map(|chunk| MirroredUnbonding {
+                        value: chunk.value,
+                        era: chunk.era,
+                    })
+                    .collect(),
+                nominations: pallet_staking::Nominators::<T>::get(&stash)
+                    .map(|nominations| nominations.targets.into_inner())
+                    .unwrap_or_default(),
+                validating: pallet_staking::Validators::<T>::contains_key(&stash),
+                account: stash,
+            })
+        }
     }
 
     /// The Lock Identifier used by the staking pallet to lock funds in the balances pallet
@@ -163,10 +602,121 @@ pub mod pallet {
             SystemRequestType::Staking(StakingSystemRequest::UnstakeInitiated) => {
                 process_unstake_initiated::<T>(request)
             }
-            // SystemRequestType::ZkPayRequest => {
-            //     Ok(())
-            // }
-            _ => Ok(()),
+            SystemRequestType::ZkPay => process_zkpay::<T>(request),
+        }
+    }
+
+    /// Read the EVM event a staking row was indexed from, if the row identifies it
+    fn staking_event_source<T: Config>(
+        row: &BTreeMap<String, SystemFieldValue>,
+    ) -> Result<Option<StakingEventSource>, DispatchError> {
+        match (
+            row.get("BLOCK_NUMBER"),
+            row.get("TRANSACTION_HASH"),
+            row.get("EVENT_INDEX"),
+        ) {
+            (None, None, None) => Ok(None),
+            (
+                Some(SystemFieldValue::BigInt(block_number)),
+                Some(SystemFieldValue::Bytes(transaction_hash)),
+                Some(SystemFieldValue::Int(event_index)),
+            ) => {
+                let invalid = |_| Error::<T>::InvalidStakingEventSource;
+                Ok(Some(StakingEventSource {
+                    block_number: u64::try_from(*block_number).map_err(invalid)?,
+                    transaction_hash: transaction_hash
+                        .as_slice()
+                        .try_into()
+                        .map_err(|_| Error::<T>::InvalidStakingEventSource)?,
+                    event_index: u32::try_from(*event_index).map_err(invalid)?,
+                }))
+            }
+            _ => Err(Error::<T>::InvalidStakingEventSource.into()),
+        }
+    }
+
+    /// Apply a staking row for the staker whose ethereum address is in `staker_field`.
+    ///
+    /// Rows identifying their EVM event are applied once per event: replays of an applied
+    /// event are skipped, and events preceding the last event applied for the staker are
+    /// applied but reported, both with a `SystemTableError` event. Events below the watermark
+    /// of the last event are forgotten, so they are skipped and reported too. Rows are applied
+    /// entirely or not at all, and only applied events are recorded.
+    fn apply_staking_row<T: Config>(
+        table: &TableIdentifier,
+        row: &BTreeMap<String, SystemFieldValue>,
+        staker_field: &str,
+        apply: impl FnOnce(&T::AccountId) -> DispatchResult,
+    ) -> DispatchResult {
+        let Some(SystemFieldValue::Bytes(staker)) = row.get(staker_field) else {
+            return Err(Error::<T>::MissingExpectedField.into());
+        };
+        let staker = eth_address_to_substrate_account_id::<T>(&hex::encode(staker))?;
+        let source = staking_event_source::<T>(row)?;
+
+        if let Some(source) = source {
+            let last = LastStakingEvents::<T>::get(&staker);
+            let issue = if ProcessedStakingEvents::<T>::contains_key(
+                &staker,
+                (source.transaction_hash, source.event_index),
+            ) {
+                Some(StakingEventIssue::Duplicate)
+            } else {
+                last.filter(|last| source.precedes(last)).map(|last| {
+                    if source.block_number < last.watermark() {
+                        StakingEventIssue::Expired { last }
+                    } else {
+                        StakingEventIssue::OutOfOrder { last }
+                    }
+                })
+            };
+
+            if let Some(issue) = issue {
+                log::warn!("Staking event {source:?} of {table:?} reported: {issue:?}");
+                Pallet::<T>::deposit_event(Event::<T>::SystemTableError {
+                    table: table.clone(),
+                    staker: staker.clone(),
+                    source,
+                    issue,
+                });
+                if matches!(
+                    issue,
+                    StakingEventIssue::Duplicate | StakingEventIssue::Expired { .. }
+                ) {
+                    return Ok(());
+                }
+            }
+        }
+
+        frame_support::storage::with_storage_layer(|| {
+            apply(&staker)?;
+
+            if let Some(source) = source {
+                ProcessedStakingEvents::<T>::insert(
+                    &staker,
+                    (source.transaction_hash, source.event_index),
+                    source.block_number,
+                );
+                let last = LastStakingEvents::<T>::get(&staker);
+                if last.is_none_or(|last| last.precedes(&source)) {
+                    LastStakingEvents::<T>::insert(&staker, source);
+                    if last.is_none_or(|last| last.watermark() < source.watermark()) {
+                        prune_staking_events::<T>(&staker, source.watermark());
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Forget the staking events of a staker emitted below the watermark
+    fn prune_staking_events<T: Config>(staker: &T::AccountId, watermark: u64) {
+        let expired: Vec<_> = ProcessedStakingEvents::<T>::iter_prefix(staker)
+            .filter(|(_, block_number)| *block_number < watermark)
+            .map(|(event, _)| event)
+            .collect();
+        for event in expired {
+            ProcessedStakingEvents::<T>::remove(staker, event);
         }
     }
 
@@ -175,48 +725,42 @@ pub mod pallet {
         request
             .rows()
             .map(|row| -> DispatchResult {
-                match (row.get("STAKER"), row.get("AMOUNT")) {
-                    (
-                        Some(SystemFieldValue::Bytes(staker_bytes)),
-                        Some(SystemFieldValue::Decimal(amount)),
-                    ) => {
-                        let staker = hex::encode(staker_bytes);
-                        let staker_id =
-                            sxt_core::utils::eth_address_to_substrate_account_id::<T>(&staker)?;
-                        let staker_signer = RawOrigin::Signed(staker_id.clone());
-                        let stake_amount = amount.min(&U256::from(u128::MAX)).low_u128();
-                        // Increase the account balance by the new stake
-                        let balance: u128 = pallet_balances::Pallet::<T>::free_balance(&staker_id)
-                            .unique_saturated_into();
-                        let new_total_balance = balance.saturating_add(stake_amount);
-
-                        let staker_lookup =
-                            <T as frame_system::Config>::Lookup::unlookup(staker_id.clone());
-
-                        pallet_balances::Pallet::<T>::force_set_balance(
-                            RawOrigin::Root.into(),
-                            staker_lookup,
-                            new_total_balance.saturated_into(),
-                        )?;
+                apply_staking_row::<T>(&request.table_id, &row, "STAKER", |staker_id| {
+                    let Some(SystemFieldValue::Decimal(amount)) = row.get("AMOUNT") else {
+                        return Err(Error::<T>::MissingExpectedField.into());
+                    };
+                    let staker_signer = RawOrigin::Signed(staker_id.clone());
+                    let stake_amount = amount.min(&U256::from(u128::MAX)).low_u128();
+                    // Increase the account balance by the new stake
+                    let balance: u128 = pallet_balances::Pallet::<T>::free_balance(staker_id)
+                        .unique_saturated_into();
+                    let new_total_balance = balance.saturating_add(stake_amount);
 
-                        // If the user already had a bonded amount use bond_extra
-                        if balance > 0 {
-                            pallet_staking::Pallet::<T>::bond_extra(
-                                staker_signer.clone().into(),
-                                stake_amount,
-                            )?;
-                        } else {
-                            pallet_staking::Pallet::<T>::bond(
-                                staker_signer.clone().into(),
-                                stake_amount,
-                                pallet_staking::RewardDestination::Staked,
-                            )?;
-                        }
+                    let staker_lookup =
+                        <T as frame_system::Config>::Lookup::unlookup(staker_id.clone());
 
-                        Ok(())
+                    pallet_balances::Pallet::<T>::force_set_balance(
+                        RawOrigin::Root.into(),
+                        staker_lookup,
+                        new_total_balance.saturated_into(),
+                    )?;
+
+                    // If the user already had a bonded amount use bond_extra
+                    if balance > 0 {
+                        pallet_staking::Pallet::<T>::bond_extra(
+                            staker_signer.clone().into(),
+                            stake_amount,
+                        )?;
+                    } else {
+                        pallet_staking::Pallet::<T>::bond(
+                            staker_signer.clone().into(),
+                            stake_amount,
+                            pallet_staking::RewardDestination::Staked,
+                        )?;
                     }
-                    _ => Err(Error::<T>::MissingExpectedField.into()),
-                }
+
+                    Ok(())
+                })
             })
             .for_each(emit_for_error::<T>);
 
@@ -228,45 +772,41 @@ pub mod pallet {
         request
             .rows()
             .map(|row| -> DispatchResult {
-                match (row.get("NOMINATOR"), row.get("NODESED25519PUBKEYS")) {
-                    (
-                        Some(SystemFieldValue::Bytes(nominator)),
-                        Some(SystemFieldValue::Varchar(nodes)),
-                    ) => {
-                        // Parse the input string as a JSON list
-                        let parsed = sxt_core::utils::parse_address_list_json::<T>(nodes)
-                            .map_err(|_| Error::<T>::ErrorParsingNominations)?;
-
-                        let (nominations, errors): (Vec<_>, Vec<_>) =
-                            parsed.into_iter().partition_result();
+                apply_staking_row::<T>(&request.table_id, &row, "NOMINATOR", |nominator_id| {
+                    let Some(SystemFieldValue::Varchar(nodes)) = row.get("NODESED25519PUBKEYS")
+                    else {
+                        return Err(Error::<T>::MissingExpectedField.into());
+                    };
+                    // Parse the input string as a JSON list
+                    let parsed = sxt_core::utils::parse_address_list_json::<T>(nodes)
+                        .map_err(|_| Error::<T>::ErrorParsingNominations)?;
 
-                        if !errors.is_empty() {
-                            log::warn!(
-                                "❌ {} invalid nominations were skipped: {:?}",
-                                errors.len(),
-                                errors
-                            );
+                    let (nominations, errors): (Vec<_>, Vec<_>) =
+                        parsed.into_iter().partition_result();
 
+                    if !errors.is_empty() {
+                        log::warn!(
+                            "❌ {} invalid nominations were skipped: {:?}",
+                            errors.len(),
                             errors
-                                .into_iter()
-                                .for_each(|e| emit_for_error::<T>(Result::<(), _>::Err(e)));
-                        }
-
-                        if nominations.is_empty() {
-                            log::warn!("❌ All nominations failed to parse: {}", nodes);
-                            Err(Error::<T>::EmptyNominationSet)?;
-                        }
+                        );
 
-                        let nominator = hex::encode(nominator);
-                        let nominator_id =
-                            sxt_core::utils::eth_address_to_substrate_account_id::<T>(&nominator)?;
-                        let nominator_signer: OriginFor<T> = RawOrigin::Signed(nominator_id).into();
+                        errors
+                            .into_iter()
+                            .for_each(|e| emit_for_error::<T>(Result::<(), _>::Err(e)));
+                    }
 
-                        pallet_staking::Pallet::<T>::nominate(nominator_signer, nominations)?;
-                        Ok(())
+                    if nominations.is_empty() {
+                        log::warn!("❌ All nominations failed to parse: {}", nodes);
+                        Err(Error::<T>::EmptyNominationSet)?;
                     }
-                    _ => Err(Error::<T>::MissingExpectedField.into()),
-                }
+
+                    let nominator_signer: OriginFor<T> =
+                        RawOrigin::Signed(nominator_id.clone()).into();
+
+                    pallet_staking::Pallet::<T>::nominate(nominator_signer, nominations)?;
+                    Ok(())
+                })
             })
             .for_each(emit_for_error::<T>);
 
@@ -278,26 +818,18 @@ pub mod pallet {
         request
             .rows()
             .map(|row| -> DispatchResult {
-                match row.get("STAKER") {
-                    Some(SystemFieldValue::Bytes(staker)) => {
-                        let staker = hex::encode(staker);
-                        let staker_id =
-                            sxt_core::utils::eth_address_to_substrate_account_id::<T>(&staker)?;
-                        let staker_signer: OriginFor<T> =
-                            RawOrigin::Signed(staker_id.clone()).into();
-
-                        let raw_balance: u128 =
-                            pallet_balances::Pallet::<T>::free_balance(staker_id)
-                                .unique_saturated_into();
-                        let staking_balance: T::CurrencyBalance = T::CurrencyBalance::from(
-                            UniqueSaturatedInto::<u64>::unique_saturated_into(raw_balance),
-                        );
-                        pallet_staking::Pallet::<T>::unbond(staker_signer, staking_balance)
-                            .map_err(|e| e.error)?;
-                        Ok(())
-                    }
-                    _ => Err(Error::<T>::MissingExpectedField.into()),
-                }
+                apply_staking_row::<T>(&request.table_id, &row, "STAKER", |staker_id| {
+                    let staker_signer: OriginFor<T> = RawOrigin::Signed(staker_id.clone()).into();
+
+                    let raw_balance: u128 = pallet_balances::Pallet::<T>::free_balance(staker_id)
+                        .unique_saturated_into();
+                    let staking_balance: T::CurrencyBalance = T::CurrencyBalance::from(
+                        UniqueSaturatedInto::<u64>::unique_saturated_into(raw_balance),
+                    );
+                    pallet_staking::Pallet::<T>::unbond(staker_signer, staking_balance)
+                        .map_err(|e| e.error)?;
+                    Ok(())
+                })
             })
             .for_each(emit_for_error::<T>);
 
@@ -309,36 +841,138 @@ pub mod pallet {
         request
             .rows()
             .map(|row| -> DispatchResult {
-                match row.get("STAKER") {
-                    Some(SystemFieldValue::Bytes(staker)) => {
-                        let staker = hex::encode(staker);
-                        let staker_id =
-                            sxt_core::utils::eth_address_to_substrate_account_id::<T>(&staker)?;
-                        let staker_signer: OriginFor<T> =
-                            RawOrigin::Signed(staker_id.clone()).into();
-
-                        let raw_balance: u128 =
-                            pallet_balances::Pallet::<T>::free_balance(staker_id)
-                                .unique_saturated_into();
-                        let staking_balance: T::CurrencyBalance = T::CurrencyBalance::from(
-                            UniqueSaturatedInto::<u64>::unique_saturated_into(raw_balance),
-                        );
+                apply_staking_row::<T>(&request.table_id, &row, "STAKER", |staker_id| {
+                    let staker_signer: OriginFor<T> = RawOrigin::Signed(staker_id.clone()).into();
 
-                        pallet_staking::Pallet::<T>::rebond(staker_signer, staking_balance)
-                            .map_err(|e| e.error)?;
+                    let raw_balance: u128 = pallet_balances::Pallet::<T>::free_balance(staker_id)
+                        .unique_saturated_into();
+                    let staking_balance: T::CurrencyBalance = T::CurrencyBalance::from(
+                        UniqueSaturatedInto::<u64>::unique_saturated_into(raw_balance),
+                    );
+
+                    pallet_staking::Pallet::<T>::rebond(staker_signer, staking_balance)
+                        .map_err(|e| e.error)?;
+                    Ok(())
+                })
+            })
+            .for_each(emit_for_error::<T>);
+        Ok(())
+    }
+
+    /// Process payments made on an EVM chain through ZK-Pay, crediting the account of each payer
+    pub fn process_zkpay<T: Config>(request: SystemRequest) -> DispatchResult {
+        request
+            .rows()
+            .map(|row| -> DispatchResult {
+                match (row.get("SENDER"), row.get("AMOUNT"), row.get("NONCE")) {
+                    (
+                        Some(SystemFieldValue::Bytes(sender)),
+                        Some(SystemFieldValue::Decimal(amount)),
+                        Some(SystemFieldValue::Decimal(nonce)),
+                    ) => {
+                        let sender_address: [u8; 20] = sender
+                            .as_slice()
+                            .try_into()
+                            .map_err(|_| Error::<T>::InvalidMessageFormat)?;
+                        let account =
+                            eth_address_to_substrate_account_id::<T>(&hex::encode(sender))?;
+
+                        let nonce: U256 = *nonce;
+                        let expected =
+                            ZkPayNonces::<T>::get(&account).unwrap_or_default() + U256::from(1);
+                        if nonce < expected {
+                            return Err(Error::<T>::LateNonce.into());
+                        } else if nonce > expected {
+                            return Err(Error::<T>::FutureNonce.into());
+                        }
+
+                        // A payment that fails doesn't hold up the later payments of the payer
+                        ZkPayNonces::<T>::insert(&account, nonce);
+                        let fail = |error: DispatchError| {
+                            Pallet::<T>::deposit_event(Event::<T>::ZkPayFailed {
+                                sender: sender_address,
+                                nonce,
+                                error,
+                            })
+                        };
+
+                        // Payments of invalid amounts can never be credited, so they aren't kept
+                        let amount = match zkpay_amount::<T>(*amount) {
+                            Ok(amount) => amount,
+                            Err(error) => {
+                                fail(error);
+                                return Ok(());
+                            }
+                        };
+                        let credited = ZkPayTreasury::<T>::get()
+                            .ok_or_else(|| Error::<T>::ZkPayTreasuryNotSet.into())
+                            .and_then(|treasury| {
+                                credit_zkpay::<T>(&account, amount, &treasury).map(|()| treasury)
+                            });
+                        match credited {
+                            Ok(treasury) => Pallet::<T>::deposit_event(Event::<T>::ZkPayCredited {
+                                sender: sender_address,
+                                account,
+                                amount,
+                                nonce,
+                                treasury,
+                            }),
+                            Err(error) => {
+                                FailedZkPayments::<T>::insert(
+                                    &account,
+                                    nonce,
+                                    FailedZkPayment {
+                                        sender: sender_address,
+                                        amount,
+                                    },
+                                );
+                                fail(error)
+                            }
+                        }
                         Ok(())
                     }
                     _ => Err(Error::<T>::MissingExpectedField.into()),
                 }
             })
             .for_each(emit_for_error::<T>);
+
         Ok(())
     }
 
+    /// Check the amount of a payment is a positive balance
+    fn zkpay_amount<T: Config>(amount: U256) -> Result<u128, DispatchError> {
+        if amount.is_zero() || amount > U256::from(u128::MAX) {
+            return Err(Error::<T>::InvalidZkPayAmount.into());
+        }
+        Ok(amount.low_u128())
+    }
+
+    /// Credit a payment to an account, transferring it from the treasury
+    fn credit_zkpay<T: Config>(
+        account: &T::AccountId,
+        amount: u128,
+        treasury: &T::AccountId,
+    ) -> DispatchResult {
+        let balance: <T as pallet_balances::Config>::Balance = amount.saturated_into();
+
+        frame_support::storage::with_storage_layer(|| {
+            <pallet_balances::Pallet<T> as fungible::Mutate<T::AccountId>>::transfer(
+                treasury,
+                account,
+                balance,
+                Preservation::Preserve,
+            )?;
+            Ok(())
+        })
+    }
+
     fn emit_for_error<T: Config>(r: DispatchResult) {
         if let Err(error) = r {
             // Emit an event for any errors
-            Pallet::<T>::deposit_event(Event::<T>::MessageProcessingError { error });
+            Pallet::<T>::deposit_event(Event::<T>::MessageProcessingError {
+                error,
+                message_type: None,
+            });
         }
     }
 
@@ -363,6 +997,10 @@ pub mod pallet {
                         Some(SystemFieldValue::Bytes(body)),
                         Some(SystemFieldValue::Decimal(nonce)),
                     ) => {
+                        let sender_address: [u8; 20] = sender
+                            .as_slice()
+                            .try_into()
+                            .map_err(|_| Error::<T>::InvalidMessageFormat)?;
                         let sender = hex::encode(sender);
                         let eth_sender = eth_address_to_substrate_account_id::<T>(&sender)?;
 
@@ -378,7 +1016,21 @@ pub mod pallet {
 
                         LastProcessedUserNonce::<T>::set(&eth_sender, Some(nonce));
 
-                        messages::handle_message::<T>(eth_sender, body.to_vec())?;
+                        let message = messages::decode_message::<T>(body)?;
+                        let message_type = message.message_type();
+                        match messages::handle_message::<T>(eth_sender, message) {
+                            Ok(()) => Pallet::<T>::deposit_event(Event::<T>::MessageReceived {
+                                sender: sender_address,
+                                message_type,
+                                payload: body.to_vec(),
+                            }),
+                            Err(error) => {
+                                Pallet::<T>::deposit_event(Event::<T>::MessageProcessingError {
+                                    error,
+                                    message_type: Some(message_type),
+                                })
+                            }
+                        }
                         Ok(())
                     }
                     _ => Err(Error::<T>::MissingExpectedField.into()),
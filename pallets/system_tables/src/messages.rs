@@ -3,28 +3,152 @@ extern crate alloc;
 
 use alloc::vec;
 use alloc::vec::Vec;
-use core::str::from_utf8;
 
-use codec::{Decode, Encode};
+use codec::{Decode, DecodeAll, Encode, MaxEncodedLen};
 use frame_support::dispatch::RawOrigin;
 use frame_support::pallet_prelude::TypeInfo;
-use hex::FromHex;
-use pallet_staking::ValidatorPrefs;
+use frame_system::pallet_prelude::OriginFor;
+use pallet_staking::{RewardDestination, ValidatorPrefs};
 use sp_runtime::{DispatchError, DispatchResult, Perbill};
+use sxt_core::utils::eth_address_to_substrate_account_id;
 
 use crate::{Config, Error};
 
-/// Right now we only support one message to register session keys
-pub fn handle_message<T: Config>(sender: T::AccountId, message_bytes: Vec<u8>) -> DispatchResult {
-    let keys =
-        T::Keys::decode(&mut &message_bytes[..]).map_err(|_| Error::<T>::InvalidSessionKeys)?;
+/// The bytes every versioned message starts with. Messages without them are read as
+/// unversioned session keys.
+pub const MESSAGE_MAGIC: [u8; 4] = *b"SXTM";
 
-    // Set session keys
-    let prefs = ValidatorPrefs {
-        commission: Perbill::from_percent(10),
-        blocked: false,
+/// The latest version of the message envelope
+pub const MESSAGE_VERSION: u8 = 1;
+
+/// The commission given to validators registering session keys for the first time
+pub const DEFAULT_COMMISSION: Perbill = Perbill::from_percent(10);
+
+/// The kind of a message, as reported in events
+#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MessageType {
+    /// Register session keys, validating with the default commission if not yet validating
+    SetSessionKeys,
+    /// Change the commission of a validator, and whether it accepts nominations
+    SetCommission,
+    /// Stop validating and nominating
+    Chill,
+    /// Remove the session keys of a validator
+    PurgeKeys,
+    /// Change where staking rewards are paid
+    SetPayee,
+}
+
+/// Where staking rewards are paid, as chosen with a `SetPayee` message
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, Debug)]
+pub enum MessagePayee {
+    /// Rewards are paid to the staker and bonded
+    Staked,
+    /// Rewards are paid to the staker, without being bonded
+    Stash,
+    /// Rewards are paid to the account of another ethereum address
+    Account([u8; 20]),
+}
+
+/// A message sent through the messaging contract, SCALE encoded after the envelope header
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, Debug)]
+pub enum EvmMessage<Keys> {
+    /// Register session keys
+    #[codec(index = 0)]
+    SetSessionKeys {
+        /// The SCALE encoded session keys, as returned by `author_rotateKeys`
+        keys: Keys,
+    },
+    /// Change the commission of a validator
+    #[codec(index = 1)]
+    SetCommission {
+        /// The new commission
+        commission: Perbill,
+        /// Whether new nominations are refused
+        blocked: bool,
+    },
+    /// Stop validating and nominating
+    #[codec(index = 2)]
+    Chill,
+    /// Remove the session keys of a validator
+    #[codec(index = 3)]
+    PurgeKeys,
+    /// Change where staking rewards are paid
+    #[codec(index = 4)]
+    SetPayee {
+        /// The new reward destination
+        payee: MessagePayee,
+    },
+}
+
+impl<Keys> EvmMessage<Keys> {
+    /// Returns the kind of the message
+    pub fn message_type(&self) -> MessageType {
+        match self {
+            EvmMessage::SetSessionKeys { .. } => MessageType::SetSessionKeys,
+            EvmMessage::SetCommission { .. } => MessageType::SetCommission,
+            EvmMessage::Chill => MessageType::Chill,
+            EvmMessage::PurgeKeys => MessageType::PurgeKeys,
+            EvmMessage::SetPayee { .. } => MessageType::SetPayee,
+        }
+    }
+}
+
+/// Decode the body of a message, either a versioned envelope or unversioned session keys
+pub fn decode_message<T: Config>(
+    message_bytes: &[u8],
+) -> Result<EvmMessage<T::Keys>, DispatchError> {
+    let Some(envelope) = message_bytes.strip_prefix(&MESSAGE_MAGIC) else {
+        let keys =
+            T::Keys::decode(&mut &message_bytes[..]).map_err(|_| Error::<T>::InvalidSessionKeys)?;
+        return Ok(EvmMessage::SetSessionKeys { keys });
     };
 
+    match envelope.split_first() {
+        Some((&MESSAGE_VERSION, message)) => EvmMessage::decode_all(&mut &message[..])
+            .map_err(|_| Error::<T>::InvalidMessageFormat.into()),
+        Some(_) => Err(Error::<T>::UnsupportedMessageVersion.into()),
+        None => Err(Error::<T>::InvalidMessageFormat.into()),
+    }
+}
+
+/// Check a message can be applied for the sender before applying any of it
+fn validate_message<T: Config>(
+    sender: &T::AccountId,
+    message: &EvmMessage<T::Keys>,
+) -> DispatchResult {
+    let is_bonded = pallet_staking::Bonded::<T>::contains_key(sender);
+    match message {
+        EvmMessage::SetSessionKeys { .. } | EvmMessage::Chill | EvmMessage::PurgeKeys => {
+            if !is_bonded {
+                return Err(Error::<T>::NotBonded.into());
+            }
+            Ok(())
+        }
+        EvmMessage::SetCommission { commission, .. } => {
+            if !pallet_staking::Validators::<T>::contains_key(sender) {
+                return Err(Error::<T>::NotValidator.into());
+            }
+            if *commission < pallet_staking::MinCommission::<T>::get() {
+                return Err(Error::<T>::CommissionTooLow.into());
+            }
+            Ok(())
+        }
+        EvmMessage::SetPayee { payee } => {
+            if !is_bonded {
+                return Err(Error::<T>::NotBonded.into());
+            }
+            if *payee == MessagePayee::Account([0; 20]) {
+                return Err(Error::<T>::InvalidPayee.into());
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Register session keys for the sender, validating with the default commission if the
+/// sender isn't validating yet
+fn set_session_keys<T: Config>(sender: T::AccountId, keys: T::Keys) -> DispatchResult {
     match pallet_staking::Pallet::<T>::set_controller(RawOrigin::Signed(sender.clone()).into()) {
         Ok(_) => Ok(()),
         Err(e) => {
@@ -37,6 +161,61 @@ pub fn handle_message<T: Config>(sender: T::AccountId, message_bytes: Vec<u8>) -
         }
     }?;
 
+    // Keep the preferences of validators that already set their commission
+    let prefs = if pallet_staking::Validators::<T>::contains_key(&sender) {
+        pallet_staking::Validators::<T>::get(&sender)
+    } else {
+        ValidatorPrefs {
+            commission: DEFAULT_COMMISSION,
+            blocked: false,
+        }
+    };
+
     pallet_staking::Pallet::<T>::validate(RawOrigin::Signed(sender.clone()).into(), prefs)?;
-    pallet_session::Pallet::<T>::set_keys(RawOrigin::Signed(sender.clone()).into(), keys, vec![])
+    pallet_session::Pallet::<T>::set_keys(RawOrigin::Signed(sender).into(), keys, vec![])
+}
+
+/// Validate and apply a message from the sender. A message is applied entirely or not at all.
+pub fn handle_message<T: Config>(
+    sender: T::AccountId,
+    message: EvmMessage<T::Keys>,
+) -> DispatchResult {
+    validate_message::<T>(&sender, &message)?;
+
+    frame_support::storage::with_storage_layer(|| {
+        let origin = || -> OriginFor<T> { RawOrigin::Signed(sender.clone()).into() };
+        match message {
+            EvmMessage::SetSessionKeys { keys } => set_session_keys::<T>(sender.clone(), keys),
+            EvmMessage::SetCommission {
+                commission,
+                blocked,
+            } => pallet_staking::Pallet::<T>::validate(
+                origin(),
+                ValidatorPrefs {
+                    commission,
+                    blocked,
+                },
+            ),
+            EvmMessage::Chill => pallet_staking::Pallet::<T>::chill(origin()),
+            EvmMessage::PurgeKeys => pallet_session::Pallet::<T>::purge_keys(origin()),
+            EvmMessage::SetPayee { payee } => {
+                let payee = match payee {
+                    MessagePayee::Staked => RewardDestination::Staked,
+                    MessagePayee::Stash => RewardDestination::Stash,
+                    MessagePayee::Account(address) => RewardDestination::Account(
+                        eth_address_to_substrate_account_id::<T>(&hex::encode(address))?,
+                    ),
+                };
+                pallet_staking::Pallet::<T>::set_payee(origin(), payee)
+            }
+        }
+    })
+}
+
+/// Encode a message in the latest envelope version, as the messaging contract sends it
+pub fn encode_message<Keys: Encode>(message: &EvmMessage<Keys>) -> Vec<u8> {
+    let mut bytes = MESSAGE_MAGIC.to_vec();
+    bytes.push(MESSAGE_VERSION);
+    message.encode_to(&mut bytes);
+    bytes
 }
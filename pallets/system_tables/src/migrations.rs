@@ -0,0 +1,71 @@
+//! Storage migrations of the system tables pallet
+
+/// Migration to storage version 1, registering the default system templates that were
+/// previously built into the pallet
+pub mod v1 {
+    use frame_support::pallet_prelude::*;
+    use frame_support::traits::OnRuntimeUpgrade;
+
+    use crate::parse::default_templates;
+    use crate::{Config, Pallet, SystemTemplates};
+
+    /// Registers the default templates of the `SXT_SYSTEM_STAKING` tables, leaving templates
+    /// already registered for them untouched
+    pub struct RegisterDefaultTemplates<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for RegisterDefaultTemplates<T> {
+        fn on_runtime_upgrade() -> Weight {
+            if Pallet::<T>::on_chain_storage_version() >= 1 {
+                return T::DbWeight::get().reads(1);
+            }
+
+            let templates = default_templates();
+            let count = templates.len() as u64;
+            for (table, template) in templates {
+                if !SystemTemplates::<T>::contains_key(&table) {
+                    SystemTemplates::<T>::insert(table, template);
+                }
+            }
+            StorageVersion::new(1).put::<Pallet<T>>();
+
+            log::info!("Registered {count} default system templates");
+            T::DbWeight::get().reads_writes(count + 1, count + 1)
+        }
+    }
+}
+
+/// Migration to storage version 2, declaring the source event fields in the default staking
+/// templates so staking rows are applied once per EVM event
+pub mod v2 {
+    use frame_support::pallet_prelude::*;
+    use frame_support::traits::OnRuntimeUpgrade;
+
+    use crate::parse::{default_templates, v1_default_templates};
+    use crate::{Config, Pallet, SystemTemplates};
+
+    /// Replaces the default templates registered by the `v1` migration, leaving templates
+    /// registered since for the same tables untouched
+    pub struct DeclareStakingEventSources<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for DeclareStakingEventSources<T> {
+        fn on_runtime_upgrade() -> Weight {
+            if Pallet::<T>::on_chain_storage_version() != 1 {
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut writes = 1;
+            let templates = default_templates();
+            let count = templates.len() as u64;
+            for ((table, old), (_, new)) in v1_default_templates().into_iter().zip(templates) {
+                if SystemTemplates::<T>::get(&table) == Some(old) {
+                    SystemTemplates::<T>::insert(table, new);
+                    writes += 1;
+                }
+            }
+            StorageVersion::new(2).put::<Pallet<T>>();
+
+            log::info!("Updated {} default system templates", writes - 1);
+            T::DbWeight::get().reads_writes(count + 1, writes)
+        }
+    }
+}
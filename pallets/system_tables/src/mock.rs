@@ -191,6 +191,7 @@ sp_runtime::impl_opaque_keys! {
 
 impl pallet_system_tables::Config for Test {
     type RuntimeEvent = RuntimeEvent;
+    type TemplateAdminOrigin = frame_system::EnsureRoot<AccountId>;
 }
 
 impl pallet_tables::Config for Test {
@@ -214,5 +215,9 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
         .assimilate_storage(&mut storage)
         .unwrap();
 
+    pallet_system_tables::GenesisConfig::<Test>::default()
+        .assimilate_storage(&mut storage)
+        .unwrap();
+
     storage.into()
 }
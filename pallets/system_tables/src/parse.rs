@@ -3,23 +3,75 @@ use alloc::collections::BTreeMap;
 use alloc::string::String;
 use alloc::vec;
 use alloc::vec::Vec;
+use core::str::from_utf8;
 
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::pallet_prelude::{ConstU32, RuntimeDebug, TypeInfo};
+use frame_support::BoundedVec;
 use on_chain_table::OnChainTable;
+use proof_of_sql::base::database::ColumnType;
 use sp_core::U256;
 use sxt_core::tables::TableIdentifier;
 
 use crate::parse::SystemFieldType::{Decimal, Varchar};
 use crate::parse::SystemRequestType::{Message, Staking};
 
-/// Supported types of system requests, typically originating from data submissions
-#[derive(Clone, Copy, Eq, PartialEq)]
+/// Supported types of system requests, typically originating from data submissions.
+///
+/// This is the handler kind a system template registers for a table.
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, TypeInfo, MaxEncodedLen, RuntimeDebug)]
 pub enum SystemRequestType {
     Message,
     Staking(StakingSystemRequest),
     ZkPay,
 }
 
-#[derive(Clone, Copy, Eq, PartialEq)]
+impl SystemRequestType {
+    /// The fields the handler reads from every row, which a template for it must declare
+    pub fn required_fields(&self) -> &'static [(&'static str, SystemFieldType)] {
+        match self {
+            Message => &[
+                ("SENDER", SystemFieldType::Bytes),
+                ("BODY", SystemFieldType::Bytes),
+                ("NONCE", Decimal),
+            ],
+            Staking(StakingSystemRequest::Stake) => {
+                &[("STAKER", SystemFieldType::Bytes), ("AMOUNT", Decimal)]
+            }
+            Staking(StakingSystemRequest::Nominate) => &[
+                ("NOMINATOR", SystemFieldType::Bytes),
+                ("NODESED25519PUBKEYS", Varchar),
+            ],
+            Staking(StakingSystemRequest::UnstakeInitiated)
+            | Staking(StakingSystemRequest::UnstakeCancelled) => {
+                &[("STAKER", SystemFieldType::Bytes)]
+            }
+            SystemRequestType::ZkPay => &[
+                ("SENDER", SystemFieldType::Bytes),
+                ("AMOUNT", Decimal),
+                ("NONCE", Decimal),
+            ],
+        }
+    }
+
+    /// The fields the handler reads when present, on top of the required fields
+    pub fn optional_fields(&self) -> &'static [(&'static str, SystemFieldType)] {
+        match self {
+            Staking(_) => STAKING_EVENT_SOURCE_FIELDS,
+            Message | SystemRequestType::ZkPay => &[],
+        }
+    }
+}
+
+/// The fields identifying the EVM event a staking row was indexed from. Rows with them are
+/// applied once per event, and reported when they arrive out of order for their staker.
+pub const STAKING_EVENT_SOURCE_FIELDS: &[(&str, SystemFieldType)] = &[
+    ("BLOCK_NUMBER", SystemFieldType::BigInt),
+    ("TRANSACTION_HASH", SystemFieldType::Bytes),
+    ("EVENT_INDEX", SystemFieldType::Int),
+];
+
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, TypeInfo, MaxEncodedLen, RuntimeDebug)]
 /// Types of supported staking requests
 pub enum StakingSystemRequest {
     Stake,
@@ -56,11 +108,131 @@ impl SystemRequest {
     }
 }
 
-#[derive(Clone)]
-enum SystemFieldType {
+/// The type of a system table field, and the column type it is read from
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub enum SystemFieldType {
+    /// A VARCHAR column
     Varchar,
+    /// A BINARY column
     Bytes,
+    /// A DECIMAL column
     Decimal,
+    /// An INT column
+    Int,
+    /// A BIGINT column
+    BigInt,
+}
+
+impl SystemFieldType {
+    /// Returns `true` if fields of this type can be read from a column of the given type
+    pub fn matches(&self, column_type: &ColumnType) -> bool {
+        matches!(
+            (self, column_type),
+            (Varchar, ColumnType::VarChar)
+                | (SystemFieldType::Bytes, ColumnType::VarBinary)
+                | (Decimal, ColumnType::Decimal75(_, _))
+                | (SystemFieldType::Int, ColumnType::Int)
+                | (SystemFieldType::BigInt, ColumnType::BigInt)
+        )
+    }
+}
+
+/// The maximum number of fields of a system template
+pub const MAX_TEMPLATE_FIELDS: u32 = 32;
+
+/// The maximum length of the name of a system template field
+pub const MAX_TEMPLATE_FIELD_NAME_LENGTH: u32 = 64;
+
+/// The name of a system template field, as an uppercase column name
+pub type TemplateFieldName = BoundedVec<u8, ConstU32<MAX_TEMPLATE_FIELD_NAME_LENGTH>>;
+
+/// A field read from the inserts into a system table
+#[derive(Clone, Eq, PartialEq, Encode, Decode, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub struct TemplateField {
+    /// The name of the column the field is read from
+    pub name: TemplateFieldName,
+    /// The type of the field
+    pub field_type: SystemFieldType,
+}
+
+/// The fields of a system template
+pub type TemplateFields = BoundedVec<TemplateField, ConstU32<MAX_TEMPLATE_FIELDS>>;
+
+/// How the inserts into a system table are parsed and processed
+#[derive(Clone, Eq, PartialEq, Encode, Decode, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub struct SystemTemplate {
+    /// The handler processing the parsed inserts
+    pub request_type: SystemRequestType,
+    /// The fields parsed from the inserts
+    pub fields: TemplateFields,
+}
+
+impl SystemTemplate {
+    /// Build a template from field names and types, which must fit the template bounds
+    pub(crate) fn from_fields(
+        request_type: SystemRequestType,
+        fields: &[(&str, SystemFieldType)],
+    ) -> Self {
+        let fields = fields
+            .iter()
+            .map(|(name, field_type)| TemplateField {
+                name: TemplateFieldName::truncate_from(name.as_bytes().to_vec()),
+                field_type: *field_type,
+            })
+            .collect::<Vec<_>>();
+
+        SystemTemplate {
+            request_type,
+            fields: TemplateFields::truncate_from(fields),
+        }
+    }
+}
+
+/// The EVM event a staking row was indexed from
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub struct StakingEventSource {
+    /// The EVM block the event was emitted in
+    pub block_number: u64,
+    /// The hash of the transaction that emitted the event
+    pub transaction_hash: [u8; 32],
+    /// The index of the event in its block
+    pub event_index: u32,
+}
+
+/// The number of EVM blocks the staking events of a staker are remembered for, before their
+/// last event. About a week of ethereum blocks.
+pub const STAKING_EVENT_RETENTION: u64 = 50_400;
+
+impl StakingEventSource {
+    /// Returns `true` if this event was emitted before the other one
+    pub fn precedes(&self, other: &Self) -> bool {
+        (self.block_number, self.event_index) < (other.block_number, other.event_index)
+    }
+
+    /// The EVM block below which the staker's events are forgotten, when this is their last
+    /// event
+    pub fn watermark(&self) -> u64 {
+        self.block_number.saturating_sub(STAKING_EVENT_RETENTION)
+    }
+}
+
+/// Why a staking row was reported instead of being applied as usual
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub enum StakingEventIssue {
+    /// The event was already applied, so the row was skipped
+    Duplicate,
+    /// The event was emitted before the last event applied for the staker. The row was
+    /// applied anyway.
+    OutOfOrder {
+        /// The last event applied for the staker
+        last: StakingEventSource,
+    },
+    /// The event was emitted before the watermark of the last event applied for the staker,
+    /// so it can't be told apart from a replay. The row was skipped.
+    Expired {
+        /// The last event applied for the staker
+        last: StakingEventSource,
+    },
 }
 
 /// A wrapper for supported fields of system requests
@@ -69,6 +241,8 @@ pub enum SystemFieldValue {
     Varchar(String),
     Bytes(Vec<u8>),
     Decimal(U256),
+    Int(i32),
+    BigInt(i64),
 }
 
 /// A wrapper for a field/column containing multiple values from a request
@@ -86,6 +260,8 @@ impl SystemTableField {
             SystemFieldValue::Varchar(_) => SystemFieldType::Varchar,
             SystemFieldValue::Bytes(_) => SystemFieldType::Bytes,
             SystemFieldValue::Decimal(_) => SystemFieldType::Decimal,
+            SystemFieldValue::Int(_) => SystemFieldType::Int,
+            SystemFieldValue::BigInt(_) => SystemFieldType::BigInt,
         };
 
         SystemTableField {
@@ -96,127 +272,108 @@ impl SystemTableField {
     }
 }
 
-impl From<(&str, SystemFieldType)> for SystemTableField {
-    fn from((value, value_type): (&str, SystemFieldType)) -> Self {
-        SystemTableField {
-            name: String::from(value),
-            value_type,
-            values: vec![],
-        }
-    }
+/// The templates of the `SXT_SYSTEM_STAKING` tables, registered at genesis and by the
+/// `v1` migration. They declare the optional fields of their handlers too.
+pub fn default_templates() -> Vec<(TableIdentifier, SystemTemplate)> {
+    default_request_types()
+        .map(|(table, request_type)| {
+            let fields = [
+                request_type.required_fields(),
+                request_type.optional_fields(),
+            ]
+            .concat();
+            (table, SystemTemplate::from_fields(request_type, &fields))
+        })
+        .collect()
 }
 
-static SYSTEM_TEMPLATES: spin::Once<Vec<SystemRequest>> = spin::Once::new();
-fn get_system_templates() -> &'static Vec<SystemRequest> {
-    SYSTEM_TEMPLATES.call_once(|| {
-        vec![
-            SystemRequest {
-                request_type: Message,
-                fields: vec![
-                    ("SENDER", SystemFieldType::Bytes).into(),
-                    ("BODY", SystemFieldType::Bytes).into(),
-                    ("NONCE", Decimal).into(),
-                ],
-                table_id: TableIdentifier::from_str_unchecked("MESSAGE", "SXT_SYSTEM_STAKING"),
-            },
-            SystemRequest {
-                request_type: Staking(StakingSystemRequest::Stake),
-                fields: vec![
-                    ("STAKER", SystemFieldType::Bytes).into(),
-                    ("AMOUNT", Decimal).into(),
-                ],
-                table_id: TableIdentifier::from_str_unchecked("STAKED", "SXT_SYSTEM_STAKING"),
-            },
-            SystemRequest {
-                request_type: Staking(StakingSystemRequest::Nominate),
-                fields: vec![
-                    ("NOMINATOR", SystemFieldType::Bytes).into(),
-                    ("NODESED25519PUBKEYS", Varchar).into(),
-                ],
-                table_id: TableIdentifier::from_str_unchecked("NOMINATED", "SXT_SYSTEM_STAKING"),
-            },
-            SystemRequest {
-                request_type: Staking(StakingSystemRequest::UnstakeInitiated),
-                fields: vec![("STAKER", SystemFieldType::Bytes).into()],
-                table_id: TableIdentifier::from_str_unchecked(
-                    "UNSTAKEINITIATED",
-                    "SXT_SYSTEM_STAKING",
-                ),
-            },
-            SystemRequest {
-                request_type: Staking(StakingSystemRequest::UnstakeCancelled),
-                fields: vec![("STAKER", SystemFieldType::Bytes).into()],
-                table_id: TableIdentifier::from_str_unchecked(
-                    "UNSTAKECANCELLED",
-                    "SXT_SYSTEM_STAKING",
-                ),
-            },
-        ]
+/// The templates of the `SXT_SYSTEM_STAKING` tables as registered by storage version 1,
+/// declaring the required fields of their handlers only
+pub fn v1_default_templates() -> Vec<(TableIdentifier, SystemTemplate)> {
+    default_request_types()
+        .map(|(table, request_type)| {
+            (
+                table,
+                SystemTemplate::from_fields(request_type, request_type.required_fields()),
+            )
+        })
+        .collect()
+}
+
+/// The `SXT_SYSTEM_STAKING` tables with default templates, and their handlers
+fn default_request_types() -> impl Iterator<Item = (TableIdentifier, SystemRequestType)> {
+    [
+        ("MESSAGE", Message),
+        ("STAKED", Staking(StakingSystemRequest::Stake)),
+        ("NOMINATED", Staking(StakingSystemRequest::Nominate)),
+        (
+            "UNSTAKEINITIATED",
+            Staking(StakingSystemRequest::UnstakeInitiated),
+        ),
+        (
+            "UNSTAKECANCELLED",
+            Staking(StakingSystemRequest::UnstakeCancelled),
+        ),
+    ]
+    .into_iter()
+    .map(|(name, request_type)| {
+        (
+            TableIdentifier::from_str_unchecked(name, "SXT_SYSTEM_STAKING"),
+            request_type,
+        )
     })
 }
 
-/// Creates a SystemRequest object with relevant fields based on the supplied template
-/// and OnChainTable
-fn parse_request_with_template(oc_table: OnChainTable, template: &SystemRequest) -> SystemRequest {
+/// Converts a given OnChainTable into a SystemRequest object, with the fields of the template
+/// registered for its table
+pub fn table_to_request(
+    oc_table: OnChainTable,
+    table_id: TableIdentifier,
+    template: &SystemTemplate,
+) -> SystemRequest {
     let fields: Vec<SystemTableField> = template
         .fields
         .iter()
-        .filter_map(|f| match f.value_type {
-            Varchar => oc_table
-                .get_varchars_by_column(&f.name)
-                .map(|data| SystemTableField {
-                    name: f.name.clone(),
-                    value_type: Varchar,
-                    values: data
-                        .iter()
-                        .map(|v| SystemFieldValue::Varchar(v.clone()))
-                        .collect(),
-                }),
-            Decimal => oc_table
-                .get_decimal_by_column(&f.name)
-                .map(|data| SystemTableField {
-                    name: f.name.clone(),
-                    value_type: Decimal,
-                    values: data.iter().map(|v| SystemFieldValue::Decimal(*v)).collect(),
-                }),
-            SystemFieldType::Bytes => {
-                oc_table
-                    .get_bytes_by_column(&f.name)
-                    .map(|data| SystemTableField {
-                        name: f.name.clone(),
-                        value_type: SystemFieldType::Bytes,
-                        values: data
-                            .iter()
-                            .map(|v| SystemFieldValue::Bytes(v.clone()))
-                            .collect(),
-                    })
-            }
+        .filter_map(|f| {
+            let name = String::from(from_utf8(&f.name).ok()?);
+            let values: Vec<SystemFieldValue> = match f.field_type {
+                Varchar => oc_table
+                    .get_varchars_by_column(&name)?
+                    .iter()
+                    .map(|v| SystemFieldValue::Varchar(v.clone()))
+                    .collect(),
+                Decimal => oc_table
+                    .get_decimal_by_column(&name)?
+                    .iter()
+                    .map(|v| SystemFieldValue::Decimal(*v))
+                    .collect(),
+                SystemFieldType::Bytes => oc_table
+                    .get_bytes_by_column(&name)?
+                    .iter()
+                    .map(|v| SystemFieldValue::Bytes(v.clone()))
+                    .collect(),
+                SystemFieldType::Int => oc_table
+                    .get_ints_by_column(&name)?
+                    .iter()
+                    .map(|v| SystemFieldValue::Int(*v))
+                    .collect(),
+                SystemFieldType::BigInt => oc_table
+                    .get_bigints_by_column(&name)?
+                    .iter()
+                    .map(|v| SystemFieldValue::BigInt(*v))
+                    .collect(),
+            };
+            Some(SystemTableField {
+                name,
+                value_type: f.field_type,
+                values,
+            })
         })
         .collect();
 
     SystemRequest {
         request_type: template.request_type,
-        table_id: template.table_id.clone(),
+        table_id,
         fields,
     }
 }
-
-/// Returns the parsing template for the provided table identifier. The template is a
-/// SystemRequest that contains the corresponding SystemTableFields with no values.
-pub fn template_for_identifier(table_identifier: TableIdentifier) -> Option<SystemRequest> {
-    for t in get_system_templates() {
-        if t.table_id == table_identifier {
-            return Some(t.clone());
-        }
-    }
-    None
-}
-
-/// Converts a given OnChainTable into a SystemRequest object
-pub fn table_to_request(
-    oc_table: OnChainTable,
-    table_identifier: TableIdentifier,
-) -> Option<SystemRequest> {
-    template_for_identifier(table_identifier)
-        .map(|template| parse_request_with_template(oc_table, &template))
-}
@@ -0,0 +1,55 @@
+//! Runtime APIs for reading the staking state mirrored from the EVM staking contract.
+
+use alloc::vec::Vec;
+
+use codec::{Decode, Encode};
+use frame_support::pallet_prelude::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+/// The maximum stakers returned by one call to `StakingMirrorApi::mirrored_stakers`.
+pub const MAX_MIRRORED_STAKERS_PAGE: u32 = 1024;
+
+/// An amount being unbonded by a staker.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct MirroredUnbonding {
+    /// The amount unbonding.
+    pub value: u128,
+    /// The era the amount can be withdrawn in.
+    pub era: u32,
+}
+
+/// The staking state of an EVM staker, as mirrored on chain.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct MirroredStaker<AccountId> {
+    /// The ethereum address of the staker.
+    pub address: [u8; 20],
+    /// The account the staker is mirrored to.
+    pub account: AccountId,
+    /// The amount bonded, including the amounts unbonding.
+    pub bonded: u128,
+    /// The amount bonded and not unbonding.
+    pub active: u128,
+    /// The amounts unbonding.
+    pub unbonding: Vec<MirroredUnbonding>,
+    /// The validators nominated by the staker.
+    pub nominations: Vec<AccountId>,
+    /// Whether the staker is validating.
+    pub validating: bool,
+}
+
+sp_api::decl_runtime_apis! {
+    /// Runtime APIs for reading the staking state mirrored from the EVM staking contract.
+    pub trait StakingMirrorApi<AccountId> where AccountId: codec::Codec {
+        /// Returns the mirrored state of the bonded EVM stakers, in storage order, starting
+        /// after the staker with the address `start_after`.
+        ///
+        /// At most `limit` stakers are returned, capped at [`MAX_MIRRORED_STAKERS_PAGE`]. Fewer
+        /// stakers are returned on the last page.
+        fn mirrored_stakers(start_after: Option<[u8; 20]>, limit: u32) -> Vec<MirroredStaker<AccountId>>;
+
+        /// Returns the mirrored state of the EVM staker with the given address.
+        ///
+        /// Returns `None` if the staker isn't bonded.
+        fn mirrored_staker(address: [u8; 20]) -> Option<MirroredStaker<AccountId>>;
+    }
+}
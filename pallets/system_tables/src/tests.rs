@@ -1,6 +1,7 @@
 use core::str::from_utf8;
 
 use env_logger::Env;
+use frame_support::dispatch::GetDispatchInfo;
 use frame_support::{assert_err, assert_ok};
 use frame_system::RawOrigin;
 use log::error;
@@ -17,6 +18,7 @@ use sxt_core::utils::{
     eth_address_to_substrate_account_id,
 };
 
+use crate::messages::{encode_message, EvmMessage, MessagePayee, MessageType};
 use crate::mock::*;
 use crate::parse::{
     StakingSystemRequest,
@@ -25,7 +27,14 @@ use crate::parse::{
     SystemRequestType,
     SystemTableField,
 };
-use crate::Pallet;
+use crate::{
+    Pallet,
+    SystemTemplate,
+    SystemTemplates,
+    TemplateField,
+    TemplateFieldName,
+    TemplateFields,
+};
 
 // Example SCALE encoded Session keys from calling author_rotateKeys() on Alice
 const ALICE_SESSION_KEYS: &str = "3084486e870e12fc551eacc173291f0d75ac5fed823aeb1e158bc98db215936202a555f88490d19f7fbacac7078fc87886084efd8227187a73ad05aee6da8ad38edd8739daa5689e9e118eb3be0330bbf80a30ad7639d4f0d70970dbccff9c4a";
@@ -72,6 +81,39 @@ fn get_register_keys_message(eth_wallet: &str, session_keys: &str, nonce: U256)
     }
 }
 
+fn get_envelope_message(
+    eth_wallet: &str,
+    message: &EvmMessage<<Test as pallet_session::Config>::Keys>,
+    nonce: U256,
+) -> SystemRequest {
+    let mut request = get_register_keys_message(eth_wallet, "", nonce);
+    request.fields[1] = SystemTableField::with_value(
+        "BODY".to_string(),
+        SystemFieldValue::Bytes(encode_message(message)),
+    );
+    request
+}
+
+fn last_system_tables_event() -> Option<crate::Event<Test>> {
+    match System::events().pop().map(|record| record.event) {
+        Some(RuntimeEvent::SystemTables(event)) => Some(event),
+        _ => None,
+    }
+}
+
+/// Bond the test wallet and register session keys, returning the wallet account
+fn register_test_validator() -> AccountId32 {
+    let bonding = get_staked_message(ETH_TEST_WALLET, 100.into());
+    assert_ok!(crate::process_staking::<Test>(bonding));
+
+    let request = get_register_keys_message(ETH_TEST_WALLET, ALICE_SESSION_KEYS, U256::from(1));
+    assert_ok!(crate::process_evm_message::<Test>(request));
+
+    let wallet = eth_address_to_substrate_account_id::<Test>(ETH_TEST_WALLET).unwrap();
+    assert!(pallet_staking::Validators::<Test>::contains_key(&wallet));
+    wallet
+}
+
 #[test]
 fn bonding_with_an_account_works() {
     new_test_ext().execute_with(|| {
@@ -126,8 +168,10 @@ fn bonding_extra_with_an_account_works() {
 
         // Make sure there are no errors in the events
         let events = System::events();
-        if let Some(RuntimeEvent::SystemTables(crate::Event::MessageProcessingError { error })) =
-            events.last().map(|e| &e.event)
+        if let Some(RuntimeEvent::SystemTables(crate::Event::MessageProcessingError {
+            error,
+            ..
+        })) = events.last().map(|e| &e.event)
         {
             panic!("Expected no errors!");
         }
@@ -151,8 +195,10 @@ fn bonding_extra_with_an_account_works() {
 
         // Make sure there are no errors in the events
         let events = System::events();
-        if let Some(RuntimeEvent::SystemTables(crate::Event::MessageProcessingError { error })) =
-            events.last().map(|e| &e.event)
+        if let Some(RuntimeEvent::SystemTables(crate::Event::MessageProcessingError {
+            error,
+            ..
+        })) = events.last().map(|e| &e.event)
         {
             panic!("Expected no errors! Got: {:?}", error);
         }
@@ -197,11 +243,11 @@ fn registering_keys_without_bonding_first_causes_error() {
 
         let events = System::events();
         match events.last().map(|e| &e.event) {
-            Some(RuntimeEvent::SystemTables(crate::Event::MessageProcessingError { error })) => {
-                assert_eq!(
-                    error,
-                    &DispatchError::from(pallet_staking::Error::<Test>::NotStash)
-                );
+            Some(RuntimeEvent::SystemTables(crate::Event::MessageProcessingError {
+                error,
+                ..
+            })) => {
+                assert_eq!(error, &DispatchError::from(crate::Error::<Test>::NotBonded));
             }
             _ => panic!("Expected MessageProcessingError event not found"),
         }
@@ -396,3 +442,1120 @@ fn default_commission_is_10_percent() {
         assert_eq!(prefs.commission, Perbill::from_percent(10));
     });
 }
+
+#[test]
+fn versioned_session_keys_message_registers_keys() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let bonding = get_staked_message(ETH_TEST_WALLET, 100.into());
+        assert_ok!(crate::process_staking::<Test>(bonding));
+
+        let message = EvmMessage::SetSessionKeys {
+            keys: sp_runtime::testing::UintAuthorityId(7),
+        };
+        let request = get_envelope_message(ETH_TEST_WALLET, &message, U256::from(1));
+        assert_ok!(crate::process_evm_message::<Test>(request));
+
+        let wallet = eth_address_to_substrate_account_id::<Test>(ETH_TEST_WALLET).unwrap();
+        assert!(pallet_staking::Validators::<Test>::contains_key(&wallet));
+        assert_eq!(
+            pallet_session::NextKeys::<Test>::get(&wallet),
+            Some(sp_runtime::testing::UintAuthorityId(7))
+        );
+        assert!(matches!(
+            last_system_tables_event(),
+            Some(crate::Event::MessageReceived {
+                message_type: MessageType::SetSessionKeys,
+                ..
+            })
+        ));
+    });
+}
+
+#[test]
+fn versioned_session_keys_message_requires_a_bonded_sender() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let message = EvmMessage::SetSessionKeys {
+            keys: sp_runtime::testing::UintAuthorityId(7),
+        };
+        let request = get_envelope_message(ETH_TEST_WALLET, &message, U256::from(1));
+        assert_ok!(crate::process_evm_message::<Test>(request));
+
+        let wallet = eth_address_to_substrate_account_id::<Test>(ETH_TEST_WALLET).unwrap();
+        assert!(!pallet_staking::Validators::<Test>::contains_key(&wallet));
+        assert!(!pallet_session::NextKeys::<Test>::contains_key(&wallet));
+        assert_eq!(
+            last_system_tables_event(),
+            Some(crate::Event::MessageProcessingError {
+                error: crate::Error::<Test>::NotBonded.into(),
+                message_type: Some(MessageType::SetSessionKeys),
+            })
+        );
+    });
+}
+
+#[test]
+fn set_commission_message_updates_validator_prefs() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let wallet = register_test_validator();
+
+        let message = EvmMessage::SetCommission {
+            commission: Perbill::from_percent(20),
+            blocked: true,
+        };
+        let request = get_envelope_message(ETH_TEST_WALLET, &message, U256::from(2));
+        assert_ok!(crate::process_evm_message::<Test>(request));
+
+        let prefs = pallet_staking::Validators::<Test>::get(&wallet);
+        assert_eq!(prefs.commission, Perbill::from_percent(20));
+        assert!(prefs.blocked);
+
+        // Registering keys again keeps the commission
+        let request = get_register_keys_message(ETH_TEST_WALLET, ALICE_SESSION_KEYS, U256::from(3));
+        assert_ok!(crate::process_evm_message::<Test>(request));
+        let prefs = pallet_staking::Validators::<Test>::get(&wallet);
+        assert_eq!(prefs.commission, Perbill::from_percent(20));
+    });
+}
+
+#[test]
+fn set_commission_message_requires_a_validator() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let bonding = get_staked_message(ETH_TEST_WALLET, 100.into());
+        assert_ok!(crate::process_staking::<Test>(bonding));
+
+        let message = EvmMessage::SetCommission {
+            commission: Perbill::from_percent(20),
+            blocked: false,
+        };
+        let request = get_envelope_message(ETH_TEST_WALLET, &message, U256::from(1));
+        assert_ok!(crate::process_evm_message::<Test>(request));
+
+        assert_eq!(
+            last_system_tables_event(),
+            Some(crate::Event::MessageProcessingError {
+                error: crate::Error::<Test>::NotValidator.into(),
+                message_type: Some(MessageType::SetCommission),
+            })
+        );
+    });
+}
+
+#[test]
+fn chill_message_stops_validating() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let wallet = register_test_validator();
+
+        let request = get_envelope_message(ETH_TEST_WALLET, &EvmMessage::Chill, U256::from(2));
+        assert_ok!(crate::process_evm_message::<Test>(request));
+
+        assert!(!pallet_staking::Validators::<Test>::contains_key(&wallet));
+        assert!(matches!(
+            last_system_tables_event(),
+            Some(crate::Event::MessageReceived {
+                message_type: MessageType::Chill,
+                ..
+            })
+        ));
+    });
+}
+
+#[test]
+fn purge_keys_message_removes_session_keys() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let wallet = register_test_validator();
+        assert!(pallet_session::NextKeys::<Test>::contains_key(&wallet));
+
+        let request = get_envelope_message(ETH_TEST_WALLET, &EvmMessage::PurgeKeys, U256::from(2));
+        assert_ok!(crate::process_evm_message::<Test>(request));
+
+        assert!(!pallet_session::NextKeys::<Test>::contains_key(&wallet));
+    });
+}
+
+#[test]
+fn set_payee_message_sets_the_reward_destination() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let wallet = register_test_validator();
+
+        let payee_address = [7u8; 20];
+        let message = EvmMessage::SetPayee {
+            payee: MessagePayee::Account(payee_address),
+        };
+        let request = get_envelope_message(ETH_TEST_WALLET, &message, U256::from(2));
+        assert_ok!(crate::process_evm_message::<Test>(request));
+
+        let payee_account =
+            eth_address_to_substrate_account_id::<Test>(&hex::encode(payee_address)).unwrap();
+        assert_eq!(
+            pallet_staking::Payee::<Test>::get(&wallet),
+            Some(pallet_staking::RewardDestination::Account(payee_account))
+        );
+
+        // The zero address is refused
+        let message = EvmMessage::SetPayee {
+            payee: MessagePayee::Account([0; 20]),
+        };
+        let request = get_envelope_message(ETH_TEST_WALLET, &message, U256::from(3));
+        assert_ok!(crate::process_evm_message::<Test>(request));
+        assert_eq!(
+            last_system_tables_event(),
+            Some(crate::Event::MessageProcessingError {
+                error: crate::Error::<Test>::InvalidPayee.into(),
+                message_type: Some(MessageType::SetPayee),
+            })
+        );
+    });
+}
+
+#[test]
+fn unsupported_message_versions_are_rejected() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        register_test_validator();
+
+        let mut body = encode_message(&EvmMessage::<<Test as pallet_session::Config>::Keys>::Chill);
+        body[crate::messages::MESSAGE_MAGIC.len()] = crate::messages::MESSAGE_VERSION + 1;
+        let mut request = get_register_keys_message(ETH_TEST_WALLET, "", U256::from(2));
+        request.fields[1] =
+            SystemTableField::with_value("BODY".to_string(), SystemFieldValue::Bytes(body));
+        assert_ok!(crate::process_evm_message::<Test>(request));
+
+        assert_eq!(
+            last_system_tables_event(),
+            Some(crate::Event::MessageProcessingError {
+                error: crate::Error::<Test>::UnsupportedMessageVersion.into(),
+                message_type: None,
+            })
+        );
+    });
+}
+
+const STAKE_TABLE_DDL: &str = "CREATE TABLE SXT_SYSTEM_STAKING.RESTAKED(
+    block_number bigint not null,
+    event_index int not null,
+    staker binary not null,
+    amount decimal(75, 0) not null,
+    memo varchar not null,
+    primary key(block_number, event_index)
+)";
+
+fn restaked_table() -> TableIdentifier {
+    TableIdentifier::from_str_unchecked("RESTAKED", "SXT_SYSTEM_STAKING")
+}
+
+fn create_restaked_table() {
+    let table = restaked_table();
+    pallet_tables::Schemas::<Test>::insert(
+        &table.namespace,
+        &table.name,
+        sxt_core::tables::create_statement(STAKE_TABLE_DDL),
+    );
+}
+
+fn template(
+    request_type: SystemRequestType,
+    fields: &[(&str, crate::SystemFieldType)],
+) -> SystemTemplate {
+    SystemTemplate {
+        request_type,
+        fields: TemplateFields::try_from(
+            fields
+                .iter()
+                .map(|(name, field_type)| TemplateField {
+                    name: TemplateFieldName::try_from(name.as_bytes().to_vec()).unwrap(),
+                    field_type: *field_type,
+                })
+                .collect::<Vec<_>>(),
+        )
+        .unwrap(),
+    }
+}
+
+fn stake_template() -> SystemTemplate {
+    template(
+        SystemRequestType::Staking(StakingSystemRequest::Stake),
+        &[
+            ("STAKER", crate::SystemFieldType::Bytes),
+            ("AMOUNT", crate::SystemFieldType::Decimal),
+        ],
+    )
+}
+
+#[test]
+fn default_templates_are_registered_at_genesis() {
+    new_test_ext().execute_with(|| {
+        let template = SystemTemplates::<Test>::get(TableIdentifier::from_str_unchecked(
+            "MESSAGE",
+            "SXT_SYSTEM_STAKING",
+        ))
+        .unwrap();
+        assert_eq!(template.request_type, SystemRequestType::Message);
+        assert_eq!(template.fields.len(), 3);
+        assert_eq!(SystemTemplates::<Test>::iter().count(), 5);
+    });
+}
+
+#[test]
+fn templates_can_be_registered_and_removed_by_root() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_restaked_table();
+
+        assert_err!(
+            Pallet::<Test>::register_system_template(
+                RuntimeOrigin::signed(
+                    eth_address_to_substrate_account_id::<Test>(ETH_TEST_WALLET).unwrap()
+                ),
+                restaked_table(),
+                stake_template(),
+            ),
+            DispatchError::BadOrigin
+        );
+
+        assert_ok!(Pallet::<Test>::register_system_template(
+            RuntimeOrigin::root(),
+            restaked_table(),
+            stake_template(),
+        ));
+        assert_eq!(
+            SystemTemplates::<Test>::get(restaked_table()),
+            Some(stake_template())
+        );
+
+        assert_ok!(Pallet::<Test>::remove_system_template(
+            RuntimeOrigin::root(),
+            restaked_table(),
+        ));
+        assert_eq!(SystemTemplates::<Test>::get(restaked_table()), None);
+        assert_err!(
+            Pallet::<Test>::remove_system_template(RuntimeOrigin::root(), restaked_table()),
+            crate::Error::<Test>::TemplateNotFound
+        );
+    });
+}
+
+#[test]
+fn registering_templates_is_charged_per_byte_of_the_table_schema() {
+    new_test_ext().execute_with(|| {
+        let register_weight = || {
+            crate::Call::<Test>::register_system_template {
+                table: restaked_table(),
+                template: stake_template(),
+            }
+            .get_dispatch_info()
+            .weight
+        };
+        let without_schema = register_weight();
+
+        create_restaked_table();
+        let with_schema = register_weight();
+
+        let schema_len = STAKE_TABLE_DDL.len() as u64;
+        assert!(with_schema.ref_time() > without_schema.ref_time() + schema_len);
+        assert_eq!(
+            with_schema.proof_size(),
+            without_schema.proof_size() + schema_len
+        );
+    });
+}
+
+#[test]
+fn templates_are_validated_against_the_table_schema() {
+    new_test_ext().execute_with(|| {
+        assert_err!(
+            Pallet::<Test>::register_system_template(
+                RuntimeOrigin::root(),
+                restaked_table(),
+                stake_template(),
+            ),
+            crate::Error::<Test>::TemplateTableNotFound
+        );
+
+        create_restaked_table();
+        let stake = SystemRequestType::Staking(StakingSystemRequest::Stake);
+        let cases = [
+            (
+                template(
+                    stake,
+                    &[
+                        ("STAKER", crate::SystemFieldType::Bytes),
+                        ("AMOUNT", crate::SystemFieldType::Varchar),
+                    ],
+                ),
+                crate::Error::<Test>::TemplateColumnTypeMismatch,
+            ),
+            (
+                template(
+                    stake,
+                    &[
+                        ("STAKER", crate::SystemFieldType::Bytes),
+                        ("AMOUNT", crate::SystemFieldType::Decimal),
+                        ("SHARES", crate::SystemFieldType::Decimal),
+                    ],
+                ),
+                crate::Error::<Test>::TemplateColumnNotFound,
+            ),
+            (
+                template(stake, &[("STAKER", crate::SystemFieldType::Bytes)]),
+                crate::Error::<Test>::MissingTemplateField,
+            ),
+            (
+                template(
+                    stake,
+                    &[
+                        ("STAKER", crate::SystemFieldType::Bytes),
+                        ("STAKER", crate::SystemFieldType::Bytes),
+                        ("AMOUNT", crate::SystemFieldType::Decimal),
+                    ],
+                ),
+                crate::Error::<Test>::DuplicateTemplateField,
+            ),
+        ];
+        for (template, error) in cases {
+            assert_err!(
+                Pallet::<Test>::register_system_template(
+                    RuntimeOrigin::root(),
+                    restaked_table(),
+                    template,
+                ),
+                error
+            );
+        }
+
+        // Fields beyond the ones the handler reads are allowed
+        assert_ok!(Pallet::<Test>::register_system_template(
+            RuntimeOrigin::root(),
+            restaked_table(),
+            template(
+                stake,
+                &[
+                    ("STAKER", crate::SystemFieldType::Bytes),
+                    ("AMOUNT", crate::SystemFieldType::Decimal),
+                    ("MEMO", crate::SystemFieldType::Varchar),
+                ],
+            ),
+        ));
+    });
+}
+
+#[test]
+fn inserts_are_processed_by_the_handler_of_their_registered_template() {
+    use on_chain_table::OnChainColumn;
+    use proof_of_sql::base::math::decimal::Precision;
+    use sqlparser::ast::Ident;
+
+    new_test_ext().execute_with(|| {
+        let insert = || {
+            OnChainTable::try_from_iter([
+                (
+                    Ident::new("STAKER"),
+                    OnChainColumn::VarBinary(vec![hex::decode(ETH_TEST_WALLET).unwrap()]),
+                ),
+                (
+                    Ident::new("AMOUNT"),
+                    OnChainColumn::Decimal75(Precision::new(75).unwrap(), 0, vec![U256::from(100)]),
+                ),
+            ])
+            .unwrap()
+        };
+        let wallet = eth_address_to_substrate_account_id::<Test>(ETH_TEST_WALLET).unwrap();
+
+        // Without a template, inserts are not processed
+        assert_ok!(Pallet::<Test>::process_system_table(
+            restaked_table(),
+            insert()
+        ));
+        assert_eq!(pallet_staking::Pallet::<Test>::bonded(&wallet), None);
+
+        create_restaked_table();
+        assert_ok!(Pallet::<Test>::register_system_template(
+            RuntimeOrigin::root(),
+            restaked_table(),
+            stake_template(),
+        ));
+        assert_ok!(Pallet::<Test>::process_system_table(
+            restaked_table(),
+            insert()
+        ));
+        assert_eq!(
+            pallet_staking::Pallet::<Test>::bonded(&wallet),
+            Some(wallet.clone())
+        );
+    });
+}
+
+fn get_zkpay_payment(wallet: &str, amount: U256, nonce: U256) -> SystemRequest {
+    SystemRequest {
+        request_type: SystemRequestType::ZkPay,
+        table_id: TableIdentifier::from_str_unchecked("PAYMENT", "SXT_SYSTEM_ZKPAY"),
+        fields: vec![
+            SystemTableField::with_value(
+                "SENDER".to_string(),
+                SystemFieldValue::Bytes(hex::decode(wallet).unwrap()),
+            ),
+            SystemTableField::with_value("AMOUNT".to_string(), SystemFieldValue::Decimal(amount)),
+            SystemTableField::with_value("NONCE".to_string(), SystemFieldValue::Decimal(nonce)),
+        ],
+    }
+}
+
+/// Set a ZK-Pay treasury holding `balance`
+fn set_zkpay_treasury(balance: u128) -> AccountId32 {
+    let treasury = AccountId32::new([9; 32]);
+    assert_ok!(pallet_balances::Pallet::<Test>::force_set_balance(
+        RuntimeOrigin::root(),
+        treasury.clone(),
+        balance,
+    ));
+    assert_ok!(Pallet::<Test>::set_zkpay_treasury(
+        RuntimeOrigin::root(),
+        Some(treasury.clone()),
+    ));
+    treasury
+}
+
+#[test]
+fn zkpay_payments_fail_without_a_treasury() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let wallet = eth_address_to_substrate_account_id::<Test>(ETH_TEST_WALLET).unwrap();
+
+        let payment = get_zkpay_payment(ETH_TEST_WALLET, 500.into(), U256::from(1));
+        assert_ok!(crate::process_request::<Test>(payment));
+
+        assert_eq!(
+            last_system_tables_event(),
+            Some(crate::Event::ZkPayFailed {
+                sender: hex::decode(ETH_TEST_WALLET).unwrap().try_into().unwrap(),
+                nonce: U256::from(1),
+                error: crate::Error::<Test>::ZkPayTreasuryNotSet.into(),
+            })
+        );
+        assert_eq!(pallet_balances::Pallet::<Test>::free_balance(&wallet), 0);
+        assert_eq!(pallet_balances::Pallet::<Test>::total_issuance(), 0);
+        assert_eq!(
+            crate::ZkPayNonces::<Test>::get(&wallet),
+            Some(U256::from(1))
+        );
+        assert_eq!(
+            crate::FailedZkPayments::<Test>::get(&wallet, U256::from(1)),
+            Some(crate::FailedZkPayment {
+                sender: hex::decode(ETH_TEST_WALLET).unwrap().try_into().unwrap(),
+                amount: 500,
+            })
+        );
+    });
+}
+
+#[test]
+fn zkpay_payments_are_transferred_from_the_treasury() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let wallet = eth_address_to_substrate_account_id::<Test>(ETH_TEST_WALLET).unwrap();
+        let treasury = set_zkpay_treasury(1_000);
+
+        let payment = get_zkpay_payment(ETH_TEST_WALLET, 400.into(), U256::from(1));
+        assert_ok!(crate::process_request::<Test>(payment));
+        assert_eq!(pallet_balances::Pallet::<Test>::free_balance(&wallet), 400);
+        assert_eq!(
+            pallet_balances::Pallet::<Test>::free_balance(&treasury),
+            600
+        );
+        assert_eq!(pallet_balances::Pallet::<Test>::total_issuance(), 1_000);
+        assert_eq!(
+            last_system_tables_event(),
+            Some(crate::Event::ZkPayCredited {
+                sender: hex::decode(ETH_TEST_WALLET).unwrap().try_into().unwrap(),
+                account: wallet.clone(),
+                amount: 400,
+                nonce: U256::from(1),
+                treasury: treasury.clone(),
+            })
+        );
+
+        // A payment the treasury can't cover fails, and is kept to be retried
+        let payment = get_zkpay_payment(ETH_TEST_WALLET, 900.into(), U256::from(2));
+        assert_ok!(crate::process_request::<Test>(payment.clone()));
+        assert!(matches!(
+            last_system_tables_event(),
+            Some(crate::Event::ZkPayFailed { nonce, .. }) if nonce == U256::from(2)
+        ));
+        assert_eq!(pallet_balances::Pallet::<Test>::free_balance(&wallet), 400);
+        assert_eq!(
+            pallet_balances::Pallet::<Test>::free_balance(&treasury),
+            600
+        );
+        assert!(crate::FailedZkPayments::<Test>::contains_key(
+            &wallet,
+            U256::from(2)
+        ));
+
+        // The later payments of the payer are still credited
+        let payment = get_zkpay_payment(ETH_TEST_WALLET, 100.into(), U256::from(3));
+        assert_ok!(crate::process_request::<Test>(payment));
+        assert_eq!(pallet_balances::Pallet::<Test>::free_balance(&wallet), 500);
+        assert_eq!(
+            crate::ZkPayNonces::<Test>::get(&wallet),
+            Some(U256::from(3))
+        );
+
+        // Once the treasury is funded, the failed payment is credited when retried
+        assert_ok!(pallet_balances::Pallet::<Test>::force_set_balance(
+            RuntimeOrigin::root(),
+            treasury.clone(),
+            1_000,
+        ));
+        assert_ok!(Pallet::<Test>::retry_zkpay_payment(
+            RuntimeOrigin::root(),
+            ETH_TEST_WALLET.to_string(),
+            2,
+        ));
+        assert_eq!(
+            pallet_balances::Pallet::<Test>::free_balance(&wallet),
+            1_400
+        );
+        assert_eq!(
+            last_system_tables_event(),
+            Some(crate::Event::ZkPayCredited {
+                sender: hex::decode(ETH_TEST_WALLET).unwrap().try_into().unwrap(),
+                account: wallet.clone(),
+                amount: 900,
+                nonce: U256::from(2),
+                treasury: treasury.clone(),
+            })
+        );
+        assert!(!crate::FailedZkPayments::<Test>::contains_key(
+            &wallet,
+            U256::from(2)
+        ));
+        assert_err!(
+            Pallet::<Test>::retry_zkpay_payment(
+                RuntimeOrigin::root(),
+                ETH_TEST_WALLET.to_string(),
+                2,
+            ),
+            crate::Error::<Test>::ZkPayPaymentNotFound
+        );
+    });
+}
+
+#[test]
+fn failed_zkpay_payments_are_kept_until_a_retry_succeeds() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let wallet = eth_address_to_substrate_account_id::<Test>(ETH_TEST_WALLET).unwrap();
+
+        let payment = get_zkpay_payment(ETH_TEST_WALLET, 500.into(), U256::from(1));
+        assert_ok!(crate::process_request::<Test>(payment));
+
+        assert_err!(
+            Pallet::<Test>::retry_zkpay_payment(
+                RuntimeOrigin::signed(wallet.clone()),
+                ETH_TEST_WALLET.to_string(),
+                1,
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_err!(
+            Pallet::<Test>::retry_zkpay_payment(
+                RuntimeOrigin::root(),
+                ETH_TEST_WALLET.to_string(),
+                1,
+            ),
+            crate::Error::<Test>::ZkPayTreasuryNotSet
+        );
+
+        // The treasury can't cover the payment yet
+        set_zkpay_treasury(400);
+        assert!(Pallet::<Test>::retry_zkpay_payment(
+            RuntimeOrigin::root(),
+            ETH_TEST_WALLET.to_string(),
+            1,
+        )
+        .is_err());
+        assert!(crate::FailedZkPayments::<Test>::contains_key(
+            &wallet,
+            U256::from(1)
+        ));
+
+        set_zkpay_treasury(1_000);
+        assert_ok!(Pallet::<Test>::retry_zkpay_payment(
+            RuntimeOrigin::root(),
+            ETH_TEST_WALLET.to_string(),
+            1,
+        ));
+        assert_eq!(pallet_balances::Pallet::<Test>::free_balance(&wallet), 500);
+        assert!(!crate::FailedZkPayments::<Test>::contains_key(
+            &wallet,
+            U256::from(1)
+        ));
+    });
+}
+
+#[test]
+fn zkpay_payments_of_invalid_amounts_use_their_nonce_and_are_not_kept() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let wallet = eth_address_to_substrate_account_id::<Test>(ETH_TEST_WALLET).unwrap();
+        set_zkpay_treasury(1_000);
+
+        let payment = get_zkpay_payment(ETH_TEST_WALLET, U256::zero(), U256::from(1));
+        assert_ok!(crate::process_request::<Test>(payment));
+        assert_eq!(
+            last_system_tables_event(),
+            Some(crate::Event::ZkPayFailed {
+                sender: hex::decode(ETH_TEST_WALLET).unwrap().try_into().unwrap(),
+                nonce: U256::from(1),
+                error: crate::Error::<Test>::InvalidZkPayAmount.into(),
+            })
+        );
+        assert_eq!(
+            crate::ZkPayNonces::<Test>::get(&wallet),
+            Some(U256::from(1))
+        );
+        assert!(!crate::FailedZkPayments::<Test>::contains_key(
+            &wallet,
+            U256::from(1)
+        ));
+
+        let payment = get_zkpay_payment(ETH_TEST_WALLET, 200.into(), U256::from(2));
+        assert_ok!(crate::process_request::<Test>(payment));
+        assert_eq!(pallet_balances::Pallet::<Test>::free_balance(&wallet), 200);
+    });
+}
+
+#[test]
+fn zkpay_nonces_can_be_set_by_root() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let wallet = eth_address_to_substrate_account_id::<Test>(ETH_TEST_WALLET).unwrap();
+        set_zkpay_treasury(1_000);
+
+        assert_err!(
+            Pallet::<Test>::set_zkpay_nonce(
+                RuntimeOrigin::signed(wallet.clone()),
+                ETH_TEST_WALLET.to_string(),
+                1,
+            ),
+            DispatchError::BadOrigin
+        );
+
+        // Skip the first payment, which could not be credited
+        assert_ok!(Pallet::<Test>::set_zkpay_nonce(
+            RuntimeOrigin::root(),
+            ETH_TEST_WALLET.to_string(),
+            1,
+        ));
+        let payment = get_zkpay_payment(ETH_TEST_WALLET, 300.into(), U256::from(2));
+        assert_ok!(crate::process_request::<Test>(payment));
+        assert_eq!(pallet_balances::Pallet::<Test>::free_balance(&wallet), 300);
+        assert_eq!(
+            crate::ZkPayNonces::<Test>::get(&wallet),
+            Some(U256::from(2))
+        );
+    });
+}
+
+#[test]
+fn zkpay_payments_are_not_credited_twice() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let wallet = eth_address_to_substrate_account_id::<Test>(ETH_TEST_WALLET).unwrap();
+        set_zkpay_treasury(1_000);
+
+        let payment = get_zkpay_payment(ETH_TEST_WALLET, 500.into(), U256::from(1));
+        assert_ok!(crate::process_request::<Test>(payment.clone()));
+        assert_ok!(crate::process_request::<Test>(payment));
+        assert_eq!(
+            last_system_tables_event(),
+            Some(crate::Event::MessageProcessingError {
+                error: crate::Error::<Test>::LateNonce.into(),
+                message_type: None,
+            })
+        );
+
+        let payment = get_zkpay_payment(ETH_TEST_WALLET, 500.into(), U256::from(3));
+        assert_ok!(crate::process_request::<Test>(payment));
+        assert_eq!(
+            last_system_tables_event(),
+            Some(crate::Event::MessageProcessingError {
+                error: crate::Error::<Test>::FutureNonce.into(),
+                message_type: None,
+            })
+        );
+
+        assert_eq!(pallet_balances::Pallet::<Test>::free_balance(&wallet), 500);
+    });
+}
+
+#[test]
+fn zkpay_templates_route_payment_inserts() {
+    use on_chain_table::OnChainColumn;
+    use proof_of_sql::base::math::decimal::Precision;
+    use sqlparser::ast::Ident;
+
+    new_test_ext().execute_with(|| {
+        set_zkpay_treasury(1_000);
+        let table = TableIdentifier::from_str_unchecked("PAYMENT", "SXT_SYSTEM_ZKPAY");
+        pallet_tables::Schemas::<Test>::insert(
+            &table.namespace,
+            &table.name,
+            sxt_core::tables::create_statement(
+                "CREATE TABLE SXT_SYSTEM_ZKPAY.PAYMENT(
+                    block_number bigint not null,
+                    event_index int not null,
+                    sender binary not null,
+                    amount decimal(75, 0) not null,
+                    nonce decimal(75, 0) not null,
+                    primary key(block_number, event_index)
+                )",
+            ),
+        );
+        assert_ok!(Pallet::<Test>::register_system_template(
+            RuntimeOrigin::root(),
+            table.clone(),
+            template(
+                SystemRequestType::ZkPay,
+                &[
+                    ("SENDER", crate::SystemFieldType::Bytes),
+                    ("AMOUNT", crate::SystemFieldType::Decimal),
+                    ("NONCE", crate::SystemFieldType::Decimal),
+                ],
+            ),
+        ));
+
+        let decimal = |value: u64| {
+            OnChainColumn::Decimal75(Precision::new(75).unwrap(), 0, vec![value.into()])
+        };
+        let insert = OnChainTable::try_from_iter([
+            (
+                Ident::new("SENDER"),
+                OnChainColumn::VarBinary(vec![hex::decode(ETH_TEST_WALLET).unwrap()]),
+            ),
+            (Ident::new("AMOUNT"), decimal(250)),
+            (Ident::new("NONCE"), decimal(1)),
+        ])
+        .unwrap();
+        assert_ok!(Pallet::<Test>::process_system_table(table, insert));
+
+        let wallet = eth_address_to_substrate_account_id::<Test>(ETH_TEST_WALLET).unwrap();
+        assert_eq!(pallet_balances::Pallet::<Test>::free_balance(&wallet), 250);
+    });
+}
+
+/// Add the fields identifying the EVM event of a single row staking request
+fn with_event_source(
+    mut request: SystemRequest,
+    block_number: i64,
+    transaction_hash: [u8; 32],
+    event_index: i32,
+) -> SystemRequest {
+    request.fields.extend([
+        SystemTableField::with_value(
+            "BLOCK_NUMBER".to_string(),
+            SystemFieldValue::BigInt(block_number),
+        ),
+        SystemTableField::with_value(
+            "TRANSACTION_HASH".to_string(),
+            SystemFieldValue::Bytes(transaction_hash.to_vec()),
+        ),
+        SystemTableField::with_value(
+            "EVENT_INDEX".to_string(),
+            SystemFieldValue::Int(event_index),
+        ),
+    ]);
+    request
+}
+
+fn staked_total(wallet: &AccountId32) -> u128 {
+    pallet_staking::Pallet::<Test>::ledger(wallet.clone().into())
+        .map(|ledger| ledger.total)
+        .unwrap_or_default()
+}
+
+#[test]
+fn staking_inserts_indexed_twice_are_applied_once() {
+    use on_chain_table::OnChainColumn;
+    use proof_of_sql::base::math::decimal::Precision;
+    use sqlparser::ast::Ident;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let insert = || {
+            OnChainTable::try_from_iter([
+                (Ident::new("BLOCK_NUMBER"), OnChainColumn::BigInt(vec![10])),
+                (
+                    Ident::new("TRANSACTION_HASH"),
+                    OnChainColumn::VarBinary(vec![vec![7; 32]]),
+                ),
+                (Ident::new("EVENT_INDEX"), OnChainColumn::Int(vec![3])),
+                (
+                    Ident::new("STAKER"),
+                    OnChainColumn::VarBinary(vec![hex::decode(ETH_TEST_WALLET).unwrap()]),
+                ),
+                (
+                    Ident::new("AMOUNT"),
+                    OnChainColumn::Decimal75(Precision::new(75).unwrap(), 0, vec![U256::from(100)]),
+                ),
+            ])
+            .unwrap()
+        };
+        let table = TableIdentifier::from_str_unchecked("STAKED", "SXT_SYSTEM_STAKING");
+        let wallet = eth_address_to_substrate_account_id::<Test>(ETH_TEST_WALLET).unwrap();
+
+        assert_ok!(Pallet::<Test>::process_system_table(
+            table.clone(),
+            insert()
+        ));
+        assert_eq!(staked_total(&wallet), 100);
+        assert_eq!(
+            crate::ProcessedStakingEvents::<Test>::get(&wallet, ([7; 32], 3)),
+            Some(10)
+        );
+
+        // The same event indexed again in another batch is skipped
+        System::set_block_number(2);
+        assert_ok!(Pallet::<Test>::process_system_table(
+            table.clone(),
+            insert()
+        ));
+        assert_eq!(staked_total(&wallet), 100);
+        assert_eq!(
+            last_system_tables_event(),
+            Some(crate::Event::SystemTableError {
+                table,
+                staker: wallet,
+                source: crate::StakingEventSource {
+                    block_number: 10,
+                    transaction_hash: [7; 32],
+                    event_index: 3,
+                },
+                issue: crate::StakingEventIssue::Duplicate,
+            })
+        );
+    });
+}
+
+#[test]
+fn events_of_the_same_transaction_are_applied_separately() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let wallet = eth_address_to_substrate_account_id::<Test>(ETH_TEST_WALLET).unwrap();
+
+        for event_index in [0, 1] {
+            let bonding = get_staked_message(ETH_TEST_WALLET, 100.into());
+            assert_ok!(crate::process_staking::<Test>(with_event_source(
+                bonding,
+                10,
+                [1; 32],
+                event_index
+            )));
+        }
+
+        assert_eq!(staked_total(&wallet), 200);
+        assert_eq!(
+            crate::LastStakingEvents::<Test>::get(&wallet).map(|last| last.event_index),
+            Some(1)
+        );
+    });
+}
+
+#[test]
+fn staking_events_out_of_order_are_applied_and_reported() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let wallet = eth_address_to_substrate_account_id::<Test>(ETH_TEST_WALLET).unwrap();
+        let later = crate::StakingEventSource {
+            block_number: 20,
+            transaction_hash: [2; 32],
+            event_index: 0,
+        };
+
+        let bonding = get_staked_message(ETH_TEST_WALLET, 100.into());
+        assert_ok!(crate::process_staking::<Test>(with_event_source(
+            bonding, 20, [2; 32], 0
+        )));
+
+        let bonding = get_staked_message(ETH_TEST_WALLET, 100.into());
+        assert_ok!(crate::process_staking::<Test>(with_event_source(
+            bonding, 10, [1; 32], 5
+        )));
+
+        assert_eq!(staked_total(&wallet), 200);
+        assert!(System::events().iter().any(|record| record.event
+            == RuntimeEvent::SystemTables(crate::Event::SystemTableError {
+                table: TableIdentifier::from_str_unchecked("STAKED", "SXT_SYSTEM_STAKING"),
+                staker: wallet.clone(),
+                source: crate::StakingEventSource {
+                    block_number: 10,
+                    transaction_hash: [1; 32],
+                    event_index: 5,
+                },
+                issue: crate::StakingEventIssue::OutOfOrder { last: later },
+            })));
+        // The latest event stays the last applied one
+        assert_eq!(crate::LastStakingEvents::<Test>::get(&wallet), Some(later));
+    });
+}
+
+#[test]
+fn staking_events_below_the_watermark_are_pruned_and_skipped() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let wallet = eth_address_to_substrate_account_id::<Test>(ETH_TEST_WALLET).unwrap();
+        let later = crate::StakingEventSource {
+            block_number: 10 + crate::STAKING_EVENT_RETENTION + 1,
+            transaction_hash: [2; 32],
+            event_index: 0,
+        };
+
+        let bonding = get_staked_message(ETH_TEST_WALLET, 100.into());
+        assert_ok!(crate::process_staking::<Test>(with_event_source(
+            bonding, 10, [1; 32], 0
+        )));
+        let bonding = get_staked_message(ETH_TEST_WALLET, 100.into());
+        assert_ok!(crate::process_staking::<Test>(with_event_source(
+            bonding,
+            later.block_number as i64,
+            [2; 32],
+            0
+        )));
+
+        // The first event is below the watermark of the last one, so it is forgotten
+        assert_eq!(
+            crate::ProcessedStakingEvents::<Test>::iter_prefix(&wallet).collect::<Vec<_>>(),
+            vec![(([2; 32], 0), later.block_number)]
+        );
+
+        // and skipped if it is indexed again
+        let bonding = get_staked_message(ETH_TEST_WALLET, 100.into());
+        assert_ok!(crate::process_staking::<Test>(with_event_source(
+            bonding, 10, [1; 32], 0
+        )));
+        assert_eq!(staked_total(&wallet), 200);
+        assert_eq!(
+            last_system_tables_event(),
+            Some(crate::Event::SystemTableError {
+                table: TableIdentifier::from_str_unchecked("STAKED", "SXT_SYSTEM_STAKING"),
+                staker: wallet,
+                source: crate::StakingEventSource {
+                    block_number: 10,
+                    transaction_hash: [1; 32],
+                    event_index: 0,
+                },
+                issue: crate::StakingEventIssue::Expired { last: later },
+            })
+        );
+    });
+}
+
+#[test]
+fn failed_staking_events_are_not_recorded() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let request = SystemRequest {
+            request_type: SystemRequestType::Staking(StakingSystemRequest::UnstakeInitiated),
+            table_id: TableIdentifier::from_str_unchecked("UNSTAKEINITIATED", "SXT_SYSTEM_STAKING"),
+            fields: vec![SystemTableField::with_value(
+                "STAKER".to_string(),
+                SystemFieldValue::Bytes(hex::decode(ETH_TEST_WALLET).unwrap()),
+            )],
+        };
+
+        // The staker isn't bonded, so unbonding fails
+        assert_ok!(crate::process_unstake_initiated::<Test>(with_event_source(
+            request, 10, [3; 32], 0
+        )));
+        assert!(matches!(
+            last_system_tables_event(),
+            Some(crate::Event::MessageProcessingError { .. })
+        ));
+        let wallet = eth_address_to_substrate_account_id::<Test>(ETH_TEST_WALLET).unwrap();
+        assert!(!crate::ProcessedStakingEvents::<Test>::contains_key(
+            &wallet,
+            ([3; 32], 0)
+        ));
+    });
+}
+
+#[test]
+fn staking_rows_with_a_partial_event_source_are_rejected() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let wallet = eth_address_to_substrate_account_id::<Test>(ETH_TEST_WALLET).unwrap();
+        let mut bonding = get_staked_message(ETH_TEST_WALLET, 100.into());
+        bonding.fields.push(SystemTableField::with_value(
+            "EVENT_INDEX".to_string(),
+            SystemFieldValue::Int(0),
+        ));
+
+        assert_ok!(crate::process_staking::<Test>(bonding));
+        assert_eq!(staked_total(&wallet), 0);
+        assert_eq!(
+            last_system_tables_event(),
+            Some(crate::Event::MessageProcessingError {
+                error: crate::Error::<Test>::InvalidStakingEventSource.into(),
+                message_type: None,
+            })
+        );
+    });
+}
+
+#[test]
+fn mirrored_stakers_report_the_staking_state_of_evm_stakers() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let address: [u8; 20] = hex::decode(ETH_TEST_WALLET).unwrap().try_into().unwrap();
+        let wallet = eth_address_to_substrate_account_id::<Test>(ETH_TEST_WALLET).unwrap();
+        assert_eq!(Pallet::<Test>::mirrored_staker(address), None);
+
+        let bonding = get_staked_message(ETH_TEST_WALLET, 100.into());
+        assert_ok!(crate::process_staking::<Test>(bonding));
+
+        let staker = Pallet::<Test>::mirrored_staker(address).unwrap();
+        assert_eq!(staker.address, address);
+        assert_eq!(staker.account, wallet);
+        assert_eq!(staker.bonded, 100);
+        assert_eq!(staker.active, 100);
+        assert!(staker.unbonding.is_empty());
+        assert!(staker.nominations.is_empty());
+        assert!(!staker.validating);
+
+        // Only stakers mirrored from ethereum addresses are listed
+        assert_eq!(
+            Pallet::<Test>::mirrored_stakers(None, 10),
+            vec![staker.clone()]
+        );
+        assert!(Pallet::<Test>::mirrored_stakers(Some(address), 10).is_empty());
+
+        let request = SystemRequest {
+            request_type: SystemRequestType::Staking(StakingSystemRequest::UnstakeInitiated),
+            table_id: TableIdentifier::from_str_unchecked("UNSTAKEINITIATED", "SXT_SYSTEM_STAKING"),
+            fields: vec![SystemTableField::with_value(
+                "STAKER".to_string(),
+                SystemFieldValue::Bytes(address.to_vec()),
+            )],
+        };
+        assert_ok!(crate::process_unstake_initiated::<Test>(request));
+
+        let staker = Pallet::<Test>::mirrored_staker(address).unwrap();
+        assert_eq!(staker.bonded, 100);
+        assert_eq!(staker.active, 0);
+        assert_eq!(
+            staker
+                .unbonding
+                .iter()
+                .map(|chunk| chunk.value)
+                .sum::<u128>(),
+            100
+        );
+    });
+}
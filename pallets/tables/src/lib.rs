@@ -16,6 +16,7 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+pub mod migrations;
 pub mod weights;
 pub use weights::*;
 
@@ -28,6 +29,7 @@ pub mod pallet {
     use commitment_sql::CreateTableAndCommitmentMetadata;
     use frame_support::dispatch::DispatchResult;
     use frame_support::pallet_prelude::{StorageDoubleMap, ValueQuery, *};
+    use frame_support::weights::WeightMeter;
     use frame_support::Blake2_128Concat;
     use frame_system::pallet_prelude::*;
     use proof_of_sql_commitment_map::{
@@ -35,6 +37,7 @@ pub mod pallet {
         TableCommitmentBytes,
         TableCommitmentBytesPerCommitmentScheme,
     };
+    use sp_runtime::traits::Hash;
     use sp_runtime::Vec;
     use sqlparser::ast::helpers::stmt_create_table::CreateTableBuilder;
     use sqlparser::ast::{Expr, ObjectName, SqlOption, Value};
@@ -49,6 +52,7 @@ pub mod pallet {
         generate_column_uuid_list,
         generate_namespace_uuid,
         generate_table_uuid,
+        primary_key_constraint_from_sqlparser,
         sqlparser_to_create_statement,
         update_uuid_in_create_table_statement,
         uuids_from_create_statement,
@@ -57,9 +61,11 @@ pub mod pallet {
         CommitmentBytes,
         CommitmentScheme,
         CreateStatement,
+        DuplicateKeyPolicy,
         IdentifierList,
         IndexerMode,
         InsertQuorumSize,
+        PrimaryKeyConstraint,
         SnapshotUrl,
         Source,
         SourceAndMode,
@@ -105,6 +111,19 @@ pub mod pallet {
     /// A list of tables that we want to create or update
     pub type UpdateTableList = BoundedVec<UpdateTable, ConstU32<1024>>;
 
+    /// The maximum length of an encoded primary key
+    pub const MAX_PRIMARY_KEY_LEN: u32 = 4096;
+
+    /// The maximum number of snapshot primary keys that can be indexed in one call
+    pub const MAX_SNAPSHOT_PRIMARY_KEYS: u32 = 1024;
+
+    /// The encoded primary key of a row, as produced by `OnChainTable::row_keys`
+    pub type PrimaryKeyBytes = BoundedVec<u8, ConstU32<MAX_PRIMARY_KEY_LEN>>;
+
+    /// Encoded primary keys of snapshot rows, paired with their row numbers
+    pub type SnapshotPrimaryKeys =
+        BoundedVec<(PrimaryKeyBytes, u64), ConstU32<MAX_SNAPSHOT_PRIMARY_KEYS>>;
+
     /// What type of commitment to create
     #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
     pub enum CommitmentCreationCmd {
@@ -114,7 +133,12 @@ pub mod pallet {
         Empty(CommitmentSchemeFlags),
     }
 
+    /// The storage version of the pallet, 1 since the primary key constraints of tables are
+    /// stored on chain
+    pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     #[pallet::config]
@@ -178,6 +202,22 @@ pub mod pallet {
 
         /// A table has been successfully dropped
         TableDropped(Option<T::AccountId>, TableType, TableIdentifier, Source),
+
+        /// The insert quorum for a table has been updated
+        TableInsertQuorumUpdated {
+            /// The table that was updated
+            table: TableIdentifier,
+            /// The new insert quorum of the table
+            quorum: InsertQuorumSize,
+        },
+
+        /// Primary keys of rows loaded from a table's snapshot have been indexed
+        SnapshotPrimaryKeysIndexed {
+            /// The table whose keys were indexed
+            table: TableIdentifier,
+            /// The number of keys that were indexed
+            count: u32,
+        },
     }
 
     /// A Map of Column UUIDs by Table Identifier and Version
@@ -247,6 +287,28 @@ pub mod pallet {
     pub type TableSources<T: Config> =
         StorageMap<_, Blake2_128Concat, TableIdentifier, Source, ValueQuery>;
 
+    /// The primary key declared in each table's DDL, along with its duplicate key policy.
+    #[pallet::storage]
+    #[pallet::getter(fn primary_key_constraints)]
+    pub type PrimaryKeyConstraints<T: Config> =
+        StorageMap<_, Blake2_128Concat, TableIdentifier, PrimaryKeyConstraint>;
+
+    /// The row number of the latest row stored under each primary key, by table and key hash.
+    ///
+    /// Only maintained for tables whose primary key constraint sets a [`DuplicateKeyPolicy`].
+    #[pallet::storage]
+    #[pallet::getter(fn primary_key_index)]
+    pub type PrimaryKeyIndex<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, TableIdentifier, Identity, T::Hash, u64>;
+
+    /// Tables whose key index is still being removed after they were dropped or cleared.
+    ///
+    /// The key index can be too large to remove in one block, so it is removed in `on_idle`.
+    #[pallet::storage]
+    #[pallet::getter(fn primary_key_index_removals)]
+    pub type PrimaryKeyIndexRemovals<T: Config> =
+        StorageMap<_, Blake2_128Concat, TableIdentifier, ()>;
+
     /// A table identifier, a sql statement for table creation, and an initial commitment
     pub type CreateTableCmd = (
         TableIdentifier,
@@ -333,6 +395,27 @@ pub mod pallet {
 
         /// There was an error generating a uuid
         UUIDGenerationError,
+
+        /// The primary key or ON_DUPLICATE_KEY option of the DDL statement is invalid
+        InvalidPrimaryKeyConstraint,
+
+        /// A row with the same primary key already exists in the table
+        DuplicatePrimaryKey,
+
+        /// Not all primary key constraints were removed
+        NotAllPrimaryKeyConstraintsRemovedError,
+
+        /// The key index of a dropped table with the same identifier is still being removed
+        PrimaryKeyIndexRemovalPending,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_idle(_: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            let mut meter = WeightMeter::with_limit(remaining_weight);
+            Self::remove_primary_key_indexes(&mut meter);
+            meter.consumed()
+        }
     }
 
     /// The implementation for the pallet extrinsics
@@ -367,6 +450,7 @@ pub mod pallet {
                         table.table_type.clone(),
                         source_and_mode.source.clone(),
                     );
+                    Self::insert_primary_key_constraint(&table.table_name, table.ddl.clone())?;
 
                     let statement_with_metadata = Self::insert_initial_commitment(
                         table.table_name.clone(),
@@ -432,6 +516,17 @@ pub mod pallet {
                 Error::<T>::NotAllCommitmentsRemovedError
             );
 
+            // Clear 1000, the key index of each table is removed in on_idle
+            PrimaryKeyConstraints::<T>::drain()
+                .take(1000)
+                .for_each(|(table, _)| PrimaryKeyIndexRemovals::<T>::insert(table, ()));
+
+            // Fail if not empty
+            ensure!(
+                PrimaryKeyConstraints::<T>::iter_keys().next().is_none(),
+                Error::<T>::NotAllPrimaryKeyConstraintsRemovedError
+            );
+
             Ok(())
         }
 
@@ -576,6 +671,62 @@ pub mod pallet {
 
             Ok(())
         }
+
+        /// Override the insert quorum of an existing table, which otherwise defaults to the
+        /// quorum of its table type
+        #[pallet::call_index(9)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_table_insert_quorum())]
+        pub fn set_table_insert_quorum(
+            origin: OriginFor<T>,
+            table: TableIdentifier,
+            quorum: InsertQuorumSize,
+        ) -> DispatchResult {
+            pallet_permissions::Pallet::<T>::ensure_root_or_permissioned(
+                origin,
+                &PermissionLevel::TablesPallet(TablesPalletPermission::EditSchema),
+            )?;
+
+            ensure!(
+                Schemas::<T>::contains_key(&table.namespace, &table.name),
+                Error::<T>::TableNotFound
+            );
+
+            TableInsertQuorums::<T>::insert(&table, quorum);
+
+            Self::deposit_event(Event::<T>::TableInsertQuorumUpdated { table, quorum });
+
+            Ok(())
+        }
+
+        /// Index the primary keys of rows that were not inserted through the indexing pallet.
+        ///
+        /// Inserted rows are indexed as they are committed to, but snapshot rows never pass
+        /// through the chain, and rows of tables created before keys were indexed were never
+        /// indexed. Their keys are supplied here along with their row numbers, so that later
+        /// inserts are checked against them. Large tables are indexed over several calls.
+        #[pallet::call_index(10)]
+        #[pallet::weight(<T as Config>::WeightInfo::index_snapshot_primary_keys(keys.len() as u32))]
+        pub fn index_snapshot_primary_keys(
+            origin: OriginFor<T>,
+            table: TableIdentifier,
+            keys: SnapshotPrimaryKeys,
+        ) -> DispatchResult {
+            pallet_permissions::Pallet::<T>::ensure_root_or_permissioned(
+                origin,
+                &PermissionLevel::TablesPallet(TablesPalletPermission::EditSchema),
+            )?;
+
+            let count = keys.len() as u32;
+            Self::index_primary_keys(
+                &table,
+                keys.into_iter()
+                    .map(|(key, row_number)| (key.into_inner(), row_number)),
+            )?;
+
+            Self::deposit_event(Event::<T>::SnapshotPrimaryKeysIndexed { table, count });
+
+            Ok(())
+        }
     }
 
     fn map_uuid_error<T: Config>(error: UpdateUuidError) -> DispatchError {
@@ -755,9 +906,97 @@ pub mod pallet {
                 TableInsertQuorums::<T>::remove(&ident);
             }
 
+            // Remove the primary key constraint, its key index is removed in on_idle.
+            if PrimaryKeyConstraints::<T>::take(&ident).is_some() {
+                PrimaryKeyIndexRemovals::<T>::insert(&ident, ());
+            }
+
             Ok(())
         }
 
+        /// Reads the primary key constraint from the DDL statement and stores it for the table.
+        pub fn insert_primary_key_constraint(
+            ident: &TableIdentifier,
+            statement: CreateStatement,
+        ) -> DispatchResult {
+            ensure!(
+                !PrimaryKeyIndexRemovals::<T>::contains_key(ident),
+                Error::<T>::PrimaryKeyIndexRemovalPending
+            );
+
+            let create_table = create_statement_to_sqlparser(statement)
+                .map_err(|_| Error::<T>::CreateStatementParseError)?;
+
+            match primary_key_constraint_from_sqlparser(&create_table)
+                .map_err(|_| Error::<T>::InvalidPrimaryKeyConstraint)?
+            {
+                Some(constraint) => PrimaryKeyConstraints::<T>::insert(ident, constraint),
+                None => PrimaryKeyConstraints::<T>::remove(ident),
+            }
+
+            Ok(())
+        }
+
+        /// Applies the table's [`DuplicateKeyPolicy`] to newly inserted rows, and indexes their keys.
+        ///
+        /// `rows` pairs the encoded primary key of each inserted row with its row number.
+        /// Tables without a policy are left untouched.
+        pub fn index_primary_keys(
+            table: &TableIdentifier,
+            rows: impl IntoIterator<Item = (Vec<u8>, u64)>,
+        ) -> DispatchResult {
+            let Some(DuplicateKeyPolicy::Reject) = PrimaryKeyConstraints::<T>::get(table)
+                .and_then(|constraint| constraint.on_duplicate)
+            else {
+                return Ok(());
+            };
+
+            rows.into_iter().try_for_each(|(key, row_number)| {
+                let key_hash = T::Hashing::hash(&key);
+                ensure!(
+                    !PrimaryKeyIndex::<T>::contains_key(table, key_hash),
+                    Error::<T>::DuplicatePrimaryKey
+                );
+                PrimaryKeyIndex::<T>::insert(table, key_hash, row_number);
+                Ok(())
+            })
+        }
+
+        /// Removes the key index of dropped tables within the weight left in the meter.
+        ///
+        /// Each table's index is cleared at most once per block, since `clear_prefix` still
+        /// sees the keys removed earlier in the same block. A table stays queued in
+        /// `PrimaryKeyIndexRemovals` until its whole index is removed.
+        fn remove_primary_key_indexes(meter: &mut WeightMeter) {
+            let db_weight = T::DbWeight::get();
+            let per_key = db_weight.writes(1);
+
+            // Reading the next queued table and dequeueing it once its index is removed
+            while meter.try_consume(db_weight.reads_writes(1, 1)).is_ok() {
+                let Some(table) = PrimaryKeyIndexRemovals::<T>::iter_keys().next() else {
+                    return;
+                };
+
+                let limit = meter
+                    .remaining()
+                    .checked_div_per_component(&per_key)
+                    // Removing keys is free if the database weight is zero
+                    .unwrap_or(u64::MAX)
+                    .min(u32::MAX.into()) as u32;
+                if limit == 0 {
+                    return;
+                }
+
+                let removal = PrimaryKeyIndex::<T>::clear_prefix(&table, limit, None);
+                meter.consume(per_key.saturating_mul(removal.loops.into()));
+
+                if removal.maybe_cursor.is_some() {
+                    return;
+                }
+                PrimaryKeyIndexRemovals::<T>::remove(&table);
+            }
+        }
+
         /// Create a table. Exactly the same as the extrinsic but available to other pallets
         pub fn create_tables_inner(
             origin: OriginFor<T>,
@@ -788,6 +1027,7 @@ pub mod pallet {
                         table.table_type.clone(),
                         table.source.clone(),
                     );
+                    Self::insert_primary_key_constraint(&table.ident, updated_create_statement.clone())?;
 
                     // Parse and remove WITH clause
                     let (create_table, with_options) = create_statement_to_sqlparser_remove_with(
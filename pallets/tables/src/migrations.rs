@@ -0,0 +1,38 @@
+//! Storage migrations of the tables pallet
+
+/// Migration to storage version 1, declaring the primary key constraints of tables created before
+/// they were stored on chain
+pub mod v1 {
+    use frame_support::pallet_prelude::*;
+    use frame_support::traits::OnRuntimeUpgrade;
+    use sxt_core::tables::TableIdentifier;
+
+    use crate::{Config, Pallet, Schemas};
+
+    /// Reads the primary key constraint of every existing table from its DDL statement.
+    ///
+    /// Tables whose DDL does not declare a valid constraint are left without one. The keys of
+    /// rows inserted before the migration are not indexed, they are supplied with
+    /// `index_snapshot_primary_keys`.
+    pub struct DeclarePrimaryKeyConstraints<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for DeclarePrimaryKeyConstraints<T> {
+        fn on_runtime_upgrade() -> Weight {
+            if Pallet::<T>::on_chain_storage_version() >= 1 {
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut count = 0;
+            for (namespace, name, statement) in Schemas::<T>::iter() {
+                let table = TableIdentifier { namespace, name };
+                // Invalid constraints were never enforced, so the table keeps accepting inserts
+                let _ = Pallet::<T>::insert_primary_key_constraint(&table, statement);
+                count += 1;
+            }
+            StorageVersion::new(1).put::<Pallet<T>>();
+
+            // Reading each schema and pending key index removal, and writing each constraint
+            T::DbWeight::get().reads_writes(2 * count + 1, count + 1)
+        }
+    }
+}
@@ -1,6 +1,13 @@
 use core::str::from_utf8;
 
-use frame_support::traits::OriginTrait;
+use frame_support::traits::{
+    GetStorageVersion,
+    Hooks,
+    OnRuntimeUpgrade,
+    OriginTrait,
+    StorageVersion,
+};
+use frame_support::weights::Weight;
 use frame_support::{assert_err, assert_ok};
 use pallet_permissions::Pallet;
 use proof_of_sql_commitment_map::CommitmentSchemeFlags;
@@ -9,6 +16,9 @@ use sp_runtime::BoundedVec;
 use sxt_core::permissions::{PermissionLevel, PermissionList, TablesPalletPermission};
 use sxt_core::tables::{
     CreateStatement,
+    DuplicateKeyPolicy,
+    InsertQuorumSize,
+    PrimaryKeys,
     Source,
     SourceAndMode,
     TableIdentifier,
@@ -18,12 +28,20 @@ use sxt_core::tables::{
     TableUuid,
 };
 
+use crate::migrations::v1::DeclarePrimaryKeyConstraints;
 use crate::mock::*;
 use crate::{
     CommitmentCreationCmd,
     CreateTableList,
+    Error,
     Event,
     NamespaceVersions,
+    PrimaryKeyConstraints,
+    PrimaryKeyIndex,
+    PrimaryKeyIndexRemovals,
+    SnapshotPrimaryKeys,
+    Snapshots,
+    TableInsertQuorums,
     TableVersions,
     UpdateTable,
     UpdateTableList,
@@ -435,3 +453,307 @@ fn test_get_or_generate_uuids_for_table_generates_uuids_if_missing() {
         println!("✅ Column UUIDs: {:?}", column_uuids);
     });
 }
+
+fn primary_key_test_table(ddl: &str) -> (TableIdentifier, UpdateTableList) {
+    let ident = TableIdentifier {
+        name: TableName::try_from(b"BOOK".to_vec()).unwrap(),
+        namespace: TableNamespace::try_from(b"SOUTH".to_vec()).unwrap(),
+    };
+
+    let tables = UpdateTableList::try_from(vec![UpdateTable {
+        ident: ident.clone(),
+        create_statement: CreateStatement::try_from(ddl.as_bytes().to_vec()).unwrap(),
+        table_type: TableType::CoreBlockchain,
+        commitment: CommitmentCreationCmd::Empty(CommitmentSchemeFlags::default()),
+        source: Source::Ethereum,
+    }])
+    .unwrap();
+
+    (ident, tables)
+}
+
+#[test]
+fn create_table_should_store_primary_key_constraint_until_dropped() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let (ident, tables) = primary_key_test_table(
+            "CREATE TABLE SOUTH.BOOK (ID INT NOT NULL, NAME VARCHAR NOT NULL, PRIMARY KEY (ID, NAME)) WITH (on_duplicate_key=reject)",
+        );
+        assert_ok!(Tables::create_tables(RuntimeOrigin::root(), tables));
+
+        let constraint = PrimaryKeyConstraints::<Test>::get(&ident).unwrap();
+        assert_eq!(
+            constraint.columns,
+            PrimaryKeys::try_from(vec![
+                b"ID".to_vec().try_into().unwrap(),
+                b"NAME".to_vec().try_into().unwrap(),
+            ])
+            .unwrap()
+        );
+        assert_eq!(constraint.on_duplicate, Some(DuplicateKeyPolicy::Reject));
+
+        assert_ok!(Tables::drop_table(
+            RuntimeOrigin::root(),
+            TableType::CoreBlockchain,
+            ident.clone(),
+            Source::Ethereum,
+        ));
+        assert_eq!(PrimaryKeyConstraints::<Test>::get(&ident), None);
+    })
+}
+
+#[test]
+fn create_table_should_reject_upsert_tables() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let (ident, tables) = primary_key_test_table(
+            "CREATE TABLE SOUTH.BOOK (ID INT NOT NULL, PRIMARY KEY (ID)) WITH (on_duplicate_key=upsert)",
+        );
+        assert_err!(
+            Tables::create_tables(RuntimeOrigin::root(), tables),
+            Error::<Test>::InvalidPrimaryKeyConstraint
+        );
+        assert_eq!(PrimaryKeyConstraints::<Test>::get(&ident), None);
+    })
+}
+
+#[test]
+fn duplicate_primary_keys_should_be_rejected() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let (ident, tables) = primary_key_test_table(
+            "CREATE TABLE SOUTH.BOOK (ID INT NOT NULL, PRIMARY KEY (ID)) WITH (on_duplicate_key=reject)",
+        );
+        assert_ok!(Tables::create_tables(RuntimeOrigin::root(), tables));
+        assert_ok!(Tables::index_primary_keys(&ident, [(b"1".to_vec(), 0)]));
+
+        assert_err!(
+            Tables::index_primary_keys(&ident, [(b"2".to_vec(), 1), (b"1".to_vec(), 2)]),
+            Error::<Test>::DuplicatePrimaryKey
+        );
+    })
+}
+
+#[test]
+fn dropped_table_key_index_should_be_removed_in_on_idle() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let ddl = "CREATE TABLE SOUTH.BOOK (ID INT NOT NULL, PRIMARY KEY (ID)) WITH (on_duplicate_key=reject)";
+        let (ident, tables) = primary_key_test_table(ddl);
+        assert_ok!(Tables::create_tables(RuntimeOrigin::root(), tables));
+        assert_ok!(Tables::index_primary_keys(
+            &ident,
+            [(b"1".to_vec(), 0), (b"2".to_vec(), 1)]
+        ));
+        assert_eq!(PrimaryKeyIndex::<Test>::iter_prefix(&ident).count(), 2);
+
+        assert_ok!(Tables::drop_table(
+            RuntimeOrigin::root(),
+            TableType::CoreBlockchain,
+            ident.clone(),
+            Source::Ethereum,
+        ));
+        assert!(PrimaryKeyIndexRemovals::<Test>::contains_key(&ident));
+
+        // the table cannot be recreated while its old key index is still being removed
+        let (_, tables) = primary_key_test_table(ddl);
+        assert_err!(
+            Tables::create_tables(RuntimeOrigin::root(), tables),
+            Error::<Test>::PrimaryKeyIndexRemovalPending
+        );
+
+        Tables::on_idle(1, Weight::MAX);
+        assert_eq!(PrimaryKeyIndex::<Test>::iter_prefix(&ident).count(), 0);
+        assert!(!PrimaryKeyIndexRemovals::<Test>::contains_key(&ident));
+
+        let (_, tables) = primary_key_test_table(ddl);
+        assert_ok!(Tables::create_tables(RuntimeOrigin::root(), tables));
+    })
+}
+
+#[test]
+fn clear_tables_should_queue_key_index_removal() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let (ident, tables) = primary_key_test_table(
+            "CREATE TABLE SOUTH.BOOK (ID INT NOT NULL, PRIMARY KEY (ID)) WITH (on_duplicate_key=reject)",
+        );
+        assert_ok!(Tables::create_tables(RuntimeOrigin::root(), tables));
+        assert_ok!(Tables::index_primary_keys(&ident, [(b"1".to_vec(), 0)]));
+
+        assert_ok!(Tables::clear_tables(RuntimeOrigin::root()));
+        assert_eq!(PrimaryKeyConstraints::<Test>::get(&ident), None);
+        assert!(PrimaryKeyIndexRemovals::<Test>::contains_key(&ident));
+
+        Tables::on_idle(1, Weight::MAX);
+        assert_eq!(PrimaryKeyIndex::<Test>::iter_prefix(&ident).count(), 0);
+    })
+}
+
+#[test]
+fn snapshot_primary_keys_should_be_indexed() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let (ident, tables) = primary_key_test_table(
+            "CREATE TABLE SOUTH.BOOK (ID INT NOT NULL, PRIMARY KEY (ID)) WITH (on_duplicate_key=reject)",
+        );
+        assert_ok!(Tables::create_tables(RuntimeOrigin::root(), tables));
+
+        let keys = |keys: &[(&[u8], u64)]| {
+            SnapshotPrimaryKeys::try_from(
+                keys.iter()
+                    .map(|(key, row_number)| (key.to_vec().try_into().unwrap(), *row_number))
+                    .collect::<Vec<_>>(),
+            )
+            .unwrap()
+        };
+
+        Snapshots::<Test>::insert(&ident, BoundedVec::try_from(b"s3://snapshot".to_vec()).unwrap());
+        assert_err!(
+            Tables::index_snapshot_primary_keys(
+                user(1).1,
+                ident.clone(),
+                keys(&[(b"1", 0)])
+            ),
+            pallet_permissions::Error::<Test>::InsufficientPermissions
+        );
+        assert_ok!(Tables::index_snapshot_primary_keys(
+            RuntimeOrigin::root(),
+            ident.clone(),
+            keys(&[(b"1", 0), (b"2", 1)])
+        ));
+        assert_eq!(PrimaryKeyIndex::<Test>::iter_prefix(&ident).count(), 2);
+        System::assert_last_event(
+            Event::SnapshotPrimaryKeysIndexed {
+                table: ident.clone(),
+                count: 2,
+            }
+            .into(),
+        );
+
+        // inserted rows are checked against the snapshot keys
+        assert_err!(
+            Tables::index_primary_keys(&ident, [(b"2".to_vec(), 2)]),
+            Error::<Test>::DuplicatePrimaryKey
+        );
+    })
+}
+
+#[test]
+fn migration_should_declare_primary_key_constraints_of_existing_tables() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let (ident, tables) = primary_key_test_table(
+            "CREATE TABLE SOUTH.BOOK (ID INT NOT NULL, PRIMARY KEY (ID)) WITH (on_duplicate_key=reject)",
+        );
+        assert_ok!(Tables::create_tables(RuntimeOrigin::root(), tables));
+        let constraint = PrimaryKeyConstraints::<Test>::get(&ident).unwrap();
+
+        // as created before constraints were stored on chain
+        PrimaryKeyConstraints::<Test>::remove(&ident);
+        StorageVersion::new(0).put::<Tables>();
+
+        DeclarePrimaryKeyConstraints::<Test>::on_runtime_upgrade();
+        assert_eq!(PrimaryKeyConstraints::<Test>::get(&ident), Some(constraint));
+        assert_eq!(Tables::on_chain_storage_version(), 1);
+
+        // the keys of rows inserted before the migration can be indexed without a snapshot
+        let keys = SnapshotPrimaryKeys::try_from(vec![(b"1".to_vec().try_into().unwrap(), 0)])
+            .unwrap();
+        assert_ok!(Tables::index_snapshot_primary_keys(
+            RuntimeOrigin::root(),
+            ident.clone(),
+            keys
+        ));
+        assert_err!(
+            Tables::index_primary_keys(&ident, [(b"1".to_vec(), 1)]),
+            Error::<Test>::DuplicatePrimaryKey
+        );
+    })
+}
+
+#[test]
+fn create_table_should_fail_with_invalid_duplicate_key_policy() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let (_, tables) = primary_key_test_table(
+            "CREATE TABLE SOUTH.BOOK (ID INT NOT NULL, PRIMARY KEY (ID)) WITH (on_duplicate_key=ignore)",
+        );
+        assert_err!(
+            Tables::create_tables(RuntimeOrigin::root(), tables),
+            Error::<Test>::InvalidPrimaryKeyConstraint
+        );
+
+        let (_, tables) = primary_key_test_table(
+            "CREATE TABLE SOUTH.BOOK (ID INT NOT NULL) WITH (on_duplicate_key=reject)",
+        );
+        assert_err!(
+            Tables::create_tables(RuntimeOrigin::root(), tables),
+            Error::<Test>::InvalidPrimaryKeyConstraint
+        );
+    })
+}
+
+#[test]
+fn set_table_insert_quorum_should_override_table_type_default() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let (ident, tables) =
+            primary_key_test_table("CREATE TABLE SOUTH.BOOK (ID INT NOT NULL, PRIMARY KEY (ID))");
+        assert_ok!(Tables::create_tables(RuntimeOrigin::root(), tables));
+
+        let quorum = InsertQuorumSize {
+            public: None,
+            privileged: Some(2),
+        };
+
+        let (who, signer) = user(1);
+        assert_err!(
+            Tables::set_table_insert_quorum(signer.clone(), ident.clone(), quorum),
+            pallet_permissions::Error::<Test>::InsufficientPermissions
+        );
+
+        set_permission!(who, TablesPalletPermission::EditSchema);
+        assert_ok!(Tables::set_table_insert_quorum(
+            signer,
+            ident.clone(),
+            quorum
+        ));
+        assert_eq!(TableInsertQuorums::<Test>::get(&ident), quorum);
+
+        System::assert_last_event(
+            Event::TableInsertQuorumUpdated {
+                table: ident,
+                quorum,
+            }
+            .into(),
+        );
+    })
+}
+
+#[test]
+fn set_table_insert_quorum_should_fail_for_missing_table() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let (ident, _) = primary_key_test_table("CREATE TABLE SOUTH.BOOK (ID INT NOT NULL)");
+        assert_err!(
+            Tables::set_table_insert_quorum(
+                RuntimeOrigin::root(),
+                ident.clone(),
+                InsertQuorumSize::default()
+            ),
+            Error::<Test>::TableNotFound
+        );
+        assert!(!TableInsertQuorums::<Test>::contains_key(&ident));
+    })
+}
@@ -58,6 +58,12 @@ pub trait WeightInfo {
 	fn update_namespace_uuid() -> Weight;
 	/// Weight for updated a table UUID
 	fn update_table_uuid() -> Weight;
+	/// Weight for setting the insert quorum of a table
+	fn set_table_insert_quorum() -> Weight;
+	/// Weight for indexing the primary key of one inserted row
+	fn index_primary_key() -> Weight;
+	/// Weight for indexing `k` primary keys of snapshot rows
+	fn index_snapshot_primary_keys(k: u32) -> Weight;
 }
 
 /// TODO: add docs
@@ -99,6 +105,19 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	fn update_table_uuid() -> Weight {
 		Weight::from_parts(0,0)
 	}
+
+	fn set_table_insert_quorum() -> Weight {
+		Weight::from_parts(0,0)
+	}
+
+	fn index_primary_key() -> Weight {
+		T::DbWeight::get().reads_writes(1, 1)
+	}
+
+	fn index_snapshot_primary_keys(k: u32) -> Weight {
+		T::DbWeight::get().reads(3)
+			.saturating_add(Self::index_primary_key().saturating_mul(k.into()))
+	}
 }
 
 // For backwards compatibility and tests
@@ -136,4 +155,14 @@ impl WeightInfo for () {
 	fn update_table_uuid() -> Weight {
 		Weight::from_parts(0,0)
 	}
+	fn set_table_insert_quorum() -> Weight {
+		Weight::from_parts(0,0)
+	}
+	fn index_primary_key() -> Weight {
+		RocksDbWeight::get().reads_writes(1, 1)
+	}
+	fn index_snapshot_primary_keys(k: u32) -> Weight {
+		RocksDbWeight::get().reads(3)
+			.saturating_add(Self::index_primary_key().saturating_mul(k.into()))
+	}
 }
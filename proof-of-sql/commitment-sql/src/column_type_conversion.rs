@@ -2,7 +2,7 @@ use proof_of_sql::base::database::ColumnType;
 use proof_of_sql::base::math::decimal::Precision;
 use proof_of_sql::base::posql_time::{PoSQLTimeUnit, PoSQLTimeZone};
 use snafu::Snafu;
-use sqlparser::ast::{DataType, ExactNumberInfo, TimezoneInfo};
+use sqlparser::ast::{DataType, ExactNumberInfo};
 
 /// Error that occurs when encountering unsupported sqlparser `DataType`s.
 #[derive(Debug, Snafu)]
@@ -36,6 +36,9 @@ pub enum UnsupportedColumnType {
     },
 }
 
+/// Number of decimal digits needed to represent any `u64`.
+const UNSIGNED_BIGINT_PRECISION: u8 = 20;
+
 /// Convert sqlparser decimal number info to proof-of-sql precision and scale.
 fn sqlparser_number_info_to_proof_of_sql_precision_and_scale(
     number_info: &ExactNumberInfo,
@@ -59,7 +62,32 @@ fn sqlparser_number_info_to_proof_of_sql_precision_and_scale(
     Ok((precision, scale))
 }
 
+/// Convert sqlparser timestamp precision to proof-of-sql time unit.
+///
+/// Only the precisions of the proof-of-sql time units are supported. Without a precision,
+/// timestamps default to milliseconds.
+fn sqlparser_timestamp_precision_to_proof_of_sql_time_unit(
+    precision: &Option<u64>,
+) -> Option<PoSQLTimeUnit> {
+    match precision {
+        None | Some(3) => Some(PoSQLTimeUnit::Millisecond),
+        Some(0) => Some(PoSQLTimeUnit::Second),
+        Some(6) => Some(PoSQLTimeUnit::Microsecond),
+        Some(9) => Some(PoSQLTimeUnit::Nanosecond),
+        Some(_) => None,
+    }
+}
+
 /// Convert sqlparser data type to proof-of-sql column type.
+///
+/// Some types have no proof-of-sql equivalent and are encoded as follows..
+/// - `DATE` is a second-precision UTC timestamp at midnight
+/// - `UUID` is a `VARBINARY` of the 16 bytes of the UUID in big-endian (RFC 4122) order
+/// - `UNSIGNED SMALLINT`/`UNSIGNED MEDIUMINT` are `INT`s
+/// - `UNSIGNED INT` is a `BIGINT`
+/// - `UNSIGNED BIGINT` is a `DECIMAL(20, 0)`
+///
+/// All timestamps are stored as UTC, whether or not they are declared `WITH TIME ZONE`.
 pub fn sqlparser_data_type_to_proof_of_sql_column_type(
     sqlparser_type: &DataType,
 ) -> Result<ColumnType, UnsupportedColumnType> {
@@ -75,11 +103,30 @@ pub fn sqlparser_data_type_to_proof_of_sql_column_type(
                 sqlparser_number_info_to_proof_of_sql_precision_and_scale(number_info)?;
             Ok(ColumnType::Decimal75(precision, scale))
         }
-        DataType::Timestamp(None, TimezoneInfo::None) => Ok(ColumnType::TimestampTZ(
-            PoSQLTimeUnit::Millisecond,
+        DataType::Timestamp(precision, _) => {
+            sqlparser_timestamp_precision_to_proof_of_sql_time_unit(precision)
+                .map(|time_unit| ColumnType::TimestampTZ(time_unit, PoSQLTimeZone::utc()))
+                .ok_or_else(|| UnsupportedColumnType::DataTypeParameter {
+                    data_type: sqlparser_type.clone(),
+                })
+        }
+        DataType::Date => Ok(ColumnType::TimestampTZ(
+            PoSQLTimeUnit::Second,
             PoSQLTimeZone::utc(),
         )),
         DataType::Binary(None) => Ok(ColumnType::VarBinary),
+        DataType::Uuid => Ok(ColumnType::VarBinary),
+        DataType::UnsignedTinyInt(None) => Ok(ColumnType::Uint8),
+        DataType::UnsignedSmallInt(None)
+        | DataType::UnsignedInt2(None)
+        | DataType::UnsignedMediumInt(None) => Ok(ColumnType::Int),
+        DataType::UnsignedInt(None)
+        | DataType::UnsignedInt4(None)
+        | DataType::UnsignedInteger(None) => Ok(ColumnType::BigInt),
+        DataType::UnsignedBigInt(None) | DataType::UnsignedInt8(None) => Ok(ColumnType::Decimal75(
+            Precision::new(UNSIGNED_BIGINT_PRECISION).expect("precision is less than 76"),
+            0,
+        )),
         DataType::TinyInt(_)
         | DataType::SmallInt(_)
         | DataType::Int(_)
@@ -87,26 +134,24 @@ pub fn sqlparser_data_type_to_proof_of_sql_column_type(
         | DataType::BigInt(_)
         | DataType::Varchar(_)
         | DataType::Binary(_)
-        | DataType::Timestamp(..) => Err(UnsupportedColumnType::DataTypeParameter {
+        | DataType::UnsignedTinyInt(_)
+        | DataType::UnsignedSmallInt(_)
+        | DataType::UnsignedInt2(_)
+        | DataType::UnsignedMediumInt(_)
+        | DataType::UnsignedInt(_)
+        | DataType::UnsignedInt4(_)
+        | DataType::UnsignedInteger(_)
+        | DataType::UnsignedBigInt(_)
+        | DataType::UnsignedInt8(_) => Err(UnsupportedColumnType::DataTypeParameter {
             data_type: sqlparser_type.clone(),
         }),
         DataType::Bool
-        | DataType::Uuid
         | DataType::Float(_)
-        | DataType::UnsignedTinyInt(_)
         | DataType::MediumInt(_)
         | DataType::Int2(_)
-        | DataType::UnsignedInt2(_)
-        | DataType::UnsignedSmallInt(_)
-        | DataType::UnsignedMediumInt(_)
-        | DataType::UnsignedInt(_)
         | DataType::Int4(_)
-        | DataType::UnsignedInt4(_)
-        | DataType::UnsignedInteger(_)
         | DataType::Int8(_)
         | DataType::Int64
-        | DataType::UnsignedBigInt(_)
-        | DataType::UnsignedInt8(_)
         | DataType::Float4
         | DataType::Float64
         | DataType::Real
@@ -127,7 +172,6 @@ pub fn sqlparser_data_type_to_proof_of_sql_column_type(
         | DataType::BigNumeric(_)
         | DataType::BigDecimal(_)
         | DataType::Dec(_)
-        | DataType::Date
         | DataType::Time(..)
         | DataType::Datetime(_)
         | DataType::Varbinary(_)
@@ -228,7 +272,10 @@ mod tests {
             DataType::Varchar(Some(CharacterLength::Max)),
             DataType::Binary(Some(6)),
             DataType::Timestamp(Some(7), TimezoneInfo::None),
-            DataType::Timestamp(None, TimezoneInfo::Tz),
+            DataType::Timestamp(Some(1), TimezoneInfo::Tz),
+            DataType::UnsignedTinyInt(Some(8)),
+            DataType::UnsignedInt(Some(9)),
+            DataType::UnsignedBigInt(Some(10)),
         ]
         .iter()
         .for_each(|data_type_with_unsupported_parameter| {
@@ -241,6 +288,91 @@ mod tests {
         })
     }
 
+    #[test]
+    fn we_can_convert_sqlparser_timestamps_with_precision_to_proof_of_sql() {
+        [
+            (Some(0), PoSQLTimeUnit::Second),
+            (Some(3), PoSQLTimeUnit::Millisecond),
+            (Some(6), PoSQLTimeUnit::Microsecond),
+            (Some(9), PoSQLTimeUnit::Nanosecond),
+            (None, PoSQLTimeUnit::Millisecond),
+        ]
+        .into_iter()
+        .cartesian_product([
+            TimezoneInfo::None,
+            TimezoneInfo::WithTimeZone,
+            TimezoneInfo::WithoutTimeZone,
+            TimezoneInfo::Tz,
+        ])
+        .for_each(|((precision, time_unit), timezone_info)| {
+            assert_eq!(
+                sqlparser_data_type_to_proof_of_sql_column_type(&DataType::Timestamp(
+                    precision,
+                    timezone_info
+                ))
+                .unwrap(),
+                ColumnType::TimestampTZ(time_unit, PoSQLTimeZone::utc())
+            );
+        });
+    }
+
+    #[test]
+    fn we_can_convert_encoded_sqlparser_types_to_proof_of_sql() {
+        assert_eq!(
+            sqlparser_data_type_to_proof_of_sql_column_type(&DataType::Date).unwrap(),
+            ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc())
+        );
+
+        assert_eq!(
+            sqlparser_data_type_to_proof_of_sql_column_type(&DataType::Uuid).unwrap(),
+            ColumnType::VarBinary
+        );
+
+        assert_eq!(
+            sqlparser_data_type_to_proof_of_sql_column_type(&DataType::UnsignedTinyInt(None))
+                .unwrap(),
+            ColumnType::Uint8
+        );
+
+        assert_eq!(
+            [
+                DataType::UnsignedSmallInt(None),
+                DataType::UnsignedInt2(None),
+                DataType::UnsignedMediumInt(None),
+            ]
+            .iter()
+            .map(sqlparser_data_type_to_proof_of_sql_column_type)
+            .map(Result::unwrap)
+            .all_equal_value()
+            .unwrap(),
+            ColumnType::Int
+        );
+
+        assert_eq!(
+            [
+                DataType::UnsignedInt(None),
+                DataType::UnsignedInt4(None),
+                DataType::UnsignedInteger(None),
+            ]
+            .iter()
+            .map(sqlparser_data_type_to_proof_of_sql_column_type)
+            .map(Result::unwrap)
+            .all_equal_value()
+            .unwrap(),
+            ColumnType::BigInt
+        );
+
+        assert_eq!(
+            [DataType::UnsignedBigInt(None), DataType::UnsignedInt8(None)]
+                .iter()
+                .map(sqlparser_data_type_to_proof_of_sql_column_type)
+                .map(Result::unwrap)
+                .all_equal_value()
+                .unwrap(),
+            ColumnType::Decimal75(Precision::new(20).unwrap(), 0)
+        );
+    }
+
     #[test]
     fn we_can_convert_sqlparser_decimals_to_proof_of_sql() {
         let full_decimal = DataType::Decimal(ExactNumberInfo::PrecisionAndScale(75, 10));
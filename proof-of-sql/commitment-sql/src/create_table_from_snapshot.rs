@@ -14,7 +14,7 @@ use crate::insert::OptionZipFn;
 use crate::{process_create_table, CreateTableAndCommitmentMetadata, InvalidCreateTable};
 
 /// Generically accepts a pair of `TableCommitment`s and tries to add them.
-struct TryAddTableCommitmentsFn;
+pub(crate) struct TryAddTableCommitmentsFn;
 
 impl GenericOverCommitmentFn for TryAddTableCommitmentsFn {
     type In = PairType<TableCommitmentType, TableCommitmentType>;
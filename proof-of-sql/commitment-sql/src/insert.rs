@@ -1,9 +1,10 @@
+use alloc::collections::BTreeSet;
 use alloc::vec;
 use alloc::vec::Vec;
 use core::marker::PhantomData;
 
 use itertools::Itertools;
-use on_chain_table::{OnChainTable, OutOfScalarBounds};
+use on_chain_table::{MissingKeyColumn, OnChainTable, OutOfScalarBounds};
 use proof_of_sql::base::commitment::{
     AppendColumnCommitmentsError,
     AppendTableCommitmentError,
@@ -205,6 +206,18 @@ pub enum ProcessInsertError {
     /// No commitments to update.
     #[snafu(display("no commitments to update"))]
     NoCommitments,
+    /// Insert data is missing a primary key column.
+    #[snafu(
+        display("insert data is missing primary key column: {source}"),
+        context(false)
+    )]
+    MissingPrimaryKeyColumn {
+        /// Source missing key column error.
+        source: MissingKeyColumn,
+    },
+    /// Insert data contains multiple rows with the same primary key.
+    #[snafu(display("insert data contains multiple rows with the same primary key"))]
+    DuplicatePrimaryKey,
 }
 
 impl From<ProcessInsertError> for NativeCommitmentError {
@@ -218,6 +231,10 @@ impl From<ProcessInsertError> for NativeCommitmentError {
                 NativeCommitmentError::TableCommitmentColumnOrderMismatch
             }
             ProcessInsertError::NoCommitments => NativeCommitmentError::NoCommitments,
+            ProcessInsertError::MissingPrimaryKeyColumn { .. } => {
+                NativeCommitmentError::MissingPrimaryKeyColumn
+            }
+            ProcessInsertError::DuplicatePrimaryKey => NativeCommitmentError::DuplicatePrimaryKey,
         }
     }
 }
@@ -231,14 +248,41 @@ pub struct InsertAndCommitmentMetadata {
     pub meta_table_inserts: Vec<(TableIdentifier, OnChainTable)>,
 }
 
+/// Returns `Ok(())` if no two rows of the insert share a primary key.
+///
+/// An empty primary key places no constraint on the insert.
+fn validate_primary_key_uniqueness(
+    insert_data: &OnChainTable,
+    primary_key: &[Ident],
+) -> Result<(), ProcessInsertError> {
+    if primary_key.is_empty() {
+        return Ok(());
+    }
+
+    let mut seen_keys = BTreeSet::new();
+    if insert_data
+        .row_keys(primary_key.iter().map(|column| column.value.as_str()))?
+        .into_iter()
+        .all(|key| seen_keys.insert(key))
+    {
+        Ok(())
+    } else {
+        Err(ProcessInsertError::DuplicatePrimaryKey)
+    }
+}
+
 /// Process insert to support commitment metadata.
 ///
+/// Rows are rejected if they share a value for every column of the `primary_key`.
+/// Uniqueness against rows of previous inserts is not checked here.
+///
 /// Returns..
 /// - the processed insert as [`InsertAndCommitmentMetadata`]
 /// - the updated commitments for the table
 pub fn process_insert(
     _table_identifier: &TableIdentifier,
     insert_data: OnChainTable,
+    primary_key: &[Ident],
     previous_commitments: PerCommitmentScheme<OptionType<TableCommitmentType>>,
     setups: PerCommitmentScheme<AssociatedPublicSetupType>,
 ) -> Result<
@@ -248,6 +292,8 @@ pub fn process_insert(
     ),
     ProcessInsertError,
 > {
+    validate_primary_key_uniqueness(&insert_data, primary_key)?;
+
     // get the row count and make sure it matches across commitment schemes
     let (previous_commitments, row_counts): (Vec<_>, Vec<_>) = previous_commitments
         .into_flat_iter()
@@ -416,7 +462,7 @@ mod tests {
             .collect::<PerCommitmentScheme<OptionType<TableCommitmentType>>>();
 
         assert_eq!(
-            process_insert(&table_id, first_insert, empty_commitments, *setups).unwrap(),
+            process_insert(&table_id, first_insert, &[], empty_commitments, *setups).unwrap(),
             (
                 InsertAndCommitmentMetadata {
                     insert_with_meta_columns: expected_first_insert_with_meta_columns,
@@ -479,6 +525,7 @@ mod tests {
             process_insert(
                 &table_id,
                 second_insert_with_different_column_order,
+                &[],
                 expected_first_commitments,
                 *setups
             )
@@ -526,7 +573,7 @@ mod tests {
         };
 
         assert!(matches!(
-            process_insert(&table_id, insert_data, previous_commitments, *setups),
+            process_insert(&table_id, insert_data, &[], previous_commitments, *setups),
             Err(ProcessInsertError::TableCommitmentRangeMismatch)
         ));
     }
@@ -576,6 +623,7 @@ mod tests {
             process_insert(
                 &table_id,
                 insert_missing_column,
+                &[],
                 previous_commitments,
                 *setups
             ),
@@ -615,7 +663,13 @@ mod tests {
         let none_previous_commitments = PerCommitmentScheme::default();
 
         assert!(matches!(
-            process_insert(&table_id, insert_data, none_previous_commitments, *setups),
+            process_insert(
+                &table_id,
+                insert_data,
+                &[],
+                none_previous_commitments,
+                *setups
+            ),
             Err(ProcessInsertError::NoCommitments)
         ));
     }
@@ -653,10 +707,88 @@ mod tests {
         };
 
         assert!(matches!(
-            process_insert(&table_id, insert_data, previous_commitments, *setups),
+            process_insert(&table_id, insert_data, &[], previous_commitments, *setups),
             Err(ProcessInsertError::AppendOnChainTable {
                 source: AppendOnChainTableError::OutOfScalarBounds { .. }
             })
         ));
     }
+
+    #[test]
+    fn we_cannot_process_insert_with_duplicate_primary_keys() {
+        let setups = get_or_init_from_files_with_four_points_unchecked();
+
+        let table_id = TableIdentifier {
+            namespace: b"animal".to_vec().try_into().unwrap(),
+            name: b"population".to_vec().try_into().unwrap(),
+        };
+
+        let animals_col_id = Ident::new("animals");
+        let population_col_id = Ident::new("population");
+
+        let empty_table = OnChainTable::try_from_iter([
+            (
+                animals_col_id.clone(),
+                OnChainColumn::empty_with_type(ColumnType::VarChar),
+            ),
+            (
+                population_col_id.clone(),
+                OnChainColumn::empty_with_type(ColumnType::BigInt),
+            ),
+        ])
+        .unwrap();
+
+        let previous_commitments = setups
+            .into_iter()
+            .map(|any| {
+                any.map(OnChainTableToTableCommitmentFn::new(&empty_table, 0))
+                    .transpose_result()
+                    .unwrap()
+            })
+            .collect::<PerCommitmentScheme<OptionType<TableCommitmentType>>>();
+
+        let insert_data = OnChainTable::try_from_iter([
+            (
+                animals_col_id.clone(),
+                OnChainColumn::VarChar(["cow", "dog", "cow"].map(String::from).to_vec()),
+            ),
+            (
+                population_col_id.clone(),
+                OnChainColumn::BigInt(vec![100, 2, 7]),
+            ),
+        ])
+        .unwrap();
+
+        assert!(matches!(
+            process_insert(
+                &table_id,
+                insert_data.clone(),
+                &[animals_col_id.clone()],
+                previous_commitments.clone(),
+                *setups
+            ),
+            Err(ProcessInsertError::DuplicatePrimaryKey)
+        ));
+
+        // the same rows are unique on a composite key
+        assert!(process_insert(
+            &table_id,
+            insert_data.clone(),
+            &[animals_col_id, population_col_id],
+            previous_commitments.clone(),
+            *setups
+        )
+        .is_ok());
+
+        assert!(matches!(
+            process_insert(
+                &table_id,
+                insert_data,
+                &[Ident::new("class")],
+                previous_commitments,
+                *setups
+            ),
+            Err(ProcessInsertError::MissingPrimaryKeyColumn { .. })
+        ));
+    }
 }
@@ -17,7 +17,7 @@ pub use column_type_conversion::{
 mod metadata_prefix;
 
 mod row_number_column;
-pub use row_number_column::row_number_column_def;
+pub use row_number_column::{row_number_column_def, row_numbers_of_on_chain_table};
 
 mod validated_create_table;
 pub use validated_create_table::{InvalidCreateTable, ValidatedCreateTable};
@@ -42,3 +42,11 @@ pub use insert::{
     InsertAndCommitmentMetadata,
     ProcessInsertError,
 };
+
+mod verify_snapshot;
+pub use verify_snapshot::{
+    AppendSnapshotDataError,
+    CommitmentMismatch,
+    SnapshotCommitmentVerifier,
+    VerifySnapshotError,
+};
@@ -12,7 +12,8 @@ use crate::metadata_prefix::METADATA_PREFIX;
 const ROW_NUMBER_COLUMN_NAME_SUFFIX: &str = "ROW_NUMBER";
 
 /// Row number column name.
-const ROW_NUMBER_COLUMN_NAME: &str = formatcp!("{METADATA_PREFIX}_{ROW_NUMBER_COLUMN_NAME_SUFFIX}");
+pub(crate) const ROW_NUMBER_COLUMN_NAME: &str =
+    formatcp!("{METADATA_PREFIX}_{ROW_NUMBER_COLUMN_NAME_SUFFIX}");
 
 /// Returns a sqlparser `ColumnDef` for the row number column.
 pub fn row_number_column_def() -> ColumnDef {
@@ -54,6 +55,16 @@ pub fn on_chain_table_with_row_number_column(
     )
 }
 
+/// Returns the row numbers of an `OnChainTable` that has a row number metadata column.
+///
+/// Returns `None` if the table has no row number column.
+pub fn row_numbers_of_on_chain_table(table: &OnChainTable) -> Option<&[i64]> {
+    match table.as_map().get(&Ident::new(ROW_NUMBER_COLUMN_NAME))? {
+        OnChainColumn::BigInt(row_numbers) => Some(row_numbers),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use alloc::string::String;
@@ -127,9 +138,15 @@ mod tests {
             ))))
             .unwrap();
         assert_eq!(
-            on_chain_table_with_row_number_column(on_chain_table, 3),
+            on_chain_table_with_row_number_column(on_chain_table.clone(), 3),
             expected_from_3
         );
+
+        assert_eq!(
+            row_numbers_of_on_chain_table(&expected_from_3),
+            Some(&[3, 4, 5][..])
+        );
+        assert_eq!(row_numbers_of_on_chain_table(&on_chain_table), None);
     }
 
     #[test]
@@ -0,0 +1,520 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+
+use on_chain_table::{OnChainColumn, OnChainTable, OutOfScalarBounds};
+use proof_of_sql::base::commitment::TableCommitmentArithmeticError;
+use proof_of_sql::base::database::ColumnType;
+use proof_of_sql_commitment_map::generic_over_commitment::{
+    AssociatedPublicSetupType,
+    ConcreteType,
+    GenericOverCommitment,
+    OptionType,
+    PairType,
+    TableCommitmentType,
+};
+use proof_of_sql_commitment_map::{
+    CommitmentId,
+    CommitmentScheme,
+    GenericOverCommitmentFn,
+    PerCommitmentScheme,
+};
+use snafu::Snafu;
+use sqlparser::ast::Ident;
+
+use crate::create_table::OnChainTableToTableCommitmentFn;
+use crate::create_table_from_snapshot::TryAddTableCommitmentsFn;
+use crate::insert::OptionZipFn;
+use crate::row_number_column::{row_numbers_of_on_chain_table, ROW_NUMBER_COLUMN_NAME};
+
+/// Generically accepts a table commitment and returns its column identifiers and types, in order.
+struct GetColumnsFn;
+
+impl GenericOverCommitmentFn for GetColumnsFn {
+    type In = TableCommitmentType;
+    type Out = ConcreteType<Vec<(Ident, ColumnType)>>;
+
+    fn call<C: CommitmentId>(
+        &self,
+        input: <Self::In as GenericOverCommitment>::WithCommitment<C>,
+    ) -> <Self::Out as GenericOverCommitment>::WithCommitment<C> {
+        input
+            .column_commitments()
+            .column_metadata()
+            .iter()
+            .map(|(identifier, metadata)| (identifier.clone(), *metadata.column_type()))
+            .collect()
+    }
+}
+
+/// Generically accepts an expected and a loaded table commitment, and describes how they differ.
+struct CompareTableCommitmentsFn;
+
+impl GenericOverCommitmentFn for CompareTableCommitmentsFn {
+    type In = PairType<TableCommitmentType, TableCommitmentType>;
+    type Out = ConcreteType<Option<CommitmentMismatch>>;
+
+    fn call<C: CommitmentId>(
+        &self,
+        (expected, loaded): <Self::In as GenericOverCommitment>::WithCommitment<C>,
+    ) -> <Self::Out as GenericOverCommitment>::WithCommitment<C> {
+        (expected != loaded).then(|| CommitmentMismatch {
+            scheme: C::COMMITMENT_SCHEME,
+            expected_rows: expected.range().clone(),
+            loaded_rows: loaded.range().clone(),
+        })
+    }
+}
+
+/// A commitment recorded on-chain that differs from the commitment to the loaded snapshot data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitmentMismatch {
+    /// The scheme of the differing commitments.
+    pub scheme: CommitmentScheme,
+    /// The rows committed to on-chain.
+    pub expected_rows: Range<usize>,
+    /// The rows of the loaded snapshot data.
+    pub loaded_rows: Range<usize>,
+}
+
+impl fmt::Display for CommitmentMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?} commitment to rows {:?} differs from loaded rows {:?}",
+            self.scheme, self.expected_rows, self.loaded_rows
+        )
+    }
+}
+
+/// Errors that can occur when appending snapshot data to a [`SnapshotCommitmentVerifier`].
+#[derive(Debug, Snafu)]
+pub enum AppendSnapshotDataError {
+    /// Snapshot data has no row number column to place it in the table.
+    #[snafu(display("snapshot data has no {ROW_NUMBER_COLUMN_NAME} column"))]
+    MissingRowNumbers,
+    /// Row numbers of the snapshot data do not increment by one.
+    #[snafu(display("snapshot row numbers are not consecutive from {first}"))]
+    NonConsecutiveRowNumbers {
+        /// The first row number of the data.
+        first: i64,
+    },
+    /// Snapshot data is missing a committed column.
+    #[snafu(display("snapshot data is missing column {column}"))]
+    MissingColumn {
+        /// The missing column.
+        column: String,
+    },
+    /// Snapshot data has a column that isn't committed to.
+    #[snafu(display("snapshot data has uncommitted column {column}"))]
+    UncommittedColumn {
+        /// The uncommitted column.
+        column: String,
+    },
+    /// Some element in the snapshot data is out of bounds of target scalar field.
+    #[snafu(transparent)]
+    OutOfScalarBounds {
+        /// Source out-of-scalar-bounds error.
+        source: OutOfScalarBounds,
+    },
+}
+
+/// Errors that can occur when verifying snapshot data against its on-chain commitments.
+#[derive(Debug, Snafu)]
+pub enum VerifySnapshotError {
+    /// No commitments to verify against.
+    #[snafu(display("no commitments to verify against"))]
+    NoCommitments,
+    /// Snapshot data overlaps, has gaps or changes column types between batches.
+    #[snafu(
+        display("snapshot data cannot be combined into a table commitment: {source}"),
+        context(false)
+    )]
+    InconsistentSnapshotData {
+        /// Source table commitment error.
+        source: TableCommitmentArithmeticError,
+    },
+    /// Snapshot data doesn't produce the on-chain commitments.
+    #[snafu(display(
+        "snapshot data does not match on-chain commitments: {}",
+        mismatches
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("; ")
+    ))]
+    CommitmentMismatch {
+        /// Every scheme whose commitment differs.
+        mismatches: Vec<CommitmentMismatch>,
+    },
+}
+
+/// Verifies that snapshot data produces the commitments recorded for its table on-chain.
+///
+/// Snapshot data is streamed in as `OnChainTable`s with a row number column, in any order.
+/// Each batch is committed to at the offset of its first row number, and the batch commitments
+/// are only combined when [`SnapshotCommitmentVerifier::finish`] is called, so batches can be
+/// appended as partitions of the snapshot are read.
+pub struct SnapshotCommitmentVerifier<'s> {
+    /// Commitments recorded on-chain.
+    expected: PerCommitmentScheme<OptionType<TableCommitmentType>>,
+    /// Setups of the schemes of the expected commitments.
+    setups: PerCommitmentScheme<OptionType<AssociatedPublicSetupType<'s>>>,
+    /// Committed columns, in commitment order.
+    columns: Vec<(Ident, ColumnType)>,
+    /// Commitments to the appended batches, with the offset of their first row.
+    batches: Vec<(usize, PerCommitmentScheme<OptionType<TableCommitmentType>>)>,
+}
+
+impl<'s> SnapshotCommitmentVerifier<'s> {
+    /// Construct a new [`SnapshotCommitmentVerifier`] for the commitments recorded on-chain.
+    ///
+    /// Only the schemes of the `expected` commitments are computed.
+    pub fn new(
+        expected: PerCommitmentScheme<OptionType<TableCommitmentType>>,
+        setups: PerCommitmentScheme<AssociatedPublicSetupType<'s>>,
+    ) -> Result<Self, VerifySnapshotError> {
+        let columns = expected
+            .clone()
+            .into_flat_iter()
+            .next()
+            .ok_or(VerifySnapshotError::NoCommitments)?
+            .map(GetColumnsFn)
+            .unwrap();
+
+        let setups = setups.select(&expected.to_flags());
+
+        Ok(SnapshotCommitmentVerifier {
+            expected,
+            setups,
+            columns,
+            batches: Vec::new(),
+        })
+    }
+
+    /// Commits to a batch of snapshot data.
+    ///
+    /// Columns are matched to the committed columns case-insensitively. The row number column
+    /// places the batch in the table and isn't committed to itself.
+    pub fn append(&mut self, batch: OnChainTable) -> Result<(), AppendSnapshotDataError> {
+        if batch.num_rows() == 0 {
+            return Ok(());
+        }
+
+        let row_numbers = row_numbers_of_on_chain_table(&batch)
+            .ok_or(AppendSnapshotDataError::MissingRowNumbers)?;
+        let first = row_numbers[0];
+        let offset = usize::try_from(first)
+            .ok()
+            .filter(|_| {
+                row_numbers
+                    .iter()
+                    .zip(first..)
+                    .all(|(row_number, expected)| *row_number == expected)
+            })
+            .ok_or(AppendSnapshotDataError::NonConsecutiveRowNumbers { first })?;
+
+        let batch = self.with_committed_columns(batch)?;
+
+        let commitments = self
+            .setups
+            .into_flat_iter()
+            .map(|setup| {
+                setup
+                    .map(OnChainTableToTableCommitmentFn::new(&batch, offset))
+                    .transpose_result()
+            })
+            .collect::<Result<_, OutOfScalarBounds>>()?;
+
+        self.batches.push((offset, commitments));
+        Ok(())
+    }
+
+    /// Returns the batch with only the committed columns, in commitment order and named as
+    /// they are committed to.
+    fn with_committed_columns(
+        &self,
+        batch: OnChainTable,
+    ) -> Result<OnChainTable, AppendSnapshotDataError> {
+        let mut loaded_columns: Vec<(Ident, OnChainColumn)> = batch
+            .into_iter()
+            .filter(|(identifier, _)| identifier.value != ROW_NUMBER_COLUMN_NAME)
+            .collect();
+
+        let columns = self
+            .columns
+            .iter()
+            .map(|(identifier, _)| {
+                let position = loaded_columns
+                    .iter()
+                    .position(|(loaded, _)| loaded.value.eq_ignore_ascii_case(&identifier.value))
+                    .ok_or_else(|| AppendSnapshotDataError::MissingColumn {
+                        column: identifier.value.clone(),
+                    })?;
+                let (_, column) = loaded_columns.swap_remove(position);
+                Ok((identifier.clone(), column))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if let Some((identifier, _)) = loaded_columns.first() {
+            return Err(AppendSnapshotDataError::UncommittedColumn {
+                column: identifier.value.clone(),
+            });
+        }
+
+        Ok(OnChainTable::try_from_iter(columns)
+            .expect("columns are taken from a single OnChainTable without duplicates"))
+    }
+
+    /// Combines the commitments of every appended batch, and compares them to the commitments
+    /// recorded on-chain.
+    ///
+    /// Batches must cover the rows of the table exactly once, starting at row 0.
+    pub fn finish(mut self) -> Result<(), VerifySnapshotError> {
+        let empty_table =
+            OnChainTable::try_from_iter(self.columns.iter().map(|(identifier, column_type)| {
+                (
+                    identifier.clone(),
+                    OnChainColumn::empty_with_type(*column_type),
+                )
+            }))
+            .expect("committed columns have distinct identifiers");
+
+        let empty_commitments: PerCommitmentScheme<OptionType<TableCommitmentType>> = self
+            .setups
+            .into_flat_iter()
+            .map(|setup| {
+                setup
+                    .map(OnChainTableToTableCommitmentFn::new(&empty_table, 0))
+                    .transpose_result()
+                    .expect("table is empty, therefore has no out-of-bounds values")
+            })
+            .collect();
+
+        self.batches.sort_by_key(|(offset, _)| *offset);
+
+        let loaded =
+            self.batches
+                .into_iter()
+                .try_fold(empty_commitments, |table, (_, batch)| {
+                    table
+                        .zip(batch)
+                        .map(OptionZipFn::new())
+                        .into_flat_iter()
+                        .map(|any| any.map(TryAddTableCommitmentsFn).transpose_result())
+                        .collect::<Result<_, TableCommitmentArithmeticError>>()
+                })?;
+
+        let mismatches: Vec<_> = self
+            .expected
+            .zip(loaded)
+            .map(OptionZipFn::new())
+            .into_flat_iter()
+            .filter_map(|any| any.map(CompareTableCommitmentsFn).unwrap())
+            .collect();
+
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            Err(VerifySnapshotError::CommitmentMismatch { mismatches })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+    use alloc::vec;
+
+    use proof_of_sql_static_setups::io::get_or_init_from_files_with_four_points_unchecked;
+
+    use super::*;
+    use crate::row_number_column::on_chain_table_with_row_number_column;
+
+    fn snapshot_data(animals: &[&str], populations: &[i64]) -> OnChainTable {
+        OnChainTable::try_from_iter([
+            (
+                Ident::new("animal"),
+                OnChainColumn::VarChar(
+                    animals.iter().map(|animal| String::from(*animal)).collect(),
+                ),
+            ),
+            (
+                Ident::new("population"),
+                OnChainColumn::BigInt(populations.to_vec()),
+            ),
+        ])
+        .unwrap()
+    }
+
+    fn on_chain_commitments(
+        snapshot: &OnChainTable,
+    ) -> PerCommitmentScheme<OptionType<TableCommitmentType>> {
+        let setups = get_or_init_from_files_with_four_points_unchecked();
+
+        setups
+            .into_iter()
+            .map(|any| {
+                any.map(OnChainTableToTableCommitmentFn::new(snapshot, 0))
+                    .transpose_result()
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    fn verifier(snapshot: &OnChainTable) -> SnapshotCommitmentVerifier<'static> {
+        let setups = get_or_init_from_files_with_four_points_unchecked();
+        SnapshotCommitmentVerifier::new(on_chain_commitments(snapshot), *setups).unwrap()
+    }
+
+    #[test]
+    fn we_can_verify_snapshot_data_streamed_out_of_order() {
+        let snapshot = snapshot_data(&["cow", "dog", "cat", "pig"], &[100, 2, 7, 30]);
+        let mut verifier = verifier(&snapshot);
+
+        let second_half = snapshot_data(&["cat", "pig"], &[7, 30]);
+        verifier
+            .append(on_chain_table_with_row_number_column(second_half, 2))
+            .unwrap();
+
+        // loaded column names are matched case-insensitively
+        let first_half = OnChainTable::try_from_iter([
+            (
+                Ident::new("POPULATION"),
+                OnChainColumn::BigInt(vec![100, 2]),
+            ),
+            (
+                Ident::new("ANIMAL"),
+                OnChainColumn::VarChar(vec!["cow".into(), "dog".into()]),
+            ),
+        ])
+        .unwrap();
+        verifier
+            .append(on_chain_table_with_row_number_column(first_half, 0))
+            .unwrap();
+
+        verifier.finish().unwrap();
+    }
+
+    #[test]
+    fn we_cannot_verify_snapshot_data_with_different_values() {
+        let snapshot = snapshot_data(&["cow", "dog", "cat"], &[100, 2, 7]);
+        let mut verifier = verifier(&snapshot);
+
+        let loaded = snapshot_data(&["cow", "dog", "cat"], &[100, 2, 8]);
+        verifier
+            .append(on_chain_table_with_row_number_column(loaded, 0))
+            .unwrap();
+
+        let Err(VerifySnapshotError::CommitmentMismatch { mismatches }) = verifier.finish() else {
+            panic!("expected a commitment mismatch");
+        };
+        assert_eq!(
+            mismatches,
+            vec![
+                CommitmentMismatch {
+                    scheme: CommitmentScheme::HyperKzg,
+                    expected_rows: 0..3,
+                    loaded_rows: 0..3,
+                },
+                CommitmentMismatch {
+                    scheme: CommitmentScheme::DynamicDory,
+                    expected_rows: 0..3,
+                    loaded_rows: 0..3,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn we_cannot_verify_incomplete_snapshot_data() {
+        let snapshot = snapshot_data(&["cow", "dog", "cat"], &[100, 2, 7]);
+        let mut verifier = verifier(&snapshot);
+
+        let loaded = snapshot_data(&["cow", "dog"], &[100, 2]);
+        verifier
+            .append(on_chain_table_with_row_number_column(loaded, 0))
+            .unwrap();
+
+        assert!(matches!(
+            verifier.finish(),
+            Err(VerifySnapshotError::CommitmentMismatch { mismatches })
+                if mismatches.iter().all(|mismatch| mismatch.loaded_rows == (0..2))
+        ));
+    }
+
+    #[test]
+    fn we_cannot_verify_snapshot_data_with_gaps_or_overlaps() {
+        let snapshot = snapshot_data(&["cow", "dog", "cat"], &[100, 2, 7]);
+
+        let mut verifier_with_gap = verifier(&snapshot);
+        verifier_with_gap
+            .append(on_chain_table_with_row_number_column(
+                snapshot_data(&["dog", "cat"], &[2, 7]),
+                1,
+            ))
+            .unwrap();
+        assert!(matches!(
+            verifier_with_gap.finish(),
+            Err(VerifySnapshotError::InconsistentSnapshotData { .. })
+        ));
+
+        let mut verifier_with_overlap = verifier(&snapshot);
+        for offset in [0, 1] {
+            verifier_with_overlap
+                .append(on_chain_table_with_row_number_column(
+                    snapshot_data(&["cow", "dog"], &[100, 2]),
+                    offset,
+                ))
+                .unwrap();
+        }
+        assert!(matches!(
+            verifier_with_overlap.finish(),
+            Err(VerifySnapshotError::InconsistentSnapshotData { .. })
+        ));
+    }
+
+    #[test]
+    fn we_cannot_append_snapshot_data_that_does_not_fit_the_commitments() {
+        let snapshot = snapshot_data(&["cow", "dog", "cat"], &[100, 2, 7]);
+        let mut verifier = verifier(&snapshot);
+
+        assert!(matches!(
+            verifier.append(snapshot.clone()),
+            Err(AppendSnapshotDataError::MissingRowNumbers)
+        ));
+
+        let shuffled_row_numbers =
+            OnChainTable::try_from_iter(snapshot.clone().into_iter().chain([(
+                Ident::new(ROW_NUMBER_COLUMN_NAME),
+                OnChainColumn::BigInt(vec![0, 2, 1]),
+            )]))
+            .unwrap();
+        assert!(matches!(
+            verifier.append(shuffled_row_numbers),
+            Err(AppendSnapshotDataError::NonConsecutiveRowNumbers { first: 0 })
+        ));
+
+        let missing_column = OnChainTable::try_from_iter([(
+            Ident::new("animal"),
+            OnChainColumn::VarChar(vec!["cow".into()]),
+        )])
+        .unwrap();
+        assert!(matches!(
+            verifier.append(on_chain_table_with_row_number_column(missing_column, 0)),
+            Err(AppendSnapshotDataError::MissingColumn { column }) if column == "population"
+        ));
+
+        let extra_column = OnChainTable::try_from_iter(
+            snapshot
+                .into_iter()
+                .chain([(Ident::new("legs"), OnChainColumn::BigInt(vec![4, 4, 4]))]),
+        )
+        .unwrap();
+        assert!(matches!(
+            verifier.append(on_chain_table_with_row_number_column(extra_column, 0)),
+            Err(AppendSnapshotDataError::UncommittedColumn { column }) if column == "legs"
+        ));
+    }
+}
@@ -5,8 +5,11 @@ use arrow::array::{
     ArrayRef,
     BinaryArray,
     BooleanArray,
+    Date32Array,
+    Date64Array,
     Decimal128Array,
     Decimal256Array,
+    FixedSizeBinaryArray,
     Int16Array,
     Int32Array,
     Int64Array,
@@ -16,9 +19,13 @@ use arrow::array::{
     TimestampMillisecondArray,
     TimestampNanosecondArray,
     TimestampSecondArray,
+    UInt16Array,
+    UInt32Array,
+    UInt64Array,
     UInt8Array,
 };
 use arrow::datatypes::{DataType, TimeUnit};
+use primitive_types::U256;
 use proof_of_sql::base::math::decimal::Precision;
 use proof_of_sql::base::posql_time::{PoSQLTimeUnit, PoSQLTimeZone, PoSQLTimestampError};
 use snafu::Snafu;
@@ -26,6 +33,15 @@ use snafu::Snafu;
 use crate::i256_conversion::{arrow_i256_to_u256, u256_to_arrow_i256};
 use crate::OnChainColumn;
 
+/// Number of seconds in a day, for converting arrow dates to timestamps.
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Number of decimal digits needed to represent any `u64`.
+const UNSIGNED_BIGINT_PRECISION: u8 = 20;
+
+/// Byte width of the only supported `FixedSizeBinary` type, that of a UUID.
+const UUID_BYTE_WIDTH: i32 = 16;
+
 /// Errors that can occur when converting from an arrow `ArrayRef` to [`OnChainColumn`].
 #[derive(Debug, Snafu)]
 pub enum ArrowToOnChainColumnError {
@@ -57,6 +73,15 @@ impl From<PoSQLTimestampError> for ArrowToOnChainColumnError {
     }
 }
 
+/// Arrow types without a proof-of-sql equivalent are encoded as follows..
+/// - `UInt8` as [`OnChainColumn::UnsignedTinyInt`]
+/// - `UInt16` as [`OnChainColumn::Int`]
+/// - `UInt32` as [`OnChainColumn::BigInt`]
+/// - `UInt64` as [`OnChainColumn::Decimal75`] with precision 20 and scale 0
+/// - `Date32`/`Date64` as second-precision UTC [`OnChainColumn::TimestampTZ`]s at midnight
+/// - `FixedSizeBinary(16)` (UUIDs) as [`OnChainColumn::VarBinary`], other widths are unsupported
+///
+/// These match the column types that the corresponding DDL types are committed with.
 impl TryFrom<&ArrayRef> for OnChainColumn {
     type Error = ArrowToOnChainColumnError;
     fn try_from(value: &ArrayRef) -> Result<Self, Self::Error> {
@@ -76,6 +101,87 @@ impl TryFrom<&ArrayRef> for OnChainColumn {
                     .collect::<Option<Vec<bool>>>()
                     .ok_or(ArrowToOnChainColumnError::UnsupportedNull)?,
             )),
+            DataType::UInt8 => Ok(Self::UnsignedTinyInt(
+                value
+                    .as_any()
+                    .downcast_ref::<UInt8Array>()
+                    .ok_or(ArrowToOnChainColumnError::UnexpectedSchemaDataMismatch)?
+                    .values()
+                    .to_vec(),
+            )),
+            DataType::UInt16 => Ok(Self::Int(
+                value
+                    .as_any()
+                    .downcast_ref::<UInt16Array>()
+                    .ok_or(ArrowToOnChainColumnError::UnexpectedSchemaDataMismatch)?
+                    .values()
+                    .iter()
+                    .copied()
+                    .map(i32::from)
+                    .collect(),
+            )),
+            DataType::UInt32 => Ok(Self::BigInt(
+                value
+                    .as_any()
+                    .downcast_ref::<UInt32Array>()
+                    .ok_or(ArrowToOnChainColumnError::UnexpectedSchemaDataMismatch)?
+                    .values()
+                    .iter()
+                    .copied()
+                    .map(i64::from)
+                    .collect(),
+            )),
+            DataType::UInt64 => Ok(Self::Decimal75(
+                Precision::new(UNSIGNED_BIGINT_PRECISION).expect("precision is less than 76"),
+                0,
+                value
+                    .as_any()
+                    .downcast_ref::<UInt64Array>()
+                    .ok_or(ArrowToOnChainColumnError::UnexpectedSchemaDataMismatch)?
+                    .values()
+                    .iter()
+                    .copied()
+                    .map(U256::from)
+                    .collect(),
+            )),
+            DataType::Date32 => Ok(Self::TimestampTZ(
+                PoSQLTimeUnit::Second,
+                Some(PoSQLTimeZone::utc()),
+                value
+                    .as_any()
+                    .downcast_ref::<Date32Array>()
+                    .ok_or(ArrowToOnChainColumnError::UnexpectedSchemaDataMismatch)?
+                    .values()
+                    .iter()
+                    .map(|days| i64::from(*days) * SECONDS_PER_DAY)
+                    .collect(),
+            )),
+            DataType::Date64 => Ok(Self::TimestampTZ(
+                PoSQLTimeUnit::Second,
+                Some(PoSQLTimeZone::utc()),
+                value
+                    .as_any()
+                    .downcast_ref::<Date64Array>()
+                    .ok_or(ArrowToOnChainColumnError::UnexpectedSchemaDataMismatch)?
+                    .values()
+                    .iter()
+                    // arrow requires Date64 values to be whole days, this also truncates any
+                    // time of day that slips through
+                    .map(|millis| millis.div_euclid(SECONDS_PER_DAY * 1000) * SECONDS_PER_DAY)
+                    .collect(),
+            )),
+            DataType::FixedSizeBinary(UUID_BYTE_WIDTH) => Ok(Self::VarBinary(
+                value
+                    .as_any()
+                    .downcast_ref::<FixedSizeBinaryArray>()
+                    .ok_or(ArrowToOnChainColumnError::UnexpectedSchemaDataMismatch)?
+                    .iter()
+                    .map(|b| {
+                        b.map(|b| b.to_vec())
+                            .ok_or(ArrowToOnChainColumnError::UnsupportedNull)
+                    })
+                    .collect::<Result<_, _>>()?,
+            )),
             DataType::Int8 => Ok(Self::TinyInt(
                 value
                     .as_any()
@@ -257,7 +363,6 @@ impl From<OnChainColumn> for ArrayRef {
 mod tests {
     use arrow::array::Float32Array;
     use arrow::datatypes::i256;
-    use primitive_types::U256;
 
     use super::*;
 
@@ -419,6 +524,65 @@ mod tests {
         );
     }
 
+    #[test]
+    fn we_can_convert_unsigned_arrow_arrays_to_on_chain_column() {
+        let data = vec![0, 1, u8::MAX];
+        let array: ArrayRef = Arc::new(UInt8Array::from(data.clone()));
+        assert_eq!(
+            OnChainColumn::try_from(&array).unwrap(),
+            OnChainColumn::UnsignedTinyInt(data)
+        );
+
+        let array: ArrayRef = Arc::new(UInt16Array::from(vec![0, 1, u16::MAX]));
+        assert_eq!(
+            OnChainColumn::try_from(&array).unwrap(),
+            OnChainColumn::Int(vec![0, 1, u16::MAX as i32])
+        );
+
+        let array: ArrayRef = Arc::new(UInt32Array::from(vec![0, 1, u32::MAX]));
+        assert_eq!(
+            OnChainColumn::try_from(&array).unwrap(),
+            OnChainColumn::BigInt(vec![0, 1, u32::MAX as i64])
+        );
+
+        let array: ArrayRef = Arc::new(UInt64Array::from(vec![0, 1, u64::MAX]));
+        assert_eq!(
+            OnChainColumn::try_from(&array).unwrap(),
+            OnChainColumn::Decimal75(
+                Precision::new(20).unwrap(),
+                0,
+                vec![U256::zero(), U256::one(), U256::from(u64::MAX)]
+            )
+        );
+    }
+
+    #[test]
+    fn we_can_convert_arrow_date_arrays_to_on_chain_column() {
+        let expected = OnChainColumn::TimestampTZ(
+            PoSQLTimeUnit::Second,
+            Some(PoSQLTimeZone::utc()),
+            vec![-86_400, 0, 19_000 * 86_400],
+        );
+
+        let array: ArrayRef = Arc::new(Date32Array::from(vec![-1, 0, 19_000]));
+        assert_eq!(OnChainColumn::try_from(&array).unwrap(), expected);
+
+        let array: ArrayRef =
+            Arc::new(Date64Array::from(vec![-86_400_000, 0, 19_000 * 86_400_000]));
+        assert_eq!(OnChainColumn::try_from(&array).unwrap(), expected);
+    }
+
+    #[test]
+    fn we_can_convert_arrow_fixed_size_binary_array_to_on_chain_column() {
+        let data = vec![[0u8; 16].to_vec(), [255u8; 16].to_vec()];
+        let array: ArrayRef =
+            Arc::new(FixedSizeBinaryArray::try_from_iter(data.clone().into_iter()).unwrap());
+        assert_eq!(
+            OnChainColumn::try_from(&array).unwrap(),
+            OnChainColumn::VarBinary(data)
+        );
+    }
+
     #[test]
     fn we_cannot_convert_from_arrow_with_unsupported_type() {
         let array: ArrayRef = Arc::new(Float32Array::from(vec![1., 2., 3.]));
@@ -438,6 +602,17 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn we_cannot_convert_arrow_fixed_size_binary_array_of_non_uuid_width() {
+        let data = vec![[0u8; 20].to_vec(), [255u8; 20].to_vec()];
+        let array: ArrayRef =
+            Arc::new(FixedSizeBinaryArray::try_from_iter(data.into_iter()).unwrap());
+        assert!(matches!(
+            OnChainColumn::try_from(&array),
+            Err(ArrowToOnChainColumnError::UnsupportedType { .. })
+        ));
+    }
+
     #[test]
     fn we_cannot_convert_from_nullable_arrow_array() {
         let array: ArrayRef = Arc::new(Int16Array::from(vec![Some(1), None]));
@@ -18,6 +18,9 @@ use crate::OutOfScalarBounds;
 /// With the `arrow` feature, this implements conversion to/from arrow `ArrayRef`s.
 ///
 /// Without the `std` feature, this type can be used in `no_std` environments.
+///
+/// Types without a proof-of-sql equivalent, like dates, UUIDs and unsigned integers wider than
+/// 8 bits, are stored in the variant of the column type they are committed with.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum OnChainColumn {
     /// Column of bools.
@@ -143,6 +146,37 @@ impl OnChainColumn {
             )),
         }
     }
+
+    /// Appends an unambiguous little-endian encoding of the element at `index` to `key`.
+    ///
+    /// Variable-length elements are prefixed with their length, so concatenated encodings of
+    /// different elements cannot collide.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    pub(crate) fn extend_key_with_element(&self, index: usize, key: &mut Vec<u8>) {
+        match self {
+            OnChainColumn::Boolean(bools) => key.push(bools[index].into()),
+            OnChainColumn::UnsignedTinyInt(ints) => key.push(ints[index]),
+            OnChainColumn::TinyInt(ints) => key.extend(ints[index].to_le_bytes()),
+            OnChainColumn::SmallInt(ints) => key.extend(ints[index].to_le_bytes()),
+            OnChainColumn::Int(ints) => key.extend(ints[index].to_le_bytes()),
+            OnChainColumn::BigInt(ints) => key.extend(ints[index].to_le_bytes()),
+            OnChainColumn::Int128(ints) => key.extend(ints[index].to_le_bytes()),
+            OnChainColumn::TimestampTZ(.., ints) => key.extend(ints[index].to_le_bytes()),
+            OnChainColumn::Decimal75(.., ints) => {
+                key.extend(ints[index].0.iter().flat_map(|limb| limb.to_le_bytes()))
+            }
+            OnChainColumn::VarChar(strings) => {
+                key.extend((strings[index].len() as u64).to_le_bytes());
+                key.extend(strings[index].as_bytes());
+            }
+            OnChainColumn::VarBinary(words) => {
+                key.extend((words[index].len() as u64).to_le_bytes());
+                key.extend(&words[index]);
+            }
+        }
+    }
 }
 
 #[cfg(test)]
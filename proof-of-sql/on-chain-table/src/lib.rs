@@ -21,7 +21,7 @@ mod arrow_column_conversion;
 pub use arrow_column_conversion::ArrowToOnChainColumnError;
 
 mod table;
-pub use table::{OnChainTable, OnChainTableError};
+pub use table::{MissingKeyColumn, OnChainTable, OnChainTableError};
 
 #[cfg(feature = "arrow")]
 mod arrow_table_conversion;
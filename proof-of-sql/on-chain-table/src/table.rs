@@ -45,6 +45,14 @@ pub enum OnChainTableError {
     ColumnLengthMismatch,
 }
 
+/// [`OnChainTable`] is missing a column requested as part of a row key.
+#[derive(Debug, Snafu)]
+#[snafu(display("OnChainTable is missing key column {identifier}"))]
+pub struct MissingKeyColumn {
+    /// The identifier of the missing column.
+    pub identifier: Ident,
+}
+
 impl OnChainTable {
     /// Create a new [`OnChainTable`] from an iterator.
     ///
@@ -137,6 +145,35 @@ impl OnChainTable {
         OnChainTable(ordered_columns)
     }
 
+    /// Returns the encoded key of every row, made from the values of the given key columns.
+    ///
+    /// Key column names are matched case-insensitively. Two rows have equal keys if and only if
+    /// they have equal values in every key column.
+    pub fn row_keys<'a>(
+        &self,
+        key_columns: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Vec<Vec<u8>>, MissingKeyColumn> {
+        let key_columns = key_columns
+            .into_iter()
+            .map(|column_name| {
+                let identifier = Ident::new(column_name.to_uppercase());
+                self.0
+                    .get(&identifier)
+                    .ok_or(MissingKeyColumn { identifier })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok((0..self.num_rows())
+            .map(|index| {
+                let mut key = Vec::new();
+                key_columns
+                    .iter()
+                    .for_each(|column| column.extend_key_with_element(index, &mut key));
+                key
+            })
+            .collect())
+    }
+
     /// Attempts to retrieve the values for a given decimal column name
     /// Returns None if the provided column does not exist
     pub fn get_decimal_by_column(&self, column_name: &str) -> Option<&Vec<U256>> {
@@ -159,6 +196,28 @@ impl OnChainTable {
         }
     }
 
+    /// Attempts to retrieve the values for a given Int column name
+    /// Returns None if the provided column does not exist
+    pub fn get_ints_by_column(&self, column_name: &str) -> Option<&Vec<i32>> {
+        let column_id: Ident = Ident::new(column_name.to_uppercase());
+        let column = self.as_map().get(&column_id)?;
+        match column {
+            OnChainColumn::Int(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Attempts to retrieve the values for a given BigInt column name
+    /// Returns None if the provided column does not exist
+    pub fn get_bigints_by_column(&self, column_name: &str) -> Option<&Vec<i64>> {
+        let column_id: Ident = Ident::new(column_name.to_uppercase());
+        let column = self.as_map().get(&column_id)?;
+        match column {
+            OnChainColumn::BigInt(values) => Some(values),
+            _ => None,
+        }
+    }
+
     /// Attempts to retrieve the values for a given VarChar column name
     /// Returns None if the provided column does not exist
     pub fn get_varchars_by_column(&self, column_name: &str) -> Option<&Vec<alloc::string::String>> {
@@ -516,6 +575,41 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn get_ints_and_bigints_with_valid_params_works() {
+        let data = [
+            (Ident::new("event_index"), OnChainColumn::Int(vec![1, 2])),
+            (
+                Ident::new("block_number"),
+                OnChainColumn::BigInt(vec![10, 11]),
+            ),
+        ];
+        let table = OnChainTable::try_from_iter(data).unwrap();
+
+        assert_eq!(table.get_ints_by_column("event_index"), Some(&vec![1, 2]));
+        assert_eq!(
+            table.get_bigints_by_column("block_number"),
+            Some(&vec![10, 11])
+        );
+    }
+
+    #[test]
+    fn get_ints_and_bigints_with_wrong_type_or_missing_column_is_none() {
+        let data = [
+            (Ident::new("event_index"), OnChainColumn::Int(vec![1, 2])),
+            (
+                Ident::new("block_number"),
+                OnChainColumn::BigInt(vec![10, 11]),
+            ),
+        ];
+        let table = OnChainTable::try_from_iter(data).unwrap();
+
+        assert!(table.get_ints_by_column("block_number").is_none());
+        assert!(table.get_bigints_by_column("event_index").is_none());
+        assert!(table.get_ints_by_column("missing_column").is_none());
+        assert!(table.get_bigints_by_column("missing_column").is_none());
+    }
+
     #[test]
     fn we_can_construct_table_with_lowercase_column_identifiers_and_get_uppercase() {
         let data = [
@@ -544,4 +638,54 @@ mod tests {
         let expected_map = IndexMap::<Ident, OnChainColumn>::from_iter(expected_data);
         assert_eq!(table.as_map(), &expected_map);
     }
+
+    #[test]
+    fn we_can_get_row_keys() {
+        let table = OnChainTable::try_from_iter([
+            (Ident::new("ID"), OnChainColumn::BigInt(vec![1, 1, 2])),
+            (
+                Ident::new("NAME"),
+                OnChainColumn::VarChar(["a", "b", "a"].map(String::from).to_vec()),
+            ),
+            (Ident::new("VALUE"), OnChainColumn::Int(vec![7, 7, 7])),
+        ])
+        .unwrap();
+
+        let keys = table.row_keys(["id", "name"]).unwrap();
+        assert_eq!(keys.len(), 3);
+        assert_ne!(keys[0], keys[1]);
+        assert_ne!(keys[0], keys[2]);
+
+        let keys = table.row_keys(["VALUE"]).unwrap();
+        assert_eq!(keys[0], keys[1]);
+        assert_eq!(keys[1], keys[2]);
+
+        // length prefixes keep adjacent variable-length values from colliding
+        let table = OnChainTable::try_from_iter([
+            (
+                Ident::new("A"),
+                OnChainColumn::VarChar(["ab", "a"].map(String::from).to_vec()),
+            ),
+            (
+                Ident::new("B"),
+                OnChainColumn::VarChar(["c", "bc"].map(String::from).to_vec()),
+            ),
+        ])
+        .unwrap();
+        let keys = table.row_keys(["A", "B"]).unwrap();
+        assert_ne!(keys[0], keys[1]);
+    }
+
+    #[test]
+    fn we_cannot_get_row_keys_with_missing_column() {
+        let table =
+            OnChainTable::try_from_iter([(Ident::new("ID"), OnChainColumn::BigInt(vec![1]))])
+                .unwrap();
+
+        let result = table.row_keys(["NAME"]);
+        assert!(matches!(
+            result,
+            Err(MissingKeyColumn { identifier }) if identifier == Ident::new("NAME")
+        ));
+    }
 }
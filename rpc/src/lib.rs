@@ -7,6 +7,8 @@ mod commitments;
 
 mod attestation;
 
+mod staking_mirror;
+
 use std::sync::Arc;
 
 use attestation::{AttestationApiImpl, AttestationApiServer};
@@ -28,6 +30,7 @@ use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
 use sp_consensus::SelectChain;
 use sp_consensus_babe::BabeApi;
 use sp_keystore::KeystorePtr;
+use staking_mirror::{StakingMirrorApiImpl, StakingMirrorApiServer};
 use sxt_runtime::opaque::Block;
 use sxt_runtime::{AccountId, Balance, BlockNumber, Hash, Nonce, Runtime};
 
@@ -101,6 +104,7 @@ where
     C::Api: BabeApi<Block>,
     C::Api: BlockBuilder<Block>,
     C::Api: sxt_runtime::pallet_commitments::runtime_api::CommitmentsApi<Block>,
+    C::Api: sxt_runtime::pallet_system_tables::runtime_api::StakingMirrorApi<Block, AccountId>,
     P: TransactionPool + 'static,
     SC: SelectChain<Block> + 'static,
     B: sc_client_api::Backend<Block> + Send + Sync + 'static,
@@ -173,5 +177,7 @@ where
 
     io.merge(CommitmentsApiImpl::<_, B, Block, Runtime>::new(client.clone()).into_rpc())?;
 
+    io.merge(StakingMirrorApiImpl::<_, Block>::new(client.clone()).into_rpc())?;
+
     Ok(io)
 }
@@ -0,0 +1,92 @@
+use jsonrpsee::proc_macros::rpc;
+use serde::Serialize;
+use sp_core::H160;
+use sxt_runtime::pallet_system_tables::runtime_api::MirroredStaker;
+use sxt_runtime::AccountId;
+
+use crate::staking_mirror::StakingMirrorApiError;
+
+/// An amount being unbonded by a staker.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MirroredUnbondingResponse {
+    /// The amount unbonding, as a decimal string.
+    pub value: String,
+    /// The era the amount can be withdrawn in.
+    pub era: u32,
+}
+
+/// The staking state of an EVM staker, as mirrored on chain.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MirroredStakerResponse {
+    /// The ethereum address of the staker.
+    pub address: H160,
+    /// The account the staker is mirrored to.
+    pub account: AccountId,
+    /// The amount bonded, including the amounts unbonding, as a decimal string.
+    pub bonded: String,
+    /// The amount bonded and not unbonding, as a decimal string.
+    pub active: String,
+    /// The amounts unbonding.
+    pub unbonding: Vec<MirroredUnbondingResponse>,
+    /// The validators nominated by the staker.
+    pub nominations: Vec<AccountId>,
+    /// Whether the staker is validating.
+    pub validating: bool,
+}
+
+impl From<MirroredStaker<AccountId>> for MirroredStakerResponse {
+    fn from(staker: MirroredStaker<AccountId>) -> Self {
+        MirroredStakerResponse {
+            address: H160(staker.address),
+            account: staker.account,
+            bonded: staker.bonded.to_string(),
+            active: staker.active.to_string(),
+            unbonding: staker
+                .unbonding
+                .into_iter()
+                .map(|chunk| MirroredUnbondingResponse {
+                    value: chunk.value.to_string(),
+                    era: chunk.era,
+                })
+                .collect(),
+            nominations: staker.nominations,
+            validating: staker.validating,
+        }
+    }
+}
+
+/// Serialization format for a page of mirrored stakers.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MirroredStakersResponse<BH: Serialize> {
+    /// The mirrored stakers.
+    pub stakers: Vec<MirroredStakerResponse>,
+    /// The address to request the next page after, if this page is full.
+    pub next: Option<H160>,
+    /// The block hash that this query accessed storage with.
+    pub at: BH,
+}
+
+/// RPCs reading the staking state mirrored from the EVM staking contract.
+#[rpc(server)]
+pub trait StakingMirrorApi<BH: Serialize> {
+    /// Returns a page of the bonded EVM stakers, starting after the staker with the address
+    /// `start_after`.
+    #[method(name = "stakingMirror_v1_mirroredStakers", blocking)]
+    fn v1_mirrored_stakers(
+        &self,
+        start_after: Option<H160>,
+        limit: Option<u32>,
+        at: Option<BH>,
+    ) -> Result<MirroredStakersResponse<BH>, StakingMirrorApiError>;
+
+    /// Returns the mirrored state of an EVM staker, or `null` if it isn't bonded.
+    #[method(name = "stakingMirror_v1_mirroredStaker", blocking)]
+    fn v1_mirrored_staker(
+        &self,
+        address: H160,
+        at: Option<BH>,
+    ) -> Result<Option<MirroredStakerResponse>, StakingMirrorApiError>;
+}
@@ -0,0 +1,80 @@
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+use sxt_runtime::pallet_system_tables::runtime_api::{StakingMirrorApi, MAX_MIRRORED_STAKERS_PAGE};
+use sxt_runtime::AccountId;
+
+use crate::staking_mirror::api::StakingMirrorApiServer;
+use crate::staking_mirror::{
+    MirroredStakerResponse,
+    MirroredStakersResponse,
+    StakingMirrorApiError,
+};
+
+/// [`StakingMirrorApiServer`] implementor providing its RPCs.
+pub struct StakingMirrorApiImpl<Client, Block> {
+    client: Arc<Client>,
+    _phantom: PhantomData<Block>,
+}
+
+impl<Client, Block> StakingMirrorApiImpl<Client, Block> {
+    /// Construct a new [`StakingMirrorApiImpl`].
+    pub fn new(client: Arc<Client>) -> Self {
+        StakingMirrorApiImpl {
+            client,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<Client, Block> StakingMirrorApiServer<Block::Hash> for StakingMirrorApiImpl<Client, Block>
+where
+    Client: Send + Sync + HeaderBackend<Block> + ProvideRuntimeApi<Block> + 'static,
+    Client::Api: StakingMirrorApi<Block, AccountId>,
+    Block: BlockT + 'static,
+{
+    fn v1_mirrored_stakers(
+        &self,
+        start_after: Option<sp_core::H160>,
+        limit: Option<u32>,
+        at: Option<Block::Hash>,
+    ) -> Result<MirroredStakersResponse<Block::Hash>, StakingMirrorApiError> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        let limit = limit
+            .unwrap_or(MAX_MIRRORED_STAKERS_PAGE)
+            .min(MAX_MIRRORED_STAKERS_PAGE);
+
+        let stakers = self.client.runtime_api().mirrored_stakers(
+            at,
+            start_after.map(|address| address.0),
+            limit,
+        )?;
+
+        let next = (stakers.len() == limit as usize)
+            .then(|| stakers.last().map(|staker| sp_core::H160(staker.address)))
+            .flatten();
+
+        Ok(MirroredStakersResponse {
+            stakers: stakers.into_iter().map(Into::into).collect(),
+            next,
+            at,
+        })
+    }
+
+    fn v1_mirrored_staker(
+        &self,
+        address: sp_core::H160,
+        at: Option<Block::Hash>,
+    ) -> Result<Option<MirroredStakerResponse>, StakingMirrorApiError> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        Ok(self
+            .client
+            .runtime_api()
+            .mirrored_staker(at, address.0)?
+            .map(Into::into))
+    }
+}
@@ -0,0 +1,29 @@
+use jsonrpsee::types::ErrorObjectOwned;
+use snafu::Snafu;
+
+/// The base error code used by the staking mirror RPCs.
+const BASE_ERROR: i32 = 256000;
+
+/// Errors that can occur in the staking mirror RPCs.
+#[derive(Debug, Snafu)]
+pub enum StakingMirrorApiError {
+    /// Received error from runtime api.
+    #[snafu(display("received error from runtime api: {source}"), context(false))]
+    RuntimeApi {
+        /// The source runtime api error.
+        source: sp_api::ApiError,
+    },
+}
+
+impl From<StakingMirrorApiError> for ErrorObjectOwned {
+    fn from(error: StakingMirrorApiError) -> Self {
+        let message = error.to_string();
+
+        let code = BASE_ERROR
+            + match error {
+                StakingMirrorApiError::RuntimeApi { .. } => 0,
+            };
+
+        ErrorObjectOwned::owned(code, message, None::<()>)
+    }
+}
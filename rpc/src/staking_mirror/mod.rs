@@ -0,0 +1,8 @@
+mod error;
+pub use error::StakingMirrorApiError;
+
+mod api;
+pub use api::{MirroredStakerResponse, MirroredStakersResponse, StakingMirrorApiServer};
+
+mod api_impl;
+pub use api_impl::StakingMirrorApiImpl;
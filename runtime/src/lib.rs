@@ -35,6 +35,7 @@ pub use frame_support::weights::constants::{
 };
 use frame_support::weights::ConstantMultiplier;
 pub use frame_support::weights::{IdentityFee, Weight};
+use frame_support::PalletId;
 pub use frame_support::{construct_runtime, derive_impl, parameter_types, StorageValue};
 pub use frame_system::Call as SystemCall;
 use frame_system::EnsureRoot;
@@ -165,7 +166,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     //   `spec_version`, and `authoring_version` are the same between Wasm and native.
     // This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
     //   the compatible custom types.
-    spec_version: 229,
+    spec_version: 256,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
     transaction_version: 1,
@@ -778,11 +779,15 @@ impl pallet_commitments::Config for Runtime {}
 impl pallet_indexing::Config<native_api::Api> for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = pallet_indexing::weights::SubstrateWeight<Runtime>;
+    type PublicationTimeout = ConstU32<{ 10 * MINUTES }>;
+    type ChunkUploadTimeout = ConstU32<{ 30 * MINUTES }>;
+    type WorkRewards = Rewards;
 }
 
 impl pallet_attestation::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = pallet_attestation::weights::SubstrateWeight<Runtime>;
+    type WorkRewards = Rewards;
 }
 
 impl pallet_keystore::Config for Runtime {
@@ -792,6 +797,7 @@ impl pallet_keystore::Config for Runtime {
 
 impl pallet_system_tables::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
+    type TemplateAdminOrigin = EnsureRoot<Self::AccountId>;
 }
 
 impl pallet_system_contracts::Config for Runtime {
@@ -803,10 +809,22 @@ impl pallet_smartcontracts::Config<native_api::Api> for Runtime {
     type WeightInfo = pallet_smartcontracts::weights::SubstrateWeight<Runtime>;
 }
 
+parameter_types! {
+    pub const WorkRewardsPotId: PalletId = PalletId(*b"sxt/work");
+    pub const WorkRewardsPerEra: Balance = 1_000 * DOLLARS;
+}
+
 impl pallet_rewards::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     // Payout up to 3 pages per block
     type MaxPayoutsPerBlock = ConstU32<3>;
+    type WeightInfo = pallet_rewards::weights::SubstrateWeight<Runtime>;
+    type PotId = WorkRewardsPotId;
+    type WorkRewardsPerEra = WorkRewardsPerEra;
+    // One point per KiB indexed
+    type IndexedBytesPerPoint = ConstU64<1024>;
+    type TimelyAttestationPoints = ConstU64<10>;
+    type AttestationDeadline = ConstU32<{ 10 * MINUTES }>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -933,7 +951,11 @@ pub type SignedExtra = (
 ///
 /// This can be a tuple of types, each implementing `OnRuntimeUpgrade`.
 #[allow(unused_parens)]
-type Migrations = ();
+type Migrations = (
+    pallet_system_tables::migrations::v1::RegisterDefaultTemplates<Runtime>,
+    pallet_system_tables::migrations::v2::DeclareStakingEventSources<Runtime>,
+    pallet_tables::migrations::v1::DeclarePrimaryKeyConstraints<Runtime>,
+);
 
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic =
@@ -1298,4 +1320,26 @@ impl_runtime_apis! {
             Commitments::table_commitments_any_scheme(table_identifiers.as_slice())
         }
     }
+
+    impl pallet_system_tables::runtime_api::StakingMirrorApi<Block, AccountId> for Runtime {
+        fn mirrored_stakers(start_after: Option<[u8; 20]>, limit: u32) -> Vec<pallet_system_tables::runtime_api::MirroredStaker<AccountId>> {
+            SystemTables::mirrored_stakers(start_after, limit)
+        }
+
+        fn mirrored_staker(address: [u8; 20]) -> Option<pallet_system_tables::runtime_api::MirroredStaker<AccountId>> {
+            SystemTables::mirrored_staker(address)
+        }
+    }
+
+    impl pallet_rewards::runtime_api::RewardsApi<Block, AccountId> for Runtime {
+        fn pending_payouts() -> Vec<pallet_rewards::runtime_api::PendingPayout<AccountId>> {
+            Rewards::pending_payouts()
+        }
+    }
+
+    impl pallet_rewards::runtime_api::WorkRewardsApi<Block, AccountId, Balance> for Runtime {
+        fn expected_work_earnings(worker: AccountId) -> Vec<pallet_rewards::runtime_api::ExpectedWorkEarnings<Balance>> {
+            Rewards::expected_work_earnings(worker)
+        }
+    }
 }
@@ -152,6 +152,81 @@ pub struct DataQuorum<AccountId, Hash> {
     pub quorum_scope: QuorumScope,
 }
 
+/// The maximum number of rows a single batch can insert into a table that maintains a primary key
+/// index.
+///
+/// Indexing keys costs weight per row, which is charged up front for this many rows.
+pub const MAX_KEYED_ROWS_PER_BATCH: u32 = 1024;
+
+/// The maximum number of tables in a single multi-table submission
+pub const MAX_MULTI_TABLES: u32 = 8;
+
+/// Row data for several tables that should be committed together, in commit order
+pub type MultiTableRowData = BoundedVec<(TableIdentifier, RowData), ConstU32<MAX_MULTI_TABLES>>;
+
+/// The maximum number of chunks a single chunked batch can be split into
+pub const MAX_CHUNKS: u32 = 16;
+
+/// Position of a chunk within a chunked batch
+pub type ChunkIndex = u32;
+
+/// Announces a batch that is too large for a single [`RowData`] submission.
+///
+/// Indexers reach quorum on the hash of the manifest rather than on the data itself. Once the
+/// manifest is decided, the chunks are uploaded individually and checked against the hashes
+/// listed here.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ChunkManifest<Hash> {
+    /// The hash of each chunk's SCALE-encoded [`RowData`], in insertion order
+    pub chunk_hashes: BoundedVec<Hash, ConstU32<MAX_CHUNKS>>,
+
+    /// The highest source block covered by the batch, if known
+    pub block_number: Option<u64>,
+}
+
+impl<Hash> ChunkManifest<Hash> {
+    /// Returns the number of chunks announced by this manifest.
+    pub fn chunk_count(&self) -> u32 {
+        self.chunk_hashes.len() as u32
+    }
+}
+
+/// A chunked batch whose manifest has reached quorum, but whose chunks have not all been
+/// uploaded yet.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct PendingChunkedBatch<AccountId, Hash> {
+    /// The quorum that was reached on the manifest hash
+    pub quorum: DataQuorum<AccountId, Hash>,
+
+    /// The manifest that was agreed upon
+    pub manifest: ChunkManifest<Hash>,
+
+    /// The number of chunks uploaded so far
+    pub uploaded_chunks: u32,
+
+    /// The combined length of the chunks uploaded so far, in bytes
+    pub uploaded_len: u32,
+}
+
+/// The maximum number of hash-only quorums that can expire in the same block
+pub const MAX_PUBLICATIONS_PER_BLOCK: u32 = 1024;
+
+/// A hash-only submission that has reached quorum, but whose data has not been published yet.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct PendingPublication<AccountId, Hash, Deadline> {
+    /// The quorum that was reached on the combined hash of the data hash and block number
+    pub quorum: DataQuorum<AccountId, Hash>,
+
+    /// The agreed hash of the SCALE-encoded [`RowData`] that must be published
+    pub row_data_hash: Hash,
+
+    /// The source block number the data was submitted for
+    pub block_number: u64,
+
+    /// The block after which the quorum expires if the data has not been published
+    pub deadline: Deadline,
+}
+
 #[cfg(test)]
 mod tests {
     use alloc::collections::BTreeSet;
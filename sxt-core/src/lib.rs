@@ -21,6 +21,9 @@ pub mod native;
 
 pub mod attestation;
 
+/// Types shared by the pallets rewarding indexers and attestors
+pub mod rewards;
+
 /// Functionality relating to interacting with FlightSQL
 #[cfg(feature = "std")]
 pub mod sql;
@@ -8,7 +8,7 @@ use sp_core::RuntimeDebug;
 use sp_runtime_interface::pass_by::PassByCodec;
 
 use crate::indexing;
-use crate::tables::CreateStatement;
+use crate::tables::{CreateStatement, PrimaryKeys};
 
 /// Wrapper around [`CreateStatement`], needed to pass to pass the WASM boundary easily.
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, PassByCodec)]
@@ -17,6 +17,13 @@ pub struct CreateStatementPassBy {
     pub create_statement: CreateStatement,
 }
 
+/// Wrapper around [`PrimaryKeys`], needed to pass the WASM boundary easily.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, PassByCodec)]
+pub struct PrimaryKeysPassBy {
+    /// The uppercase names of the primary key columns, empty if the table has none.
+    pub primary_key: PrimaryKeys,
+}
+
 /// Wrapper around sxt_core::indexing::RowData, needed to pass the WASM boundary easily
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, PassByCodec)]
 pub struct RowData {
@@ -163,6 +170,10 @@ pub enum NativeCommitmentError {
     CommitmentSerialization,
     /// The table failed to serialize
     TableSerialization,
+    /// The table is missing a primary key column.
+    MissingPrimaryKeyColumn,
+    /// The table contains multiple rows with the same primary key.
+    DuplicatePrimaryKey,
 }
 
 impl From<OnChainTableToBytesError> for NativeCommitmentError {
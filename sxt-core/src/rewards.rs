@@ -0,0 +1,34 @@
+//! Types shared by the pallets that reward the work of indexers and attestors.
+
+use frame_support::weights::Weight;
+
+/// Receives the work done by indexers and attestors, so that it can be rewarded.
+///
+/// The `()` implementation ignores all work.
+pub trait WorkRewardHandler<AccountId> {
+    /// An indexer agreed in a quorum on data of `bytes` bytes that was committed to.
+    fn note_quorum_agreement(indexer: &AccountId, bytes: u64);
+
+    /// An attestor attested a block `blocks_late` blocks after it was produced.
+    fn note_attestation(attestor: &AccountId, blocks_late: u32);
+
+    /// The weight of one call to `note_quorum_agreement`
+    fn note_quorum_agreement_weight() -> Weight;
+
+    /// The weight of one call to `note_attestation`
+    fn note_attestation_weight() -> Weight;
+}
+
+impl<AccountId> WorkRewardHandler<AccountId> for () {
+    fn note_quorum_agreement(_indexer: &AccountId, _bytes: u64) {}
+
+    fn note_attestation(_attestor: &AccountId, _blocks_late: u32) {}
+
+    fn note_quorum_agreement_weight() -> Weight {
+        Weight::zero()
+    }
+
+    fn note_attestation_weight() -> Weight {
+        Weight::zero()
+    }
+}
@@ -17,14 +17,36 @@
 //! - [`NormalContract`]: A struct representing a standard (non-proxy) smart contract.
 //! - [`ProxyContract`]: A struct representing a proxy smart contract with an associated implementation contract.
 //! - [`ImplementationContract`]: A struct representing the implementation contract details used by a proxy contract.
+//! - [`ContractUpdate`]: A struct describing the events and implementation added to a registered contract.
+//! - [`ImplementationHistory`]: The implementations a proxy contract pointed to, with their starting blocks.
+//!
+//! ## DDL Generation
+//! - [`generate_event_tables`]: Generates the `CREATE TABLE` statements indexing the events of a contract from its ABI.
+//! - [`generate_namespace_ddl`]: Generates the `CREATE SCHEMA` statement of the namespace holding those tables.
+//! - [`validate_event_table_ddl`]: Checks that a supplied `CREATE TABLE` statement matches the generated one.
+
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::str::from_utf8;
 
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::storage::bounded_vec::BoundedVec;
 use frame_support::traits::ConstU32;
 use scale_info::TypeInfo;
+use serde::Deserialize;
+use snafu::Snafu;
 use sp_core::RuntimeDebug;
+use sqlparser::ast::DataType;
 
-use crate::tables::{CreateStatement, Source, TableName};
+use crate::tables::{
+    create_statement_to_sqlparser_remove_with,
+    CreateStatement,
+    Source,
+    TableIdentifier,
+    TableName,
+};
 use crate::{ByteString, IdentLength};
 
 /// A bounded vector representing a smart contract's unique address.
@@ -50,6 +72,39 @@ pub enum Contract {
     Proxy(ProxyContract),
 }
 
+impl Contract {
+    /// The details of the contract, or of the proxy for proxy contracts.
+    pub fn details(&self) -> &ContractDetails {
+        match self {
+            Contract::Normal(normal_contract) => &normal_contract.details,
+            Contract::Proxy(proxy_contract) => &proxy_contract.details,
+        }
+    }
+
+    /// Mutable access to the details of the contract, or of the proxy for proxy contracts.
+    pub fn details_mut(&mut self) -> &mut ContractDetails {
+        match self {
+            Contract::Normal(normal_contract) => &mut normal_contract.details,
+            Contract::Proxy(proxy_contract) => &mut proxy_contract.details,
+        }
+    }
+
+    /// The ABI declaring the events of the contract.
+    ///
+    /// A proxy emits the events of its implementation, so the implementation ABI is used when the
+    /// proxy has none.
+    pub fn event_abi(&self) -> Option<&ContractABI> {
+        match self {
+            Contract::Normal(normal_contract) => normal_contract.details.abi.as_ref(),
+            Contract::Proxy(proxy_contract) => proxy_contract
+                .details
+                .abi
+                .as_ref()
+                .or(proxy_contract.implementation.details.abi.as_ref()),
+        }
+    }
+}
+
 /// Represents a standard (non-proxy) smart contract.
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct NormalContract {
@@ -147,3 +202,655 @@ pub struct EventDetails {
 /// This list holds up to **100** event mappings for a single contract.
 /// A reasonable upper bound is set to prevent excessive storage usage.
 pub type EventDetailsList = BoundedVec<EventDetails, ConstU32<100>>;
+
+/// An update to a registered smart contract.
+///
+/// Updates only add to a contract: events are appended to its event details and its existing
+/// tables are kept.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ContractUpdate {
+    /// A new ABI for the contract, e.g. declaring the events of an upgraded implementation.
+    pub abi: Option<ContractABI>,
+
+    /// Event details to append to those of the contract.
+    pub event_details: Option<EventDetailsList>,
+
+    /// The implementation a proxy contract was upgraded to. Its starting block is required.
+    pub implementation: Option<ImplementationContract>,
+}
+
+/// An implementation a proxy contract pointed to, from its starting block.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ImplementationHistoryEntry {
+    /// The address of the implementation contract.
+    pub address: ContractAddress,
+
+    /// The block from which the proxy pointed to the implementation, if known.
+    pub starting_block: Option<u64>,
+}
+
+/// The implementations of a proxy contract, oldest first.
+///
+/// This list holds up to **100** implementations for a single proxy.
+pub type ImplementationHistory = BoundedVec<ImplementationHistoryEntry, ConstU32<100>>;
+
+/// The columns describing the block, transaction and log of an event, added to every generated
+/// event table ahead of the event parameters.
+pub const EVENT_META_COLUMNS: [(&str, &str); 5] = [
+    ("block_number", "BIGINT"),
+    ("transaction_hash", "BINARY"),
+    ("event_index", "INTEGER"),
+    ("time_stamp", "TIMESTAMP"),
+    ("contract_address", "BINARY"),
+];
+
+/// The primary key of every generated event table.
+const EVENT_PRIMARY_KEY: &str = "block_number, transaction_hash, event_index";
+
+/// Errors that can occur when generating DDL from a contract ABI.
+#[derive(Debug, Snafu, PartialEq, Eq)]
+pub enum AbiDdlError {
+    /// The ABI is not a valid JSON ABI.
+    #[snafu(display("the ABI is not a valid JSON ABI"))]
+    InvalidAbi,
+    /// A name is not valid utf8.
+    #[snafu(display("a name is not valid utf8"))]
+    InvalidName,
+    /// An event to index is not declared in the ABI.
+    #[snafu(display("event {event} is not declared in the ABI"))]
+    EventNotInAbi {
+        /// The name of the event.
+        event: String,
+    },
+    /// An event parameter has a type without a supported column type.
+    #[snafu(display(
+        "parameter {parameter} of event {event} has unsupported type {solidity_type}"
+    ))]
+    UnsupportedType {
+        /// The name of the event.
+        event: String,
+        /// The name of the parameter.
+        parameter: String,
+        /// The Solidity type of the parameter.
+        solidity_type: String,
+    },
+    /// Two columns of an event table have the same name.
+    #[snafu(display("event {event} has more than one column named {column}"))]
+    DuplicateColumn {
+        /// The name of the event.
+        event: String,
+        /// The name of the column.
+        column: String,
+    },
+    /// A generated statement exceeds the maximum size.
+    #[snafu(display("the statement generated for {name} exceeds the maximum size"))]
+    StatementTooLarge {
+        /// The name of the table or namespace.
+        name: String,
+    },
+    /// A supplied statement cannot be parsed.
+    #[snafu(display("the statement supplied for {table} cannot be parsed"))]
+    UnparsableDdl {
+        /// The name of the table.
+        table: String,
+    },
+    /// A supplied statement does not declare the columns generated from the ABI.
+    #[snafu(display("the statement supplied for {table} does not match the ABI"))]
+    DdlMismatch {
+        /// The name of the table.
+        table: String,
+    },
+}
+
+/// An entry of a JSON ABI. Only events are read.
+#[derive(Deserialize)]
+struct AbiItem {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    inputs: Vec<AbiParameter>,
+}
+
+/// A parameter of a JSON ABI entry.
+#[derive(Deserialize)]
+struct AbiParameter {
+    #[serde(default)]
+    name: String,
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    indexed: bool,
+}
+
+impl AbiItem {
+    /// The canonical signature of the entry, e.g. `Transfer(address,address,uint256)`.
+    fn canonical_signature(&self) -> String {
+        let types = self
+            .inputs
+            .iter()
+            .map(|input| input.kind.as_str())
+            .collect::<Vec<_>>();
+        format!("{}({})", self.name, types.join(","))
+    }
+}
+
+/// The canonical form of an event signature, keeping only the type of each parameter.
+///
+/// `Transfer(address indexed from, address indexed to, uint256 value)` becomes
+/// `Transfer(address,address,uint256)`.
+fn canonical_signature(signature: &str) -> String {
+    let signature: String = signature.split_whitespace().collect::<Vec<_>>().join(" ");
+    let Some((name, parameters)) = signature.split_once('(') else {
+        return signature;
+    };
+    let parameters = parameters.strip_suffix(')').unwrap_or(parameters);
+
+    let mut types = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in parameters.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                types.push(&parameters[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    types.push(&parameters[start..]);
+
+    let types = types
+        .into_iter()
+        .map(|parameter| parameter.split_whitespace().next().unwrap_or_default())
+        .filter(|parameter| !parameter.is_empty())
+        .collect::<Vec<_>>();
+    format!("{}({})", name.trim(), types.join(","))
+}
+
+/// The width in bits of a sized Solidity integer type, e.g. `256` for `uint` or `uint256`.
+fn integer_bits(bits: &str) -> Option<u16> {
+    if bits.is_empty() {
+        return Some(256);
+    }
+    let bits = bits.parse::<u16>().ok()?;
+    (bits % 8 == 0 && (8..=256).contains(&bits)).then_some(bits)
+}
+
+/// The column type storing a Solidity event parameter.
+///
+/// Indexed parameters of dynamic types are only logged as the keccak hash of their value, so
+/// they are stored as `BINARY`.
+fn solidity_type_to_column_type(solidity_type: &str, indexed: bool) -> Option<&'static str> {
+    let is_dynamic = solidity_type == "string"
+        || solidity_type == "bytes"
+        || solidity_type.ends_with(']')
+        || solidity_type.starts_with('(')
+        || solidity_type.starts_with("tuple");
+    if indexed && is_dynamic {
+        return Some("BINARY");
+    }
+
+    match solidity_type {
+        "bool" => Some("BOOLEAN"),
+        "address" | "bytes" => Some("BINARY"),
+        "string" => Some("VARCHAR"),
+        _ => {
+            if let Some(size) = solidity_type.strip_prefix("bytes") {
+                let size = size.parse::<u8>().ok()?;
+                (1..=32).contains(&size).then_some("BINARY")
+            } else if let Some(bits) = solidity_type.strip_prefix("uint") {
+                Some(match integer_bits(bits)? {
+                    8 => "TINYINT UNSIGNED",
+                    16 => "SMALLINT UNSIGNED",
+                    24 | 32 => "INT UNSIGNED",
+                    40..=64 => "BIGINT UNSIGNED",
+                    _ => "DECIMAL(75, 0)",
+                })
+            } else if let Some(bits) = solidity_type.strip_prefix("int") {
+                Some(match integer_bits(bits)? {
+                    8 => "TINYINT",
+                    16 => "SMALLINT",
+                    24 | 32 => "INT",
+                    40..=64 => "BIGINT",
+                    _ => "DECIMAL(75, 0)",
+                })
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// The `CREATE TABLE` statement indexing an event into a table.
+#[derive(Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct EventTableDdl {
+    /// The table the event is indexed into.
+    pub table: TableIdentifier,
+    /// The statement creating the table.
+    pub create_statement: CreateStatement,
+}
+
+/// Generate the `CREATE TABLE` statements indexing the events of a contract, from its JSON ABI.
+///
+/// Each table starts with the [`EVENT_META_COLUMNS`], followed by one column per event parameter
+/// named after the lowercase parameter name. Solidity types are mapped to column types as
+/// follows..
+/// - `uint8` up to `uint64` are `TINYINT UNSIGNED`/`SMALLINT UNSIGNED`/`INT UNSIGNED`/
+///   `BIGINT UNSIGNED` and wider unsigned integers are `DECIMAL(75, 0)`
+/// - `int8` up to `int64` are `TINYINT`/`SMALLINT`/`INT`/`BIGINT` and wider signed integers are
+///   `DECIMAL(75, 0)`
+/// - `address`, `bytes` and `bytes1` to `bytes32` are `BINARY`, the `VARBINARY` column type
+/// - `string` is `VARCHAR` and `bool` is `BOOLEAN`
+///
+/// Events are matched by name, or by their canonical signature if the ABI overloads the name.
+/// The output is deterministic: the same ABI and events always produce the same statements.
+pub fn generate_event_tables(
+    abi: &[u8],
+    schema: &str,
+    event_details: &[EventDetails],
+) -> Result<Vec<EventTableDdl>, AbiDdlError> {
+    let abi: Vec<AbiItem> = serde_json::from_slice(abi).map_err(|_| AbiDdlError::InvalidAbi)?;
+    let abi_events = abi
+        .iter()
+        .filter(|item| item.kind == "event")
+        .collect::<Vec<_>>();
+
+    event_details
+        .iter()
+        .map(|details| {
+            let event = from_utf8(&details.name).map_err(|_| AbiDdlError::InvalidName)?;
+            let signature = from_utf8(&details.signature).map_err(|_| AbiDdlError::InvalidName)?;
+            let table = from_utf8(&details.table).map_err(|_| AbiDdlError::InvalidName)?;
+
+            let candidates = abi_events
+                .iter()
+                .filter(|item| item.name == event)
+                .collect::<Vec<_>>();
+            let abi_event = match candidates.as_slice() {
+                [abi_event] => Some(*abi_event),
+                _ => {
+                    let signature = canonical_signature(signature);
+                    candidates
+                        .into_iter()
+                        .find(|item| item.canonical_signature() == signature)
+                }
+            }
+            .ok_or_else(|| AbiDdlError::EventNotInAbi {
+                event: event.to_string(),
+            })?;
+
+            let mut columns = EVENT_META_COLUMNS
+                .iter()
+                .map(|(name, column_type)| (name.to_string(), *column_type))
+                .collect::<Vec<_>>();
+            for (index, input) in abi_event.inputs.iter().enumerate() {
+                let column_type = solidity_type_to_column_type(&input.kind, input.indexed)
+                    .ok_or_else(|| AbiDdlError::UnsupportedType {
+                        event: event.to_string(),
+                        parameter: input.name.clone(),
+                        solidity_type: input.kind.clone(),
+                    })?;
+                let name = match input.name.as_str() {
+                    "" => format!("param{index}"),
+                    name => name.to_lowercase(),
+                };
+                if columns.iter().any(|(column, _)| *column == name) {
+                    return Err(AbiDdlError::DuplicateColumn {
+                        event: event.to_string(),
+                        column: name,
+                    });
+                }
+                columns.push((name, column_type));
+            }
+
+            let table = TableIdentifier {
+                name: TableName::try_from(table.to_uppercase().into_bytes())
+                    .map_err(|_| AbiDdlError::InvalidName)?,
+                namespace: ByteString::try_from(schema.to_uppercase().into_bytes())
+                    .map_err(|_| AbiDdlError::InvalidName)?,
+            };
+            let qualified_name =
+                String::try_from(&table).map_err(|_| AbiDdlError::InvalidName)?;
+            let columns = columns
+                .into_iter()
+                .map(|(name, column_type)| format!("{name} {column_type} NOT NULL"))
+                .collect::<Vec<_>>();
+            let statement = format!(
+                "CREATE TABLE IF NOT EXISTS {qualified_name} ({}, PRIMARY KEY ({EVENT_PRIMARY_KEY}))",
+                columns.join(", ")
+            );
+            let create_statement = CreateStatement::try_from(statement.into_bytes())
+                .map_err(|_| AbiDdlError::StatementTooLarge {
+                    name: qualified_name,
+                })?;
+
+            Ok(EventTableDdl {
+                table,
+                create_statement,
+            })
+        })
+        .collect()
+}
+
+/// Generate the `CREATE SCHEMA` statement of the namespace holding the event tables.
+pub fn generate_namespace_ddl(schema: &str) -> Result<CreateStatement, AbiDdlError> {
+    let schema = schema.to_uppercase();
+    CreateStatement::try_from(format!("CREATE SCHEMA IF NOT EXISTS {schema}").into_bytes())
+        .map_err(|_| AbiDdlError::StatementTooLarge { name: schema })
+}
+
+/// Normalize equivalent spellings of a data type.
+fn normalized_data_type(data_type: DataType) -> DataType {
+    match data_type {
+        DataType::Integer(length) => DataType::Int(length),
+        DataType::UnsignedInt2(length) => DataType::UnsignedSmallInt(length),
+        DataType::UnsignedInteger(length) | DataType::UnsignedInt4(length) => {
+            DataType::UnsignedInt(length)
+        }
+        DataType::UnsignedInt8(length) => DataType::UnsignedBigInt(length),
+        data_type => data_type,
+    }
+}
+
+/// Check that a supplied `CREATE TABLE` statement declares the same columns, with the same
+/// types, as the statement generated from the ABI.
+///
+/// Column order, constraints and `WITH` options are not compared.
+pub fn validate_event_table_ddl(
+    generated: &EventTableDdl,
+    supplied: &CreateStatement,
+) -> Result<(), AbiDdlError> {
+    let table = String::try_from(&generated.table).map_err(|_| AbiDdlError::InvalidName)?;
+    let columns = |statement: &CreateStatement| {
+        create_statement_to_sqlparser_remove_with(statement.clone())
+            .map(|(create_table, _)| {
+                create_table
+                    .columns
+                    .into_iter()
+                    .map(|column| {
+                        (
+                            column.name.value.to_uppercase(),
+                            normalized_data_type(column.data_type),
+                        )
+                    })
+                    .collect::<BTreeMap<_, _>>()
+            })
+            .map_err(|_| AbiDdlError::UnparsableDdl {
+                table: table.clone(),
+            })
+    };
+
+    if columns(&generated.create_statement)? == columns(supplied)? {
+        Ok(())
+    } else {
+        Err(AbiDdlError::DdlMismatch { table })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+
+    const ERC20_ABI: &str = r#"[
+        {"type": "function", "name": "transfer", "inputs": [{"name": "to", "type": "address"}]},
+        {"type": "event", "name": "Transfer", "anonymous": false, "inputs": [
+            {"name": "from", "type": "address", "indexed": true},
+            {"name": "to", "type": "address", "indexed": true},
+            {"name": "value", "type": "uint256", "indexed": false}
+        ]},
+        {"type": "event", "name": "Memo", "inputs": [
+            {"name": "topic", "type": "string", "indexed": true},
+            {"name": "text", "type": "string", "indexed": false},
+            {"name": "hash", "type": "bytes32", "indexed": false},
+            {"name": "decimals", "type": "uint8", "indexed": false},
+            {"name": "delta", "type": "int64", "indexed": false},
+            {"name": "", "type": "bool", "indexed": false}
+        ]},
+        {"type": "event", "name": "Memo", "inputs": [
+            {"name": "text", "type": "string", "indexed": false}
+        ]}
+    ]"#;
+
+    fn event_details(name: &str, signature: &str, table: &str) -> EventDetails {
+        EventDetails {
+            name: EventName::try_from(name.as_bytes().to_vec()).unwrap(),
+            signature: EventSignature::try_from(signature.as_bytes().to_vec()).unwrap(),
+            table: TableName::try_from(table.as_bytes().to_vec()).unwrap(),
+        }
+    }
+
+    fn statement(statement: &str) -> CreateStatement {
+        CreateStatement::try_from(statement.as_bytes().to_vec()).unwrap()
+    }
+
+    #[test]
+    fn we_can_generate_event_tables_from_an_abi() {
+        let tables = generate_event_tables(
+            ERC20_ABI.as_bytes(),
+            "erc20",
+            &[
+                event_details("Transfer", "Transfer(address,address,uint256)", "transfers"),
+                event_details(
+                    "Memo",
+                    "Memo(string indexed topic, string text, bytes32 hash, uint8 decimals, int64 delta, bool)",
+                    "memos",
+                ),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            tables,
+            vec![
+                EventTableDdl {
+                    table: TableIdentifier::from_str_unchecked("TRANSFERS", "ERC20"),
+                    create_statement: statement(
+                        "CREATE TABLE IF NOT EXISTS ERC20.TRANSFERS (block_number BIGINT NOT NULL, \
+                         transaction_hash BINARY NOT NULL, event_index INTEGER NOT NULL, \
+                         time_stamp TIMESTAMP NOT NULL, contract_address BINARY NOT NULL, \
+                         from BINARY NOT NULL, to BINARY NOT NULL, value DECIMAL(75, 0) NOT NULL, \
+                         PRIMARY KEY (block_number, transaction_hash, event_index))"
+                    ),
+                },
+                EventTableDdl {
+                    table: TableIdentifier::from_str_unchecked("MEMOS", "ERC20"),
+                    create_statement: statement(
+                        "CREATE TABLE IF NOT EXISTS ERC20.MEMOS (block_number BIGINT NOT NULL, \
+                         transaction_hash BINARY NOT NULL, event_index INTEGER NOT NULL, \
+                         time_stamp TIMESTAMP NOT NULL, contract_address BINARY NOT NULL, \
+                         topic BINARY NOT NULL, text VARCHAR NOT NULL, hash BINARY NOT NULL, \
+                         decimals TINYINT UNSIGNED NOT NULL, delta BIGINT NOT NULL, param5 BOOLEAN NOT NULL, \
+                         PRIMARY KEY (block_number, transaction_hash, event_index))"
+                    ),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn we_cannot_generate_event_tables_from_invalid_abis() {
+        let transfer = event_details("Transfer", "Transfer(address,address,uint256)", "t");
+
+        assert_eq!(
+            generate_event_tables(&[0xBB; 16], "s", &[transfer.clone()]),
+            Err(AbiDdlError::InvalidAbi)
+        );
+        assert_eq!(
+            generate_event_tables(
+                ERC20_ABI.as_bytes(),
+                "s",
+                &[event_details("Approval", "Approval()", "t")]
+            ),
+            Err(AbiDdlError::EventNotInAbi {
+                event: "Approval".into()
+            })
+        );
+        // Overloaded events must match a signature
+        assert_eq!(
+            generate_event_tables(
+                ERC20_ABI.as_bytes(),
+                "s",
+                &[event_details("Memo", "Memo(bytes)", "t")]
+            ),
+            Err(AbiDdlError::EventNotInAbi {
+                event: "Memo".into()
+            })
+        );
+
+        let abi = r#"[{"type": "event", "name": "Batch", "inputs": [
+            {"name": "ids", "type": "uint256[]", "indexed": false}
+        ]}]"#;
+        assert_eq!(
+            generate_event_tables(
+                abi.as_bytes(),
+                "s",
+                &[event_details("Batch", "Batch(uint256[])", "t")]
+            ),
+            Err(AbiDdlError::UnsupportedType {
+                event: "Batch".into(),
+                parameter: "ids".into(),
+                solidity_type: "uint256[]".into(),
+            })
+        );
+
+        let abi = r#"[{"type": "event", "name": "Block", "inputs": [
+            {"name": "block_number", "type": "uint64", "indexed": false}
+        ]}]"#;
+        assert_eq!(
+            generate_event_tables(
+                abi.as_bytes(),
+                "s",
+                &[event_details("Block", "Block(uint64)", "t")]
+            ),
+            Err(AbiDdlError::DuplicateColumn {
+                event: "Block".into(),
+                column: "block_number".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn we_can_canonicalize_event_signatures() {
+        assert_eq!(
+            canonical_signature(
+                "Transfer(address indexed from, address indexed to, uint256 value)"
+            ),
+            "Transfer(address,address,uint256)"
+        );
+        assert_eq!(
+            canonical_signature("Order((uint256,address) order, bytes32[] ids)"),
+            "Order((uint256,address),bytes32[])"
+        );
+        assert_eq!(canonical_signature("Ping()"), "Ping()");
+    }
+
+    #[test]
+    fn we_can_validate_supplied_ddl_against_the_abi() {
+        let generated = generate_event_tables(
+            ERC20_ABI.as_bytes(),
+            "erc20",
+            &[event_details(
+                "Transfer",
+                "Transfer(address,address,uint256)",
+                "transfers",
+            )],
+        )
+        .unwrap()
+        .remove(0);
+
+        // Order, case, constraints and WITH options don't matter
+        let supplied = statement(
+            "CREATE TABLE erc20.transfers (from binary, to binary, value decimal(75,0), \
+             block_number bigint not null, transaction_hash binary not null, event_index int not null, \
+             time_stamp timestamp not null, contract_address binary not null) WITH (TABLE_UUID=abc)",
+        );
+        assert_eq!(validate_event_table_ddl(&generated, &supplied), Ok(()));
+
+        let supplied = statement(
+            "CREATE TABLE erc20.transfers (from binary, to binary, value bigint, \
+             block_number bigint, transaction_hash binary, event_index int, \
+             time_stamp timestamp, contract_address binary)",
+        );
+        assert_eq!(
+            validate_event_table_ddl(&generated, &supplied),
+            Err(AbiDdlError::DdlMismatch {
+                table: "ERC20.TRANSFERS".into()
+            })
+        );
+
+        assert_eq!(
+            validate_event_table_ddl(&generated, &statement("not sql")),
+            Err(AbiDdlError::UnparsableDdl {
+                table: "ERC20.TRANSFERS".into()
+            })
+        );
+    }
+
+    #[test]
+    fn unsigned_integers_are_indexed_into_unsigned_columns() {
+        let abi = r#"[{"type": "event", "name": "Sizes", "inputs": [
+            {"name": "small", "type": "uint16", "indexed": false},
+            {"name": "medium", "type": "uint32", "indexed": false},
+            {"name": "large", "type": "uint64", "indexed": false},
+            {"name": "huge", "type": "uint128", "indexed": false}
+        ]}]"#;
+        let generated = generate_event_tables(
+            abi.as_bytes(),
+            "s",
+            &[event_details(
+                "Sizes",
+                "Sizes(uint16,uint32,uint64,uint128)",
+                "t",
+            )],
+        )
+        .unwrap()
+        .remove(0);
+
+        assert_eq!(
+            generated.create_statement,
+            statement(
+                "CREATE TABLE IF NOT EXISTS S.T (block_number BIGINT NOT NULL, \
+                 transaction_hash BINARY NOT NULL, event_index INTEGER NOT NULL, \
+                 time_stamp TIMESTAMP NOT NULL, contract_address BINARY NOT NULL, \
+                 small SMALLINT UNSIGNED NOT NULL, medium INT UNSIGNED NOT NULL, \
+                 large BIGINT UNSIGNED NOT NULL, huge DECIMAL(75, 0) NOT NULL, \
+                 PRIMARY KEY (block_number, transaction_hash, event_index))"
+            )
+        );
+
+        // Equivalent spellings of the unsigned types are accepted
+        let supplied = statement(
+            "CREATE TABLE s.t (block_number bigint, transaction_hash binary, event_index int, \
+             time_stamp timestamp, contract_address binary, small int2 unsigned, \
+             medium integer unsigned, large int8 unsigned, huge decimal(75, 0))",
+        );
+        assert_eq!(validate_event_table_ddl(&generated, &supplied), Ok(()));
+
+        // but signed types are not
+        let supplied = statement(
+            "CREATE TABLE s.t (block_number bigint, transaction_hash binary, event_index int, \
+             time_stamp timestamp, contract_address binary, small int, medium bigint, \
+             large decimal(20, 0), huge decimal(75, 0))",
+        );
+        assert_eq!(
+            validate_event_table_ddl(&generated, &supplied),
+            Err(AbiDdlError::DdlMismatch {
+                table: "S.T".into()
+            })
+        );
+    }
+
+    #[test]
+    fn we_can_generate_namespace_ddl() {
+        assert_eq!(
+            generate_namespace_ddl("erc20"),
+            Ok(statement("CREATE SCHEMA IF NOT EXISTS ERC20"))
+        );
+    }
+}
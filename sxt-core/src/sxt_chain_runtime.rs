@@ -37,7 +37,7 @@ pub mod api {
         "SystemContracts",
         "Rewards",
     ];
-    pub static RUNTIME_APIS: [&str; 16usize] = [
+    pub static RUNTIME_APIS: [&str; 19usize] = [
         "Core",
         "Metadata",
         "BlockBuilder",
@@ -54,6 +54,9 @@ pub mod api {
         "GenesisBuilder",
         "AuthorityDiscoveryApi",
         "CommitmentsApi",
+        "StakingMirrorApi",
+        "RewardsApi",
+        "WorkRewardsApi",
     ];
     #[doc = r" The error type that is returned when there is a runtime issue."]
     pub type DispatchError = runtime_types::sp_runtime::DispatchError;
@@ -137,6 +140,15 @@ pub mod api {
             pub fn commitments_api(&self) -> commitments_api::CommitmentsApi {
                 commitments_api::CommitmentsApi
             }
+            pub fn staking_mirror_api(&self) -> staking_mirror_api::StakingMirrorApi {
+                staking_mirror_api::StakingMirrorApi
+            }
+            pub fn rewards_api(&self) -> rewards_api::RewardsApi {
+                rewards_api::RewardsApi
+            }
+            pub fn work_rewards_api(&self) -> work_rewards_api::WorkRewardsApi {
+                work_rewards_api::WorkRewardsApi
+            }
         }
         pub mod core {
             use super::{root_mod, runtime_types};
@@ -2376,6 +2388,222 @@ pub mod api {
                 }
             }
         }
+        pub mod staking_mirror_api {
+            use super::{root_mod, runtime_types};
+            #[doc = " Runtime APIs for reading the staking state mirrored from the EVM staking contract."]
+            pub struct StakingMirrorApi;
+            impl StakingMirrorApi {
+                #[doc = " Returns the mirrored state of the bonded EVM stakers, in storage order, starting"]
+                #[doc = " after the staker with the address `start_after`."]
+                #[doc = ""]
+                #[doc = " At most `limit` stakers are returned, capped at [`MAX_MIRRORED_STAKERS_PAGE`]. Fewer"]
+                #[doc = " stakers are returned on the last page."]
+                pub fn mirrored_stakers(
+                    &self,
+                    start_after: types::mirrored_stakers::StartAfter,
+                    limit: types::mirrored_stakers::Limit,
+                ) -> ::subxt::ext::subxt_core::runtime_api::payload::StaticPayload<
+                    types::MirroredStakers,
+                    types::mirrored_stakers::output::Output,
+                > {
+                    ::subxt::ext::subxt_core::runtime_api::payload::StaticPayload::new(
+                        "StakingMirrorApi",
+                        "mirrored_stakers",
+                        types::MirroredStakers { start_after, limit },
+                    )
+                }
+                #[doc = " Returns the mirrored state of the EVM staker with the given address."]
+                #[doc = ""]
+                #[doc = " Returns `None` if the staker isn't bonded."]
+                pub fn mirrored_staker(
+                    &self,
+                    address: types::mirrored_staker::Address,
+                ) -> ::subxt::ext::subxt_core::runtime_api::payload::StaticPayload<
+                    types::MirroredStaker,
+                    types::mirrored_staker::output::Output,
+                > {
+                    ::subxt::ext::subxt_core::runtime_api::payload::StaticPayload::new(
+                        "StakingMirrorApi",
+                        "mirrored_staker",
+                        types::MirroredStaker { address },
+                    )
+                }
+            }
+            pub mod types {
+                use super::runtime_types;
+                pub mod mirrored_stakers {
+                    use super::runtime_types;
+                    pub type StartAfter = ::core::option::Option<[::core::primitive::u8; 20usize]>;
+                    pub type Limit = ::core::primitive::u32;
+                    pub mod output {
+                        use super::runtime_types;
+                        pub type Output = ::subxt::ext::subxt_core::alloc::vec::Vec<
+                            runtime_types::pallet_system_tables::runtime_api::MirroredStaker<
+                                ::subxt::ext::subxt_core::utils::AccountId32,
+                            >,
+                        >;
+                    }
+                }
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
+                pub struct MirroredStakers {
+                    pub start_after: mirrored_stakers::StartAfter,
+                    pub limit: mirrored_stakers::Limit,
+                }
+                pub mod mirrored_staker {
+                    use super::runtime_types;
+                    pub type Address = [::core::primitive::u8; 20usize];
+                    pub mod output {
+                        use super::runtime_types;
+                        pub type Output = ::core::option::Option<
+                            runtime_types::pallet_system_tables::runtime_api::MirroredStaker<
+                                ::subxt::ext::subxt_core::utils::AccountId32,
+                            >,
+                        >;
+                    }
+                }
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
+                pub struct MirroredStaker {
+                    pub address: mirrored_staker::Address,
+                }
+            }
+        }
+        pub mod rewards_api {
+            use super::{root_mod, runtime_types};
+            #[doc = " Runtime APIs for reading the validator rewards that are not paid out yet."]
+            pub struct RewardsApi;
+            impl RewardsApi {
+                #[doc = " Returns the pages that are not paid out yet, for every validator with reward points"]
+                #[doc = " in the eras from the next era to pay up to the active era."]
+                pub fn pending_payouts(
+                    &self,
+                ) -> ::subxt::ext::subxt_core::runtime_api::payload::StaticPayload<
+                    types::PendingPayouts,
+                    types::pending_payouts::output::Output,
+                > {
+                    ::subxt::ext::subxt_core::runtime_api::payload::StaticPayload::new(
+                        "RewardsApi",
+                        "pending_payouts",
+                        types::PendingPayouts {},
+                    )
+                }
+            }
+            pub mod types {
+                use super::runtime_types;
+                pub mod pending_payouts {
+                    use super::runtime_types;
+                    pub mod output {
+                        use super::runtime_types;
+                        pub type Output = ::subxt::ext::subxt_core::alloc::vec::Vec<
+                            runtime_types::pallet_rewards::runtime_api::PendingPayout<
+                                ::subxt::ext::subxt_core::utils::AccountId32,
+                            >,
+                        >;
+                    }
+                }
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
+                pub struct PendingPayouts {}
+            }
+        }
+        pub mod work_rewards_api {
+            use super::{root_mod, runtime_types};
+            #[doc = " Runtime APIs for reading the expected earnings of indexers and attestors."]
+            pub struct WorkRewardsApi;
+            impl WorkRewardsApi {
+                #[doc = " Returns the expected earnings of an indexer or attestor, for each era up to the active"]
+                #[doc = " era that it earned points in and was not paid for yet."]
+                #[doc = ""]
+                #[doc = " Eras whose payouts have not started are expected to be paid what the pot could pay for"]
+                #[doc = " one era now."]
+                pub fn expected_work_earnings(
+                    &self,
+                    worker: types::expected_work_earnings::Worker,
+                ) -> ::subxt::ext::subxt_core::runtime_api::payload::StaticPayload<
+                    types::ExpectedWorkEarnings,
+                    types::expected_work_earnings::output::Output,
+                > {
+                    ::subxt::ext::subxt_core::runtime_api::payload::StaticPayload::new(
+                        "WorkRewardsApi",
+                        "expected_work_earnings",
+                        types::ExpectedWorkEarnings { worker },
+                    )
+                }
+            }
+            pub mod types {
+                use super::runtime_types;
+                pub mod expected_work_earnings {
+                    use super::runtime_types;
+                    pub type Worker = ::subxt::ext::subxt_core::utils::AccountId32;
+                    pub mod output {
+                        use super::runtime_types;
+                        pub type Output = ::subxt::ext::subxt_core::alloc::vec::Vec<
+                            runtime_types::pallet_rewards::runtime_api::ExpectedWorkEarnings<
+                                ::core::primitive::u128,
+                            >,
+                        >;
+                    }
+                }
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
+                pub struct ExpectedWorkEarnings {
+                    pub worker: expected_work_earnings::Worker,
+                }
+            }
+        }
     }
     pub fn view_functions() -> ViewFunctionsApi {
         ViewFunctionsApi
@@ -2428,6 +2656,12 @@ pub mod api {
         pub fn statement(&self) -> statement::constants::ConstantsApi {
             statement::constants::ConstantsApi
         }
+        pub fn indexing(&self) -> indexing::constants::ConstantsApi {
+            indexing::constants::ConstantsApi
+        }
+        pub fn rewards(&self) -> rewards::constants::ConstantsApi {
+            rewards::constants::ConstantsApi
+        }
     }
     pub struct StorageApi;
     impl StorageApi {
@@ -17687,6 +17921,144 @@ pub mod api {
                     const PALLET: &'static str = "Tables";
                     const CALL: &'static str = "drop_invalid_commits";
                 }
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
+                #[doc = "Update the UUID for the specificed namespace and version to the provided UUID"]
+                pub struct UpdateNamespaceUuid {
+                    pub namespace: update_namespace_uuid::Namespace,
+                    pub version: update_namespace_uuid::Version,
+                    pub new_uuid: update_namespace_uuid::NewUuid,
+                }
+                pub mod update_namespace_uuid {
+                    use super::runtime_types;
+                    pub type Namespace =
+                        runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            ::core::primitive::u8,
+                        >;
+                    pub type Version = ::core::primitive::u16;
+                    pub type NewUuid = runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                        ::core::primitive::u8,
+                    >;
+                }
+                impl ::subxt::ext::subxt_core::blocks::StaticExtrinsic for UpdateNamespaceUuid {
+                    const PALLET: &'static str = "Tables";
+                    const CALL: &'static str = "update_namespace_uuid";
+                }
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
+                #[doc = "Update the UUID for the specified table and version to the provided UUID"]
+                pub struct UpdateTableUuid {
+                    pub table: update_table_uuid::Table,
+                    pub version: update_table_uuid::Version,
+                    pub new_uuid: update_table_uuid::NewUuid,
+                }
+                pub mod update_table_uuid {
+                    use super::runtime_types;
+                    pub type Table = runtime_types::sxt_core::tables::TableIdentifier;
+                    pub type Version = ::core::primitive::u16;
+                    pub type NewUuid = runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                        ::core::primitive::u8,
+                    >;
+                }
+                impl ::subxt::ext::subxt_core::blocks::StaticExtrinsic for UpdateTableUuid {
+                    const PALLET: &'static str = "Tables";
+                    const CALL: &'static str = "update_table_uuid";
+                }
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
+                #[doc = "Override the insert quorum of an existing table, which otherwise defaults to the"]
+                #[doc = "quorum of its table type"]
+                pub struct SetTableInsertQuorum {
+                    pub table: set_table_insert_quorum::Table,
+                    pub quorum: set_table_insert_quorum::Quorum,
+                }
+                pub mod set_table_insert_quorum {
+                    use super::runtime_types;
+                    pub type Table = runtime_types::sxt_core::tables::TableIdentifier;
+                    pub type Quorum = runtime_types::sxt_core::tables::InsertQuorumSize;
+                }
+                impl ::subxt::ext::subxt_core::blocks::StaticExtrinsic for SetTableInsertQuorum {
+                    const PALLET: &'static str = "Tables";
+                    const CALL: &'static str = "set_table_insert_quorum";
+                }
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
+                #[doc = "Index the primary keys of rows that were not inserted through the indexing pallet."]
+                #[doc = ""]
+                #[doc = "Inserted rows are indexed as they are committed to, but snapshot rows never pass"]
+                #[doc = "through the chain, and rows of tables created before keys were indexed were never"]
+                #[doc = "indexed. Their keys are supplied here along with their row numbers, so that later"]
+                #[doc = "inserts are checked against them. Large tables are indexed over several calls."]
+                pub struct IndexSnapshotPrimaryKeys {
+                    pub table: index_snapshot_primary_keys::Table,
+                    pub keys: index_snapshot_primary_keys::Keys,
+                }
+                pub mod index_snapshot_primary_keys {
+                    use super::runtime_types;
+                    pub type Table = runtime_types::sxt_core::tables::TableIdentifier;
+                    pub type Keys = runtime_types::bounded_collections::bounded_vec::BoundedVec<(
+                        runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            ::core::primitive::u8,
+                        >,
+                        ::core::primitive::u64,
+                    )>;
+                }
+                impl ::subxt::ext::subxt_core::blocks::StaticExtrinsic for IndexSnapshotPrimaryKeys {
+                    const PALLET: &'static str = "Tables";
+                    const CALL: &'static str = "index_snapshot_primary_keys";
+                }
             }
             pub struct TransactionApi;
             impl TransactionApi {
@@ -17814,21 +18186,90 @@ pub mod api {
                         ],
                     )
                 }
-            }
-        }
-        #[doc = "The `Event` enum of this pallet"]
-        pub type Event = runtime_types::pallet_tables::pallet::Event;
-        pub mod events {
-            use super::runtime_types;
-            #[derive(
-                :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
-                :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
-                :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
-                :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
-                Debug,
-            )]
-            # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
-            #[codec(dumb_trait_bound)]
+                #[doc = "Update the UUID for the specificed namespace and version to the provided UUID"]
+                pub fn update_namespace_uuid(
+                    &self,
+                    namespace: types::update_namespace_uuid::Namespace,
+                    version: types::update_namespace_uuid::Version,
+                    new_uuid: types::update_namespace_uuid::NewUuid,
+                ) -> ::subxt::ext::subxt_core::tx::payload::StaticPayload<types::UpdateNamespaceUuid>
+                {
+                    ::subxt::ext::subxt_core::tx::payload::StaticPayload::new(
+                        "Tables",
+                        "update_namespace_uuid",
+                        types::UpdateNamespaceUuid {
+                            namespace,
+                            version,
+                            new_uuid,
+                        },
+                    )
+                }
+                #[doc = "Update the UUID for the specified table and version to the provided UUID"]
+                pub fn update_table_uuid(
+                    &self,
+                    table: types::update_table_uuid::Table,
+                    version: types::update_table_uuid::Version,
+                    new_uuid: types::update_table_uuid::NewUuid,
+                ) -> ::subxt::ext::subxt_core::tx::payload::StaticPayload<types::UpdateTableUuid>
+                {
+                    ::subxt::ext::subxt_core::tx::payload::StaticPayload::new(
+                        "Tables",
+                        "update_table_uuid",
+                        types::UpdateTableUuid {
+                            table,
+                            version,
+                            new_uuid,
+                        },
+                    )
+                }
+                #[doc = "Override the insert quorum of an existing table, which otherwise defaults to the"]
+                #[doc = "quorum of its table type"]
+                pub fn set_table_insert_quorum(
+                    &self,
+                    table: types::set_table_insert_quorum::Table,
+                    quorum: types::set_table_insert_quorum::Quorum,
+                ) -> ::subxt::ext::subxt_core::tx::payload::StaticPayload<types::SetTableInsertQuorum>
+                {
+                    ::subxt::ext::subxt_core::tx::payload::StaticPayload::new(
+                        "Tables",
+                        "set_table_insert_quorum",
+                        types::SetTableInsertQuorum { table, quorum },
+                    )
+                }
+                #[doc = "Index the primary keys of rows that were not inserted through the indexing pallet."]
+                #[doc = ""]
+                #[doc = "Inserted rows are indexed as they are committed to, but snapshot rows never pass"]
+                #[doc = "through the chain, and rows of tables created before keys were indexed were never"]
+                #[doc = "indexed. Their keys are supplied here along with their row numbers, so that later"]
+                #[doc = "inserts are checked against them. Large tables are indexed over several calls."]
+                pub fn index_snapshot_primary_keys(
+                    &self,
+                    table: types::index_snapshot_primary_keys::Table,
+                    keys: types::index_snapshot_primary_keys::Keys,
+                ) -> ::subxt::ext::subxt_core::tx::payload::StaticPayload<
+                    types::IndexSnapshotPrimaryKeys,
+                > {
+                    ::subxt::ext::subxt_core::tx::payload::StaticPayload::new(
+                        "Tables",
+                        "index_snapshot_primary_keys",
+                        types::IndexSnapshotPrimaryKeys { table, keys },
+                    )
+                }
+            }
+        }
+        #[doc = "The `Event` enum of this pallet"]
+        pub type Event = runtime_types::pallet_tables::pallet::Event;
+        pub mod events {
+            use super::runtime_types;
+            #[derive(
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                Debug,
+            )]
+            # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+            #[codec(dumb_trait_bound)]
             #[decode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode")]
             #[encode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode")]
             #[doc = "The namespace for a schema has been created"]
@@ -17867,6 +18308,74 @@ pub mod api {
             #[codec(dumb_trait_bound)]
             #[decode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode")]
             #[encode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode")]
+            #[doc = "The UUID for a given namespace has been updated"]
+            pub struct NamespaceUuidUpdated {
+                pub old_uuid: namespace_uuid_updated::OldUuid,
+                pub new_uuid: namespace_uuid_updated::NewUuid,
+                pub version: namespace_uuid_updated::Version,
+                pub namespace: namespace_uuid_updated::Namespace,
+            }
+            pub mod namespace_uuid_updated {
+                use super::runtime_types;
+                pub type OldUuid = runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                    ::core::primitive::u8,
+                >;
+                pub type NewUuid = runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                    ::core::primitive::u8,
+                >;
+                pub type Version = ::core::primitive::u16;
+                pub type Namespace = runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                    ::core::primitive::u8,
+                >;
+            }
+            impl ::subxt::ext::subxt_core::events::StaticEvent for NamespaceUuidUpdated {
+                const PALLET: &'static str = "Tables";
+                const EVENT: &'static str = "NamespaceUuidUpdated";
+            }
+            #[derive(
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                Debug,
+            )]
+            # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+            #[codec(dumb_trait_bound)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode")]
+            #[doc = "The UUID for a given table has been updated"]
+            pub struct TableUuidUpdated {
+                pub old_uuid: table_uuid_updated::OldUuid,
+                pub new_uuid: table_uuid_updated::NewUuid,
+                pub version: table_uuid_updated::Version,
+                pub table: table_uuid_updated::Table,
+            }
+            pub mod table_uuid_updated {
+                use super::runtime_types;
+                pub type OldUuid = runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                    ::core::primitive::u8,
+                >;
+                pub type NewUuid = runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                    ::core::primitive::u8,
+                >;
+                pub type Version = ::core::primitive::u16;
+                pub type Table = runtime_types::sxt_core::tables::TableIdentifier;
+            }
+            impl ::subxt::ext::subxt_core::events::StaticEvent for TableUuidUpdated {
+                const PALLET: &'static str = "Tables";
+                const EVENT: &'static str = "TableUuidUpdated";
+            }
+            #[derive(
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                Debug,
+            )]
+            # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+            #[codec(dumb_trait_bound)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode")]
             #[doc = "The schema for a table has been updated"]
             pub struct SchemaUpdated(pub schema_updated::Field0, pub schema_updated::Field1);
             pub mod schema_updated {
@@ -17938,6 +18447,56 @@ pub mod api {
                 const PALLET: &'static str = "Tables";
                 const EVENT: &'static str = "TableDropped";
             }
+            #[derive(
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                Debug,
+            )]
+            # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+            #[codec(dumb_trait_bound)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode")]
+            #[doc = "The insert quorum for a table has been updated"]
+            pub struct TableInsertQuorumUpdated {
+                pub table: table_insert_quorum_updated::Table,
+                pub quorum: table_insert_quorum_updated::Quorum,
+            }
+            pub mod table_insert_quorum_updated {
+                use super::runtime_types;
+                pub type Table = runtime_types::sxt_core::tables::TableIdentifier;
+                pub type Quorum = runtime_types::sxt_core::tables::InsertQuorumSize;
+            }
+            impl ::subxt::ext::subxt_core::events::StaticEvent for TableInsertQuorumUpdated {
+                const PALLET: &'static str = "Tables";
+                const EVENT: &'static str = "TableInsertQuorumUpdated";
+            }
+            #[derive(
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                Debug,
+            )]
+            # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+            #[codec(dumb_trait_bound)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode")]
+            #[doc = "Primary keys of rows loaded from a table's snapshot have been indexed"]
+            pub struct SnapshotPrimaryKeysIndexed {
+                pub table: snapshot_primary_keys_indexed::Table,
+                pub count: snapshot_primary_keys_indexed::Count,
+            }
+            pub mod snapshot_primary_keys_indexed {
+                use super::runtime_types;
+                pub type Table = runtime_types::sxt_core::tables::TableIdentifier;
+                pub type Count = ::core::primitive::u32;
+            }
+            impl ::subxt::ext::subxt_core::events::StaticEvent for SnapshotPrimaryKeysIndexed {
+                const PALLET: &'static str = "Tables";
+                const EVENT: &'static str = "SnapshotPrimaryKeysIndexed";
+            }
         }
         pub mod storage {
             use super::runtime_types;
@@ -18010,6 +18569,23 @@ pub mod api {
                     pub type TableSources = runtime_types::sxt_core::tables::Source;
                     pub type Param0 = runtime_types::sxt_core::tables::TableIdentifier;
                 }
+                pub mod primary_key_constraints {
+                    use super::runtime_types;
+                    pub type PrimaryKeyConstraints =
+                        runtime_types::sxt_core::tables::PrimaryKeyConstraint;
+                    pub type Param0 = runtime_types::sxt_core::tables::TableIdentifier;
+                }
+                pub mod primary_key_index {
+                    use super::runtime_types;
+                    pub type PrimaryKeyIndex = ::core::primitive::u64;
+                    pub type Param0 = runtime_types::sxt_core::tables::TableIdentifier;
+                    pub type Param1 = ::subxt::ext::subxt_core::utils::H256;
+                }
+                pub mod primary_key_index_removals {
+                    use super::runtime_types;
+                    pub type PrimaryKeyIndexRemovals = ();
+                    pub type Param0 = runtime_types::sxt_core::tables::TableIdentifier;
+                }
             }
             pub struct StorageApi;
             impl StorageApi {
@@ -18540,6 +19116,159 @@ pub mod api {
                         ],
                     )
                 }
+                #[doc = " The primary key declared in each table's DDL, along with its duplicate key policy."]
+                pub fn primary_key_constraints_iter(
+                    &self,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    (),
+                    types::primary_key_constraints::PrimaryKeyConstraints,
+                    (),
+                    (),
+                    ::subxt::ext::subxt_core::utils::Yes,
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "Tables",
+                        "PrimaryKeyConstraints",
+                        (),
+                    )
+                }
+                #[doc = " The primary key declared in each table's DDL, along with its duplicate key policy."]
+                pub fn primary_key_constraints(
+                    &self,
+                    _0: impl ::core::borrow::Borrow<types::primary_key_constraints::Param0>,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    ::subxt::ext::subxt_core::storage::address::StaticStorageKey<
+                        types::primary_key_constraints::Param0,
+                    >,
+                    types::primary_key_constraints::PrimaryKeyConstraints,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    (),
+                    (),
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "Tables",
+                        "PrimaryKeyConstraints",
+                        ::subxt::ext::subxt_core::storage::address::StaticStorageKey::new(
+                            _0.borrow(),
+                        ),
+                    )
+                }
+                #[doc = " The row number of the latest row stored under each primary key, by table and key hash."]
+                #[doc = ""]
+                #[doc = " Only maintained for tables whose primary key constraint sets a [`DuplicateKeyPolicy`]."]
+                pub fn primary_key_index_iter(
+                    &self,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    (),
+                    types::primary_key_index::PrimaryKeyIndex,
+                    (),
+                    (),
+                    ::subxt::ext::subxt_core::utils::Yes,
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "Tables",
+                        "PrimaryKeyIndex",
+                        (),
+                    )
+                }
+                #[doc = " The row number of the latest row stored under each primary key, by table and key hash."]
+                #[doc = ""]
+                #[doc = " Only maintained for tables whose primary key constraint sets a [`DuplicateKeyPolicy`]."]
+                pub fn primary_key_index_iter1(
+                    &self,
+                    _0: impl ::core::borrow::Borrow<types::primary_key_index::Param0>,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    ::subxt::ext::subxt_core::storage::address::StaticStorageKey<
+                        types::primary_key_index::Param0,
+                    >,
+                    types::primary_key_index::PrimaryKeyIndex,
+                    (),
+                    (),
+                    ::subxt::ext::subxt_core::utils::Yes,
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "Tables",
+                        "PrimaryKeyIndex",
+                        ::subxt::ext::subxt_core::storage::address::StaticStorageKey::new(
+                            _0.borrow(),
+                        ),
+                    )
+                }
+                #[doc = " The row number of the latest row stored under each primary key, by table and key hash."]
+                #[doc = ""]
+                #[doc = " Only maintained for tables whose primary key constraint sets a [`DuplicateKeyPolicy`]."]
+                pub fn primary_key_index(
+                    &self,
+                    _0: impl ::core::borrow::Borrow<types::primary_key_index::Param0>,
+                    _1: impl ::core::borrow::Borrow<types::primary_key_index::Param1>,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    (
+                        ::subxt::ext::subxt_core::storage::address::StaticStorageKey<
+                            types::primary_key_index::Param0,
+                        >,
+                        ::subxt::ext::subxt_core::storage::address::StaticStorageKey<
+                            types::primary_key_index::Param1,
+                        >,
+                    ),
+                    types::primary_key_index::PrimaryKeyIndex,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    (),
+                    (),
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "Tables",
+                        "PrimaryKeyIndex",
+                        (
+                            ::subxt::ext::subxt_core::storage::address::StaticStorageKey::new(
+                                _0.borrow(),
+                            ),
+                            ::subxt::ext::subxt_core::storage::address::StaticStorageKey::new(
+                                _1.borrow(),
+                            ),
+                        ),
+                    )
+                }
+                #[doc = " Tables whose key index is still being removed after they were dropped or cleared."]
+                #[doc = ""]
+                #[doc = " The key index can be too large to remove in one block, so it is removed in `on_idle`."]
+                pub fn primary_key_index_removals_iter(
+                    &self,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    (),
+                    types::primary_key_index_removals::PrimaryKeyIndexRemovals,
+                    (),
+                    (),
+                    ::subxt::ext::subxt_core::utils::Yes,
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "Tables",
+                        "PrimaryKeyIndexRemovals",
+                        (),
+                    )
+                }
+                #[doc = " Tables whose key index is still being removed after they were dropped or cleared."]
+                #[doc = ""]
+                #[doc = " The key index can be too large to remove in one block, so it is removed in `on_idle`."]
+                pub fn primary_key_index_removals(
+                    &self,
+                    _0: impl ::core::borrow::Borrow<types::primary_key_index_removals::Param0>,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    ::subxt::ext::subxt_core::storage::address::StaticStorageKey<
+                        types::primary_key_index_removals::Param0,
+                    >,
+                    types::primary_key_index_removals::PrimaryKeyIndexRemovals,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    (),
+                    (),
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "Tables",
+                        "PrimaryKeyIndexRemovals",
+                        ::subxt::ext::subxt_core::storage::address::StaticStorageKey::new(
+                            _0.borrow(),
+                        ),
+                    )
+                }
             }
         }
     }
@@ -18632,30 +19361,228 @@ pub mod api {
                     const PALLET: &'static str = "Indexing";
                     const CALL: &'static str = "submit_blockchain_data";
                 }
-            }
-            pub struct TransactionApi;
-            impl TransactionApi {
-                #[doc = "This extrinsic provides a transaction that indexers will use to submit"]
-                #[doc = "data they've indexed."]
-                pub fn submit_data(
-                    &self,
-                    table: types::submit_data::Table,
-                    batch_id: types::submit_data::BatchId,
-                    data: types::submit_data::Data,
-                ) -> ::subxt::ext::subxt_core::tx::payload::StaticPayload<types::SubmitData>
-                {
-                    ::subxt::ext::subxt_core::tx::payload::StaticPayload::new_static(
-                        "Indexing",
-                        "submit_data",
-                        types::SubmitData {
-                            table,
-                            batch_id,
-                            data,
-                        },
-                        [
-                            16u8, 204u8, 211u8, 228u8, 62u8, 61u8, 6u8, 23u8, 45u8, 255u8, 147u8,
-                            69u8, 104u8, 3u8, 182u8, 66u8, 28u8, 11u8, 42u8, 15u8, 68u8, 187u8,
-                            179u8, 11u8, 124u8, 128u8, 192u8, 44u8, 247u8, 191u8, 194u8, 92u8,
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
+                #[doc = "Submit the manifest of a batch that is too large for a single submission."]
+                #[doc = ""]
+                #[doc = "Quorum is reached on the hash of the manifest. Once it is reached, the batch awaits"]
+                #[doc = "its chunks, which are uploaded with `submit_chunk` before `ChunkUploadTimeout` elapses."]
+                pub struct SubmitChunkManifest {
+                    pub table: submit_chunk_manifest::Table,
+                    pub batch_id: submit_chunk_manifest::BatchId,
+                    pub manifest: submit_chunk_manifest::Manifest,
+                }
+                pub mod submit_chunk_manifest {
+                    use super::runtime_types;
+                    pub type Table = runtime_types::sxt_core::tables::TableIdentifier;
+                    pub type BatchId = runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                        ::core::primitive::u8,
+                    >;
+                    pub type Manifest = runtime_types::sxt_core::indexing::ChunkManifest<
+                        ::subxt::ext::subxt_core::utils::H256,
+                    >;
+                }
+                impl ::subxt::ext::subxt_core::blocks::StaticExtrinsic for SubmitChunkManifest {
+                    const PALLET: &'static str = "Indexing";
+                    const CALL: &'static str = "submit_chunk_manifest";
+                }
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
+                #[doc = "Upload one chunk of a chunked batch whose manifest has reached quorum."]
+                #[doc = ""]
+                #[doc = "The upload that completes the batch commits every chunk in manifest order, and is"]
+                #[doc = "charged for the combined length of all of them. If any chunk fails to be processed,"]
+                #[doc = "none of them are committed and the batch is discarded."]
+                pub struct SubmitChunk {
+                    pub batch_id: submit_chunk::BatchId,
+                    pub chunk_index: submit_chunk::ChunkIndex,
+                    pub data: submit_chunk::Data,
+                }
+                pub mod submit_chunk {
+                    use super::runtime_types;
+                    pub type BatchId = runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                        ::core::primitive::u8,
+                    >;
+                    pub type ChunkIndex = ::core::primitive::u32;
+                    pub type Data = runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                        ::core::primitive::u8,
+                    >;
+                }
+                impl ::subxt::ext::subxt_core::blocks::StaticExtrinsic for SubmitChunk {
+                    const PALLET: &'static str = "Indexing";
+                    const CALL: &'static str = "submit_chunk";
+                }
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
+                #[doc = "Submit only the hash of a data batch, without the data itself."]
+                #[doc = ""]
+                #[doc = "`row_data_hash` is the hash of the SCALE-encoded [`RowData`]. Quorum is reached on the"]
+                #[doc = "hash of `row_data_hash` and `block_number` combined, see [`quorum_data_hash`], so"]
+                #[doc = "hash-only submissions never count toward the quorum of submissions of the data itself."]
+                #[doc = "Once reached, any permitted indexer publishes the data with `publish_data` before"]
+                #[doc = "`PublicationTimeout` elapses."]
+                pub struct SubmitDataHash {
+                    pub table: submit_data_hash::Table,
+                    pub batch_id: submit_data_hash::BatchId,
+                    pub row_data_hash: submit_data_hash::RowDataHash,
+                    pub block_number: submit_data_hash::BlockNumber,
+                }
+                pub mod submit_data_hash {
+                    use super::runtime_types;
+                    pub type Table = runtime_types::sxt_core::tables::TableIdentifier;
+                    pub type BatchId = runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                        ::core::primitive::u8,
+                    >;
+                    pub type RowDataHash = ::subxt::ext::subxt_core::utils::H256;
+                    pub type BlockNumber = ::core::primitive::u64;
+                }
+                impl ::subxt::ext::subxt_core::blocks::StaticExtrinsic for SubmitDataHash {
+                    const PALLET: &'static str = "Indexing";
+                    const CALL: &'static str = "submit_data_hash";
+                }
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
+                #[doc = "Publish the data of a hash-only quorum."]
+                #[doc = ""]
+                #[doc = "The data is committed to only if it matches the hash agreed upon by the quorum."]
+                pub struct PublishData {
+                    pub batch_id: publish_data::BatchId,
+                    pub data: publish_data::Data,
+                }
+                pub mod publish_data {
+                    use super::runtime_types;
+                    pub type BatchId = runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                        ::core::primitive::u8,
+                    >;
+                    pub type Data = runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                        ::core::primitive::u8,
+                    >;
+                }
+                impl ::subxt::ext::subxt_core::blocks::StaticExtrinsic for PublishData {
+                    const PALLET: &'static str = "Indexing";
+                    const CALL: &'static str = "publish_data";
+                }
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
+                #[doc = "Submit data for several tables of the same source block under one batch id."]
+                #[doc = ""]
+                #[doc = "Quorum is reached on the hash of all tables' data combined, see [`quorum_data_hash`],"]
+                #[doc = "using the strictest insert quorum of the tables involved. The submission is recorded"]
+                #[doc = "under the first table, and a `DataSubmitted` event is emitted for every table. Once"]
+                #[doc = "quorum is reached, every table is committed to in the order submitted. If any of them"]
+                #[doc = "fails, none are committed."]
+                pub struct SubmitMultiTableData {
+                    pub batch_id: submit_multi_table_data::BatchId,
+                    pub tables: submit_multi_table_data::Tables,
+                    pub block_number: submit_multi_table_data::BlockNumber,
+                }
+                pub mod submit_multi_table_data {
+                    use super::runtime_types;
+                    pub type BatchId = runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                        ::core::primitive::u8,
+                    >;
+                    pub type Tables =
+                        runtime_types::bounded_collections::bounded_vec::BoundedVec<(
+                            runtime_types::sxt_core::tables::TableIdentifier,
+                            runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                                ::core::primitive::u8,
+                            >,
+                        )>;
+                    pub type BlockNumber = ::core::primitive::u64;
+                }
+                impl ::subxt::ext::subxt_core::blocks::StaticExtrinsic for SubmitMultiTableData {
+                    const PALLET: &'static str = "Indexing";
+                    const CALL: &'static str = "submit_multi_table_data";
+                }
+            }
+            pub struct TransactionApi;
+            impl TransactionApi {
+                #[doc = "This extrinsic provides a transaction that indexers will use to submit"]
+                #[doc = "data they've indexed."]
+                pub fn submit_data(
+                    &self,
+                    table: types::submit_data::Table,
+                    batch_id: types::submit_data::BatchId,
+                    data: types::submit_data::Data,
+                ) -> ::subxt::ext::subxt_core::tx::payload::StaticPayload<types::SubmitData>
+                {
+                    ::subxt::ext::subxt_core::tx::payload::StaticPayload::new_static(
+                        "Indexing",
+                        "submit_data",
+                        types::SubmitData {
+                            table,
+                            batch_id,
+                            data,
+                        },
+                        [
+                            16u8, 204u8, 211u8, 228u8, 62u8, 61u8, 6u8, 23u8, 45u8, 255u8, 147u8,
+                            69u8, 104u8, 3u8, 182u8, 66u8, 28u8, 11u8, 42u8, 15u8, 68u8, 187u8,
+                            179u8, 11u8, 124u8, 128u8, 192u8, 44u8, 247u8, 191u8, 194u8, 92u8,
                         ],
                     )
                 }
@@ -18689,6 +19616,114 @@ pub mod api {
                         ],
                     )
                 }
+                #[doc = "Submit the manifest of a batch that is too large for a single submission."]
+                #[doc = ""]
+                #[doc = "Quorum is reached on the hash of the manifest. Once it is reached, the batch awaits"]
+                #[doc = "its chunks, which are uploaded with `submit_chunk` before `ChunkUploadTimeout` elapses."]
+                pub fn submit_chunk_manifest(
+                    &self,
+                    table: types::submit_chunk_manifest::Table,
+                    batch_id: types::submit_chunk_manifest::BatchId,
+                    manifest: types::submit_chunk_manifest::Manifest,
+                ) -> ::subxt::ext::subxt_core::tx::payload::StaticPayload<types::SubmitChunkManifest>
+                {
+                    ::subxt::ext::subxt_core::tx::payload::StaticPayload::new(
+                        "Indexing",
+                        "submit_chunk_manifest",
+                        types::SubmitChunkManifest {
+                            table,
+                            batch_id,
+                            manifest,
+                        },
+                    )
+                }
+                #[doc = "Upload one chunk of a chunked batch whose manifest has reached quorum."]
+                #[doc = ""]
+                #[doc = "The upload that completes the batch commits every chunk in manifest order, and is"]
+                #[doc = "charged for the combined length of all of them. If any chunk fails to be processed,"]
+                #[doc = "none of them are committed and the batch is discarded."]
+                pub fn submit_chunk(
+                    &self,
+                    batch_id: types::submit_chunk::BatchId,
+                    chunk_index: types::submit_chunk::ChunkIndex,
+                    data: types::submit_chunk::Data,
+                ) -> ::subxt::ext::subxt_core::tx::payload::StaticPayload<types::SubmitChunk>
+                {
+                    ::subxt::ext::subxt_core::tx::payload::StaticPayload::new(
+                        "Indexing",
+                        "submit_chunk",
+                        types::SubmitChunk {
+                            batch_id,
+                            chunk_index,
+                            data,
+                        },
+                    )
+                }
+                #[doc = "Submit only the hash of a data batch, without the data itself."]
+                #[doc = ""]
+                #[doc = "`row_data_hash` is the hash of the SCALE-encoded [`RowData`]. Quorum is reached on the"]
+                #[doc = "hash of `row_data_hash` and `block_number` combined, see [`quorum_data_hash`], so"]
+                #[doc = "hash-only submissions never count toward the quorum of submissions of the data itself."]
+                #[doc = "Once reached, any permitted indexer publishes the data with `publish_data` before"]
+                #[doc = "`PublicationTimeout` elapses."]
+                pub fn submit_data_hash(
+                    &self,
+                    table: types::submit_data_hash::Table,
+                    batch_id: types::submit_data_hash::BatchId,
+                    row_data_hash: types::submit_data_hash::RowDataHash,
+                    block_number: types::submit_data_hash::BlockNumber,
+                ) -> ::subxt::ext::subxt_core::tx::payload::StaticPayload<types::SubmitDataHash>
+                {
+                    ::subxt::ext::subxt_core::tx::payload::StaticPayload::new(
+                        "Indexing",
+                        "submit_data_hash",
+                        types::SubmitDataHash {
+                            table,
+                            batch_id,
+                            row_data_hash,
+                            block_number,
+                        },
+                    )
+                }
+                #[doc = "Publish the data of a hash-only quorum."]
+                #[doc = ""]
+                #[doc = "The data is committed to only if it matches the hash agreed upon by the quorum."]
+                pub fn publish_data(
+                    &self,
+                    batch_id: types::publish_data::BatchId,
+                    data: types::publish_data::Data,
+                ) -> ::subxt::ext::subxt_core::tx::payload::StaticPayload<types::PublishData>
+                {
+                    ::subxt::ext::subxt_core::tx::payload::StaticPayload::new(
+                        "Indexing",
+                        "publish_data",
+                        types::PublishData { batch_id, data },
+                    )
+                }
+                #[doc = "Submit data for several tables of the same source block under one batch id."]
+                #[doc = ""]
+                #[doc = "Quorum is reached on the hash of all tables' data combined, see [`quorum_data_hash`],"]
+                #[doc = "using the strictest insert quorum of the tables involved. The submission is recorded"]
+                #[doc = "under the first table, and a `DataSubmitted` event is emitted for every table. Once"]
+                #[doc = "quorum is reached, every table is committed to in the order submitted. If any of them"]
+                #[doc = "fails, none are committed."]
+                pub fn submit_multi_table_data(
+                    &self,
+                    batch_id: types::submit_multi_table_data::BatchId,
+                    tables: types::submit_multi_table_data::Tables,
+                    block_number: types::submit_multi_table_data::BlockNumber,
+                ) -> ::subxt::ext::subxt_core::tx::payload::StaticPayload<types::SubmitMultiTableData>
+                {
+                    ::subxt::ext::subxt_core::tx::payload::StaticPayload::new(
+                        "Indexing",
+                        "submit_multi_table_data",
+                        types::SubmitMultiTableData {
+                            batch_id,
+                            tables,
+                            block_number,
+                        },
+                    )
+                }
             }
         }
         #[doc = "The `Event` enum of this pallet"]
@@ -18847,42 +19882,377 @@ pub mod api {
                 const PALLET: &'static str = "Indexing";
                 const EVENT: &'static str = "QuorumEmptyBlock";
             }
-        }
-        pub mod storage {
-            use super::runtime_types;
-            pub mod types {
+            #[derive(
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                Debug,
+            )]
+            # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+            #[codec(dumb_trait_bound)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode")]
+            #[doc = "A quorum has been reached on a chunk manifest. Its chunks can now be uploaded."]
+            pub struct ChunkManifestQuorumReached {
+                pub quorum: chunk_manifest_quorum_reached::Quorum,
+                pub chunk_count: chunk_manifest_quorum_reached::ChunkCount,
+            }
+            pub mod chunk_manifest_quorum_reached {
                 use super::runtime_types;
-                pub mod submissions {
-                    use super::runtime_types;
-                    pub type Submissions = runtime_types::sxt_core::indexing::SubmittersByScope<
-                        ::subxt::ext::subxt_core::utils::AccountId32,
-                    >;
-                    pub type Param0 = runtime_types::bounded_collections::bounded_vec::BoundedVec<
-                        ::core::primitive::u8,
-                    >;
-                    pub type Param1 = ::subxt::ext::subxt_core::utils::H256;
-                }
-                pub mod final_data {
-                    use super::runtime_types;
-                    pub type FinalData = runtime_types::sxt_core::indexing::DataQuorum<
-                        ::subxt::ext::subxt_core::utils::AccountId32,
-                        ::subxt::ext::subxt_core::utils::H256,
-                    >;
-                    pub type Param0 = runtime_types::bounded_collections::bounded_vec::BoundedVec<
-                        ::core::primitive::u8,
-                    >;
-                }
-                pub mod block_numbers {
-                    use super::runtime_types;
-                    pub type BlockNumbers = ::core::primitive::u64;
-                    pub type Param0 = runtime_types::sxt_core::tables::TableIdentifier;
-                }
+                pub type Quorum = runtime_types::sxt_core::indexing::DataQuorum<
+                    ::subxt::ext::subxt_core::utils::AccountId32,
+                    ::subxt::ext::subxt_core::utils::H256,
+                >;
+                pub type ChunkCount = ::core::primitive::u32;
             }
-            pub struct StorageApi;
-            impl StorageApi {
-                #[doc = " Double Map of Submissions using the batch-id as the first key and the submitter's"]
-                #[doc = " public key as the second key to hold the hash of the submitted data."]
-                #[doc = " Each submission for a given batch id will have an entry here"]
+            impl ::subxt::ext::subxt_core::events::StaticEvent for ChunkManifestQuorumReached {
+                const PALLET: &'static str = "Indexing";
+                const EVENT: &'static str = "ChunkManifestQuorumReached";
+            }
+            #[derive(
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                Debug,
+            )]
+            # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+            #[codec(dumb_trait_bound)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode")]
+            #[doc = "A chunk of a pending chunked batch has been uploaded."]
+            pub struct ChunkSubmitted {
+                pub who: chunk_submitted::Who,
+                pub batch_id: chunk_submitted::BatchId,
+                pub chunk_index: chunk_submitted::ChunkIndex,
+            }
+            pub mod chunk_submitted {
+                use super::runtime_types;
+                pub type Who = ::subxt::ext::subxt_core::utils::AccountId32;
+                pub type BatchId = runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                    ::core::primitive::u8,
+                >;
+                pub type ChunkIndex = ::core::primitive::u32;
+            }
+            impl ::subxt::ext::subxt_core::events::StaticEvent for ChunkSubmitted {
+                const PALLET: &'static str = "Indexing";
+                const EVENT: &'static str = "ChunkSubmitted";
+            }
+            #[derive(
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                Debug,
+            )]
+            # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+            #[codec(dumb_trait_bound)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode")]
+            #[doc = "A multi-table submission has reached quorum and every table has been committed to."]
+            #[doc = ""]
+            #[doc = "A `QuorumReached` event is emitted for each table beforehand, in submission order."]
+            pub struct MultiTableQuorumReached {
+                pub batch_id: multi_table_quorum_reached::BatchId,
+                pub tables: multi_table_quorum_reached::Tables,
+                pub block_number: multi_table_quorum_reached::BlockNumber,
+            }
+            pub mod multi_table_quorum_reached {
+                use super::runtime_types;
+                pub type BatchId = runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                    ::core::primitive::u8,
+                >;
+                pub type Tables = runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                    runtime_types::sxt_core::tables::TableIdentifier,
+                >;
+                pub type BlockNumber = ::core::primitive::u64;
+            }
+            impl ::subxt::ext::subxt_core::events::StaticEvent for MultiTableQuorumReached {
+                const PALLET: &'static str = "Indexing";
+                const EVENT: &'static str = "MultiTableQuorumReached";
+            }
+            #[derive(
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                Debug,
+            )]
+            # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+            #[codec(dumb_trait_bound)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode")]
+            #[doc = "A quorum has been reached on a data hash. The data can now be published."]
+            pub struct DataHashQuorumReached {
+                pub quorum: data_hash_quorum_reached::Quorum,
+                pub row_data_hash: data_hash_quorum_reached::RowDataHash,
+                pub deadline: data_hash_quorum_reached::Deadline,
+            }
+            pub mod data_hash_quorum_reached {
+                use super::runtime_types;
+                pub type Quorum = runtime_types::sxt_core::indexing::DataQuorum<
+                    ::subxt::ext::subxt_core::utils::AccountId32,
+                    ::subxt::ext::subxt_core::utils::H256,
+                >;
+                pub type RowDataHash = ::subxt::ext::subxt_core::utils::H256;
+                pub type Deadline = ::core::primitive::u32;
+            }
+            impl ::subxt::ext::subxt_core::events::StaticEvent for DataHashQuorumReached {
+                const PALLET: &'static str = "Indexing";
+                const EVENT: &'static str = "DataHashQuorumReached";
+            }
+            #[derive(
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                Debug,
+            )]
+            # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+            #[codec(dumb_trait_bound)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode")]
+            #[doc = "The data for a hash-only quorum has been published and committed to."]
+            #[doc = ""]
+            #[doc = "It is preceded by the usual `QuorumReached` or `QuorumEmptyBlock` event."]
+            pub struct DataPublished {
+                pub who: data_published::Who,
+                pub batch_id: data_published::BatchId,
+            }
+            pub mod data_published {
+                use super::runtime_types;
+                pub type Who = ::subxt::ext::subxt_core::utils::AccountId32;
+                pub type BatchId = runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                    ::core::primitive::u8,
+                >;
+            }
+            impl ::subxt::ext::subxt_core::events::StaticEvent for DataPublished {
+                const PALLET: &'static str = "Indexing";
+                const EVENT: &'static str = "DataPublished";
+            }
+            #[derive(
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                Debug,
+            )]
+            # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+            #[codec(dumb_trait_bound)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode")]
+            #[doc = "No data was published for a hash-only quorum before its deadline."]
+            #[doc = ""]
+            #[doc = "The quorum is discarded and the batch id can be submitted to again."]
+            pub struct PublicationExpired {
+                pub table: publication_expired::Table,
+                pub batch_id: publication_expired::BatchId,
+            }
+            pub mod publication_expired {
+                use super::runtime_types;
+                pub type Table = runtime_types::sxt_core::tables::TableIdentifier;
+                pub type BatchId = runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                    ::core::primitive::u8,
+                >;
+            }
+            impl ::subxt::ext::subxt_core::events::StaticEvent for PublicationExpired {
+                const PALLET: &'static str = "Indexing";
+                const EVENT: &'static str = "PublicationExpired";
+            }
+            #[derive(
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                Debug,
+            )]
+            # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+            #[codec(dumb_trait_bound)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode")]
+            #[doc = "Every chunk of a chunked batch has been uploaded and committed to."]
+            #[doc = ""]
+            #[doc = "A `QuorumReached` event is emitted for each chunk beforehand, in manifest order."]
+            pub struct ChunkedBatchFinalized {
+                pub table: chunked_batch_finalized::Table,
+                pub batch_id: chunked_batch_finalized::BatchId,
+                pub chunk_count: chunked_batch_finalized::ChunkCount,
+            }
+            pub mod chunked_batch_finalized {
+                use super::runtime_types;
+                pub type Table = runtime_types::sxt_core::tables::TableIdentifier;
+                pub type BatchId = runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                    ::core::primitive::u8,
+                >;
+                pub type ChunkCount = ::core::primitive::u32;
+            }
+            impl ::subxt::ext::subxt_core::events::StaticEvent for ChunkedBatchFinalized {
+                const PALLET: &'static str = "Indexing";
+                const EVENT: &'static str = "ChunkedBatchFinalized";
+            }
+            #[derive(
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                Debug,
+            )]
+            # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+            #[codec(dumb_trait_bound)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode")]
+            #[doc = "Not every chunk of a chunked batch was uploaded before its deadline."]
+            #[doc = ""]
+            #[doc = "The batch and its uploaded chunks are discarded and the batch id can be submitted to"]
+            #[doc = "again."]
+            pub struct ChunkedBatchExpired {
+                pub table: chunked_batch_expired::Table,
+                pub batch_id: chunked_batch_expired::BatchId,
+            }
+            pub mod chunked_batch_expired {
+                use super::runtime_types;
+                pub type Table = runtime_types::sxt_core::tables::TableIdentifier;
+                pub type BatchId = runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                    ::core::primitive::u8,
+                >;
+            }
+            impl ::subxt::ext::subxt_core::events::StaticEvent for ChunkedBatchExpired {
+                const PALLET: &'static str = "Indexing";
+                const EVENT: &'static str = "ChunkedBatchExpired";
+            }
+            #[derive(
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                Debug,
+            )]
+            # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+            #[codec(dumb_trait_bound)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode")]
+            #[doc = "Every chunk of a chunked batch was uploaded, but committing to them failed."]
+            #[doc = ""]
+            #[doc = "None of the chunks are committed. The batch and its chunks are discarded and the"]
+            #[doc = "batch id can be submitted to again."]
+            pub struct ChunkedBatchFailed {
+                pub table: chunked_batch_failed::Table,
+                pub batch_id: chunked_batch_failed::BatchId,
+                pub error: chunked_batch_failed::Error,
+            }
+            pub mod chunked_batch_failed {
+                use super::runtime_types;
+                pub type Table = runtime_types::sxt_core::tables::TableIdentifier;
+                pub type BatchId = runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                    ::core::primitive::u8,
+                >;
+                pub type Error = runtime_types::sp_runtime::DispatchError;
+            }
+            impl ::subxt::ext::subxt_core::events::StaticEvent for ChunkedBatchFailed {
+                const PALLET: &'static str = "Indexing";
+                const EVENT: &'static str = "ChunkedBatchFailed";
+            }
+        }
+        pub mod storage {
+            use super::runtime_types;
+            pub mod types {
+                use super::runtime_types;
+                pub mod submissions {
+                    use super::runtime_types;
+                    pub type Submissions = runtime_types::sxt_core::indexing::SubmittersByScope<
+                        ::subxt::ext::subxt_core::utils::AccountId32,
+                    >;
+                    pub type Param0 = runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                        ::core::primitive::u8,
+                    >;
+                    pub type Param1 = ::subxt::ext::subxt_core::utils::H256;
+                }
+                pub mod final_data {
+                    use super::runtime_types;
+                    pub type FinalData = runtime_types::sxt_core::indexing::DataQuorum<
+                        ::subxt::ext::subxt_core::utils::AccountId32,
+                        ::subxt::ext::subxt_core::utils::H256,
+                    >;
+                    pub type Param0 = runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                        ::core::primitive::u8,
+                    >;
+                }
+                pub mod final_table_data {
+                    use super::runtime_types;
+                    pub type FinalTableData = runtime_types::sxt_core::indexing::DataQuorum<
+                        ::subxt::ext::subxt_core::utils::AccountId32,
+                        ::subxt::ext::subxt_core::utils::H256,
+                    >;
+                    pub type Param0 = runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                        ::core::primitive::u8,
+                    >;
+                    pub type Param1 = runtime_types::sxt_core::tables::TableIdentifier;
+                }
+                pub mod block_numbers {
+                    use super::runtime_types;
+                    pub type BlockNumbers = ::core::primitive::u64;
+                    pub type Param0 = runtime_types::sxt_core::tables::TableIdentifier;
+                }
+                pub mod pending_chunked_batches {
+                    use super::runtime_types;
+                    pub type PendingChunkedBatches =
+                        runtime_types::sxt_core::indexing::PendingChunkedBatch<
+                            ::subxt::ext::subxt_core::utils::AccountId32,
+                            ::subxt::ext::subxt_core::utils::H256,
+                        >;
+                    pub type Param0 = runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                        ::core::primitive::u8,
+                    >;
+                }
+                pub mod chunks {
+                    use super::runtime_types;
+                    pub type Chunks = runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                        ::core::primitive::u8,
+                    >;
+                    pub type Param0 = runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                        ::core::primitive::u8,
+                    >;
+                    pub type Param1 = ::core::primitive::u32;
+                }
+                pub mod chunked_batch_deadlines {
+                    use super::runtime_types;
+                    pub type ChunkedBatchDeadlines =
+                        runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                                ::core::primitive::u8,
+                            >,
+                        >;
+                    pub type Param0 = ::core::primitive::u32;
+                }
+                pub mod pending_publications {
+                    use super::runtime_types;
+                    pub type PendingPublications =
+                        runtime_types::sxt_core::indexing::PendingPublication<
+                            ::subxt::ext::subxt_core::utils::AccountId32,
+                            ::subxt::ext::subxt_core::utils::H256,
+                            ::core::primitive::u32,
+                        >;
+                    pub type Param0 = runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                        ::core::primitive::u8,
+                    >;
+                }
+                pub mod publication_deadlines {
+                    use super::runtime_types;
+                    pub type PublicationDeadlines =
+                        runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                                ::core::primitive::u8,
+                            >,
+                        >;
+                    pub type Param0 = ::core::primitive::u32;
+                }
+            }
+            pub struct StorageApi;
+            impl StorageApi {
+                #[doc = " Double Map of Submissions using the batch-id as the first key and the submitter's"]
+                #[doc = " public key as the second key to hold the hash of the submitted data."]
+                #[doc = " Each submission for a given batch id will have an entry here"]
                 pub fn submissions_iter(
                     &self,
                 ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
@@ -19017,23 +20387,98 @@ pub mod api {
                         ],
                     )
                 }
-                pub fn block_numbers_iter(
+                #[doc = " The quorum of every table of a multi-table batch, keyed by batch id and table."]
+                #[doc = ""]
+                #[doc = " `FinalData` holds the quorum of a multi-table batch under the first table only."]
+                pub fn final_table_data_iter(
                     &self,
                 ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
                     (),
-                    types::block_numbers::BlockNumbers,
+                    types::final_table_data::FinalTableData,
                     (),
                     (),
                     ::subxt::ext::subxt_core::utils::Yes,
                 > {
-                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new_static(
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
                         "Indexing",
-                        "BlockNumbers",
+                        "FinalTableData",
                         (),
-                        [
-                            248u8, 188u8, 245u8, 221u8, 204u8, 2u8, 192u8, 134u8, 55u8, 214u8,
-                            83u8, 50u8, 103u8, 147u8, 67u8, 1u8, 37u8, 192u8, 219u8, 155u8, 144u8,
-                            235u8, 148u8, 181u8, 194u8, 82u8, 250u8, 217u8, 214u8, 63u8, 254u8,
+                    )
+                }
+                #[doc = " The quorum of every table of a multi-table batch, keyed by batch id and table."]
+                #[doc = ""]
+                #[doc = " `FinalData` holds the quorum of a multi-table batch under the first table only."]
+                pub fn final_table_data_iter1(
+                    &self,
+                    _0: impl ::core::borrow::Borrow<types::final_table_data::Param0>,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    ::subxt::ext::subxt_core::storage::address::StaticStorageKey<
+                        types::final_table_data::Param0,
+                    >,
+                    types::final_table_data::FinalTableData,
+                    (),
+                    (),
+                    ::subxt::ext::subxt_core::utils::Yes,
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "Indexing",
+                        "FinalTableData",
+                        ::subxt::ext::subxt_core::storage::address::StaticStorageKey::new(
+                            _0.borrow(),
+                        ),
+                    )
+                }
+                #[doc = " The quorum of every table of a multi-table batch, keyed by batch id and table."]
+                #[doc = ""]
+                #[doc = " `FinalData` holds the quorum of a multi-table batch under the first table only."]
+                pub fn final_table_data(
+                    &self,
+                    _0: impl ::core::borrow::Borrow<types::final_table_data::Param0>,
+                    _1: impl ::core::borrow::Borrow<types::final_table_data::Param1>,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    (
+                        ::subxt::ext::subxt_core::storage::address::StaticStorageKey<
+                            types::final_table_data::Param0,
+                        >,
+                        ::subxt::ext::subxt_core::storage::address::StaticStorageKey<
+                            types::final_table_data::Param1,
+                        >,
+                    ),
+                    types::final_table_data::FinalTableData,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    (),
+                    (),
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "Indexing",
+                        "FinalTableData",
+                        (
+                            ::subxt::ext::subxt_core::storage::address::StaticStorageKey::new(
+                                _0.borrow(),
+                            ),
+                            ::subxt::ext::subxt_core::storage::address::StaticStorageKey::new(
+                                _1.borrow(),
+                            ),
+                        ),
+                    )
+                }
+                pub fn block_numbers_iter(
+                    &self,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    (),
+                    types::block_numbers::BlockNumbers,
+                    (),
+                    (),
+                    ::subxt::ext::subxt_core::utils::Yes,
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new_static(
+                        "Indexing",
+                        "BlockNumbers",
+                        (),
+                        [
+                            248u8, 188u8, 245u8, 221u8, 204u8, 2u8, 192u8, 134u8, 55u8, 214u8,
+                            83u8, 50u8, 103u8, 147u8, 67u8, 1u8, 37u8, 192u8, 219u8, 155u8, 144u8,
+                            235u8, 148u8, 181u8, 194u8, 82u8, 250u8, 217u8, 214u8, 63u8, 254u8,
                             173u8,
                         ],
                     )
@@ -19064,6 +20509,251 @@ pub mod api {
                         ],
                     )
                 }
+                #[doc = " Chunked batches whose manifest has reached quorum and that are awaiting chunk uploads."]
+                pub fn pending_chunked_batches_iter(
+                    &self,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    (),
+                    types::pending_chunked_batches::PendingChunkedBatches,
+                    (),
+                    (),
+                    ::subxt::ext::subxt_core::utils::Yes,
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "Indexing",
+                        "PendingChunkedBatches",
+                        (),
+                    )
+                }
+                #[doc = " Chunked batches whose manifest has reached quorum and that are awaiting chunk uploads."]
+                pub fn pending_chunked_batches(
+                    &self,
+                    _0: impl ::core::borrow::Borrow<types::pending_chunked_batches::Param0>,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    ::subxt::ext::subxt_core::storage::address::StaticStorageKey<
+                        types::pending_chunked_batches::Param0,
+                    >,
+                    types::pending_chunked_batches::PendingChunkedBatches,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    (),
+                    (),
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "Indexing",
+                        "PendingChunkedBatches",
+                        ::subxt::ext::subxt_core::storage::address::StaticStorageKey::new(
+                            _0.borrow(),
+                        ),
+                    )
+                }
+                #[doc = " Uploaded chunks of pending chunked batches, keyed by batch id and chunk index."]
+                pub fn chunks_iter(
+                    &self,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    (),
+                    types::chunks::Chunks,
+                    (),
+                    (),
+                    ::subxt::ext::subxt_core::utils::Yes,
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "Indexing",
+                        "Chunks",
+                        (),
+                    )
+                }
+                #[doc = " Uploaded chunks of pending chunked batches, keyed by batch id and chunk index."]
+                pub fn chunks_iter1(
+                    &self,
+                    _0: impl ::core::borrow::Borrow<types::chunks::Param0>,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    ::subxt::ext::subxt_core::storage::address::StaticStorageKey<
+                        types::chunks::Param0,
+                    >,
+                    types::chunks::Chunks,
+                    (),
+                    (),
+                    ::subxt::ext::subxt_core::utils::Yes,
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "Indexing",
+                        "Chunks",
+                        ::subxt::ext::subxt_core::storage::address::StaticStorageKey::new(
+                            _0.borrow(),
+                        ),
+                    )
+                }
+                #[doc = " Uploaded chunks of pending chunked batches, keyed by batch id and chunk index."]
+                pub fn chunks(
+                    &self,
+                    _0: impl ::core::borrow::Borrow<types::chunks::Param0>,
+                    _1: impl ::core::borrow::Borrow<types::chunks::Param1>,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    (
+                        ::subxt::ext::subxt_core::storage::address::StaticStorageKey<
+                            types::chunks::Param0,
+                        >,
+                        ::subxt::ext::subxt_core::storage::address::StaticStorageKey<
+                            types::chunks::Param1,
+                        >,
+                    ),
+                    types::chunks::Chunks,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    (),
+                    (),
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "Indexing",
+                        "Chunks",
+                        (
+                            ::subxt::ext::subxt_core::storage::address::StaticStorageKey::new(
+                                _0.borrow(),
+                            ),
+                            ::subxt::ext::subxt_core::storage::address::StaticStorageKey::new(
+                                _1.borrow(),
+                            ),
+                        ),
+                    )
+                }
+                #[doc = " Batch ids of pending chunked batches, keyed by the block at which they expire."]
+                pub fn chunked_batch_deadlines_iter(
+                    &self,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    (),
+                    types::chunked_batch_deadlines::ChunkedBatchDeadlines,
+                    (),
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "Indexing",
+                        "ChunkedBatchDeadlines",
+                        (),
+                    )
+                }
+                #[doc = " Batch ids of pending chunked batches, keyed by the block at which they expire."]
+                pub fn chunked_batch_deadlines(
+                    &self,
+                    _0: impl ::core::borrow::Borrow<types::chunked_batch_deadlines::Param0>,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    ::subxt::ext::subxt_core::storage::address::StaticStorageKey<
+                        types::chunked_batch_deadlines::Param0,
+                    >,
+                    types::chunked_batch_deadlines::ChunkedBatchDeadlines,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    (),
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "Indexing",
+                        "ChunkedBatchDeadlines",
+                        ::subxt::ext::subxt_core::storage::address::StaticStorageKey::new(
+                            _0.borrow(),
+                        ),
+                    )
+                }
+                #[doc = " Hash-only quorums awaiting publication of their data."]
+                pub fn pending_publications_iter(
+                    &self,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    (),
+                    types::pending_publications::PendingPublications,
+                    (),
+                    (),
+                    ::subxt::ext::subxt_core::utils::Yes,
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "Indexing",
+                        "PendingPublications",
+                        (),
+                    )
+                }
+                #[doc = " Hash-only quorums awaiting publication of their data."]
+                pub fn pending_publications(
+                    &self,
+                    _0: impl ::core::borrow::Borrow<types::pending_publications::Param0>,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    ::subxt::ext::subxt_core::storage::address::StaticStorageKey<
+                        types::pending_publications::Param0,
+                    >,
+                    types::pending_publications::PendingPublications,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    (),
+                    (),
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "Indexing",
+                        "PendingPublications",
+                        ::subxt::ext::subxt_core::storage::address::StaticStorageKey::new(
+                            _0.borrow(),
+                        ),
+                    )
+                }
+                #[doc = " Batch ids of pending publications, keyed by the block at which they expire."]
+                pub fn publication_deadlines_iter(
+                    &self,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    (),
+                    types::publication_deadlines::PublicationDeadlines,
+                    (),
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "Indexing",
+                        "PublicationDeadlines",
+                        (),
+                    )
+                }
+                #[doc = " Batch ids of pending publications, keyed by the block at which they expire."]
+                pub fn publication_deadlines(
+                    &self,
+                    _0: impl ::core::borrow::Borrow<types::publication_deadlines::Param0>,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    ::subxt::ext::subxt_core::storage::address::StaticStorageKey<
+                        types::publication_deadlines::Param0,
+                    >,
+                    types::publication_deadlines::PublicationDeadlines,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    (),
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "Indexing",
+                        "PublicationDeadlines",
+                        ::subxt::ext::subxt_core::storage::address::StaticStorageKey::new(
+                            _0.borrow(),
+                        ),
+                    )
+                }
+            }
+        }
+        pub mod constants {
+            use super::runtime_types;
+            pub struct ConstantsApi;
+            impl ConstantsApi {
+                #[doc = " How many blocks a hash-only quorum waits for its data to be published"]
+                pub fn publication_timeout(
+                    &self,
+                ) -> ::subxt::ext::subxt_core::constants::address::StaticAddress<
+                    ::core::primitive::u32,
+                > {
+                    ::subxt::ext::subxt_core::constants::address::StaticAddress::new(
+                        "Indexing",
+                        "PublicationTimeout",
+                    )
+                }
+                #[doc = " How many blocks a chunked batch waits for all of its chunks to be uploaded"]
+                pub fn chunk_upload_timeout(
+                    &self,
+                ) -> ::subxt::ext::subxt_core::constants::address::StaticAddress<
+                    ::core::primitive::u32,
+                > {
+                    ::subxt::ext::subxt_core::constants::address::StaticAddress::new(
+                        "Indexing",
+                        "ChunkUploadTimeout",
+                    )
+                }
             }
         }
     }
@@ -19879,6 +21569,10 @@ pub mod api {
                 #[doc = "verifies it doesn’t already exist, emits a `SmartContractAdded` event, and registers any"]
                 #[doc = "associated indexing tables via `pallet_tables::create_tables_inner`."]
                 #[doc = ""]
+                #[doc = "If the contract has an ABI and event details, the event tables are generated from the"]
+                #[doc = "ABI. Supplied tables for those events must match the generated DDL. Without a DDL"]
+                #[doc = "statement, the namespace and the missing event tables are created from the generated DDL."]
+                #[doc = ""]
                 #[doc = "# Parameters"]
                 #[doc = "- `origin`: Must be either `Root` or a signed user with appropriate smart contract permissions."]
                 #[doc = "- `contract`: The [`Contract`] to be added. Can be a normal or proxy contract."]
@@ -19891,6 +21585,8 @@ pub mod api {
                 #[doc = ""]
                 #[doc = "# Errors"]
                 #[doc = "- [`Error::ExistingContractError`] — if a contract with the same `source` and `address` already exists."]
+                #[doc = "- [`Error::InvalidAbi`] — if the event tables cannot be generated from the ABI."]
+                #[doc = "- [`Error::DdlMismatch`] — if a supplied event table does not match the generated DDL."]
                 #[doc = "- Any error from:"]
                 #[doc = "    - [`pallet_permissions::Pallet::ensure_root_or_permissioned`] if origin is unauthorized."]
                 #[doc = "    - [`pallet_tables::Pallet::create_tables_inner`] if any table creation fails."]
@@ -19909,22 +21605,85 @@ pub mod api {
                     const PALLET: &'static str = "Smartcontracts";
                     const CALL: &'static str = "add_smartcontract";
                 }
-            }
-            pub struct TransactionApi;
-            impl TransactionApi {
-                #[doc = "**Remove a Smart Contract Entry**"]
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
+                #[doc = "Updates a registered smart contract without dropping its tables."]
                 #[doc = ""]
-                #[doc = "Deletes a smart contract entry from storage."]
+                #[doc = "The update can replace the ABI, append event details and upgrade a proxy to a new"]
+                #[doc = "implementation. The tables of the appended events are generated from the ABI unless"]
+                #[doc = "they are supplied, in which case they must match it. Only new tables are created; the"]
+                #[doc = "existing tables of the contract and their commitments are left intact. New tables are"]
+                #[doc = "indexed from the starting block of the new implementation, or of the contract."]
                 #[doc = ""]
-                #[doc = "**Parameters:**"]
-                #[doc = "- `origin`: Must be a signed account."]
+                #[doc = "# Parameters"]
+                #[doc = "- `origin`: Must be either `Root` or a signed user with appropriate smart contract permissions."]
                 #[doc = "- `source`: The `Source` identifier for the contract."]
-                #[doc = "- `contract_address`: The address of the smart contract."]
+                #[doc = "- `address`: The address of the smart contract."]
+                #[doc = "- `update`: The [`ContractUpdate`] to apply."]
+                #[doc = "- `tables`: Additional [`UpdateTable`] entries to create for this contract."]
                 #[doc = ""]
-                #[doc = "**Emits:** `SmartContractRemoved`"]
-                pub fn remove_smartcontract(
-                    &self,
-                    source: types::remove_smartcontract::Source,
+                #[doc = "# Emits"]
+                #[doc = "- [`Event::ImplementationUpgraded`] — when a proxy is upgraded to a new implementation."]
+                #[doc = "- [`Event::SmartContractUpdated`] — when the contract is successfully updated."]
+                #[doc = ""]
+                #[doc = "# Errors"]
+                #[doc = "- [`Error::ContractNotFound`] — if no contract exists for the `source` and `address`."]
+                #[doc = "- [`Error::NotAProxy`] — if a new implementation is supplied for a normal contract."]
+                #[doc = "- [`Error::InvalidStartingBlock`] — if the new implementation has no starting block, or"]
+                #[doc = "  one before the previous implementation."]
+                #[doc = "- [`Error::DuplicateEvent`] — if an appended event or its table is already indexed."]
+                #[doc = "- [`Error::ExistingTable`] — if a table to create already exists."]
+                #[doc = "- Any error from [`pallet_tables::Pallet::create_tables_inner`] if table creation fails."]
+                pub struct UpdateSmartcontract {
+                    pub source: update_smartcontract::Source,
+                    pub address: update_smartcontract::Address,
+                    pub update: update_smartcontract::Update,
+                    pub tables: update_smartcontract::Tables,
+                }
+                pub mod update_smartcontract {
+                    use super::runtime_types;
+                    pub type Source = runtime_types::sxt_core::tables::Source;
+                    pub type Address = runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                        ::core::primitive::u8,
+                    >;
+                    pub type Update = runtime_types::sxt_core::smartcontracts::ContractUpdate;
+                    pub type Tables = runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                        runtime_types::pallet_tables::pallet::UpdateTable,
+                    >;
+                }
+                impl ::subxt::ext::subxt_core::blocks::StaticExtrinsic for UpdateSmartcontract {
+                    const PALLET: &'static str = "Smartcontracts";
+                    const CALL: &'static str = "update_smartcontract";
+                }
+            }
+            pub struct TransactionApi;
+            impl TransactionApi {
+                #[doc = "**Remove a Smart Contract Entry**"]
+                #[doc = ""]
+                #[doc = "Deletes a smart contract entry from storage."]
+                #[doc = ""]
+                #[doc = "**Parameters:**"]
+                #[doc = "- `origin`: Must be a signed account."]
+                #[doc = "- `source`: The `Source` identifier for the contract."]
+                #[doc = "- `contract_address`: The address of the smart contract."]
+                #[doc = ""]
+                #[doc = "**Emits:** `SmartContractRemoved`"]
+                pub fn remove_smartcontract(
+                    &self,
+                    source: types::remove_smartcontract::Source,
                     address: types::remove_smartcontract::Address,
                 ) -> ::subxt::ext::subxt_core::tx::payload::StaticPayload<types::RemoveSmartcontract>
                 {
@@ -19946,6 +21705,10 @@ pub mod api {
                 #[doc = "verifies it doesn’t already exist, emits a `SmartContractAdded` event, and registers any"]
                 #[doc = "associated indexing tables via `pallet_tables::create_tables_inner`."]
                 #[doc = ""]
+                #[doc = "If the contract has an ABI and event details, the event tables are generated from the"]
+                #[doc = "ABI. Supplied tables for those events must match the generated DDL. Without a DDL"]
+                #[doc = "statement, the namespace and the missing event tables are created from the generated DDL."]
+                #[doc = ""]
                 #[doc = "# Parameters"]
                 #[doc = "- `origin`: Must be either `Root` or a signed user with appropriate smart contract permissions."]
                 #[doc = "- `contract`: The [`Contract`] to be added. Can be a normal or proxy contract."]
@@ -19958,6 +21721,8 @@ pub mod api {
                 #[doc = ""]
                 #[doc = "# Errors"]
                 #[doc = "- [`Error::ExistingContractError`] — if a contract with the same `source` and `address` already exists."]
+                #[doc = "- [`Error::InvalidAbi`] — if the event tables cannot be generated from the ABI."]
+                #[doc = "- [`Error::DdlMismatch`] — if a supplied event table does not match the generated DDL."]
                 #[doc = "- Any error from:"]
                 #[doc = "    - [`pallet_permissions::Pallet::ensure_root_or_permissioned`] if origin is unauthorized."]
                 #[doc = "    - [`pallet_tables::Pallet::create_tables_inner`] if any table creation fails."]
@@ -19978,6 +21743,52 @@ pub mod api {
                         ],
                     )
                 }
+                #[doc = "Updates a registered smart contract without dropping its tables."]
+                #[doc = ""]
+                #[doc = "The update can replace the ABI, append event details and upgrade a proxy to a new"]
+                #[doc = "implementation. The tables of the appended events are generated from the ABI unless"]
+                #[doc = "they are supplied, in which case they must match it. Only new tables are created; the"]
+                #[doc = "existing tables of the contract and their commitments are left intact. New tables are"]
+                #[doc = "indexed from the starting block of the new implementation, or of the contract."]
+                #[doc = ""]
+                #[doc = "# Parameters"]
+                #[doc = "- `origin`: Must be either `Root` or a signed user with appropriate smart contract permissions."]
+                #[doc = "- `source`: The `Source` identifier for the contract."]
+                #[doc = "- `address`: The address of the smart contract."]
+                #[doc = "- `update`: The [`ContractUpdate`] to apply."]
+                #[doc = "- `tables`: Additional [`UpdateTable`] entries to create for this contract."]
+                #[doc = ""]
+                #[doc = "# Emits"]
+                #[doc = "- [`Event::ImplementationUpgraded`] — when a proxy is upgraded to a new implementation."]
+                #[doc = "- [`Event::SmartContractUpdated`] — when the contract is successfully updated."]
+                #[doc = ""]
+                #[doc = "# Errors"]
+                #[doc = "- [`Error::ContractNotFound`] — if no contract exists for the `source` and `address`."]
+                #[doc = "- [`Error::NotAProxy`] — if a new implementation is supplied for a normal contract."]
+                #[doc = "- [`Error::InvalidStartingBlock`] — if the new implementation has no starting block, or"]
+                #[doc = "  one before the previous implementation."]
+                #[doc = "- [`Error::DuplicateEvent`] — if an appended event or its table is already indexed."]
+                #[doc = "- [`Error::ExistingTable`] — if a table to create already exists."]
+                #[doc = "- Any error from [`pallet_tables::Pallet::create_tables_inner`] if table creation fails."]
+                pub fn update_smartcontract(
+                    &self,
+                    source: types::update_smartcontract::Source,
+                    address: types::update_smartcontract::Address,
+                    update: types::update_smartcontract::Update,
+                    tables: types::update_smartcontract::Tables,
+                ) -> ::subxt::ext::subxt_core::tx::payload::StaticPayload<types::UpdateSmartcontract>
+                {
+                    ::subxt::ext::subxt_core::tx::payload::StaticPayload::new(
+                        "Smartcontracts",
+                        "update_smartcontract",
+                        types::UpdateSmartcontract {
+                            source,
+                            address,
+                            update,
+                            tables,
+                        },
+                    )
+                }
             }
         }
         #[doc = "Events for the Pallet"]
@@ -20025,6 +21836,70 @@ pub mod api {
             #[codec(dumb_trait_bound)]
             #[decode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode")]
             #[encode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode")]
+            #[doc = "A smart contract was updated with new events or a new implementation."]
+            pub struct SmartContractUpdated {
+                pub owner: smart_contract_updated::Owner,
+                pub source: smart_contract_updated::Source,
+                pub address: smart_contract_updated::Address,
+            }
+            pub mod smart_contract_updated {
+                use super::runtime_types;
+                pub type Owner =
+                    ::core::option::Option<::subxt::ext::subxt_core::utils::AccountId32>;
+                pub type Source = runtime_types::sxt_core::tables::Source;
+                pub type Address = runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                    ::core::primitive::u8,
+                >;
+            }
+            impl ::subxt::ext::subxt_core::events::StaticEvent for SmartContractUpdated {
+                const PALLET: &'static str = "Smartcontracts";
+                const EVENT: &'static str = "SmartContractUpdated";
+            }
+            #[derive(
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                Debug,
+            )]
+            # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+            #[codec(dumb_trait_bound)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode")]
+            #[doc = "A proxy contract was upgraded to a new implementation."]
+            pub struct ImplementationUpgraded {
+                pub source: implementation_upgraded::Source,
+                pub address: implementation_upgraded::Address,
+                pub implementation: implementation_upgraded::Implementation,
+                pub starting_block: implementation_upgraded::StartingBlock,
+            }
+            pub mod implementation_upgraded {
+                use super::runtime_types;
+                pub type Source = runtime_types::sxt_core::tables::Source;
+                pub type Address = runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                    ::core::primitive::u8,
+                >;
+                pub type Implementation =
+                    runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                        ::core::primitive::u8,
+                    >;
+                pub type StartingBlock = ::core::primitive::u64;
+            }
+            impl ::subxt::ext::subxt_core::events::StaticEvent for ImplementationUpgraded {
+                const PALLET: &'static str = "Smartcontracts";
+                const EVENT: &'static str = "ImplementationUpgraded";
+            }
+            #[derive(
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                Debug,
+            )]
+            # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+            #[codec(dumb_trait_bound)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode")]
             #[doc = "A smart contract was removed from storage."]
             pub struct SmartContractRemoved {
                 pub owner: smart_contract_removed::Owner,
@@ -20069,6 +21944,17 @@ pub mod api {
                         ::core::primitive::u8,
                     >;
                 }
+                pub mod implementation_histories {
+                    use super::runtime_types;
+                    pub type ImplementationHistories =
+                        runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            runtime_types::sxt_core::smartcontracts::ImplementationHistoryEntry,
+                        >;
+                    pub type Param0 = runtime_types::sxt_core::tables::Source;
+                    pub type Param1 = runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                        ::core::primitive::u8,
+                    >;
+                }
             }
             pub struct StorageApi;
             impl StorageApi {
@@ -20237,6 +22123,78 @@ pub mod api {
                         ],
                     )
                 }
+                #[doc = " The implementations each upgraded proxy contract pointed to, with their starting blocks,"]
+                #[doc = " oldest first."]
+                pub fn implementation_histories_iter(
+                    &self,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    (),
+                    types::implementation_histories::ImplementationHistories,
+                    (),
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "Smartcontracts",
+                        "ImplementationHistories",
+                        (),
+                    )
+                }
+                #[doc = " The implementations each upgraded proxy contract pointed to, with their starting blocks,"]
+                #[doc = " oldest first."]
+                pub fn implementation_histories_iter1(
+                    &self,
+                    _0: impl ::core::borrow::Borrow<types::implementation_histories::Param0>,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    ::subxt::ext::subxt_core::storage::address::StaticStorageKey<
+                        types::implementation_histories::Param0,
+                    >,
+                    types::implementation_histories::ImplementationHistories,
+                    (),
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "Smartcontracts",
+                        "ImplementationHistories",
+                        ::subxt::ext::subxt_core::storage::address::StaticStorageKey::new(
+                            _0.borrow(),
+                        ),
+                    )
+                }
+                #[doc = " The implementations each upgraded proxy contract pointed to, with their starting blocks,"]
+                #[doc = " oldest first."]
+                pub fn implementation_histories(
+                    &self,
+                    _0: impl ::core::borrow::Borrow<types::implementation_histories::Param0>,
+                    _1: impl ::core::borrow::Borrow<types::implementation_histories::Param1>,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    (
+                        ::subxt::ext::subxt_core::storage::address::StaticStorageKey<
+                            types::implementation_histories::Param0,
+                        >,
+                        ::subxt::ext::subxt_core::storage::address::StaticStorageKey<
+                            types::implementation_histories::Param1,
+                        >,
+                    ),
+                    types::implementation_histories::ImplementationHistories,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    (),
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "Smartcontracts",
+                        "ImplementationHistories",
+                        (
+                            ::subxt::ext::subxt_core::storage::address::StaticStorageKey::new(
+                                _0.borrow(),
+                            ),
+                            ::subxt::ext::subxt_core::storage::address::StaticStorageKey::new(
+                                _1.borrow(),
+                            ),
+                        ),
+                    )
+                }
             }
         }
     }
@@ -20280,6 +22238,153 @@ pub mod api {
                     const PALLET: &'static str = "SystemTables";
                     const CALL: &'static str = "set_last_nonce";
                 }
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
+                #[doc = "Register the template of a system table, replacing any previous template."]
+                #[doc = ""]
+                #[doc = "Every field must be a column of the table, as created in `pallet_tables`, with a"]
+                #[doc = "matching type, and the fields read by the handler must be declared."]
+                pub struct RegisterSystemTemplate {
+                    pub table: register_system_template::Table,
+                    pub template: register_system_template::Template,
+                }
+                pub mod register_system_template {
+                    use super::runtime_types;
+                    pub type Table = runtime_types::sxt_core::tables::TableIdentifier;
+                    pub type Template = runtime_types::pallet_system_tables::parse::SystemTemplate;
+                }
+                impl ::subxt::ext::subxt_core::blocks::StaticExtrinsic for RegisterSystemTemplate {
+                    const PALLET: &'static str = "SystemTables";
+                    const CALL: &'static str = "register_system_template";
+                }
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
+                #[doc = "Sudo call to set the account ZK-Pay payments are credited from, or to stop crediting"]
+                #[doc = "payments when `None`"]
+                pub struct SetZkpayTreasury {
+                    pub treasury: set_zkpay_treasury::Treasury,
+                }
+                pub mod set_zkpay_treasury {
+                    use super::runtime_types;
+                    pub type Treasury =
+                        ::core::option::Option<::subxt::ext::subxt_core::utils::AccountId32>;
+                }
+                impl ::subxt::ext::subxt_core::blocks::StaticExtrinsic for SetZkpayTreasury {
+                    const PALLET: &'static str = "SystemTables";
+                    const CALL: &'static str = "set_zkpay_treasury";
+                }
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
+                #[doc = "Sudo call to set the nonce of the last ZK-Pay payment processed for a payer, to"]
+                #[doc = "skip or replay payments"]
+                pub struct SetZkpayNonce {
+                    pub eth_wallet: set_zkpay_nonce::EthWallet,
+                    pub new_nonce: set_zkpay_nonce::NewNonce,
+                }
+                pub mod set_zkpay_nonce {
+                    use super::runtime_types;
+                    pub type EthWallet = ::subxt::ext::subxt_core::alloc::string::String;
+                    pub type NewNonce = ::core::primitive::u64;
+                }
+                impl ::subxt::ext::subxt_core::blocks::StaticExtrinsic for SetZkpayNonce {
+                    const PALLET: &'static str = "SystemTables";
+                    const CALL: &'static str = "set_zkpay_nonce";
+                }
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
+                #[doc = "Sudo call to credit a ZK-Pay payment that failed, once the treasury can cover it"]
+                pub struct RetryZkpayPayment {
+                    pub eth_wallet: retry_zkpay_payment::EthWallet,
+                    pub nonce: retry_zkpay_payment::Nonce,
+                }
+                pub mod retry_zkpay_payment {
+                    use super::runtime_types;
+                    pub type EthWallet = ::subxt::ext::subxt_core::alloc::string::String;
+                    pub type Nonce = ::core::primitive::u64;
+                }
+                impl ::subxt::ext::subxt_core::blocks::StaticExtrinsic for RetryZkpayPayment {
+                    const PALLET: &'static str = "SystemTables";
+                    const CALL: &'static str = "retry_zkpay_payment";
+                }
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
+                #[doc = "Remove the template of a system table, so inserts into it are no longer processed"]
+                pub struct RemoveSystemTemplate {
+                    pub table: remove_system_template::Table,
+                }
+                pub mod remove_system_template {
+                    use super::runtime_types;
+                    pub type Table = runtime_types::sxt_core::tables::TableIdentifier;
+                }
+                impl ::subxt::ext::subxt_core::blocks::StaticExtrinsic for RemoveSystemTemplate {
+                    const PALLET: &'static str = "SystemTables";
+                    const CALL: &'static str = "remove_system_template";
+                }
             }
             pub struct TransactionApi;
             impl TransactionApi {
@@ -20305,8 +22410,80 @@ pub mod api {
                         ],
                     )
                 }
-            }
-        }
+                #[doc = "Register the template of a system table, replacing any previous template."]
+                #[doc = ""]
+                #[doc = "Every field must be a column of the table, as created in `pallet_tables`, with a"]
+                #[doc = "matching type, and the fields read by the handler must be declared."]
+                pub fn register_system_template(
+                    &self,
+                    table: types::register_system_template::Table,
+                    template: types::register_system_template::Template,
+                ) -> ::subxt::ext::subxt_core::tx::payload::StaticPayload<
+                    types::RegisterSystemTemplate,
+                > {
+                    ::subxt::ext::subxt_core::tx::payload::StaticPayload::new(
+                        "SystemTables",
+                        "register_system_template",
+                        types::RegisterSystemTemplate { table, template },
+                    )
+                }
+                #[doc = "Sudo call to set the account ZK-Pay payments are credited from, or to stop crediting"]
+                #[doc = "payments when `None`"]
+                pub fn set_zkpay_treasury(
+                    &self,
+                    treasury: types::set_zkpay_treasury::Treasury,
+                ) -> ::subxt::ext::subxt_core::tx::payload::StaticPayload<types::SetZkpayTreasury>
+                {
+                    ::subxt::ext::subxt_core::tx::payload::StaticPayload::new(
+                        "SystemTables",
+                        "set_zkpay_treasury",
+                        types::SetZkpayTreasury { treasury },
+                    )
+                }
+                #[doc = "Sudo call to set the nonce of the last ZK-Pay payment processed for a payer, to"]
+                #[doc = "skip or replay payments"]
+                pub fn set_zkpay_nonce(
+                    &self,
+                    eth_wallet: types::set_zkpay_nonce::EthWallet,
+                    new_nonce: types::set_zkpay_nonce::NewNonce,
+                ) -> ::subxt::ext::subxt_core::tx::payload::StaticPayload<types::SetZkpayNonce>
+                {
+                    ::subxt::ext::subxt_core::tx::payload::StaticPayload::new(
+                        "SystemTables",
+                        "set_zkpay_nonce",
+                        types::SetZkpayNonce {
+                            eth_wallet,
+                            new_nonce,
+                        },
+                    )
+                }
+                #[doc = "Sudo call to credit a ZK-Pay payment that failed, once the treasury can cover it"]
+                pub fn retry_zkpay_payment(
+                    &self,
+                    eth_wallet: types::retry_zkpay_payment::EthWallet,
+                    nonce: types::retry_zkpay_payment::Nonce,
+                ) -> ::subxt::ext::subxt_core::tx::payload::StaticPayload<types::RetryZkpayPayment>
+                {
+                    ::subxt::ext::subxt_core::tx::payload::StaticPayload::new(
+                        "SystemTables",
+                        "retry_zkpay_payment",
+                        types::RetryZkpayPayment { eth_wallet, nonce },
+                    )
+                }
+                #[doc = "Remove the template of a system table, so inserts into it are no longer processed"]
+                pub fn remove_system_template(
+                    &self,
+                    table: types::remove_system_template::Table,
+                ) -> ::subxt::ext::subxt_core::tx::payload::StaticPayload<types::RemoveSystemTemplate>
+                {
+                    ::subxt::ext::subxt_core::tx::payload::StaticPayload::new(
+                        "SystemTables",
+                        "remove_system_template",
+                        types::RemoveSystemTemplate { table },
+                    )
+                }
+            }
+        }
         #[doc = "The `Event` enum of this pallet"]
         pub type Event = runtime_types::pallet_system_tables::pallet::Event;
         pub mod events {
@@ -20325,11 +22502,13 @@ pub mod api {
             #[doc = "This event is emitted whenever a message is received by Substrate from the EVM"]
             pub struct MessageReceived {
                 pub sender: message_received::Sender,
+                pub message_type: message_received::MessageType,
                 pub payload: message_received::Payload,
             }
             pub mod message_received {
                 use super::runtime_types;
                 pub type Sender = [::core::primitive::u8; 20usize];
+                pub type MessageType = runtime_types::pallet_system_tables::messages::MessageType;
                 pub type Payload = ::subxt::ext::subxt_core::alloc::vec::Vec<::core::primitive::u8>;
             }
             impl ::subxt::ext::subxt_core::events::StaticEvent for MessageReceived {
@@ -20347,13 +22526,17 @@ pub mod api {
             #[codec(dumb_trait_bound)]
             #[decode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode")]
             #[encode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode")]
-            #[doc = "There was an error processing an evm message"]
+            #[doc = "There was an error processing an evm message or system table insert"]
             pub struct MessageProcessingError {
                 pub error: message_processing_error::Error,
+                pub message_type: message_processing_error::MessageType,
             }
             pub mod message_processing_error {
                 use super::runtime_types;
                 pub type Error = runtime_types::sp_runtime::DispatchError;
+                pub type MessageType = ::core::option::Option<
+                    runtime_types::pallet_system_tables::messages::MessageType,
+                >;
             }
             impl ::subxt::ext::subxt_core::events::StaticEvent for MessageProcessingError {
                 const PALLET: &'static str = "SystemTables";
@@ -20382,15 +22565,212 @@ pub mod api {
                 const PALLET: &'static str = "SystemTables";
                 const EVENT: &'static str = "ValidatorForceChilled";
             }
+            #[derive(
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                Debug,
+            )]
+            # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+            #[codec(dumb_trait_bound)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode")]
+            #[doc = "A system template was registered, replacing any previous template of the table"]
+            pub struct SystemTemplateRegistered {
+                pub table: system_template_registered::Table,
+                pub request_type: system_template_registered::RequestType,
+            }
+            pub mod system_template_registered {
+                use super::runtime_types;
+                pub type Table = runtime_types::sxt_core::tables::TableIdentifier;
+                pub type RequestType =
+                    runtime_types::pallet_system_tables::parse::SystemRequestType;
+            }
+            impl ::subxt::ext::subxt_core::events::StaticEvent for SystemTemplateRegistered {
+                const PALLET: &'static str = "SystemTables";
+                const EVENT: &'static str = "SystemTemplateRegistered";
+            }
+            #[derive(
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                Debug,
+            )]
+            # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+            #[codec(dumb_trait_bound)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode")]
+            #[doc = "A system template was removed, inserts into the table are no longer processed"]
+            pub struct SystemTemplateRemoved {
+                pub table: system_template_removed::Table,
+            }
+            pub mod system_template_removed {
+                use super::runtime_types;
+                pub type Table = runtime_types::sxt_core::tables::TableIdentifier;
+            }
+            impl ::subxt::ext::subxt_core::events::StaticEvent for SystemTemplateRemoved {
+                const PALLET: &'static str = "SystemTables";
+                const EVENT: &'static str = "SystemTemplateRemoved";
+            }
+            #[derive(
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                Debug,
+            )]
+            # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+            #[codec(dumb_trait_bound)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode")]
+            #[doc = "A ZK-Pay payment was credited to the account of its payer"]
+            pub struct ZkPayCredited {
+                pub sender: zk_pay_credited::Sender,
+                pub account: zk_pay_credited::Account,
+                pub amount: zk_pay_credited::Amount,
+                pub nonce: zk_pay_credited::Nonce,
+                pub treasury: zk_pay_credited::Treasury,
+            }
+            pub mod zk_pay_credited {
+                use super::runtime_types;
+                pub type Sender = [::core::primitive::u8; 20usize];
+                pub type Account = ::subxt::ext::subxt_core::utils::AccountId32;
+                pub type Amount = ::core::primitive::u128;
+                pub type Nonce = runtime_types::primitive_types::U256;
+                pub type Treasury = ::subxt::ext::subxt_core::utils::AccountId32;
+            }
+            impl ::subxt::ext::subxt_core::events::StaticEvent for ZkPayCredited {
+                const PALLET: &'static str = "SystemTables";
+                const EVENT: &'static str = "ZkPayCredited";
+            }
+            #[derive(
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                Debug,
+            )]
+            # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+            #[codec(dumb_trait_bound)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode")]
+            #[doc = "A ZK-Pay payment could not be credited. Its nonce is used all the same. Payments with"]
+            #[doc = "a valid amount are kept in `FailedZkPayments` to be retried with"]
+            #[doc = "`retry_zkpay_payment`."]
+            pub struct ZkPayFailed {
+                pub sender: zk_pay_failed::Sender,
+                pub nonce: zk_pay_failed::Nonce,
+                pub error: zk_pay_failed::Error,
+            }
+            pub mod zk_pay_failed {
+                use super::runtime_types;
+                pub type Sender = [::core::primitive::u8; 20usize];
+                pub type Nonce = runtime_types::primitive_types::U256;
+                pub type Error = runtime_types::sp_runtime::DispatchError;
+            }
+            impl ::subxt::ext::subxt_core::events::StaticEvent for ZkPayFailed {
+                const PALLET: &'static str = "SystemTables";
+                const EVENT: &'static str = "ZkPayFailed";
+            }
+            #[derive(
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                Debug,
+            )]
+            # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+            #[codec(dumb_trait_bound)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode")]
+            #[doc = "The ZK-Pay treasury was set or cleared"]
+            pub struct ZkPayTreasurySet {
+                pub treasury: zk_pay_treasury_set::Treasury,
+            }
+            pub mod zk_pay_treasury_set {
+                use super::runtime_types;
+                pub type Treasury =
+                    ::core::option::Option<::subxt::ext::subxt_core::utils::AccountId32>;
+            }
+            impl ::subxt::ext::subxt_core::events::StaticEvent for ZkPayTreasurySet {
+                const PALLET: &'static str = "SystemTables";
+                const EVENT: &'static str = "ZkPayTreasurySet";
+            }
+            #[derive(
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                Debug,
+            )]
+            # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+            #[codec(dumb_trait_bound)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode")]
+            #[doc = "A staking row was skipped or applied out of order, and the staking state may need"]
+            #[doc = "to be reconciled with the EVM contract"]
+            pub struct SystemTableError {
+                pub table: system_table_error::Table,
+                pub staker: system_table_error::Staker,
+                pub source: system_table_error::Source,
+                pub issue: system_table_error::Issue,
+            }
+            pub mod system_table_error {
+                use super::runtime_types;
+                pub type Table = runtime_types::sxt_core::tables::TableIdentifier;
+                pub type Staker = ::subxt::ext::subxt_core::utils::AccountId32;
+                pub type Source = runtime_types::pallet_system_tables::parse::StakingEventSource;
+                pub type Issue = runtime_types::pallet_system_tables::parse::StakingEventIssue;
+            }
+            impl ::subxt::ext::subxt_core::events::StaticEvent for SystemTableError {
+                const PALLET: &'static str = "SystemTables";
+                const EVENT: &'static str = "SystemTableError";
+            }
         }
         pub mod storage {
             use super::runtime_types;
             pub mod types {
                 use super::runtime_types;
+                pub mod system_templates {
+                    use super::runtime_types;
+                    pub type SystemTemplates =
+                        runtime_types::pallet_system_tables::parse::SystemTemplate;
+                    pub type Param0 = runtime_types::sxt_core::tables::TableIdentifier;
+                }
                 pub mod message_nonce {
                     use super::runtime_types;
                     pub type MessageNonce = runtime_types::primitive_types::U256;
                 }
+                pub mod zk_pay_nonces {
+                    use super::runtime_types;
+                    pub type ZkPayNonces = runtime_types::primitive_types::U256;
+                    pub type Param0 = ::subxt::ext::subxt_core::utils::AccountId32;
+                }
+                pub mod failed_zk_payments {
+                    use super::runtime_types;
+                    pub type FailedZkPayments =
+                        runtime_types::pallet_system_tables::pallet::FailedZkPayment;
+                    pub type Param0 = ::subxt::ext::subxt_core::utils::AccountId32;
+                    pub type Param1 = runtime_types::primitive_types::U256;
+                }
+                pub mod zk_pay_treasury {
+                    use super::runtime_types;
+                    pub type ZkPayTreasury = ::subxt::ext::subxt_core::utils::AccountId32;
+                }
+                pub mod processed_staking_events {
+                    use super::runtime_types;
+                    pub type ProcessedStakingEvents = ::core::primitive::u64;
+                    pub type Param0 = ::subxt::ext::subxt_core::utils::AccountId32;
+                    pub type Param1 = ([::core::primitive::u8; 32usize], ::core::primitive::u32);
+                }
+                pub mod last_staking_events {
+                    use super::runtime_types;
+                    pub type LastStakingEvents =
+                        runtime_types::pallet_system_tables::parse::StakingEventSource;
+                    pub type Param0 = ::subxt::ext::subxt_core::utils::AccountId32;
+                }
                 pub mod last_processed_nonce {
                     use super::runtime_types;
                     pub type LastProcessedNonce = runtime_types::primitive_types::U256;
@@ -20403,6 +22783,45 @@ pub mod api {
             }
             pub struct StorageApi;
             impl StorageApi {
+                #[doc = " The templates of the system tables, mapping each table to the fields parsed from its"]
+                #[doc = " inserts and the handler processing them"]
+                pub fn system_templates_iter(
+                    &self,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    (),
+                    types::system_templates::SystemTemplates,
+                    (),
+                    (),
+                    ::subxt::ext::subxt_core::utils::Yes,
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "SystemTables",
+                        "SystemTemplates",
+                        (),
+                    )
+                }
+                #[doc = " The templates of the system tables, mapping each table to the fields parsed from its"]
+                #[doc = " inserts and the handler processing them"]
+                pub fn system_templates(
+                    &self,
+                    _0: impl ::core::borrow::Borrow<types::system_templates::Param0>,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    ::subxt::ext::subxt_core::storage::address::StaticStorageKey<
+                        types::system_templates::Param0,
+                    >,
+                    types::system_templates::SystemTemplates,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    (),
+                    (),
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "SystemTables",
+                        "SystemTemplates",
+                        ::subxt::ext::subxt_core::storage::address::StaticStorageKey::new(
+                            _0.borrow(),
+                        ),
+                    )
+                }
                 #[doc = " The last processed message"]
                 pub fn message_nonce(
                     &self,
@@ -20425,96 +22844,332 @@ pub mod api {
                         ],
                     )
                 }
-                pub fn last_processed_nonce(
+                #[doc = " The nonce of the last ZK-Pay payment processed for each payer"]
+                pub fn zk_pay_nonces_iter(
                     &self,
                 ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
                     (),
-                    types::last_processed_nonce::LastProcessedNonce,
+                    types::zk_pay_nonces::ZkPayNonces,
+                    (),
+                    (),
                     ::subxt::ext::subxt_core::utils::Yes,
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "SystemTables",
+                        "ZkPayNonces",
+                        (),
+                    )
+                }
+                #[doc = " The nonce of the last ZK-Pay payment processed for each payer"]
+                pub fn zk_pay_nonces(
+                    &self,
+                    _0: impl ::core::borrow::Borrow<types::zk_pay_nonces::Param0>,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    ::subxt::ext::subxt_core::storage::address::StaticStorageKey<
+                        types::zk_pay_nonces::Param0,
+                    >,
+                    types::zk_pay_nonces::ZkPayNonces,
                     ::subxt::ext::subxt_core::utils::Yes,
                     (),
+                    (),
                 > {
-                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new_static(
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
                         "SystemTables",
-                        "LastProcessedNonce",
-                        (),
-                        [
-                            71u8, 0u8, 133u8, 181u8, 45u8, 232u8, 96u8, 28u8, 204u8, 25u8, 213u8,
-                            247u8, 161u8, 13u8, 250u8, 133u8, 30u8, 43u8, 230u8, 248u8, 126u8,
-                            19u8, 129u8, 93u8, 131u8, 135u8, 14u8, 117u8, 255u8, 233u8, 248u8,
-                            207u8,
-                        ],
+                        "ZkPayNonces",
+                        ::subxt::ext::subxt_core::storage::address::StaticStorageKey::new(
+                            _0.borrow(),
+                        ),
                     )
                 }
-                pub fn last_processed_user_nonce_iter(
+                #[doc = " The ZK-Pay payments that could not be credited, by payer and nonce, until they are"]
+                #[doc = " retried with `retry_zkpay_payment`"]
+                pub fn failed_zk_payments_iter(
                     &self,
                 ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
                     (),
-                    types::last_processed_user_nonce::LastProcessedUserNonce,
+                    types::failed_zk_payments::FailedZkPayments,
                     (),
                     (),
                     ::subxt::ext::subxt_core::utils::Yes,
                 > {
-                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new_static(
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
                         "SystemTables",
-                        "LastProcessedUserNonce",
+                        "FailedZkPayments",
                         (),
-                        [
-                            17u8, 76u8, 178u8, 95u8, 186u8, 30u8, 65u8, 26u8, 105u8, 206u8, 182u8,
-                            235u8, 200u8, 79u8, 2u8, 163u8, 81u8, 239u8, 53u8, 7u8, 226u8, 104u8,
-                            98u8, 14u8, 169u8, 228u8, 160u8, 50u8, 73u8, 95u8, 190u8, 251u8,
-                        ],
                     )
                 }
-                pub fn last_processed_user_nonce(
+                #[doc = " The ZK-Pay payments that could not be credited, by payer and nonce, until they are"]
+                #[doc = " retried with `retry_zkpay_payment`"]
+                pub fn failed_zk_payments_iter1(
                     &self,
-                    _0: impl ::core::borrow::Borrow<types::last_processed_user_nonce::Param0>,
+                    _0: impl ::core::borrow::Borrow<types::failed_zk_payments::Param0>,
                 ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
                     ::subxt::ext::subxt_core::storage::address::StaticStorageKey<
-                        types::last_processed_user_nonce::Param0,
+                        types::failed_zk_payments::Param0,
                     >,
-                    types::last_processed_user_nonce::LastProcessedUserNonce,
-                    ::subxt::ext::subxt_core::utils::Yes,
+                    types::failed_zk_payments::FailedZkPayments,
                     (),
                     (),
+                    ::subxt::ext::subxt_core::utils::Yes,
                 > {
-                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new_static(
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
                         "SystemTables",
-                        "LastProcessedUserNonce",
+                        "FailedZkPayments",
                         ::subxt::ext::subxt_core::storage::address::StaticStorageKey::new(
                             _0.borrow(),
                         ),
-                        [
-                            17u8, 76u8, 178u8, 95u8, 186u8, 30u8, 65u8, 26u8, 105u8, 206u8, 182u8,
-                            235u8, 200u8, 79u8, 2u8, 163u8, 81u8, 239u8, 53u8, 7u8, 226u8, 104u8,
-                            98u8, 14u8, 169u8, 228u8, 160u8, 50u8, 73u8, 95u8, 190u8, 251u8,
-                        ],
                     )
                 }
-            }
-        }
-    }
-    pub mod system_contracts {
-        use super::{root_mod, runtime_types};
-        #[doc = "The system contracts pallet's dispatchable functions ([`Call`]s)."]
-        pub type Call = runtime_types::pallet_system_contracts::pallet::Call;
-        pub mod calls {
-            use super::{root_mod, runtime_types};
-            type DispatchError = runtime_types::sp_runtime::DispatchError;
-            pub mod types {
-                use super::runtime_types;
-                #[derive(
-                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
-                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
-                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
-                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
-                    Debug,
-                )]
-                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
-                #[codec(dumb_trait_bound)]
-                #[decode_as_type(
-                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
-                )]
+                #[doc = " The ZK-Pay payments that could not be credited, by payer and nonce, until they are"]
+                #[doc = " retried with `retry_zkpay_payment`"]
+                pub fn failed_zk_payments(
+                    &self,
+                    _0: impl ::core::borrow::Borrow<types::failed_zk_payments::Param0>,
+                    _1: impl ::core::borrow::Borrow<types::failed_zk_payments::Param1>,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    (
+                        ::subxt::ext::subxt_core::storage::address::StaticStorageKey<
+                            types::failed_zk_payments::Param0,
+                        >,
+                        ::subxt::ext::subxt_core::storage::address::StaticStorageKey<
+                            types::failed_zk_payments::Param1,
+                        >,
+                    ),
+                    types::failed_zk_payments::FailedZkPayments,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    (),
+                    (),
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "SystemTables",
+                        "FailedZkPayments",
+                        (
+                            ::subxt::ext::subxt_core::storage::address::StaticStorageKey::new(
+                                _0.borrow(),
+                            ),
+                            ::subxt::ext::subxt_core::storage::address::StaticStorageKey::new(
+                                _1.borrow(),
+                            ),
+                        ),
+                    )
+                }
+                #[doc = " The account ZK-Pay payments are credited from. Without it, payments fail."]
+                pub fn zk_pay_treasury(
+                    &self,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    (),
+                    types::zk_pay_treasury::ZkPayTreasury,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    (),
+                    (),
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "SystemTables",
+                        "ZkPayTreasury",
+                        (),
+                    )
+                }
+                #[doc = " The EVM block of each staking event applied for a staker, by transaction hash and event"]
+                #[doc = " index. Events below the watermark of the staker's last event are pruned."]
+                pub fn processed_staking_events_iter(
+                    &self,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    (),
+                    types::processed_staking_events::ProcessedStakingEvents,
+                    (),
+                    (),
+                    ::subxt::ext::subxt_core::utils::Yes,
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "SystemTables",
+                        "ProcessedStakingEvents",
+                        (),
+                    )
+                }
+                #[doc = " The EVM block of each staking event applied for a staker, by transaction hash and event"]
+                #[doc = " index. Events below the watermark of the staker's last event are pruned."]
+                pub fn processed_staking_events_iter1(
+                    &self,
+                    _0: impl ::core::borrow::Borrow<types::processed_staking_events::Param0>,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    ::subxt::ext::subxt_core::storage::address::StaticStorageKey<
+                        types::processed_staking_events::Param0,
+                    >,
+                    types::processed_staking_events::ProcessedStakingEvents,
+                    (),
+                    (),
+                    ::subxt::ext::subxt_core::utils::Yes,
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "SystemTables",
+                        "ProcessedStakingEvents",
+                        ::subxt::ext::subxt_core::storage::address::StaticStorageKey::new(
+                            _0.borrow(),
+                        ),
+                    )
+                }
+                #[doc = " The EVM block of each staking event applied for a staker, by transaction hash and event"]
+                #[doc = " index. Events below the watermark of the staker's last event are pruned."]
+                pub fn processed_staking_events(
+                    &self,
+                    _0: impl ::core::borrow::Borrow<types::processed_staking_events::Param0>,
+                    _1: impl ::core::borrow::Borrow<types::processed_staking_events::Param1>,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    (
+                        ::subxt::ext::subxt_core::storage::address::StaticStorageKey<
+                            types::processed_staking_events::Param0,
+                        >,
+                        ::subxt::ext::subxt_core::storage::address::StaticStorageKey<
+                            types::processed_staking_events::Param1,
+                        >,
+                    ),
+                    types::processed_staking_events::ProcessedStakingEvents,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    (),
+                    (),
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "SystemTables",
+                        "ProcessedStakingEvents",
+                        (
+                            ::subxt::ext::subxt_core::storage::address::StaticStorageKey::new(
+                                _0.borrow(),
+                            ),
+                            ::subxt::ext::subxt_core::storage::address::StaticStorageKey::new(
+                                _1.borrow(),
+                            ),
+                        ),
+                    )
+                }
+                #[doc = " The latest staking event applied for each staker, whose watermark bounds the events"]
+                #[doc = " kept in `ProcessedStakingEvents`"]
+                pub fn last_staking_events_iter(
+                    &self,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    (),
+                    types::last_staking_events::LastStakingEvents,
+                    (),
+                    (),
+                    ::subxt::ext::subxt_core::utils::Yes,
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "SystemTables",
+                        "LastStakingEvents",
+                        (),
+                    )
+                }
+                #[doc = " The latest staking event applied for each staker, whose watermark bounds the events"]
+                #[doc = " kept in `ProcessedStakingEvents`"]
+                pub fn last_staking_events(
+                    &self,
+                    _0: impl ::core::borrow::Borrow<types::last_staking_events::Param0>,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    ::subxt::ext::subxt_core::storage::address::StaticStorageKey<
+                        types::last_staking_events::Param0,
+                    >,
+                    types::last_staking_events::LastStakingEvents,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    (),
+                    (),
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "SystemTables",
+                        "LastStakingEvents",
+                        ::subxt::ext::subxt_core::storage::address::StaticStorageKey::new(
+                            _0.borrow(),
+                        ),
+                    )
+                }
+                pub fn last_processed_nonce(
+                    &self,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    (),
+                    types::last_processed_nonce::LastProcessedNonce,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    (),
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new_static(
+                        "SystemTables",
+                        "LastProcessedNonce",
+                        (),
+                        [
+                            71u8, 0u8, 133u8, 181u8, 45u8, 232u8, 96u8, 28u8, 204u8, 25u8, 213u8,
+                            247u8, 161u8, 13u8, 250u8, 133u8, 30u8, 43u8, 230u8, 248u8, 126u8,
+                            19u8, 129u8, 93u8, 131u8, 135u8, 14u8, 117u8, 255u8, 233u8, 248u8,
+                            207u8,
+                        ],
+                    )
+                }
+                pub fn last_processed_user_nonce_iter(
+                    &self,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    (),
+                    types::last_processed_user_nonce::LastProcessedUserNonce,
+                    (),
+                    (),
+                    ::subxt::ext::subxt_core::utils::Yes,
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new_static(
+                        "SystemTables",
+                        "LastProcessedUserNonce",
+                        (),
+                        [
+                            17u8, 76u8, 178u8, 95u8, 186u8, 30u8, 65u8, 26u8, 105u8, 206u8, 182u8,
+                            235u8, 200u8, 79u8, 2u8, 163u8, 81u8, 239u8, 53u8, 7u8, 226u8, 104u8,
+                            98u8, 14u8, 169u8, 228u8, 160u8, 50u8, 73u8, 95u8, 190u8, 251u8,
+                        ],
+                    )
+                }
+                pub fn last_processed_user_nonce(
+                    &self,
+                    _0: impl ::core::borrow::Borrow<types::last_processed_user_nonce::Param0>,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    ::subxt::ext::subxt_core::storage::address::StaticStorageKey<
+                        types::last_processed_user_nonce::Param0,
+                    >,
+                    types::last_processed_user_nonce::LastProcessedUserNonce,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    (),
+                    (),
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new_static(
+                        "SystemTables",
+                        "LastProcessedUserNonce",
+                        ::subxt::ext::subxt_core::storage::address::StaticStorageKey::new(
+                            _0.borrow(),
+                        ),
+                        [
+                            17u8, 76u8, 178u8, 95u8, 186u8, 30u8, 65u8, 26u8, 105u8, 206u8, 182u8,
+                            235u8, 200u8, 79u8, 2u8, 163u8, 81u8, 239u8, 53u8, 7u8, 226u8, 104u8,
+                            98u8, 14u8, 169u8, 228u8, 160u8, 50u8, 73u8, 95u8, 190u8, 251u8,
+                        ],
+                    )
+                }
+            }
+        }
+    }
+    pub mod system_contracts {
+        use super::{root_mod, runtime_types};
+        #[doc = "The system contracts pallet's dispatchable functions ([`Call`]s)."]
+        pub type Call = runtime_types::pallet_system_contracts::pallet::Call;
+        pub mod calls {
+            use super::{root_mod, runtime_types};
+            type DispatchError = runtime_types::sp_runtime::DispatchError;
+            pub mod types {
+                use super::runtime_types;
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
                 #[encode_as_type(
                     crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
                 )]
@@ -20811,10 +23466,14 @@ pub mod api {
             #[doc = "A payout of accrued rewards was made automatically by the system"]
             pub struct Payout {
                 pub validator: payout::Validator,
+                pub era: payout::Era,
+                pub page: payout::Page,
             }
             pub mod payout {
                 use super::runtime_types;
                 pub type Validator = ::subxt::ext::subxt_core::utils::AccountId32;
+                pub type Era = ::core::primitive::u32;
+                pub type Page = ::core::primitive::u32;
             }
             impl ::subxt::ext::subxt_core::events::StaticEvent for Payout {
                 const PALLET: &'static str = "Rewards";
@@ -20916,6 +23575,87 @@ pub mod api {
                 const PALLET: &'static str = "Rewards";
                 const EVENT: &'static str = "PayerUpdated";
             }
+            #[derive(
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                Debug,
+            )]
+            # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+            #[codec(dumb_trait_bound)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode")]
+            #[doc = "An indexer or attestor was paid out of the pot for their work in an era"]
+            pub struct WorkRewardPaid {
+                pub era: work_reward_paid::Era,
+                pub worker: work_reward_paid::Worker,
+                pub amount: work_reward_paid::Amount,
+            }
+            pub mod work_reward_paid {
+                use super::runtime_types;
+                pub type Era = ::core::primitive::u32;
+                pub type Worker = ::subxt::ext::subxt_core::utils::AccountId32;
+                pub type Amount = ::core::primitive::u128;
+            }
+            impl ::subxt::ext::subxt_core::events::StaticEvent for WorkRewardPaid {
+                const PALLET: &'static str = "Rewards";
+                const EVENT: &'static str = "WorkRewardPaid";
+            }
+            #[derive(
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                Debug,
+            )]
+            # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+            #[codec(dumb_trait_bound)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode")]
+            #[doc = "An error occurred paying an indexer or attestor"]
+            pub struct WorkRewardError {
+                pub era: work_reward_error::Era,
+                pub worker: work_reward_error::Worker,
+                pub error: work_reward_error::Error,
+            }
+            pub mod work_reward_error {
+                use super::runtime_types;
+                pub type Era = ::core::primitive::u32;
+                pub type Worker = ::subxt::ext::subxt_core::utils::AccountId32;
+                pub type Error = runtime_types::sp_runtime::DispatchError;
+            }
+            impl ::subxt::ext::subxt_core::events::StaticEvent for WorkRewardError {
+                const PALLET: &'static str = "Rewards";
+                const EVENT: &'static str = "WorkRewardError";
+            }
+            #[derive(
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                Debug,
+            )]
+            # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+            #[codec(dumb_trait_bound)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode")]
+            #[doc = "The work of an era has been fully paid"]
+            pub struct WorkEraPaid {
+                pub era: work_era_paid::Era,
+                pub total_points: work_era_paid::TotalPoints,
+                pub budget: work_era_paid::Budget,
+            }
+            pub mod work_era_paid {
+                use super::runtime_types;
+                pub type Era = ::core::primitive::u32;
+                pub type TotalPoints = ::core::primitive::u64;
+                pub type Budget = ::core::primitive::u128;
+            }
+            impl ::subxt::ext::subxt_core::events::StaticEvent for WorkEraPaid {
+                const PALLET: &'static str = "Rewards";
+                const EVENT: &'static str = "WorkEraPaid";
+            }
         }
         pub mod storage {
             use super::runtime_types;
@@ -20929,15 +23669,39 @@ pub mod api {
                     use super::runtime_types;
                     pub type PayerAccount = ::subxt::ext::subxt_core::utils::AccountId32;
                 }
-            }
-            pub struct StorageApi;
-            impl StorageApi {
-                #[doc = " The next era that we expect to pay out."]
-                pub fn next_paid_era(
-                    &self,
-                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
-                    (),
-                    types::next_paid_era::NextPaidEra,
+                pub mod payout_cursor {
+                    use super::runtime_types;
+                    pub type PayoutCursor = ::subxt::ext::subxt_core::utils::AccountId32;
+                }
+                pub mod eras_work_points {
+                    use super::runtime_types;
+                    pub type ErasWorkPoints = runtime_types::pallet_rewards::WorkPoints;
+                    pub type Param0 = ::core::primitive::u32;
+                    pub type Param1 = ::subxt::ext::subxt_core::utils::AccountId32;
+                }
+                pub mod eras_total_work_points {
+                    use super::runtime_types;
+                    pub type ErasTotalWorkPoints = ::core::primitive::u64;
+                    pub type Param0 = ::core::primitive::u32;
+                }
+                pub mod eras_work_budget {
+                    use super::runtime_types;
+                    pub type ErasWorkBudget = ::core::primitive::u128;
+                    pub type Param0 = ::core::primitive::u32;
+                }
+                pub mod next_work_paid_era {
+                    use super::runtime_types;
+                    pub type NextWorkPaidEra = ::core::primitive::u32;
+                }
+            }
+            pub struct StorageApi;
+            impl StorageApi {
+                #[doc = " The next era that we expect to pay out."]
+                pub fn next_paid_era(
+                    &self,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    (),
+                    types::next_paid_era::NextPaidEra,
                     ::subxt::ext::subxt_core::utils::Yes,
                     ::subxt::ext::subxt_core::utils::Yes,
                     (),
@@ -20974,6 +23738,242 @@ pub mod api {
                         ],
                     )
                 }
+                #[doc = " The validator of the era being paid that the payouts continue from, if a previous block"]
+                #[doc = " ran out of weight."]
+                pub fn payout_cursor(
+                    &self,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    (),
+                    types::payout_cursor::PayoutCursor,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    (),
+                    (),
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "Rewards",
+                        "PayoutCursor",
+                        (),
+                    )
+                }
+                #[doc = " The points earned by indexers and attestors in each era that is not paid out yet."]
+                pub fn eras_work_points_iter(
+                    &self,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    (),
+                    types::eras_work_points::ErasWorkPoints,
+                    (),
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "Rewards",
+                        "ErasWorkPoints",
+                        (),
+                    )
+                }
+                #[doc = " The points earned by indexers and attestors in each era that is not paid out yet."]
+                pub fn eras_work_points_iter1(
+                    &self,
+                    _0: impl ::core::borrow::Borrow<types::eras_work_points::Param0>,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    ::subxt::ext::subxt_core::storage::address::StaticStorageKey<
+                        types::eras_work_points::Param0,
+                    >,
+                    types::eras_work_points::ErasWorkPoints,
+                    (),
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "Rewards",
+                        "ErasWorkPoints",
+                        ::subxt::ext::subxt_core::storage::address::StaticStorageKey::new(
+                            _0.borrow(),
+                        ),
+                    )
+                }
+                #[doc = " The points earned by indexers and attestors in each era that is not paid out yet."]
+                pub fn eras_work_points(
+                    &self,
+                    _0: impl ::core::borrow::Borrow<types::eras_work_points::Param0>,
+                    _1: impl ::core::borrow::Borrow<types::eras_work_points::Param1>,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    (
+                        ::subxt::ext::subxt_core::storage::address::StaticStorageKey<
+                            types::eras_work_points::Param0,
+                        >,
+                        ::subxt::ext::subxt_core::storage::address::StaticStorageKey<
+                            types::eras_work_points::Param1,
+                        >,
+                    ),
+                    types::eras_work_points::ErasWorkPoints,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    (),
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "Rewards",
+                        "ErasWorkPoints",
+                        (
+                            ::subxt::ext::subxt_core::storage::address::StaticStorageKey::new(
+                                _0.borrow(),
+                            ),
+                            ::subxt::ext::subxt_core::storage::address::StaticStorageKey::new(
+                                _1.borrow(),
+                            ),
+                        ),
+                    )
+                }
+                #[doc = " The total points earned by indexers and attestors in each era that is not paid out yet."]
+                pub fn eras_total_work_points_iter(
+                    &self,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    (),
+                    types::eras_total_work_points::ErasTotalWorkPoints,
+                    (),
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "Rewards",
+                        "ErasTotalWorkPoints",
+                        (),
+                    )
+                }
+                #[doc = " The total points earned by indexers and attestors in each era that is not paid out yet."]
+                pub fn eras_total_work_points(
+                    &self,
+                    _0: impl ::core::borrow::Borrow<types::eras_total_work_points::Param0>,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    ::subxt::ext::subxt_core::storage::address::StaticStorageKey<
+                        types::eras_total_work_points::Param0,
+                    >,
+                    types::eras_total_work_points::ErasTotalWorkPoints,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    (),
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "Rewards",
+                        "ErasTotalWorkPoints",
+                        ::subxt::ext::subxt_core::storage::address::StaticStorageKey::new(
+                            _0.borrow(),
+                        ),
+                    )
+                }
+                #[doc = " The amount shared by the indexers and attestors of an era, fixed when its payouts start."]
+                pub fn eras_work_budget_iter(
+                    &self,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    (),
+                    types::eras_work_budget::ErasWorkBudget,
+                    (),
+                    (),
+                    ::subxt::ext::subxt_core::utils::Yes,
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "Rewards",
+                        "ErasWorkBudget",
+                        (),
+                    )
+                }
+                #[doc = " The amount shared by the indexers and attestors of an era, fixed when its payouts start."]
+                pub fn eras_work_budget(
+                    &self,
+                    _0: impl ::core::borrow::Borrow<types::eras_work_budget::Param0>,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    ::subxt::ext::subxt_core::storage::address::StaticStorageKey<
+                        types::eras_work_budget::Param0,
+                    >,
+                    types::eras_work_budget::ErasWorkBudget,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    (),
+                    (),
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "Rewards",
+                        "ErasWorkBudget",
+                        ::subxt::ext::subxt_core::storage::address::StaticStorageKey::new(
+                            _0.borrow(),
+                        ),
+                    )
+                }
+                #[doc = " The next era that we expect to pay indexers and attestors for."]
+                pub fn next_work_paid_era(
+                    &self,
+                ) -> ::subxt::ext::subxt_core::storage::address::StaticAddress<
+                    (),
+                    types::next_work_paid_era::NextWorkPaidEra,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    ::subxt::ext::subxt_core::utils::Yes,
+                    (),
+                > {
+                    ::subxt::ext::subxt_core::storage::address::StaticAddress::new(
+                        "Rewards",
+                        "NextWorkPaidEra",
+                        (),
+                    )
+                }
+            }
+        }
+        pub mod constants {
+            use super::runtime_types;
+            pub struct ConstantsApi;
+            impl ConstantsApi {
+                #[doc = " The id of the pot account that pays indexers and attestors."]
+                pub fn pot_id(
+                    &self,
+                ) -> ::subxt::ext::subxt_core::constants::address::StaticAddress<
+                    runtime_types::frame_support::PalletId,
+                > {
+                    ::subxt::ext::subxt_core::constants::address::StaticAddress::new(
+                        "Rewards", "PotId",
+                    )
+                }
+                #[doc = " The most the pot pays indexers and attestors for one era."]
+                pub fn work_rewards_per_era(
+                    &self,
+                ) -> ::subxt::ext::subxt_core::constants::address::StaticAddress<
+                    ::core::primitive::u128,
+                > {
+                    ::subxt::ext::subxt_core::constants::address::StaticAddress::new(
+                        "Rewards",
+                        "WorkRewardsPerEra",
+                    )
+                }
+                #[doc = " The bytes of indexed data worth one point to each indexer agreeing in a quorum."]
+                pub fn indexed_bytes_per_point(
+                    &self,
+                ) -> ::subxt::ext::subxt_core::constants::address::StaticAddress<
+                    ::core::primitive::u64,
+                > {
+                    ::subxt::ext::subxt_core::constants::address::StaticAddress::new(
+                        "Rewards",
+                        "IndexedBytesPerPoint",
+                    )
+                }
+                #[doc = " The points earned by an attestor for a timely attestation."]
+                pub fn timely_attestation_points(
+                    &self,
+                ) -> ::subxt::ext::subxt_core::constants::address::StaticAddress<
+                    ::core::primitive::u64,
+                > {
+                    ::subxt::ext::subxt_core::constants::address::StaticAddress::new(
+                        "Rewards",
+                        "TimelyAttestationPoints",
+                    )
+                }
+                #[doc = " How many blocks after a block an attestation of it is still timely."]
+                pub fn attestation_deadline(
+                    &self,
+                ) -> ::subxt::ext::subxt_core::constants::address::StaticAddress<
+                    ::core::primitive::u32,
+                > {
+                    ::subxt::ext::subxt_core::constants::address::StaticAddress::new(
+                        "Rewards",
+                        "AttestationDeadline",
+                    )
+                }
             }
         }
     }
@@ -21250,6 +24250,18 @@ pub mod api {
                     }
                 }
             }
+            #[derive(
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                Debug,
+            )]
+            # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+            #[codec(dumb_trait_bound)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode")]
+            pub struct PalletId(pub [::core::primitive::u8; 8usize]);
         }
         pub mod frame_system {
             use super::runtime_types;
@@ -22790,6 +25802,12 @@ pub mod api {
                     #[codec(index = 26)]
                     #[doc = "Table identifier already exists in commitment storage."]
                     TableAlreadyExists,
+                    #[codec(index = 27)]
+                    #[doc = "Insert data is missing a primary key column."]
+                    InsertDataMissingPrimaryKeyColumn,
+                    #[codec(index = 28)]
+                    #[doc = "Insert data contains multiple rows with the same primary key."]
+                    InsertDataWithDuplicatePrimaryKey,
                 }
             }
         }
@@ -23474,6 +26492,83 @@ pub mod api {
                         >,
                         block_number: ::core::primitive::u64,
                     },
+                    #[codec(index = 2)]
+                    #[doc = "Submit the manifest of a batch that is too large for a single submission."]
+                    #[doc = ""]
+                    #[doc = "Quorum is reached on the hash of the manifest. Once it is reached, the batch awaits"]
+                    #[doc = "its chunks, which are uploaded with `submit_chunk` before `ChunkUploadTimeout` elapses."]
+                    submit_chunk_manifest {
+                        table: runtime_types::sxt_core::tables::TableIdentifier,
+                        batch_id: runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            ::core::primitive::u8,
+                        >,
+                        manifest: runtime_types::sxt_core::indexing::ChunkManifest<
+                            ::subxt::ext::subxt_core::utils::H256,
+                        >,
+                    },
+                    #[codec(index = 3)]
+                    #[doc = "Upload one chunk of a chunked batch whose manifest has reached quorum."]
+                    #[doc = ""]
+                    #[doc = "The upload that completes the batch commits every chunk in manifest order, and is"]
+                    #[doc = "charged for the combined length of all of them. If any chunk fails to be processed,"]
+                    #[doc = "none of them are committed and the batch is discarded."]
+                    submit_chunk {
+                        batch_id: runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            ::core::primitive::u8,
+                        >,
+                        chunk_index: ::core::primitive::u32,
+                        data: runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            ::core::primitive::u8,
+                        >,
+                    },
+                    #[codec(index = 5)]
+                    #[doc = "Submit only the hash of a data batch, without the data itself."]
+                    #[doc = ""]
+                    #[doc = "`row_data_hash` is the hash of the SCALE-encoded [`RowData`]. Quorum is reached on the"]
+                    #[doc = "hash of `row_data_hash` and `block_number` combined, see [`quorum_data_hash`], so"]
+                    #[doc = "hash-only submissions never count toward the quorum of submissions of the data itself."]
+                    #[doc = "Once reached, any permitted indexer publishes the data with `publish_data` before"]
+                    #[doc = "`PublicationTimeout` elapses."]
+                    submit_data_hash {
+                        table: runtime_types::sxt_core::tables::TableIdentifier,
+                        batch_id: runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            ::core::primitive::u8,
+                        >,
+                        row_data_hash: ::subxt::ext::subxt_core::utils::H256,
+                        block_number: ::core::primitive::u64,
+                    },
+                    #[codec(index = 6)]
+                    #[doc = "Publish the data of a hash-only quorum."]
+                    #[doc = ""]
+                    #[doc = "The data is committed to only if it matches the hash agreed upon by the quorum."]
+                    publish_data {
+                        batch_id: runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            ::core::primitive::u8,
+                        >,
+                        data: runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            ::core::primitive::u8,
+                        >,
+                    },
+                    #[codec(index = 4)]
+                    #[doc = "Submit data for several tables of the same source block under one batch id."]
+                    #[doc = ""]
+                    #[doc = "Quorum is reached on the hash of all tables' data combined, see [`quorum_data_hash`],"]
+                    #[doc = "using the strictest insert quorum of the tables involved. The submission is recorded"]
+                    #[doc = "under the first table, and a `DataSubmitted` event is emitted for every table. Once"]
+                    #[doc = "quorum is reached, every table is committed to in the order submitted. If any of them"]
+                    #[doc = "fails, none are committed."]
+                    submit_multi_table_data {
+                        batch_id: runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            ::core::primitive::u8,
+                        >,
+                        tables: runtime_types::bounded_collections::bounded_vec::BoundedVec<(
+                            runtime_types::sxt_core::tables::TableIdentifier,
+                            runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                                ::core::primitive::u8,
+                            >,
+                        )>,
+                        block_number: ::core::primitive::u64,
+                    },
                 }
                 #[derive(
                     :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
@@ -23546,6 +26641,37 @@ pub mod api {
                     #[codec(index = 17)]
                     #[doc = "Error deserializing the table as an OnChainTable"]
                     TableSerializationError,
+                    #[codec(index = 18)]
+                    #[doc = "There is no chunked batch awaiting chunks for this batch id"]
+                    UnknownChunkedBatch,
+                    #[codec(index = 19)]
+                    #[doc = "The chunk index is out of range for the manifest"]
+                    InvalidChunkIndex,
+                    #[codec(index = 20)]
+                    #[doc = "This chunk has already been uploaded"]
+                    ChunkAlreadySubmitted,
+                    #[codec(index = 21)]
+                    #[doc = "The chunk does not match the hash agreed upon in the manifest"]
+                    ChunkHashMismatch,
+                    #[codec(index = 22)]
+                    #[doc = "The same table appears more than once in a multi-table submission"]
+                    DuplicateTable,
+                    #[codec(index = 23)]
+                    #[doc = "There is no hash-only quorum awaiting publication for this batch id"]
+                    UnknownPendingPublication,
+                    #[codec(index = 24)]
+                    #[doc = "The published data does not match the hash agreed upon by the quorum"]
+                    PublishedDataHashMismatch,
+                    #[codec(index = 25)]
+                    #[doc = "Too many hash-only quorums are already set to expire in the same block"]
+                    TooManyPendingPublications,
+                    #[codec(index = 26)]
+                    #[doc = "Too many chunked batches are already set to expire in the same block"]
+                    TooManyPendingChunkedBatches,
+                    #[codec(index = 27)]
+                    #[doc = "The batch inserts more than `MAX_KEYED_ROWS_PER_BATCH` rows into a table that"]
+                    #[doc = "maintains a primary key index"]
+                    TooManyKeyedRows,
                 }
                 #[derive(
                     :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
@@ -23618,13 +26744,108 @@ pub mod api {
                                 ::subxt::ext::subxt_core::utils::AccountId32,
                             >,
                     },
-                }
-            }
-        }
-        pub mod pallet_keystore {
-            use super::runtime_types;
-            pub mod pallet {
-                use super::runtime_types;
+                    #[codec(index = 5)]
+                    #[doc = "A quorum has been reached on a chunk manifest. Its chunks can now be uploaded."]
+                    ChunkManifestQuorumReached {
+                        quorum: runtime_types::sxt_core::indexing::DataQuorum<
+                            ::subxt::ext::subxt_core::utils::AccountId32,
+                            ::subxt::ext::subxt_core::utils::H256,
+                        >,
+                        chunk_count: ::core::primitive::u32,
+                    },
+                    #[codec(index = 6)]
+                    #[doc = "A chunk of a pending chunked batch has been uploaded."]
+                    ChunkSubmitted {
+                        who: ::subxt::ext::subxt_core::utils::AccountId32,
+                        batch_id: runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            ::core::primitive::u8,
+                        >,
+                        chunk_index: ::core::primitive::u32,
+                    },
+                    #[codec(index = 7)]
+                    #[doc = "A multi-table submission has reached quorum and every table has been committed to."]
+                    #[doc = ""]
+                    #[doc = "A `QuorumReached` event is emitted for each table beforehand, in submission order."]
+                    MultiTableQuorumReached {
+                        batch_id: runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            ::core::primitive::u8,
+                        >,
+                        tables: runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            runtime_types::sxt_core::tables::TableIdentifier,
+                        >,
+                        block_number: ::core::primitive::u64,
+                    },
+                    #[codec(index = 8)]
+                    #[doc = "A quorum has been reached on a data hash. The data can now be published."]
+                    DataHashQuorumReached {
+                        quorum: runtime_types::sxt_core::indexing::DataQuorum<
+                            ::subxt::ext::subxt_core::utils::AccountId32,
+                            ::subxt::ext::subxt_core::utils::H256,
+                        >,
+                        row_data_hash: ::subxt::ext::subxt_core::utils::H256,
+                        deadline: ::core::primitive::u32,
+                    },
+                    #[codec(index = 9)]
+                    #[doc = "The data for a hash-only quorum has been published and committed to."]
+                    #[doc = ""]
+                    #[doc = "It is preceded by the usual `QuorumReached` or `QuorumEmptyBlock` event."]
+                    DataPublished {
+                        who: ::subxt::ext::subxt_core::utils::AccountId32,
+                        batch_id: runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            ::core::primitive::u8,
+                        >,
+                    },
+                    #[codec(index = 10)]
+                    #[doc = "No data was published for a hash-only quorum before its deadline."]
+                    #[doc = ""]
+                    #[doc = "The quorum is discarded and the batch id can be submitted to again."]
+                    PublicationExpired {
+                        table: runtime_types::sxt_core::tables::TableIdentifier,
+                        batch_id: runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            ::core::primitive::u8,
+                        >,
+                    },
+                    #[codec(index = 11)]
+                    #[doc = "Every chunk of a chunked batch has been uploaded and committed to."]
+                    #[doc = ""]
+                    #[doc = "A `QuorumReached` event is emitted for each chunk beforehand, in manifest order."]
+                    ChunkedBatchFinalized {
+                        table: runtime_types::sxt_core::tables::TableIdentifier,
+                        batch_id: runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            ::core::primitive::u8,
+                        >,
+                        chunk_count: ::core::primitive::u32,
+                    },
+                    #[codec(index = 12)]
+                    #[doc = "Not every chunk of a chunked batch was uploaded before its deadline."]
+                    #[doc = ""]
+                    #[doc = "The batch and its uploaded chunks are discarded and the batch id can be submitted to"]
+                    #[doc = "again."]
+                    ChunkedBatchExpired {
+                        table: runtime_types::sxt_core::tables::TableIdentifier,
+                        batch_id: runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            ::core::primitive::u8,
+                        >,
+                    },
+                    #[codec(index = 13)]
+                    #[doc = "Every chunk of a chunked batch was uploaded, but committing to them failed."]
+                    #[doc = ""]
+                    #[doc = "None of the chunks are committed. The batch and its chunks are discarded and the"]
+                    #[doc = "batch id can be submitted to again."]
+                    ChunkedBatchFailed {
+                        table: runtime_types::sxt_core::tables::TableIdentifier,
+                        batch_id: runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            ::core::primitive::u8,
+                        >,
+                        error: runtime_types::sp_runtime::DispatchError,
+                    },
+                }
+            }
+        }
+        pub mod pallet_keystore {
+            use super::runtime_types;
+            pub mod pallet {
+                use super::runtime_types;
                 #[derive(
                     :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
                     :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
@@ -24281,6 +27502,8 @@ pub mod api {
                     #[doc = "A payout of accrued rewards was made automatically by the system"]
                     Payout {
                         validator: ::subxt::ext::subxt_core::utils::AccountId32,
+                        era: ::core::primitive::u32,
+                        page: ::core::primitive::u32,
                     },
                     #[codec(index = 2)]
                     #[doc = "Events regarding status of payouts"]
@@ -24304,8 +27527,88 @@ pub mod api {
                     PayerUpdated {
                         payer: ::subxt::ext::subxt_core::utils::AccountId32,
                     },
+                    #[codec(index = 6)]
+                    #[doc = "An indexer or attestor was paid out of the pot for their work in an era"]
+                    WorkRewardPaid {
+                        era: ::core::primitive::u32,
+                        worker: ::subxt::ext::subxt_core::utils::AccountId32,
+                        amount: ::core::primitive::u128,
+                    },
+                    #[codec(index = 7)]
+                    #[doc = "An error occurred paying an indexer or attestor"]
+                    WorkRewardError {
+                        era: ::core::primitive::u32,
+                        worker: ::subxt::ext::subxt_core::utils::AccountId32,
+                        error: runtime_types::sp_runtime::DispatchError,
+                    },
+                    #[codec(index = 8)]
+                    #[doc = "The work of an era has been fully paid"]
+                    WorkEraPaid {
+                        era: ::core::primitive::u32,
+                        total_points: ::core::primitive::u64,
+                        budget: ::core::primitive::u128,
+                    },
+                }
+            }
+            pub mod runtime_api {
+                use super::runtime_types;
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
+                pub struct ExpectedWorkEarnings<_0> {
+                    pub era: ::core::primitive::u32,
+                    pub points: runtime_types::pallet_rewards::WorkPoints,
+                    pub total_points: ::core::primitive::u64,
+                    pub amount: _0,
+                }
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
+                pub struct PendingPayout<_0> {
+                    pub era: ::core::primitive::u32,
+                    pub validator: _0,
+                    pub pages: ::subxt::ext::subxt_core::alloc::vec::Vec<::core::primitive::u32>,
                 }
             }
+            #[derive(
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                Debug,
+            )]
+            # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+            #[codec(dumb_trait_bound)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode")]
+            pub struct WorkPoints {
+                pub indexing: ::core::primitive::u64,
+                pub attestation: ::core::primitive::u64,
+            }
         }
         pub mod pallet_session {
             use super::runtime_types;
@@ -24462,6 +27765,10 @@ pub mod api {
                     #[doc = "verifies it doesn’t already exist, emits a `SmartContractAdded` event, and registers any"]
                     #[doc = "associated indexing tables via `pallet_tables::create_tables_inner`."]
                     #[doc = ""]
+                    #[doc = "If the contract has an ABI and event details, the event tables are generated from the"]
+                    #[doc = "ABI. Supplied tables for those events must match the generated DDL. Without a DDL"]
+                    #[doc = "statement, the namespace and the missing event tables are created from the generated DDL."]
+                    #[doc = ""]
                     #[doc = "# Parameters"]
                     #[doc = "- `origin`: Must be either `Root` or a signed user with appropriate smart contract permissions."]
                     #[doc = "- `contract`: The [`Contract`] to be added. Can be a normal or proxy contract."]
@@ -24474,6 +27781,8 @@ pub mod api {
                     #[doc = ""]
                     #[doc = "# Errors"]
                     #[doc = "- [`Error::ExistingContractError`] — if a contract with the same `source` and `address` already exists."]
+                    #[doc = "- [`Error::InvalidAbi`] — if the event tables cannot be generated from the ABI."]
+                    #[doc = "- [`Error::DdlMismatch`] — if a supplied event table does not match the generated DDL."]
                     #[doc = "- Any error from:"]
                     #[doc = "    - [`pallet_permissions::Pallet::ensure_root_or_permissioned`] if origin is unauthorized."]
                     #[doc = "    - [`pallet_tables::Pallet::create_tables_inner`] if any table creation fails."]
@@ -24483,6 +27792,44 @@ pub mod api {
                             runtime_types::pallet_tables::pallet::UpdateTable,
                         >,
                     },
+                    #[codec(index = 3)]
+                    #[doc = "Updates a registered smart contract without dropping its tables."]
+                    #[doc = ""]
+                    #[doc = "The update can replace the ABI, append event details and upgrade a proxy to a new"]
+                    #[doc = "implementation. The tables of the appended events are generated from the ABI unless"]
+                    #[doc = "they are supplied, in which case they must match it. Only new tables are created; the"]
+                    #[doc = "existing tables of the contract and their commitments are left intact. New tables are"]
+                    #[doc = "indexed from the starting block of the new implementation, or of the contract."]
+                    #[doc = ""]
+                    #[doc = "# Parameters"]
+                    #[doc = "- `origin`: Must be either `Root` or a signed user with appropriate smart contract permissions."]
+                    #[doc = "- `source`: The `Source` identifier for the contract."]
+                    #[doc = "- `address`: The address of the smart contract."]
+                    #[doc = "- `update`: The [`ContractUpdate`] to apply."]
+                    #[doc = "- `tables`: Additional [`UpdateTable`] entries to create for this contract."]
+                    #[doc = ""]
+                    #[doc = "# Emits"]
+                    #[doc = "- [`Event::ImplementationUpgraded`] — when a proxy is upgraded to a new implementation."]
+                    #[doc = "- [`Event::SmartContractUpdated`] — when the contract is successfully updated."]
+                    #[doc = ""]
+                    #[doc = "# Errors"]
+                    #[doc = "- [`Error::ContractNotFound`] — if no contract exists for the `source` and `address`."]
+                    #[doc = "- [`Error::NotAProxy`] — if a new implementation is supplied for a normal contract."]
+                    #[doc = "- [`Error::InvalidStartingBlock`] — if the new implementation has no starting block, or"]
+                    #[doc = "  one before the previous implementation."]
+                    #[doc = "- [`Error::DuplicateEvent`] — if an appended event or its table is already indexed."]
+                    #[doc = "- [`Error::ExistingTable`] — if a table to create already exists."]
+                    #[doc = "- Any error from [`pallet_tables::Pallet::create_tables_inner`] if table creation fails."]
+                    update_smartcontract {
+                        source: runtime_types::sxt_core::tables::Source,
+                        address: runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            ::core::primitive::u8,
+                        >,
+                        update: runtime_types::sxt_core::smartcontracts::ContractUpdate,
+                        tables: runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            runtime_types::pallet_tables::pallet::UpdateTable,
+                        >,
+                    },
                 }
                 #[derive(
                     :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
@@ -24513,6 +27860,33 @@ pub mod api {
                     #[codec(index = 3)]
                     #[doc = "The smart contract is missing its ddl statement"]
                     MissingDdlStatement,
+                    #[codec(index = 4)]
+                    #[doc = "The event tables of the smart contract cannot be generated from its ABI"]
+                    InvalidAbi,
+                    #[codec(index = 5)]
+                    #[doc = "A supplied event table does not match the DDL generated from the ABI"]
+                    DdlMismatch,
+                    #[codec(index = 6)]
+                    #[doc = "No contract exists for the source and address you requested"]
+                    ContractNotFound,
+                    #[codec(index = 7)]
+                    #[doc = "Only proxy contracts can be upgraded to a new implementation"]
+                    NotAProxy,
+                    #[codec(index = 8)]
+                    #[doc = "A new implementation must have a starting block, no earlier than the previous one"]
+                    InvalidStartingBlock,
+                    #[codec(index = 9)]
+                    #[doc = "Too many implementations were recorded for this proxy contract"]
+                    TooManyImplementations,
+                    #[codec(index = 10)]
+                    #[doc = "Too many events were attempted to be indexed for this smart contract"]
+                    TooManyEvents,
+                    #[codec(index = 11)]
+                    #[doc = "An event or its table is already indexed for this smart contract"]
+                    DuplicateEvent,
+                    #[codec(index = 12)]
+                    #[doc = "A table to create for this smart contract already exists"]
+                    ExistingTable,
                 }
                 #[derive(
                     :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
@@ -24541,6 +27915,27 @@ pub mod api {
                         >,
                     },
                     #[codec(index = 1)]
+                    #[doc = "A smart contract was updated with new events or a new implementation."]
+                    SmartContractUpdated {
+                        owner: ::core::option::Option<::subxt::ext::subxt_core::utils::AccountId32>,
+                        source: runtime_types::sxt_core::tables::Source,
+                        address: runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            ::core::primitive::u8,
+                        >,
+                    },
+                    #[codec(index = 2)]
+                    #[doc = "A proxy contract was upgraded to a new implementation."]
+                    ImplementationUpgraded {
+                        source: runtime_types::sxt_core::tables::Source,
+                        address: runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            ::core::primitive::u8,
+                        >,
+                        implementation: runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            ::core::primitive::u8,
+                        >,
+                        starting_block: ::core::primitive::u64,
+                    },
+                    #[codec(index = 3)]
                     #[doc = "A smart contract was removed from storage."]
                     SmartContractRemoved {
                         owner: ::core::option::Option<::subxt::ext::subxt_core::utils::AccountId32>,
@@ -25789,6 +29184,37 @@ pub mod api {
         }
         pub mod pallet_system_tables {
             use super::runtime_types;
+            pub mod messages {
+                use super::runtime_types;
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
+
+                pub enum MessageType {
+                    #[codec(index = 0)]
+                    SetSessionKeys,
+                    #[codec(index = 1)]
+                    SetCommission,
+                    #[codec(index = 2)]
+                    Chill,
+                    #[codec(index = 3)]
+                    PurgeKeys,
+                    #[codec(index = 4)]
+                    SetPayee,
+                }
+            }
             pub mod pallet {
                 use super::runtime_types;
                 #[derive(
@@ -25814,7 +29240,414 @@ pub mod api {
                         eth_wallet: ::subxt::ext::subxt_core::alloc::string::String,
                         new_nonce: ::core::primitive::u64,
                     },
+                    #[codec(index = 1)]
+                    #[doc = "Register the template of a system table, replacing any previous template."]
+                    #[doc = ""]
+                    #[doc = "Every field must be a column of the table, as created in `pallet_tables`, with a"]
+                    #[doc = "matching type, and the fields read by the handler must be declared."]
+                    register_system_template {
+                        table: runtime_types::sxt_core::tables::TableIdentifier,
+                        template: runtime_types::pallet_system_tables::parse::SystemTemplate,
+                    },
+                    #[codec(index = 3)]
+                    #[doc = "Sudo call to set the account ZK-Pay payments are credited from, or to stop crediting"]
+                    #[doc = "payments when `None`"]
+                    set_zkpay_treasury {
+                        treasury:
+                            ::core::option::Option<::subxt::ext::subxt_core::utils::AccountId32>,
+                    },
+                    #[codec(index = 4)]
+                    #[doc = "Sudo call to set the nonce of the last ZK-Pay payment processed for a payer, to"]
+                    #[doc = "skip or replay payments"]
+                    set_zkpay_nonce {
+                        eth_wallet: ::subxt::ext::subxt_core::alloc::string::String,
+                        new_nonce: ::core::primitive::u64,
+                    },
+                    #[codec(index = 5)]
+                    #[doc = "Sudo call to credit a ZK-Pay payment that failed, once the treasury can cover it"]
+                    retry_zkpay_payment {
+                        eth_wallet: ::subxt::ext::subxt_core::alloc::string::String,
+                        nonce: ::core::primitive::u64,
+                    },
+                    #[codec(index = 2)]
+                    #[doc = "Remove the template of a system table, so inserts into it are no longer processed"]
+                    remove_system_template {
+                        table: runtime_types::sxt_core::tables::TableIdentifier,
+                    },
+                }
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
+                #[doc = "The `Error` enum of this pallet."]
+                pub enum Error {
+                    #[codec(index = 0)]
+                    #[doc = "The system table insert was missing an expected field for the supplied table identifier"]
+                    MissingExpectedField,
+                    #[codec(index = 1)]
+                    #[doc = "The field expected was present, but it was not the expected type represenation"]
+                    IncorrectFieldType,
+                    #[codec(index = 2)]
+                    #[doc = "Catchall error for sanity checks in parsing (i.e. request was passed to the wrong function)"]
+                    InternalError,
+                    #[codec(index = 3)]
+                    #[doc = "The signature of the message was corrupt or invalid"]
+                    InvalidSignature,
+                    #[codec(index = 4)]
+                    #[doc = "The address provided is not the address that signed the message"]
+                    AddressMismatch,
+                    #[codec(index = 5)]
+                    #[doc = "This nonce has already been processed"]
+                    LateNonce,
+                    #[codec(index = 6)]
+                    #[doc = "This nonce is too far in the future"]
+                    FutureNonce,
+                    #[codec(index = 7)]
+                    #[doc = "This message is malformed"]
+                    InvalidMessageFormat,
+                    #[codec(index = 8)]
+                    #[doc = "The session keys provided are malformed"]
+                    InvalidSessionKeys,
+                    #[codec(index = 9)]
+                    #[doc = "The provided validator proof couldn't be verified"]
+                    InvalidValidatorProof,
+                    #[codec(index = 10)]
+                    #[doc = "Error parsing the list of nominated nodes from the message request"]
+                    ErrorParsingNominations,
+                    #[codec(index = 11)]
+                    #[doc = "Empty Nomination Set"]
+                    EmptyNominationSet,
+                    #[codec(index = 12)]
+                    #[doc = "The message envelope version is not supported"]
+                    UnsupportedMessageVersion,
+                    #[codec(index = 13)]
+                    #[doc = "The message can only be sent by a validator"]
+                    NotValidator,
+                    #[codec(index = 14)]
+                    #[doc = "The message can only be sent by a bonded staker"]
+                    NotBonded,
+                    #[codec(index = 15)]
+                    #[doc = "The commission is lower than the minimum commission of the staking pallet"]
+                    CommissionTooLow,
+                    #[codec(index = 16)]
+                    #[doc = "The reward destination is not a valid account"]
+                    InvalidPayee,
+                    #[codec(index = 17)]
+                    #[doc = "The table of the system template doesn't exist"]
+                    TemplateTableNotFound,
+                    #[codec(index = 18)]
+                    #[doc = "The create statement of the template's table couldn't be parsed"]
+                    TemplateSchemaParseError,
+                    #[codec(index = 19)]
+                    #[doc = "A field of the system template is not a column of the table"]
+                    TemplateColumnNotFound,
+                    #[codec(index = 20)]
+                    #[doc = "A field of the system template doesn't match the type of its column"]
+                    TemplateColumnTypeMismatch,
+                    #[codec(index = 21)]
+                    #[doc = "The system template declares the same field more than once"]
+                    DuplicateTemplateField,
+                    #[codec(index = 22)]
+                    #[doc = "The system template lacks a field its handler reads"]
+                    MissingTemplateField,
+                    #[codec(index = 23)]
+                    #[doc = "There is no system template for the table"]
+                    TemplateNotFound,
+                    #[codec(index = 24)]
+                    #[doc = "The ZK-Pay payment amount is zero or doesn't fit a balance"]
+                    InvalidZkPayAmount,
+                    #[codec(index = 25)]
+                    #[doc = "The staking row identifies its EVM event partially, or with invalid values"]
+                    InvalidStakingEventSource,
+                    #[codec(index = 26)]
+                    #[doc = "There is no ZK-Pay treasury to credit payments from"]
+                    ZkPayTreasuryNotSet,
+                    #[codec(index = 27)]
+                    #[doc = "There is no failed ZK-Pay payment with this payer and nonce"]
+                    ZkPayPaymentNotFound,
+                }
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
+                #[doc = "The `Event` enum of this pallet"]
+                pub enum Event {
+                    #[codec(index = 0)]
+                    #[doc = "This event is emitted whenever a message is received by Substrate from the EVM"]
+                    MessageReceived {
+                        sender: [::core::primitive::u8; 20usize],
+                        message_type: runtime_types::pallet_system_tables::messages::MessageType,
+                        payload: ::subxt::ext::subxt_core::alloc::vec::Vec<::core::primitive::u8>,
+                    },
+                    #[codec(index = 1)]
+                    #[doc = "There was an error processing an evm message or system table insert"]
+                    MessageProcessingError {
+                        error: runtime_types::sp_runtime::DispatchError,
+                        message_type: ::core::option::Option<
+                            runtime_types::pallet_system_tables::messages::MessageType,
+                        >,
+                    },
+                    #[codec(index = 2)]
+                    #[doc = "Emitted when a validator is chilled by the offence handler"]
+                    ValidatorForceChilled {
+                        validator: ::subxt::ext::subxt_core::utils::AccountId32,
+                    },
+                    #[codec(index = 3)]
+                    #[doc = "A system template was registered, replacing any previous template of the table"]
+                    SystemTemplateRegistered {
+                        table: runtime_types::sxt_core::tables::TableIdentifier,
+                        request_type: runtime_types::pallet_system_tables::parse::SystemRequestType,
+                    },
+                    #[codec(index = 4)]
+                    #[doc = "A system template was removed, inserts into the table are no longer processed"]
+                    SystemTemplateRemoved {
+                        table: runtime_types::sxt_core::tables::TableIdentifier,
+                    },
+                    #[codec(index = 5)]
+                    #[doc = "A ZK-Pay payment was credited to the account of its payer"]
+                    ZkPayCredited {
+                        sender: [::core::primitive::u8; 20usize],
+                        account: ::subxt::ext::subxt_core::utils::AccountId32,
+                        amount: ::core::primitive::u128,
+                        nonce: runtime_types::primitive_types::U256,
+                        treasury: ::subxt::ext::subxt_core::utils::AccountId32,
+                    },
+                    #[codec(index = 6)]
+                    #[doc = "A ZK-Pay payment could not be credited. Its nonce is used all the same. Payments with"]
+                    #[doc = "a valid amount are kept in `FailedZkPayments` to be retried with"]
+                    #[doc = "`retry_zkpay_payment`."]
+                    ZkPayFailed {
+                        sender: [::core::primitive::u8; 20usize],
+                        nonce: runtime_types::primitive_types::U256,
+                        error: runtime_types::sp_runtime::DispatchError,
+                    },
+                    #[codec(index = 7)]
+                    #[doc = "The ZK-Pay treasury was set or cleared"]
+                    ZkPayTreasurySet {
+                        treasury:
+                            ::core::option::Option<::subxt::ext::subxt_core::utils::AccountId32>,
+                    },
+                    #[codec(index = 8)]
+                    #[doc = "A staking row was skipped or applied out of order, and the staking state may need"]
+                    #[doc = "to be reconciled with the EVM contract"]
+                    SystemTableError {
+                        table: runtime_types::sxt_core::tables::TableIdentifier,
+                        staker: ::subxt::ext::subxt_core::utils::AccountId32,
+                        source: runtime_types::pallet_system_tables::parse::StakingEventSource,
+                        issue: runtime_types::pallet_system_tables::parse::StakingEventIssue,
+                    },
+                }
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
+                pub struct FailedZkPayment {
+                    pub sender: [::core::primitive::u8; 20usize],
+                    pub amount: ::core::primitive::u128,
+                }
+            }
+            pub mod parse {
+                use super::runtime_types;
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
+                pub enum StakingEventIssue {
+                    #[codec(index = 0)]
+                    Duplicate,
+                    #[codec(index = 1)]
+                    OutOfOrder {
+                        last: runtime_types::pallet_system_tables::parse::StakingEventSource,
+                    },
+                    #[codec(index = 2)]
+                    Expired {
+                        last: runtime_types::pallet_system_tables::parse::StakingEventSource,
+                    },
+                }
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
+                pub struct StakingEventSource {
+                    pub block_number: ::core::primitive::u64,
+                    pub transaction_hash: [::core::primitive::u8; 32usize],
+                    pub event_index: ::core::primitive::u32,
+                }
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
+                pub enum StakingSystemRequest {
+                    #[codec(index = 0)]
+                    Stake,
+                    #[codec(index = 1)]
+                    Nominate,
+                    #[codec(index = 2)]
+                    UnstakeInitiated,
+                    #[codec(index = 3)]
+                    UnstakeCancelled,
+                }
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
+                pub enum SystemFieldType {
+                    #[codec(index = 0)]
+                    Varchar,
+                    #[codec(index = 1)]
+                    Bytes,
+                    #[codec(index = 2)]
+                    Decimal,
+                    #[codec(index = 3)]
+                    Int,
+                    #[codec(index = 4)]
+                    BigInt,
+                }
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
+                pub enum SystemRequestType {
+                    #[codec(index = 0)]
+                    Message,
+                    #[codec(index = 1)]
+                    Staking(runtime_types::pallet_system_tables::parse::StakingSystemRequest),
+                    #[codec(index = 2)]
+                    ZkPay,
+                }
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
+                pub struct SystemTemplate {
+                    pub request_type: runtime_types::pallet_system_tables::parse::SystemRequestType,
+                    pub fields: runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                        runtime_types::pallet_system_tables::parse::TemplateField,
+                    >,
+                }
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
+                pub struct TemplateField {
+                    pub name: runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                        ::core::primitive::u8,
+                    >,
+                    pub field_type: runtime_types::pallet_system_tables::parse::SystemFieldType,
                 }
+            }
+            pub mod runtime_api {
+                use super::runtime_types;
                 #[derive(
                     :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
                     :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
@@ -25830,44 +29663,16 @@ pub mod api {
                 #[encode_as_type(
                     crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
                 )]
-                #[doc = "The `Error` enum of this pallet."]
-                pub enum Error {
-                    #[codec(index = 0)]
-                    #[doc = "The system table insert was missing an expected field for the supplied table identifier"]
-                    MissingExpectedField,
-                    #[codec(index = 1)]
-                    #[doc = "The field expected was present, but it was not the expected type represenation"]
-                    IncorrectFieldType,
-                    #[codec(index = 2)]
-                    #[doc = "Catchall error for sanity checks in parsing (i.e. request was passed to the wrong function)"]
-                    InternalError,
-                    #[codec(index = 3)]
-                    #[doc = "The signature of the message was corrupt or invalid"]
-                    InvalidSignature,
-                    #[codec(index = 4)]
-                    #[doc = "The address provided is not the address that signed the message"]
-                    AddressMismatch,
-                    #[codec(index = 5)]
-                    #[doc = "This nonce has already been processed"]
-                    LateNonce,
-                    #[codec(index = 6)]
-                    #[doc = "This nonce is too far in the future"]
-                    FutureNonce,
-                    #[codec(index = 7)]
-                    #[doc = "This message is malformed"]
-                    InvalidMessageFormat,
-                    #[codec(index = 8)]
-                    #[doc = "The session keys provided are malformed"]
-                    InvalidSessionKeys,
-                    #[codec(index = 9)]
-                    #[doc = "The provided validator proof couldn't be verified"]
-                    InvalidValidatorProof,
-                    #[codec(index = 10)]
-                    #[doc = "Error parsing the list of nominated nodes from the message request"]
-                    ErrorParsingNominations,
-                    #[codec(index = 11)]
-                    #[doc = "Empty Nomination Set"]
-                    EmptyNominationSet,
+                pub struct MirroredStaker<_0> {
+                    pub address: [::core::primitive::u8; 20usize],
+                    pub account: _0,
+                    pub bonded: ::core::primitive::u128,
+                    pub active: ::core::primitive::u128,
+                    pub unbonding: ::subxt::ext::subxt_core::alloc::vec::Vec<
+                        runtime_types::pallet_system_tables::runtime_api::MirroredUnbonding,
+                    >,
+                    pub nominations: ::subxt::ext::subxt_core::alloc::vec::Vec<_0>,
+                    pub validating: ::core::primitive::bool,
                 }
                 #[derive(
                     :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
@@ -25884,24 +29689,9 @@ pub mod api {
                 #[encode_as_type(
                     crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
                 )]
-                #[doc = "The `Event` enum of this pallet"]
-                pub enum Event {
-                    #[codec(index = 0)]
-                    #[doc = "This event is emitted whenever a message is received by Substrate from the EVM"]
-                    MessageReceived {
-                        sender: [::core::primitive::u8; 20usize],
-                        payload: ::subxt::ext::subxt_core::alloc::vec::Vec<::core::primitive::u8>,
-                    },
-                    #[codec(index = 1)]
-                    #[doc = "There was an error processing an evm message"]
-                    MessageProcessingError {
-                        error: runtime_types::sp_runtime::DispatchError,
-                    },
-                    #[codec(index = 2)]
-                    #[doc = "Emitted when a validator is chilled by the offence handler"]
-                    ValidatorForceChilled {
-                        validator: ::subxt::ext::subxt_core::utils::AccountId32,
-                    },
+                pub struct MirroredUnbonding {
+                    pub value: ::core::primitive::u128,
+                    pub era: ::core::primitive::u32,
                 }
             }
         }
@@ -25971,6 +29761,49 @@ pub mod api {
                     drop_invalid_commits {
                         ident: runtime_types::sxt_core::tables::TableIdentifier,
                     },
+                    #[codec(index = 7)]
+                    #[doc = "Update the UUID for the specificed namespace and version to the provided UUID"]
+                    update_namespace_uuid {
+                        namespace: runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            ::core::primitive::u8,
+                        >,
+                        version: ::core::primitive::u16,
+                        new_uuid: runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            ::core::primitive::u8,
+                        >,
+                    },
+                    #[codec(index = 8)]
+                    #[doc = "Update the UUID for the specified table and version to the provided UUID"]
+                    update_table_uuid {
+                        table: runtime_types::sxt_core::tables::TableIdentifier,
+                        version: ::core::primitive::u16,
+                        new_uuid: runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            ::core::primitive::u8,
+                        >,
+                    },
+                    #[codec(index = 9)]
+                    #[doc = "Override the insert quorum of an existing table, which otherwise defaults to the"]
+                    #[doc = "quorum of its table type"]
+                    set_table_insert_quorum {
+                        table: runtime_types::sxt_core::tables::TableIdentifier,
+                        quorum: runtime_types::sxt_core::tables::InsertQuorumSize,
+                    },
+                    #[codec(index = 10)]
+                    #[doc = "Index the primary keys of rows that were not inserted through the indexing pallet."]
+                    #[doc = ""]
+                    #[doc = "Inserted rows are indexed as they are committed to, but snapshot rows never pass"]
+                    #[doc = "through the chain, and rows of tables created before keys were indexed were never"]
+                    #[doc = "indexed. Their keys are supplied here along with their row numbers, so that later"]
+                    #[doc = "inserts are checked against them. Large tables are indexed over several calls."]
+                    index_snapshot_primary_keys {
+                        table: runtime_types::sxt_core::tables::TableIdentifier,
+                        keys: runtime_types::bounded_collections::bounded_vec::BoundedVec<(
+                            runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                                ::core::primitive::u8,
+                            >,
+                            ::core::primitive::u64,
+                        )>,
+                    },
                 }
                 #[derive(
                     :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
@@ -26073,6 +29906,18 @@ pub mod api {
                     #[codec(index = 16)]
                     #[doc = "There was an error generating a uuid"]
                     UUIDGenerationError,
+                    #[codec(index = 17)]
+                    #[doc = "The primary key or ON_DUPLICATE_KEY option of the DDL statement is invalid"]
+                    InvalidPrimaryKeyConstraint,
+                    #[codec(index = 18)]
+                    #[doc = "A row with the same primary key already exists in the table"]
+                    DuplicatePrimaryKey,
+                    #[codec(index = 19)]
+                    #[doc = "Not all primary key constraints were removed"]
+                    NotAllPrimaryKeyConstraintsRemovedError,
+                    #[codec(index = 20)]
+                    #[doc = "The key index of a dropped table with the same identifier is still being removed"]
+                    PrimaryKeyIndexRemovalPending,
                 }
                 #[derive(
                     :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
@@ -26105,6 +29950,32 @@ pub mod api {
                         source: runtime_types::sxt_core::tables::Source,
                     },
                     #[codec(index = 1)]
+                    #[doc = "The UUID for a given namespace has been updated"]
+                    NamespaceUuidUpdated {
+                        old_uuid: runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            ::core::primitive::u8,
+                        >,
+                        new_uuid: runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            ::core::primitive::u8,
+                        >,
+                        version: ::core::primitive::u16,
+                        namespace: runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            ::core::primitive::u8,
+                        >,
+                    },
+                    #[codec(index = 2)]
+                    #[doc = "The UUID for a given table has been updated"]
+                    TableUuidUpdated {
+                        old_uuid: runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            ::core::primitive::u8,
+                        >,
+                        new_uuid: runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            ::core::primitive::u8,
+                        >,
+                        version: ::core::primitive::u16,
+                        table: runtime_types::sxt_core::tables::TableIdentifier,
+                    },
+                    #[codec(index = 3)]
                     #[doc = "The schema for a table has been updated"]
                     SchemaUpdated(
                         ::core::option::Option<::subxt::ext::subxt_core::utils::AccountId32>,
@@ -26112,7 +29983,7 @@ pub mod api {
                             runtime_types::pallet_tables::pallet::UpdateTable,
                         >,
                     ),
-                    #[codec(index = 2)]
+                    #[codec(index = 4)]
                     #[doc = "Tables have been created with known commitments"]
                     TablesCreatedWithCommitments {
                         source_and_mode: runtime_types::sxt_core::tables::SourceAndMode,
@@ -26120,7 +29991,7 @@ pub mod api {
                             runtime_types::pallet_tables::pallet::CreateTableRequest,
                         >,
                     },
-                    #[codec(index = 3)]
+                    #[codec(index = 5)]
                     #[doc = "A table has been successfully dropped"]
                     TableDropped(
                         ::core::option::Option<::subxt::ext::subxt_core::utils::AccountId32>,
@@ -26128,6 +29999,18 @@ pub mod api {
                         runtime_types::sxt_core::tables::TableIdentifier,
                         runtime_types::sxt_core::tables::Source,
                     ),
+                    #[codec(index = 6)]
+                    #[doc = "The insert quorum for a table has been updated"]
+                    TableInsertQuorumUpdated {
+                        table: runtime_types::sxt_core::tables::TableIdentifier,
+                        quorum: runtime_types::sxt_core::tables::InsertQuorumSize,
+                    },
+                    #[codec(index = 7)]
+                    #[doc = "Primary keys of rows loaded from a table's snapshot have been indexed"]
+                    SnapshotPrimaryKeysIndexed {
+                        table: runtime_types::sxt_core::tables::TableIdentifier,
+                        count: ::core::primitive::u32,
+                    },
                 }
                 #[derive(
                     :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
@@ -28641,6 +32524,26 @@ pub mod api {
                 #[encode_as_type(
                     crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
                 )]
+                pub struct ChunkManifest<_0> {
+                    pub chunk_hashes:
+                        runtime_types::bounded_collections::bounded_vec::BoundedVec<_0>,
+                    pub block_number: ::core::option::Option<::core::primitive::u64>,
+                }
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
                 pub struct DataQuorum<_0, _1> {
                     pub table: runtime_types::sxt_core::tables::TableIdentifier,
                     pub batch_id: runtime_types::bounded_collections::bounded_vec::BoundedVec<
@@ -28692,6 +32595,48 @@ pub mod api {
                 #[encode_as_type(
                     crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
                 )]
+                pub struct PendingChunkedBatch<_0, _1> {
+                    pub quorum: runtime_types::sxt_core::indexing::DataQuorum<_0, _1>,
+                    pub manifest: runtime_types::sxt_core::indexing::ChunkManifest<_1>,
+                    pub uploaded_chunks: ::core::primitive::u32,
+                    pub uploaded_len: ::core::primitive::u32,
+                }
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
+                pub struct PendingPublication<_0, _1, _2> {
+                    pub quorum: runtime_types::sxt_core::indexing::DataQuorum<_0, _1>,
+                    pub row_data_hash: _1,
+                    pub block_number: ::core::primitive::u64,
+                    pub deadline: _2,
+                }
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
                 pub struct SubmittersByScope<_0> {
                     pub public:
                         runtime_types::bounded_collections::bounded_btree_set::BoundedBTreeSet<_0>,
@@ -28999,6 +32944,36 @@ pub mod api {
                 #[encode_as_type(
                     crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
                 )]
+                pub struct ContractUpdate {
+                    pub abi: ::core::option::Option<
+                        runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            ::core::primitive::u8,
+                        >,
+                    >,
+                    pub event_details: ::core::option::Option<
+                        runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            runtime_types::sxt_core::smartcontracts::EventDetails,
+                        >,
+                    >,
+                    pub implementation: ::core::option::Option<
+                        runtime_types::sxt_core::smartcontracts::ImplementationContract,
+                    >,
+                }
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
                 pub struct EventDetails {
                     pub name: runtime_types::bounded_collections::bounded_vec::BoundedVec<
                         ::core::primitive::u8,
@@ -29043,6 +33018,27 @@ pub mod api {
                 #[encode_as_type(
                     crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
                 )]
+                pub struct ImplementationHistoryEntry {
+                    pub address: runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                        ::core::primitive::u8,
+                    >,
+                    pub starting_block: ::core::option::Option<::core::primitive::u64>,
+                }
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
                 pub struct NormalContract {
                     pub details: runtime_types::sxt_core::smartcontracts::ContractDetails,
                 }
@@ -29129,6 +33125,25 @@ pub mod api {
                 #[encode_as_type(
                     crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
                 )]
+                pub enum DuplicateKeyPolicy {
+                    #[codec(index = 0)]
+                    Reject,
+                }
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
                 pub enum IndexerMode {
                     #[codec(index = 0)]
                     Core,
@@ -29183,6 +33198,30 @@ pub mod api {
                 #[encode_as_type(
                     crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
                 )]
+                pub struct PrimaryKeyConstraint {
+                    pub columns: runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                        runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            ::core::primitive::u8,
+                        >,
+                    >,
+                    pub on_duplicate:
+                        ::core::option::Option<runtime_types::sxt_core::tables::DuplicateKeyPolicy>,
+                }
+                #[derive(
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Decode,
+                    :: subxt :: ext :: subxt_core :: ext :: codec :: Encode,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: subxt_core :: ext :: scale_encode :: EncodeAsType,
+                    Debug,
+                )]
+                # [codec (crate = :: subxt :: ext :: subxt_core :: ext :: codec)]
+                #[codec(dumb_trait_bound)]
+                #[decode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_decode"
+                )]
+                #[encode_as_type(
+                    crate_path = ":: subxt :: ext :: subxt_core :: ext :: scale_encode"
+                )]
                 pub enum QuorumScope {
                     #[codec(index = 0)]
                     Public,
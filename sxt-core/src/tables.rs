@@ -19,7 +19,7 @@ use sp_core::{blake2_256, RuntimeDebug, U256};
 use sp_runtime::DispatchError;
 use sp_runtime_interface::pass_by::PassByCodec;
 use sqlparser::ast::helpers::stmt_create_table::CreateTableBuilder;
-use sqlparser::ast::{Expr, ObjectName, SqlOption, Value};
+use sqlparser::ast::{Expr, ObjectName, SqlOption, TableConstraint, Value};
 use sqlparser::dialect::PostgreSqlDialect;
 use sqlparser::parser::Parser;
 
@@ -299,6 +299,142 @@ pub type ForeignKey = ByteString;
 /// TODO: add docs
 pub type ForeignKeys = BoundedVec<ForeignKey, ConstU32<MAX_FOREIGN_KEYS>>;
 
+/// Name of the table `WITH` option that selects the [`DuplicateKeyPolicy`].
+pub const ON_DUPLICATE_KEY_OPTION: &str = "ON_DUPLICATE_KEY";
+
+/// How inserts of a primary key that already exists in a table are handled.
+#[derive(
+    Copy,
+    Clone,
+    Encode,
+    Decode,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+    Serialize,
+    Deserialize,
+)]
+pub enum DuplicateKeyPolicy {
+    /// Reject the entire insert.
+    ///
+    /// This is currently the only policy. Commitments are append-only, so a policy that replaces
+    /// the row stored under a key would leave the replaced row committed.
+    Reject,
+}
+
+/// The declared primary key of a table and how re-inserted keys are handled.
+#[derive(
+    Clone,
+    Encode,
+    Decode,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+    Serialize,
+    Deserialize,
+)]
+pub struct PrimaryKeyConstraint {
+    /// The uppercase names of the primary key columns.
+    pub columns: PrimaryKeys,
+    /// The policy for keys that already exist in the table.
+    ///
+    /// `None` only enforces uniqueness within each insert, and no key index is maintained.
+    pub on_duplicate: Option<DuplicateKeyPolicy>,
+}
+
+/// Errors that can occur when reading the primary key constraint of a table definition.
+#[derive(Snafu, Debug, PartialEq, Eq)]
+pub enum PrimaryKeyConstraintError {
+    /// Table declares more than one primary key.
+    #[snafu(display("table declares more than one primary key"))]
+    MultiplePrimaryKeys,
+    /// Primary key has too many columns, or a column name that is too long.
+    #[snafu(display("primary key exceeds maximum size"))]
+    PrimaryKeyTooLarge,
+    /// The `ON_DUPLICATE_KEY` option has an unknown value.
+    #[snafu(display("unknown ON_DUPLICATE_KEY value: {value}"))]
+    UnknownDuplicateKeyPolicy {
+        /// The unrecognized value.
+        value: String,
+    },
+    /// The `ON_DUPLICATE_KEY` option requests upserts, which commitments cannot express.
+    #[snafu(display("ON_DUPLICATE_KEY=upsert is not supported, commitments cannot replace rows"))]
+    UpsertNotSupported,
+    /// The `ON_DUPLICATE_KEY` option is set on a table without a primary key.
+    #[snafu(display("ON_DUPLICATE_KEY requires a primary key"))]
+    DuplicateKeyPolicyWithoutPrimaryKey,
+}
+
+/// Reads the primary key constraint of a table definition.
+///
+/// The primary key comes from the table-level `PRIMARY KEY (..)` constraint, and the
+/// [`DuplicateKeyPolicy`] from the `ON_DUPLICATE_KEY` option of the `WITH` clause, which accepts
+/// `reject`. Returns `None` if the table has no primary key.
+pub fn primary_key_constraint_from_sqlparser(
+    create_table: &CreateTableBuilder,
+) -> Result<Option<PrimaryKeyConstraint>, PrimaryKeyConstraintError> {
+    let mut primary_keys =
+        create_table
+            .constraints
+            .iter()
+            .filter_map(|constraint| match constraint {
+                TableConstraint::Unique {
+                    columns,
+                    is_primary: true,
+                    ..
+                } => Some(columns),
+                _ => None,
+            });
+
+    let primary_key = primary_keys.next();
+    if primary_keys.next().is_some() {
+        return Err(PrimaryKeyConstraintError::MultiplePrimaryKeys);
+    }
+
+    let on_duplicate = create_table
+        .with_options
+        .iter()
+        .find(|option| {
+            option
+                .name
+                .value
+                .eq_ignore_ascii_case(ON_DUPLICATE_KEY_OPTION)
+        })
+        .map(|option| {
+            let value = option.value.to_string();
+            match value.trim_matches('\'').to_lowercase().as_str() {
+                "reject" => Ok(DuplicateKeyPolicy::Reject),
+                "upsert" => Err(PrimaryKeyConstraintError::UpsertNotSupported),
+                _ => Err(PrimaryKeyConstraintError::UnknownDuplicateKeyPolicy { value }),
+            }
+        })
+        .transpose()?;
+
+    let Some(primary_key) = primary_key else {
+        return match on_duplicate {
+            Some(_) => Err(PrimaryKeyConstraintError::DuplicateKeyPolicyWithoutPrimaryKey),
+            None => Ok(None),
+        };
+    };
+
+    let columns = primary_key
+        .iter()
+        .map(|column| PrimaryKey::try_from(column.value.to_uppercase().into_bytes()))
+        .collect::<Result<Vec<_>, _>>()
+        .ok()
+        .and_then(|columns| PrimaryKeys::try_from(columns).ok())
+        .ok_or(PrimaryKeyConstraintError::PrimaryKeyTooLarge)?;
+
+    Ok(Some(PrimaryKeyConstraint {
+        columns,
+        on_duplicate,
+    }))
+}
+
 /// TODO: add docs
 pub const CREATE_STMNT_LENGTH: u32 = 8192;
 /// TODO: add docs
@@ -362,6 +498,16 @@ impl InsertQuorumSize {
             QuorumScope::Privileged => &self.privileged,
         }
     }
+
+    /// Returns the quorum size that satisfies both `self` and `other`.
+    ///
+    /// A scope is only enabled if it is enabled in both, and requires the larger of the two sizes.
+    pub fn strictest(&self, other: &InsertQuorumSize) -> InsertQuorumSize {
+        InsertQuorumSize {
+            public: self.public.zip(other.public).map(|(a, b)| a.max(b)),
+            privileged: self.privileged.zip(other.privileged).map(|(a, b)| a.max(b)),
+        }
+    }
 }
 
 /// A table commitment
@@ -982,6 +1128,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn we_can_get_strictest_quorum_size() {
+        let a = InsertQuorumSize {
+            public: Some(3),
+            privileged: Some(0),
+        };
+        let b = InsertQuorumSize {
+            public: Some(1),
+            privileged: None,
+        };
+
+        let expected = InsertQuorumSize {
+            public: Some(3),
+            privileged: None,
+        };
+        assert_eq!(a.strictest(&b), expected);
+        assert_eq!(b.strictest(&a), expected);
+        assert_eq!(a.strictest(&a), a);
+    }
+
     #[test]
     fn we_can_convert_table_identifier_to_string() {
         let table_identifier = TableIdentifier {
@@ -1077,6 +1243,94 @@ mod tests {
         let expected = "CREATE TABLE SOUTH.BOOK (ID INT NOT NULL, NAME VARCHAR NOT NULL, PRIMARY KEY (ID, NAME)) WITH (access_type = public_read, immutable = true, public_key = A1D9C617F01C9975117B3D605CD4F945853E263D6E52888EE6E3AF5CB0FA1026, TABLE_UUID = TESTUUID)";
         assert_eq!(expected, from_utf8(&result_statement).unwrap());
     }
+
+    fn primary_key_constraint_of(
+        sql: &str,
+    ) -> Result<Option<PrimaryKeyConstraint>, PrimaryKeyConstraintError> {
+        let create_table = create_statement_to_sqlparser(
+            CreateStatement::try_from(sql.as_bytes().to_vec()).unwrap(),
+        )
+        .unwrap();
+
+        primary_key_constraint_from_sqlparser(&create_table)
+    }
+
+    #[test]
+    fn we_can_read_primary_key_constraint_from_ddl_statement() {
+        let expected_columns = PrimaryKeys::try_from(vec![
+            PrimaryKey::try_from(b"ID".to_vec()).unwrap(),
+            PrimaryKey::try_from(b"NAME".to_vec()).unwrap(),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            primary_key_constraint_of(
+                "CREATE TABLE SOUTH.BOOK (id INT NOT NULL, name VARCHAR NOT NULL, PRIMARY KEY (id, name))"
+            ),
+            Ok(Some(PrimaryKeyConstraint {
+                columns: expected_columns.clone(),
+                on_duplicate: None,
+            }))
+        );
+
+        assert_eq!(
+            primary_key_constraint_of(
+                "CREATE TABLE SOUTH.BOOK (ID INT NOT NULL, NAME VARCHAR NOT NULL, PRIMARY KEY (ID, NAME)) WITH (access_type=public_read, on_duplicate_key=reject)"
+            ),
+            Ok(Some(PrimaryKeyConstraint {
+                columns: expected_columns.clone(),
+                on_duplicate: Some(DuplicateKeyPolicy::Reject),
+            }))
+        );
+
+        assert_eq!(
+            primary_key_constraint_of(
+                "CREATE TABLE SOUTH.BOOK (ID INT NOT NULL, NAME VARCHAR NOT NULL, PRIMARY KEY (ID, NAME)) WITH (ON_DUPLICATE_KEY='REJECT')"
+            ),
+            Ok(Some(PrimaryKeyConstraint {
+                columns: expected_columns,
+                on_duplicate: Some(DuplicateKeyPolicy::Reject),
+            }))
+        );
+
+        assert_eq!(
+            primary_key_constraint_of("CREATE TABLE SOUTH.BOOK (ID INT NOT NULL)"),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn we_cannot_read_invalid_primary_key_constraint_from_ddl_statement() {
+        assert_eq!(
+            primary_key_constraint_of(
+                "CREATE TABLE SOUTH.BOOK (ID INT NOT NULL, PRIMARY KEY (ID)) WITH (on_duplicate_key=ignore)"
+            ),
+            Err(PrimaryKeyConstraintError::UnknownDuplicateKeyPolicy {
+                value: "ignore".to_string()
+            })
+        );
+
+        assert_eq!(
+            primary_key_constraint_of(
+                "CREATE TABLE SOUTH.BOOK (ID INT NOT NULL) WITH (on_duplicate_key=reject)"
+            ),
+            Err(PrimaryKeyConstraintError::DuplicateKeyPolicyWithoutPrimaryKey)
+        );
+
+        assert_eq!(
+            primary_key_constraint_of(
+                "CREATE TABLE SOUTH.BOOK (ID INT NOT NULL, PRIMARY KEY (ID)) WITH (ON_DUPLICATE_KEY='UPSERT')"
+            ),
+            Err(PrimaryKeyConstraintError::UpsertNotSupported)
+        );
+
+        assert_eq!(
+            primary_key_constraint_of(
+                "CREATE TABLE SOUTH.BOOK (ID INT NOT NULL, NAME VARCHAR NOT NULL, PRIMARY KEY (ID), PRIMARY KEY (NAME))"
+            ),
+            Err(PrimaryKeyConstraintError::MultiplePrimaryKeys)
+        );
+    }
 }
 
 /// The type of table that we are indexing
@@ -0,0 +1,233 @@
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::{Extension, Json};
+use log::error;
+use subxt::dynamic::Value;
+use subxt::Metadata;
+
+use crate::api::permissions::{add_proxy_permission_call, set_permissions_call};
+use crate::api::smartcontracts::{
+    add_smartcontract_call,
+    fetch_contract_tables,
+    remove_smartcontract_call,
+};
+use crate::api::tables::{
+    create_namespace_call,
+    create_table_call,
+    drop_table_call,
+    set_insert_quorum_call,
+    update_namespace_uuid_call,
+    update_table_uuid_call,
+};
+use crate::auth::{Caller, Endpoint};
+use crate::model::{
+    ApiResponse,
+    BatchCall,
+    BatchCallResult,
+    BatchCallStatus,
+    BatchRequest,
+    BatchResponse,
+};
+use crate::state::TranslationLayerState;
+use crate::tx_submitter::{batch_payload, runtime_call};
+use crate::utils::internal_server_error;
+use crate::webhooks::CallbackUrl;
+
+/// Returns the endpoint a batched call would otherwise be sent to.
+fn endpoint_of(call: &BatchCall) -> Endpoint {
+    match call {
+        BatchCall::CreateTable(_) => Endpoint::CreateTable,
+        BatchCall::DropTable(_) => Endpoint::DropTable,
+        BatchCall::CreateNamespace(_) => Endpoint::CreateNamespace,
+        BatchCall::UpdateNamespaceUuid(_) => Endpoint::UpdateNamespaceUuid,
+        BatchCall::UpdateTableUuid(_) => Endpoint::UpdateTableUuid,
+        BatchCall::SetInsertQuorum(_) => Endpoint::SetInsertQuorum,
+        BatchCall::AddSmartcontract(_) => Endpoint::AddSmartcontract,
+        BatchCall::RemoveSmartcontract(_) => Endpoint::RemoveSmartcontract,
+        BatchCall::SetPermissions(_) => Endpoint::SetPermissions,
+        BatchCall::AddProxyPermission(_) => Endpoint::AddProxyPermission,
+    }
+}
+
+/// Validates a batched call exactly as its own endpoint would, then encodes it as a
+/// `RuntimeCall` that can be nested in the batch.
+async fn prepare_call(
+    call: BatchCall,
+    caller: &Caller,
+    state: &TranslationLayerState,
+    metadata: &Metadata,
+) -> Result<Value, (StatusCode, Json<ApiResponse>)> {
+    caller.check_endpoint(endpoint_of(&call))?;
+
+    let encoded = match call {
+        BatchCall::CreateTable(tables) => {
+            runtime_call(&create_table_call(&tables, caller)?, metadata)
+        }
+        BatchCall::DropTable(request) => runtime_call(&drop_table_call(request, caller)?, metadata),
+        BatchCall::CreateNamespace(request) => {
+            runtime_call(&create_namespace_call(request, caller)?, metadata)
+        }
+        BatchCall::UpdateNamespaceUuid(request) => {
+            runtime_call(&update_namespace_uuid_call(request, caller)?, metadata)
+        }
+        BatchCall::UpdateTableUuid(request) => {
+            runtime_call(&update_table_uuid_call(request, caller)?, metadata)
+        }
+        BatchCall::SetInsertQuorum(request) => {
+            runtime_call(&set_insert_quorum_call(request, caller)?, metadata)
+        }
+        BatchCall::AddSmartcontract(request) => {
+            runtime_call(&add_smartcontract_call(request, caller)?, metadata)
+        }
+        BatchCall::RemoveSmartcontract(request) => {
+            let contract_tables = fetch_contract_tables(state, &request).await?;
+            runtime_call(
+                &remove_smartcontract_call(request, caller, &contract_tables)?,
+                metadata,
+            )
+        }
+        BatchCall::SetPermissions(request) => {
+            runtime_call(&set_permissions_call(&request, caller)?, metadata)
+        }
+        BatchCall::AddProxyPermission(request) => {
+            runtime_call(&add_proxy_permission_call(&request, caller)?, metadata)
+        }
+    };
+
+    encoded.map_err(|e| internal_server_error(&e.to_string()))
+}
+
+/// Submits several calls as a single `utility` batch extrinsic.
+///
+/// Each call is validated up front exactly as its own endpoint would, and the API key must be
+/// allowed to use that endpoint. If any call is invalid nothing is submitted and the response
+/// reports the error of every invalid call.
+///
+/// Atomic batches (the default) are submitted as `utility.batch_all`, which applies every call
+/// or none of them. Non-atomic batches are submitted as `utility.batch`, which stops at the
+/// first failing call and keeps the calls dispatched before it. The result of each call is
+/// reported by `/get_extrinsic_status` once the batch is in a block.
+///
+/// # Request Body
+/// - `calls` (Vec<BatchCall>): The calls, each tagged by the endpoint it would otherwise be sent to.
+/// - `atomic` (bool): Whether the calls are applied all or nothing. Defaults to `true`.
+///
+/// # Responses
+/// - **200 OK**: Batch submitted.
+/// - **400 BAD REQUEST**: The batch is empty or one of its calls is invalid.
+/// - **401 UNAUTHORIZED**: Missing or invalid API key.
+/// - **403 FORBIDDEN**: The API key is not scoped to this network, or to the endpoint or namespace of one of the calls.
+/// - **429 TOO MANY REQUESTS**: The API key exceeded its rate limit.
+/// - **500 INTERNAL SERVER ERROR**: A call could not be encoded.
+///
+/// # Example Usage
+/// ```sh
+/// curl -X POST "http://127.0.0.1:3000/batch" -H "Content-Type: application/json" -d '{
+///     "calls": [
+///         { "call": "create_namespace", "args": { ... } },
+///         { "call": "create_table", "args": [{ ... }] }
+///     ]
+/// }'
+/// ```
+#[utoipa::path(
+    post,
+    path = "/batch",
+    tag = "batch",
+    request_body = BatchRequest,
+    params(
+        ("x-callback-url" = Option<String>, Header, description = "URL to notify of the transaction's lifecycle events")
+    ),
+    responses(
+        (status = 200, description = "Batch submitted", body = BatchResponse),
+        (status = 400, description = "Invalid request", body = BatchResponse),
+        (status = 401, description = "Missing or invalid API key", body = ApiResponse),
+        (status = 403, description = "API key is not allowed to make this request", body = BatchResponse),
+        (status = 429, description = "API key rate limit exceeded", body = ApiResponse),
+        (status = 500, description = "Internal server error", body = BatchResponse),
+    )
+)]
+pub async fn batch(
+    State(state): State<Arc<TranslationLayerState>>,
+    Extension(caller): Extension<Caller>,
+    callback: CallbackUrl,
+    Json(BatchRequest { calls, atomic }): Json<BatchRequest>,
+) -> Result<Json<BatchResponse>, (StatusCode, Json<BatchResponse>)> {
+    if calls.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(BatchResponse {
+                success: false,
+                err_msg: Some("A batch needs at least one call".into()),
+                tx_hash: None,
+                results: Vec::new(),
+            }),
+        ));
+    }
+
+    let metadata = state.client.metadata();
+    let names: Vec<String> = calls
+        .iter()
+        .map(|call| endpoint_of(call).as_str().to_string())
+        .collect();
+
+    let mut encoded = Vec::with_capacity(calls.len());
+    let mut results = Vec::with_capacity(calls.len());
+    let mut rejection = None;
+    for ((index, call), name) in calls.into_iter().enumerate().zip(&names) {
+        let (status, error) = match prepare_call(call, &caller, &state, &metadata).await {
+            Ok(call) => {
+                encoded.push(call);
+                (BatchCallStatus::NotExecuted, None)
+            }
+            Err((status, Json(response))) => {
+                rejection.get_or_insert(status);
+                (BatchCallStatus::Invalid, response.err_msg)
+            }
+        };
+        results.push(BatchCallResult {
+            index: index as u32,
+            call: name.clone(),
+            status,
+            error,
+        });
+    }
+
+    if let Some(status) = rejection {
+        return Err((
+            status,
+            Json(BatchResponse {
+                success: false,
+                err_msg: Some("The batch contains invalid calls".into()),
+                tx_hash: None,
+                results,
+            }),
+        ));
+    }
+
+    let response = state
+        .submit(
+            &batch_payload(encoded, atomic),
+            &caller,
+            Endpoint::Batch,
+            &callback,
+        )
+        .await;
+
+    if let Some(tx_hash) = &response.tx_hash {
+        if let Err(err) = state.tx_db.record_batch(tx_hash, &names, atomic).await {
+            error!("Error recording calls of batch {}: {}", tx_hash, err);
+        }
+        for result in &mut results {
+            result.status = BatchCallStatus::Pending;
+        }
+    }
+
+    Ok(Json(BatchResponse {
+        success: response.success,
+        err_msg: response.err_msg,
+        tx_hash: response.tx_hash,
+        results,
+    }))
+}
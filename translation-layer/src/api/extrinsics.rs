@@ -4,15 +4,20 @@ use std::sync::Arc;
 
 use axum::extract::{Query, State};
 use axum::http::StatusCode;
+use axum::response::sse::{Event, KeepAlive, Sse};
 use axum::Json;
-use subxt::tx::TxStatus;
+use futures::Stream;
+use log::warn;
 use sxt_core::sxt_chain_runtime;
+use tokio::sync::broadcast::error::RecvError;
 
-use crate::model::{ApiResponse, TxExecutionStatus, TxStatusDetails, TxStatusResponse};
+use crate::model::{ApiResponse, TxEvent, TxExecutionStatus, TxStatusDetails, TxStatusResponse};
 use crate::state::TranslationLayerState;
+use crate::tx_progress::StoredTxStatus;
 use crate::utils::{
     bad_request,
     decode_system_module_error,
+    extract_param,
     internal_server_error,
     not_found,
     parse_h256_from_hex,
@@ -124,6 +129,7 @@ pub async fn get_extrinsic_status_in_block(
 ///
 /// This endpoint queries the transaction progress based on its `tx_hash`. It returns detailed status information,
 /// including whether the transaction has been validated, broadcasted, included in a block, finalized, or encountered any errors.
+/// The history is persisted, so it remains available across restarts until it expires.
 ///
 /// # Query Parameters
 /// - `tx_hash` (String): The transaction hash to check.
@@ -146,6 +152,10 @@ pub async fn get_extrinsic_status_in_block(
 /// - `dropped_message`: If the transaction was dropped, the reason.
 /// - `invalid_message`: If the transaction was marked invalid, the reason.
 /// - `error_message`: If the transaction encountered an error, details of the error.
+/// - `dispatch_success`: Whether the extrinsic dispatched successfully once included in a block.
+/// - `dispatch_error`: If the dispatch failed, the decoded module error.
+/// - `submitted_by`: Identity of the API key that submitted the transaction.
+/// - `batch_results`: The result of each call, if the transaction was submitted through `/batch`.
 #[utoipa::path(
     get,
     path = "/get_extrinsic_status",
@@ -172,8 +182,14 @@ pub async fn get_extrinsic_status(
         )
     })?;
 
-    match state.tx_db.get_history(tx_hash).await {
-        Some(status_list) => {
+    let history =
+        state.tx_db.get_history(tx_hash).await.map_err(|e| {
+            internal_server_error(&format!("Error reading transaction history: {}", e))
+        })?;
+
+    match history {
+        Some(history) => {
+            let batch_results = history.batch_results();
             let mut tx_status = TxStatusDetails {
                 validated: false,
                 no_longer_in_best_block: false,
@@ -183,37 +199,46 @@ pub async fn get_extrinsic_status(
                 dropped_message: None,
                 invalid_message: None,
                 error_message: None,
+                dispatch_success: None,
+                dispatch_error: None,
+                submitted_by: history.submitted_by,
+                batch_results,
             };
 
-            for status in status_list.iter() {
-                match **status {
-                    TxStatus::Broadcasted { num_peers } => {
+            for status in history.statuses {
+                match status {
+                    StoredTxStatus::Broadcasted { num_peers } => {
                         tx_status.broadcasted_peers = Some(num_peers);
                     }
-                    TxStatus::InBestBlock(ref block) => {
-                        tx_status.in_best_block = Some(format!("{:#x}", block.block_hash()));
+                    StoredTxStatus::InBestBlock { block_hash } => {
+                        tx_status.in_best_block = Some(block_hash);
                     }
-                    TxStatus::InFinalizedBlock(ref block) => {
-                        tx_status.finalized_in_block = Some(format!("{:#x}", block.block_hash()));
+                    StoredTxStatus::InFinalizedBlock { block_hash } => {
+                        tx_status.finalized_in_block = Some(block_hash);
                     }
-                    TxStatus::Dropped { ref message } => {
-                        tx_status.dropped_message = Some(message.clone());
+                    StoredTxStatus::Dropped { message } => {
+                        tx_status.dropped_message = Some(message);
                     }
-                    TxStatus::Invalid { ref message } => {
-                        tx_status.invalid_message = Some(message.clone());
+                    StoredTxStatus::Invalid { message } => {
+                        tx_status.invalid_message = Some(message);
                     }
-                    TxStatus::Error { ref message } => {
-                        tx_status.error_message = Some(message.clone());
+                    StoredTxStatus::Error { message } => {
+                        tx_status.error_message = Some(message);
                     }
-                    TxStatus::Validated => {
+                    StoredTxStatus::Validated => {
                         tx_status.validated = true;
                     }
-                    TxStatus::NoLongerInBestBlock => {
+                    StoredTxStatus::NoLongerInBestBlock => {
                         tx_status.no_longer_in_best_block = true;
                     }
                 }
             }
 
+            if let Some(outcome) = history.outcome {
+                tx_status.dispatch_success = Some(outcome.success);
+                tx_status.dispatch_error = outcome.module_error;
+            }
+
             Ok(Json(TxStatusResponse {
                 success: true,
                 status: tx_status,
@@ -229,3 +254,82 @@ pub async fn get_extrinsic_status(
         )),
     }
 }
+
+/// Streams the lifecycle events of a transaction as server-sent events.
+///
+/// Events already observed are replayed first, so the stream can be opened at any time after
+/// submission. The stream ends after a `Finalized`, `Dropped`, `Invalid` or `Error` event.
+///
+/// # Query Parameters
+/// - `tx_hash` (String): The transaction hash to follow.
+///
+/// # Responses
+/// - **200 OK**: A `text/event-stream` of `TxEvent`s, named after their kind.
+/// - **400 BAD REQUEST**: Missing `tx_hash`.
+/// - **500 INTERNAL SERVER ERROR**: An error occurred while reading the transaction history.
+///
+/// # Example Usage
+/// ```sh
+/// curl -N "http://127.0.0.1:3000/stream_extrinsic_events?tx_hash=0x123..."
+/// ```
+#[utoipa::path(
+    get,
+    path = "/stream_extrinsic_events",
+    tag = "stream-extrinsic-events",
+    params(("tx_hash" = String, Query, description = "Transaction hash")),
+    responses(
+        (status = 200, description = "Stream of transaction events", body = TxEvent, content_type = "text/event-stream"),
+        (status = 400, description = "Invalid request parameters", body = ApiResponse),
+        (status = 500, description = "Internal server error", body = ApiResponse)
+    )
+)]
+pub async fn stream_extrinsic_events(
+    State(state): State<Arc<TranslationLayerState>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, (StatusCode, Json<ApiResponse>)> {
+    let tx_hash = extract_param(&params, "tx_hash")?.to_lowercase();
+
+    // Subscribe before reading the history, so no event can fall between the two.
+    let mut events = state.tx_db.subscribe();
+    let replayed = state
+        .tx_db
+        .get_history(&tx_hash)
+        .await
+        .map_err(|e| internal_server_error(&format!("Error reading transaction history: {}", e)))?
+        .map(|history| history.events(&tx_hash))
+        .unwrap_or_default();
+    let finished = replayed.iter().any(|event| event.kind.is_terminal());
+
+    let stream = async_stream::stream! {
+        for event in &replayed {
+            yield to_sse_event(event);
+        }
+
+        if !finished {
+            loop {
+                match events.recv().await {
+                    Ok(event) if event.tx_hash == tx_hash && !replayed.contains(&event) => {
+                        yield to_sse_event(&event);
+                        if event.kind.is_terminal() {
+                            break;
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!("Event stream of {} skipped {} events", tx_hash, skipped);
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        }
+    };
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+/// Serializes a transaction event into a server-sent event named after its kind.
+fn to_sse_event(event: &TxEvent) -> Result<Event, axum::Error> {
+    Event::default()
+        .event(format!("{:?}", event.kind))
+        .json_data(event)
+}
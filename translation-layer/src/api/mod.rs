@@ -1,8 +1,14 @@
 //! translation-layer api crate
 
+/// Handlers related to batched submissions
+pub mod batch;
+
 /// Handlers related to extrinsics
 pub mod extrinsics;
 
+/// Handlers related to permissions
+pub mod permissions;
+
 /// Handlers related to smartcontracts
 pub mod smartcontracts;
 
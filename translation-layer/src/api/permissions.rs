@@ -0,0 +1,291 @@
+use std::str::FromStr;
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::{Extension, Json};
+use subxt::ext::codec::{Decode, Encode};
+use subxt::tx::DefaultPayload;
+use subxt::utils::AccountId32;
+use sxt_core::permissions::{IndexingPalletPermission, PermissionLevel};
+use sxt_core::sxt_chain_runtime;
+use sxt_core::sxt_chain_runtime::api::permissions::calls::types::{
+    AddProxyPermission,
+    SetPermissions,
+};
+use sxt_core::sxt_chain_runtime::api::runtime_types::bounded_collections::bounded_vec::BoundedVec;
+use sxt_core::sxt_chain_runtime::api::runtime_types::sxt_core::permissions::PermissionLevel as RuntimePermissionLevel;
+
+use crate::auth::{Caller, Endpoint};
+use crate::model::{AddProxyPermissionRequest, ApiResponse, SetPermissionsRequest};
+use crate::state::TranslationLayerState;
+use crate::utils::bad_request;
+use crate::webhooks::CallbackUrl;
+
+/// Parses an SS58 address into an `AccountId32`.
+fn parse_account(account: &str) -> Result<AccountId32, (StatusCode, Json<ApiResponse>)> {
+    AccountId32::from_str(account)
+        .map_err(|e| bad_request(&format!("Invalid account {}: {}", account, e)))
+}
+
+/// Converts a `PermissionLevel` into its generated runtime counterpart.
+///
+/// Both types share the same SCALE encoding, so the conversion round-trips through it.
+fn to_runtime_permission(
+    permission: &PermissionLevel,
+) -> Result<RuntimePermissionLevel, (StatusCode, Json<ApiResponse>)> {
+    RuntimePermissionLevel::decode(&mut permission.encode().as_slice())
+        .map_err(|e| bad_request(&format!("Unsupported permission: {}", e)))
+}
+
+/// Returns a `403 Forbidden` response if the caller may not grant `permission`.
+///
+/// Permissions scoped to a table need access to the table's namespace. The others apply to every
+/// namespace, so only callers that may touch any namespace can grant them.
+fn check_permission_namespace(
+    caller: &Caller,
+    permission: &PermissionLevel,
+) -> Result<(), (StatusCode, Json<ApiResponse>)> {
+    match permission {
+        PermissionLevel::IndexingPallet(
+            IndexingPalletPermission::SubmitDataForPrivilegedQuorum(table),
+        ) => caller.check_namespace(&String::from_utf8_lossy(&table.namespace)),
+        PermissionLevel::EditSpecificPermission(permission) => {
+            check_permission_namespace(caller, permission)
+        }
+        _ => caller.check_unrestricted(),
+    }
+}
+
+/// Builds the `set_permissions` call of a `/set_permissions` request.
+pub(crate) fn set_permissions_call(
+    request: &SetPermissionsRequest,
+    caller: &Caller,
+) -> Result<DefaultPayload<SetPermissions>, (StatusCode, Json<ApiResponse>)> {
+    let who = parse_account(&request.account)?;
+    let permissions = request
+        .permissions
+        .iter()
+        .map(|permission| {
+            check_permission_namespace(caller, permission)?;
+            to_runtime_permission(permission)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(sxt_chain_runtime::api::tx()
+        .permissions()
+        .set_permissions(who, BoundedVec(permissions)))
+}
+
+/// Builds the `add_proxy_permission` call of an `/add_proxy_permission` request.
+pub(crate) fn add_proxy_permission_call(
+    request: &AddProxyPermissionRequest,
+    caller: &Caller,
+) -> Result<DefaultPayload<AddProxyPermission>, (StatusCode, Json<ApiResponse>)> {
+    let proxy = parse_account(&request.account)?;
+    check_permission_namespace(caller, &request.permission)?;
+    let permission = to_runtime_permission(&request.permission)?;
+
+    Ok(sxt_chain_runtime::api::tx()
+        .permissions()
+        .add_proxy_permission(proxy, permission))
+}
+
+/// Submits a transaction replacing the permissions of an account.
+///
+/// The signer of the translation layer must hold the `UpdatePermissions` permission for this
+/// transaction to succeed.
+///
+/// # Request Body
+/// - `account` (String): The SS58 address of the account.
+/// - `permissions` (Vec<PermissionLevel>): The full list of permissions for the account.
+///
+/// # Responses
+/// - **200 OK**: Transaction submitted.
+/// - **400 BAD REQUEST**: Invalid request parameters.
+/// - **401 UNAUTHORIZED**: Missing or invalid API key.
+/// - **403 FORBIDDEN**: The API key is not scoped to this network or endpoint, or to the
+///   namespace of a permission. Keys restricted to namespaces cannot grant permissions that
+///   apply to every namespace.
+/// - **429 TOO MANY REQUESTS**: The API key exceeded its rate limit.
+///
+/// # Example Usage
+/// ```sh
+/// curl -X POST "http://127.0.0.1:3000/set_permissions" -H "Content-Type: application/json" -d '{
+///     "account": "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+///     "permissions": [{"TablesPallet": "EditSchema"}]
+/// }'
+/// ```
+#[utoipa::path(
+    post,
+    path = "/set_permissions",
+    tag = "set-permissions",
+    request_body = SetPermissionsRequest,
+    params(
+        ("x-callback-url" = Option<String>, Header, description = "URL to notify of the transaction's lifecycle events")
+    ),
+    responses(
+        (status = 200, description = "Transaction submitted", body = ApiResponse),
+        (status = 400, description = "Invalid request", body = ApiResponse),
+        (status = 401, description = "Missing or invalid API key", body = ApiResponse),
+        (status = 403, description = "API key is not allowed to make this request", body = ApiResponse),
+        (status = 429, description = "API key rate limit exceeded", body = ApiResponse),
+    )
+)]
+pub async fn set_permissions(
+    State(state): State<Arc<TranslationLayerState>>,
+    Extension(caller): Extension<Caller>,
+    callback: CallbackUrl,
+    Json(request): Json<SetPermissionsRequest>,
+) -> Result<Json<ApiResponse>, (StatusCode, Json<ApiResponse>)> {
+    let tx = set_permissions_call(&request, &caller)?;
+
+    Ok(Json(
+        state
+            .submit(&tx, &caller, Endpoint::SetPermissions, &callback)
+            .await,
+    ))
+}
+
+/// Submits a transaction granting a single permission to an account on behalf of the signer.
+///
+/// The signer of the translation layer must itself hold the permission being granted.
+///
+/// # Request Body
+/// - `account` (String): The SS58 address of the account.
+/// - `permission` (PermissionLevel): The permission to grant.
+///
+/// # Responses
+/// - **200 OK**: Transaction submitted.
+/// - **400 BAD REQUEST**: Invalid request parameters.
+/// - **401 UNAUTHORIZED**: Missing or invalid API key.
+/// - **403 FORBIDDEN**: The API key is not scoped to this network or endpoint, or to the
+///   namespace of the permission. Keys restricted to namespaces cannot grant permissions that
+///   apply to every namespace.
+/// - **429 TOO MANY REQUESTS**: The API key exceeded its rate limit.
+#[utoipa::path(
+    post,
+    path = "/add_proxy_permission",
+    tag = "add-proxy-permission",
+    request_body = AddProxyPermissionRequest,
+    params(
+        ("x-callback-url" = Option<String>, Header, description = "URL to notify of the transaction's lifecycle events")
+    ),
+    responses(
+        (status = 200, description = "Transaction submitted", body = ApiResponse),
+        (status = 400, description = "Invalid request", body = ApiResponse),
+        (status = 401, description = "Missing or invalid API key", body = ApiResponse),
+        (status = 403, description = "API key is not allowed to make this request", body = ApiResponse),
+        (status = 429, description = "API key rate limit exceeded", body = ApiResponse),
+    )
+)]
+pub async fn add_proxy_permission(
+    State(state): State<Arc<TranslationLayerState>>,
+    Extension(caller): Extension<Caller>,
+    callback: CallbackUrl,
+    Json(request): Json<AddProxyPermissionRequest>,
+) -> Result<Json<ApiResponse>, (StatusCode, Json<ApiResponse>)> {
+    let tx = add_proxy_permission_call(&request, &caller)?;
+
+    Ok(Json(
+        state
+            .submit(&tx, &caller, Endpoint::AddProxyPermission, &callback)
+            .await,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use sxt_core::permissions::TablesPalletPermission;
+    use sxt_core::tables::TableIdentifier;
+
+    use super::*;
+
+    const ACCOUNT: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+
+    fn submit_data_to(namespace: &str) -> PermissionLevel {
+        PermissionLevel::IndexingPallet(IndexingPalletPermission::SubmitDataForPrivilegedQuorum(
+            TableIdentifier::from_str_unchecked("BLOCKS", namespace),
+        ))
+    }
+
+    fn set_permissions(permissions: Vec<PermissionLevel>) -> SetPermissionsRequest {
+        SetPermissionsRequest {
+            account: ACCOUNT.to_string(),
+            permissions,
+        }
+    }
+
+    fn add_proxy_permission(permission: PermissionLevel) -> AddProxyPermissionRequest {
+        AddProxyPermissionRequest {
+            account: ACCOUNT.to_string(),
+            permission,
+        }
+    }
+
+    #[test]
+    fn scoped_keys_can_only_set_permissions_within_their_namespaces() {
+        let caller = Caller::scoped_to(&["ethereum"]);
+
+        assert!(
+            set_permissions_call(&set_permissions(vec![submit_data_to("ETHEREUM")]), &caller)
+                .is_ok()
+        );
+
+        for permissions in [
+            vec![submit_data_to("ETHEREUM"), submit_data_to("BITCOIN")],
+            vec![PermissionLevel::TablesPallet(
+                TablesPalletPermission::EditSchema,
+            )],
+            vec![PermissionLevel::EditSpecificPermission(Box::new(
+                submit_data_to("BITCOIN"),
+            ))],
+        ] {
+            let (status, _) =
+                set_permissions_call(&set_permissions(permissions), &caller).unwrap_err();
+            assert_eq!(status, StatusCode::FORBIDDEN);
+        }
+    }
+
+    #[test]
+    fn scoped_keys_can_only_add_proxy_permissions_within_their_namespaces() {
+        let caller = Caller::scoped_to(&["ethereum"]);
+
+        assert!(add_proxy_permission_call(
+            &add_proxy_permission(submit_data_to("ETHEREUM")),
+            &caller
+        )
+        .is_ok());
+
+        for permission in [
+            submit_data_to("BITCOIN"),
+            PermissionLevel::UpdatePermissions,
+            PermissionLevel::EditSpecificPermission(Box::new(PermissionLevel::TablesPallet(
+                TablesPalletPermission::EditSchema,
+            ))),
+        ] {
+            let (status, _) =
+                add_proxy_permission_call(&add_proxy_permission(permission), &caller).unwrap_err();
+            assert_eq!(status, StatusCode::FORBIDDEN);
+        }
+    }
+
+    #[test]
+    fn unrestricted_keys_can_grant_any_permission() {
+        let caller = Caller::anonymous();
+
+        assert!(set_permissions_call(
+            &set_permissions(vec![
+                submit_data_to("BITCOIN"),
+                PermissionLevel::TablesPallet(TablesPalletPermission::EditSchema),
+            ]),
+            &caller
+        )
+        .is_ok());
+        assert!(add_proxy_permission_call(
+            &add_proxy_permission(PermissionLevel::UpdatePermissions),
+            &caller
+        )
+        .is_ok());
+    }
+}
@@ -3,11 +3,19 @@ use std::sync::Arc;
 
 use axum::extract::{Query, State};
 use axum::http::StatusCode;
-use axum::Json;
+use axum::{Extension, Json};
+use subxt::tx::DefaultPayload;
 use sxt_core::sxt_chain_runtime;
 use sxt_core::sxt_chain_runtime::api::runtime_types::bounded_collections::bounded_vec::BoundedVec;
 use sxt_core::sxt_chain_runtime::api::runtime_types::sxt_core::smartcontracts::Contract;
+use sxt_core::sxt_chain_runtime::api::runtime_types::sxt_core::tables::TableIdentifier;
+use sxt_core::sxt_chain_runtime::api::smartcontracts::calls::types::{
+    AddSmartcontract,
+    RemoveSmartcontract,
+};
 
+use crate::api::tables::table_creator_for;
+use crate::auth::{Caller, Endpoint};
 use crate::model::{
     AddContractRequest,
     ApiContract,
@@ -18,14 +26,15 @@ use crate::model::{
     TableRequest,
 };
 use crate::state::TranslationLayerState;
-use crate::table_builder::TableCreator;
 use crate::utils::{
+    bad_request,
     extract_param,
     internal_server_error,
     map_contract_to_api,
     not_found,
     string_to_source,
 };
+use crate::webhooks::CallbackUrl;
 
 /// Submits a transaction to add a smart contract to the indexing system.
 ///
@@ -45,6 +54,9 @@ use crate::utils::{
 /// # Responses
 /// - **200 OK**: Smart contract successfully added.
 /// - **400 BAD REQUEST**: Invalid request parameters.
+/// - **401 UNAUTHORIZED**: Missing or invalid API key.
+/// - **403 FORBIDDEN**: The API key is not scoped to this network, endpoint or namespace.
+/// - **429 TOO MANY REQUESTS**: The API key exceeded its rate limit.
 /// - **500 INTERNAL SERVER ERROR**: Transaction submission failed.
 ///
 /// # Example Usage
@@ -53,92 +65,45 @@ use crate::utils::{
 /// ```
 #[utoipa::path(post, path = "/add_smartcontract", tag = "add-smartcontract",
   request_body = AddContractRequest,
+  params(
+      ("x-callback-url" = Option<String>, Header, description = "URL to notify of the transaction's lifecycle events")
+  ),
   responses(
       (status = 200, description = "Smart contract added successfully", body = ApiResponse),
       (status = 400, description = "Invalid request", body = ApiResponse),
+      (status = 401, description = "Missing or invalid API key", body = ApiResponse),
+      (status = 403, description = "API key is not allowed to make this request", body = ApiResponse),
+      (status = 429, description = "API key rate limit exceeded", body = ApiResponse),
       (status = 500, description = "Internal server error", body = ApiResponse)
   ))]
 pub async fn add_smartcontract(
     State(state): State<Arc<TranslationLayerState>>, // Get shared API instance
+    Extension(caller): Extension<Caller>,
+    callback: CallbackUrl,
     Json(request): Json<AddContractRequest>,
-) -> Json<ApiResponse> {
-    let (tables, contract) = match request.try_into() {
-        Ok(v) => v,
-        Err(e) => {
-            return Json(ApiResponse {
-                success: false,
-                err_msg: Some(format!("Invalid contract input: {e}")),
-                tx_hash: None,
-            });
-        }
-    };
-
-    let mut table_creator = TableCreator::new();
-
-    for table in tables.iter() {
-        let mut builder = table_creator
-            .add_table()
-            .identifier(&table.table_name, &table.schema_name)
-            .ddl_statement(&table.ddl_statement)
-            .table_type(table.table_type.clone().into())
-            .source(table.source.clone());
-
-        if let (Some(commitment_hex), Some(scheme), Some(snapshot)) = (
-            &table.commitment,
-            &table.commitment_scheme,
-            &table.snapshot_url,
-        ) {
-            match hex::decode(commitment_hex.trim_start_matches("0x")) {
-                Ok(decoded_commitment) => {
-                    builder = builder
-                        .commitment_scheme(scheme.clone())
-                        .commitment(&decoded_commitment)
-                        .snapshot_url(snapshot);
-                }
-                Err(_) => {
-                    return Json(ApiResponse {
-                        success: false,
-                        err_msg: Some("Invalid hex commitment".into()),
-                        tx_hash: None,
-                    });
-                }
-            }
-        }
-
-        builder.add();
-    }
+) -> Result<Json<ApiResponse>, (StatusCode, Json<ApiResponse>)> {
+    let tx = add_smartcontract_call(request, &caller)?;
+
+    Ok(Json(
+        state
+            .submit(&tx, &caller, Endpoint::AddSmartcontract, &callback)
+            .await,
+    ))
+}
 
-    let tables = BoundedVec(table_creator.tables());
-    let tx = sxt_chain_runtime::api::tx()
+/// Builds the `add_smartcontract` call of an `/add_smartcontract` request.
+pub(crate) fn add_smartcontract_call(
+    request: AddContractRequest,
+    caller: &Caller,
+) -> Result<DefaultPayload<AddSmartcontract>, (StatusCode, Json<ApiResponse>)> {
+    let (tables, contract): (Vec<TableRequest>, Contract) = request
+        .try_into()
+        .map_err(|e| bad_request(&format!("Invalid contract input: {e}")))?;
+
+    let tables = BoundedVec(table_creator_for(&tables, caller)?.tables());
+    Ok(sxt_chain_runtime::api::tx()
         .smartcontracts()
-        .add_smartcontract(contract, tables);
-
-    let submitter_opt = match state.network {
-        crate::state::Network::Mainnet => state.mainnet_submitter.as_ref(),
-        crate::state::Network::Testnet => state.testnet_submitter.as_ref(),
-    };
-
-    let Some(submitter) = submitter_opt else {
-        return Json(ApiResponse {
-            success: false,
-            err_msg: Some("TxSubmitter not configured for this network".into()),
-            tx_hash: None,
-        });
-    };
-
-    // ✅ Lock and use submitter
-    let mut submitter = submitter.lock().await;
-
-    let (success, err_msg, tx_hash) = match submitter.submit_tx_get_hash(&tx).await {
-        Ok(hash) => (true, None, Some(format!("{:#x}", hash))),
-        Err(err) => (false, Some(format!("Error: {err}")), None),
-    };
-
-    Json(ApiResponse {
-        success,
-        err_msg,
-        tx_hash,
-    })
+        .add_smartcontract(contract, tables))
 }
 
 /// Submits a transaction to remove a smart contract from the indexing system.
@@ -152,6 +117,11 @@ pub async fn add_smartcontract(
 /// # Responses
 /// - **200 OK**: Smart contract successfully removed.
 /// - **400 BAD REQUEST**: Invalid request parameters.
+/// - **401 UNAUTHORIZED**: Missing or invalid API key.
+/// - **403 FORBIDDEN**: The API key is not scoped to this network, endpoint or the namespace of
+///   one of the contract's tables. Keys restricted to namespaces cannot remove contracts without
+///   tables.
+/// - **429 TOO MANY REQUESTS**: The API key exceeded its rate limit.
 /// - **500 INTERNAL SERVER ERROR**: Transaction submission failed.
 ///
 /// # Example Usage
@@ -160,47 +130,83 @@ pub async fn add_smartcontract(
 /// ```
 #[utoipa::path(post, path = "/remove_smartcontract", tag = "remove-smartcontract",
   request_body = RemoveContractRequest,
+  params(
+      ("x-callback-url" = Option<String>, Header, description = "URL to notify of the transaction's lifecycle events")
+  ),
   responses(
       (status = 200, description = "Smart contract removed successfully", body = ApiResponse),
       (status = 400, description = "Invalid request", body = ApiResponse),
+      (status = 401, description = "Missing or invalid API key", body = ApiResponse),
+      (status = 403, description = "API key is not allowed to make this request", body = ApiResponse),
+      (status = 429, description = "API key rate limit exceeded", body = ApiResponse),
       (status = 500, description = "Internal server error", body = ApiResponse)
   ))]
 pub async fn remove_smartcontract(
     State(state): State<Arc<TranslationLayerState>>, // Get shared API instance
-    Json(RemoveContractRequest { source, address }): Json<RemoveContractRequest>,
-) -> Json<ApiResponse> {
-    let source = string_to_source(&source);
-    let address = BoundedVec(address.into_bytes().to_vec());
+    Extension(caller): Extension<Caller>,
+    callback: CallbackUrl,
+    Json(request): Json<RemoveContractRequest>,
+) -> Result<Json<ApiResponse>, (StatusCode, Json<ApiResponse>)> {
+    let contract_tables = fetch_contract_tables(&state, &request).await?;
+    let tx = remove_smartcontract_call(request, &caller, &contract_tables)?;
+
+    Ok(Json(
+        state
+            .submit(&tx, &caller, Endpoint::RemoveSmartcontract, &callback)
+            .await,
+    ))
+}
 
-    let tx = sxt_chain_runtime::api::tx()
+/// Fetches the tables created for the contract of a `/remove_smartcontract` request, which the
+/// removal drops.
+pub(crate) async fn fetch_contract_tables(
+    state: &TranslationLayerState,
+    request: &RemoveContractRequest,
+) -> Result<Vec<TableIdentifier>, (StatusCode, Json<ApiResponse>)> {
+    let query = sxt_chain_runtime::api::storage()
         .smartcontracts()
-        .remove_smartcontract(source, address);
+        .contract_tables(
+            string_to_source(&request.source),
+            BoundedVec(request.address.as_bytes().to_vec()),
+        );
 
-    let submitter_opt = match state.network {
-        crate::state::Network::Mainnet => state.mainnet_submitter.as_ref(),
-        crate::state::Network::Testnet => state.testnet_submitter.as_ref(),
-    };
+    let contract_tables = state
+        .client
+        .storage()
+        .at_latest()
+        .await
+        .map_err(|e| internal_server_error(&format!("Failed to access storage: {}", e)))?
+        .fetch(&query)
+        .await
+        .map_err(|e| internal_server_error(&format!("Failed to fetch contract tables: {}", e)))?;
 
-    let Some(submitter) = submitter_opt else {
-        return Json(ApiResponse {
-            success: false,
-            err_msg: Some("TxSubmitter not configured for this network".into()),
-            tx_hash: None,
-        });
-    };
+    Ok(contract_tables
+        .map(|BoundedVec(tables)| tables.into_iter().map(|(table, _)| table).collect())
+        .unwrap_or_default())
+}
 
-    let mut submitter = submitter.lock().await;
+/// Builds the `remove_smartcontract` call of a `/remove_smartcontract` request.
+///
+/// The caller must be allowed to touch the namespace of every table in `contract_tables`.
+pub(crate) fn remove_smartcontract_call(
+    RemoveContractRequest { source, address }: RemoveContractRequest,
+    caller: &Caller,
+    contract_tables: &[TableIdentifier],
+) -> Result<DefaultPayload<RemoveSmartcontract>, (StatusCode, Json<ApiResponse>)> {
+    // A contract without tables cannot be attributed to a namespace
+    if contract_tables.is_empty() {
+        caller.check_unrestricted()?;
+    }
+    for table in contract_tables {
+        caller.check_namespace(&String::from_utf8_lossy(&table.namespace.0))?;
+    }
 
-    let (success, err_msg, tx_hash) = match submitter.submit_tx_get_hash(&tx).await {
-        Ok(hash) => (true, None, Some(format!("{:#x}", hash))),
-        Err(err) => (false, Some(format!("Error: {err}")), None),
-    };
+    let source = string_to_source(&source);
+    let address = BoundedVec(address.into_bytes().to_vec());
 
-    Json(ApiResponse {
-        success,
-        err_msg,
-        tx_hash,
-    })
+    Ok(sxt_chain_runtime::api::tx()
+        .smartcontracts()
+        .remove_smartcontract(source, address))
 }
 
 /// Retrieves the details of a specific smart contract.
@@ -371,3 +377,51 @@ pub async fn get_smartcontracts(
         contracts,
     }))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table_in(namespace: &str) -> TableIdentifier {
+        TableIdentifier {
+            name: BoundedVec(b"TRANSFERS".to_vec()),
+            namespace: BoundedVec(namespace.as_bytes().to_vec()),
+        }
+    }
+
+    fn remove_request() -> RemoveContractRequest {
+        RemoveContractRequest {
+            source: "ethereum".to_string(),
+            address: "0x4e3b31eb0e5cb73641ee1e65e7dcefe520ba3ef2".to_string(),
+        }
+    }
+
+    #[test]
+    fn scoped_keys_can_only_remove_contracts_within_their_namespaces() {
+        let caller = Caller::scoped_to(&["ethereum"]);
+
+        assert!(
+            remove_smartcontract_call(remove_request(), &caller, &[table_in("ETHEREUM")]).is_ok()
+        );
+
+        for contract_tables in [
+            vec![table_in("BITCOIN")],
+            vec![table_in("ETHEREUM"), table_in("BITCOIN")],
+            vec![],
+        ] {
+            let (status, _) =
+                remove_smartcontract_call(remove_request(), &caller, &contract_tables).unwrap_err();
+            assert_eq!(status, StatusCode::FORBIDDEN);
+        }
+    }
+
+    #[test]
+    fn unrestricted_keys_can_remove_any_contract() {
+        let caller = Caller::anonymous();
+
+        assert!(
+            remove_smartcontract_call(remove_request(), &caller, &[table_in("BITCOIN")]).is_ok()
+        );
+        assert!(remove_smartcontract_call(remove_request(), &caller, &[]).is_ok());
+    }
+}
@@ -1,18 +1,54 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
-use axum::extract::State;
-use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::{Extension, Json};
+use subxt::dynamic::Value;
+use subxt::tx::{DefaultPayload, DynamicPayload};
 use sxt_core::sxt_chain_runtime;
 use sxt_core::sxt_chain_runtime::api::runtime_types::bounded_collections::bounded_vec::BoundedVec;
 use sxt_core::sxt_chain_runtime::api::runtime_types::sxt_core::tables::{
     SourceAndMode,
     TableIdentifier,
+    TableType as RuntimeTableType,
+};
+use sxt_core::sxt_chain_runtime::api::tables::calls::types::{
+    CreateNamespace,
+    CreateTables,
+    DropTable,
 };
 
-use crate::model::{ApiResponse, CreateTableRequest, DropTableRequest, TableRequest};
+use crate::auth::{Caller, Endpoint};
+use crate::model::{
+    ApiResponse,
+    ApiSchema,
+    ApiTable,
+    CreateNamespaceRequest,
+    CreateTableRequest,
+    DropTableRequest,
+    GetSchemasResponse,
+    GetTablesResponse,
+    QuorumSize,
+    SetInsertQuorumRequest,
+    TableRequest,
+    TableType,
+    UpdateNamespaceUuidRequest,
+    UpdateTableUuidRequest,
+};
 use crate::state::TranslationLayerState;
-use crate::table_builder::TableCreator;
-use crate::utils::{string_to_mode, string_to_source};
+use crate::table_builder::{validate_ddl, validate_identifier, validate_uuid, TableCreator};
+use crate::utils::{
+    bad_request,
+    bytes_to_string,
+    decode_namespace_versions_key,
+    extract_param,
+    internal_server_error,
+    not_found,
+    string_to_mode,
+    string_to_source,
+};
+use crate::webhooks::CallbackUrl;
 
 /// Submits a transaction to create a new table in the indexing system.
 ///
@@ -29,6 +65,9 @@ use crate::utils::{string_to_mode, string_to_source};
 /// # Responses
 /// - **200 OK**: Table successfully created.
 /// - **400 BAD REQUEST**: Invalid request parameters.
+/// - **401 UNAUTHORIZED**: Missing or invalid API key.
+/// - **403 FORBIDDEN**: The API key is not scoped to this network, endpoint or namespace.
+/// - **429 TOO MANY REQUESTS**: The API key exceeded its rate limit.
 /// - **500 INTERNAL SERVER ERROR**: Transaction submission failed.
 ///
 /// # Example Usage
@@ -50,18 +89,50 @@ use crate::utils::{string_to_mode, string_to_source};
 /// ```
 #[utoipa::path(post, path = "/create_table", tag = "create-table",
     request_body = CreateTableRequest,
+    params(
+        ("x-callback-url" = Option<String>, Header, description = "URL to notify of the transaction's lifecycle events")
+    ),
     responses(
         (status = 200, description = "Table created successfully", body = ApiResponse),
         (status = 400, description = "Invalid request", body = ApiResponse),
+        (status = 401, description = "Missing or invalid API key", body = ApiResponse),
+        (status = 403, description = "API key is not allowed to make this request", body = ApiResponse),
+        (status = 429, description = "API key rate limit exceeded", body = ApiResponse),
         (status = 500, description = "Internal server error", body = ApiResponse)
     ))]
 pub async fn create_table(
     State(state): State<Arc<TranslationLayerState>>,
+    Extension(caller): Extension<Caller>,
+    callback: CallbackUrl,
     Json(request): Json<Vec<TableRequest>>,
-) -> Json<ApiResponse> {
+) -> Result<Json<ApiResponse>, (StatusCode, Json<ApiResponse>)> {
+    let tx = create_table_call(&request, &caller)?;
+
+    Ok(Json(
+        state
+            .submit(&tx, &caller, Endpoint::CreateTable, &callback)
+            .await,
+    ))
+}
+
+/// Builds the `create_tables` call of a `/create_table` request.
+pub(crate) fn create_table_call(
+    tables: &[TableRequest],
+    caller: &Caller,
+) -> Result<DefaultPayload<CreateTables>, (StatusCode, Json<ApiResponse>)> {
+    Ok(table_creator_for(tables, caller)?.build())
+}
+
+/// Validates the given tables and adds them to a new `TableCreator`.
+pub(crate) fn table_creator_for(
+    tables: &[TableRequest],
+    caller: &Caller,
+) -> Result<TableCreator, (StatusCode, Json<ApiResponse>)> {
     let mut table_creator = TableCreator::new();
 
-    for table in request.iter() {
+    for table in tables {
+        caller.check_namespace(&table.schema_name)?;
+
         let mut builder = table_creator
             .add_table()
             .identifier(&table.table_name, &table.schema_name)
@@ -74,56 +145,21 @@ pub async fn create_table(
             &table.commitment_scheme,
             &table.snapshot_url,
         ) {
-            match hex::decode(commitment_hex.trim_start_matches("0x")) {
-                Ok(decoded_commitment) => {
-                    builder = builder
-                        .commitment_scheme(scheme.clone())
-                        .commitment(&decoded_commitment)
-                        .snapshot_url(snapshot);
-                }
-                Err(_) => {
-                    return Json(ApiResponse {
-                        success: false,
-                        err_msg: Some("Invalid hex commitment".into()),
-                        tx_hash: None,
-                    });
-                }
-            }
+            let decoded_commitment = hex::decode(commitment_hex.trim_start_matches("0x"))
+                .map_err(|_| bad_request("Invalid hex commitment"))?;
+            builder = builder
+                .commitment_scheme(scheme.clone())
+                .commitment(&decoded_commitment)
+                .snapshot_url(snapshot);
         }
 
+        builder
+            .validate()
+            .map_err(|e| bad_request(&e.to_string()))?;
         builder.add();
     }
 
-    let tx = table_creator.build();
-
-    // 🚦 Get the right submitter based on the current network
-    let submitter_opt = match state.network {
-        crate::state::Network::Mainnet => state.mainnet_submitter.as_ref(),
-        crate::state::Network::Testnet => state.testnet_submitter.as_ref(),
-    };
-
-    // ❌ Return 500 if submitter is missing
-    let Some(submitter) = submitter_opt else {
-        return Json(ApiResponse {
-            success: false,
-            err_msg: Some("TxSubmitter not configured for this network".into()),
-            tx_hash: None,
-        });
-    };
-
-    // ✅ Lock and use submitter
-    let mut submitter = submitter.lock().await;
-
-    let (success, err_msg, tx_hash) = match submitter.submit_tx_get_hash(&tx).await {
-        Ok(hash) => (true, None, Some(format!("{:#x}", hash))),
-        Err(err) => (false, Some(format!("Error: {err}")), None),
-    };
-
-    Json(ApiResponse {
-        success,
-        err_msg,
-        tx_hash,
-    })
+    Ok(table_creator)
 }
 
 /// Submits a transaction to drop a table from the indexing system.
@@ -139,6 +175,9 @@ pub async fn create_table(
 /// # Responses
 /// - **200 OK**: Table successfully removed.
 /// - **400 BAD REQUEST**: Invalid request parameters.
+/// - **401 UNAUTHORIZED**: Missing or invalid API key.
+/// - **403 FORBIDDEN**: The API key is not scoped to this network, endpoint or namespace.
+/// - **429 TOO MANY REQUESTS**: The API key exceeded its rate limit.
 /// - **500 INTERNAL SERVER ERROR**: Transaction submission failed.
 ///
 /// # Example Usage
@@ -155,50 +194,557 @@ pub async fn create_table(
     path = "/drop_table",
     tag = "drop-table",
     request_body = DropTableRequest,
+    params(
+        ("x-callback-url" = Option<String>, Header, description = "URL to notify of the transaction's lifecycle events")
+    ),
     responses(
         (status = 200, description = "Table dropped successfully", body = ApiResponse),
         (status = 400, description = "Invalid request", body = ApiResponse),
+        (status = 401, description = "Missing or invalid API key", body = ApiResponse),
+        (status = 403, description = "API key is not allowed to make this request", body = ApiResponse),
+        (status = 429, description = "API key rate limit exceeded", body = ApiResponse),
         (status = 500, description = "Internal server error", body = ApiResponse)
     )
 )]
 pub async fn drop_table(
     State(state): State<Arc<TranslationLayerState>>,
+    Extension(caller): Extension<Caller>,
+    callback: CallbackUrl,
     Json(request): Json<DropTableRequest>,
-) -> Json<ApiResponse> {
-    let tx = sxt_chain_runtime::api::tx().tables().drop_table(
+) -> Result<Json<ApiResponse>, (StatusCode, Json<ApiResponse>)> {
+    let tx = drop_table_call(request, &caller)?;
+
+    Ok(Json(
+        state
+            .submit(&tx, &caller, Endpoint::DropTable, &callback)
+            .await,
+    ))
+}
+
+/// Builds the `drop_table` call of a `/drop_table` request.
+pub(crate) fn drop_table_call(
+    request: DropTableRequest,
+    caller: &Caller,
+) -> Result<DefaultPayload<DropTable>, (StatusCode, Json<ApiResponse>)> {
+    caller.check_namespace(&request.schema_name)?;
+
+    Ok(sxt_chain_runtime::api::tx().tables().drop_table(
         request.table_type.into(),
         TableIdentifier {
             name: BoundedVec(request.table_name.into()),
             namespace: BoundedVec(request.schema_name.into()),
         },
         request.source.into(),
-    );
+    ))
+}
 
-    let submitter_opt = match state.network {
-        crate::state::Network::Mainnet => state.mainnet_submitter.as_ref(),
-        crate::state::Network::Testnet => state.testnet_submitter.as_ref(),
-    };
+/// Submits a transaction to create a new namespace (schema).
+///
+/// The namespace UUID is taken from the `CREATE SCHEMA` statement if present, and generated
+/// on chain otherwise.
+///
+/// # Request Body
+/// - `schema_name` (String): The namespace to create.
+/// - `version` (u16): The version of the namespace.
+/// - `ddl_statement` (String): The `CREATE SCHEMA` statement.
+/// - `table_type` (TableType): The table type of the namespace.
+/// - `source` (ChainSource): The source chain of the namespace.
+///
+/// # Responses
+/// - **200 OK**: Transaction submitted.
+/// - **400 BAD REQUEST**: Invalid request parameters.
+/// - **401 UNAUTHORIZED**: Missing or invalid API key.
+/// - **403 FORBIDDEN**: The API key is not scoped to this network, endpoint or namespace.
+/// - **429 TOO MANY REQUESTS**: The API key exceeded its rate limit.
+///
+/// # Example Usage
+/// ```sh
+/// curl -X POST "http://127.0.0.1:3000/create_namespace" -H "Content-Type: application/json" -d '{
+///     "schemaName": "ETHEREUM",
+///     "version": 1,
+///     "ddlStatement": "CREATE SCHEMA ETHEREUM",
+///     "tableType": "CoreBlockchain",
+///     "source": "ethereum"
+/// }'
+/// ```
+#[utoipa::path(
+    post,
+    path = "/create_namespace",
+    tag = "create-namespace",
+    request_body = CreateNamespaceRequest,
+    params(
+        ("x-callback-url" = Option<String>, Header, description = "URL to notify of the transaction's lifecycle events")
+    ),
+    responses(
+        (status = 200, description = "Transaction submitted", body = ApiResponse),
+        (status = 400, description = "Invalid request", body = ApiResponse),
+        (status = 401, description = "Missing or invalid API key", body = ApiResponse),
+        (status = 403, description = "API key is not allowed to make this request", body = ApiResponse),
+        (status = 429, description = "API key rate limit exceeded", body = ApiResponse),
+    )
+)]
+pub async fn create_namespace(
+    State(state): State<Arc<TranslationLayerState>>,
+    Extension(caller): Extension<Caller>,
+    callback: CallbackUrl,
+    Json(request): Json<CreateNamespaceRequest>,
+) -> Result<Json<ApiResponse>, (StatusCode, Json<ApiResponse>)> {
+    let tx = create_namespace_call(request, &caller)?;
 
-    // ❌ Return 500 if submitter is missing
-    let Some(submitter) = submitter_opt else {
-        return Json(ApiResponse {
-            success: false,
-            err_msg: Some("TxSubmitter not configured for this network".into()),
-            tx_hash: None,
-        });
-    };
+    Ok(Json(
+        state
+            .submit(&tx, &caller, Endpoint::CreateNamespace, &callback)
+            .await,
+    ))
+}
+
+/// Builds the `create_namespace` call of a `/create_namespace` request.
+pub(crate) fn create_namespace_call(
+    request: CreateNamespaceRequest,
+    caller: &Caller,
+) -> Result<DefaultPayload<CreateNamespace>, (StatusCode, Json<ApiResponse>)> {
+    validate_identifier("Schema", &request.schema_name).map_err(|e| bad_request(&e.to_string()))?;
+    validate_ddl(&request.ddl_statement).map_err(|e| bad_request(&e.to_string()))?;
+    caller.check_namespace(&request.schema_name)?;
+
+    Ok(sxt_chain_runtime::api::tx().tables().create_namespace(
+        BoundedVec(request.schema_name.into_bytes()),
+        request.version,
+        BoundedVec(request.ddl_statement.into_bytes()),
+        request.table_type.into(),
+        request.source.into(),
+    ))
+}
+
+/// Submits a transaction to update the UUID of a namespace version.
+///
+/// # Request Body
+/// - `schema_name` (String): The namespace to update.
+/// - `version` (u16): The namespace version to update.
+/// - `uuid` (String): The new UUID.
+///
+/// # Responses
+/// - **200 OK**: Transaction submitted.
+/// - **400 BAD REQUEST**: Invalid request parameters.
+/// - **401 UNAUTHORIZED**: Missing or invalid API key.
+/// - **403 FORBIDDEN**: The API key is not scoped to this network, endpoint or namespace.
+/// - **429 TOO MANY REQUESTS**: The API key exceeded its rate limit.
+#[utoipa::path(
+    post,
+    path = "/update_namespace_uuid",
+    tag = "update-namespace-uuid",
+    request_body = UpdateNamespaceUuidRequest,
+    params(
+        ("x-callback-url" = Option<String>, Header, description = "URL to notify of the transaction's lifecycle events")
+    ),
+    responses(
+        (status = 200, description = "Transaction submitted", body = ApiResponse),
+        (status = 400, description = "Invalid request", body = ApiResponse),
+        (status = 401, description = "Missing or invalid API key", body = ApiResponse),
+        (status = 403, description = "API key is not allowed to make this request", body = ApiResponse),
+        (status = 429, description = "API key rate limit exceeded", body = ApiResponse),
+    )
+)]
+pub async fn update_namespace_uuid(
+    State(state): State<Arc<TranslationLayerState>>,
+    Extension(caller): Extension<Caller>,
+    callback: CallbackUrl,
+    Json(request): Json<UpdateNamespaceUuidRequest>,
+) -> Result<Json<ApiResponse>, (StatusCode, Json<ApiResponse>)> {
+    let tx = update_namespace_uuid_call(request, &caller)?;
+
+    Ok(Json(
+        state
+            .submit(&tx, &caller, Endpoint::UpdateNamespaceUuid, &callback)
+            .await,
+    ))
+}
+
+/// Builds the `update_namespace_uuid` call of a `/update_namespace_uuid` request.
+pub(crate) fn update_namespace_uuid_call(
+    request: UpdateNamespaceUuidRequest,
+    caller: &Caller,
+) -> Result<DynamicPayload, (StatusCode, Json<ApiResponse>)> {
+    validate_identifier("Schema", &request.schema_name).map_err(|e| bad_request(&e.to_string()))?;
+    validate_uuid(&request.uuid).map_err(|e| bad_request(&e.to_string()))?;
+    caller.check_namespace(&request.schema_name)?;
+
+    // `update_namespace_uuid` is newer than the generated runtime api, so it is built
+    // dynamically and checked against the node's metadata on submission.
+    Ok(subxt::dynamic::tx(
+        "Tables",
+        "update_namespace_uuid",
+        vec![
+            Value::from_bytes(request.schema_name),
+            Value::u128(request.version.into()),
+            Value::from_bytes(request.uuid),
+        ],
+    ))
+}
+
+/// Submits a transaction to update the UUID of a table version.
+///
+/// # Request Body
+/// - `schema_name` (String): The namespace of the table.
+/// - `table_name` (String): The table to update.
+/// - `version` (u16): The table version to update.
+/// - `uuid` (String): The new UUID.
+///
+/// # Responses
+/// - **200 OK**: Transaction submitted.
+/// - **400 BAD REQUEST**: Invalid request parameters.
+/// - **401 UNAUTHORIZED**: Missing or invalid API key.
+/// - **403 FORBIDDEN**: The API key is not scoped to this network, endpoint or namespace.
+/// - **429 TOO MANY REQUESTS**: The API key exceeded its rate limit.
+#[utoipa::path(
+    post,
+    path = "/update_table_uuid",
+    tag = "update-table-uuid",
+    request_body = UpdateTableUuidRequest,
+    params(
+        ("x-callback-url" = Option<String>, Header, description = "URL to notify of the transaction's lifecycle events")
+    ),
+    responses(
+        (status = 200, description = "Transaction submitted", body = ApiResponse),
+        (status = 400, description = "Invalid request", body = ApiResponse),
+        (status = 401, description = "Missing or invalid API key", body = ApiResponse),
+        (status = 403, description = "API key is not allowed to make this request", body = ApiResponse),
+        (status = 429, description = "API key rate limit exceeded", body = ApiResponse),
+    )
+)]
+pub async fn update_table_uuid(
+    State(state): State<Arc<TranslationLayerState>>,
+    Extension(caller): Extension<Caller>,
+    callback: CallbackUrl,
+    Json(request): Json<UpdateTableUuidRequest>,
+) -> Result<Json<ApiResponse>, (StatusCode, Json<ApiResponse>)> {
+    let tx = update_table_uuid_call(request, &caller)?;
+
+    Ok(Json(
+        state
+            .submit(&tx, &caller, Endpoint::UpdateTableUuid, &callback)
+            .await,
+    ))
+}
 
-    // ✅ Lock and use submitter
-    let mut submitter = submitter.lock().await;
+/// Builds the `update_table_uuid` call of a `/update_table_uuid` request.
+pub(crate) fn update_table_uuid_call(
+    request: UpdateTableUuidRequest,
+    caller: &Caller,
+) -> Result<DynamicPayload, (StatusCode, Json<ApiResponse>)> {
+    validate_identifier("Schema", &request.schema_name).map_err(|e| bad_request(&e.to_string()))?;
+    validate_identifier("Table", &request.table_name).map_err(|e| bad_request(&e.to_string()))?;
+    validate_uuid(&request.uuid).map_err(|e| bad_request(&e.to_string()))?;
+    caller.check_namespace(&request.schema_name)?;
 
-    let (success, err_msg, tx_hash) = match submitter.submit_tx_get_hash(&tx).await {
-        Ok(hash) => (true, None, Some(format!("{:#x}", hash))),
-        Err(err) => (false, Some(format!("Error: {err}")), None),
+    // `update_table_uuid` is newer than the generated runtime api, so it is built
+    // dynamically and checked against the node's metadata on submission.
+    Ok(subxt::dynamic::tx(
+        "Tables",
+        "update_table_uuid",
+        vec![
+            table_identifier_value(request.table_name, request.schema_name),
+            Value::u128(request.version.into()),
+            Value::from_bytes(request.uuid),
+        ],
+    ))
+}
+
+/// Submits a transaction to override the insert quorum of a table.
+///
+/// Tables otherwise use the default quorum of their table type.
+///
+/// # Request Body
+/// - `schema_name` (String): The namespace of the table.
+/// - `table_name` (String): The table to update.
+/// - `quorum` (QuorumSize): The public and privileged quorum sizes. `null` disables a scope.
+///
+/// # Responses
+/// - **200 OK**: Transaction submitted.
+/// - **400 BAD REQUEST**: Invalid request parameters.
+/// - **401 UNAUTHORIZED**: Missing or invalid API key.
+/// - **403 FORBIDDEN**: The API key is not scoped to this network, endpoint or namespace.
+/// - **429 TOO MANY REQUESTS**: The API key exceeded its rate limit.
+#[utoipa::path(
+    post,
+    path = "/set_insert_quorum",
+    tag = "set-insert-quorum",
+    request_body = SetInsertQuorumRequest,
+    params(
+        ("x-callback-url" = Option<String>, Header, description = "URL to notify of the transaction's lifecycle events")
+    ),
+    responses(
+        (status = 200, description = "Transaction submitted", body = ApiResponse),
+        (status = 400, description = "Invalid request", body = ApiResponse),
+        (status = 401, description = "Missing or invalid API key", body = ApiResponse),
+        (status = 403, description = "API key is not allowed to make this request", body = ApiResponse),
+        (status = 429, description = "API key rate limit exceeded", body = ApiResponse),
+    )
+)]
+pub async fn set_insert_quorum(
+    State(state): State<Arc<TranslationLayerState>>,
+    Extension(caller): Extension<Caller>,
+    callback: CallbackUrl,
+    Json(request): Json<SetInsertQuorumRequest>,
+) -> Result<Json<ApiResponse>, (StatusCode, Json<ApiResponse>)> {
+    let tx = set_insert_quorum_call(request, &caller)?;
+
+    Ok(Json(
+        state
+            .submit(&tx, &caller, Endpoint::SetInsertQuorum, &callback)
+            .await,
+    ))
+}
+
+/// Builds the `set_table_insert_quorum` call of a `/set_insert_quorum` request.
+pub(crate) fn set_insert_quorum_call(
+    request: SetInsertQuorumRequest,
+    caller: &Caller,
+) -> Result<DynamicPayload, (StatusCode, Json<ApiResponse>)> {
+    validate_identifier("Schema", &request.schema_name).map_err(|e| bad_request(&e.to_string()))?;
+    validate_identifier("Table", &request.table_name).map_err(|e| bad_request(&e.to_string()))?;
+    caller.check_namespace(&request.schema_name)?;
+
+    // `set_table_insert_quorum` is newer than the generated runtime api, so it is built
+    // dynamically and checked against the node's metadata on submission.
+    let quorum_scope = |size: Option<u8>| match size {
+        Some(size) => Value::unnamed_variant("Some", [Value::u128(size.into())]),
+        None => Value::unnamed_variant("None", []),
     };
+    Ok(subxt::dynamic::tx(
+        "Tables",
+        "set_table_insert_quorum",
+        vec![
+            table_identifier_value(request.table_name, request.schema_name),
+            Value::named_composite([
+                ("public", quorum_scope(request.quorum.public)),
+                ("privileged", quorum_scope(request.quorum.privileged)),
+            ]),
+        ],
+    ))
+}
+
+/// Builds a dynamic `TableIdentifier` argument.
+fn table_identifier_value(table_name: String, schema_name: String) -> Value {
+    Value::named_composite([
+        ("name", Value::from_bytes(table_name)),
+        ("namespace", Value::from_bytes(schema_name)),
+    ])
+}
 
-    Json(ApiResponse {
-        success,
-        err_msg,
-        tx_hash,
+/// Fetches every table on chain that matches `filter`, along with its DDL and insert quorum.
+async fn fetch_tables(
+    state: &TranslationLayerState,
+    filter: impl Fn(&str, &str) -> bool,
+) -> Result<Vec<ApiTable>, (StatusCode, Json<ApiResponse>)> {
+    let storage = state
+        .client
+        .storage()
+        .at_latest()
+        .await
+        .map_err(|e| internal_server_error(&format!("Failed to access storage: {}", e)))?;
+
+    let mut tables = Vec::new();
+    for table_type in [
+        TableType::CoreBlockchain,
+        TableType::SCI,
+        TableType::Community,
+    ] {
+        let query = sxt_chain_runtime::api::storage()
+            .tables()
+            .identifiers(RuntimeTableType::from(table_type.clone()));
+        let identifiers = storage
+            .fetch_or_default(&query)
+            .await
+            .map_err(|e| internal_server_error(&format!("Failed to fetch tables: {}", e)))?;
+
+        for ident in identifiers.0 {
+            let schema_name = bytes_to_string(&ident.namespace.0);
+            let table_name = bytes_to_string(&ident.name.0);
+            if !filter(&schema_name, &table_name) {
+                continue;
+            }
+
+            let schema_query = sxt_chain_runtime::api::storage()
+                .tables()
+                .schemas(&ident.namespace, &ident.name);
+            let ddl_statement = storage
+                .fetch(&schema_query)
+                .await
+                .map_err(|e| internal_server_error(&format!("Failed to fetch schema: {}", e)))?
+                .map(|ddl| bytes_to_string(&ddl.0));
+
+            let quorum_query = sxt_chain_runtime::api::storage()
+                .tables()
+                .table_insert_quorums(&ident);
+            let quorum = storage.fetch_or_default(&quorum_query).await.map_err(|e| {
+                internal_server_error(&format!("Failed to fetch insert quorum: {}", e))
+            })?;
+
+            tables.push(ApiTable {
+                schema_name,
+                table_name,
+                table_type: table_type.clone(),
+                ddl_statement,
+                insert_quorum: QuorumSize {
+                    public: quorum.public,
+                    privileged: quorum.privileged,
+                },
+            });
+        }
+    }
+
+    Ok(tables)
+}
+
+/// Retrieves the tables on chain, optionally restricted to a single namespace.
+///
+/// # Query Parameters
+/// - `schema_name` (String, optional): Only return tables in this namespace.
+///
+/// # Responses
+/// - **200 OK**: Successfully retrieved the tables.
+/// - **500 INTERNAL SERVER ERROR**: Error accessing blockchain storage.
+///
+/// # Example Usage
+/// ```sh
+/// curl -X GET "http://127.0.0.1:3000/get_tables?schema_name=ETHEREUM"
+/// ```
+#[utoipa::path(
+    get,
+    path = "/get_tables",
+    tag = "get-tables",
+    params(
+        ("schema_name" = Option<String>, Query, description = "Namespace to list tables of")
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved tables", body = GetTablesResponse),
+        (status = 500, description = "Internal server error", body = ApiResponse)
+    )
+)]
+pub async fn get_tables(
+    State(state): State<Arc<TranslationLayerState>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<GetTablesResponse>, (StatusCode, Json<ApiResponse>)> {
+    let schema_name = params.get("schema_name");
+
+    let tables = fetch_tables(&state, |namespace, _| {
+        schema_name.is_none_or(|schema_name| namespace.eq_ignore_ascii_case(schema_name))
+    })
+    .await?;
+
+    Ok(Json(GetTablesResponse {
+        success: true,
+        err_msg: None,
+        tables,
+    }))
+}
+
+/// Retrieves a single table on chain.
+///
+/// # Query Parameters
+/// - `schema_name` (String): The namespace of the table.
+/// - `table_name` (String): The table name.
+///
+/// # Responses
+/// - **200 OK**: Successfully retrieved the table.
+/// - **400 BAD REQUEST**: Missing query parameters.
+/// - **404 NOT FOUND**: The table does not exist.
+/// - **500 INTERNAL SERVER ERROR**: Error accessing blockchain storage.
+///
+/// # Example Usage
+/// ```sh
+/// curl -X GET "http://127.0.0.1:3000/get_table?schema_name=ETHEREUM&table_name=BLOCKS"
+/// ```
+#[utoipa::path(
+    get,
+    path = "/get_table",
+    tag = "get-table",
+    params(
+        ("schema_name" = String, Query, description = "Namespace of the table"),
+        ("table_name" = String, Query, description = "Name of the table")
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved table", body = ApiTable),
+        (status = 400, description = "Invalid request parameters", body = ApiResponse),
+        (status = 404, description = "Table not found", body = ApiResponse),
+        (status = 500, description = "Internal server error", body = ApiResponse)
+    )
+)]
+pub async fn get_table(
+    State(state): State<Arc<TranslationLayerState>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<ApiTable>, (StatusCode, Json<ApiResponse>)> {
+    let schema_name = extract_param(&params, "schema_name")?;
+    let table_name = extract_param(&params, "table_name")?;
+
+    fetch_tables(&state, |namespace, name| {
+        namespace.eq_ignore_ascii_case(&schema_name) && name.eq_ignore_ascii_case(&table_name)
     })
+    .await?
+    .into_iter()
+    .next()
+    .map(Json)
+    .ok_or_else(|| not_found("Table not found"))
+}
+
+/// Retrieves every namespace version on chain along with its UUID.
+///
+/// # Responses
+/// - **200 OK**: Successfully retrieved the namespaces.
+/// - **500 INTERNAL SERVER ERROR**: Error accessing blockchain storage.
+///
+/// # Example Usage
+/// ```sh
+/// curl -X GET "http://127.0.0.1:3000/get_schemas"
+/// ```
+#[utoipa::path(
+    get,
+    path = "/get_schemas",
+    tag = "get-schemas",
+    responses(
+        (status = 200, description = "Successfully retrieved namespaces", body = GetSchemasResponse),
+        (status = 500, description = "Internal server error", body = ApiResponse)
+    )
+)]
+pub async fn get_schemas(
+    State(state): State<Arc<TranslationLayerState>>,
+) -> Result<Json<GetSchemasResponse>, (StatusCode, Json<ApiResponse>)> {
+    let storage = state
+        .client
+        .storage()
+        .at_latest()
+        .await
+        .map_err(|e| internal_server_error(&format!("Failed to access storage: {}", e)))?;
+
+    let query = sxt_chain_runtime::api::storage()
+        .tables()
+        .namespace_versions_iter();
+    let mut namespace_stream = storage
+        .iter(query)
+        .await
+        .map_err(|e| internal_server_error(&format!("Failed to get storage iterator: {}", e)))?;
+
+    let mut schemas = Vec::new();
+    while let Some(entry) = namespace_stream.next().await {
+        let entry = entry
+            .map_err(|e| internal_server_error(&format!("Failed to read namespace: {}", e)))?;
+        let Some((schema_name, version)) = decode_namespace_versions_key(&entry.key_bytes) else {
+            return Err(internal_server_error(
+                "Failed to decode namespace storage key",
+            ));
+        };
+
+        schemas.push(ApiSchema {
+            schema_name,
+            version,
+            uuid: bytes_to_string(&entry.value.0),
+        });
+    }
+
+    Ok(Json(GetSchemasResponse {
+        success: true,
+        err_msg: None,
+        schemas,
+    }))
 }
@@ -0,0 +1,677 @@
+//! API-key authentication, scoping and rate limiting.
+//!
+//! Every endpoint that submits an extrinsic signs it with the server key, so those endpoints
+//! require an API key. Keys are configured in a JSON file and each key is scoped to a set of
+//! networks, a set of endpoints and, optionally, an allowlist of table namespaces. Each key
+//! also has its own token-bucket rate limit.
+//!
+//! Keys are stored as the hex encoded SHA-256 hash of the key so the configuration file does
+//! not hold usable credentials:
+//!
+//! ```json
+//! {
+//!   "keys": [
+//!     {
+//!       "id": "indexing-team",
+//!       "keySha256": "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08",
+//!       "networks": ["testnet"],
+//!       "endpoints": ["create_table", "drop_table"],
+//!       "namespaces": ["ETHEREUM"],
+//!       "rateLimit": { "capacity": 10, "refillPerSecond": 0.5 }
+//!     }
+//!   ]
+//! }
+//! ```
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use axum::extract::{MatchedPath, Request, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use log::warn;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use snafu::{ResultExt, Snafu};
+
+use crate::model::ApiResponse;
+use crate::state::Network;
+
+/// Header carrying the API key. `Authorization: Bearer <key>` is accepted as well.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Endpoints that submit extrinsics and therefore require an API key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Endpoint {
+    /// `/create_table`
+    CreateTable,
+    /// `/drop_table`
+    DropTable,
+    /// `/add_smartcontract`
+    AddSmartcontract,
+    /// `/remove_smartcontract`
+    RemoveSmartcontract,
+    /// `/create_namespace`
+    CreateNamespace,
+    /// `/update_namespace_uuid`
+    UpdateNamespaceUuid,
+    /// `/update_table_uuid`
+    UpdateTableUuid,
+    /// `/set_insert_quorum`
+    SetInsertQuorum,
+    /// `/set_permissions`
+    SetPermissions,
+    /// `/add_proxy_permission`
+    AddProxyPermission,
+    /// `/batch`
+    Batch,
+}
+
+impl Endpoint {
+    /// Returns the endpoint served at `path`, ignoring any network prefix.
+    pub fn from_path(path: &str) -> Option<Self> {
+        match path.rsplit('/').next()? {
+            "create_table" => Some(Endpoint::CreateTable),
+            "drop_table" => Some(Endpoint::DropTable),
+            "add_smartcontract" => Some(Endpoint::AddSmartcontract),
+            "remove_smartcontract" => Some(Endpoint::RemoveSmartcontract),
+            "create_namespace" => Some(Endpoint::CreateNamespace),
+            "update_namespace_uuid" => Some(Endpoint::UpdateNamespaceUuid),
+            "update_table_uuid" => Some(Endpoint::UpdateTableUuid),
+            "set_insert_quorum" => Some(Endpoint::SetInsertQuorum),
+            "set_permissions" => Some(Endpoint::SetPermissions),
+            "add_proxy_permission" => Some(Endpoint::AddProxyPermission),
+            "batch" => Some(Endpoint::Batch),
+            _ => None,
+        }
+    }
+
+    /// The name of the endpoint, as used in the key configuration.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Endpoint::CreateTable => "create_table",
+            Endpoint::DropTable => "drop_table",
+            Endpoint::AddSmartcontract => "add_smartcontract",
+            Endpoint::RemoveSmartcontract => "remove_smartcontract",
+            Endpoint::CreateNamespace => "create_namespace",
+            Endpoint::UpdateNamespaceUuid => "update_namespace_uuid",
+            Endpoint::UpdateTableUuid => "update_table_uuid",
+            Endpoint::SetInsertQuorum => "set_insert_quorum",
+            Endpoint::SetPermissions => "set_permissions",
+            Endpoint::AddProxyPermission => "add_proxy_permission",
+            Endpoint::Batch => "batch",
+        }
+    }
+}
+
+/// Token-bucket rate limit of a single key.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimit {
+    /// Maximum number of requests that can be made in a burst.
+    pub capacity: u32,
+    /// Number of requests regained per second.
+    pub refill_per_second: f64,
+}
+
+/// Configuration of a single API key.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKeyEntry {
+    /// Identity of the caller, recorded alongside every extrinsic it submits.
+    pub id: String,
+    /// Hex encoded SHA-256 hash of the key.
+    pub key_sha256: String,
+    /// Networks the key may submit to.
+    pub networks: Vec<Network>,
+    /// Endpoints the key may call.
+    pub endpoints: Vec<Endpoint>,
+    /// Table namespaces the key may touch. Any namespace is allowed if absent.
+    #[serde(default)]
+    pub namespaces: Option<Vec<String>>,
+    /// Rate limit of the key.
+    pub rate_limit: RateLimit,
+}
+
+/// Contents of the API key configuration file.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApiKeysConfig {
+    /// The configured keys.
+    pub keys: Vec<ApiKeyEntry>,
+}
+
+/// Errors loading the API key configuration.
+#[derive(Debug, Snafu)]
+pub enum ApiKeysConfigError {
+    /// The configuration file could not be read.
+    #[snafu(display("Failed to read API key configuration '{path}': {source}"))]
+    ReadApiKeys {
+        /// Path of the configuration file.
+        path: String,
+        /// The underlying I/O error.
+        source: std::io::Error,
+    },
+    /// The configuration file is not valid JSON.
+    #[snafu(display("Failed to parse API key configuration: {source}"))]
+    ParseApiKeys {
+        /// The underlying JSON error.
+        source: serde_json::Error,
+    },
+    /// A key hash is not a hex encoded SHA-256 hash.
+    #[snafu(display("API key '{id}' does not have a valid SHA-256 key hash"))]
+    InvalidKeyHash {
+        /// Identity of the misconfigured key.
+        id: String,
+    },
+}
+
+/// Reasons a request is rejected by [`ApiKeyAuth::authorize`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthError {
+    /// No API key was provided.
+    MissingApiKey,
+    /// The API key is not configured.
+    InvalidApiKey,
+    /// The API key may not submit to this network.
+    NetworkNotAllowed,
+    /// The API key may not call this endpoint.
+    EndpointNotAllowed,
+    /// The API key has exhausted its rate limit.
+    RateLimited,
+}
+
+impl IntoResponse for AuthError {
+    fn into_response(self) -> Response {
+        let (status, message) = match self {
+            AuthError::MissingApiKey => (StatusCode::UNAUTHORIZED, "Missing API key"),
+            AuthError::InvalidApiKey => (StatusCode::UNAUTHORIZED, "Invalid API key"),
+            AuthError::NetworkNotAllowed => (
+                StatusCode::FORBIDDEN,
+                "API key is not allowed on this network",
+            ),
+            AuthError::EndpointNotAllowed => (
+                StatusCode::FORBIDDEN,
+                "API key is not allowed to call this endpoint",
+            ),
+            AuthError::RateLimited => {
+                (StatusCode::TOO_MANY_REQUESTS, "API key rate limit exceeded")
+            }
+        };
+
+        (
+            status,
+            Json(ApiResponse {
+                success: false,
+                err_msg: Some(message.to_string()),
+                tx_hash: None,
+            }),
+        )
+            .into_response()
+    }
+}
+
+/// The authenticated caller of a request, inserted as a request extension.
+#[derive(Debug, Clone)]
+pub struct Caller {
+    /// Identity of the caller.
+    pub id: String,
+    /// Uppercased namespaces the caller may touch, or `None` if any namespace is allowed.
+    namespaces: Option<BTreeSet<String>>,
+    /// Endpoints the caller may call, or `None` if every endpoint is allowed.
+    endpoints: Option<HashSet<Endpoint>>,
+}
+
+impl Caller {
+    /// The caller of every request when authentication is disabled.
+    pub fn anonymous() -> Self {
+        Self {
+            id: "anonymous".to_string(),
+            namespaces: None,
+            endpoints: None,
+        }
+    }
+
+    /// Returns whether the caller may touch tables in `namespace`.
+    pub fn is_namespace_allowed(&self, namespace: &str) -> bool {
+        self.namespaces
+            .as_ref()
+            .is_none_or(|namespaces| namespaces.contains(&namespace.to_uppercase()))
+    }
+
+    /// Returns a `403 Forbidden` response if the caller may not touch tables in `namespace`.
+    pub fn check_namespace(&self, namespace: &str) -> Result<(), (StatusCode, Json<ApiResponse>)> {
+        if self.is_namespace_allowed(namespace) {
+            return Ok(());
+        }
+
+        Err((
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse {
+                success: false,
+                err_msg: Some(format!(
+                    "API key is not allowed to modify namespace {namespace}"
+                )),
+                tx_hash: None,
+            }),
+        ))
+    }
+
+    /// Returns a `403 Forbidden` response if the caller is restricted to some namespaces.
+    ///
+    /// Requests that affect every namespace, or that cannot be attributed to one, need a key
+    /// that may touch any namespace.
+    pub fn check_unrestricted(&self) -> Result<(), (StatusCode, Json<ApiResponse>)> {
+        if self.namespaces.is_none() {
+            return Ok(());
+        }
+
+        Err((
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse {
+                success: false,
+                err_msg: Some(
+                    "API key is restricted to namespaces and may not modify other ones".to_string(),
+                ),
+                tx_hash: None,
+            }),
+        ))
+    }
+
+    /// The caller of a key that may only touch `namespaces`.
+    #[cfg(test)]
+    pub(crate) fn scoped_to(namespaces: &[&str]) -> Self {
+        Self {
+            id: "scoped".to_string(),
+            namespaces: Some(namespaces.iter().map(|ns| ns.to_uppercase()).collect()),
+            endpoints: None,
+        }
+    }
+
+    /// Returns a `403 Forbidden` response if the caller may not call `endpoint`.
+    ///
+    /// Endpoints are checked by the [`require_api_key`] middleware, so this is only needed for
+    /// calls made on the caller's behalf through `/batch`.
+    pub fn check_endpoint(
+        &self,
+        endpoint: Endpoint,
+    ) -> Result<(), (StatusCode, Json<ApiResponse>)> {
+        if self
+            .endpoints
+            .as_ref()
+            .is_none_or(|endpoints| endpoints.contains(&endpoint))
+        {
+            return Ok(());
+        }
+
+        Err((
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse {
+                success: false,
+                err_msg: Some(format!(
+                    "API key is not allowed to call {}",
+                    endpoint.as_str()
+                )),
+                tx_hash: None,
+            }),
+        ))
+    }
+}
+
+/// Tokens available to a single key.
+#[derive(Debug)]
+struct TokenBucket {
+    /// Requests that can currently be made.
+    tokens: f64,
+    /// When `tokens` was last refilled.
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// A full bucket for the given limit.
+    fn full(limit: &RateLimit, now: Instant) -> Self {
+        Self {
+            tokens: limit.capacity as f64,
+            last_refill: now,
+        }
+    }
+
+    /// Refills the bucket and takes a token from it, if one is available.
+    fn try_take(&mut self, limit: &RateLimit, now: Instant) -> bool {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * limit.refill_per_second).min(limit.capacity as f64);
+        self.last_refill = now;
+
+        if self.tokens < 1.0 {
+            return false;
+        }
+        self.tokens -= 1.0;
+        true
+    }
+}
+
+/// Validates API keys and enforces their scopes and rate limits.
+#[derive(Debug)]
+pub struct ApiKeyAuth {
+    /// Configured keys by the SHA-256 hash of the key, or `None` if authentication is disabled.
+    keys: Option<HashMap<[u8; 32], ApiKeyEntry>>,
+    /// Token buckets by key identity.
+    buckets: Mutex<HashMap<String, TokenBucket>>,
+}
+
+impl ApiKeyAuth {
+    /// Accepts every request as [`Caller::anonymous`].
+    pub fn disabled() -> Self {
+        Self {
+            keys: None,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Builds the authenticator from a parsed configuration.
+    pub fn from_config(config: ApiKeysConfig) -> Result<Self, ApiKeysConfigError> {
+        let keys = config
+            .keys
+            .into_iter()
+            .map(|entry| {
+                let hash = hex::decode(entry.key_sha256.trim_start_matches("0x"))
+                    .ok()
+                    .and_then(|hash| <[u8; 32]>::try_from(hash).ok())
+                    .ok_or_else(|| ApiKeysConfigError::InvalidKeyHash {
+                        id: entry.id.clone(),
+                    })?;
+                Ok((hash, entry))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            keys: Some(keys),
+            buckets: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Loads the authenticator from a JSON configuration file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ApiKeysConfigError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).context(ReadApiKeysSnafu {
+            path: path.display().to_string(),
+        })?;
+        let config = serde_json::from_str(&contents).context(ParseApiKeysSnafu)?;
+        Self::from_config(config)
+    }
+
+    /// Authenticates `api_key` and checks that it may call `endpoint` on `network`.
+    ///
+    /// A successful authorization takes a token from the key's rate limit.
+    pub fn authorize(
+        &self,
+        api_key: Option<&str>,
+        network: Network,
+        endpoint: Endpoint,
+        now: Instant,
+    ) -> Result<Caller, AuthError> {
+        let Some(keys) = &self.keys else {
+            return Ok(Caller::anonymous());
+        };
+
+        let api_key = api_key.ok_or(AuthError::MissingApiKey)?;
+        let hash: [u8; 32] = Sha256::digest(api_key.as_bytes()).into();
+        let entry = keys.get(&hash).ok_or(AuthError::InvalidApiKey)?;
+
+        if !entry.networks.contains(&network) {
+            return Err(AuthError::NetworkNotAllowed);
+        }
+        if !entry.endpoints.contains(&endpoint) {
+            return Err(AuthError::EndpointNotAllowed);
+        }
+
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        let allowed = buckets
+            .entry(entry.id.clone())
+            .or_insert_with(|| TokenBucket::full(&entry.rate_limit, now))
+            .try_take(&entry.rate_limit, now);
+        if !allowed {
+            return Err(AuthError::RateLimited);
+        }
+
+        Ok(Caller {
+            id: entry.id.clone(),
+            namespaces: entry.namespaces.as_ref().map(|namespaces| {
+                namespaces
+                    .iter()
+                    .map(|namespace| namespace.to_uppercase())
+                    .collect()
+            }),
+            endpoints: Some(entry.endpoints.iter().copied().collect()),
+        })
+    }
+}
+
+/// State of the [`require_api_key`] middleware for the routes of one network.
+#[derive(Clone)]
+pub struct AuthScope {
+    /// The shared authenticator.
+    pub auth: Arc<ApiKeyAuth>,
+    /// The network the routes submit to.
+    pub network: Network,
+}
+
+/// Reads the API key from the `x-api-key` or `Authorization: Bearer` header.
+fn api_key_from_headers(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(API_KEY_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .or_else(|| {
+            headers
+                .get(header::AUTHORIZATION)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.strip_prefix("Bearer "))
+        })
+}
+
+/// Middleware authorizing requests to the extrinsic submitting endpoints.
+///
+/// On success the [`Caller`] is inserted as a request extension for the handler.
+pub async fn require_api_key(
+    State(scope): State<AuthScope>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let Some(endpoint) = request
+        .extensions()
+        .get::<MatchedPath>()
+        .and_then(|path| Endpoint::from_path(path.as_str()))
+    else {
+        return AuthError::EndpointNotAllowed.into_response();
+    };
+
+    let api_key = api_key_from_headers(request.headers());
+    match scope
+        .auth
+        .authorize(api_key, scope.network, endpoint, Instant::now())
+    {
+        Ok(caller) => {
+            request.extensions_mut().insert(caller);
+            next.run(request).await
+        }
+        Err(err) => {
+            warn!(
+                "Rejected request to {} on {:?}: {:?}",
+                endpoint.as_str(),
+                scope.network,
+                err
+            );
+            err.into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use axum::http::HeaderValue;
+
+    use super::*;
+
+    /// SHA-256 of `"test"`.
+    const TEST_KEY_SHA256: &str =
+        "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+    fn entry(networks: Vec<Network>, capacity: u32) -> ApiKeyEntry {
+        ApiKeyEntry {
+            id: "indexing-team".to_string(),
+            key_sha256: TEST_KEY_SHA256.to_string(),
+            networks,
+            endpoints: vec![Endpoint::CreateTable],
+            namespaces: Some(vec!["ethereum".to_string()]),
+            rate_limit: RateLimit {
+                capacity,
+                refill_per_second: 1.0,
+            },
+        }
+    }
+
+    fn auth(entry: ApiKeyEntry) -> ApiKeyAuth {
+        ApiKeyAuth::from_config(ApiKeysConfig { keys: vec![entry] }).unwrap()
+    }
+
+    #[test]
+    fn keys_are_matched_by_their_sha256_hash() {
+        let auth = auth(entry(vec![Network::Testnet], 10));
+        let now = Instant::now();
+
+        let caller = auth
+            .authorize(Some("test"), Network::Testnet, Endpoint::CreateTable, now)
+            .unwrap();
+        assert_eq!(caller.id, "indexing-team");
+        assert_eq!(
+            auth.authorize(
+                Some(TEST_KEY_SHA256),
+                Network::Testnet,
+                Endpoint::CreateTable,
+                now
+            )
+            .unwrap_err(),
+            AuthError::InvalidApiKey
+        );
+    }
+
+    #[test]
+    fn prefixed_hashes_are_accepted_and_malformed_ones_rejected() {
+        let mut prefixed = entry(vec![Network::Testnet], 10);
+        prefixed.key_sha256 = format!("0x{TEST_KEY_SHA256}");
+        assert!(ApiKeyAuth::from_config(ApiKeysConfig {
+            keys: vec![prefixed]
+        })
+        .is_ok());
+
+        let mut truncated = entry(vec![Network::Testnet], 10);
+        truncated.key_sha256 = TEST_KEY_SHA256[..32].to_string();
+        assert!(matches!(
+            ApiKeyAuth::from_config(ApiKeysConfig {
+                keys: vec![truncated]
+            }),
+            Err(ApiKeysConfigError::InvalidKeyHash { id }) if id == "indexing-team"
+        ));
+    }
+
+    #[test]
+    fn missing_and_unknown_keys_are_rejected() {
+        let auth = auth(entry(vec![Network::Testnet], 10));
+        let now = Instant::now();
+
+        assert_eq!(
+            auth.authorize(None, Network::Testnet, Endpoint::CreateTable, now)
+                .unwrap_err(),
+            AuthError::MissingApiKey
+        );
+        assert_eq!(
+            auth.authorize(Some("wrong"), Network::Testnet, Endpoint::CreateTable, now)
+                .unwrap_err(),
+            AuthError::InvalidApiKey
+        );
+    }
+
+    #[test]
+    fn keys_are_scoped_per_network_and_endpoint() {
+        let auth = auth(entry(vec![Network::Testnet], 10));
+        let now = Instant::now();
+
+        assert_eq!(
+            auth.authorize(Some("test"), Network::Mainnet, Endpoint::CreateTable, now)
+                .unwrap_err(),
+            AuthError::NetworkNotAllowed
+        );
+        assert_eq!(
+            auth.authorize(Some("test"), Network::Testnet, Endpoint::DropTable, now)
+                .unwrap_err(),
+            AuthError::EndpointNotAllowed
+        );
+
+        let caller = auth
+            .authorize(Some("test"), Network::Testnet, Endpoint::CreateTable, now)
+            .unwrap();
+        assert!(caller.is_namespace_allowed("Ethereum"));
+        assert!(!caller.is_namespace_allowed("BITCOIN"));
+        assert!(caller.check_endpoint(Endpoint::CreateTable).is_ok());
+        assert!(caller.check_endpoint(Endpoint::SetPermissions).is_err());
+    }
+
+    #[test]
+    fn keys_are_rate_limited_until_their_bucket_refills() {
+        let auth = auth(entry(vec![Network::Testnet], 1));
+        let now = Instant::now();
+
+        assert!(auth
+            .authorize(Some("test"), Network::Testnet, Endpoint::CreateTable, now)
+            .is_ok());
+        assert_eq!(
+            auth.authorize(Some("test"), Network::Testnet, Endpoint::CreateTable, now)
+                .unwrap_err(),
+            AuthError::RateLimited
+        );
+        assert!(auth
+            .authorize(
+                Some("test"),
+                Network::Testnet,
+                Endpoint::CreateTable,
+                now + Duration::from_secs(1)
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn disabled_auth_accepts_every_request_anonymously() {
+        let caller = ApiKeyAuth::disabled()
+            .authorize(
+                None,
+                Network::Mainnet,
+                Endpoint::SetPermissions,
+                Instant::now(),
+            )
+            .unwrap();
+        assert_eq!(caller.id, "anonymous");
+        assert!(caller.is_namespace_allowed("ANY"));
+    }
+
+    #[test]
+    fn api_key_is_read_from_either_header() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(api_key_from_headers(&headers), None);
+
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer test"),
+        );
+        assert_eq!(api_key_from_headers(&headers), Some("test"));
+
+        headers.insert(API_KEY_HEADER, HeaderValue::from_static("other"));
+        assert_eq!(api_key_from_headers(&headers), Some("other"));
+    }
+}
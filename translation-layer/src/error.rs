@@ -83,6 +83,42 @@ pub enum Error {
         /// Source Error
         source: subxt::Error,
     },
+
+    /// Error when reading from or writing to the persistent transaction history store.
+    #[snafu(display("Transaction history store error: {source}"))]
+    TxHistoryStoreError {
+        /// The underlying error from the `rusqlite` library.
+        source: rusqlite::Error,
+    },
+
+    /// Error when a query of the transaction history store panics or is cancelled before it
+    /// completes on the blocking thread pool.
+    #[snafu(display("Transaction history store task failed: {source}"))]
+    TxHistoryTaskError {
+        /// The underlying error from the `tokio` runtime.
+        source: tokio::task::JoinError,
+    },
+
+    /// Error when a call cannot be encoded against the chain metadata.
+    #[snafu(display("Error encoding call: {source}"))]
+    EncodeCallError {
+        /// The underlying error from the `subxt` library.
+        source: subxt::Error,
+    },
+
+    /// Error when an encoded call cannot be read back as a `RuntimeCall`.
+    #[snafu(display("Error decoding call as a runtime call: {source}"))]
+    DecodeCallError {
+        /// The underlying error from the `scale-decode` library.
+        source: subxt::ext::scale_decode::Error,
+    },
+
+    /// Error when building the HTTP client used to deliver webhooks.
+    #[snafu(display("Error building webhook client: {source}"))]
+    WebhookClientError {
+        /// The underlying error from the `reqwest` library.
+        source: reqwest::Error,
+    },
 }
 
 /// Type alias for results that return a `Result<T, Error>`, simplifying error handling.
@@ -32,6 +32,9 @@ pub mod tx_progress;
 /// Api
 pub mod api;
 
+/// API-key authentication, scoping and rate limiting.
+pub mod auth;
+
 /// Data models
 pub mod model;
 
@@ -40,3 +43,6 @@ pub mod utils;
 
 /// Represents the state of the api
 pub mod state;
+
+/// Signed delivery of transaction lifecycle events to webhooks.
+pub mod webhooks;
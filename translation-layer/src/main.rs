@@ -3,17 +3,19 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use axum::routing::{get, post};
-use axum::Router;
+use axum::{middleware, Router};
 use clap::Parser;
-use log::info;
+use log::{info, warn};
 use subxt::backend::rpc::reconnecting_rpc_client::{PingConfig, RpcClient};
 use subxt::OnlineClient;
 use tokio::net::TcpListener;
 use tokio::sync::{mpsc, Mutex};
 use tower_http::cors::{Any, CorsLayer};
+use translation_layer::auth::{self, ApiKeyAuth, AuthScope};
 use translation_layer::state::{Network, TranslationLayerState};
 use translation_layer::tx_progress::TxProgressDb;
 use translation_layer::tx_submitter::TxSubmitter;
+use translation_layer::webhooks::WebhookDeliverer;
 use translation_layer::{api, signer};
 use utoipa::{Modify, OpenApi};
 use utoipa_swagger_ui::SwaggerUi;
@@ -57,6 +59,60 @@ struct Cli {
         help = "Address to bind the Axum HTTP server"
     )]
     bind_addr: String,
+
+    /// Path of the SQLite file storing mainnet transaction history (default: tx-history-mainnet.sqlite)
+    #[arg(
+        long,
+        env = "MAINNET_TX_DB_PATH",
+        default_value = "tx-history-mainnet.sqlite",
+        help = "Path of the mainnet transaction history store, may be shared by several replicas"
+    )]
+    mainnet_tx_db_path: String,
+
+    /// Path of the SQLite file storing testnet transaction history (default: tx-history-testnet.sqlite)
+    #[arg(
+        long,
+        env = "TESTNET_TX_DB_PATH",
+        default_value = "tx-history-testnet.sqlite",
+        help = "Path of the testnet transaction history store, may be shared by several replicas"
+    )]
+    testnet_tx_db_path: String,
+
+    /// Seconds a transaction's history is kept after its last update (default: 7 days)
+    #[arg(
+        long,
+        env = "TX_HISTORY_TTL",
+        default_value_t = 604800,
+        help = "Seconds to keep a transaction's history after its last update"
+    )]
+    tx_history_ttl: u64,
+
+    /// Path of the JSON file configuring API keys. Required unless `--insecure-no-auth` is set.
+    #[arg(
+        long,
+        env = "API_KEYS_FILE",
+        required_unless_present = "insecure_no_auth",
+        help = "Path of the API key configuration"
+    )]
+    api_keys: Option<String>,
+
+    /// Serve every endpoint without authentication. Only meant for local development.
+    #[arg(
+        long,
+        env = "INSECURE_NO_AUTH",
+        conflicts_with = "api_keys",
+        help = "Disable authentication, leaving the endpoints signing with the server keys open"
+    )]
+    insecure_no_auth: bool,
+
+    /// Secret used to sign webhook payloads. Webhooks are disabled if omitted.
+    #[arg(
+        long,
+        env = "WEBHOOK_SECRET",
+        hide_env_values = true,
+        help = "Secret signing webhook payloads; webhooks are disabled if omitted"
+    )]
+    webhook_secret: Option<String>,
 }
 
 #[tokio::main]
@@ -68,17 +124,43 @@ async fn main() -> anyhow::Result<()> {
 
     info!("🚀 Starting Translation Layer...");
 
-    // Channel for tracking transaction progress
-    let (tx, rx) = mpsc::channel(100);
-    let tx_db = Arc::new(TxProgressDb::new(rx));
-    tokio::spawn({
-        let tx_db = tx_db.clone();
-        async move {
-            info!("🟡 TxProgressDb is running...");
-            tx_db.run().await;
+    let tx_history_ttl = Duration::from_secs(cli.tx_history_ttl);
+
+    let api_key_auth = Arc::new(match &cli.api_keys {
+        Some(path) => {
+            info!("🔐 Loading API keys from {}", path);
+            ApiKeyAuth::from_file(path)?
+        }
+        // Clap only allows omitting the keys together with `--insecure-no-auth`.
+        None => {
+            warn!("⚠️ --insecure-no-auth is set, authentication is disabled");
+            ApiKeyAuth::disabled()
         }
     });
 
+    if cli.mainnet_tx_db_path == cli.testnet_tx_db_path {
+        anyhow::bail!("Mainnet and testnet must not share a transaction history store");
+    }
+
+    let webhooks_enabled = match &cli.webhook_secret {
+        Some(secret) => {
+            // Every network queues its deliveries in its own store.
+            for path in [&cli.mainnet_tx_db_path, &cli.testnet_tx_db_path] {
+                let deliverer = WebhookDeliverer::open(path, secret.as_bytes())?;
+                let path = path.clone();
+                tokio::spawn(async move {
+                    info!("🟡 WebhookDeliverer for {} is running...", path);
+                    deliverer.run().await;
+                });
+            }
+            true
+        }
+        None => {
+            warn!("⚠️ No webhook secret configured, webhooks are disabled");
+            false
+        }
+    };
+
     // ──────────────── MAINNET ────────────────
     info!("🔵 Connecting to mainnet: {}", cli.mainnet_url);
     let mainnet_rpc_client = RpcClient::builder()
@@ -90,12 +172,28 @@ async fn main() -> anyhow::Result<()> {
 
     let mainnet_api = OnlineClient::from_rpc_client(mainnet_rpc_client).await?;
 
+    // Channel for tracking mainnet transaction progress
+    let (mainnet_tx, mainnet_rx) = mpsc::channel(100);
+    let mainnet_tx_db = Arc::new(TxProgressDb::open(
+        &cli.mainnet_tx_db_path,
+        tx_history_ttl,
+        mainnet_api.clone(),
+        mainnet_rx,
+    )?);
+    tokio::spawn({
+        let tx_db = mainnet_tx_db.clone();
+        async move {
+            info!("🟡 Mainnet TxProgressDb is running...");
+            tx_db.run().await;
+        }
+    });
+
     let mainnet_key = signer::load_substrate_key(&cli.mainnet_key).await?;
     let mainnet_submitter = Arc::new(Mutex::new(
         TxSubmitter::new(
             mainnet_api.clone(),
             mainnet_key,
-            tx.clone(),
+            mainnet_tx,
             cli.mainnet_url,
         )
         .await?,
@@ -106,8 +204,9 @@ async fn main() -> anyhow::Result<()> {
         network: Network::Mainnet,
         mainnet_submitter: Some(mainnet_submitter),
         testnet_submitter: None,
-        tx_db: tx_db.clone(),
+        tx_db: mainnet_tx_db,
         client: Arc::new(mainnet_api),
+        webhooks_enabled,
     });
 
     // ──────────────── TESTNET ────────────────
@@ -121,12 +220,28 @@ async fn main() -> anyhow::Result<()> {
 
     let testnet_api = OnlineClient::from_rpc_client(testnet_rpc_client).await?;
 
+    // Channel for tracking testnet transaction progress
+    let (testnet_tx, testnet_rx) = mpsc::channel(100);
+    let testnet_tx_db = Arc::new(TxProgressDb::open(
+        &cli.testnet_tx_db_path,
+        tx_history_ttl,
+        testnet_api.clone(),
+        testnet_rx,
+    )?);
+    tokio::spawn({
+        let tx_db = testnet_tx_db.clone();
+        async move {
+            info!("🟡 Testnet TxProgressDb is running...");
+            tx_db.run().await;
+        }
+    });
+
     let testnet_key = signer::load_substrate_key(&cli.testnet_key).await?;
     let testnet_submitter = Arc::new(Mutex::new(
         TxSubmitter::new(
             testnet_api.clone(),
             testnet_key,
-            tx.clone(),
+            testnet_tx,
             cli.testnet_url,
         )
         .await?,
@@ -137,8 +252,9 @@ async fn main() -> anyhow::Result<()> {
         network: Network::Testnet,
         mainnet_submitter: None,
         testnet_submitter: Some(testnet_submitter),
-        tx_db: tx_db.clone(),
+        tx_db: testnet_tx_db,
         client: Arc::new(testnet_api),
+        webhooks_enabled,
     });
 
     // ──────────────── ROUTING ────────────────
@@ -146,7 +262,12 @@ async fn main() -> anyhow::Result<()> {
     let swagger = SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi());
 
     let common_routes = |state: Arc<TranslationLayerState>| {
-        Router::new()
+        // Routes submitting extrinsics signed with the server key require an API key
+        let auth_scope = AuthScope {
+            auth: api_key_auth.clone(),
+            network: state.network,
+        };
+        let authenticated_routes = Router::new()
             .route(
                 "/add_smartcontract",
                 post(api::smartcontracts::add_smartcontract),
@@ -155,11 +276,34 @@ async fn main() -> anyhow::Result<()> {
                 "/remove_smartcontract",
                 post(api::smartcontracts::remove_smartcontract),
             )
+            .route("/create_table", post(api::tables::create_table))
+            .route("/drop_table", post(api::tables::drop_table))
+            .route("/create_namespace", post(api::tables::create_namespace))
+            .route(
+                "/update_namespace_uuid",
+                post(api::tables::update_namespace_uuid),
+            )
+            .route("/update_table_uuid", post(api::tables::update_table_uuid))
+            .route("/set_insert_quorum", post(api::tables::set_insert_quorum))
+            .route("/set_permissions", post(api::permissions::set_permissions))
+            .route(
+                "/add_proxy_permission",
+                post(api::permissions::add_proxy_permission),
+            )
+            .route("/batch", post(api::batch::batch))
+            .route_layer(middleware::from_fn_with_state(
+                auth_scope,
+                auth::require_api_key,
+            ));
+
+        Router::new()
             .route(
                 "/get_smartcontract",
                 get(api::smartcontracts::get_smartcontract),
             )
-            .route("/create_table", post(api::tables::create_table))
+            .route("/get_tables", get(api::tables::get_tables))
+            .route("/get_table", get(api::tables::get_table))
+            .route("/get_schemas", get(api::tables::get_schemas))
             .route(
                 "/get_extrinsic_status_in_block",
                 get(api::extrinsics::get_extrinsic_status_in_block),
@@ -168,7 +312,11 @@ async fn main() -> anyhow::Result<()> {
                 "/get_extrinsic_status",
                 get(api::extrinsics::get_extrinsic_status),
             )
-            .route("/drop_table", post(api::tables::drop_table))
+            .route(
+                "/stream_extrinsic_events",
+                get(api::extrinsics::stream_extrinsic_events),
+            )
+            .merge(authenticated_routes)
             .with_state(state)
     };
 
@@ -196,8 +344,19 @@ async fn main() -> anyhow::Result<()> {
     api::smartcontracts::get_smartcontracts,
     api::tables::create_table,
     api::tables::drop_table,
+    api::tables::create_namespace,
+    api::tables::update_namespace_uuid,
+    api::tables::update_table_uuid,
+    api::tables::set_insert_quorum,
+    api::tables::get_tables,
+    api::tables::get_table,
+    api::tables::get_schemas,
+    api::permissions::set_permissions,
+    api::permissions::add_proxy_permission,
+    api::batch::batch,
     api::extrinsics::get_extrinsic_status_in_block,
     api::extrinsics::get_extrinsic_status,
+    api::extrinsics::stream_extrinsic_events,
 ), modifiers(&AddRoutePrefixes))]
 struct ApiDoc;
 
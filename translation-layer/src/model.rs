@@ -40,6 +40,58 @@ pub struct TxStatusResponse {
     pub status: TxStatusDetails,
 }
 
+/// The stage of a transaction's lifecycle reported by a [`TxEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum TxEventKind {
+    /// The transaction was included in a best block.
+    InBestBlock,
+    /// The transaction was included in a finalized block.
+    Finalized,
+    /// The transaction was dropped from the pool.
+    Dropped,
+    /// The transaction was deemed invalid.
+    Invalid,
+    /// The node stopped reporting the progress of the transaction.
+    Error,
+}
+
+impl TxEventKind {
+    /// Whether no further events follow this one.
+    pub fn is_terminal(&self) -> bool {
+        !matches!(self, TxEventKind::InBestBlock)
+    }
+}
+
+/// A transaction lifecycle event pushed to webhooks and server-sent event streams.
+///
+/// # Example Payload
+/// ```json
+/// {
+///   "txHash": "0x5c9b...",
+///   "kind": "Finalized",
+///   "blockHash": "0xdef456...",
+///   "dispatchSuccess": false,
+///   "dispatchError": "Tables::TableNotFound",
+///   "message": null
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TxEvent {
+    /// The hash of the transaction.
+    pub tx_hash: String,
+    /// The lifecycle stage this event reports.
+    pub kind: TxEventKind,
+    /// The block the transaction was included in, for `InBestBlock` and `Finalized`.
+    pub block_hash: Option<String>,
+    /// Whether the extrinsic dispatched successfully, for `InBestBlock` and `Finalized`.
+    pub dispatch_success: Option<bool>,
+    /// The decoded module error if the dispatch failed.
+    pub dispatch_error: Option<String>,
+    /// The reason given by the node for `Dropped`, `Invalid` and `Error`.
+    pub message: Option<String>,
+}
+
 /// Represents a request to add a smart contract to the indexing system.
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(tag = "type", rename_all = "camelCase")]
@@ -471,6 +523,134 @@ pub struct DropTableRequest {
     pub table_type: TableType,
 }
 
+/// Represents a request to create a namespace (schema).
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateNamespaceRequest {
+    /// The namespace to create.
+    pub schema_name: String,
+    /// The version of the namespace.
+    pub version: u16,
+    /// The `CREATE SCHEMA` statement, optionally carrying the namespace UUID.
+    pub ddl_statement: String,
+    /// Table type of the namespace.
+    pub table_type: TableType,
+    /// Source chain of the namespace.
+    pub source: ChainSource,
+}
+
+/// Represents a request to update the UUID of a namespace version.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateNamespaceUuidRequest {
+    /// The namespace to update.
+    pub schema_name: String,
+    /// The namespace version to update.
+    pub version: u16,
+    /// The new UUID.
+    pub uuid: String,
+}
+
+/// Represents a request to update the UUID of a table version.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTableUuidRequest {
+    /// The namespace of the table.
+    pub schema_name: String,
+    /// The table to update.
+    pub table_name: String,
+    /// The table version to update.
+    pub version: u16,
+    /// The new UUID.
+    pub uuid: String,
+}
+
+/// Represents a request to override the insert quorum of a table.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SetInsertQuorumRequest {
+    /// The namespace of the table.
+    pub schema_name: String,
+    /// The table to update.
+    pub table_name: String,
+    /// The new insert quorum. A `None` scope disables inserts through that scope.
+    pub quorum: QuorumSize,
+}
+
+/// Represents a request to replace the permissions of an account.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SetPermissionsRequest {
+    /// The SS58 address of the account.
+    pub account: String,
+    /// The permissions to grant, e.g. `{"TablesPallet": "EditSchema"}`.
+    #[schema(value_type = Vec<Object>)]
+    pub permissions: Vec<sxt_core::permissions::PermissionLevel>,
+}
+
+/// Represents a request to grant a single permission to an account on behalf of the signer.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AddProxyPermissionRequest {
+    /// The SS58 address of the account.
+    pub account: String,
+    /// The permission to grant, e.g. `{"TablesPallet": "EditSchema"}`.
+    #[schema(value_type = Object)]
+    pub permission: sxt_core::permissions::PermissionLevel,
+}
+
+/// Details of a table stored on chain.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiTable {
+    /// The namespace of the table.
+    pub schema_name: String,
+    /// The table name.
+    pub table_name: String,
+    /// The table type.
+    pub table_type: TableType,
+    /// The DDL statement of the table.
+    pub ddl_statement: Option<String>,
+    /// The insert quorum of the table.
+    pub insert_quorum: QuorumSize,
+}
+
+/// Details of a namespace version stored on chain.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiSchema {
+    /// The namespace.
+    pub schema_name: String,
+    /// The namespace version.
+    pub version: u16,
+    /// The UUID of the namespace version.
+    pub uuid: String,
+}
+
+/// Response structure for retrieving tables.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTablesResponse {
+    /// Indicates whether the query was successful.
+    pub success: bool,
+    /// An optional error message in case of failure.
+    pub err_msg: Option<String>,
+    /// The retrieved tables.
+    pub tables: Vec<ApiTable>,
+}
+
+/// Response structure for retrieving namespaces.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetSchemasResponse {
+    /// Indicates whether the query was successful.
+    pub success: bool,
+    /// An optional error message in case of failure.
+    pub err_msg: Option<String>,
+    /// The retrieved namespaces.
+    pub schemas: Vec<ApiSchema>,
+}
+
 /// Response structure for retrieving multiple smart contracts.
 ///
 /// This struct is returned when querying for all smart contracts associated with a given source.
@@ -528,6 +708,10 @@ pub struct GetContractsResponse {
 /// - `dropped_message` (Option<String>): If applicable, a message explaining why the transaction was dropped.
 /// - `invalid_message` (Option<String>): If applicable, a message explaining why the transaction was deemed invalid.
 /// - `error_message` (Option<String>): If applicable, a message describing any error encountered during execution.
+/// - `dispatch_success` (Option<bool>): Whether the extrinsic dispatched successfully, once it is in a block.
+/// - `dispatch_error` (Option<String>): The decoded module error if the dispatch failed.
+/// - `submitted_by` (Option<String>): Identity of the API key that submitted the transaction.
+/// - `batch_results` (Option<Vec<BatchCallResult>>): The result of each call, if the transaction was submitted through `/batch`.
 ///
 /// # Example Response
 /// ```json
@@ -539,7 +723,11 @@ pub struct GetContractsResponse {
 ///   "finalizedInBlock": "0xdef456...",
 ///   "droppedMessage": null,
 ///   "invalidMessage": null,
-///   "errorMessage": null
+///   "errorMessage": null,
+///   "dispatchSuccess": true,
+///   "dispatchError": null,
+///   "submittedBy": "indexing-team",
+///   "batchResults": null
 /// }
 /// ```
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -560,6 +748,107 @@ pub struct TxStatusDetails {
     pub invalid_message: Option<String>,
     /// If applicable, a message describing any error encountered during execution.
     pub error_message: Option<String>,
+    /// Whether the extrinsic dispatched successfully in the block it was included in.
+    pub dispatch_success: Option<bool>,
+    /// If the dispatch failed, the decoded module error.
+    pub dispatch_error: Option<String>,
+    /// Identity of the API key that submitted the transaction.
+    pub submitted_by: Option<String>,
+    /// The result of each call, if the transaction was submitted through `/batch`.
+    pub batch_results: Option<Vec<BatchCallResult>>,
+}
+
+/// A single call of a [`BatchRequest`], tagged by the endpoint it would otherwise be sent to.
+///
+/// # Example
+/// ```json
+/// { "call": "set_insert_quorum", "args": { "schemaName": "ETHEREUM", "tableName": "BLOCKS", "quorum": { "public": 2, "privileged": null } } }
+/// ```
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "call", content = "args", rename_all = "snake_case")]
+pub enum BatchCall {
+    /// Same as `/create_table`.
+    CreateTable(Vec<TableRequest>),
+    /// Same as `/drop_table`.
+    DropTable(DropTableRequest),
+    /// Same as `/create_namespace`.
+    CreateNamespace(CreateNamespaceRequest),
+    /// Same as `/update_namespace_uuid`.
+    UpdateNamespaceUuid(UpdateNamespaceUuidRequest),
+    /// Same as `/update_table_uuid`.
+    UpdateTableUuid(UpdateTableUuidRequest),
+    /// Same as `/set_insert_quorum`.
+    SetInsertQuorum(SetInsertQuorumRequest),
+    /// Same as `/add_smartcontract`.
+    AddSmartcontract(AddContractRequest),
+    /// Same as `/remove_smartcontract`.
+    RemoveSmartcontract(RemoveContractRequest),
+    /// Same as `/set_permissions`.
+    SetPermissions(SetPermissionsRequest),
+    /// Same as `/add_proxy_permission`.
+    AddProxyPermission(AddProxyPermissionRequest),
+}
+
+/// Represents a request to submit several calls as a single extrinsic.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchRequest {
+    /// The calls to submit, in dispatch order.
+    pub calls: Vec<BatchCall>,
+    /// Whether the calls are applied all or nothing (`utility.batch_all`). Otherwise dispatch
+    /// stops at the first failing call and earlier calls are kept (`utility.batch`).
+    #[serde(default = "default_atomic")]
+    pub atomic: bool,
+}
+
+/// Batches are atomic unless stated otherwise.
+fn default_atomic() -> bool {
+    true
+}
+
+/// The state of a single call of a batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum BatchCallStatus {
+    /// The call was rejected before submission.
+    Invalid,
+    /// The batch was submitted but is not in a block yet.
+    Pending,
+    /// The call dispatched successfully.
+    Completed,
+    /// The call failed, interrupting a non-atomic batch.
+    Failed,
+    /// The call was not dispatched because an earlier call of a non-atomic batch failed.
+    NotExecuted,
+    /// The atomic batch failed, so none of its calls were applied.
+    Reverted,
+}
+
+/// The result of a single call of a batch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchCallResult {
+    /// Position of the call in the batch.
+    pub index: u32,
+    /// The `call` tag of the call.
+    pub call: String,
+    /// The state of the call.
+    pub status: BatchCallStatus,
+    /// Why the call was rejected or failed.
+    pub error: Option<String>,
+}
+
+/// Response structure for submitting a batch.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchResponse {
+    /// Indicates whether the batch was submitted.
+    pub success: bool,
+    /// An optional error message in case of failure.
+    pub err_msg: Option<String>,
+    /// The hash of the batch extrinsic, if it was submitted.
+    pub tx_hash: Option<String>,
+    /// The result of each call, in dispatch order.
+    pub results: Vec<BatchCallResult>,
 }
 
 /// Represents the type of a table being created or referenced within the translation layer.
@@ -5,11 +5,19 @@
 
 use std::sync::Arc;
 
+use log::{error, info};
+use serde::Deserialize;
+use subxt::ext::scale_encode::EncodeAsFields;
+use subxt::tx::DefaultPayload;
+use subxt::utils::H256;
 use subxt::{OnlineClient, PolkadotConfig};
 use tokio::sync::Mutex;
 
+use crate::auth::{Caller, Endpoint};
+use crate::model::ApiResponse;
 use crate::tx_progress::TxProgressDb;
 use crate::tx_submitter::TxSubmitter;
+use crate::webhooks::CallbackUrl;
 
 /// Represents the shared state for the translation layer.
 ///
@@ -43,6 +51,96 @@ pub struct TranslationLayerState {
 
     /// Network that this state applies to
     pub network: Network,
+
+    /// Whether requests may register a callback URL for the lifecycle events of their
+    /// transactions.
+    pub webhooks_enabled: bool,
+}
+
+impl TranslationLayerState {
+    /// Signs and submits an extrinsic with this network's submitter on behalf of `caller`.
+    ///
+    /// The callback URL is registered for the extrinsic hash before it is submitted, so no
+    /// lifecycle event is missed. The caller is recorded alongside the hash on success.
+    pub async fn submit<T: EncodeAsFields>(
+        &self,
+        tx: &DefaultPayload<T>,
+        caller: &Caller,
+        endpoint: Endpoint,
+        callback: &CallbackUrl,
+    ) -> ApiResponse {
+        let submitter_opt = match self.network {
+            Network::Mainnet => self.mainnet_submitter.as_ref(),
+            Network::Testnet => self.testnet_submitter.as_ref(),
+        };
+
+        let Some(submitter) = submitter_opt else {
+            return ApiResponse {
+                success: false,
+                err_msg: Some("TxSubmitter not configured for this network".into()),
+                tx_hash: None,
+            };
+        };
+
+        let result = submitter
+            .lock()
+            .await
+            .submit_tx_get_hash_notifying(tx, |hash| self.register_callback(hash, callback))
+            .await;
+        match result {
+            Ok(hash) => {
+                let tx_hash = format!("{:#x}", hash);
+                self.record_submission(&tx_hash, caller, endpoint).await;
+                ApiResponse {
+                    success: true,
+                    err_msg: None,
+                    tx_hash: Some(tx_hash),
+                }
+            }
+            Err(err) => ApiResponse {
+                success: false,
+                err_msg: Some(format!("Error: {err}")),
+                tx_hash: None,
+            },
+        }
+    }
+
+    /// Registers the callback URL the caller asked to be notified at for an extrinsic that is
+    /// about to be submitted.
+    ///
+    /// Failures are logged rather than returned, so a store outage does not block submission.
+    async fn register_callback(&self, hash: H256, callback: &CallbackUrl) {
+        let Some(url) = &callback.0 else {
+            return;
+        };
+
+        let tx_hash = format!("{:#x}", hash);
+        if let Err(err) = self.tx_db.register_webhook(&tx_hash, url).await {
+            error!(
+                "Error registering webhook of transaction {}: {}",
+                tx_hash, err
+            );
+        }
+    }
+
+    /// Records which caller submitted an extrinsic, so its status can be attributed later.
+    ///
+    /// Failures are logged rather than returned, since the extrinsic has already been submitted.
+    pub async fn record_submission(&self, tx_hash: &str, caller: &Caller, endpoint: Endpoint) {
+        info!(
+            "{} submitted {} via {}",
+            caller.id,
+            tx_hash,
+            endpoint.as_str()
+        );
+        if let Err(err) = self
+            .tx_db
+            .record_submission(tx_hash, &caller.id, endpoint.as_str())
+            .await
+        {
+            error!("Error recording caller of transaction {}: {}", tx_hash, err);
+        }
+    }
 }
 
 /// Represents the target network environment for the translation layer.
@@ -54,7 +152,8 @@ pub struct TranslationLayerState {
 /// # Variants
 /// - `Mainnet`: Indicates that the state or operation is associated with the main production network.
 /// - `Testnet`: Indicates that the state or operation is associated with a test or development network.
-#[derive(Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Network {
     /// SxT Mainnet
     Mainnet,
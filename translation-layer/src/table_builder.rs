@@ -1,3 +1,4 @@
+use snafu::{ensure, Snafu};
 use subxt::tx::DefaultPayload;
 use sxt_core::sxt_chain_runtime;
 use sxt_core::sxt_chain_runtime::api::runtime_types::bounded_collections::bounded_vec::BoundedVec;
@@ -12,6 +13,100 @@ use sxt_core::sxt_chain_runtime::api::tables::calls::types::CreateTables;
 
 use crate::model::{ChainSource, CommitmentScheme};
 
+/// Maximum length of a table UUID, matching the on-chain bound.
+const UUID_MAX_LEN: usize = sxt_core::IDENT_LENGTH as usize;
+
+/// Errors validating tables, namespaces and UUIDs before they are submitted on chain.
+#[derive(Debug, Snafu)]
+pub enum TableValidationError {
+    /// An identifier is empty.
+    #[snafu(display("{kind} name must not be empty"))]
+    EmptyIdentifier {
+        /// What the identifier names, e.g. "Table".
+        kind: &'static str,
+    },
+    /// An identifier exceeds the on-chain bound.
+    #[snafu(display("{kind} name {identifier} is longer than {max} bytes"))]
+    IdentifierTooLong {
+        /// What the identifier names, e.g. "Table".
+        kind: &'static str,
+        /// The offending identifier.
+        identifier: String,
+        /// The maximum length.
+        max: usize,
+    },
+    /// An identifier contains characters other than letters, digits and underscores.
+    #[snafu(display(
+        "{kind} name {identifier} must start with a letter or underscore and only contain letters, digits and underscores"
+    ))]
+    InvalidIdentifier {
+        /// What the identifier names, e.g. "Table".
+        kind: &'static str,
+        /// The offending identifier.
+        identifier: String,
+    },
+    /// A DDL statement is empty or exceeds the on-chain bound.
+    #[snafu(display("DDL statement must be between 1 and {max} bytes"))]
+    InvalidDdlLength {
+        /// The maximum length.
+        max: usize,
+    },
+    /// A UUID is empty, too long or contains characters other than letters, digits and dashes.
+    #[snafu(display("UUID {uuid} is not valid"))]
+    InvalidUuid {
+        /// The offending UUID.
+        uuid: String,
+    },
+}
+
+/// Validates a table or namespace name against the rules enforced for SQL identifiers on chain.
+pub fn validate_identifier(
+    kind: &'static str,
+    identifier: &str,
+) -> Result<(), TableValidationError> {
+    ensure!(!identifier.is_empty(), EmptyIdentifierSnafu { kind });
+
+    let max = sxt_core::IDENT_LENGTH as usize;
+    ensure!(
+        identifier.len() <= max,
+        IdentifierTooLongSnafu {
+            kind,
+            identifier,
+            max
+        }
+    );
+
+    let mut chars = identifier.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    ensure!(valid, InvalidIdentifierSnafu { kind, identifier });
+
+    Ok(())
+}
+
+/// Validates that a DDL statement fits the on-chain bound.
+pub fn validate_ddl(ddl: &str) -> Result<(), TableValidationError> {
+    let max = sxt_core::tables::CREATE_STMNT_LENGTH as usize;
+    ensure!(
+        !ddl.trim().is_empty() && ddl.len() <= max,
+        InvalidDdlLengthSnafu { max }
+    );
+    Ok(())
+}
+
+/// Validates a table or namespace UUID.
+pub fn validate_uuid(uuid: &str) -> Result<(), TableValidationError> {
+    ensure!(
+        !uuid.is_empty()
+            && uuid.len() <= UUID_MAX_LEN
+            && uuid.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'),
+        InvalidUuidSnafu { uuid }
+    );
+    Ok(())
+}
+
 /// A builder for constructing table configurations before adding them to a `TableCreator`.
 pub struct TableBuilder<'a> {
     identifier: TableIdentifier,
@@ -87,6 +182,16 @@ impl<'a> TableBuilder<'a> {
         self
     }
 
+    /// Validates the table's identifier and DDL statement.
+    pub fn validate(&self) -> Result<(), TableValidationError> {
+        validate_identifier(
+            "Schema",
+            &String::from_utf8_lossy(&self.identifier.namespace.0),
+        )?;
+        validate_identifier("Table", &String::from_utf8_lossy(&self.identifier.name.0))?;
+        validate_ddl(&String::from_utf8_lossy(&self.ddl_statement.0))
+    }
+
     /// Finalizes the table configuration and adds it to the parent `TableCreator`.
     pub fn add(self) -> &'a mut TableCreator {
         let commitment = match (self.commitment_scheme, self.commitment, self.snapshot_url) {
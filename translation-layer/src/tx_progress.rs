@@ -1,12 +1,19 @@
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::path::Path;
+use std::sync::{Arc, PoisonError};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use linked_list::LinkedList;
-use log::error;
+use log::{error, info, warn};
+use rusqlite::{params, Connection, OptionalExtension};
+use snafu::ResultExt;
 use subxt::{OnlineClient, PolkadotConfig};
-use tokio::sync::{mpsc, Mutex};
+use sxt_core::sxt_chain_runtime::api::system::events::ExtrinsicFailed;
+use sxt_core::sxt_chain_runtime::api::utility::events::{BatchInterrupted, ItemCompleted};
+use tokio::sync::{broadcast, mpsc, Mutex};
 
+use crate::error::{FetchEventsSnafu, Result, TxHistoryStoreSnafu, TxHistoryTaskSnafu};
+use crate::model::{BatchCallResult, BatchCallStatus, TxEvent, TxEventKind};
 use crate::tx_submitter::TxUpdate;
+use crate::utils::decode_system_module_error;
 
 /// Alias for transaction status type in a Substrate-based blockchain.
 pub type TxStatus = subxt::tx::TxStatus<PolkadotConfig, OnlineClient<PolkadotConfig>>;
@@ -14,88 +21,1004 @@ pub type TxStatus = subxt::tx::TxStatus<PolkadotConfig, OnlineClient<PolkadotCon
 /// Alias for tracking transaction progress asynchronously.
 pub type TxProgress = subxt::tx::TxProgress<PolkadotConfig, OnlineClient<PolkadotConfig>>;
 
-/// A hashmap storing transaction history, where:
-/// - The key is the transaction hash (as a `String`).
-/// - The value is a linked list of `Arc<TxStatus>` representing transaction statuses over time.
-pub type TxDb = HashMap<String, LinkedList<Arc<TxStatus>>>;
+/// Alias for a transaction that has been included in a block.
+pub type TxInBlock = subxt::tx::TxInBlock<PolkadotConfig, OnlineClient<PolkadotConfig>>;
 
-/// A thread-safe database for tracking transaction progress in real-time.
+/// How often expired transaction history is evicted from the store.
+const EVICTION_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How long a write waits for a lock held by another replica sharing the store.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How many lifecycle events are buffered for slow server-sent event subscribers.
+const EVENT_CHANNEL_CAPACITY: usize = 1024;
+
+/// Schema of the transaction history store.
+///
+/// Statuses are ordered by their autoincrementing `id`, so the history of a transaction is
+/// returned in the order it was observed. Webhook deliveries are queued in the same store, so
+/// pending deliveries survive restarts.
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS tx_status (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    tx_hash TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    detail TEXT,
+    num_peers INTEGER,
+    recorded_at INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS tx_status_by_hash ON tx_status (tx_hash, id);
+CREATE TABLE IF NOT EXISTS tx_outcome (
+    tx_hash TEXT PRIMARY KEY,
+    block_hash TEXT NOT NULL,
+    success INTEGER NOT NULL,
+    module_error TEXT,
+    recorded_at INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tx_caller (
+    tx_hash TEXT PRIMARY KEY,
+    caller TEXT NOT NULL,
+    endpoint TEXT NOT NULL,
+    recorded_at INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tx_batch (
+    tx_hash TEXT PRIMARY KEY,
+    calls TEXT NOT NULL,
+    atomic INTEGER NOT NULL,
+    recorded_at INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tx_batch_outcome (
+    tx_hash TEXT PRIMARY KEY,
+    completed INTEGER NOT NULL,
+    interrupted_index INTEGER,
+    interrupted_error TEXT,
+    recorded_at INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS webhook_subscription (
+    tx_hash TEXT NOT NULL,
+    url TEXT NOT NULL,
+    recorded_at INTEGER NOT NULL,
+    PRIMARY KEY (tx_hash, url)
+);
+CREATE TABLE IF NOT EXISTS webhook_delivery (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    tx_hash TEXT NOT NULL,
+    url TEXT NOT NULL,
+    payload TEXT NOT NULL,
+    attempts INTEGER NOT NULL,
+    next_attempt_at INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS webhook_delivery_by_due ON webhook_delivery (next_attempt_at);
+CREATE INDEX IF NOT EXISTS webhook_delivery_by_tx ON webhook_delivery (tx_hash, url, id);
+";
+
+/// Opens (or creates) the history store at `path`.
+pub(crate) fn open_store(path: impl AsRef<Path>) -> Result<Connection> {
+    let conn = Connection::open(path).context(TxHistoryStoreSnafu)?;
+    conn.busy_timeout(BUSY_TIMEOUT)
+        .context(TxHistoryStoreSnafu)?;
+    // WAL lets replicas keep reading while another one writes.
+    conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get::<_, String>(0))
+        .context(TxHistoryStoreSnafu)?;
+    conn.execute_batch(SCHEMA).context(TxHistoryStoreSnafu)?;
+    Ok(conn)
+}
+
+/// A connection to the history store whose queries run on the blocking thread pool.
+///
+/// `rusqlite` is synchronous and a query may wait up to [`BUSY_TIMEOUT`] for another replica,
+/// so queries are never run on the async runtime itself.
+#[derive(Clone)]
+pub(crate) struct Store {
+    /// Connection to the SQLite file.
+    conn: Arc<std::sync::Mutex<Connection>>,
+}
+
+impl Store {
+    /// Opens (or creates) the history store at `path`.
+    pub(crate) fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self {
+            conn: Arc::new(std::sync::Mutex::new(open_store(path)?)),
+        })
+    }
+
+    /// Runs `query` against the store on the blocking thread pool.
+    pub(crate) async fn call<T, F>(&self, query: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Connection) -> rusqlite::Result<T> + Send + 'static,
+    {
+        let conn = Arc::clone(&self.conn);
+        tokio::task::spawn_blocking(move || {
+            // A panicking query leaves the connection usable, so the poison is ignored.
+            let mut conn = conn.lock().unwrap_or_else(PoisonError::into_inner);
+            query(&mut conn)
+        })
+        .await
+        .context(TxHistoryTaskSnafu)?
+        .context(TxHistoryStoreSnafu)
+    }
+}
+
+/// A transaction status as it is persisted in the history store.
+///
+/// Unlike [`TxStatus`], this does not hold a client, so it can be read back after a restart
+/// or by another replica sharing the same store.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StoredTxStatus {
+    /// The transaction has been validated by the pool.
+    Validated,
+    /// The transaction has been broadcasted to the given number of peers.
+    Broadcasted {
+        /// Number of peers the transaction was broadcasted to.
+        num_peers: u32,
+    },
+    /// The transaction is no longer in a best block.
+    NoLongerInBestBlock,
+    /// The transaction was included in the best block with this hash.
+    InBestBlock {
+        /// Hash of the block, formatted as `0x`-prefixed hex.
+        block_hash: String,
+    },
+    /// The transaction was included in the finalized block with this hash.
+    InFinalizedBlock {
+        /// Hash of the block, formatted as `0x`-prefixed hex.
+        block_hash: String,
+    },
+    /// An error occurred while watching the transaction.
+    Error {
+        /// The error message.
+        message: String,
+    },
+    /// The transaction was deemed invalid.
+    Invalid {
+        /// The reason the transaction is invalid.
+        message: String,
+    },
+    /// The transaction was dropped from the pool.
+    Dropped {
+        /// The reason the transaction was dropped.
+        message: String,
+    },
+}
+
+impl From<&TxStatus> for StoredTxStatus {
+    fn from(status: &TxStatus) -> Self {
+        match status {
+            TxStatus::Validated => StoredTxStatus::Validated,
+            TxStatus::Broadcasted { num_peers } => StoredTxStatus::Broadcasted {
+                num_peers: *num_peers,
+            },
+            TxStatus::NoLongerInBestBlock => StoredTxStatus::NoLongerInBestBlock,
+            TxStatus::InBestBlock(block) => StoredTxStatus::InBestBlock {
+                block_hash: format!("{:#x}", block.block_hash()),
+            },
+            TxStatus::InFinalizedBlock(block) => StoredTxStatus::InFinalizedBlock {
+                block_hash: format!("{:#x}", block.block_hash()),
+            },
+            TxStatus::Error { message } => StoredTxStatus::Error {
+                message: message.clone(),
+            },
+            TxStatus::Invalid { message } => StoredTxStatus::Invalid {
+                message: message.clone(),
+            },
+            TxStatus::Dropped { message } => StoredTxStatus::Dropped {
+                message: message.clone(),
+            },
+        }
+    }
+}
+
+impl StoredTxStatus {
+    /// Returns the `(kind, detail, num_peers)` columns this status is stored as.
+    fn to_columns(&self) -> (&'static str, Option<&str>, Option<u32>) {
+        match self {
+            StoredTxStatus::Validated => ("validated", None, None),
+            StoredTxStatus::Broadcasted { num_peers } => ("broadcasted", None, Some(*num_peers)),
+            StoredTxStatus::NoLongerInBestBlock => ("no_longer_in_best_block", None, None),
+            StoredTxStatus::InBestBlock { block_hash } => ("in_best_block", Some(block_hash), None),
+            StoredTxStatus::InFinalizedBlock { block_hash } => {
+                ("in_finalized_block", Some(block_hash), None)
+            }
+            StoredTxStatus::Error { message } => ("error", Some(message), None),
+            StoredTxStatus::Invalid { message } => ("invalid", Some(message), None),
+            StoredTxStatus::Dropped { message } => ("dropped", Some(message), None),
+        }
+    }
+
+    /// Rebuilds a status from its stored columns.
+    ///
+    /// Returns `None` for kinds this version does not know about.
+    fn from_columns(kind: &str, detail: Option<String>, num_peers: Option<u32>) -> Option<Self> {
+        let detail = detail.unwrap_or_default();
+        Some(match kind {
+            "validated" => StoredTxStatus::Validated,
+            "broadcasted" => StoredTxStatus::Broadcasted {
+                num_peers: num_peers.unwrap_or_default(),
+            },
+            "no_longer_in_best_block" => StoredTxStatus::NoLongerInBestBlock,
+            "in_best_block" => StoredTxStatus::InBestBlock { block_hash: detail },
+            "in_finalized_block" => StoredTxStatus::InFinalizedBlock { block_hash: detail },
+            "error" => StoredTxStatus::Error { message: detail },
+            "invalid" => StoredTxStatus::Invalid { message: detail },
+            "dropped" => StoredTxStatus::Dropped { message: detail },
+            _ => return None,
+        })
+    }
+
+    /// Returns the lifecycle event reported for this status, if it is one that is pushed to
+    /// subscribers.
+    ///
+    /// The dispatch outcome is attached if it was read from the same block.
+    pub fn to_event(&self, tx_hash: &str, outcome: Option<&TxOutcome>) -> Option<TxEvent> {
+        let (kind, block_hash, message) = match self {
+            StoredTxStatus::InBestBlock { block_hash } => {
+                (TxEventKind::InBestBlock, Some(block_hash), None)
+            }
+            StoredTxStatus::InFinalizedBlock { block_hash } => {
+                (TxEventKind::Finalized, Some(block_hash), None)
+            }
+            StoredTxStatus::Dropped { message } => (TxEventKind::Dropped, None, Some(message)),
+            StoredTxStatus::Invalid { message } => (TxEventKind::Invalid, None, Some(message)),
+            StoredTxStatus::Error { message } => (TxEventKind::Error, None, Some(message)),
+            StoredTxStatus::Validated
+            | StoredTxStatus::Broadcasted { .. }
+            | StoredTxStatus::NoLongerInBestBlock => return None,
+        };
+        let outcome = outcome.filter(|outcome| Some(&outcome.block_hash) == block_hash);
+
+        Some(TxEvent {
+            tx_hash: tx_hash.to_string(),
+            kind,
+            block_hash: block_hash.cloned(),
+            dispatch_success: outcome.map(|outcome| outcome.success),
+            dispatch_error: outcome.and_then(|outcome| outcome.module_error.clone()),
+            message: message.cloned(),
+        })
+    }
+}
+
+/// The dispatch outcome of a transaction in the latest block it was seen in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxOutcome {
+    /// Hash of the block the outcome was read from, formatted as `0x`-prefixed hex.
+    pub block_hash: String,
+    /// Whether the extrinsic dispatched successfully.
+    pub success: bool,
+    /// The module error decoded with [`decode_system_module_error`], if the dispatch failed.
+    pub module_error: Option<String>,
+    /// How far the calls of a utility batch got, if the extrinsic is one.
+    pub batch: Option<BatchOutcome>,
+}
+
+/// How far the calls of a utility batch got, read from its `Utility` events.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchOutcome {
+    /// Number of calls that dispatched successfully (`ItemCompleted`).
+    pub completed: u32,
+    /// The call that interrupted a non-atomic batch (`BatchInterrupted`).
+    pub interrupted: Option<BatchInterruption>,
+}
+
+/// The call that interrupted a non-atomic batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchInterruption {
+    /// Position of the failed call in the batch.
+    pub index: u32,
+    /// The dispatch error of the failed call.
+    pub error: String,
+}
+
+/// The calls of a transaction submitted through `/batch`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchSubmission {
+    /// The `call` tag of every call, in dispatch order.
+    pub calls: Vec<String>,
+    /// Whether the calls were submitted as a `utility.batch_all`.
+    pub atomic: bool,
+}
+
+/// The persisted history of a single transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxHistory {
+    /// Every status observed for the transaction, oldest first.
+    pub statuses: Vec<StoredTxStatus>,
+    /// The dispatch outcome, once the transaction has been included in a block.
+    pub outcome: Option<TxOutcome>,
+    /// Identity of the API caller that submitted the transaction, if it was submitted through
+    /// an authenticated endpoint.
+    pub submitted_by: Option<String>,
+    /// The calls of the transaction, if it was submitted through `/batch`.
+    pub batch: Option<BatchSubmission>,
+}
+
+impl TxHistory {
+    /// Returns the lifecycle events of the transaction observed so far, oldest first.
+    pub fn events(&self, tx_hash: &str) -> Vec<TxEvent> {
+        self.statuses
+            .iter()
+            .filter_map(|status| status.to_event(tx_hash, self.outcome.as_ref()))
+            .collect()
+    }
+
+    /// Returns the result of every call of a transaction submitted through `/batch`.
+    ///
+    /// Calls are pending until the batch is in a block. An atomic batch either completes as a
+    /// whole or reverts every call, while a non-atomic one keeps the calls dispatched before
+    /// the one that interrupted it.
+    pub fn batch_results(&self) -> Option<Vec<BatchCallResult>> {
+        let batch = self.batch.as_ref()?;
+
+        Some(
+            batch
+                .calls
+                .iter()
+                .enumerate()
+                .map(|(index, call)| {
+                    let index = index as u32;
+                    let (status, error) = match &self.outcome {
+                        None => (BatchCallStatus::Pending, None),
+                        Some(outcome) if !outcome.success => {
+                            (BatchCallStatus::Reverted, outcome.module_error.clone())
+                        }
+                        Some(outcome) => match outcome
+                            .batch
+                            .as_ref()
+                            .and_then(|batch| batch.interrupted.as_ref())
+                        {
+                            Some(interrupted) if interrupted.index == index => {
+                                (BatchCallStatus::Failed, Some(interrupted.error.clone()))
+                            }
+                            Some(interrupted) if interrupted.index < index => {
+                                (BatchCallStatus::NotExecuted, None)
+                            }
+                            _ => (BatchCallStatus::Completed, None),
+                        },
+                    };
+
+                    BatchCallResult {
+                        index,
+                        call: call.clone(),
+                        status,
+                        error,
+                    }
+                })
+                .collect(),
+        )
+    }
+}
+
+/// A persistent database for tracking transaction progress in real-time.
 ///
 /// This structure listens for transaction updates via a Tokio MPSC channel and stores
-/// the history of transaction statuses for each extrinsic hash.
+/// the history of transaction statuses for each extrinsic hash in an SQLite file. Histories
+/// survive restarts, can be shared by several replicas pointing at the same file, and are
+/// evicted once they have not been updated for the configured time-to-live.
+///
+/// Each network keeps its history in its own file, so transactions of one network are never
+/// reported by another.
+///
+/// Lifecycle events are broadcast to in-process subscribers and queued for delivery to the
+/// webhooks registered for the transaction.
 pub struct TxProgressDb {
-    /// Shared map containing transaction progress history.
-    tx_map: Arc<Mutex<TxDb>>,
+    /// The SQLite history store.
+    store: Store,
+    /// How long a transaction's history is kept after its last update.
+    ttl: Duration,
+    /// Client of the network the tracked transactions are submitted to, used to decode errors.
+    client: OnlineClient<PolkadotConfig>,
     /// Asynchronous receiver for transaction status updates.
     rx: Mutex<mpsc::Receiver<TxUpdate>>,
+    /// Broadcasts lifecycle events to server-sent event subscribers.
+    events: broadcast::Sender<TxEvent>,
 }
 
 impl TxProgressDb {
-    /// Creates a new instance of `TxProgressDb` with the given receiver.
+    /// Opens (or creates) the history store at `path` and returns a `TxProgressDb` reading
+    /// updates from the given receiver.
     ///
     /// # Arguments
+    /// * `path` - Path of the SQLite file. Replicas sharing this file share their history, so
+    ///   it must not be shared with the store of another network.
+    /// * `ttl` - How long a transaction's history is kept after its last update.
+    /// * `client` - Client of the network the tracked transactions are submitted to.
     /// * `rx` - The receiver that listens for transaction progress updates.
-    ///
-    /// # Returns
-    /// A new instance of `TxProgressDb`.
-    pub fn new(rx: mpsc::Receiver<TxUpdate>) -> Self {
-        Self {
-            tx_map: Arc::new(Mutex::new(HashMap::new())),
+    pub fn open(
+        path: impl AsRef<Path>,
+        ttl: Duration,
+        client: OnlineClient<PolkadotConfig>,
+        rx: mpsc::Receiver<TxUpdate>,
+    ) -> Result<Self> {
+        let store = Store::open(path)?;
+        let (events, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+
+        Ok(Self {
+            store,
+            ttl,
+            client,
             rx: Mutex::new(rx),
-        }
+            events,
+        })
+    }
+
+    /// Subscribes to the lifecycle events of every transaction tracked from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<TxEvent> {
+        self.events.subscribe()
     }
 
     /// Starts the transaction progress listener loop.
     ///
-    /// This function continuously listens for transaction updates and stores
-    /// the progress history in a shared `HashMap`. Each transaction's updates are processed
-    /// in a separate async task to avoid blocking the main event loop.
+    /// This function continuously listens for transaction updates and persists
+    /// the progress history. Each transaction's updates are processed in a separate
+    /// async task to avoid blocking the main event loop. Expired histories are evicted
+    /// in the background.
     pub async fn run(self: Arc<Self>) {
+        tokio::spawn({
+            let db = Arc::clone(&self);
+            async move {
+                let mut interval = tokio::time::interval(EVICTION_INTERVAL);
+                loop {
+                    interval.tick().await;
+                    match db.evict_expired().await {
+                        Ok(0) => {}
+                        Ok(evicted) => info!("Evicted {} expired transaction statuses", evicted),
+                        Err(err) => error!("Error evicting transaction history: {}", err),
+                    }
+                }
+            }
+        });
+
         let mut rx = self.rx.lock().await; // Lock receiver
 
         while let Some((progress, _, _)) = rx.recv().await {
-            let tx_hash = format!("{:#x}", progress.extrinsic_hash());
-            let tx_map = Arc::clone(&self.tx_map);
-
-            tokio::spawn(async move {
-                let mut progress = progress;
-                let mut history = LinkedList::new();
-
-                while let Some(event) = progress.next().await {
-                    match event {
-                        Ok(status) => {
-                            let arc_status = Arc::new(status);
-                            history.push_back(Arc::clone(&arc_status));
-
-                            // ✅ Store the intermediate status update in `tx_map`
-                            let mut map = tx_map.lock().await;
-                            map.entry(tx_hash.clone())
-                                .or_insert_with(LinkedList::new)
-                                .push_back(arc_status);
-                        }
-                        Err(err) => {
-                            error!("Error watching transaction {}: {}", tx_hash, err);
-                            return;
+            let db = Arc::clone(&self);
+            tokio::spawn(async move { db.track(progress).await });
+        }
+    }
+
+    /// Persists every status of a single transaction until its progress stream ends.
+    async fn track(&self, mut progress: TxProgress) {
+        let tx_hash = format!("{:#x}", progress.extrinsic_hash());
+
+        while let Some(event) = progress.next().await {
+            let status = match event {
+                Ok(status) => status,
+                Err(err) => {
+                    error!("Error watching transaction {}: {}", tx_hash, err);
+                    return;
+                }
+            };
+
+            let stored = StoredTxStatus::from(&status);
+            if let Err(err) = self.record_status(&tx_hash, &stored).await {
+                error!("Error recording status of transaction {}: {}", tx_hash, err);
+            }
+
+            let mut outcome = None;
+            if let TxStatus::InBestBlock(block) | TxStatus::InFinalizedBlock(block) = &status {
+                match self.dispatch_outcome(block).await {
+                    Ok(dispatched) => {
+                        if let Err(err) = self.record_outcome(&tx_hash, &dispatched).await {
+                            error!(
+                                "Error recording outcome of transaction {}: {}",
+                                tx_hash, err
+                            );
                         }
+                        outcome = Some(dispatched);
+                    }
+                    Err(err) => {
+                        warn!("Error reading outcome of transaction {}: {}", tx_hash, err)
                     }
                 }
-            });
+            }
+
+            if let Some(event) = stored.to_event(&tx_hash, outcome.as_ref()) {
+                self.publish(event).await;
+            }
         }
     }
 
+    /// Broadcasts a lifecycle event and queues it for every webhook registered for its
+    /// transaction.
+    async fn publish(&self, event: TxEvent) {
+        let payload = match serde_json::to_string(&event) {
+            Ok(payload) => payload,
+            Err(err) => {
+                error!(
+                    "Error serializing event of transaction {}: {}",
+                    event.tx_hash, err
+                );
+                return;
+            }
+        };
+
+        let tx_hash = event.tx_hash.clone();
+        let queued = self
+            .store
+            .call(move |conn| queue_deliveries(conn, &tx_hash, &payload, unix_now()))
+            .await;
+        if let Err(err) = queued {
+            error!(
+                "Error queueing webhooks of transaction {}: {}",
+                event.tx_hash, err
+            );
+        }
+
+        // Sending only fails when nobody is subscribed.
+        let _ = self.events.send(event);
+    }
+
+    /// Reads the dispatch outcome of a transaction from the events of the block it is in.
+    async fn dispatch_outcome(&self, block: &TxInBlock) -> Result<TxOutcome> {
+        let events = block.fetch_events().await.context(FetchEventsSnafu)?;
+        let failed = events
+            .find_first::<ExtrinsicFailed>()
+            .context(FetchEventsSnafu)?;
+        let metadata = self.client.metadata();
+
+        let completed = events.find::<ItemCompleted>().count() as u32;
+        let interrupted = events
+            .find_first::<BatchInterrupted>()
+            .context(FetchEventsSnafu)?
+            .map(|interrupted| BatchInterruption {
+                index: interrupted.index,
+                error: decode_system_module_error(&interrupted.error, &metadata)
+                    .unwrap_or_else(|| format!("{:?}", interrupted.error)),
+            });
+        let batch = (completed > 0 || interrupted.is_some()).then_some(BatchOutcome {
+            completed,
+            interrupted,
+        });
+
+        Ok(TxOutcome {
+            block_hash: format!("{:#x}", block.block_hash()),
+            success: failed.is_none(),
+            module_error: failed
+                .and_then(|failed| decode_system_module_error(&failed.dispatch_error, &metadata)),
+            batch,
+        })
+    }
+
+    /// Appends a status to the history of a transaction.
+    async fn record_status(&self, tx_hash: &str, status: &StoredTxStatus) -> Result<()> {
+        let (tx_hash, status) = (tx_hash.to_string(), status.clone());
+        self.store
+            .call(move |conn| insert_status(conn, &tx_hash, &status, unix_now()))
+            .await
+    }
+
+    /// Records the dispatch outcome of a transaction, replacing any earlier outcome.
+    ///
+    /// A transaction is first seen in a best block and later in a finalized block, so the
+    /// finalized outcome supersedes the best-block one.
+    async fn record_outcome(&self, tx_hash: &str, outcome: &TxOutcome) -> Result<()> {
+        let (tx_hash, outcome) = (tx_hash.to_string(), outcome.clone());
+        self.store
+            .call(move |conn| insert_outcome(conn, &tx_hash, &outcome, unix_now()))
+            .await
+    }
+
+    /// Records the identity of the API caller that submitted a transaction and the endpoint
+    /// it was submitted through.
+    pub async fn record_submission(
+        &self,
+        tx_hash: &str,
+        caller: &str,
+        endpoint: &str,
+    ) -> Result<()> {
+        let (tx_hash, caller, endpoint) = (
+            tx_hash.to_string(),
+            caller.to_string(),
+            endpoint.to_string(),
+        );
+        self.store
+            .call(move |conn| {
+                conn.execute(
+                    "INSERT OR REPLACE INTO tx_caller (tx_hash, caller, endpoint, recorded_at)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    params![tx_hash, caller, endpoint, unix_now()],
+                )?;
+                Ok(())
+            })
+            .await
+    }
+
+    /// Records the calls of a transaction submitted through `/batch`, so the result of each
+    /// call can be reported once the batch is in a block.
+    pub async fn record_batch(&self, tx_hash: &str, calls: &[String], atomic: bool) -> Result<()> {
+        let tx_hash = tx_hash.to_string();
+        let calls = serde_json::to_string(calls).unwrap_or_default();
+        self.store
+            .call(move |conn| {
+                conn.execute(
+                    "INSERT OR REPLACE INTO tx_batch (tx_hash, calls, atomic, recorded_at)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    params![tx_hash, calls, atomic, unix_now()],
+                )?;
+                Ok(())
+            })
+            .await
+    }
+
+    /// Registers a webhook to be called with every subsequent lifecycle event of a transaction.
+    pub async fn register_webhook(&self, tx_hash: &str, url: &str) -> Result<()> {
+        let (tx_hash, url) = (tx_hash.to_string(), url.to_string());
+        self.store
+            .call(move |conn| {
+                conn.execute(
+                    "INSERT OR IGNORE INTO webhook_subscription (tx_hash, url, recorded_at)
+                     VALUES (?1, ?2, ?3)",
+                    params![tx_hash, url, unix_now()],
+                )?;
+                Ok(())
+            })
+            .await
+    }
+
+    /// Deletes the history of every transaction that has not been updated within the TTL.
+    ///
+    /// Returns the number of statuses removed.
+    async fn evict_expired(&self) -> Result<usize> {
+        let cutoff = unix_now().saturating_sub(self.ttl.as_secs() as i64);
+        self.store.call(move |conn| evict(conn, cutoff)).await
+    }
+
     /// Retrieves the history of a transaction based on its hash.
     ///
     /// # Arguments
     /// * `tx_hash` - The transaction hash as a string.
     ///
     /// # Returns
-    /// * `Some(Vec<Arc<TxStatus>>)` - A vector containing the transaction status history.
-    /// * `None` - If the transaction hash is not found.
-    pub async fn get_history(&self, tx_hash: &str) -> Option<Vec<Arc<TxStatus>>> {
-        self.tx_map
-            .lock()
+    /// * `Ok(Some(TxHistory))` - The statuses and dispatch outcome of the transaction.
+    /// * `Ok(None)` - If the transaction hash is not found.
+    pub async fn get_history(&self, tx_hash: &str) -> Result<Option<TxHistory>> {
+        let tx_hash = tx_hash.to_string();
+        self.store
+            .call(move |conn| read_history(conn, &tx_hash))
+            .await
+    }
+}
+
+/// Queues a lifecycle event for every webhook registered for its transaction.
+fn queue_deliveries(
+    conn: &Connection,
+    tx_hash: &str,
+    payload: &str,
+    now: i64,
+) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO webhook_delivery (tx_hash, url, payload, attempts, next_attempt_at)
+         SELECT tx_hash, url, ?2, 0, ?3 FROM webhook_subscription WHERE tx_hash = ?1",
+        params![tx_hash, payload, now],
+    )?;
+    Ok(())
+}
+
+/// Appends a status to the history of a transaction.
+fn insert_status(
+    conn: &Connection,
+    tx_hash: &str,
+    status: &StoredTxStatus,
+    now: i64,
+) -> rusqlite::Result<()> {
+    let (kind, detail, num_peers) = status.to_columns();
+    conn.execute(
+        "INSERT INTO tx_status (tx_hash, kind, detail, num_peers, recorded_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![tx_hash, kind, detail, num_peers, now],
+    )?;
+    Ok(())
+}
+
+/// Records the dispatch outcome of a transaction, replacing any earlier outcome.
+fn insert_outcome(
+    conn: &mut Connection,
+    tx_hash: &str,
+    outcome: &TxOutcome,
+    now: i64,
+) -> rusqlite::Result<()> {
+    let tx = conn.transaction()?;
+
+    tx.execute(
+        "INSERT OR REPLACE INTO tx_outcome
+             (tx_hash, block_hash, success, module_error, recorded_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            tx_hash,
+            outcome.block_hash,
+            outcome.success,
+            outcome.module_error,
+            now
+        ],
+    )?;
+
+    match &outcome.batch {
+        Some(batch) => {
+            let interrupted = batch.interrupted.as_ref();
+            tx.execute(
+                "INSERT OR REPLACE INTO tx_batch_outcome
+                 (tx_hash, completed, interrupted_index, interrupted_error, recorded_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    tx_hash,
+                    batch.completed,
+                    interrupted.map(|interrupted| interrupted.index),
+                    interrupted.map(|interrupted| &interrupted.error),
+                    now
+                ],
+            )?;
+        }
+        None => {
+            tx.execute(
+                "DELETE FROM tx_batch_outcome WHERE tx_hash = ?1",
+                params![tx_hash],
+            )?;
+        }
+    }
+
+    tx.commit()
+}
+
+/// Deletes the history of every transaction that has not been updated since `cutoff`.
+///
+/// Returns the number of statuses removed.
+fn evict(conn: &mut Connection, cutoff: i64) -> rusqlite::Result<usize> {
+    let tx = conn.transaction()?;
+
+    let evicted = tx.execute(
+        "DELETE FROM tx_status WHERE tx_hash IN (
+             SELECT tx_hash FROM tx_status GROUP BY tx_hash HAVING MAX(recorded_at) < ?1
+         )",
+        params![cutoff],
+    )?;
+    for table in [
+        "tx_outcome",
+        "tx_caller",
+        "tx_batch",
+        "tx_batch_outcome",
+        "webhook_subscription",
+    ] {
+        tx.execute(
+            &format!(
+                "DELETE FROM {table} WHERE recorded_at < ?1
+                 AND tx_hash NOT IN (SELECT tx_hash FROM tx_status)"
+            ),
+            params![cutoff],
+        )?;
+    }
+
+    tx.commit()?;
+    Ok(evicted)
+}
+
+/// Reads the history of a transaction, or `None` if nothing is stored for it.
+fn read_history(conn: &Connection, tx_hash: &str) -> rusqlite::Result<Option<TxHistory>> {
+    let statuses = conn
+        .prepare("SELECT kind, detail, num_peers FROM tx_status WHERE tx_hash = ?1 ORDER BY id")?
+        .query_map(params![tx_hash], |row| {
+            Ok(StoredTxStatus::from_columns(
+                &row.get::<_, String>(0)?,
+                row.get(1)?,
+                row.get(2)?,
+            ))
+        })?
+        .filter_map(|status| status.transpose())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut outcome = conn
+        .query_row(
+            "SELECT block_hash, success, module_error FROM tx_outcome WHERE tx_hash = ?1",
+            params![tx_hash],
+            |row| {
+                Ok(TxOutcome {
+                    block_hash: row.get(0)?,
+                    success: row.get(1)?,
+                    module_error: row.get(2)?,
+                    batch: None,
+                })
+            },
+        )
+        .optional()?;
+
+    let batch_outcome = conn
+        .query_row(
+            "SELECT completed, interrupted_index, interrupted_error
+             FROM tx_batch_outcome WHERE tx_hash = ?1",
+            params![tx_hash],
+            |row| {
+                let index: Option<u32> = row.get(1)?;
+                let error: Option<String> = row.get(2)?;
+                Ok(BatchOutcome {
+                    completed: row.get(0)?,
+                    interrupted: index.map(|index| BatchInterruption {
+                        index,
+                        error: error.unwrap_or_default(),
+                    }),
+                })
+            },
+        )
+        .optional()?;
+    if let Some(outcome) = &mut outcome {
+        outcome.batch = batch_outcome;
+    }
+
+    let submitted_by = conn
+        .query_row(
+            "SELECT caller FROM tx_caller WHERE tx_hash = ?1",
+            params![tx_hash],
+            |row| row.get(0),
+        )
+        .optional()?;
+
+    let batch = conn
+        .query_row(
+            "SELECT calls, atomic FROM tx_batch WHERE tx_hash = ?1",
+            params![tx_hash],
+            |row| Ok((row.get::<_, String>(0)?, row.get(1)?)),
+        )
+        .optional()?
+        .map(|(calls, atomic)| BatchSubmission {
+            calls: serde_json::from_str(&calls).unwrap_or_default(),
+            atomic,
+        });
+
+    if statuses.is_empty() && outcome.is_none() && submitted_by.is_none() {
+        return Ok(None);
+    }
+
+    Ok(Some(TxHistory {
+        statuses,
+        outcome,
+        submitted_by,
+        batch,
+    }))
+}
+
+/// Current unix time in seconds.
+pub(crate) fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|since_epoch| since_epoch.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TX: &str = "0x01";
+
+    fn store() -> Connection {
+        open_store(":memory:").unwrap()
+    }
+
+    #[test]
+    fn statuses_are_read_back_in_the_order_they_were_recorded() {
+        let conn = store();
+        let statuses = [
+            StoredTxStatus::Validated,
+            StoredTxStatus::Broadcasted { num_peers: 3 },
+            StoredTxStatus::InBestBlock {
+                block_hash: "0xaa".into(),
+            },
+            StoredTxStatus::InFinalizedBlock {
+                block_hash: "0xaa".into(),
+            },
+        ];
+        for status in &statuses {
+            insert_status(&conn, TX, status, 10).unwrap();
+        }
+
+        let history = read_history(&conn, TX).unwrap().unwrap();
+        assert_eq!(history.statuses, statuses);
+        assert_eq!(history.outcome, None);
+        assert_eq!(read_history(&conn, "0x02").unwrap(), None);
+    }
+
+    #[test]
+    fn a_finalized_outcome_replaces_the_best_block_outcome() {
+        let mut conn = store();
+        insert_status(&conn, TX, &StoredTxStatus::Validated, 10).unwrap();
+
+        let best = TxOutcome {
+            block_hash: "0xaa".into(),
+            success: false,
+            module_error: Some("Tables.NoSuchTable".into()),
+            batch: Some(BatchOutcome {
+                completed: 1,
+                interrupted: Some(BatchInterruption {
+                    index: 1,
+                    error: "Tables.NoSuchTable".into(),
+                }),
+            }),
+        };
+        insert_outcome(&mut conn, TX, &best, 10).unwrap();
+        assert_eq!(
+            read_history(&conn, TX).unwrap().unwrap().outcome,
+            Some(best)
+        );
+
+        let finalized = TxOutcome {
+            block_hash: "0xbb".into(),
+            success: true,
+            module_error: None,
+            batch: None,
+        };
+        insert_outcome(&mut conn, TX, &finalized, 11).unwrap();
+        assert_eq!(
+            read_history(&conn, TX).unwrap().unwrap().outcome,
+            Some(finalized)
+        );
+    }
+
+    #[test]
+    fn only_histories_older_than_the_cutoff_are_evicted() {
+        let mut conn = store();
+        insert_status(&conn, TX, &StoredTxStatus::Validated, 10).unwrap();
+        insert_status(&conn, TX, &StoredTxStatus::Broadcasted { num_peers: 1 }, 10).unwrap();
+        insert_status(&conn, "0x02", &StoredTxStatus::Validated, 10).unwrap();
+        insert_status(
+            &conn,
+            "0x02",
+            &StoredTxStatus::Broadcasted { num_peers: 1 },
+            30,
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO webhook_subscription (tx_hash, url, recorded_at) VALUES (?1, ?2, 10)",
+            params![TX, "https://example.com/hook"],
+        )
+        .unwrap();
+
+        assert_eq!(evict(&mut conn, 20).unwrap(), 2);
+        assert_eq!(read_history(&conn, TX).unwrap(), None);
+        assert_eq!(
+            read_history(&conn, "0x02").unwrap().unwrap().statuses.len(),
+            2
+        );
+        let subscriptions: u32 = conn
+            .query_row("SELECT COUNT(*) FROM webhook_subscription", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(subscriptions, 0);
+    }
+
+    #[test]
+    fn events_are_queued_for_every_registered_webhook() {
+        let conn = store();
+        for url in ["https://a.example.com", "https://b.example.com"] {
+            conn.execute(
+                "INSERT INTO webhook_subscription (tx_hash, url, recorded_at) VALUES (?1, ?2, 10)",
+                params![TX, url],
+            )
+            .unwrap();
+        }
+
+        queue_deliveries(&conn, TX, "{}", 10).unwrap();
+        queue_deliveries(&conn, "0x02", "{}", 10).unwrap();
+
+        let queued: u32 = conn
+            .query_row("SELECT COUNT(*) FROM webhook_delivery", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(queued, 2);
+    }
+
+    #[tokio::test]
+    async fn store_queries_run_off_the_async_runtime() {
+        let store = Store::open(":memory:").unwrap();
+        store
+            .call(|conn| insert_status(conn, TX, &StoredTxStatus::Validated, 10))
+            .await
+            .unwrap();
+
+        let history = store
+            .call(|conn| read_history(conn, TX))
             .await
-            .get(tx_hash)
-            .map(|list| list.iter().cloned().collect())
+            .unwrap()
+            .unwrap();
+        assert_eq!(history.statuses, vec![StoredTxStatus::Validated]);
     }
 }
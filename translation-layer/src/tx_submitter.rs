@@ -1,18 +1,27 @@
 //! substrate transaction submitter
+use std::future::Future;
 use std::sync::Arc;
 
 use log::{error, info, warn};
 use snafu::ResultExt;
 use subxt::config::polkadot::PolkadotExtrinsicParamsBuilder as Params;
-use subxt::tx::{DefaultPayload, TxProgress, TxStatus};
+use subxt::dynamic::Value;
+use subxt::tx::{DefaultPayload, DynamicPayload, Payload, TxProgress, TxStatus};
 use subxt::utils::H256;
-use subxt::{OnlineClient, PolkadotConfig};
+use subxt::{Metadata, OnlineClient, PolkadotConfig};
 use subxt_signer::sr25519::Keypair;
 use sxt_core::sxt_chain_runtime;
 use tokio::sync::{mpsc, Mutex};
 use tokio::time::{sleep, Duration};
 
-use crate::error::{Error, FetchEventsSnafu, FetchInitialNonceSnafu, Result};
+use crate::error::{
+    DecodeCallSnafu,
+    EncodeCallSnafu,
+    Error,
+    FetchEventsSnafu,
+    FetchInitialNonceSnafu,
+    Result,
+};
 
 const MAX_RETRIES: usize = 3;
 
@@ -210,7 +219,26 @@ impl TxSubmitter {
         &mut self,
         tx: &DefaultPayload<T>,
     ) -> Result<subxt::utils::H256> {
-        self.submit_tx_get_hash_inner(tx, None).await
+        self.submit_tx_get_hash_inner(tx, None, |_| std::future::ready(()))
+            .await
+    }
+
+    /// Submit a transaction and return its hash, awaiting `before_submit` with the hash of
+    /// every signed attempt before it is submitted.
+    ///
+    /// This lets callers attach state to the hash before any of its progress is tracked.
+    /// No mortality limit (immortal transaction).
+    pub async fn submit_tx_get_hash_notifying<T, F, Fut>(
+        &mut self,
+        tx: &DefaultPayload<T>,
+        before_submit: F,
+    ) -> Result<subxt::utils::H256>
+    where
+        T: subxt::ext::scale_encode::EncodeAsFields,
+        F: FnMut(H256) -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        self.submit_tx_get_hash_inner(tx, None, before_submit).await
     }
 
     /// Submit a transaction with optional mortality.
@@ -224,16 +252,24 @@ impl TxSubmitter {
         >,
         for_n_blocks: u64,
     ) -> Result<subxt::utils::H256> {
-        self.submit_tx_get_hash_inner(tx, Some((from_block.clone(), for_n_blocks)))
-            .await
+        self.submit_tx_get_hash_inner(tx, Some((from_block.clone(), for_n_blocks)), |_| {
+            std::future::ready(())
+        })
+        .await
     }
 
     /// Shared inner logic that accepts an Option<u64> for mortality.
-    async fn submit_tx_get_hash_inner<T: subxt::ext::scale_encode::EncodeAsFields>(
+    async fn submit_tx_get_hash_inner<T, F, Fut>(
         &mut self,
         tx: &DefaultPayload<T>,
         mortality: Option<(DefaultHeader, u64)>,
-    ) -> Result<subxt::utils::H256> {
+        mut before_submit: F,
+    ) -> Result<subxt::utils::H256>
+    where
+        T: subxt::ext::scale_encode::EncodeAsFields,
+        F: FnMut(H256) -> Fut,
+        Fut: Future<Output = ()>,
+    {
         for attempt in 0..=MAX_RETRIES {
             let mut nonce_guard = self.nonce.lock().await;
             let nonce_value = *nonce_guard;
@@ -249,12 +285,17 @@ impl TxSubmitter {
             let tx_params = params.build();
 
             let client = self.client.lock().await;
-            let tx_result = client
-                .tx()
-                .sign_and_submit_then_watch(tx, &self.signer, tx_params)
-                .await;
+            let signed = client.tx().create_signed(tx, &self.signer, tx_params).await;
             drop(client);
 
+            let tx_result = match signed {
+                Ok(signed) => {
+                    before_submit(signed.hash()).await;
+                    signed.submit_and_watch().await
+                }
+                Err(err) => Err(err),
+            };
+
             match tx_result {
                 Ok(tx_progress) => {
                     *nonce_guard += 1;
@@ -362,6 +403,36 @@ impl TxSubmitter {
     }
 }
 
+/// Encodes a call as a `RuntimeCall` value, so it can be nested in a `utility` batch.
+///
+/// # Arguments
+///
+/// * `call` - The call, as it would be submitted on its own.
+/// * `metadata` - Metadata of the chain the batch is submitted to.
+pub fn runtime_call(call: &impl Payload, metadata: &Metadata) -> Result<Value> {
+    let call_data = call
+        .encode_call_data(metadata)
+        .map_err(subxt::Error::from)
+        .context(EncodeCallSnafu)?;
+
+    let call = subxt::ext::scale_value::scale::decode_as_type(
+        &mut &call_data[..],
+        metadata.outer_enums().call_enum_ty(),
+        metadata.types(),
+    )
+    .context(DecodeCallSnafu)?;
+
+    Ok(call.remove_context())
+}
+
+/// Wraps `RuntimeCall` values into a `utility.batch_all`, which applies every call or none of
+/// them, or into a `utility.batch` when `atomic` is false, which stops at the first failing
+/// call and keeps the calls dispatched before it.
+pub fn batch_payload(calls: Vec<Value>, atomic: bool) -> DynamicPayload {
+    let call_name = if atomic { "batch_all" } else { "batch" };
+    subxt::dynamic::tx("Utility", call_name, vec![Value::unnamed_composite(calls)])
+}
+
 /// Fetches the initial nonce for an account.
 ///
 /// # Arguments
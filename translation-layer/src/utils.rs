@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use axum::http::StatusCode;
 use axum::Json;
 use hex::FromHex;
+use subxt::ext::codec::Decode;
 use subxt::utils::H256;
 use subxt::Metadata;
 use sxt_core::sxt_chain_runtime::api::runtime_types::bounded_collections::bounded_vec::BoundedVec;
@@ -261,6 +262,28 @@ pub fn map_contract_to_api(
     }
 }
 
+/// Length of the pallet and storage prefix of a storage key.
+const STORAGE_PREFIX_LEN: usize = 32;
+
+/// Length of the hash preceding each `Blake2_128Concat` key.
+const BLAKE2_128_LEN: usize = 16;
+
+/// Decodes the namespace and version from a raw `Tables::NamespaceVersions` storage key.
+///
+/// Both keys are hashed with `Blake2_128Concat`, so each is preceded by a 16-byte hash.
+///
+/// # Returns
+/// * `Some((namespace, version))` - If the key is well formed.
+/// * `None` - If the key could not be decoded.
+pub fn decode_namespace_versions_key(key_bytes: &[u8]) -> Option<(String, u16)> {
+    let mut cursor = key_bytes.get(STORAGE_PREFIX_LEN + BLAKE2_128_LEN..)?;
+    let namespace = Vec::<u8>::decode(&mut cursor).ok()?;
+    let mut cursor = cursor.get(BLAKE2_128_LEN..)?;
+    let version = u16::decode(&mut cursor).ok()?;
+
+    Some((bytes_to_string(&namespace), version))
+}
+
 /// Converts a `BoundedVec<u8>` into a `String`, handling UTF-8 conversion.
 pub fn bytes_to_string(bytes: &[u8]) -> String {
     String::from_utf8_lossy(bytes).into_owned()
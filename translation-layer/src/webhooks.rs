@@ -0,0 +1,476 @@
+use std::net::{IpAddr, SocketAddr};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+use axum::Json;
+use futures::future::join_all;
+use hmac::{Hmac, Mac};
+use log::{error, info, warn};
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use rusqlite::{params, TransactionBehavior};
+use sha2::Sha256;
+use snafu::ResultExt;
+
+use crate::error::{Result, WebhookClientSnafu};
+use crate::model::ApiResponse;
+use crate::state::TranslationLayerState;
+use crate::tx_progress::{unix_now, Store};
+use crate::utils::bad_request;
+
+/// Header a submitting request sets to receive the lifecycle events of its transaction.
+pub const CALLBACK_URL_HEADER: &str = "x-callback-url";
+
+/// Header carrying the HMAC-SHA256 signature of a delivered payload, as `sha256=<hex>`.
+pub const SIGNATURE_HEADER: &str = "x-sxt-signature";
+
+/// Header carrying the id of a delivery, which stays the same across retries.
+pub const DELIVERY_ID_HEADER: &str = "x-sxt-delivery-id";
+
+/// How often the queue is polled for due deliveries.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long a callback may take to respond before the delivery is retried.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long a claimed delivery is hidden from other replicas sharing the queue.
+const CLAIM_LEASE: Duration = Duration::from_secs(60);
+
+/// Maximum number of deliveries attempted per poll.
+const BATCH_SIZE: u32 = 64;
+
+/// Number of failed attempts after which a delivery is abandoned.
+const MAX_ATTEMPTS: u32 = 12;
+
+/// Upper bound of the delay between two attempts of a delivery.
+const MAX_BACKOFF: Duration = Duration::from_secs(3600);
+
+/// Signs a webhook payload with the shared secret.
+///
+/// Receivers verify a delivery by computing the HMAC-SHA256 of the raw request body and
+/// comparing it to the [`SIGNATURE_HEADER`].
+pub fn sign(secret: &[u8], payload: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(payload);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Delay before retrying a delivery that has failed `attempts` times.
+fn backoff(attempts: u32) -> Duration {
+    Duration::from_secs(1u64 << attempts.min(12)).min(MAX_BACKOFF)
+}
+
+/// Returns whether `ip` is a publicly routable address webhooks may be delivered to.
+///
+/// Loopback, private, link-local and other special-purpose ranges are rejected, so callbacks
+/// cannot reach services on the server's own network.
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip.to_canonical() {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_unspecified()
+                || ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_broadcast()
+                || ip.is_multicast()
+                || ip.is_documentation()
+                // 0.0.0.0/8, "this network"
+                || a == 0
+                // 100.64.0.0/10, shared address space
+                || (a == 100 && (64..128).contains(&b))
+                // 198.18.0.0/15, benchmarking
+                || (a == 198 && (18..20).contains(&b))
+                // 240.0.0.0/4, reserved
+                || a >= 240)
+        }
+        IpAddr::V6(ip) => {
+            !(ip.is_unspecified()
+                || ip.is_loopback()
+                || ip.is_multicast()
+                || ip.is_unique_local()
+                || ip.is_unicast_link_local())
+        }
+    }
+}
+
+/// Parses a callback URL, accepting only http(s) URLs that do not point at a local or
+/// private address.
+///
+/// Hostnames are checked again when they are resolved for a delivery, see [`PublicResolver`].
+fn parse_callback_url(value: &str) -> Option<reqwest::Url> {
+    let url = reqwest::Url::parse(value).ok()?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
+
+    let host = url.host_str()?;
+    let allowed = match host
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse::<IpAddr>()
+    {
+        Ok(ip) => is_public_ip(ip),
+        Err(_) => {
+            let host = host.trim_end_matches('.').to_ascii_lowercase();
+            host != "localhost" && !host.ends_with(".localhost")
+        }
+    };
+
+    allowed.then_some(url)
+}
+
+/// Resolves callback hostnames to their public addresses only.
+///
+/// Checking the hostname when the callback is registered is not enough, since it may later
+/// resolve to a private address.
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((name.as_str(), 0))
+                .await?
+                .filter(|addr| is_public_ip(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(format!("{} has no public address", name.as_str()).into());
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// The callback URL a submitting request registered through the [`CALLBACK_URL_HEADER`].
+#[derive(Debug, Clone, Default)]
+pub struct CallbackUrl(pub Option<String>);
+
+impl FromRequestParts<Arc<TranslationLayerState>> for CallbackUrl {
+    type Rejection = (StatusCode, Json<ApiResponse>);
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<TranslationLayerState>,
+    ) -> Result<Self, Self::Rejection> {
+        let Some(value) = parts.headers.get(CALLBACK_URL_HEADER) else {
+            return Ok(CallbackUrl(None));
+        };
+
+        if !state.webhooks_enabled {
+            return Err(bad_request("Webhooks are not enabled on this server"));
+        }
+
+        let url = value
+            .to_str()
+            .ok()
+            .and_then(parse_callback_url)
+            .ok_or_else(|| bad_request(&format!("Invalid {CALLBACK_URL_HEADER} header")))?;
+
+        Ok(CallbackUrl(Some(url.to_string())))
+    }
+}
+
+/// A queued webhook delivery.
+#[derive(Debug, PartialEq, Eq)]
+struct PendingDelivery {
+    /// Id of the delivery.
+    id: i64,
+    /// The callback URL.
+    url: String,
+    /// The serialized [`TxEvent`](crate::model::TxEvent).
+    payload: String,
+    /// Number of failed attempts so far.
+    attempts: u32,
+}
+
+/// Delivers the lifecycle events queued by [`TxProgressDb`](crate::tx_progress::TxProgressDb)
+/// to their callback URLs.
+///
+/// Deliveries are read from the transaction history store, so they survive restarts and are
+/// shared by every replica pointing at the same file. Failed deliveries are retried with
+/// exponential backoff until they succeed or `MAX_ATTEMPTS` is reached.
+///
+/// The events of a transaction are delivered to each callback one at a time and in the order
+/// they were queued, while the events of different transactions are delivered concurrently.
+pub struct WebhookDeliverer {
+    /// The SQLite history store holding the delivery queue.
+    store: Store,
+    /// Client used to call the webhooks.
+    http: reqwest::Client,
+    /// Secret used to sign every payload.
+    secret: Vec<u8>,
+}
+
+impl WebhookDeliverer {
+    /// Opens the delivery queue in the history store at `path`.
+    ///
+    /// # Arguments
+    /// * `path` - Path of the SQLite transaction history store.
+    /// * `secret` - Secret used to sign every payload.
+    pub fn open(path: impl AsRef<Path>, secret: impl Into<Vec<u8>>) -> Result<Self> {
+        // Redirects are not followed, since they could point at a private address.
+        let http = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .dns_resolver(Arc::new(PublicResolver))
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .context(WebhookClientSnafu)?;
+
+        Ok(Self {
+            store: Store::open(path)?,
+            http,
+            secret: secret.into(),
+        })
+    }
+
+    /// Starts the delivery loop.
+    pub async fn run(self) {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+
+            let deliveries = match self.claim_due().await {
+                Ok(deliveries) => deliveries,
+                Err(err) => {
+                    error!("Error reading webhook deliveries: {}", err);
+                    continue;
+                }
+            };
+
+            // At most one delivery per transaction and callback is claimed at a time, so
+            // delivering them concurrently keeps the order of every transaction's events.
+            join_all(deliveries.iter().map(|delivery| self.deliver(delivery))).await;
+        }
+    }
+
+    /// Claims the deliveries that are due, hiding them from other replicas until their
+    /// lease expires.
+    ///
+    /// Only the oldest queued delivery of a transaction to a callback is claimed. The next one
+    /// becomes due once it has been delivered or abandoned, so events arrive in order.
+    async fn claim_due(&self) -> Result<Vec<PendingDelivery>> {
+        let now = unix_now();
+        self.store
+            .call(move |conn| {
+                let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+
+                let deliveries = tx
+                    .prepare(
+                        "SELECT id, url, payload, attempts FROM webhook_delivery AS delivery
+                         WHERE next_attempt_at <= ?1 AND NOT EXISTS (
+                             SELECT 1 FROM webhook_delivery AS earlier
+                             WHERE earlier.tx_hash = delivery.tx_hash
+                             AND earlier.url = delivery.url AND earlier.id < delivery.id
+                         )
+                         ORDER BY next_attempt_at, id LIMIT ?2",
+                    )?
+                    .query_map(params![now, BATCH_SIZE], |row| {
+                        Ok(PendingDelivery {
+                            id: row.get(0)?,
+                            url: row.get(1)?,
+                            payload: row.get(2)?,
+                            attempts: row.get(3)?,
+                        })
+                    })?
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                for delivery in &deliveries {
+                    tx.execute(
+                        "UPDATE webhook_delivery SET next_attempt_at = ?2 WHERE id = ?1",
+                        params![delivery.id, now + CLAIM_LEASE.as_secs() as i64],
+                    )?;
+                }
+
+                tx.commit()?;
+                Ok(deliveries)
+            })
+            .await
+    }
+
+    /// Attempts a single delivery, then removes it from the queue or schedules a retry.
+    async fn deliver(&self, delivery: &PendingDelivery) {
+        let response = self
+            .http
+            .post(&delivery.url)
+            .header("content-type", "application/json")
+            .header(
+                SIGNATURE_HEADER,
+                sign(&self.secret, delivery.payload.as_bytes()),
+            )
+            .header(DELIVERY_ID_HEADER, delivery.id.to_string())
+            .body(delivery.payload.clone())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+
+        let result = match response {
+            Ok(_) => {
+                info!("Delivered webhook {} to {}", delivery.id, delivery.url);
+                self.complete(delivery.id).await
+            }
+            Err(err) => self.retry(delivery, &err.to_string()).await,
+        };
+
+        if let Err(err) = result {
+            error!("Error updating webhook delivery {}: {}", delivery.id, err);
+        }
+    }
+
+    /// Removes a delivery from the queue.
+    async fn complete(&self, id: i64) -> Result<()> {
+        self.store
+            .call(move |conn| {
+                conn.execute("DELETE FROM webhook_delivery WHERE id = ?1", params![id])?;
+                Ok(())
+            })
+            .await
+    }
+
+    /// Schedules the next attempt of a failed delivery, or abandons it once it has been
+    /// attempted [`MAX_ATTEMPTS`] times.
+    async fn retry(&self, delivery: &PendingDelivery, reason: &str) -> Result<()> {
+        let attempts = delivery.attempts + 1;
+        if attempts >= MAX_ATTEMPTS {
+            warn!(
+                "Abandoning webhook {} to {} after {} attempts: {}",
+                delivery.id, delivery.url, attempts, reason
+            );
+            return self.complete(delivery.id).await;
+        }
+
+        let delay = backoff(attempts);
+        warn!(
+            "Webhook {} to {} failed, retrying in {}s: {}",
+            delivery.id,
+            delivery.url,
+            delay.as_secs(),
+            reason
+        );
+        let (id, next_attempt_at) = (delivery.id, unix_now() + delay.as_secs() as i64);
+        self.store
+            .call(move |conn| {
+                conn.execute(
+                    "UPDATE webhook_delivery SET attempts = ?2, next_attempt_at = ?3 WHERE id = ?1",
+                    params![id, attempts, next_attempt_at],
+                )?;
+                Ok(())
+            })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOOK: &str = "https://hooks.example.com/tx";
+
+    async fn queue(deliverer: &WebhookDeliverer, tx_hash: &'static str, payload: &'static str) {
+        deliverer
+            .store
+            .call(move |conn| {
+                conn.execute(
+                    "INSERT INTO webhook_delivery (tx_hash, url, payload, attempts, next_attempt_at)
+                     VALUES (?1, ?2, ?3, 0, 0)",
+                    params![tx_hash, HOOK, payload],
+                )?;
+                Ok(())
+            })
+            .await
+            .unwrap();
+    }
+
+    fn payloads(deliveries: &[PendingDelivery]) -> Vec<&str> {
+        deliveries
+            .iter()
+            .map(|delivery| delivery.payload.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn payloads_are_signed_with_hmac_sha256() {
+        // RFC 4231, test case 2.
+        assert_eq!(
+            sign(b"Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_an_hour() {
+        assert_eq!(backoff(1), Duration::from_secs(2));
+        assert_eq!(backoff(2), Duration::from_secs(4));
+        assert_eq!(backoff(11), Duration::from_secs(2048));
+        assert_eq!(backoff(12), MAX_BACKOFF);
+        assert_eq!(backoff(u32::MAX), MAX_BACKOFF);
+    }
+
+    #[test]
+    fn callbacks_to_local_or_private_addresses_are_rejected() {
+        assert!(parse_callback_url(HOOK).is_some());
+        assert!(parse_callback_url("http://93.184.215.14/hook").is_some());
+        assert!(parse_callback_url("http://[2606:2800:21f:cb07:6820:80da:af6b:8b2c]/").is_some());
+
+        for url in [
+            "ftp://hooks.example.com/tx",
+            "http://localhost:8080/hook",
+            "http://api.localhost/hook",
+            "http://127.0.0.1/hook",
+            "http://10.0.0.8/hook",
+            "http://172.16.4.2/hook",
+            "http://192.168.1.1/hook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://100.64.0.1/hook",
+            "http://0.0.0.0/hook",
+            "http://[::1]/hook",
+            "http://[fd00::1]/hook",
+            "http://[fe80::1]/hook",
+            "http://[::ffff:127.0.0.1]/hook",
+        ] {
+            assert!(parse_callback_url(url).is_none(), "{url} was accepted");
+        }
+    }
+
+    #[tokio::test]
+    async fn claims_deliver_each_transactions_events_in_order() {
+        let deliverer = WebhookDeliverer::open(":memory:", "secret").unwrap();
+        queue(&deliverer, "0x01", "first").await;
+        queue(&deliverer, "0x01", "second").await;
+        queue(&deliverer, "0x02", "other").await;
+
+        let claimed = deliverer.claim_due().await.unwrap();
+        assert_eq!(payloads(&claimed), ["first", "other"]);
+
+        // Claimed deliveries are leased, and the next event waits for the first one.
+        assert!(deliverer.claim_due().await.unwrap().is_empty());
+
+        deliverer.complete(claimed[0].id).await.unwrap();
+        let claimed = deliverer.claim_due().await.unwrap();
+        assert_eq!(payloads(&claimed), ["second"]);
+    }
+
+    #[tokio::test]
+    async fn failed_deliveries_hold_back_later_events_until_abandoned() {
+        let deliverer = WebhookDeliverer::open(":memory:", "secret").unwrap();
+        queue(&deliverer, "0x01", "first").await;
+        queue(&deliverer, "0x01", "second").await;
+
+        let first = deliverer.claim_due().await.unwrap().remove(0);
+        deliverer.retry(&first, "connection refused").await.unwrap();
+        assert!(deliverer.claim_due().await.unwrap().is_empty());
+
+        let exhausted = PendingDelivery {
+            attempts: MAX_ATTEMPTS - 1,
+            ..first
+        };
+        deliverer
+            .retry(&exhausted, "connection refused")
+            .await
+            .unwrap();
+        let claimed = deliverer.claim_due().await.unwrap();
+        assert_eq!(payloads(&claimed), ["second"]);
+    }
+}